@@ -13,6 +13,8 @@ struct OrmOpts {
     table: Option<String>,
     #[darling(default, multiple, rename = "index")]
     indexes: Vec<ModelIndexOpts>,
+    #[darling(default, multiple, rename = "check")]
+    checks: Vec<String>,
     data: Data<(), OrmFieldOpts>,
 }
 
@@ -42,9 +44,13 @@ struct OrmFieldOpts {
     #[darling(default)]
     primary_key: bool,
     #[darling(default)]
+    auto_increment: bool,
+    #[darling(default)]
     unique: bool,
     #[darling(default)]
     index: bool,
+    check: Option<String>,
+    references: Option<String>,
 }
 
 pub(crate) fn handle(ast: DeriveInput) -> Result<TokenStream, Error> {
@@ -76,6 +82,8 @@ pub(crate) fn handle(ast: DeriveInput) -> Result<TokenStream, Error> {
     let mut column_names = Vec::new();
     let mut placeholder_names = Vec::new();
     let mut orm_indexes = Vec::new();
+    let mut orm_checks = Vec::new();
+    let mut orm_foreign_keys = Vec::new();
     let mut persisted_columns = Vec::new();
     let mut index_names = BTreeSet::new();
     index_names.insert("pk_index".to_string());
@@ -98,6 +106,12 @@ pub(crate) fn handle(ast: DeriveInput) -> Result<TokenStream, Error> {
                     "primary key field cannot be skipped",
                 ));
             }
+            if field.auto_increment {
+                return Err(Error::new_spanned(
+                    field_name,
+                    "auto_increment field cannot be skipped",
+                ));
+            }
             if field.unique {
                 return Err(Error::new_spanned(
                     field_name,
@@ -128,6 +142,18 @@ pub(crate) fn handle(ast: DeriveInput) -> Result<TokenStream, Error> {
                     "default field cannot be skipped",
                 ));
             }
+            if field.check.is_some() {
+                return Err(Error::new_spanned(
+                    field_name,
+                    "check field cannot be skipped",
+                ));
+            }
+            if field.references.is_some() {
+                return Err(Error::new_spanned(
+                    field_name,
+                    "references field cannot be skipped",
+                ));
+            }
             if field.decimal_precision.is_some() || field.decimal_scale.is_some() {
                 return Err(Error::new_spanned(
                     field_name,
@@ -160,6 +186,12 @@ pub(crate) fn handle(ast: DeriveInput) -> Result<TokenStream, Error> {
                 "decimal_scale requires decimal_precision",
             ));
         }
+        if field.auto_increment && field.default_literal.is_some() {
+            return Err(Error::new_spanned(
+                field_name,
+                "auto_increment field cannot have a default",
+            ));
+        }
         let default_literal = field
             .default_literal
             .map(|value| LitStr::new(&value, Span::call_site()));
@@ -169,6 +201,7 @@ pub(crate) fn handle(ast: DeriveInput) -> Result<TokenStream, Error> {
         let column_name_lit = LitStr::new(&column_name, Span::call_site());
         let placeholder_lit = LitStr::new(&placeholder_name, Span::call_site());
         let is_primary_key = field.primary_key;
+        let is_auto_increment = field.auto_increment;
         let is_unique = field.unique;
         let is_index = field.index;
         let column_index = orm_columns.len();
@@ -259,6 +292,7 @@ pub(crate) fn handle(ast: DeriveInput) -> Result<TokenStream, Error> {
                 placeholder: #placeholder_lit,
                 primary_key: #is_primary_key,
                 unique: #is_unique,
+                auto_increment: #is_auto_increment,
             }
         });
         let getter_name = format_ident!("{}", field_name);
@@ -278,16 +312,19 @@ pub(crate) fn handle(ast: DeriveInput) -> Result<TokenStream, Error> {
                                 .expect("failed to cast ORM default value to column type"),
                         )
                     });
-                let desc = ::kite_sql::catalog::column::ColumnDesc::new(
+                let mut desc = ::kite_sql::catalog::column::ColumnDesc::new(
                     data_type,
                     #is_primary_key.then_some(#column_index),
                     #is_unique,
                     default,
                 )
                     .expect("failed to build ORM column descriptor");
+                if #is_auto_increment {
+                    desc.set_identity(::kite_sql::catalog::column::ColumnIdentity::ByDefault);
+                }
                 ::kite_sql::catalog::column::ColumnCatalog::new(
                     #column_name_lit.to_string(),
-                    if #is_primary_key {
+                    if #is_primary_key || #is_auto_increment {
                         false
                     } else {
                         <#field_ty as ::kite_sql::orm::ModelColumnType>::nullable()
@@ -319,6 +356,36 @@ pub(crate) fn handle(ast: DeriveInput) -> Result<TokenStream, Error> {
                 (#index_name_lit, &[#column_name_for_index], false)
             });
         }
+        if let Some(check) = field.check {
+            let check_lit = LitStr::new(&check, Span::call_site());
+            orm_checks.push(quote! {
+                (Some(#column_name_lit), #check_lit)
+            });
+        }
+        if let Some(references) = field.references {
+            let Some((referenced_table, referenced_column)) = references
+                .strip_suffix(')')
+                .and_then(|references| references.split_once('('))
+                .map(|(table, column)| (table.trim(), column.trim()))
+                .filter(|(table, column)| !table.is_empty() && !column.is_empty())
+            else {
+                return Err(Error::new_spanned(
+                    field_name,
+                    "references must look like \"table(column)\"",
+                ));
+            };
+            let referenced_table_lit = LitStr::new(referenced_table, Span::call_site());
+            let referenced_column_lit = LitStr::new(referenced_column, Span::call_site());
+            orm_foreign_keys.push(quote! {
+                (#column_name_lit, #referenced_table_lit, #referenced_column_lit)
+            });
+        }
+    }
+    for check in orm_opts.checks {
+        let check_lit = LitStr::new(&check, Span::call_site());
+        orm_checks.push(quote! {
+            (None, #check_lit)
+        });
     }
 
     for index in orm_opts.indexes {
@@ -464,6 +531,18 @@ pub(crate) fn handle(ast: DeriveInput) -> Result<TokenStream, Error> {
                 ]
             }
 
+            fn checks() -> &'static [(Option<&'static str>, &'static str)] {
+                &[
+                    #(#orm_checks),*
+                ]
+            }
+
+            fn foreign_keys() -> &'static [(&'static str, &'static str, &'static str)] {
+                &[
+                    #(#orm_foreign_keys),*
+                ]
+            }
+
             fn params(&self) -> Vec<(&'static str, ::kite_sql::types::value::DataValue)> {
                 vec![
                     #(#params),*
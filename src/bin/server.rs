@@ -0,0 +1,212 @@
+// Copyright 2024 KipData/KiteSQL
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[cfg(not(target_arch = "wasm32"))]
+mod native {
+    use kite_sql::db::{CatalogKind, DataBaseBuilder, Database, ResultIter};
+    use kite_sql::errors::DatabaseError;
+    use kite_sql::net::Server;
+    use kite_sql::storage::rocksdb::RocksStorage;
+    use std::env;
+    use std::net::TcpListener;
+    use std::process::ExitCode;
+
+    const DEFAULT_PATH: &str = "./kitesql_data";
+    const DEFAULT_LISTEN: &str = "127.0.0.1:5432";
+
+    const HELP: &str = "\
+kitesql-server
+
+Serves a KiteSQL database over the PostgreSQL wire protocol.
+
+Usage:
+  kitesql-server [--path PATH] [--listen ADDR]
+
+Options:
+  --path PATH     RocksDB data directory (default: ./kitesql_data)
+  --listen ADDR   Address to accept connections on (default: 127.0.0.1:5432)
+  -h, --help      Show this help
+
+Connect with e.g. `psql -h 127.0.0.1 -p 5432`. Clients are not authenticated.
+";
+
+    #[derive(Debug)]
+    struct Args {
+        path: String,
+        listen: String,
+    }
+
+    fn parse_args() -> Result<Args, String> {
+        parse_args_from(env::args().skip(1))
+    }
+
+    fn parse_args_from<I, S>(args: I) -> Result<Args, String>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let mut path = DEFAULT_PATH.to_string();
+        let mut listen = DEFAULT_LISTEN.to_string();
+        let mut args = args.into_iter().map(Into::into);
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--path" => {
+                    path = args
+                        .next()
+                        .ok_or_else(|| "--path expects a value".to_string())?;
+                }
+                "--listen" => {
+                    listen = args
+                        .next()
+                        .ok_or_else(|| "--listen expects a value".to_string())?;
+                }
+                "-h" | "--help" => {
+                    println!("{HELP}");
+                    return Err(String::new());
+                }
+                other => {
+                    return Err(format!("unknown argument: {other}\n\n{HELP}"));
+                }
+            }
+        }
+
+        Ok(Args { path, listen })
+    }
+
+    /// Loads the tables and views persisted in the data directory into the catalog.
+    fn load_catalog(database: &mut Database<RocksStorage>) -> Result<(), DatabaseError> {
+        let names = |database: &Database<RocksStorage>, sql: &str| {
+            let mut iter = database.run(sql)?;
+            let mut names = Vec::new();
+            while let Some(name) = iter.next_tuple(|_, tuple| {
+                tuple
+                    .values
+                    .first()
+                    .and_then(|value| value.utf8())
+                    .map(str::to_string)
+            })? {
+                names.extend(name);
+            }
+            iter.done()?;
+            Ok::<_, DatabaseError>(names)
+        };
+
+        for name in names(database, "show tables")? {
+            database.load(CatalogKind::Table(name.into()))?;
+        }
+        for name in names(database, "show views")? {
+            database.load(CatalogKind::View(name.into()))?;
+        }
+        Ok(())
+    }
+
+    pub(super) fn run() -> ExitCode {
+        let args = match parse_args() {
+            Ok(args) => args,
+            Err(err) if err.is_empty() => return ExitCode::SUCCESS,
+            Err(err) => {
+                eprintln!("{err}");
+                return ExitCode::from(2);
+            }
+        };
+
+        let mut database = match DataBaseBuilder::path(&args.path).build_rocksdb() {
+            Ok(database) => database,
+            Err(err) => {
+                eprintln!("{err}");
+                return ExitCode::FAILURE;
+            }
+        };
+        if let Err(err) = load_catalog(&mut database) {
+            eprintln!("{err}");
+            return ExitCode::FAILURE;
+        }
+        let listener = match TcpListener::bind(&args.listen) {
+            Ok(listener) => listener,
+            Err(err) => {
+                eprintln!("failed to listen on {}: {err}", args.listen);
+                return ExitCode::FAILURE;
+            }
+        };
+
+        eprintln!("KiteSQL server");
+        eprintln!("data path: {}", args.path);
+        eprintln!("listening on {}", args.listen);
+        match Server::new(database).serve(listener) {
+            Ok(()) => ExitCode::SUCCESS,
+            Err(err) => {
+                eprintln!("{err}");
+                ExitCode::FAILURE
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use tempfile::TempDir;
+
+        #[test]
+        fn parse_args_uses_defaults() {
+            let args = parse_args_from(Vec::<String>::new()).expect("args should parse");
+
+            assert_eq!(args.path, DEFAULT_PATH);
+            assert_eq!(args.listen, DEFAULT_LISTEN);
+        }
+
+        #[test]
+        fn parse_args_accepts_path_and_listen_address() {
+            let args = parse_args_from(["--path", "/tmp/kite", "--listen", "0.0.0.0:15432"])
+                .expect("args should parse");
+
+            assert_eq!(args.path, "/tmp/kite");
+            assert_eq!(args.listen, "0.0.0.0:15432");
+        }
+
+        #[test]
+        fn parse_args_rejects_missing_values_and_unknown_flags() {
+            assert_eq!(
+                parse_args_from(["--listen"]).expect_err("--listen should require a value"),
+                "--listen expects a value"
+            );
+            assert!(parse_args_from(["--unknown"])
+                .expect_err("unknown flags should be rejected")
+                .starts_with("unknown argument: --unknown"));
+        }
+
+        #[test]
+        fn load_catalog_restores_persisted_tables_and_views() -> Result<(), DatabaseError> {
+            let temp_dir = TempDir::new().expect("failed to create temp dir");
+            {
+                let mut database = DataBaseBuilder::path(temp_dir.path()).build_rocksdb()?;
+                database.ddl("create table t1 (id int primary key)")?;
+                database.ddl("create view v1 as select id from t1")?;
+            }
+            let mut database = DataBaseBuilder::path(temp_dir.path()).build_rocksdb()?;
+            load_catalog(&mut database)?;
+            database.run("select id from v1")?.done()?;
+
+            Ok(())
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn main() -> std::process::ExitCode {
+    native::run()
+}
+
+#[cfg(target_arch = "wasm32")]
+fn main() {}
@@ -16,6 +16,7 @@ use std::collections::HashSet;
 
 use super::{Binder, QueryBindStep};
 use crate::errors::DatabaseError;
+use crate::expression::agg::AggKind;
 use crate::expression::visitor::{walk_expr, ExprVisitor};
 use crate::expression::visitor_mut::{walk_mut_expr, ExprVisitorMut};
 use crate::planner::LogicalPlan;
@@ -48,12 +49,14 @@ impl<T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'_, '_, T, A>
         children: LogicalPlan,
         agg_calls: Vec<ScalarExpression>,
         groupby_exprs: Vec<ScalarExpression>,
+        grouping_sets: Vec<Vec<usize>>,
     ) -> Result<LogicalPlan, DatabaseError> {
         self.context.step(QueryBindStep::Agg);
         Ok(AggregateOperator::build(
             children,
             agg_calls,
             groupby_exprs,
+            grouping_sets,
             false,
             self.force_spill,
         ))
@@ -135,13 +138,44 @@ impl<T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'_, '_, T, A>
         Ok(())
     }
 
-    fn collect_aggregate_calls(&mut self, expr: &ScalarExpression) -> Result<(), DatabaseError> {
+    pub(crate) fn collect_aggregate_calls(
+        &mut self,
+        expr: &ScalarExpression,
+    ) -> Result<(), DatabaseError> {
         AggregateCallCollector {
             agg_calls: &mut self.context.agg_calls,
         }
         .visit(expr)
     }
 
+    /// Validate the arguments of `GROUPING()` are expressions of the GROUP BY clause.
+    pub(crate) fn validate_grouping_calls(
+        &self,
+        agg_calls: &[ScalarExpression],
+        group_by_exprs: &[ScalarExpression],
+    ) -> Result<(), DatabaseError> {
+        for agg_call in agg_calls {
+            let ScalarExpression::AggCall {
+                kind: AggKind::Grouping,
+                args,
+                ..
+            } = agg_call
+            else {
+                continue;
+            };
+            if let Some(arg) = args.iter().find(|arg| {
+                !group_by_exprs
+                    .iter()
+                    .any(|expr| expr.unpack_alias_ref() == arg.unpack_alias_ref())
+            }) {
+                return Err(DatabaseError::AggMiss(format!(
+                    "argument `{arg}` of grouping() must appear in the GROUP BY clause"
+                )));
+            }
+        }
+        Ok(())
+    }
+
     /// Validate select exprs must appear in the GROUP BY clause or be used in
     /// an aggregate function.
     /// e.g. SELECT a,count(b) FROM t GROUP BY a. it's ok.
@@ -385,6 +419,8 @@ mod tests {
             distinct: false,
             kind: AggKind::Count,
             args: vec![expr],
+            filter: None,
+            order_by: Vec::new(),
             ty: LogicalType::Bigint,
         }
     }
@@ -470,6 +506,7 @@ mod tests {
         let tables = build_t1_table()?;
         let scala_functions = Default::default();
         let table_functions = Default::default();
+        let aggregate_functions = Default::default();
         let transaction = tables.storage.transaction()?;
         let args: [(&'static str, DataValue); 0] = [];
         let mut binder = Binder::new(
@@ -479,6 +516,7 @@ mod tests {
                 &transaction,
                 &scala_functions,
                 &table_functions,
+                &aggregate_functions,
             ),
             &args,
             None,
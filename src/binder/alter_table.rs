@@ -12,16 +12,19 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use super::check::check_constraint_name;
 use super::Binder;
-use crate::catalog::{ColumnCatalog, TableName};
+use crate::catalog::{CheckConstraint, ColumnCatalog, TableName};
 use crate::errors::DatabaseError;
 use crate::planner::operator::alter_table::add_column::AddColumnOperator;
+use crate::planner::operator::alter_table::add_constraint::AddConstraintOperator;
 use crate::planner::operator::alter_table::change_column::{
     ChangeColumnOperator, DefaultChange, NotNullChange,
 };
 use crate::planner::operator::alter_table::drop_column::DropColumnOperator;
+use crate::planner::operator::alter_table::drop_constraint::DropConstraintOperator;
 use crate::planner::operator::Operator;
-use crate::planner::{Childrens, LogicalPlan};
+use crate::planner::{Childrens, LogicalPlan, PlanArena};
 use crate::storage::Transaction;
 use crate::types::value::DataValue;
 use crate::types::LogicalType;
@@ -48,7 +51,11 @@ impl<T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'_, '_, T, A>
         table_name: TableName,
         column_name: String,
         if_exists: bool,
+        arena: &mut PlanArena,
     ) -> Result<LogicalPlan, DatabaseError> {
+        self.ensure_column_unchecked(&table_name, &column_name, arena)?;
+        self.ensure_column_unreferenced(&table_name, &column_name)?;
+
         Ok(LogicalPlan::new(
             Operator::DropColumn(DropColumnOperator {
                 table_name,
@@ -59,6 +66,7 @@ impl<T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'_, '_, T, A>
         ))
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub(crate) fn bind_change_column(
         &mut self,
         table_name: TableName,
@@ -67,7 +75,13 @@ impl<T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'_, '_, T, A>
         data_type: LogicalType,
         default_change: DefaultChange,
         not_null_change: NotNullChange,
+        arena: &mut PlanArena,
     ) -> Result<LogicalPlan, DatabaseError> {
+        if old_column_name != new_column_name {
+            self.ensure_column_unchecked(&table_name, &old_column_name, arena)?;
+            self.ensure_column_unreferenced(&table_name, &old_column_name)?;
+        }
+
         Ok(LogicalPlan::new(
             Operator::ChangeColumn(ChangeColumnOperator {
                 table_name,
@@ -80,4 +94,55 @@ impl<T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'_, '_, T, A>
             Childrens::None,
         ))
     }
+
+    pub(crate) fn bind_add_constraint(
+        &mut self,
+        table_name: TableName,
+        constraint_name: Option<String>,
+        expr: String,
+        arena: &mut PlanArena,
+    ) -> Result<LogicalPlan, DatabaseError> {
+        let (name, schema) = {
+            let table = self
+                .context
+                .table(table_name.clone())?
+                .ok_or(DatabaseError::TableNotFound)?;
+            let name = match constraint_name {
+                Some(name) => {
+                    if table.has_constraint(&name) {
+                        return Err(DatabaseError::DuplicateConstraint(name));
+                    }
+                    name
+                }
+                None => check_constraint_name(&table_name, None, table.checks()),
+            };
+            (name, table.columns().copied().collect())
+        };
+        let predicate = self.bind_check_predicate(&table_name, schema, &expr, arena)?;
+
+        Ok(LogicalPlan::new(
+            Operator::AddConstraint(AddConstraintOperator {
+                table_name,
+                check: CheckConstraint { name, expr },
+                predicate,
+            }),
+            Childrens::None,
+        ))
+    }
+
+    pub(crate) fn bind_drop_constraint(
+        &mut self,
+        table_name: TableName,
+        constraint_name: String,
+        if_exists: bool,
+    ) -> Result<LogicalPlan, DatabaseError> {
+        Ok(LogicalPlan::new(
+            Operator::DropConstraint(DropConstraintOperator {
+                table_name,
+                constraint_name,
+                if_exists,
+            }),
+            Childrens::None,
+        ))
+    }
 }
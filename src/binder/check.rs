@@ -0,0 +1,144 @@
+// Copyright 2024 KipData/KiteSQL
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::Binder;
+use crate::catalog::{CheckConstraint, TableName};
+use crate::errors::DatabaseError;
+use crate::expression::ScalarExpression;
+use crate::planner::PlanArena;
+use crate::storage::Transaction;
+use crate::types::tuple::Schema;
+use crate::types::value::DataValue;
+
+/// Names an unnamed `CHECK` constraint `{table}_{column}_check` for a column constraint
+/// and `{table}_check` otherwise, appending a counter while the name is taken.
+pub(crate) fn check_constraint_name(
+    table_name: &str,
+    column_name: Option<&str>,
+    checks: &[CheckConstraint],
+) -> String {
+    let prefix = match column_name {
+        Some(column_name) => format!("{table_name}_{column_name}_check"),
+        None => format!("{table_name}_check"),
+    };
+    let mut name = prefix.clone();
+    let mut counter = 0;
+    while checks.iter().any(|check| check.name == name) {
+        counter += 1;
+        name = format!("{prefix}{counter}");
+    }
+    name
+}
+
+impl<T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'_, '_, T, A> {
+    /// Binds the `CHECK` constraints of a table against its columns in table column
+    /// order, the row layout writers evaluate them against.
+    pub(crate) fn bind_table_checks(
+        &self,
+        table_name: &TableName,
+        arena: &mut PlanArena,
+    ) -> Result<Vec<(String, ScalarExpression)>, DatabaseError> {
+        let Some(table) = self.context.table(table_name.clone())? else {
+            return Ok(Vec::new());
+        };
+        if table.checks().is_empty() {
+            return Ok(Vec::new());
+        }
+        let checks = table.checks().to_vec();
+        let schema: Schema = table.columns().copied().collect();
+
+        checks
+            .into_iter()
+            .map(|check| {
+                let predicate =
+                    self.bind_check_predicate(table_name, schema.clone(), &check.expr, arena)?;
+                Ok((check.name, predicate))
+            })
+            .collect()
+    }
+
+    /// Rejects dropping or renaming a column that a `CHECK` constraint of the table reads.
+    pub(crate) fn ensure_column_unchecked(
+        &self,
+        table_name: &TableName,
+        column_name: &str,
+        arena: &mut PlanArena,
+    ) -> Result<(), DatabaseError> {
+        for (name, predicate) in self.bind_table_checks(table_name, arena)? {
+            if predicate.any_referenced_column(arena, |arena, column| {
+                arena.column(*column).name() == column_name
+            })? {
+                return Err(DatabaseError::invalid_column(format!(
+                    "column `{column_name}` is referenced by check constraint `{name}`"
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Binds the SQL text of a `CHECK` expression against `schema`, rejecting
+    /// anything but a scalar boolean expression over those columns.
+    #[cfg(feature = "parser")]
+    pub(crate) fn bind_check_predicate(
+        &self,
+        table_name: &TableName,
+        schema: Schema,
+        expr: &str,
+        arena: &mut PlanArena,
+    ) -> Result<ScalarExpression, DatabaseError> {
+        use super::{QueryBindStep, Source};
+        use crate::types::LogicalType;
+
+        let expr = crate::parser::parse_expr(expr)?;
+        let mut binder = Binder::new(self.context.fork_empty(), self.args, None);
+        binder
+            .context
+            .add_bound_source(table_name.clone(), None, None, Source::Schema(schema));
+        let predicate = with_query_bind_step!(binder, QueryBindStep::Where, {
+            let predicate = binder.bind_expr(&expr, arena)?;
+            if predicate.has_agg_call()?
+                || predicate.has_window_call()?
+                || binder.context.sub_queries_at_now().is_some()
+            {
+                return Err(DatabaseError::UnsupportedStmt(
+                    "`CHECK` only supports scalar expressions over the table columns".to_string(),
+                ));
+            }
+            predicate
+        })?;
+        if !matches!(
+            predicate.return_type(arena).as_ref(),
+            LogicalType::Boolean | LogicalType::SqlNull
+        ) {
+            return Err(DatabaseError::InvalidValue(format!(
+                "`CHECK` expression must be boolean: {expr}"
+            )));
+        }
+
+        Ok(predicate)
+    }
+
+    #[cfg(not(feature = "parser"))]
+    pub(crate) fn bind_check_predicate(
+        &self,
+        _: &TableName,
+        _: Schema,
+        _: &str,
+        _: &mut PlanArena,
+    ) -> Result<ScalarExpression, DatabaseError> {
+        Err(DatabaseError::UnsupportedStmt(
+            "`CHECK` constraints require the `parser` feature".to_string(),
+        ))
+    }
+}
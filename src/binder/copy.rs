@@ -81,7 +81,7 @@ impl<T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'_, '_, T, A>
         table_name: TableName,
         to: bool,
         ext_source: ExtSource,
-        arena: &crate::planner::PlanArena,
+        arena: &mut crate::planner::PlanArena,
     ) -> Result<LogicalPlan, DatabaseError> {
         if let Some(table) = self.context.table(table_name.clone())?.cloned() {
             if to {
@@ -93,11 +93,13 @@ impl<T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'_, '_, T, A>
                 ))
             } else {
                 let schema_ref = table.columns().copied().collect();
+                let checks = self.bind_table_checks(&table_name, arena)?;
                 Ok(LogicalPlan::new(
                     Operator::CopyFromFile(CopyFromFileOperator {
                         source: ext_source,
                         schema_ref,
                         table: table_name,
+                        checks,
                     }),
                     Childrens::None,
                 ))
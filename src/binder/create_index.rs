@@ -20,7 +20,7 @@ use crate::planner::operator::table_scan::TableScanOperator;
 use crate::planner::operator::Operator;
 use crate::planner::{Childrens, LogicalPlan};
 use crate::storage::Transaction;
-use crate::types::index::IndexType;
+use crate::types::index::{IndexPathStep, IndexType};
 use crate::types::value::DataValue;
 
 impl<T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'_, '_, T, A> {
@@ -44,11 +44,13 @@ impl<T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'_, '_, T, A>
         }
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub(crate) fn bind_create_index(
         &mut self,
         table_name: TableName,
         index_name: String,
         columns: Vec<ColumnRef>,
+        json_path: Vec<IndexPathStep>,
         if_not_exists: bool,
         is_unique: bool,
         input: LogicalPlan,
@@ -68,6 +70,7 @@ impl<T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'_, '_, T, A>
                 index_name,
                 if_not_exists,
                 ty,
+                json_path,
             }),
             Childrens::Only(Box::new(input)),
         ))
@@ -0,0 +1,38 @@
+// Copyright 2024 KipData/KiteSQL
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::binder::Binder;
+use crate::catalog::sequence::Sequence;
+use crate::errors::DatabaseError;
+use crate::planner::operator::create_sequence::CreateSequenceOperator;
+use crate::planner::operator::Operator;
+use crate::planner::{Childrens, LogicalPlan};
+use crate::storage::Transaction;
+use crate::types::value::DataValue;
+
+impl<T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'_, '_, T, A> {
+    pub(crate) fn bind_create_sequence(
+        &mut self,
+        sequence: Sequence,
+        if_not_exists: bool,
+    ) -> Result<LogicalPlan, DatabaseError> {
+        Ok(LogicalPlan::new(
+            Operator::CreateSequence(CreateSequenceOperator {
+                sequence,
+                if_not_exists,
+            }),
+            Childrens::None,
+        ))
+    }
+}
@@ -13,22 +13,29 @@
 // limitations under the License.
 
 use super::{is_valid_identifier, Binder};
-use crate::catalog::{ColumnCatalog, TableName};
+use crate::catalog::sequence::{identity_sequence_name, Sequence};
+use crate::catalog::{CheckConstraint, ColumnCatalog, ForeignKey, TableName};
 use crate::errors::DatabaseError;
+use crate::expression::{ScalarExpression, SequenceFunction};
 use crate::planner::operator::create_table::CreateTableOperator;
 use crate::planner::operator::Operator;
-use crate::planner::{Childrens, LogicalPlan};
+use crate::planner::{Childrens, LogicalPlan, PlanArena};
 use crate::storage::Transaction;
 use crate::types::value::DataValue;
 use std::collections::HashSet;
 
 impl<T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'_, '_, T, A> {
     // TODO: TableConstraint
+    #[allow(clippy::too_many_arguments)]
     pub(crate) fn bind_create_table(
         &mut self,
         table_name: TableName,
-        columns: Vec<ColumnCatalog>,
+        mut columns: Vec<ColumnCatalog>,
+        checks: Vec<CheckConstraint>,
+        mut foreign_keys: Vec<ForeignKey>,
+        mut sequences: Vec<Sequence>,
         if_not_exists: bool,
+        arena: &mut PlanArena,
     ) -> Result<LogicalPlan, DatabaseError> {
         let mut names = HashSet::new();
         for column in &columns {
@@ -42,16 +49,74 @@ impl<T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'_, '_, T, A>
             }
         }
 
+        // identity columns without configured options get a sequence with the defaults
+        for column in columns.iter_mut() {
+            if column.desc().identity().is_none() {
+                continue;
+            }
+            if column.desc().default.is_some() {
+                return Err(DatabaseError::UnsupportedStmt(format!(
+                    "identity column `{}` cannot have a DEFAULT",
+                    column.name()
+                )));
+            }
+            let sequence_name = identity_sequence_name(&table_name, column.name());
+            if !sequences
+                .iter()
+                .any(|sequence| sequence.name() == sequence_name)
+            {
+                sequences.push(Sequence::new(
+                    sequence_name.clone(),
+                    column.datatype(),
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                )?);
+            }
+            column.desc_mut().default = Some(ScalarExpression::Sequence {
+                function: SequenceFunction::NextVal,
+                name: sequence_name,
+            });
+            column.set_nullable(false);
+        }
+        for sequence in sequences.iter_mut() {
+            sequence.owned_by = Some(table_name.clone());
+        }
+
         if columns.iter().filter(|col| col.desc().is_primary()).count() == 0 {
             return Err(DatabaseError::invalid_table(
                 "the primary key field must exist and have at least one".to_string(),
             ));
         }
+        let mut constraint_names = HashSet::with_capacity(checks.len() + foreign_keys.len());
+        if !checks.is_empty() {
+            let schema = columns
+                .iter()
+                .map(|column| arena.alloc_column(column.clone()))
+                .collect::<Vec<_>>();
+            for check in &checks {
+                if !constraint_names.insert(check.name.clone()) {
+                    return Err(DatabaseError::DuplicateConstraint(check.name.clone()));
+                }
+                self.bind_check_predicate(&table_name, schema.clone(), &check.expr, arena)?;
+            }
+        }
+        for foreign_key in foreign_keys.iter_mut() {
+            if !constraint_names.insert(foreign_key.name.clone()) {
+                return Err(DatabaseError::DuplicateConstraint(foreign_key.name.clone()));
+            }
+            self.bind_foreign_key(&table_name, &columns, foreign_key, arena)?;
+        }
 
         Ok(LogicalPlan::new(
             Operator::CreateTable(CreateTableOperator {
                 table_name,
                 columns,
+                checks,
+                foreign_keys,
+                sequences,
                 if_not_exists,
             }),
             Childrens::None,
@@ -63,7 +128,7 @@ impl<T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'_, '_, T, A>
 mod tests {
     use super::*;
     use crate::binder::BinderContext;
-    use crate::catalog::ColumnDesc;
+    use crate::catalog::{ColumnDesc, ReferentialAction};
     use crate::storage::rocksdb::RocksStorage;
     use crate::storage::Storage;
     use crate::types::CharLengthUnits;
@@ -79,6 +144,7 @@ mod tests {
         let view_cache = crate::storage::ViewCache::default();
         let scala_functions = Default::default();
         let table_functions = Default::default();
+        let aggregate_functions = Default::default();
 
         let sql = "create table t1 (id int primary key, name varchar(10) null)";
         let mut binder = Binder::new(
@@ -88,6 +154,7 @@ mod tests {
                 &transaction,
                 &scala_functions,
                 &table_functions,
+                &aggregate_functions,
             ),
             &[],
             None,
@@ -97,6 +164,11 @@ mod tests {
         let table_arena = crate::planner::TableArenaCell::default();
         let mut plan_arena = crate::planner::PlanArena::new(&table_arena);
         let plan1 = binder.bind(&stmt, &mut plan_arena).unwrap();
+        let checked = crate::parser::parse_sql(
+            "create table t2 (id int primary key check (id > 0), c int, constraint c_positive check (c > 0), check (c > id))",
+        )
+        .unwrap();
+        let plan2 = binder.bind(&checked[0], &mut plan_arena)?;
 
         match plan1.operator {
             Operator::CreateTable(op) => {
@@ -121,6 +193,54 @@ mod tests {
             }
             _ => unreachable!(),
         }
+        match plan2.operator {
+            Operator::CreateTable(op) => {
+                let checks = op
+                    .checks
+                    .iter()
+                    .map(|check| (check.name.as_str(), check.expr.as_str()))
+                    .collect::<Vec<_>>();
+                assert_eq!(
+                    checks,
+                    vec![
+                        ("t2_id_check", "id > 0"),
+                        ("c_positive", "c > 0"),
+                        ("t2_check", "c > id"),
+                    ]
+                );
+            }
+            _ => unreachable!(),
+        }
+        let invalid =
+            crate::parser::parse_sql("create table t3 (id int primary key, c int check (c + 1))")
+                .unwrap();
+        assert!(binder.bind(&invalid[0], &mut plan_arena).is_err());
+
+        let referencing = crate::parser::parse_sql(
+            "create table t4 (id int primary key, p int references t4 on delete cascade)",
+        )
+        .unwrap();
+        match binder.bind(&referencing[0], &mut plan_arena)?.operator {
+            Operator::CreateTable(op) => {
+                assert_eq!(
+                    op.foreign_keys,
+                    vec![ForeignKey {
+                        name: "t4_p_fkey".to_string(),
+                        columns: vec!["p".to_string()],
+                        referenced_table: "t4".into(),
+                        referenced_columns: vec!["id".to_string()],
+                        on_delete: ReferentialAction::Cascade,
+                        on_update: ReferentialAction::Restrict,
+                    }]
+                );
+            }
+            _ => unreachable!(),
+        }
+        let invalid = crate::parser::parse_sql(
+            "create table t5 (id int primary key, p int references t5(p))",
+        )
+        .unwrap();
+        assert!(binder.bind(&invalid[0], &mut plan_arena).is_err());
 
         Ok(())
     }
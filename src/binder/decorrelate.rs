@@ -0,0 +1,405 @@
+// Copyright 2024 KipData/KiteSQL
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::binder::Binder;
+use crate::catalog::ColumnRef;
+use crate::errors::DatabaseError;
+use crate::execution::dql::aggregate::create_accumulator;
+use crate::expression::visitor_mut::{walk_mut_expr, ExprVisitorMut};
+use crate::expression::{AliasType, BinaryOperator, ScalarExpression};
+use crate::planner::operator::join::JoinType;
+use crate::planner::operator::project::ProjectOperator;
+use crate::planner::operator::scalar_apply::ScalarApplyOperator;
+use crate::planner::operator::Operator;
+use crate::planner::{Childrens, LogicalPlan, PlanArena};
+use crate::storage::Transaction;
+use crate::types::tuple::Schema;
+use crate::types::value::DataValue;
+use crate::types::LogicalType;
+
+struct DecorrelatedAggregate {
+    plan: LogicalPlan,
+    key_column: ColumnRef,
+    predicates: Vec<ScalarExpression>,
+    empty_value: Option<ScalarExpression>,
+}
+
+// Replaces the outputs of an aggregate with its results over no rows
+struct EmptyAggregateBinder<'a, 'p> {
+    outputs: &'a [(ColumnRef, DataValue)],
+    arena: &'a PlanArena<'p>,
+}
+
+impl ExprVisitorMut<'_> for EmptyAggregateBinder<'_, '_> {
+    fn visit(&mut self, expr: &mut ScalarExpression) -> Result<(), DatabaseError> {
+        if let ScalarExpression::ColumnRef { column, .. } = expr {
+            if let Some((_, value)) = self
+                .outputs
+                .iter()
+                .find(|(output, _)| self.arena.same_column(*output, *column))
+            {
+                *expr = ScalarExpression::Constant(value.clone());
+            }
+            return Ok(());
+        }
+        walk_mut_expr(self, expr)
+    }
+}
+
+impl<'a, T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'a, '_, T, A> {
+    /// Appends the value of a correlated scalar subquery to every row of `children`.
+    ///
+    /// A scalar aggregate correlated by equalities becomes a left join on the aggregate
+    /// grouped by the inner side of those equalities. Any other subquery is re-executed
+    /// for every row by a parameterized `ScalarApply`.
+    pub(super) fn bind_correlated_scalar_subquery(
+        &mut self,
+        mut children: LogicalPlan,
+        plan: LogicalPlan,
+        arena: &mut PlanArena,
+    ) -> Result<LogicalPlan, DatabaseError> {
+        let left_schema = children.output_schema(arena).clone();
+        let Some(mut decorrelated) =
+            self.decorrelate_scalar_aggregate(&plan, &left_schema, arena)?
+        else {
+            let parameters = Self::correlated_parameters(&plan, &left_schema, arena)?;
+            return Ok(ScalarApplyOperator::build_parameterized(
+                children, plan, parameters,
+            ));
+        };
+        let left_len = left_schema.len();
+        let value_column = decorrelated.plan.output_schema(arena)[0];
+        let value_ty = arena.column(value_column).datatype().clone();
+
+        let join = self.build_join_from_split_scope_predicates(
+            children,
+            decorrelated.plan,
+            JoinType::LeftOuter,
+            decorrelated.predicates,
+            true,
+            arena,
+        )?;
+        let left_exprs = || {
+            left_schema
+                .iter()
+                .enumerate()
+                .map(|(position, column)| ScalarExpression::column_expr(*column, position))
+                .collect::<Vec<_>>()
+        };
+        let value = ScalarExpression::column_expr(value_column, left_len);
+        let Some(empty_value) = decorrelated.empty_value else {
+            let mut exprs = left_exprs();
+            exprs.push(value);
+            return Ok(Self::build_project_plan(join, exprs));
+        };
+        // A row without a matching group sees the aggregate over no rows, e.g. `COUNT(*)`
+        // is 0 rather than NULL
+        let mut exprs = left_exprs();
+        exprs.push(ScalarExpression::Alias {
+            expr: Box::new(ScalarExpression::CaseWhen {
+                operand_expr: None,
+                expr_pairs: vec![(
+                    ScalarExpression::IsNull {
+                        negated: false,
+                        expr: Box::new(ScalarExpression::column_expr(
+                            decorrelated.key_column,
+                            left_len + 1,
+                        )),
+                    },
+                    empty_value,
+                )],
+                else_expr: Some(Box::new(value.clone())),
+                ty: value_ty,
+            }),
+            alias: AliasType::Name(arena.column(value_column).name().to_string()),
+        });
+        let mut plan = Self::build_project_plan(join, exprs);
+        let fixed_column = plan.output_schema(arena)[left_len];
+
+        // Renames the fixed value back to the column the outer query refers to
+        let mut exprs = left_exprs();
+        exprs.push(ScalarExpression::Alias {
+            expr: Box::new(ScalarExpression::column_expr(fixed_column, left_len)),
+            alias: AliasType::Expr(Box::new(value)),
+        });
+        Ok(Self::build_project_plan(plan, exprs))
+    }
+
+    fn decorrelate_scalar_aggregate(
+        &mut self,
+        plan: &LogicalPlan,
+        left_schema: &Schema,
+        arena: &mut PlanArena,
+    ) -> Result<Option<DecorrelatedAggregate>, DatabaseError> {
+        let mut child = plan.clone();
+        // In the SELECT list the value is renamed by a projection over the subquery, while the
+        // outer query reads the value it renames
+        if let Operator::Project(op) = &child.operator {
+            if !Self::is_temp_alias_projection(&op.exprs, arena) {
+                return Ok(None);
+            }
+            child = child.childrens.pop_only();
+        }
+        let LogicalPlan {
+            operator: Operator::ScalarSubquery(_),
+            childrens,
+            ..
+        } = child
+        else {
+            return Ok(None);
+        };
+        let child = childrens.pop_only();
+        let LogicalPlan {
+            operator: Operator::Project(project),
+            childrens,
+            ..
+        } = child
+        else {
+            return Ok(None);
+        };
+        let mut child = childrens.pop_only();
+        if matches!(child.operator, Operator::Sort(_)) {
+            child = child.childrens.pop_only();
+        }
+        let LogicalPlan {
+            operator: Operator::Aggregate(mut aggregate),
+            childrens,
+            ..
+        } = child
+        else {
+            return Ok(None);
+        };
+        if project.exprs.len() != 1 || !aggregate.groupby_exprs.is_empty() || aggregate.is_distinct
+        {
+            return Ok(None);
+        }
+        for expr in project.exprs.iter().chain(&aggregate.agg_calls) {
+            if Self::expr_has_correlated_refs(expr, left_schema, arena)? {
+                return Ok(None);
+            }
+        }
+        let input = childrens.pop_only();
+        if !Self::can_pull_up_correlated_filters(&input, left_schema, true, false, arena)? {
+            return Ok(None);
+        }
+        let (mut input, filters) =
+            Self::prepare_correlated_subquery_plan(input, left_schema, true, arena)?;
+        let input_schema = input.output_schema(arena).clone();
+        let mut outer_exprs = Vec::with_capacity(filters.len());
+        for filter in filters {
+            let Some((outer_expr, mut inner_expr)) =
+                Self::correlation_key(filter, left_schema, arena)?
+            else {
+                return Ok(None);
+            };
+            Self::rebind_split_scope_positions(&mut inner_expr, &input_schema, &Vec::new(), arena)?;
+            outer_exprs.push(outer_expr);
+            aggregate.groupby_exprs.push(inner_expr);
+        }
+        if outer_exprs.is_empty() {
+            return Ok(None);
+        }
+
+        let empty_values = aggregate
+            .agg_calls
+            .iter()
+            .map(|agg_call| {
+                let ScalarExpression::AggCall { kind, ty, .. } = agg_call.unpack_alias_ref() else {
+                    return Err(DatabaseError::InvalidType);
+                };
+                let mut accumulator = create_accumulator(kind, ty)?;
+                accumulator.evaluate()?;
+                Ok(accumulator.result_owned())
+            })
+            .collect::<Result<Vec<_>, DatabaseError>>()?;
+        let agg_len = aggregate.agg_calls.len();
+        let mut plan = LogicalPlan::new(
+            Operator::Aggregate(aggregate),
+            Childrens::Only(Box::new(input)),
+        );
+        let aggregate_outputs = plan.output_schema(arena).clone();
+
+        let value_expr = project.exprs.into_iter().next().unwrap();
+        let mut empty_value = value_expr.unpack_alias_ref().clone();
+        let empty_outputs = aggregate_outputs
+            .iter()
+            .copied()
+            .zip(empty_values)
+            .collect::<Vec<_>>();
+        EmptyAggregateBinder {
+            outputs: &empty_outputs,
+            arena,
+        }
+        .visit(&mut empty_value)?;
+        // A bare aggregate that is NULL over no rows needs no fallback, while an expression
+        // over it, e.g. `COALESCE(MAX(v), 0)`, still has to be evaluated for it
+        let has_empty_value =
+            !matches!(&empty_value, ScalarExpression::Constant(value) if value.is_null());
+
+        let key_outputs = (agg_len..aggregate_outputs.len())
+            .map(|position| ScalarExpression::column_expr(aggregate_outputs[position], position))
+            .collect::<Vec<_>>();
+        let mut exprs = Vec::with_capacity(key_outputs.len() + 1);
+        exprs.push(value_expr);
+        exprs.extend(key_outputs);
+        plan = LogicalPlan::new(
+            Operator::Project(ProjectOperator { exprs }),
+            Childrens::Only(Box::new(plan)),
+        );
+        let key_columns = plan.output_schema(arena)[1..].to_vec();
+        let key_column = key_columns[0];
+        let predicates = outer_exprs
+            .into_iter()
+            .zip(key_columns)
+            .enumerate()
+            .map(
+                |(position, (outer_expr, key_column))| ScalarExpression::Binary {
+                    op: BinaryOperator::Eq,
+                    left_expr: Box::new(outer_expr),
+                    right_expr: Box::new(ScalarExpression::column_expr(key_column, position + 1)),
+                    evaluator: None,
+                    ty: LogicalType::Boolean,
+                },
+            )
+            .collect();
+
+        Ok(Some(DecorrelatedAggregate {
+            plan,
+            key_column,
+            predicates,
+            empty_value: has_empty_value.then_some(empty_value),
+        }))
+    }
+
+    // Splits `inner = outer` into its outer and inner side
+    fn correlation_key(
+        filter: ScalarExpression,
+        left_schema: &Schema,
+        arena: &mut PlanArena,
+    ) -> Result<Option<(ScalarExpression, ScalarExpression)>, DatabaseError> {
+        let ScalarExpression::Binary {
+            op: BinaryOperator::Eq,
+            left_expr,
+            right_expr,
+            ..
+        } = filter
+        else {
+            return Ok(None);
+        };
+        let is_outer = |expr: &ScalarExpression, arena: &mut PlanArena| {
+            expr.any_referenced_column(arena, |arena, column| {
+                !left_schema
+                    .iter()
+                    .any(|left| arena.same_column(*left, *column))
+            })
+            .map(|has_inner| !has_inner)
+        };
+        let is_inner = |expr: &ScalarExpression, arena: &mut PlanArena| {
+            Ok::<_, DatabaseError>(
+                !Self::expr_has_correlated_refs(expr, left_schema, arena)?
+                    && expr.any_referenced_column(arena, |_, _| true)?,
+            )
+        };
+
+        if is_outer(&left_expr, arena)? && is_inner(&right_expr, arena)? {
+            Ok(Some((*left_expr, *right_expr)))
+        } else if is_outer(&right_expr, arena)? && is_inner(&left_expr, arena)? {
+            Ok(Some((*right_expr, *left_expr)))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Whether every correlated reference of a subquery sits in filters that can be pulled
+    /// up into the predicates of an apply, see `prepare_correlated_subquery_plan`.
+    pub(super) fn can_pull_up_correlated_filters(
+        plan: &LogicalPlan,
+        left_schema: &Schema,
+        preserve_projection: bool,
+        strip_limit: bool,
+        arena: &mut PlanArena,
+    ) -> Result<bool, DatabaseError> {
+        let Childrens::Only(child) = plan.childrens.as_ref() else {
+            return Ok(!Self::plan_has_correlated_refs(plan, left_schema, arena)?);
+        };
+        match &plan.operator {
+            Operator::Filter(_) | Operator::Sort(_) => {}
+            Operator::Limit(op) => {
+                if !strip_limit || op.offset.unwrap_or(0) > 0 || op.limit == Some(0) {
+                    return Ok(!Self::plan_has_correlated_refs(plan, left_schema, arena)?);
+                }
+            }
+            Operator::TopK(op) => {
+                if !strip_limit || op.offset.unwrap_or(0) > 0 || op.limit == 0 {
+                    return Ok(!Self::plan_has_correlated_refs(plan, left_schema, arena)?);
+                }
+            }
+            Operator::Project(op) => {
+                if preserve_projection
+                    && !Self::is_temp_alias_projection(&op.exprs, arena)
+                    && Self::operator_has_correlated_refs(&plan.operator, left_schema, arena)?
+                {
+                    return Ok(false);
+                }
+            }
+            _ => return Ok(!Self::plan_has_correlated_refs(plan, left_schema, arena)?),
+        }
+        Self::can_pull_up_correlated_filters(
+            child,
+            left_schema,
+            preserve_projection,
+            strip_limit,
+            arena,
+        )
+    }
+
+    /// The left columns a correlated subquery refers to, bound per row by a parameterized apply.
+    pub(super) fn correlated_parameters(
+        plan: &LogicalPlan,
+        left_schema: &Schema,
+        arena: &mut PlanArena,
+    ) -> Result<Vec<ScalarExpression>, DatabaseError> {
+        fn collect(
+            plan: &LogicalPlan,
+            left_schema: &Schema,
+            positions: &mut Vec<usize>,
+            arena: &mut PlanArena,
+        ) -> Result<(), DatabaseError> {
+            plan.operator
+                .visit_referenced_columns(arena, &mut |arena, column| {
+                    if let Some(position) = left_schema
+                        .iter()
+                        .position(|left| arena.same_column(*left, *column))
+                    {
+                        positions.push(position);
+                    }
+                    true
+                })?;
+            for child in plan.childrens.iter() {
+                collect(child, left_schema, positions, arena)?;
+            }
+            Ok(())
+        }
+
+        let mut positions = Vec::new();
+        collect(plan, left_schema, &mut positions, arena)?;
+        positions.sort_unstable();
+        positions.dedup();
+
+        Ok(positions
+            .into_iter()
+            .map(|position| ScalarExpression::column_expr(left_schema[position], position))
+            .collect())
+    }
+}
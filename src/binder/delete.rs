@@ -15,6 +15,7 @@
 use crate::binder::Binder;
 use crate::catalog::{ColumnRef, TableName};
 use crate::errors::DatabaseError;
+use crate::expression::ScalarExpression;
 use crate::planner::operator::delete::DeleteOperator;
 use crate::planner::operator::Operator;
 use crate::planner::{Childrens, LogicalPlan};
@@ -27,11 +28,13 @@ impl<T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'_, '_, T, A>
         table_name: TableName,
         primary_keys: Vec<ColumnRef>,
         input: LogicalPlan,
+        returning: Vec<ScalarExpression>,
     ) -> Result<LogicalPlan, DatabaseError> {
         Ok(LogicalPlan::new(
             Operator::Delete(DeleteOperator {
                 table_name,
                 primary_keys,
+                returning,
             }),
             Childrens::Only(Box::new(input)),
         ))
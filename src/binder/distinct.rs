@@ -34,6 +34,7 @@ impl<T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'_, '_, T, A>
             children,
             vec![],
             select_list,
+            Vec::new(),
             true,
             self.force_spill,
         ))
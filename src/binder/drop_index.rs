@@ -17,7 +17,7 @@ use crate::catalog::TableName;
 use crate::errors::DatabaseError;
 use crate::planner::operator::drop_index::DropIndexOperator;
 use crate::planner::operator::Operator;
-use crate::planner::{Childrens, LogicalPlan};
+use crate::planner::{Childrens, LogicalPlan, PlanArena};
 use crate::storage::Transaction;
 use crate::types::value::DataValue;
 
@@ -27,7 +27,10 @@ impl<T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'_, '_, T, A>
         table_name: TableName,
         index_name: String,
         if_exists: bool,
+        arena: &PlanArena,
     ) -> Result<LogicalPlan, DatabaseError> {
+        self.ensure_index_unreferenced(&table_name, &index_name, arena)?;
+
         Ok(LogicalPlan::new(
             Operator::DropIndex(DropIndexOperator {
                 table_name,
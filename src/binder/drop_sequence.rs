@@ -0,0 +1,37 @@
+// Copyright 2024 KipData/KiteSQL
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::binder::Binder;
+use crate::errors::DatabaseError;
+use crate::planner::operator::drop_sequence::DropSequenceOperator;
+use crate::planner::operator::Operator;
+use crate::planner::{Childrens, LogicalPlan};
+use crate::storage::Transaction;
+use crate::types::value::DataValue;
+
+impl<T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'_, '_, T, A> {
+    pub(crate) fn bind_drop_sequence(
+        &mut self,
+        sequence_name: String,
+        if_exists: bool,
+    ) -> Result<LogicalPlan, DatabaseError> {
+        Ok(LogicalPlan::new(
+            Operator::DropSequence(DropSequenceOperator {
+                sequence_name,
+                if_exists,
+            }),
+            Childrens::None,
+        ))
+    }
+}
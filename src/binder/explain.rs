@@ -14,15 +14,21 @@
 
 use crate::binder::Binder;
 use crate::errors::DatabaseError;
+use crate::planner::operator::explain::{ExplainFormat, ExplainOperator};
 use crate::planner::operator::Operator;
 use crate::planner::{Childrens, LogicalPlan};
 use crate::storage::Transaction;
 use crate::types::value::DataValue;
 
 impl<T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'_, '_, T, A> {
-    pub(crate) fn bind_explain(&mut self, plan: LogicalPlan) -> Result<LogicalPlan, DatabaseError> {
+    pub(crate) fn bind_explain(
+        &mut self,
+        plan: LogicalPlan,
+        analyze: bool,
+        format: ExplainFormat,
+    ) -> Result<LogicalPlan, DatabaseError> {
         Ok(LogicalPlan::new(
-            Operator::Explain,
+            Operator::Explain(ExplainOperator { analyze, format }),
             Childrens::Only(Box::new(plan)),
         ))
     }
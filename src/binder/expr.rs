@@ -12,23 +12,34 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::catalog::ColumnRef;
+use crate::catalog::{ColumnCatalog, ColumnDesc, ColumnRef};
 use crate::errors::DatabaseError;
 use crate::expression;
 use crate::expression::agg::AggKind;
 use crate::iter_ext::Itertools;
 
+use super::insert::EXCLUDED_TABLE;
+use super::select::TableAliasInput;
 use super::{Binder, BinderContext, QueryBindStep, SubQueryType};
-use crate::expression::function::scala::{ArcScalarFunctionImpl, ScalarFunction};
-use crate::expression::function::table::TableFunction;
+use crate::db::{ScalaFunctions, TableFunctions};
+use crate::expression::array::ArrayFunction;
+use crate::expression::function::aggregate::ArcAggregateFunctionImpl;
+use crate::expression::function::scala::{
+    ArcScalarFunctionImpl, ScalarFunction, ScalarFunctionImpl,
+};
+use crate::expression::function::table::{TableFunction, TableFunctionCatalog};
 use crate::expression::function::FunctionSummary;
-use crate::expression::{AliasType, ScalarExpression};
+use crate::expression::{AliasType, ScalarExpression, SequenceFunction};
 use crate::planner::operator::mark_apply::MarkApplyQuantifier;
 use crate::planner::operator::scalar_subquery::ScalarSubqueryOperator;
+use crate::planner::operator::sort::SortField;
 use crate::planner::{LogicalPlan, PlanArena};
 use crate::storage::Transaction;
+use crate::types::evaluator::{binary_create, temporal_binary_create};
 use crate::types::value::{DataValue, Utf8Type};
 use crate::types::{CharLengthUnits, LogicalType};
+use std::borrow::Cow;
+use std::sync::Arc;
 
 macro_rules! try_default {
     ($table_name:expr, $column_name:expr) => {
@@ -134,25 +145,12 @@ impl<'a, T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'a, '_, T
             &mut PlanArena<'arena>,
         ) -> Result<LogicalPlan, DatabaseError>,
     {
-        let BinderContext {
-            table_cache,
-            view_cache,
-            transaction,
-            scala_functions,
-            table_functions,
-            ..
-        } = &self.context;
-        let mut binder = Binder::new(
-            BinderContext::new(
-                table_cache,
-                view_cache,
-                *transaction,
-                scala_functions,
-                table_functions,
-            ),
-            self.args,
-            Some(&self.context),
-        );
+        let mut binder = Binder::new(self.context.fork_empty(), self.args, Some(&self.context));
+        binder.ancestors = self
+            .parent
+            .into_iter()
+            .chain(self.ancestors.iter().copied())
+            .collect();
         let sub_query = build(&mut binder, arena)?;
         let correlated = binder.context.has_outer_refs();
         Ok((sub_query, correlated))
@@ -356,18 +354,32 @@ impl<'a, T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'a, '_, T
             try_default!(&table_name, column_name);
         }
         if let Some(table) = table_name.or(bind_table_name) {
-            let (source, position_offset) =
-                match Self::resolve_source_columns_in_scope(&self.context, table) {
-                    Ok(source) => source,
-                    Err(err) => {
-                        if let Some(parent) = self.parent {
-                            self.context.mark_outer_ref();
-                            Self::resolve_source_columns_in_scope(parent, table).map_err(|_| err)?
-                        } else {
-                            return Err(err);
-                        }
+            let resolve = if table_name.is_some() {
+                Self::resolve_visible_source_columns_in_scope
+            } else {
+                Self::resolve_source_columns_in_scope
+            };
+            let (source, position_offset) = match resolve(&self.context, table) {
+                Ok(source) => source,
+                Err(err) => {
+                    if let Some(parent) = self.parent {
+                        self.context.mark_outer_ref();
+                        resolve(parent, table).map_err(|_| {
+                            if self
+                                .ancestors
+                                .iter()
+                                .any(|ancestor| resolve(ancestor, table).is_ok())
+                            {
+                                Self::ancestor_ref_error(table)
+                            } else {
+                                err
+                            }
+                        })?
+                    } else {
+                        return Err(err);
                     }
-                };
+                }
+            };
             let (position, column) =
                 Self::find_column_in_schema(source.schema().iter(), arena, column_name)
                     .ok_or_else(|| DatabaseError::column_not_found(column_name.to_string()))?;
@@ -396,6 +408,13 @@ impl<'a, T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'a, '_, T
                     got_column = find_visible_column(parent)?;
                 }
             }
+            if got_column.is_none() {
+                for ancestor in self.ancestors.iter() {
+                    if find_visible_column(ancestor)?.is_some() {
+                        return Err(Self::ancestor_ref_error(column_name));
+                    }
+                }
+            }
             match got_column {
                 Some(column) => Ok(column),
                 None => Err(DatabaseError::column_not_found(column_name.to_string())),
@@ -403,6 +422,12 @@ impl<'a, T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'a, '_, T
         }
     }
 
+    fn ancestor_ref_error(name: &str) -> DatabaseError {
+        DatabaseError::UnsupportedStmt(format!(
+            "correlated subqueries referring to `{name}` beyond the directly enclosing query are not supported"
+        ))
+    }
+
     pub(crate) fn bind_binary_op_expr(
         &mut self,
         left_expr: ScalarExpression,
@@ -414,6 +439,15 @@ impl<'a, T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'a, '_, T
         let right_expr = Box::new(right_expr);
         let left_ty = left_expr.return_type(arena);
         let right_ty = right_expr.return_type(arena);
+        if let Some((_, ty)) = temporal_binary_create(&left_ty, &right_ty, &op) {
+            return Ok(ScalarExpression::Binary {
+                op,
+                left_expr,
+                right_expr,
+                evaluator: None,
+                ty,
+            });
+        }
         let ty = match &op {
             expression::BinaryOperator::Plus
             | expression::BinaryOperator::Minus
@@ -479,13 +513,38 @@ impl<'a, T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'a, '_, T
     pub(crate) fn bind_aggregate_function(
         &mut self,
         kind: AggKind,
-        args: Vec<ScalarExpression>,
+        mut args: Vec<ScalarExpression>,
         is_distinct: bool,
+        filter: Option<ScalarExpression>,
+        order_by: Vec<SortField>,
         arena: &mut PlanArena,
     ) -> Result<ScalarExpression, DatabaseError> {
+        if is_distinct
+            && order_by
+                .iter()
+                .any(|field| !args.iter().any(|arg| arg == &field.expr))
+        {
+            return Err(DatabaseError::UnsupportedStmt(
+                "in an aggregate with DISTINCT, ORDER BY expressions must appear in the argument list"
+                    .to_string(),
+            ));
+        }
+        if let Some(filter) = &filter {
+            if filter.has_agg_call()? {
+                return Err(DatabaseError::UnsupportedStmt(
+                    "aggregate functions are not allowed in FILTER".to_string(),
+                ));
+            }
+        }
+        let cast_args = |args: Vec<ScalarExpression>, ty: &LogicalType| {
+            args.into_iter()
+                .map(|arg| ScalarExpression::type_cast(arg, Cow::Borrowed(ty), arena))
+                .collect::<Result<Vec<_>, _>>()
+        };
         let ty = match kind {
             AggKind::Count => {
-                if args.len() != 1 {
+                // `COUNT(DISTINCT a, b)` counts the distinct rows in which no argument is NULL
+                if args.is_empty() || (args.len() > 1 && !is_distinct) {
                     return Err(DatabaseError::MisMatch("number of count() parameters", "1"));
                 }
                 LogicalType::Integer
@@ -514,15 +573,122 @@ impl<'a, T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'a, '_, T
                 }
                 LogicalType::Double
             }
+            AggKind::StringAgg => {
+                if args.len() != 2 {
+                    return Err(DatabaseError::MisMatch(
+                        "number of string_agg() parameters",
+                        "2",
+                    ));
+                }
+                let ty = LogicalType::Varchar(None, CharLengthUnits::Characters);
+                args = cast_args(args, &ty)?;
+                ty
+            }
+            AggKind::BoolAnd | AggKind::BoolOr => {
+                if args.len() != 1 {
+                    return Err(DatabaseError::MisMatch(
+                        "number of bool_and()/bool_or() parameters",
+                        "1",
+                    ));
+                }
+                args = cast_args(args, &LogicalType::Boolean)?;
+                LogicalType::Boolean
+            }
+            AggKind::StddevPop | AggKind::StddevSamp | AggKind::VarPop | AggKind::VarSamp => {
+                if args.len() != 1 {
+                    return Err(DatabaseError::MisMatch(
+                        "number of stddev()/variance() parameters",
+                        "1",
+                    ));
+                }
+                args = cast_args(args, &LogicalType::Double)?;
+                LogicalType::Double
+            }
+            AggKind::Grouping => {
+                if args.is_empty() || args.len() > 31 {
+                    return Err(DatabaseError::MisMatch(
+                        "number of grouping() parameters",
+                        "1 to 31",
+                    ));
+                }
+                if is_distinct || filter.is_some() || !order_by.is_empty() {
+                    return Err(DatabaseError::UnsupportedStmt(
+                        "DISTINCT, FILTER and ORDER BY are not supported for grouping()"
+                            .to_string(),
+                    ));
+                }
+                LogicalType::Integer
+            }
+            AggKind::JsonAgg => {
+                if args.len() != 1 {
+                    return Err(DatabaseError::MisMatch(
+                        "number of json_agg() parameters",
+                        "1",
+                    ));
+                }
+                LogicalType::Json
+            }
+            AggKind::JsonObjectAgg => {
+                if args.len() != 2 {
+                    return Err(DatabaseError::MisMatch(
+                        "number of json_object_agg() parameters",
+                        "2",
+                    ));
+                }
+                LogicalType::Json
+            }
+            AggKind::ArrayAgg => {
+                if args.len() != 1 {
+                    return Err(DatabaseError::MisMatch(
+                        "number of array_agg() parameters",
+                        "1",
+                    ));
+                }
+                LogicalType::Array(Box::new(args[0].return_type(arena).into_owned()))
+            }
+            AggKind::UserDefined(ref function) => {
+                let summary = function.summary();
+                if args.len() != summary.arg_types.len()
+                    || args
+                        .iter()
+                        .zip(summary.arg_types.iter())
+                        .any(|(arg, ty)| arg.return_type(arena).as_ref() != ty)
+                {
+                    return Err(DatabaseError::function_not_found(summary.name.to_string()));
+                }
+                function.return_type().clone()
+            }
         };
         Ok(ScalarExpression::AggCall {
             distinct: is_distinct,
             kind,
             args,
+            filter: filter.map(Box::new),
+            order_by,
             ty,
         })
     }
 
+    /// Resolves a registered user-defined aggregate by its name and argument types.
+    pub(crate) fn user_defined_aggregate(
+        &self,
+        function_name: &str,
+        args: &[ScalarExpression],
+        arena: &PlanArena,
+    ) -> Option<AggKind> {
+        let summary = FunctionSummary {
+            name: function_name.into(),
+            arg_types: args
+                .iter()
+                .map(|arg| arg.return_type(arena).into_owned())
+                .collect_vec(),
+        };
+        self.context
+            .aggregate_functions
+            .get(&summary)
+            .map(|function| AggKind::UserDefined(ArcAggregateFunctionImpl(function.clone())))
+    }
+
     pub(crate) fn bind_function_call(
         &mut self,
         function_name: String,
@@ -530,6 +696,23 @@ impl<'a, T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'a, '_, T
         arena: &mut PlanArena,
     ) -> Result<ScalarExpression, DatabaseError> {
         match function_name.as_str() {
+            // MySQL `ON DUPLICATE KEY UPDATE c = VALUES(c)`
+            "values" if args.len() == 1 => {
+                if let (ScalarExpression::ColumnRef { column, .. }, Ok((source, position_offset))) = (
+                    &args[0],
+                    Self::resolve_source_columns_in_scope(&self.context, EXCLUDED_TABLE),
+                ) {
+                    let column_name = arena.column(*column).name();
+                    if let Some((position, column)) =
+                        Self::find_column_in_schema(source.schema(), arena, column_name)
+                    {
+                        return Ok(ScalarExpression::column_expr(
+                            column,
+                            position_offset + position,
+                        ));
+                    }
+                }
+            }
             "if" => {
                 if args.len() != 3 {
                     return Err(DatabaseError::MisMatch("number of if() parameters", "3"));
@@ -601,6 +784,62 @@ impl<'a, T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'a, '_, T
                 }
                 return Ok(ScalarExpression::Coalesce { exprs: args, ty });
             }
+            "array_length" => {
+                if !matches!(args.len(), 1 | 2) {
+                    return Err(DatabaseError::MisMatch(
+                        "number of array_length() parameters",
+                        "1 or 2",
+                    ));
+                }
+                Self::array_element_type(&function_name, &args[0], arena)?;
+                if let Some(dimension) = args.get_mut(1) {
+                    Self::cast_in_place(dimension, &LogicalType::Bigint, arena)?;
+                }
+                return Ok(ScalarExpression::Array {
+                    function: ArrayFunction::Length,
+                    args,
+                    ty: LogicalType::Integer,
+                });
+            }
+            "array_append" => {
+                if args.len() != 2 {
+                    return Err(DatabaseError::MisMatch(
+                        "number of array_append() parameters",
+                        "2",
+                    ));
+                }
+                let element_ty = Self::array_element_type(&function_name, &args[0], arena)?;
+                let element_ty =
+                    LogicalType::max_logical_type(&element_ty, &args[1].return_type(arena))?
+                        .into_owned();
+                let ty = LogicalType::Array(Box::new(element_ty.clone()));
+                Self::cast_in_place(&mut args[0], &ty, arena)?;
+                Self::cast_in_place(&mut args[1], &element_ty, arena)?;
+
+                return Ok(ScalarExpression::Array {
+                    function: ArrayFunction::Append,
+                    args,
+                    ty,
+                });
+            }
+            "nextval" | "currval" => {
+                let function = if function_name == "nextval" {
+                    SequenceFunction::NextVal
+                } else {
+                    SequenceFunction::CurrVal
+                };
+                let [ScalarExpression::Constant(DataValue::Utf8 { value, .. })] = args.as_slice()
+                else {
+                    return Err(DatabaseError::InvalidValue(format!(
+                        "{function_name}() expects the name of a sequence"
+                    )));
+                };
+
+                return Ok(ScalarExpression::Sequence {
+                    function,
+                    name: value.to_lowercase(),
+                });
+            }
             _ => (),
         }
         let arg_types = args
@@ -611,27 +850,325 @@ impl<'a, T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'a, '_, T
             name: function_name.into(),
             arg_types,
         };
-        if let Some(function) = self.context.scala_functions.get(&summary) {
+        let function = match self.context.scala_functions.get(&summary) {
+            Some(function) => Some(function.clone()),
+            None => Self::resolve_scalar_function(self.context.scala_functions, &summary),
+        };
+        if let Some(function) = function {
+            let arg_types = &function.summary().arg_types;
+            let args = args
+                .into_iter()
+                .enumerate()
+                .map(|(i, arg)| {
+                    let ty = arg_types.get(i).or(arg_types.last()).unwrap();
+                    ScalarExpression::type_cast(arg, Cow::Borrowed(ty), arena)
+                })
+                .try_collect()?;
             return Ok(ScalarExpression::ScalaFunction(ScalarFunction {
                 args,
-                inner: ArcScalarFunctionImpl(function.clone()),
+                inner: ArcScalarFunctionImpl(function),
             }));
         }
-        if let Some(function) = self.context.table_functions.get(&summary) {
+        let function = match self.context.table_functions.get(&summary) {
+            Some(function) => Some(function.clone()),
+            None => Self::resolve_table_function(self.context.table_functions, &summary),
+        };
+        if let Some(mut function) = function {
             if !matches!(self.context.step_now(), QueryBindStep::From) {
                 return Err(DatabaseError::UnsupportedStmt(
                     "`TableFunction` cannot bind in non-From step".to_string(),
                 ));
             }
+            if function.schema.is_empty() {
+                function.schema = function
+                    .inner
+                    .bound_output_columns()
+                    .into_iter()
+                    .map(|column| arena.alloc_column(column))
+                    .collect();
+            }
+            let args = args
+                .into_iter()
+                .zip(function.inner.summary().arg_types.iter())
+                .map(|(arg, ty)| ScalarExpression::type_cast(arg, Cow::Borrowed(ty), arena))
+                .try_collect()?;
             return Ok(ScalarExpression::TableFunction(TableFunction {
                 args,
-                catalog: function.clone(),
+                catalog: function,
             }));
         }
+        // Tips: `strftime` follows SQLite and takes the format first, unlike `to_char`
+        if summary.name.as_ref() == "strftime" && summary.arg_types.len() == 2 {
+            let swapped = FunctionSummary {
+                name: summary.name.clone(),
+                arg_types: summary.arg_types.iter().rev().cloned().collect(),
+            };
+            if Self::resolve_scalar_function(self.context.scala_functions, &swapped).is_some() {
+                return Err(DatabaseError::InvalidValue(
+                    "strftime() takes the format first: strftime(format, value)".to_string(),
+                ));
+            }
+        }
 
         Err(DatabaseError::function_not_found(summary.name.to_string()))
     }
 
+    /// Picks the overload of `summary.name` the arguments can be cast to with the fewest
+    /// casts, preferring the narrowest argument types on a tie. The arguments are then cast
+    /// to the declared types, so functions only see the values they declare.
+    fn resolve_scalar_function(
+        functions: &ScalaFunctions,
+        summary: &FunctionSummary,
+    ) -> Option<Arc<dyn ScalarFunctionImpl>> {
+        functions
+            .values()
+            .filter(|function| function.summary().name == summary.name)
+            .filter_map(|function| {
+                let casts = Self::overload_casts(
+                    &function.summary().arg_types,
+                    function.is_variadic(),
+                    &summary.arg_types,
+                )?;
+                Some((casts, function))
+            })
+            .min_by(|(casts_1, function_1), (casts_2, function_2)| {
+                casts_1.cmp(casts_2).then_with(|| {
+                    function_1
+                        .summary()
+                        .arg_types
+                        .cmp(&function_2.summary().arg_types)
+                })
+            })
+            .map(|(_, function)| function.clone())
+    }
+
+    /// Like [`Self::resolve_scalar_function`], for table functions such as `json_each('[1]')`.
+    fn resolve_table_function(
+        functions: &TableFunctions,
+        summary: &FunctionSummary,
+    ) -> Option<TableFunctionCatalog> {
+        functions
+            .values()
+            .filter(|function| function.inner.summary().name == summary.name)
+            .filter_map(|function| {
+                let casts = Self::overload_casts(
+                    &function.inner.summary().arg_types,
+                    false,
+                    &summary.arg_types,
+                )?;
+                Some((casts, function))
+            })
+            .min_by(|(casts_1, function_1), (casts_2, function_2)| {
+                casts_1.cmp(casts_2).then_with(|| {
+                    function_1
+                        .inner
+                        .summary()
+                        .arg_types
+                        .cmp(&function_2.inner.summary().arg_types)
+                })
+            })
+            .map(|(_, function)| function.clone())
+    }
+
+    /// How many of `arg_types` must be cast to call an overload declaring `declared`, `None`
+    /// when it cannot be called with them.
+    fn overload_casts(
+        declared: &[LogicalType],
+        is_variadic: bool,
+        arg_types: &[LogicalType],
+    ) -> Option<usize> {
+        let arity_matches = if is_variadic {
+            !declared.is_empty() && arg_types.len() >= declared.len()
+        } else {
+            arg_types.len() == declared.len()
+        };
+        if !arity_matches {
+            return None;
+        }
+        let mut casts = 0;
+        for (i, from) in arg_types.iter().enumerate() {
+            let to = declared.get(i).or(declared.last())?;
+            if from == to {
+                continue;
+            }
+            if !Self::can_cast_function_arg(from, to) {
+                return None;
+            }
+            casts += 1;
+        }
+        Some(casts)
+    }
+
+    fn can_cast_function_arg(from: &LogicalType, to: &LogicalType) -> bool {
+        match (from, to) {
+            // every value but a tuple can be rendered as a string
+            (LogicalType::Tuple(_), _) => false,
+            (_, LogicalType::Varchar(None, _)) => true,
+            (LogicalType::Decimal(..), LogicalType::Decimal(None, None)) => true,
+            // strings are parsed as documents, like a `::json` cast
+            (LogicalType::Varchar(..) | LogicalType::Char(..), LogicalType::Json) => true,
+            (from, to) => LogicalType::can_implicit_cast(from, to),
+        }
+    }
+
+    /// `ARRAY[...]`, whose elements are cast to their common type.
+    pub(crate) fn bind_array_construct(
+        mut args: Vec<ScalarExpression>,
+        arena: &PlanArena,
+    ) -> Result<ScalarExpression, DatabaseError> {
+        let mut element_ty = LogicalType::SqlNull;
+        for arg in args.iter() {
+            element_ty =
+                LogicalType::max_logical_type(&element_ty, &arg.return_type(arena))?.into_owned();
+        }
+        for arg in args.iter_mut() {
+            Self::cast_in_place(arg, &element_ty, arena)?;
+        }
+
+        Ok(ScalarExpression::Array {
+            function: ArrayFunction::Construct,
+            args,
+            ty: LogicalType::Array(Box::new(element_ty)),
+        })
+    }
+
+    /// `array[index]`, which is `NULL` when the index is out of range.
+    pub(crate) fn bind_array_element(
+        array: ScalarExpression,
+        index: ScalarExpression,
+        arena: &PlanArena,
+    ) -> Result<ScalarExpression, DatabaseError> {
+        let ty = Self::array_element_type("subscript", &array, arena)?;
+        let index = ScalarExpression::type_cast(index, Cow::Owned(LogicalType::Bigint), arena)?;
+
+        Ok(ScalarExpression::Array {
+            function: ArrayFunction::Element,
+            args: vec![array, index],
+            ty,
+        })
+    }
+
+    /// `container @> contained`, with both sides cast to a common array type.
+    pub(crate) fn bind_array_contains(
+        container: ScalarExpression,
+        contained: ScalarExpression,
+        arena: &PlanArena,
+    ) -> Result<ScalarExpression, DatabaseError> {
+        let mut args = vec![container, contained];
+        let ty = LogicalType::max_logical_type(
+            &args[0].return_type(arena),
+            &args[1].return_type(arena),
+        )?
+        .into_owned();
+        if !matches!(ty, LogicalType::Array(_) | LogicalType::SqlNull) {
+            return Err(DatabaseError::InvalidValue(format!(
+                "array containment expects arrays, found {ty}"
+            )));
+        }
+        for arg in args.iter_mut() {
+            Self::cast_in_place(arg, &ty, arena)?;
+        }
+
+        Ok(ScalarExpression::Array {
+            function: ArrayFunction::Contains,
+            args,
+            ty: LogicalType::Boolean,
+        })
+    }
+
+    /// `value <op> ANY(array)` and `value <op> ALL(array)`, comparing the value with every
+    /// element after casting both to their common type.
+    pub(crate) fn bind_array_quantified(
+        quantifier: MarkApplyQuantifier,
+        expr: ScalarExpression,
+        op: expression::BinaryOperator,
+        array: ScalarExpression,
+        arena: &PlanArena,
+    ) -> Result<ScalarExpression, DatabaseError> {
+        let element_ty = Self::array_element_type(&format!("{quantifier:?}"), &array, arena)?;
+        let ty = LogicalType::max_logical_type(&expr.return_type(arena), &element_ty)?.into_owned();
+        let mut args = vec![expr, array];
+        Self::cast_in_place(&mut args[0], &ty, arena)?;
+        Self::cast_in_place(
+            &mut args[1],
+            &LogicalType::Array(Box::new(ty.clone())),
+            arena,
+        )?;
+        let evaluator = binary_create(Cow::Owned(ty), op)?;
+        let function = match quantifier {
+            MarkApplyQuantifier::Any => ArrayFunction::Any { op, evaluator },
+            MarkApplyQuantifier::All => ArrayFunction::All { op, evaluator },
+        };
+
+        Ok(ScalarExpression::Array {
+            function,
+            args,
+            ty: LogicalType::Boolean,
+        })
+    }
+
+    /// `unnest(array)` in FROM. The output column takes the element type of the array and is
+    /// named after the table alias, so `unnest(tags) AS tag` reads as a `tag` column.
+    pub(crate) fn bind_unnest(
+        &mut self,
+        array: ScalarExpression,
+        alias: Option<&TableAliasInput>,
+        arena: &mut PlanArena,
+    ) -> Result<ScalarExpression, DatabaseError> {
+        let summary = FunctionSummary {
+            name: "unnest".into(),
+            arg_types: Vec::new(),
+        };
+        let Some(TableFunctionCatalog { inner, .. }) =
+            self.context.table_functions.get(&summary).cloned()
+        else {
+            return Err(DatabaseError::function_not_found(summary.name.to_string()));
+        };
+        let ty = Self::array_element_type("unnest", &array, arena)?;
+        let name = alias.map_or("unnest", |alias| alias.name.as_ref());
+        let column = arena.alloc_column(ColumnCatalog::new(
+            name.to_string(),
+            true,
+            ColumnDesc::new(ty, None, false, None)?,
+        ));
+
+        Ok(ScalarExpression::TableFunction(TableFunction {
+            args: vec![array],
+            catalog: TableFunctionCatalog {
+                schema: vec![column],
+                inner,
+            },
+        }))
+    }
+
+    /// The element type of an array argument, `NULL` for a `NULL` literal.
+    fn array_element_type(
+        name: &str,
+        expr: &ScalarExpression,
+        arena: &PlanArena,
+    ) -> Result<LogicalType, DatabaseError> {
+        match expr.return_type(arena).as_ref() {
+            LogicalType::Array(element_ty) => Ok(element_ty.as_ref().clone()),
+            LogicalType::SqlNull => Ok(LogicalType::SqlNull),
+            ty => Err(DatabaseError::InvalidValue(format!(
+                "{name} expects an array, found {ty}"
+            ))),
+        }
+    }
+
+    fn cast_in_place(
+        expr: &mut ScalarExpression,
+        ty: &LogicalType,
+        arena: &PlanArena,
+    ) -> Result<(), DatabaseError> {
+        *expr = ScalarExpression::type_cast(
+            std::mem::replace(expr, ScalarExpression::Empty),
+            Cow::Borrowed(ty),
+            arena,
+        )?;
+        Ok(())
+    }
+
     pub(crate) fn return_type(
         expr_1: &ScalarExpression,
         expr_2: &ScalarExpression,
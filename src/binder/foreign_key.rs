@@ -0,0 +1,286 @@
+// Copyright 2024 KipData/KiteSQL
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::Binder;
+use crate::catalog::{ColumnCatalog, ForeignKey, TableName};
+use crate::errors::DatabaseError;
+use crate::planner::PlanArena;
+use crate::storage::Transaction;
+use crate::types::value::DataValue;
+use crate::types::LogicalType;
+
+/// Names an unnamed `FOREIGN KEY` constraint `{table}_{columns}_fkey`, appending a
+/// counter while the name is taken.
+pub(crate) fn foreign_key_name(
+    table_name: &str,
+    columns: &[String],
+    foreign_keys: &[ForeignKey],
+) -> String {
+    let prefix = format!("{table_name}_{}_fkey", columns.join("_"));
+    let mut name = prefix.clone();
+    let mut counter = 0;
+    while foreign_keys
+        .iter()
+        .any(|foreign_key| foreign_key.name == name)
+    {
+        counter += 1;
+        name = format!("{prefix}{counter}");
+    }
+    name
+}
+
+/// Whether values of `left` can be looked up in an index over `right`: the same type, up
+/// to the declared length of character strings.
+fn same_key_type(left: &LogicalType, right: &LogicalType) -> bool {
+    match (left, right) {
+        (LogicalType::Varchar(_, left_unit), LogicalType::Varchar(_, right_unit)) => {
+            left_unit == right_unit
+        }
+        (left, right) => left == right,
+    }
+}
+
+impl<T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'_, '_, T, A> {
+    /// Resolves the referenced columns of a foreign key declared by the table being
+    /// created: an omitted list means the primary key, and a primary key reference is
+    /// reordered to the primary key order the runtime lookups build keys in.
+    pub(crate) fn bind_foreign_key(
+        &self,
+        table_name: &TableName,
+        columns: &[ColumnCatalog],
+        foreign_key: &mut ForeignKey,
+        arena: &PlanArena,
+    ) -> Result<(), DatabaseError> {
+        for column in &foreign_key.columns {
+            if !columns.iter().any(|catalog| catalog.name() == column) {
+                return Err(DatabaseError::column_not_found(column.clone()));
+            }
+        }
+        let (primary_keys, unique_columns, referenced_types) =
+            if &foreign_key.referenced_table == table_name {
+                let mut primary_keys = columns
+                    .iter()
+                    .filter_map(|column| column.desc().primary().map(|i| (i, column.name())))
+                    .collect::<Vec<_>>();
+                primary_keys.sort_by_key(|(i, _)| *i);
+                (
+                    primary_keys
+                        .into_iter()
+                        .map(|(_, name)| name.to_string())
+                        .collect::<Vec<_>>(),
+                    columns
+                        .iter()
+                        .filter(|column| column.desc().is_unique())
+                        .map(|column| column.name().to_string())
+                        .collect::<Vec<_>>(),
+                    columns
+                        .iter()
+                        .map(|column| (column.name().to_string(), column.datatype().clone()))
+                        .collect::<Vec<_>>(),
+                )
+            } else {
+                let referenced = self
+                    .context
+                    .table(foreign_key.referenced_table.clone())?
+                    .ok_or(DatabaseError::TableNotFound)?;
+                (
+                    referenced
+                        .primary_keys()
+                        .iter()
+                        .map(|(_, column)| arena.column(*column).name().to_string())
+                        .collect(),
+                    referenced
+                        .columns()
+                        .map(|column| arena.column(*column))
+                        .filter(|column| column.desc().is_unique())
+                        .map(|column| column.name().to_string())
+                        .collect(),
+                    referenced
+                        .columns()
+                        .map(|column| arena.column(*column))
+                        .map(|column| (column.name().to_string(), column.datatype().clone()))
+                        .collect(),
+                )
+            };
+        if foreign_key.referenced_columns.is_empty() {
+            foreign_key.referenced_columns = primary_keys.clone();
+        }
+        if foreign_key.columns.len() != foreign_key.referenced_columns.len() {
+            return Err(DatabaseError::invalid_column(format!(
+                "number of referencing and referenced columns of foreign key `{}` disagree",
+                foreign_key.name
+            )));
+        }
+
+        if foreign_key.referenced_columns.len() == primary_keys.len()
+            && primary_keys
+                .iter()
+                .all(|name| foreign_key.referenced_columns.contains(name))
+        {
+            let columns = primary_keys
+                .iter()
+                .map(|name| {
+                    let i = foreign_key
+                        .referenced_columns
+                        .iter()
+                        .position(|column| column == name)
+                        .expect("primary key column is referenced");
+                    foreign_key.columns[i].clone()
+                })
+                .collect();
+            foreign_key.columns = columns;
+            foreign_key.referenced_columns = primary_keys;
+        } else if foreign_key.referenced_columns.len() != 1
+            || !unique_columns.contains(&foreign_key.referenced_columns[0])
+        {
+            return Err(DatabaseError::invalid_column(format!(
+                "foreign key `{}` must reference the primary key or a unique column of `{}`",
+                foreign_key.name, foreign_key.referenced_table
+            )));
+        }
+
+        // the runtime checks probe the referenced index with the referencing values as
+        // they are, so both sides must share a key encoding
+        let column_type = |columns: &[(String, LogicalType)], name: &str| {
+            columns
+                .iter()
+                .find(|(column, _)| column == name)
+                .map(|(_, ty)| ty.clone())
+                .ok_or_else(|| DatabaseError::column_not_found(name.to_string()))
+        };
+        let referencing_types = columns
+            .iter()
+            .map(|column| (column.name().to_string(), column.datatype().clone()))
+            .collect::<Vec<_>>();
+        for (column, referenced_column) in foreign_key
+            .columns
+            .iter()
+            .zip(foreign_key.referenced_columns.iter())
+        {
+            let ty = column_type(&referencing_types, column)?;
+            let referenced_ty = column_type(&referenced_types, referenced_column)?;
+            if !same_key_type(&ty, &referenced_ty) {
+                return Err(DatabaseError::invalid_column(format!(
+                    "foreign key `{}` column `{column}` of type {ty} cannot reference column `{referenced_column}` of type {referenced_ty}",
+                    foreign_key.name
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Rejects dropping or renaming a column that a foreign key of the table, or a
+    /// foreign key referencing the table, is declared over.
+    pub(crate) fn ensure_column_unreferenced(
+        &self,
+        table_name: &TableName,
+        column_name: &str,
+    ) -> Result<(), DatabaseError> {
+        let Some(table) = self.context.table(table_name.clone())? else {
+            return Ok(());
+        };
+        let mut names = table
+            .foreign_keys()
+            .iter()
+            .filter(|foreign_key| foreign_key.columns.iter().any(|name| name == column_name))
+            .map(|foreign_key| foreign_key.name.clone())
+            .collect::<Vec<_>>();
+        for referencing in table.referenced_by() {
+            let Some(referencing) = self.context.table(referencing.clone())? else {
+                continue;
+            };
+            names.extend(
+                referencing
+                    .foreign_keys()
+                    .iter()
+                    .filter(|foreign_key| {
+                        &foreign_key.referenced_table == table_name
+                            && foreign_key
+                                .referenced_columns
+                                .iter()
+                                .any(|name| name == column_name)
+                    })
+                    .map(|foreign_key| foreign_key.name.clone()),
+            );
+        }
+        if let Some(name) = names.first() {
+            return Err(DatabaseError::invalid_column(format!(
+                "column `{column_name}` is referenced by foreign key `{name}`"
+            )));
+        }
+        Ok(())
+    }
+
+    /// Rejects dropping an index that foreign key lookups of the table run through:
+    /// the index over the columns of one of its foreign keys, or the unique index of a
+    /// column another table references.
+    pub(crate) fn ensure_index_unreferenced(
+        &self,
+        table_name: &TableName,
+        index_name: &str,
+        arena: &PlanArena,
+    ) -> Result<(), DatabaseError> {
+        let Some(table) = self.context.table(table_name.clone())? else {
+            return Ok(());
+        };
+        let Some(index) = table
+            .indexes()
+            .map(|index| arena.index(*index))
+            .find(|index| index.name == index_name)
+        else {
+            return Ok(());
+        };
+        if !index.json_path.is_empty() {
+            return Ok(());
+        }
+        let index_columns = index
+            .column_ids
+            .iter()
+            .map(|column_id| {
+                table
+                    .get_column_by_id(column_id)
+                    .map(|column| arena.column(column).name().to_string())
+            })
+            .collect::<Option<Vec<_>>>()
+            .unwrap_or_default();
+
+        let mut names = table
+            .foreign_keys()
+            .iter()
+            .filter(|foreign_key| foreign_key.columns == index_columns)
+            .map(|foreign_key| foreign_key.name.clone())
+            .collect::<Vec<_>>();
+        for referencing in table.referenced_by() {
+            let Some(referencing) = self.context.table(referencing.clone())? else {
+                continue;
+            };
+            names.extend(
+                referencing
+                    .foreign_keys()
+                    .iter()
+                    .filter(|foreign_key| {
+                        &foreign_key.referenced_table == table_name
+                            && foreign_key.referenced_columns == index_columns
+                    })
+                    .map(|foreign_key| foreign_key.name.clone()),
+            );
+        }
+        if let Some(name) = names.first() {
+            return Err(DatabaseError::UnsupportedStmt(format!(
+                "index `{index_name}` is required by foreign key `{name}`"
+            )));
+        }
+        Ok(())
+    }
+}
@@ -12,18 +12,26 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::binder::Binder;
-use crate::catalog::TableName;
+use crate::binder::{Binder, Source};
+use crate::catalog::{ColumnRef, TableName};
 use crate::errors::DatabaseError;
-use crate::planner::operator::insert::InsertOperator;
+use crate::expression::ScalarExpression;
+use crate::planner::operator::insert::{InsertOperator, OnConflict};
 use crate::planner::operator::values::ValuesOperator;
 use crate::planner::operator::Operator;
-use crate::planner::{Childrens, LogicalPlan};
+use crate::planner::{Childrens, LogicalPlan, PlanArena};
 use crate::storage::Transaction;
+use crate::types::index::{IndexId, IndexType};
 use crate::types::tuple::Schema;
 use crate::types::value::DataValue;
+use std::borrow::Cow;
+use std::collections::HashSet;
+
+/// Name of the pseudo table holding the proposed row in `ON CONFLICT DO UPDATE`.
+pub(crate) const EXCLUDED_TABLE: &str = "excluded";
 
 impl<T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'_, '_, T, A> {
+    #[allow(clippy::too_many_arguments)]
     pub(crate) fn bind_insert_values(
         &mut self,
         table_name: TableName,
@@ -31,14 +39,21 @@ impl<T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'_, '_, T, A>
         rows: Vec<Vec<DataValue>>,
         is_overwrite: bool,
         is_mapping_by_name: bool,
+        on_conflict: Option<OnConflict>,
+        returning: Vec<ScalarExpression>,
+        arena: &mut PlanArena,
     ) -> Result<LogicalPlan, DatabaseError> {
         let values_plan = self.bind_values(rows, schema_ref);
+        let checks = self.bind_table_checks(&table_name, arena)?;
 
         Ok(LogicalPlan::new(
             Operator::Insert(InsertOperator {
                 table_name,
                 is_overwrite,
                 is_mapping_by_name,
+                on_conflict,
+                returning,
+                checks,
             }),
             Childrens::Only(Box::new(values_plan)),
         ))
@@ -49,12 +64,20 @@ impl<T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'_, '_, T, A>
         table_name: TableName,
         input_plan: LogicalPlan,
         is_overwrite: bool,
+        on_conflict: Option<OnConflict>,
+        returning: Vec<ScalarExpression>,
+        arena: &mut PlanArena,
     ) -> Result<LogicalPlan, DatabaseError> {
+        let checks = self.bind_table_checks(&table_name, arena)?;
+
         Ok(LogicalPlan::new(
             Operator::Insert(InsertOperator {
                 table_name,
                 is_overwrite,
                 is_mapping_by_name: true,
+                on_conflict,
+                returning,
+                checks,
             }),
             Childrens::Only(Box::new(input_plan)),
         ))
@@ -71,3 +94,123 @@ impl<T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'_, '_, T, A>
         )
     }
 }
+
+impl<'a, T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'a, '_, T, A> {
+    /// Resolves `ON CONFLICT (columns)` to the primary key or unique index on exactly
+    /// those columns. An empty target matches any of them.
+    pub(crate) fn bind_conflict_target(
+        &self,
+        table_name: &TableName,
+        columns: &[String],
+        arena: &PlanArena,
+    ) -> Result<Option<IndexId>, DatabaseError> {
+        if columns.is_empty() {
+            return Ok(None);
+        }
+        let table = self
+            .context
+            .table(table_name.clone())?
+            .ok_or(DatabaseError::TableNotFound)?;
+        let mut column_ids = HashSet::with_capacity(columns.len());
+        for column_name in columns {
+            let column = table
+                .get_column_by_name(column_name)
+                .ok_or_else(|| DatabaseError::column_not_found(column_name.clone()))?;
+            column_ids.extend(arena.column(column).id());
+        }
+        table
+            .indexes()
+            .map(|index_meta| arena.index(*index_meta))
+            .find(|index_meta| {
+                matches!(
+                    index_meta.ty,
+                    IndexType::PrimaryKey { .. } | IndexType::Unique
+                ) && index_meta.json_path.is_empty()
+                    && index_meta.column_ids.len() == column_ids.len()
+                    && index_meta
+                        .column_ids
+                        .iter()
+                        .all(|column_id| column_ids.contains(column_id))
+            })
+            .map(|index_meta| Some(index_meta.id))
+            .ok_or_else(|| DatabaseError::ConflictTargetNotFound(columns.join(", ")))
+    }
+
+    /// Creates a binder scoped to the target table alone, so positions follow the
+    /// table column order `RETURNING` expressions are evaluated against.
+    pub(crate) fn returning_binder(
+        &self,
+        table_name: TableName,
+        alias: Option<&TableName>,
+    ) -> Result<Binder<'a, 'a, T, A>, DatabaseError> {
+        let mut binder = Binder::new(self.context.fork_empty(), self.args, None);
+        binder
+            .context
+            .source_and_bind(table_name, alias, None, true)?
+            .ok_or(DatabaseError::TableNotFound)?;
+
+        Ok(binder)
+    }
+
+    /// Creates a binder scoped to the target table followed by [`EXCLUDED_TABLE`],
+    /// the row layout `ConflictAction::DoUpdate` expressions are evaluated against.
+    pub(crate) fn conflict_binder(
+        &self,
+        table_name: TableName,
+        arena: &mut PlanArena,
+    ) -> Result<Binder<'a, 'a, T, A>, DatabaseError> {
+        let mut binder = Binder::new(self.context.fork_empty(), self.args, None);
+        let schema = binder
+            .context
+            .source_and_bind(table_name, None, None, true)?
+            .ok_or(DatabaseError::TableNotFound)?
+            .schema()
+            .to_vec();
+        let excluded_schema = schema
+            .into_iter()
+            .map(|column| {
+                let mut excluded_column = arena.column(column).clone();
+                let column_id = excluded_column.id().unwrap_or_default();
+                excluded_column.set_ref_table(EXCLUDED_TABLE.into(), column_id, true);
+                arena.alloc_column(excluded_column)
+            })
+            .collect();
+        binder.context.add_bound_source(
+            EXCLUDED_TABLE.into(),
+            None,
+            None,
+            Source::Schema(excluded_schema),
+        );
+        binder.context.allow_default = true;
+
+        Ok(binder)
+    }
+
+    pub(crate) fn bind_conflict_assignment(
+        &self,
+        table_name: &TableName,
+        column_name: &str,
+        expr: ScalarExpression,
+        arena: &PlanArena,
+    ) -> Result<(ColumnRef, ScalarExpression), DatabaseError> {
+        let column = self
+            .context
+            .table(table_name.clone())?
+            .ok_or(DatabaseError::TableNotFound)?
+            .get_column_by_name(column_name)
+            .ok_or_else(|| DatabaseError::column_not_found(column_name.to_string()))?;
+        let column_catalog = arena.column(column);
+        let expr = if matches!(expr, ScalarExpression::Empty) {
+            let default_value = column_catalog
+                .default_value()?
+                .ok_or(DatabaseError::DefaultNotExist)?;
+            ScalarExpression::Constant(default_value)
+        } else {
+            expr
+        };
+        let expr =
+            ScalarExpression::type_cast(expr, Cow::Borrowed(column_catalog.datatype()), arena)?;
+
+        Ok((column, expr))
+    }
+}
@@ -27,29 +27,38 @@ pub(crate) use with_query_bind_step;
 pub mod aggregate;
 mod alter_table;
 mod analyze;
+pub(crate) mod check;
 #[cfg(feature = "copy")]
 pub mod copy;
 mod create_index;
+mod create_sequence;
 mod create_table;
 mod create_view;
+mod decorrelate;
 mod delete;
 mod describe;
 mod distinct;
 mod drop_index;
+mod drop_sequence;
 mod drop_table;
 mod drop_view;
 mod explain;
 pub mod expr;
+pub(crate) mod foreign_key;
 mod insert;
 #[cfg(feature = "parser")]
 mod parser;
 mod select;
 mod show_table;
 mod show_view;
+#[cfg(all(feature = "parser", feature = "time"))]
+pub(crate) mod time_zone;
 mod truncate;
 mod update;
 mod window;
 
+#[cfg(feature = "orm")]
+pub(crate) use insert::EXCLUDED_TABLE;
 #[cfg(feature = "parser")]
 pub use parser::{command_type, prepare, prepare_all, CommandType, Statement};
 #[cfg(feature = "orm")]
@@ -57,10 +66,11 @@ pub use select::{BindPlanFrom, BindPlanSelectList};
 #[cfg(feature = "orm")]
 pub(crate) use select::{JoinConstraintInput, TableAliasInput};
 use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
 
 use crate::catalog::view::View;
 use crate::catalog::{ColumnRef, TableCatalog, TableName};
-use crate::db::{ScalaFunctions, TableFunctions};
+use crate::db::{AggregateFunctions, ScalaFunctions, TableFunctions};
 use crate::errors::DatabaseError;
 use crate::expression::ScalarExpression;
 use crate::planner::operator::join::JoinType;
@@ -91,6 +101,7 @@ pub enum QueryBindStep {
     Where,
     Agg,
     Having,
+    Qualify,
     Window,
     Distinct,
     Sort,
@@ -126,6 +137,13 @@ pub enum Source<'a> {
     Schema(Schema),
 }
 
+/// A query bound from a `WITH` clause that can be referenced by name as a derived source.
+#[derive(Debug)]
+pub(crate) struct CommonTableExpr {
+    pub(crate) plan: LogicalPlan,
+    pub(crate) columns: Vec<String>,
+}
+
 #[derive(Debug, Clone)]
 pub struct BoundSource<'a> {
     pub(crate) table_name: TableName,
@@ -225,6 +243,7 @@ impl UsingColumn {
 pub struct BinderContext<'a, T: Transaction> {
     pub(crate) scala_functions: &'a ScalaFunctions,
     pub(crate) table_functions: &'a TableFunctions,
+    pub(crate) aggregate_functions: &'a AggregateFunctions,
     pub(crate) table_cache: &'a TableCache,
     pub(crate) view_cache: &'a ViewCache,
     pub(crate) transaction: &'a T,
@@ -243,6 +262,8 @@ pub struct BinderContext<'a, T: Transaction> {
     bind_step: QueryBindStep,
     sub_queries: HashMap<QueryBindStep, Vec<SubQueryType>>,
     has_outer_refs: bool,
+    // cte
+    ctes: HashMap<TableName, Arc<CommonTableExpr>>,
 
     pub(crate) allow_default: bool,
 }
@@ -287,10 +308,12 @@ impl<'a, T: Transaction> BinderContext<'a, T> {
         transaction: &'a T,
         scala_functions: &'a ScalaFunctions,
         table_functions: &'a TableFunctions,
+        aggregate_functions: &'a AggregateFunctions,
     ) -> Self {
         BinderContext {
             scala_functions,
             table_functions,
+            aggregate_functions,
             table_cache,
             view_cache,
             transaction,
@@ -303,6 +326,7 @@ impl<'a, T: Transaction> BinderContext<'a, T> {
             bind_step: QueryBindStep::From,
             sub_queries: Default::default(),
             has_outer_refs: false,
+            ctes: Default::default(),
             allow_default: false,
         }
     }
@@ -315,6 +339,7 @@ impl<'a, T: Transaction> BinderContext<'a, T> {
         BinderContext {
             scala_functions: self.scala_functions,
             table_functions: self.table_functions,
+            aggregate_functions: self.aggregate_functions,
             table_cache: self.table_cache,
             view_cache: self.view_cache,
             transaction: self.transaction,
@@ -327,6 +352,7 @@ impl<'a, T: Transaction> BinderContext<'a, T> {
             bind_step: self.bind_step,
             sub_queries: Default::default(),
             has_outer_refs: false,
+            ctes: self.ctes.clone(),
             allow_default: self.allow_default,
         }
     }
@@ -336,13 +362,24 @@ impl<'a, T: Transaction> BinderContext<'a, T> {
     /// This is used while binding an independent input, such as the right side
     /// of a join, before merging its newly bound sources into the parent scope.
     pub(crate) fn fork_empty(&self) -> Self {
-        BinderContext::new(
+        let mut context = BinderContext::new(
             self.table_cache,
             self.view_cache,
             self.transaction,
             self.scala_functions,
             self.table_functions,
-        )
+            self.aggregate_functions,
+        );
+        context.ctes = self.ctes.clone();
+        context
+    }
+
+    pub(crate) fn add_cte(&mut self, name: TableName, cte: CommonTableExpr) {
+        self.ctes.insert(name, Arc::new(cte));
+    }
+
+    pub(crate) fn cte(&self, name: &TableName) -> Option<Arc<CommonTableExpr>> {
+        self.ctes.get(name).cloned()
     }
 
     pub fn step(&mut self, bind_step: QueryBindStep) {
@@ -368,6 +405,16 @@ impl<'a, T: Transaction> BinderContext<'a, T> {
         self.sub_queries.remove(&self.bind_step)
     }
 
+    pub fn has_correlated_sub_query(&self, bind_step: QueryBindStep) -> bool {
+        self.sub_queries.get(&bind_step).is_some_and(|sub_queries| {
+            sub_queries.iter().any(|sub_query| match sub_query {
+                SubQueryType::SubQuery { correlated, .. }
+                | SubQueryType::ExistsSubQuery { correlated, .. }
+                | SubQueryType::QuantifiedSubQuery { correlated, .. } => *correlated,
+            })
+        })
+    }
+
     pub fn mark_outer_ref(&mut self) {
         self.has_outer_refs = true;
     }
@@ -391,6 +438,7 @@ impl<'a, T: Transaction> BinderContext<'a, T> {
                 self.view_cache,
                 self.scala_functions,
                 self.table_functions,
+                self.aggregate_functions,
                 real_name.clone(),
             )
         } else {
@@ -399,6 +447,7 @@ impl<'a, T: Transaction> BinderContext<'a, T> {
                 self.view_cache,
                 self.scala_functions,
                 self.table_functions,
+                self.aggregate_functions,
                 view_name.clone(),
             )
         }
@@ -428,6 +477,7 @@ impl<'a, T: Transaction> BinderContext<'a, T> {
                     self.view_cache,
                     self.scala_functions,
                     self.table_functions,
+                    self.aggregate_functions,
                     real_name.clone(),
                 )
             } else {
@@ -436,6 +486,7 @@ impl<'a, T: Transaction> BinderContext<'a, T> {
                     self.view_cache,
                     self.scala_functions,
                     self.table_functions,
+                    self.aggregate_functions,
                     table_name.clone(),
                 )
             }?
@@ -467,6 +518,7 @@ impl<'a, T: Transaction> BinderContext<'a, T> {
                     self.view_cache,
                     self.scala_functions,
                     self.table_functions,
+                    self.aggregate_functions,
                     real_name.clone(),
                 )
             } else {
@@ -475,6 +527,7 @@ impl<'a, T: Transaction> BinderContext<'a, T> {
                     self.view_cache,
                     self.scala_functions,
                     self.table_functions,
+                    self.aggregate_functions,
                     table_name.clone(),
                 )
             }?
@@ -563,8 +616,14 @@ pub struct Binder<'a, 'parent, T: Transaction, A: AsRef<[(&'static str, DataValu
     pub(crate) args: &'a A,
     pub(crate) force_spill: bool,
     pub(crate) force_nested_loop: bool,
+    pub(crate) force_merge_join: bool,
+    // `WINDOW` clause of the SELECT being bound, with inherited windows already merged
+    #[cfg(feature = "parser")]
+    named_windows: HashMap<String, parser::WindowSpec>,
     with_pk: Option<TableName>,
     pub(crate) parent: Option<&'parent BinderContext<'a, T>>,
+    // the scopes enclosing `parent`, only searched to reject references that skip a query level
+    pub(crate) ancestors: Vec<&'parent BinderContext<'a, T>>,
 }
 
 impl<'a, 'parent, T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'a, 'parent, T, A> {
@@ -578,8 +637,12 @@ impl<'a, 'parent, T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<
             args,
             force_spill: false,
             force_nested_loop: false,
+            force_merge_join: false,
+            #[cfg(feature = "parser")]
+            named_windows: HashMap::new(),
             with_pk: None,
             parent,
+            ancestors: Vec::new(),
         }
     }
 
@@ -656,6 +719,7 @@ pub mod test {
         ) -> Result<LogicalPlan, DatabaseError> {
             let scala_functions = Default::default();
             let table_functions = Default::default();
+            let aggregate_functions = Default::default();
             let transaction = self.storage.transaction()?;
             let mut binder = Binder::new(
                 BinderContext::new(
@@ -664,6 +728,7 @@ pub mod test {
                     &transaction,
                     &scala_functions,
                     &table_functions,
+                    &aggregate_functions,
                 ),
                 &[],
                 None,
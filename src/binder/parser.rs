@@ -12,49 +12,78 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use super::check::check_constraint_name;
+use super::foreign_key::foreign_key_name;
 use super::select::{
     BindPlanAggregated, BindPlanComplete, BindPlanDistinct, BindPlanFiltered, BindPlanFrom,
     BindPlanProjected, BindPlanSelectList, BindPlanStart, JoinConstraintInput, TableAliasInput,
 };
-use super::{is_valid_identifier, with_query_bind_step, Binder, QueryBindStep, SetOperatorKind};
+use super::{
+    is_valid_identifier, with_query_bind_step, Binder, CommonTableExpr, QueryBindStep,
+    SetOperatorKind,
+};
 #[cfg(feature = "copy")]
 use crate::binder::copy::{ExtSource, FileFormat};
-use crate::catalog::{ColumnCatalog, ColumnDesc, ColumnRef, TableName};
+#[cfg(feature = "time")]
+use crate::binder::time_zone::{is_time_zone_set, is_time_zone_variable, time_zone_assignment};
+use crate::catalog::sequence::{identity_sequence_name, Sequence};
+use crate::catalog::{
+    CheckConstraint, ColumnCatalog, ColumnDesc, ColumnIdentity, ColumnRef, ForeignKey,
+    ReferentialAction, TableName,
+};
 use crate::db::{BindSource, DBTransaction, Database, DatabaseIter, TransactionIter};
 use crate::errors::{DatabaseError, SqlErrorSpan};
 use crate::expression;
 use crate::expression::agg::AggKind;
 use crate::expression::simplify::ConstantCalculator;
 use crate::expression::visitor_mut::ExprVisitorMut;
-use crate::expression::window::WindowFunctionKind;
+use crate::expression::window::{
+    WindowFrame, WindowFrameBound, WindowFrameUnits, WindowFunctionKind,
+};
 use crate::expression::{AliasType, ScalarExpression};
+use crate::function::json_path::split_json_path;
 use crate::iter_ext::Itertools;
 use crate::parser::parse_sql;
+use crate::planner::explain::ExplainNode;
 use crate::planner::operator::alter_table::change_column::{DefaultChange, NotNullChange};
+use crate::planner::operator::explain::{ExplainFormat, ExplainOperator};
+use crate::planner::operator::insert::{ConflictAction, OnConflict};
 use crate::planner::operator::join::{JoinCondition, JoinOperator as LJoinOperator, JoinType};
+use crate::planner::operator::lateral_apply::LateralApplyOperator;
 use crate::planner::operator::mark_apply::MarkApplyQuantifier;
 use crate::planner::operator::project::ProjectOperator;
 use crate::planner::operator::sort::SortField;
 use crate::planner::operator::Operator;
 use crate::planner::{Childrens, LogicalPlan, PlanArena};
 use crate::storage::{Storage, Transaction};
+use crate::types::interval::Interval;
+use crate::types::json::parse_text_path;
 use crate::types::value::{DataValue, Utf8Type};
 use crate::types::{CharLengthUnits, ColumnId, LogicalType};
 pub(super) use sqlparser::ast::{
-    AlterColumnOperation, AlterTableOperation, Assignment, AssignmentTarget, BinaryOperator,
-    ColumnDef, ColumnOption, CreateView, DataType, DescribeAlias, Distinct, DuplicateTreatment,
-    Expr, FromTable, Function, FunctionArg, FunctionArgExpr, FunctionArguments, GroupByExpr, Ident,
-    IndexColumn, Join, JoinConstraint, JoinOperator, LimitClause, ObjectName, ObjectNamePart,
-    ObjectType, OrderByExpr, OrderByKind, Query, Select, SelectInto, SelectItem,
-    SelectItemQualifiedWildcardKind, SetExpr, SetOperator, SetQuantifier, Spanned, TableAlias,
-    TableConstraint, TableFactor, TableObject, TableWithJoins, TypedString, UnaryOperator, Value,
-    WindowType,
+    AccessExpr, AlterColumnOperation, AlterTableOperation, AnalyzeFormat, AnalyzeFormatKind,
+    ArrayElemTypeDef, Assignment, AssignmentTarget, BinaryOperator, CeilFloorKind, ColumnDef,
+    ColumnOption, ConflictTarget, ConstraintReferenceMatchKind, CreateView, DataType,
+    DateTimeField, DescribeAlias, Distinct, DoUpdate, DuplicateTreatment, Expr,
+    ForeignKeyConstraint, FromTable, Function, FunctionArg, FunctionArgExpr,
+    FunctionArgumentClause, FunctionArguments, GeneratedAs, GroupByExpr, GroupByWithModifier,
+    Ident, IndexColumn, Join, JoinConstraint, JoinOperator, LimitClause, NamedWindowDefinition,
+    NamedWindowExpr, NullTreatment, ObjectName, ObjectNamePart, ObjectType, OnConflictAction,
+    OnInsert, OrderByExpr, OrderByKind, Query, ReferentialAction as SqlReferentialAction, Select,
+    SelectInto, SelectItem, SelectItemQualifiedWildcardKind, SequenceOptions, SetExpr, SetOperator,
+    SetQuantifier, Spanned, Subscript, TableAlias, TableConstraint, TableFactor, TableObject,
+    TableWithJoins, TypedString, UnaryOperator, UtilityOption, Value,
+    WindowFrame as SqlWindowFrame, WindowFrameBound as SqlWindowFrameBound,
+    WindowFrameUnits as SqlWindowFrameUnits, WindowSpec, WindowType, With,
 };
 #[cfg(feature = "copy")]
 pub(super) use sqlparser::ast::{CopyOption, CopySource, CopyTarget};
-use sqlparser::tokenizer::Span;
+use sqlparser::keywords::Keyword;
+use sqlparser::tokenizer::{Span, Token};
 use std::borrow::{Borrow, Cow};
 use std::cmp;
+use std::collections::HashMap;
+use std::mem;
 use std::slice;
 
 /// Parsed SQL statement type used by KiteSQL SQL frontend APIs.
@@ -66,6 +95,8 @@ pub enum CommandType {
     DML,
     DDL,
     Analyze,
+    /// Session settings such as `SET TIME ZONE`.
+    Set,
 }
 
 pub(crate) trait AttachSpanSource {
@@ -118,13 +149,38 @@ pub fn command_type(stmt: &Statement) -> Result<CommandType, DatabaseError> {
         Statement::CreateTable(_)
         | Statement::CreateIndex(_)
         | Statement::CreateView(_)
+        | Statement::CreateSequence { .. }
         | Statement::AlterTable(_)
         | Statement::Drop { .. } => Ok(CommandType::DDL),
+        // `EXPLAIN ANALYZE` runs the statement, so it is classified like the statement itself
+        Statement::Explain {
+            analyze,
+            format,
+            options,
+            statement,
+            ..
+        } => {
+            let explain = explain_operator(*analyze, format.as_ref(), options.as_deref())?;
+            if !explain.analyze {
+                return Ok(CommandType::DQL);
+            }
+            match command_type(statement)? {
+                command_type @ (CommandType::DQL | CommandType::DML) => Ok(command_type),
+                _ => Err(DatabaseError::UnsupportedStmt(
+                    "EXPLAIN ANALYZE only supports queries and DML statements".to_string(),
+                )),
+            }
+        }
         Statement::Query(_)
-        | Statement::Explain { .. }
         | Statement::ExplainTable { .. }
         | Statement::ShowTables { .. }
         | Statement::ShowViews { .. } => Ok(CommandType::DQL),
+        #[cfg(feature = "time")]
+        Statement::ShowVariable { variable } if is_time_zone_variable(variable) => {
+            Ok(CommandType::DQL)
+        }
+        #[cfg(feature = "time")]
+        Statement::Set(set) if is_time_zone_set(set) => Ok(CommandType::Set),
         Statement::Analyze(_) => Ok(CommandType::Analyze),
         Statement::Truncate(_)
         | Statement::Update(_)
@@ -136,6 +192,53 @@ pub fn command_type(stmt: &Statement) -> Result<CommandType, DatabaseError> {
     }
 }
 
+/// Resolves `EXPLAIN [ANALYZE] [FORMAT {TEXT | JSON}]` and its parenthesized form
+/// `EXPLAIN (ANALYZE [boolean], FORMAT {TEXT | JSON})`.
+fn explain_operator(
+    analyze: bool,
+    format: Option<&AnalyzeFormatKind>,
+    options: Option<&[UtilityOption]>,
+) -> Result<ExplainOperator, DatabaseError> {
+    let unsupported = |option: String| {
+        DatabaseError::UnsupportedStmt(format!("EXPLAIN option {option} is not supported"))
+    };
+    let mut explain = ExplainOperator {
+        analyze,
+        format: ExplainFormat::Text,
+    };
+
+    if let Some(AnalyzeFormatKind::Keyword(format) | AnalyzeFormatKind::Assignment(format)) = format
+    {
+        explain.format = match format {
+            AnalyzeFormat::TEXT => ExplainFormat::Text,
+            AnalyzeFormat::JSON => ExplainFormat::Json,
+            format => return Err(unsupported(format!("FORMAT {format}"))),
+        };
+    }
+    for option in options.unwrap_or_default() {
+        match (
+            option.name.value.to_uppercase().as_str(),
+            option.arg.as_ref(),
+        ) {
+            ("ANALYZE", None) => explain.analyze = true,
+            ("ANALYZE", Some(Expr::Value(value))) => match &value.value {
+                Value::Boolean(analyze) => explain.analyze = *analyze,
+                _ => return Err(unsupported(option.to_string())),
+            },
+            ("FORMAT", Some(Expr::Identifier(ident))) => {
+                explain.format = match ident.value.to_uppercase().as_str() {
+                    "TEXT" => ExplainFormat::Text,
+                    "JSON" => ExplainFormat::Json,
+                    _ => return Err(unsupported(option.to_string())),
+                }
+            }
+            _ => return Err(unsupported(option.to_string())),
+        }
+    }
+
+    Ok(explain)
+}
+
 /// Parses a single SQL statement into a reusable [`Statement`].
 pub fn prepare<T: AsRef<str>>(sql: T) -> Result<Statement, DatabaseError> {
     let mut stmts = prepare_all(sql)?;
@@ -174,6 +277,12 @@ impl<S: Storage> Database<S> {
                 "DDL and ANALYZE require `Database::ddl` or `Database::analyze`".to_string(),
             ));
         }
+        #[cfg(feature = "time")]
+        if let Some(time_zone) =
+            time_zone_assignment(statement.borrow(), self.state.default_time_zone(), false)?
+        {
+            self.set_time_zone(time_zone);
+        }
         BindSource::execute(self, params, |binder, arena| {
             binder.bind(statement.borrow(), arena)
         })
@@ -239,6 +348,18 @@ impl<S: Storage> Database<S> {
         let mut statements = statements.into_iter().peekable();
 
         while let Some(statement) = statements.next() {
+            #[cfg(feature = "time")]
+            let _scope = {
+                match time_zone_assignment(&statement, self.state.default_time_zone(), false) {
+                    Ok(Some(time_zone)) => self.set_time_zone(time_zone),
+                    Ok(None) => (),
+                    Err(err) => {
+                        unsafe { drop(Box::from_raw(transaction)) };
+                        return Err(err.with_sql_context(sql));
+                    }
+                }
+                self.time_zone().enter()
+            };
             let (schema, plan_arena, executor) =
                 match self
                     .state
@@ -273,6 +394,27 @@ impl<S: Storage> Database<S> {
         unsafe { drop(Box::from_raw(transaction)) };
         Err(DatabaseError::EmptyStatement.with_sql_context(sql))
     }
+
+    /// Plans a SQL statement without running it and returns the chosen plan as an
+    /// [`ExplainNode`] tree, the same plan `EXPLAIN (FORMAT JSON)` reports.
+    pub fn explain_plan<T: AsRef<str>>(&self, sql: T) -> Result<ExplainNode, DatabaseError> {
+        let sql = sql.as_ref();
+        let statement = prepare(sql).map_err(|err| err.with_sql_context(sql))?;
+        #[cfg(feature = "time")]
+        let _scope = self.time_zone().enter();
+        let mut transaction = self
+            .storage
+            .transaction_with_isolation(self.transaction_isolation)?;
+        transaction.begin_statement_scope()?;
+        let (plan, mut arena) = self
+            .state
+            .build_plan(&[], &transaction, |binder, arena| {
+                binder.bind(&statement, arena)
+            })
+            .map_err(|err| err.with_sql_context(sql))?;
+
+        self.state.explain_node(plan, &mut arena)
+    }
 }
 
 impl<'txn, S: Storage> DBTransaction<'txn, S> {
@@ -294,6 +436,12 @@ impl<'txn, S: Storage> DBTransaction<'txn, S> {
                 "`DDL` and `ANALYZE` are not allowed to execute within a transaction".to_string(),
             ));
         }
+        #[cfg(feature = "time")]
+        if let Some(time_zone) =
+            time_zone_assignment(statement.borrow(), self.state.default_time_zone(), true)?
+        {
+            self.time_zone = time_zone;
+        }
         BindSource::execute(self, params, |binder, arena| {
             binder.bind(statement.borrow(), arena)
         })
@@ -320,6 +468,23 @@ impl<'txn, S: Storage> DBTransaction<'txn, S> {
         self.execute(&last_statement, &[])
             .map_err(|err| err.with_sql_context(sql))
     }
+
+    /// Plans a SQL statement inside the current transaction without running it, see
+    /// [`Database::explain_plan`].
+    pub fn explain_plan<T: AsRef<str>>(&mut self, sql: T) -> Result<ExplainNode, DatabaseError> {
+        let sql = sql.as_ref();
+        let statement = prepare(sql).map_err(|err| err.with_sql_context(sql))?;
+        #[cfg(feature = "time")]
+        let _scope = self.time_zone.enter();
+        self.inner.begin_statement_scope()?;
+        let plan = self.state.build_plan(&[], &self.inner, |binder, arena| {
+            binder.bind(&statement, arena)
+        });
+        self.inner.end_statement_scope()?;
+        let (plan, mut arena) = plan.map_err(|err| err.with_sql_context(sql))?;
+
+        self.state.explain_node(plan, &mut arena)
+    }
 }
 
 struct BindStatementStart<'s, 'a, 'b, 'arena, T, A>
@@ -386,6 +551,12 @@ where
                 Statement::Truncate(truncate) => self.truncate(truncate.clone())?,
                 Statement::ShowTables { .. } => self.binder.bind_show_tables()?,
                 Statement::ShowViews { .. } => self.binder.bind_show_views()?,
+                #[cfg(feature = "time")]
+                Statement::ShowVariable { variable } if is_time_zone_variable(variable) => {
+                    self.binder.bind_show_time_zone(self.arena)?
+                }
+                #[cfg(feature = "time")]
+                Statement::Set(set) if is_time_zone_set(set) => self.binder.bind_set_time_zone()?,
                 #[cfg(feature = "copy")]
                 Statement::Copy {
                     source,
@@ -400,7 +571,16 @@ where
                         "COPY requires the `copy` feature".to_string(),
                     ))
                 }
-                Statement::Explain { statement, .. } => self.explain(statement)?,
+                Statement::Explain {
+                    statement,
+                    analyze,
+                    format,
+                    options,
+                    ..
+                } => self.explain(
+                    statement,
+                    explain_operator(*analyze, format.as_ref(), options.as_deref())?,
+                )?,
                 Statement::ExplainTable {
                     describe_alias: DescribeAlias::Describe | DescribeAlias::Desc,
                     table_name,
@@ -410,6 +590,22 @@ where
                     .bind_describe(sql_table_name(table_name.clone())?)?,
                 Statement::CreateIndex(create) => self.create_index(create.clone())?,
                 Statement::CreateView(create) => self.create_view(create.clone())?,
+                Statement::CreateSequence {
+                    temporary,
+                    if_not_exists,
+                    name,
+                    data_type,
+                    sequence_options,
+                    owned_by,
+                } => {
+                    if *temporary || owned_by.is_some() {
+                        return Err(DatabaseError::UnsupportedStmt(
+                            "`CREATE SEQUENCE` does not support `TEMPORARY` or `OWNED BY`"
+                                .to_string(),
+                        ));
+                    }
+                    self.create_sequence(name, data_type.clone(), sequence_options, *if_not_exists)?
+                }
                 _ => return Err(DatabaseError::UnsupportedStmt(stmt.to_string())),
             };
 
@@ -446,7 +642,41 @@ where
                 ..
             } => {
                 let column_span = column_def.name.span;
-                let column = self.bind_column(column_def, None)?;
+                let mut checks = Vec::new();
+                let mut foreign_keys = Vec::new();
+                let mut sequences = Vec::new();
+                let column = self.bind_column(
+                    column_def,
+                    None,
+                    &table_name,
+                    &mut checks,
+                    &mut foreign_keys,
+                    &mut sequences,
+                )?;
+                if !checks.is_empty() {
+                    return Err(DatabaseError::UnsupportedStmt(
+                        "`ADD COLUMN` does not support `CHECK`, use `ADD CONSTRAINT`".to_string(),
+                    ));
+                }
+                if !foreign_keys.is_empty() {
+                    return Err(DatabaseError::UnsupportedStmt(
+                        "`ADD COLUMN` does not support `REFERENCES`".to_string(),
+                    ));
+                }
+                if column.desc().identity().is_some()
+                    || column
+                        .desc()
+                        .default
+                        .as_ref()
+                        .map(ScalarExpression::has_sequence_call)
+                        .transpose()?
+                        .unwrap_or(false)
+                {
+                    return Err(DatabaseError::UnsupportedStmt(
+                        "`ADD COLUMN` does not support identity columns or sequence defaults"
+                            .to_string(),
+                    ));
+                }
 
                 if !is_valid_identifier(column.name()) {
                     return Err(attach_span_if_absent(
@@ -471,8 +701,26 @@ where
                 let column_name = column_names[0].value.clone();
 
                 self.binder
-                    .bind_drop_column(table_name, column_name, if_exists)
+                    .bind_drop_column(table_name, column_name, if_exists, self.arena)
             }
+            AlterTableOperation::AddConstraint { constraint, .. } => match constraint {
+                TableConstraint::Check(check) => self.binder.bind_add_constraint(
+                    table_name,
+                    check.name.map(|name| lower_ident(&name).into_owned()),
+                    check.expr.to_string(),
+                    self.arena,
+                ),
+                constraint => Err(DatabaseError::UnsupportedStmt(format!(
+                    "`ADD CONSTRAINT` only supports `CHECK`: {constraint}"
+                ))),
+            },
+            AlterTableOperation::DropConstraint {
+                if_exists, name, ..
+            } => self.binder.bind_drop_constraint(
+                table_name,
+                lower_ident(&name).into_owned(),
+                if_exists,
+            ),
             AlterTableOperation::RenameColumn {
                 old_column_name,
                 new_column_name,
@@ -506,6 +754,7 @@ where
                     data_type,
                     DefaultChange::NoChange,
                     NotNullChange::NoChange,
+                    self.arena,
                 )
             }
             AlterTableOperation::AlterColumn { column_name, op } => {
@@ -573,6 +822,7 @@ where
                     data_type,
                     default_change,
                     not_null_change,
+                    self.arena,
                 )
             }
             AlterTableOperation::ModifyColumn {
@@ -611,6 +861,7 @@ where
                     data_type,
                     default_change,
                     not_null_change,
+                    self.arena,
                 )
             }
             AlterTableOperation::ChangeColumn {
@@ -656,6 +907,7 @@ where
                     data_type,
                     default_change,
                     not_null_change,
+                    self.arena,
                 )
             }
             op => Err(DatabaseError::UnsupportedStmt(format!(
@@ -731,8 +983,18 @@ where
         }
 
         let mut columns = Vec::with_capacity(create.columns.len());
+        let mut checks = Vec::new();
+        let mut foreign_keys = Vec::new();
+        let mut sequences = Vec::new();
         for (i, column) in create.columns.into_iter().enumerate() {
-            columns.push(self.bind_column(column, Some(i))?);
+            columns.push(self.bind_column(
+                column,
+                Some(i),
+                &table_name,
+                &mut checks,
+                &mut foreign_keys,
+                &mut sequences,
+            )?);
         }
         for constraint in create.constraints {
             match constraint {
@@ -746,6 +1008,31 @@ where
                         desc.set_unique()
                     })?;
                 }
+                TableConstraint::Check(check) => {
+                    let name = check
+                        .name
+                        .map(|name| lower_ident(&name).into_owned())
+                        .unwrap_or_else(|| check_constraint_name(&table_name, None, &checks));
+                    checks.push(CheckConstraint {
+                        name,
+                        expr: check.expr.to_string(),
+                    });
+                }
+                TableConstraint::ForeignKey(foreign_key) => {
+                    let columns = foreign_key
+                        .columns
+                        .iter()
+                        .map(|column| lower_ident(column).into_owned())
+                        .collect();
+                    let name = foreign_key.name.clone();
+                    foreign_keys.push(sql_foreign_key(
+                        &table_name,
+                        name,
+                        columns,
+                        foreign_key,
+                        &foreign_keys,
+                    )?);
+                }
                 constraint => {
                     return Err(DatabaseError::UnsupportedStmt(format!(
                         "`CreateTable` does not currently support this constraint: {constraint:?}"
@@ -754,14 +1041,25 @@ where
             }
         }
 
-        self.binder
-            .bind_create_table(table_name, columns, create.if_not_exists)
+        self.binder.bind_create_table(
+            table_name,
+            columns,
+            checks,
+            foreign_keys,
+            sequences,
+            create.if_not_exists,
+            self.arena,
+        )
     }
 
     fn bind_column(
         &mut self,
         column_def: ColumnDef,
         column_index: Option<usize>,
+        table_name: &str,
+        checks: &mut Vec<CheckConstraint>,
+        foreign_keys: &mut Vec<ForeignKey>,
+        sequences: &mut Vec<Sequence>,
     ) -> Result<ColumnCatalog, DatabaseError> {
         let column_name = lower_ident(&column_def.name).into_owned();
         let mut column_desc = ColumnDesc::new(
@@ -771,6 +1069,7 @@ where
             None,
         )?;
         let mut nullable = true;
+        let mut is_primary = false;
 
         for option_def in column_def.options {
             match option_def.option {
@@ -778,8 +1077,31 @@ where
                 ColumnOption::NotNull => nullable = false,
                 ColumnOption::PrimaryKey(_) => {
                     column_desc.set_primary(column_index);
-                    nullable = false;
-                    break;
+                    is_primary = true;
+                }
+                ColumnOption::Check(check) => {
+                    let name = option_def
+                        .name
+                        .or(check.name)
+                        .map(|name| lower_ident(&name).into_owned())
+                        .unwrap_or_else(|| {
+                            check_constraint_name(table_name, Some(&column_name), checks)
+                        });
+                    checks.push(CheckConstraint {
+                        name,
+                        expr: check.expr.to_string(),
+                    });
+                }
+                ColumnOption::ForeignKey(foreign_key) => {
+                    let name = option_def.name.or(foreign_key.name.clone());
+                    let foreign_key = sql_foreign_key(
+                        table_name,
+                        name,
+                        vec![column_name.clone()],
+                        foreign_key,
+                        foreign_keys,
+                    )?;
+                    foreign_keys.push(foreign_key);
                 }
                 ColumnOption::Unique(_) => column_desc.set_unique(),
                 ColumnOption::Default(expr) => {
@@ -797,6 +1119,39 @@ where
                     )?;
                     column_desc.default = Some(expr);
                 }
+                ColumnOption::Generated {
+                    generated_as,
+                    sequence_options,
+                    generation_expr: None,
+                    ..
+                } => {
+                    let identity = match generated_as {
+                        GeneratedAs::Always => ColumnIdentity::Always,
+                        GeneratedAs::ByDefault => ColumnIdentity::ByDefault,
+                        GeneratedAs::ExpStored => {
+                            return Err(DatabaseError::UnsupportedStmt(
+                                "generated columns are not supported".to_string(),
+                            ))
+                        }
+                    };
+                    if let Some(options) = sequence_options.filter(|options| !options.is_empty()) {
+                        sequences.push(self.bind_sequence(
+                            identity_sequence_name(table_name, &column_name),
+                            &column_desc.column_datatype,
+                            &options,
+                        )?);
+                    }
+                    column_desc.set_identity(identity);
+                }
+                ColumnOption::DialectSpecific(tokens)
+                    if matches!(
+                        tokens.as_slice(),
+                        [Token::Word(word)]
+                            if matches!(word.keyword, Keyword::AUTO_INCREMENT | Keyword::AUTOINCREMENT)
+                    ) =>
+                {
+                    column_desc.set_identity(ColumnIdentity::ByDefault);
+                }
                 option => {
                     return Err(DatabaseError::UnsupportedStmt(format!(
                         "`Column` does not currently support this option: {option:?}"
@@ -805,6 +1160,10 @@ where
             }
         }
 
+        if is_primary {
+            nullable = false;
+        }
+
         Ok(ColumnCatalog::new(column_name, nullable, column_desc))
     }
 
@@ -845,6 +1204,8 @@ where
             .binder
             .bind_create_index_source(table_name.clone(), self.arena)?;
         let mut columns = Vec::with_capacity(create.columns.len());
+        let mut json_path = Vec::new();
+        let is_single = create.columns.len() == 1;
 
         for index_column in create.columns {
             match self
@@ -852,11 +1213,23 @@ where
                 .bind_expr(&index_column.column.expr, self.arena)?
             {
                 ScalarExpression::ColumnRef { column, .. } => columns.push(column),
-                expr => {
-                    return Err(DatabaseError::UnsupportedStmt(format!(
-                        "'CREATE INDEX' by {expr}"
-                    )))
-                }
+                // a JSON path such as `(doc ->> 'name')`, alone in its index
+                expr => match split_json_path(&expr) {
+                    Some((column, path)) if is_single => {
+                        columns.push(column);
+                        json_path = path;
+                    }
+                    Some(_) => {
+                        return Err(DatabaseError::UnsupportedStmt(
+                            "an index on a JSON path can not have other columns".to_string(),
+                        ))
+                    }
+                    None => {
+                        return Err(DatabaseError::UnsupportedStmt(format!(
+                            "'CREATE INDEX' by {expr}"
+                        )))
+                    }
+                },
             }
         }
 
@@ -864,6 +1237,7 @@ where
             table_name,
             index_name,
             columns,
+            json_path,
             create.if_not_exists,
             create.unique,
             input,
@@ -896,6 +1270,89 @@ where
         )
     }
 
+    fn create_sequence(
+        mut self,
+        name: &ObjectName,
+        data_type: Option<DataType>,
+        options: &[SequenceOptions],
+        if_not_exists: bool,
+    ) -> Result<LogicalPlan, DatabaseError> {
+        let sequence_name = lower_case_name(name)?.into_owned();
+        if !is_valid_identifier(&sequence_name) {
+            return Err(attach_span_if_absent(
+                DatabaseError::InvalidValue("illegal sequence naming".to_string()),
+                name,
+            ));
+        }
+        let ty = data_type
+            .map(LogicalType::try_from)
+            .transpose()?
+            .unwrap_or(LogicalType::Bigint);
+        let sequence = self.bind_sequence(sequence_name, &ty, options)?;
+
+        self.binder.bind_create_sequence(sequence, if_not_exists)
+    }
+
+    /// Builds a sequence generating `ty` from `CREATE SEQUENCE` or identity column options.
+    fn bind_sequence(
+        &mut self,
+        name: String,
+        ty: &LogicalType,
+        options: &[SequenceOptions],
+    ) -> Result<Sequence, DatabaseError> {
+        let (mut increment, mut min_value, mut max_value, mut start, mut cache) =
+            (None, None, None, None, None);
+        for option in options {
+            match option {
+                SequenceOptions::IncrementBy(expr, _) => {
+                    increment = Some(self.bind_sequence_option(expr)?)
+                }
+                SequenceOptions::MinValue(expr) => {
+                    min_value = expr
+                        .as_ref()
+                        .map(|expr| self.bind_sequence_option(expr))
+                        .transpose()?
+                }
+                SequenceOptions::MaxValue(expr) => {
+                    max_value = expr
+                        .as_ref()
+                        .map(|expr| self.bind_sequence_option(expr))
+                        .transpose()?
+                }
+                SequenceOptions::StartWith(expr, _) => {
+                    start = Some(self.bind_sequence_option(expr)?)
+                }
+                SequenceOptions::Cache(expr) => cache = Some(self.bind_sequence_option(expr)?),
+                // `NO CYCLE`
+                SequenceOptions::Cycle(true) => (),
+                SequenceOptions::Cycle(false) => {
+                    return Err(DatabaseError::UnsupportedStmt(
+                        "sequences do not support `CYCLE`".to_string(),
+                    ))
+                }
+            }
+        }
+
+        Sequence::new(name, ty, increment, min_value, max_value, start, cache)
+    }
+
+    fn bind_sequence_option(&mut self, expr: &Expr) -> Result<i64, DatabaseError> {
+        let mut bound = self.binder.bind_expr(expr, self.arena)?;
+        ConstantCalculator::new(self.arena).visit(&mut bound)?;
+        match bound {
+            ScalarExpression::Constant(value) if !value.is_null() => {
+                match value.cast(&LogicalType::Bigint)? {
+                    DataValue::Int64(value) => Ok(value),
+                    _ => Err(DatabaseError::InvalidType),
+                }
+            }
+            _ => Err(attach_span_if_absent(
+                DatabaseError::InvalidValue(format!("sequence option `{expr}` must be a constant")),
+                expr,
+            )),
+        }
+    }
+
     fn drop_object(
         self,
         object_type: ObjectType,
@@ -918,10 +1375,14 @@ where
             ObjectType::Index => {
                 let (table_name, index_name) = sql_index_name(names.remove(0))?;
                 self.binder
-                    .bind_drop_index(table_name, index_name, if_exists)
+                    .bind_drop_index(table_name, index_name, if_exists, self.arena)
             }
+            ObjectType::Sequence => self
+                .binder
+                .bind_drop_sequence(lower_case_name(&names[0])?.into_owned(), if_exists),
             _ => Err(DatabaseError::UnsupportedStmt(
-                "only `Table` and `View` are allowed to be Dropped".to_string(),
+                "only `Table`, `View`, `Index` and `Sequence` are allowed to be Dropped"
+                    .to_string(),
             )),
         }
     }
@@ -959,6 +1420,9 @@ where
             columns,
             overwrite,
             source,
+            on,
+            returning,
+            table_alias,
             ..
         } = insert;
         let table_name = match table {
@@ -973,13 +1437,124 @@ where
         let source = source.as_ref().ok_or_else(|| {
             DatabaseError::UnsupportedStmt("insert without source is not supported".to_string())
         })?;
+        let alias = table_alias.as_ref().map(|alias| lower_ident(alias).into());
+        let returning = self.bind_returning(&table_name, alias.as_ref(), returning)?;
         if let SetExpr::Values(values) = source.body.as_ref() {
-            self.insert_values(table_name, columns, &values.rows, *overwrite, false)
+            self.insert_values(
+                table_name,
+                columns,
+                &values.rows,
+                *overwrite,
+                false,
+                on,
+                returning,
+            )
         } else {
-            self.insert_query(table_name, columns, source, *overwrite)
+            self.insert_query(table_name, columns, source, *overwrite, on, returning)
+        }
+    }
+
+    fn bind_returning(
+        &mut self,
+        table_name: &TableName,
+        alias: Option<&TableName>,
+        returning: &Option<Vec<SelectItem>>,
+    ) -> Result<Vec<ScalarExpression>, DatabaseError> {
+        let Some(items) = returning else {
+            return Ok(Vec::new());
+        };
+        let mut binder = self.binder.returning_binder(table_name.clone(), alias)?;
+        with_query_bind_step!(binder, QueryBindStep::Project, {
+            let exprs = binder.normalize_select_item(items, self.arena)?;
+            let mut has_agg_or_window = false;
+            for expr in exprs.iter() {
+                has_agg_or_window |= expr.has_agg_call()? || expr.has_window_call()?;
+            }
+            if has_agg_or_window || binder.context.sub_queries_at_now().is_some() {
+                return Err(DatabaseError::UnsupportedStmt(
+                    "RETURNING only supports scalar expressions over the target table".to_string(),
+                ));
+            }
+            exprs
+        })
+    }
+
+    fn bind_on_insert(
+        &mut self,
+        table_name: &TableName,
+        on: &OnInsert,
+    ) -> Result<OnConflict, DatabaseError> {
+        let (target, assignments, selection) = match on {
+            OnInsert::DuplicateKeyUpdate(assignments) => (None, assignments, None),
+            OnInsert::OnConflict(sqlparser::ast::OnConflict {
+                conflict_target,
+                action,
+            }) => {
+                let target = match conflict_target {
+                    None => None,
+                    Some(ConflictTarget::Columns(idents)) => {
+                        let columns = idents
+                            .iter()
+                            .map(|ident| lower_ident(ident).into_owned())
+                            .collect_vec();
+                        self.binder
+                            .bind_conflict_target(table_name, &columns, self.arena)?
+                    }
+                    Some(ConflictTarget::OnConstraint(name)) => {
+                        return Err(DatabaseError::UnsupportedStmt(format!(
+                            "ON CONFLICT ON CONSTRAINT {name}"
+                        )))
+                    }
+                };
+                match action {
+                    OnConflictAction::DoNothing => {
+                        return Ok(OnConflict {
+                            target,
+                            action: ConflictAction::DoNothing,
+                        })
+                    }
+                    OnConflictAction::DoUpdate(DoUpdate {
+                        assignments,
+                        selection,
+                    }) => (target, assignments, selection.as_ref()),
+                }
+            }
+            on => return Err(DatabaseError::UnsupportedStmt(on.to_string())),
+        };
+        let mut binder = self
+            .binder
+            .conflict_binder(table_name.clone(), self.arena)?;
+        let mut value_exprs = Vec::with_capacity(assignments.len());
+        for Assignment { target, value } in assignments {
+            let AssignmentTarget::ColumnName(name) = target else {
+                return Err(DatabaseError::UnsupportedStmt(format!(
+                    "ON CONFLICT assignment target: {target}"
+                )));
+            };
+            let ident = single_ident_from_object_name(name)?;
+            let column_name = lower_ident(&ident);
+            let expr = binder.bind_expr(value, self.arena)?;
+            value_exprs.push(binder.bind_conflict_assignment(
+                table_name,
+                &column_name,
+                expr,
+                self.arena,
+            )?);
         }
+        let predicate = selection
+            .map(|selection| binder.bind_expr(selection, self.arena))
+            .transpose()?;
+
+        Ok(OnConflict {
+            target,
+            action: ConflictAction::DoUpdate {
+                value_exprs,
+                predicate,
+            },
+        })
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn insert_values(
         &mut self,
         table_name: TableName,
@@ -987,6 +1562,8 @@ where
         expr_rows: &[Vec<Expr>],
         is_overwrite: bool,
         is_mapping_by_name: bool,
+        on: &Option<OnInsert>,
+        returning: Vec<ScalarExpression>,
     ) -> Result<LogicalPlan, DatabaseError> {
         self.binder.context.allow_default = true;
         let source = self
@@ -1041,7 +1618,11 @@ where
 
                         value = value.cast(ty)?;
                         value.check_len(ty)?;
-                        if value.is_null() && !column.nullable() {
+                        // a missing identity value is generated while inserting
+                        if value.is_null()
+                            && !column.nullable()
+                            && column.desc().identity().is_none()
+                        {
                             return Err(attach_span_if_absent(
                                 DatabaseError::not_null_column(column.name().to_string()),
                                 span,
@@ -1052,6 +1633,10 @@ where
                     }
                     ScalarExpression::Empty => {
                         let column = self.arena.column(schema_ref[i]);
+                        if column.desc().identity().is_some() {
+                            row.push(DataValue::Null);
+                            continue;
+                        }
                         let default_value = column
                             .default_value()?
                             .ok_or(DatabaseError::DefaultNotExist)?;
@@ -1076,6 +1661,10 @@ where
             rows.push(row);
         }
         self.binder.context.allow_default = false;
+        let on_conflict = on
+            .as_ref()
+            .map(|on| self.bind_on_insert(&table_name, on))
+            .transpose()?;
 
         self.binder.bind_insert_values(
             table_name,
@@ -1083,6 +1672,9 @@ where
             rows,
             is_overwrite,
             is_mapping_by_name,
+            on_conflict,
+            returning,
+            self.arena,
         )
     }
 
@@ -1092,6 +1684,8 @@ where
         idents: &[Ident],
         query: &Query,
         is_overwrite: bool,
+        on: &Option<OnInsert>,
+        returning: Vec<ScalarExpression>,
     ) -> Result<LogicalPlan, DatabaseError> {
         let mut input_plan = self.binder.bind_query(query, self.arena)?;
         let input_schema = input_plan.output_schema(self.arena).clone();
@@ -1151,17 +1745,31 @@ where
         input_plan = self
             .binder
             .bind_project(input_plan, projection, self.arena)?;
+        let on_conflict = on
+            .as_ref()
+            .map(|on| self.bind_on_insert(&table_name, on))
+            .transpose()?;
 
-        self.binder
-            .bind_insert_query(table_name, input_plan, is_overwrite)
+        self.binder.bind_insert_query(
+            table_name,
+            input_plan,
+            is_overwrite,
+            on_conflict,
+            returning,
+            self.arena,
+        )
     }
 
-    fn update(self, update: &sqlparser::ast::Update) -> Result<LogicalPlan, DatabaseError> {
+    fn update(mut self, update: &sqlparser::ast::Update) -> Result<LogicalPlan, DatabaseError> {
         self.binder.context.allow_default = true;
         let to = &update.table;
-        if let TableFactor::Table { name, .. } = &to.relation {
+        if let TableFactor::Table { name, alias, .. } = &to.relation {
             let is_joined_update = !to.joins.is_empty();
             let table_name = sql_table_name(name.clone())?;
+            let alias = alias
+                .as_ref()
+                .map(|alias| TableName::from(lower_ident(&alias.name)));
+            let returning = self.bind_returning(&table_name, alias.as_ref(), &update.returning)?;
             self.binder.with_pk(table_name.clone());
 
             let mut plan = self.binder.bind_table_ref_sql(to, self.arena)?;
@@ -1202,6 +1810,13 @@ where
                             }
                         }
                     };
+                    let column_catalog = self.arena.column(column);
+                    if column_catalog.desc().identity() == Some(ColumnIdentity::Always) {
+                        return Err(attach_span_if_absent(
+                            DatabaseError::GeneratedAlways(column_catalog.name().to_string()),
+                            ident.span,
+                        ));
+                    }
 
                     let mut expr = if matches!(expression, ScalarExpression::Empty) {
                         let column_catalog = self.arena.column(column);
@@ -1267,7 +1882,8 @@ where
                     Childrens::Only(Box::new(plan)),
                 );
             }
-            self.binder.bind_update(table_name, value_exprs, plan)
+            self.binder
+                .bind_update(table_name, value_exprs, plan, returning, self.arena)
         } else {
             Err(DatabaseError::UnsupportedStmt(format!(
                 "UPDATE target must be a table: {:?}",
@@ -1276,7 +1892,7 @@ where
         }
     }
 
-    fn delete(self, delete: &sqlparser::ast::Delete) -> Result<LogicalPlan, DatabaseError> {
+    fn delete(mut self, delete: &sqlparser::ast::Delete) -> Result<LogicalPlan, DatabaseError> {
         let from = match &delete.from {
             FromTable::WithFromKeyword(from) | FromTable::WithoutKeyword(from) => from,
         };
@@ -1285,8 +1901,12 @@ where
             .next()
             .ok_or_else(|| DatabaseError::invalid_table("DELETE without FROM"))?;
 
-        if let TableFactor::Table { name, .. } = &table.relation {
+        if let TableFactor::Table { name, alias, .. } = &table.relation {
             let table_name = sql_table_name(name.clone())?;
+            let alias = alias
+                .as_ref()
+                .map(|alias| TableName::from(lower_ident(&alias.name)));
+            let returning = self.bind_returning(&table_name, alias.as_ref(), &delete.returning)?;
             let primary_keys = self
                 .binder
                 .context
@@ -1303,7 +1923,8 @@ where
                 plan = self.binder.bind_where(plan, predicate, self.arena)?;
             }
 
-            self.binder.bind_delete(table_name, primary_keys, plan)
+            self.binder
+                .bind_delete(table_name, primary_keys, plan, returning)
         } else {
             Err(DatabaseError::UnsupportedStmt(format!(
                 "DELETE target must be a table: {:?}",
@@ -1331,10 +1952,14 @@ where
         )?)
     }
 
-    fn explain(self, statement: &Statement) -> Result<LogicalPlan, DatabaseError> {
+    fn explain(
+        self,
+        statement: &Statement,
+        explain: ExplainOperator,
+    ) -> Result<LogicalPlan, DatabaseError> {
         let BindStatementStart { binder, arena } = self;
         let plan = binder.bind(statement, arena)?;
-        binder.bind_explain(plan)
+        binder.bind_explain(plan, explain.analyze, explain.format)
     }
 }
 
@@ -1359,12 +1984,19 @@ where
             let mut plan = self.binder.bind_table_ref_sql(from, self.arena)?;
 
             for from in froms {
+                if Binder::<T, A>::is_lateral_table_factor(&from.relation) {
+                    plan = self
+                        .binder
+                        .bind_lateral_table_ref_sql(plan, from, self.arena)?;
+                    continue;
+                }
                 plan = LJoinOperator::build(
                     plan,
                     self.binder.bind_table_ref_sql(from, self.arena)?,
                     JoinCondition::None,
                     JoinType::Cross,
                     self.binder.force_nested_loop,
+                    self.binder.force_merge_join,
                 )
             }
             plan
@@ -1424,20 +2056,42 @@ where
         self,
         group_by: &GroupByExpr,
         having: Option<&Expr>,
+        qualify: Option<&Expr>,
         orderby: Option<&[OrderByExpr]>,
     ) -> Result<BindPlanAggregated<'s, 'a, 'b, 'arena, T, A>, DatabaseError> {
-        let group_by = with_query_bind_step!(self.binder, QueryBindStep::Agg, {
+        let (group_by, grouping_sets) = with_query_bind_step!(self.binder, QueryBindStep::Agg, {
             match group_by {
                 GroupByExpr::Expressions(group_by_exprs, modifiers) => {
-                    if !modifiers.is_empty() {
-                        return Err(DatabaseError::UnsupportedStmt(
-                            "GROUP BY modifiers are not supported".to_string(),
-                        ));
+                    let Some(sets) = grouping_sets_sql(group_by_exprs, modifiers)? else {
+                        let group_by = group_by_exprs
+                            .iter()
+                            .map(|expr| self.binder.bind_expr(expr, self.arena))
+                            .collect::<Result<Vec<_>, DatabaseError>>()?;
+                        return Ok((group_by, Vec::new()));
+                    };
+                    let mut exprs: Vec<&Expr> = Vec::new();
+                    let mut grouping_sets = Vec::with_capacity(sets.len());
+                    for set in sets {
+                        let mut positions = Vec::with_capacity(set.len());
+                        for expr in set {
+                            let position = exprs
+                                .iter()
+                                .position(|other| *other == expr)
+                                .unwrap_or_else(|| {
+                                    exprs.push(expr);
+                                    exprs.len() - 1
+                                });
+                            if !positions.contains(&position) {
+                                positions.push(position);
+                            }
+                        }
+                        grouping_sets.push(positions);
                     }
-                    group_by_exprs
-                        .iter()
+                    let group_by = exprs
+                        .into_iter()
                         .map(|expr| self.binder.bind_expr(expr, self.arena))
-                        .collect::<Result<Vec<_>, DatabaseError>>()?
+                        .collect::<Result<Vec<_>, DatabaseError>>()?;
+                    (group_by, grouping_sets)
                 }
                 GroupByExpr::All(_) => {
                     return Err(DatabaseError::UnsupportedStmt(
@@ -1453,50 +2107,209 @@ where
                 })
             })
             .transpose()?;
-        self.aggregate(group_by, having, orderby, |binder, arena, orderby| {
-            let OrderByExpr { expr, options, .. } = orderby;
-            with_query_bind_step!(binder, QueryBindStep::Sort, {
-                SortField::new(
-                    binder.bind_expr(expr, arena)?,
-                    options.asc.is_none_or(|asc| asc),
-                    options.nulls_first.unwrap_or(false),
-                )
+        let qualify = qualify
+            .map(|qualify| {
+                with_query_bind_step!(self.binder, QueryBindStep::Qualify, {
+                    self.binder.bind_expr(qualify, self.arena)?
+                })
             })
-        })
+            .transpose()?;
+        self.aggregate(
+            group_by,
+            grouping_sets,
+            having,
+            qualify,
+            orderby,
+            |binder, arena, orderby| {
+                let OrderByExpr { expr, options, .. } = orderby;
+                with_query_bind_step!(binder, QueryBindStep::Sort, {
+                    SortField::new(
+                        binder.bind_expr(expr, arena)?,
+                        options.asc.is_none_or(|asc| asc),
+                        options.nulls_first.unwrap_or(false),
+                    )
+                })
+            },
+        )
     }
 }
 
-impl<'s, 'a: 'b, 'b, 'arena, T, A> super::select::BindPlanWindowed<'s, 'a, 'b, 'arena, T, A>
-where
-    T: Transaction,
-    A: AsRef<[(&'static str, DataValue)]>,
-{
-    pub(crate) fn distinct_sql(
-        self,
-        distinct: Option<&Distinct>,
-    ) -> Result<BindPlanDistinct<'s, 'a, 'b, 'arena, T, A>, DatabaseError> {
-        self.distinct(matches!(distinct, Some(Distinct::Distinct)))
+/// Expands the `GROUPING SETS`, `ROLLUP` and `CUBE` items of a GROUP BY, and MySQL's `WITH ROLLUP`,
+/// into the cross product of their grouping sets. A plain list of expressions has none.
+fn grouping_sets_sql<'e>(
+    exprs: &'e [Expr],
+    modifiers: &'e [GroupByWithModifier],
+) -> Result<Option<Vec<Vec<&'e Expr>>>, DatabaseError> {
+    fn rollup<'e>(lists: &[&'e [Expr]]) -> Vec<Vec<&'e Expr>> {
+        (0..=lists.len())
+            .rev()
+            .map(|len| lists[..len].iter().flat_map(|list| list.iter()).collect())
+            .collect()
     }
-}
-
-impl<'s, 'a: 'b, 'b, 'arena, T, A> BindPlanProjected<'s, 'a, 'b, 'arena, T, A>
-where
-    T: Transaction,
-    A: AsRef<[(&'static str, DataValue)]>,
-{
-    pub(crate) fn select_into_sql(
-        self,
-        into: Option<&SelectInto>,
-    ) -> Result<BindPlanComplete, DatabaseError> {
-        self.insert_into(
-            into.map(|SelectInto { name, .. }| lower_case_name(name).map(Into::into))
-                .transpose()?,
-        )
+    fn cube<'e>(lists: &[&'e [Expr]]) -> Result<Vec<Vec<&'e Expr>>, DatabaseError> {
+        if lists.len() > MAX_CUBE_LISTS {
+            return Err(DatabaseError::UnsupportedStmt(format!(
+                "CUBE is limited to {MAX_CUBE_LISTS} elements"
+            )));
+        }
+        // From all elements down to the empty set, the same order as PostgreSQL
+        Ok((0..1_usize << lists.len())
+            .rev()
+            .map(|mask| {
+                lists
+                    .iter()
+                    .enumerate()
+                    .filter(|(i, _)| mask & (1 << (lists.len() - 1 - i)) != 0)
+                    .flat_map(|(_, list)| list.iter())
+                    .collect()
+            })
+            .collect())
     }
-}
+    const MAX_CUBE_LISTS: usize = 12;
+    const MAX_GROUPING_SETS: usize = 4096;
 
-fn sql_table_name(name: ObjectName) -> Result<TableName, DatabaseError> {
-    Ok(lower_case_name(&name)?.into())
+    let has_grouping_items = exprs.iter().any(|expr| {
+        matches!(
+            expr,
+            Expr::GroupingSets(_) | Expr::Rollup(_) | Expr::Cube(_)
+        )
+    });
+    let items = match modifiers {
+        [] => {
+            if !has_grouping_items {
+                return Ok(None);
+            }
+            exprs
+                .iter()
+                .map(|expr| match expr {
+                    Expr::GroupingSets(sets) => {
+                        Ok(sets.iter().map(|set| set.iter().collect()).collect())
+                    }
+                    Expr::Rollup(lists) => {
+                        Ok(rollup(&lists.iter().map(Vec::as_slice).collect_vec()))
+                    }
+                    Expr::Cube(lists) => cube(&lists.iter().map(Vec::as_slice).collect_vec()),
+                    expr => Ok(vec![vec![expr]]),
+                })
+                .collect::<Result<Vec<Vec<Vec<_>>>, DatabaseError>>()?
+        }
+        [modifier @ (GroupByWithModifier::Rollup | GroupByWithModifier::Cube)] => {
+            if has_grouping_items {
+                return Err(DatabaseError::UnsupportedStmt(format!(
+                    "{modifier} cannot be combined with GROUPING SETS, ROLLUP or CUBE"
+                )));
+            }
+            let lists = exprs.iter().map(slice::from_ref).collect_vec();
+            if matches!(modifier, GroupByWithModifier::Rollup) {
+                vec![rollup(&lists)]
+            } else {
+                vec![cube(&lists)?]
+            }
+        }
+        modifiers => {
+            return Err(DatabaseError::UnsupportedStmt(format!(
+                "GROUP BY {} is not supported",
+                modifiers.iter().join(" ")
+            )))
+        }
+    };
+
+    let mut sets = vec![Vec::new()];
+    for item in items {
+        if sets.len().saturating_mul(item.len()) > MAX_GROUPING_SETS {
+            return Err(DatabaseError::UnsupportedStmt(format!(
+                "a GROUP BY is limited to {MAX_GROUPING_SETS} grouping sets"
+            )));
+        }
+        sets = sets
+            .iter()
+            .flat_map(|set: &Vec<&Expr>| {
+                item.iter()
+                    .map(move |item_set| set.iter().chain(item_set.iter()).copied().collect())
+            })
+            .collect();
+    }
+    Ok(Some(sets))
+}
+
+impl<'s, 'a: 'b, 'b, 'arena, T, A> super::select::BindPlanWindowed<'s, 'a, 'b, 'arena, T, A>
+where
+    T: Transaction,
+    A: AsRef<[(&'static str, DataValue)]>,
+{
+    pub(crate) fn distinct_sql(
+        self,
+        distinct: Option<&Distinct>,
+    ) -> Result<BindPlanDistinct<'s, 'a, 'b, 'arena, T, A>, DatabaseError> {
+        self.distinct(matches!(distinct, Some(Distinct::Distinct)))
+    }
+}
+
+impl<'s, 'a: 'b, 'b, 'arena, T, A> BindPlanProjected<'s, 'a, 'b, 'arena, T, A>
+where
+    T: Transaction,
+    A: AsRef<[(&'static str, DataValue)]>,
+{
+    pub(crate) fn select_into_sql(
+        self,
+        into: Option<&SelectInto>,
+    ) -> Result<BindPlanComplete, DatabaseError> {
+        self.insert_into(
+            into.map(|SelectInto { name, .. }| lower_case_name(name).map(Into::into))
+                .transpose()?,
+        )
+    }
+}
+
+fn sql_table_name(name: ObjectName) -> Result<TableName, DatabaseError> {
+    Ok(lower_case_name(&name)?.into())
+}
+
+/// Converts a `REFERENCES` clause over `columns`; the referenced columns stay empty
+/// when omitted and are resolved to the primary key by the binder.
+fn sql_foreign_key(
+    table_name: &str,
+    name: Option<Ident>,
+    columns: Vec<String>,
+    foreign_key: ForeignKeyConstraint,
+    foreign_keys: &[ForeignKey],
+) -> Result<ForeignKey, DatabaseError> {
+    if foreign_key
+        .match_kind
+        .is_some_and(|kind| !matches!(kind, ConstraintReferenceMatchKind::Simple))
+        || foreign_key.characteristics.is_some()
+    {
+        return Err(DatabaseError::UnsupportedStmt(format!(
+            "`FOREIGN KEY` only supports `MATCH SIMPLE` without constraint characteristics: {foreign_key}"
+        )));
+    }
+    let name = name
+        .map(|name| lower_ident(&name).into_owned())
+        .unwrap_or_else(|| foreign_key_name(table_name, &columns, foreign_keys));
+
+    Ok(ForeignKey {
+        name,
+        columns,
+        referenced_table: sql_table_name(foreign_key.foreign_table)?,
+        referenced_columns: foreign_key
+            .referred_columns
+            .iter()
+            .map(|column| lower_ident(column).into_owned())
+            .collect(),
+        on_delete: sql_referential_action(foreign_key.on_delete),
+        on_update: sql_referential_action(foreign_key.on_update),
+    })
+}
+
+fn sql_referential_action(action: Option<SqlReferentialAction>) -> ReferentialAction {
+    match action {
+        None | Some(SqlReferentialAction::Restrict | SqlReferentialAction::NoAction) => {
+            ReferentialAction::Restrict
+        }
+        Some(SqlReferentialAction::Cascade) => ReferentialAction::Cascade,
+        Some(SqlReferentialAction::SetNull) => ReferentialAction::SetNull,
+        Some(SqlReferentialAction::SetDefault) => ReferentialAction::SetDefault,
+    }
 }
 
 fn sql_table_alias(alias: TableAlias) -> TableAliasInput {
@@ -1725,7 +2538,8 @@ impl TryFrom<DataType> for LogicalType {
                     }
                     if !matches!(info, sqlparser::ast::TimezoneInfo::None) {
                         return Err(DatabaseError::UnsupportedStmt(
-                            "time's zone is not supported".to_string(),
+                            "TIME WITH TIME ZONE is not supported, use TIMESTAMP WITH TIME ZONE"
+                                .to_string(),
                         ));
                     }
                     Ok(Self::Time(precision))
@@ -1741,7 +2555,6 @@ impl TryFrom<DataType> for LogicalType {
             DataType::Timestamp(precision, info) => {
                 #[cfg(feature = "time")]
                 {
-                    let mut zone = false;
                     match precision {
                         Some(3 | 6 | 9) | None => (),
                         _ => {
@@ -1750,9 +2563,11 @@ impl TryFrom<DataType> for LogicalType {
                             ))
                         }
                     }
-                    if matches!(info, sqlparser::ast::TimezoneInfo::WithTimeZone) {
-                        zone = true;
-                    }
+                    let zone = matches!(
+                        info,
+                        sqlparser::ast::TimezoneInfo::WithTimeZone
+                            | sqlparser::ast::TimezoneInfo::Tz
+                    );
                     Ok(Self::TimeStamp(precision, zone))
                 }
                 #[cfg(not(feature = "time"))]
@@ -1763,6 +2578,24 @@ impl TryFrom<DataType> for LogicalType {
                     ))
                 }
             }
+            DataType::Interval { fields, precision } => {
+                if fields.is_some() || precision.is_some() {
+                    return Err(DatabaseError::UnsupportedStmt(
+                        "interval fields and precision".to_string(),
+                    ));
+                }
+                Ok(Self::Interval)
+            }
+            DataType::JSON | DataType::JSONB => Ok(Self::Json),
+            // like PostgreSQL, a declared size such as `INT[3]` is not enforced
+            DataType::Array(
+                ArrayElemTypeDef::SquareBracket(element, _)
+                | ArrayElemTypeDef::AngleBracket(element)
+                | ArrayElemTypeDef::Parenthesis(element),
+            ) => Ok(Self::Array(Box::new(LogicalType::try_from(*element)?))),
+            DataType::Array(ArrayElemTypeDef::None) => Err(DatabaseError::UnsupportedStmt(
+                "arrays must declare their element type".to_string(),
+            )),
             DataType::Decimal(info)
             | DataType::DecimalUnsigned(info)
             | DataType::Dec(info)
@@ -1799,6 +2632,52 @@ fn sql_object_name(name: ObjectName) -> Result<String, DatabaseError> {
     Ok(lower_case_name(&name)?.into_owned())
 }
 
+fn named_window<'w>(
+    name: &Ident,
+    named_windows: &'w HashMap<String, WindowSpec>,
+) -> Result<&'w WindowSpec, DatabaseError> {
+    named_windows
+        .get(lower_ident(name).as_ref())
+        .ok_or_else(|| DatabaseError::UnsupportedStmt(format!("window `{name}` does not exist")))
+}
+
+/// Merges the window a spec refers to into it, the spec may only add an `ORDER BY` the base
+/// window lacks and a frame.
+fn resolve_window_spec(
+    spec: &WindowSpec,
+    named_windows: &HashMap<String, WindowSpec>,
+) -> Result<WindowSpec, DatabaseError> {
+    let Some(name) = &spec.window_name else {
+        return Ok(spec.clone());
+    };
+    let base = named_window(name, named_windows)?;
+    if !spec.partition_by.is_empty() {
+        return Err(DatabaseError::UnsupportedStmt(format!(
+            "cannot override PARTITION BY of window `{name}`"
+        )));
+    }
+    if !spec.order_by.is_empty() && !base.order_by.is_empty() {
+        return Err(DatabaseError::UnsupportedStmt(format!(
+            "cannot override ORDER BY of window `{name}`"
+        )));
+    }
+    if base.window_frame.is_some() {
+        return Err(DatabaseError::UnsupportedStmt(format!(
+            "cannot copy window `{name}` because it has a frame"
+        )));
+    }
+    Ok(WindowSpec {
+        window_name: None,
+        partition_by: base.partition_by.clone(),
+        order_by: if spec.order_by.is_empty() {
+            base.order_by.clone()
+        } else {
+            spec.order_by.clone()
+        },
+        window_frame: spec.window_frame.clone(),
+    })
+}
+
 pub(super) fn lower_ident(ident: &Ident) -> Cow<'_, str> {
     let value = &ident.value;
 
@@ -1939,6 +2818,22 @@ impl<'a, 'parent, T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<
         arena: &mut PlanArena,
     ) -> Result<LogicalPlan, DatabaseError> {
         match table {
+            // `FROM json_each(...)` without the `TABLE(...)` wrapper
+            TableFactor::Table {
+                name,
+                alias,
+                args: Some(args),
+                ..
+            } => {
+                let args = self.bind_function_args(&args.args, arena)?;
+                let expr = self.bind_function_call(name.to_string().to_lowercase(), args, arena)?;
+                self.bind_table_function_source(
+                    expr,
+                    sql_optional_table_alias(alias.clone()),
+                    joint_type,
+                    arena,
+                )
+            }
             TableFactor::Table { name, alias, .. } => self.bind_base_table_ref(
                 joint_type,
                 sql_table_name(name.clone())?,
@@ -1946,10 +2841,32 @@ impl<'a, 'parent, T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<
                 arena,
             ),
             TableFactor::Derived {
-                subquery, alias, ..
+                lateral,
+                subquery,
+                alias,
+                ..
             } => {
-                let mut binder = Binder::new(self.context.fork(), self.args, Some(&self.context));
-                let plan = binder.bind_query(subquery, arena)?;
+                // a `LATERAL` subquery is bound beside the preceding FROM items, which are then
+                // resolved through the parent scope, see `bind_lateral_join_sql`
+                let parent = if *lateral {
+                    self.parent
+                } else {
+                    Some(&self.context)
+                };
+                let mut binder = Binder::new(self.context.fork_empty(), self.args, parent);
+                if *lateral {
+                    binder.ancestors = self.ancestors.clone();
+                }
+                let mut plan = binder.bind_query(subquery, arena)?;
+                if binder.context.has_outer_refs() {
+                    if !*lateral {
+                        return Err(DatabaseError::UnsupportedStmt(
+                            "subquery in FROM must be `LATERAL` to refer to preceding FROM items"
+                                .to_string(),
+                        ));
+                    }
+                    Self::name_projected_outer_columns(&mut plan, arena);
+                }
                 self.bind_derived_source(
                     plan,
                     sql_optional_table_alias(alias.clone()),
@@ -1966,10 +2883,162 @@ impl<'a, 'parent, T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<
                     arena,
                 )
             }
+            TableFactor::Function {
+                name, args, alias, ..
+            } => {
+                let args = self.bind_function_args(args, arena)?;
+                let alias = sql_optional_table_alias(alias.clone());
+                let function_name = name.to_string().to_lowercase();
+                // `LATERAL unnest(...)` is parsed as a plain call rather than `UNNEST`
+                let expr = if function_name == "unnest" {
+                    let Ok([array]) = <[ScalarExpression; 1]>::try_from(args) else {
+                        return Err(DatabaseError::MisMatch(
+                            "number of unnest() parameters",
+                            "1",
+                        ));
+                    };
+                    self.bind_unnest(array, alias.as_ref(), arena)?
+                } else {
+                    self.bind_function_call(function_name, args, arena)?
+                };
+                self.bind_table_function_source(expr, alias, joint_type, arena)
+            }
+            TableFactor::UNNEST {
+                alias,
+                array_exprs,
+                with_offset,
+                with_ordinality,
+                ..
+            } => {
+                let [array_expr] = array_exprs.as_slice() else {
+                    return Err(DatabaseError::UnsupportedStmt(
+                        "UNNEST of more than one array".to_string(),
+                    ));
+                };
+                if *with_offset || *with_ordinality {
+                    return Err(DatabaseError::UnsupportedStmt(
+                        "UNNEST with WITH OFFSET or WITH ORDINALITY".to_string(),
+                    ));
+                }
+                let alias = sql_optional_table_alias(alias.clone());
+                let array = self.bind_expr(array_expr, arena)?;
+                let expr = self.bind_unnest(array, alias.as_ref(), arena)?;
+                self.bind_table_function_source(expr, alias, joint_type, arena)
+            }
             table => Err(DatabaseError::UnsupportedStmt(format!("{table:#?}"))),
         }
     }
 
+    /// Names the outer columns a `LATERAL` subquery projects as they are, as in `t.id AS id`.
+    /// A projection of bare columns is merged into the alias projection over the subquery,
+    /// which then reads them from the subquery's input instead of the left row.
+    fn name_projected_outer_columns(plan: &mut LogicalPlan, arena: &mut PlanArena) {
+        let input_schema = match (&plan.operator, plan.childrens.as_mut()) {
+            (Operator::Project(_), Childrens::Only(child)) => child.output_schema(arena).clone(),
+            _ => return,
+        };
+        let Operator::Project(project) = &mut plan.operator else {
+            return;
+        };
+        for expr in project.exprs.iter_mut() {
+            let ScalarExpression::ColumnRef { column, .. } = expr else {
+                continue;
+            };
+            let column = *column;
+            if input_schema
+                .iter()
+                .any(|input| arena.same_column(*input, column))
+            {
+                continue;
+            }
+            *expr = ScalarExpression::Alias {
+                expr: Box::new(expr.clone()),
+                alias: AliasType::Name(arena.column(column).name().to_string()),
+            };
+        }
+        plan.reset_output_schema_cache();
+    }
+
+    /// Whether `table` may refer to the FROM items before it: `LATERAL` subqueries and,
+    /// as in PostgreSQL, every table function call.
+    pub(crate) fn is_lateral_table_factor(table: &TableFactor) -> bool {
+        matches!(
+            table,
+            TableFactor::Derived { lateral: true, .. }
+                | TableFactor::Table { args: Some(_), .. }
+                | TableFactor::Function { .. }
+                | TableFactor::TableFunction { .. }
+                | TableFactor::UNNEST { .. }
+        )
+    }
+
+    /// Binds `relation` with the sources of `left` visible as outer references. A relation
+    /// that refers to none of them is joined as usual, otherwise it is re-executed for every
+    /// left row by a `LateralApply`.
+    fn bind_lateral_join_sql(
+        &mut self,
+        mut left: LogicalPlan,
+        relation: &TableFactor,
+        join_type: JoinType,
+        constraint: Option<&JoinConstraint>,
+        arena: &mut PlanArena,
+    ) -> Result<LogicalPlan, DatabaseError> {
+        let (right, context) = {
+            let mut binder = Binder::new(self.context.fork_empty(), self.args, Some(&self.context));
+            let right = binder.bind_single_table_ref_sql(relation, Some(join_type), arena)?;
+            (right, binder.context)
+        };
+        self.extend(context);
+
+        let constraint = match constraint {
+            Some(constraint) => self.bind_join_constraint_sql(constraint, arena)?,
+            None => JoinConstraintInput::None,
+        };
+        let left_schema = left.output_schema(arena).clone();
+        let parameters = Self::correlated_parameters(&right, &left_schema, arena)?;
+        if parameters.is_empty() {
+            return self.bind_join_plans(left, right, join_type, constraint, arena);
+        }
+        if !matches!(
+            join_type,
+            JoinType::Inner | JoinType::Cross | JoinType::LeftOuter
+        ) {
+            return Err(DatabaseError::UnsupportedStmt(format!(
+                "`LATERAL` referring to the left side of a {join_type} join"
+            )));
+        }
+        let filter = match constraint {
+            JoinConstraintInput::On(expr) => Some(expr),
+            JoinConstraintInput::None => None,
+            JoinConstraintInput::Using(_) | JoinConstraintInput::Natural => {
+                return Err(DatabaseError::UnsupportedStmt(
+                    "`USING` and `NATURAL` with `LATERAL`".to_string(),
+                ))
+            }
+        };
+
+        Ok(LateralApplyOperator::build(
+            left, right, join_type, filter, parameters,
+        ))
+    }
+
+    pub(crate) fn bind_lateral_table_ref_sql(
+        &mut self,
+        left: LogicalPlan,
+        from: &TableWithJoins,
+        arena: &mut PlanArena,
+    ) -> Result<LogicalPlan, DatabaseError> {
+        self.context.step(QueryBindStep::From);
+
+        let TableWithJoins { relation, joins } = from;
+        let mut plan = self.bind_lateral_join_sql(left, relation, JoinType::Cross, None, arena)?;
+
+        for join in joins {
+            plan = self.bind_join_sql(plan, join, arena)?;
+        }
+        Ok(plan)
+    }
+
     fn bind_join_sql(
         &mut self,
         left: LogicalPlan,
@@ -2006,6 +3075,9 @@ impl<'a, 'parent, T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<
                 return Err(DatabaseError::UnsupportedStmt(format!("{join_operator:?}")))
             }
         };
+        if Self::is_lateral_table_factor(relation) {
+            return self.bind_lateral_join_sql(left, relation, join_type, joint_condition, arena);
+        }
         let (right, context) = {
             let mut binder = Binder::new(self.context.fork_empty(), self.args, Some(&self.context));
             let right = binder.bind_single_table_ref_sql(relation, Some(join_type), arena)?;
@@ -2075,6 +3147,53 @@ impl<'a, 'parent, T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<
             Expr::CompoundIdentifier(idents) => {
                 self.bind_column_ref_from_identifiers(idents, None, arena)
             }
+            Expr::BinaryOp {
+                left,
+                right,
+                op:
+                    op @ (BinaryOperator::Arrow
+                    | BinaryOperator::LongArrow
+                    | BinaryOperator::HashArrow
+                    | BinaryOperator::HashLongArrow),
+            } => {
+                let left_expr = self.bind_expr(left, arena)?;
+                let right_expr = self.bind_expr(right, arena)?;
+                self.bind_json_operator(left_expr, right_expr, op, arena)
+            }
+            Expr::BinaryOp {
+                left,
+                right,
+                op: op @ (BinaryOperator::AtArrow | BinaryOperator::ArrowAt),
+            } => {
+                let left_expr = self.bind_expr(left, arena)?;
+                let right_expr = self.bind_expr(right, arena)?;
+                if matches!(op, BinaryOperator::AtArrow) {
+                    Self::bind_array_contains(left_expr, right_expr, arena)
+                } else {
+                    Self::bind_array_contains(right_expr, left_expr, arena)
+                }
+            }
+            Expr::Array(array) => {
+                let args = array
+                    .elem
+                    .iter()
+                    .map(|expr| self.bind_expr(expr, arena))
+                    .try_collect()?;
+                Self::bind_array_construct(args, arena)
+            }
+            Expr::CompoundFieldAccess { root, access_chain } => {
+                let mut expr = self.bind_expr(root, arena)?;
+                for access in access_chain {
+                    let AccessExpr::Subscript(Subscript::Index { index }) = access else {
+                        return Err(DatabaseError::UnsupportedStmt(format!(
+                            "field access `{access}`, only `array[index]` subscripts are supported"
+                        )));
+                    };
+                    let index = self.bind_expr(index, arena)?;
+                    expr = Self::bind_array_element(expr, index, arena)?;
+                }
+                Ok(expr)
+            }
             Expr::BinaryOp { left, right, op } => {
                 let left_expr = self.bind_expr(left, arena)?;
                 let right_expr = self.bind_expr(right, arena)?;
@@ -2212,6 +3331,29 @@ impl<'a, 'parent, T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<
                 expr: Box::new(self.bind_expr(expr, arena)?),
                 in_expr: Box::new(self.bind_expr(r#in, arena)?),
             }),
+            Expr::Interval(interval) => self.bind_interval(interval, arena),
+            Expr::Extract { field, expr, .. } => {
+                // `extract` returns whole numbers, the epoch keeps its fractional seconds
+                let function_name = if matches!(field, DateTimeField::Epoch) {
+                    "date_part"
+                } else {
+                    "extract"
+                };
+                let field =
+                    ScalarExpression::Constant(DataValue::from(field.to_string().to_lowercase()));
+                let arg = self.bind_expr(expr, arena)?;
+                self.bind_function_call(function_name.to_string(), vec![field, arg], arena)
+            }
+            Expr::AtTimeZone {
+                timestamp,
+                time_zone,
+            } => {
+                let time_zone = self.bind_expr(time_zone, arena)?;
+                let timestamp = self.bind_expr(timestamp, arena)?;
+                self.bind_function_call("timezone".to_string(), vec![time_zone, timestamp], arena)
+            }
+            Expr::Ceil { expr, field } => self.bind_ceil_floor("ceil", expr, field, arena),
+            Expr::Floor { expr, field } => self.bind_ceil_floor("floor", expr, field, arena),
             Expr::Trim {
                 expr,
                 trim_what,
@@ -2327,9 +3469,15 @@ impl<'a, 'parent, T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<
         arena: &mut PlanArena,
     ) -> Result<ScalarExpression, DatabaseError> {
         let Expr::Subquery(subquery) = right else {
-            return Err(DatabaseError::UnsupportedStmt(format!(
-                "{quantifier:?} only supports subquery operands"
-            )));
+            let left_expr = self.bind_expr(left, arena)?;
+            let array = self.bind_expr(right, arena)?;
+            return Self::bind_array_quantified(
+                quantifier,
+                left_expr,
+                compare_op.clone().try_into()?,
+                array,
+                arena,
+            );
         };
 
         self.bind_quantified_subquery(quantifier, false, left, compare_op, subquery, arena)
@@ -2389,6 +3537,32 @@ impl<'a, 'parent, T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<
         })
     }
 
+    fn bind_function_args(
+        &mut self,
+        func_args: &[FunctionArg],
+        arena: &mut PlanArena,
+    ) -> Result<Vec<ScalarExpression>, DatabaseError> {
+        let mut args = Vec::with_capacity(func_args.len());
+
+        for arg in func_args {
+            let arg_expr = match arg {
+                FunctionArg::Named { arg, .. } => arg,
+                FunctionArg::ExprNamed { arg, .. } => arg,
+                FunctionArg::Unnamed(arg) => arg,
+            };
+            match arg_expr {
+                FunctionArgExpr::Expr(expr) => args.push(self.bind_expr(expr, arena)?),
+                FunctionArgExpr::Wildcard => args.push(Self::wildcard_expr()),
+                expr => {
+                    return Err(DatabaseError::UnsupportedStmt(format!(
+                        "function arg: {expr:#?}"
+                    )))
+                }
+            }
+        }
+        Ok(args)
+    }
+
     pub(crate) fn bind_function_sql(
         &mut self,
         func: &Function,
@@ -2416,34 +3590,45 @@ impl<'a, 'parent, T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<
                 ))
             }
         };
-        let mut args = Vec::with_capacity(func_args.len());
-
-        for arg in func_args {
-            let arg_expr = match arg {
-                FunctionArg::Named { arg, .. } => arg,
-                FunctionArg::ExprNamed { arg, .. } => arg,
-                FunctionArg::Unnamed(arg) => arg,
-            };
-            match arg_expr {
-                FunctionArgExpr::Expr(expr) => args.push(self.bind_expr(expr, arena)?),
-                FunctionArgExpr::Wildcard => args.push(Self::wildcard_expr()),
-                expr => {
-                    return Err(DatabaseError::UnsupportedStmt(format!(
-                        "function arg: {expr:#?}"
-                    )))
+        // `lag(x) IGNORE NULLS` and `lag(x IGNORE NULLS)`
+        let mut null_treatment = *null_treatment;
+        // `string_agg(x, ',' ORDER BY y)` and `group_concat(x ORDER BY y SEPARATOR ';')`
+        let mut order_by: &[OrderByExpr] = &[];
+        let mut separator = None;
+        if let FunctionArguments::List(args) = args {
+            for clause in &args.clauses {
+                match clause {
+                    FunctionArgumentClause::IgnoreOrRespectNulls(treatment) => {
+                        null_treatment = null_treatment.or(Some(*treatment))
+                    }
+                    FunctionArgumentClause::OrderBy(exprs) => order_by = exprs,
+                    FunctionArgumentClause::Separator(value) => separator = Some(value),
+                    clause => {
+                        return Err(attach_span_if_absent(
+                            DatabaseError::UnsupportedStmt(format!(
+                                "function argument clause: {clause}"
+                            )),
+                            func_span,
+                        ))
+                    }
                 }
             }
         }
+
+        let args = self.bind_function_args(func_args, arena)?;
         let function_name = name.to_string().to_lowercase();
 
         if let Some(over) = over {
-            if filter.is_some() || null_treatment.is_some() || !within_group.is_empty() {
+            if filter.is_some() || !order_by.is_empty() || !within_group.is_empty() {
                 return Err(DatabaseError::UnsupportedStmt(
-                    "FILTER, NULL treatment, and WITHIN GROUP are not supported for window functions"
+                    "FILTER, ORDER BY and WITHIN GROUP are not supported for window functions"
                         .to_string(),
                 ));
             }
-            let Some(kind) = WindowFunctionKind::from_name(&function_name) else {
+            let Some(kind) = WindowFunctionKind::from_name(&function_name).or_else(|| {
+                self.user_defined_aggregate(&function_name, &args, arena)
+                    .map(WindowFunctionKind::Aggregate)
+            }) else {
                 return Err(attach_span_if_absent(
                     DatabaseError::UnsupportedStmt(format!(
                         "window function `{function_name}` is not supported"
@@ -2451,19 +3636,197 @@ impl<'a, 'parent, T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<
                     func_span,
                 ));
             };
+            let kind = kind
+                .with_ignore_nulls(matches!(null_treatment, Some(NullTreatment::IgnoreNulls)))
+                .map_err(|err| attach_span_if_absent(err, func_span))?;
             return self
                 .bind_window_call(kind, args, is_distinct, over, arena)
                 .map_err(|err| attach_span_if_absent(err, func_span));
         }
 
-        let result = if let Some(kind) = AggKind::from_name(&function_name) {
-            self.bind_aggregate_function(kind, args, is_distinct, arena)
+        if !within_group.is_empty() {
+            return Err(attach_span_if_absent(
+                DatabaseError::UnsupportedStmt("WITHIN GROUP is not supported".to_string()),
+                func_span,
+            ));
+        }
+        let result = if let Some(kind) = AggKind::from_name(&function_name)
+            .or_else(|| self.user_defined_aggregate(&function_name, &args, arena))
+        {
+            self.bind_aggregate_call(
+                kind,
+                &function_name,
+                args,
+                is_distinct,
+                filter.as_deref(),
+                order_by,
+                separator,
+                arena,
+            )
+        } else if filter.is_some() || !order_by.is_empty() || separator.is_some() {
+            Err(DatabaseError::UnsupportedStmt(format!(
+                "FILTER, ORDER BY and SEPARATOR are only supported for aggregate functions, not `{function_name}`"
+            )))
         } else {
             self.bind_function_call(function_name, args, arena)
         };
         result.map_err(|err| attach_span_if_absent(err, func_span))
     }
 
+    /// Binds the JSON operators to the functions behind them: `->` and `->>` look up an
+    /// object key, or an array element when given an integer, `#>` and `#>>` follow a
+    /// constant `'{a,0}'` path. The `>>` forms return text.
+    fn bind_json_operator(
+        &mut self,
+        left_expr: ScalarExpression,
+        right_expr: ScalarExpression,
+        op: &BinaryOperator,
+        arena: &mut PlanArena,
+    ) -> Result<ScalarExpression, DatabaseError> {
+        let as_text = matches!(
+            op,
+            BinaryOperator::LongArrow | BinaryOperator::HashLongArrow
+        );
+        let suffix = if as_text { "_text" } else { "" };
+        if matches!(op, BinaryOperator::Arrow | BinaryOperator::LongArrow) {
+            let right_ty = right_expr.return_type(arena);
+            let function_name = match right_ty.as_ref() {
+                ty if ty.is_signed_numeric() || ty.is_unsigned_numeric() => "json_array_element",
+                LogicalType::Char(..) | LogicalType::Varchar(..) | LogicalType::SqlNull => {
+                    "json_object_field"
+                }
+                ty => {
+                    return Err(DatabaseError::UnsupportedStmt(format!(
+                        "`{op}` with a {ty} key, expected text or an integer"
+                    )))
+                }
+            };
+            return self.bind_function_call(
+                format!("{function_name}{suffix}"),
+                vec![left_expr, right_expr],
+                arena,
+            );
+        }
+        let Some(DataValue::Utf8 { value: path, .. }) = right_expr.unpack_val() else {
+            return Err(DatabaseError::UnsupportedStmt(format!(
+                "`{op}` with a path that is not a constant such as '{{a,0}}'"
+            )));
+        };
+        let path = parse_text_path(&path)?;
+        if path.is_empty() {
+            let varchar = LogicalType::Varchar(None, CharLengthUnits::Characters);
+            let ty = if as_text {
+                &varchar
+            } else {
+                &LogicalType::Json
+            };
+            return ScalarExpression::type_cast(left_expr, Cow::Borrowed(ty), arena);
+        }
+        let mut args = vec![left_expr];
+        args.extend(path.into_iter().map(|element| {
+            ScalarExpression::Constant(DataValue::Utf8 {
+                value: element,
+                ty: Utf8Type::Variable(None),
+                unit: CharLengthUnits::Characters,
+            })
+        }));
+        self.bind_function_call(format!("json_extract_path{suffix}"), args, arena)
+    }
+
+    /// `CEIL(x)` and `FLOOR(x)` are parsed as dedicated expressions rather than plain calls.
+    fn bind_ceil_floor(
+        &mut self,
+        function_name: &str,
+        expr: &Expr,
+        field: &CeilFloorKind,
+        arena: &mut PlanArena,
+    ) -> Result<ScalarExpression, DatabaseError> {
+        if !matches!(
+            field,
+            CeilFloorKind::DateTimeField(DateTimeField::NoDateTime)
+        ) {
+            return Err(DatabaseError::UnsupportedStmt(format!(
+                "{function_name}() with a scale or datetime field"
+            )));
+        }
+        let arg = self.bind_expr(expr, arena)?;
+        self.bind_function_call(function_name.to_string(), vec![arg], arena)
+    }
+
+    /// Binds `INTERVAL '1 day 2 hours'` and MySQL's `INTERVAL 3 DAY` to a constant.
+    fn bind_interval(
+        &mut self,
+        interval: &sqlparser::ast::Interval,
+        arena: &mut PlanArena,
+    ) -> Result<ScalarExpression, DatabaseError> {
+        if interval.last_field.is_some() || interval.fractional_seconds_precision.is_some() {
+            return Err(DatabaseError::UnsupportedStmt(format!(
+                "interval qualifier in `{interval}`"
+            )));
+        }
+        let Some(value) = self.bind_expr(&interval.value, arena)?.unpack_val() else {
+            return Err(DatabaseError::UnsupportedStmt(format!(
+                "non-constant value in `{interval}`"
+            )));
+        };
+        let value = match value {
+            DataValue::Null => return Ok(ScalarExpression::Constant(DataValue::Null)),
+            DataValue::Utf8 { value, .. } => value,
+            value => value.to_string(),
+        };
+        let interval = match &interval.leading_field {
+            Some(field) => Interval::parse_with_unit(&value, &field.to_string())?,
+            None => Interval::parse(&value)?,
+        };
+        Ok(ScalarExpression::Constant(DataValue::Interval(interval)))
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn bind_aggregate_call(
+        &mut self,
+        kind: AggKind,
+        function_name: &str,
+        mut args: Vec<ScalarExpression>,
+        is_distinct: bool,
+        filter: Option<&Expr>,
+        order_by: &[OrderByExpr],
+        separator: Option<&Value>,
+        arena: &mut PlanArena,
+    ) -> Result<ScalarExpression, DatabaseError> {
+        // MySQL's `GROUP_CONCAT` joins its values with `,` unless given a `SEPARATOR`
+        match (function_name, separator) {
+            ("group_concat", None) => {
+                args.push(ScalarExpression::Constant(DataValue::from(",".to_string())))
+            }
+            (
+                "group_concat",
+                Some(Value::SingleQuotedString(separator) | Value::DoubleQuotedString(separator)),
+            ) => args.push(ScalarExpression::Constant(DataValue::from(
+                separator.clone(),
+            ))),
+            (_, None) => (),
+            (_, Some(separator)) => {
+                return Err(DatabaseError::UnsupportedStmt(format!(
+                    "SEPARATOR {separator} is only supported by group_concat() with a string"
+                )))
+            }
+        }
+        let filter = filter
+            .map(|filter| self.bind_expr(filter, arena))
+            .transpose()?;
+        let order_by = order_by
+            .iter()
+            .map(|OrderByExpr { expr, options, .. }| {
+                Ok(SortField::new(
+                    self.bind_expr(expr, arena)?,
+                    options.asc.unwrap_or(true),
+                    options.nulls_first.unwrap_or(false),
+                ))
+            })
+            .collect::<Result<Vec<_>, DatabaseError>>()?;
+        self.bind_aggregate_function(kind, args, is_distinct, filter, order_by, arena)
+    }
+
     fn bind_window_call(
         &mut self,
         kind: WindowFunctionKind,
@@ -2474,10 +3837,10 @@ impl<'a, 'parent, T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<
     ) -> Result<ScalarExpression, DatabaseError> {
         if !matches!(
             self.context.step_now(),
-            QueryBindStep::Project | QueryBindStep::Sort
+            QueryBindStep::Project | QueryBindStep::Sort | QueryBindStep::Qualify
         ) {
             return Err(DatabaseError::UnsupportedStmt(
-                "window functions are only allowed in SELECT and ORDER BY".to_string(),
+                "window functions are only allowed in SELECT, QUALIFY and ORDER BY".to_string(),
             ));
         }
         if is_distinct {
@@ -2485,21 +3848,10 @@ impl<'a, 'parent, T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<
                 "DISTINCT window aggregates are not supported".to_string(),
             ));
         }
-        let WindowType::WindowSpec(spec) = over else {
-            return Err(DatabaseError::UnsupportedStmt(
-                "named windows are not supported".to_string(),
-            ));
+        let spec = match over {
+            WindowType::WindowSpec(spec) => resolve_window_spec(spec, &self.named_windows)?,
+            WindowType::NamedWindow(name) => named_window(name, &self.named_windows)?.clone(),
         };
-        if spec.window_name.is_some() {
-            return Err(DatabaseError::UnsupportedStmt(
-                "inherited named windows are not supported".to_string(),
-            ));
-        }
-        if spec.window_frame.is_some() {
-            return Err(DatabaseError::UnsupportedStmt(
-                "explicit window frames are not supported".to_string(),
-            ));
-        }
 
         let partition_by = spec
             .partition_by
@@ -2517,7 +3869,79 @@ impl<'a, 'parent, T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<
                 ))
             })
             .collect::<Result<Vec<_>, DatabaseError>>()?;
-        self.bind_window_function(kind, args, partition_by, order_by, arena)
+        let frame = spec
+            .window_frame
+            .as_ref()
+            .map(|frame| self.bind_window_frame_sql(frame, arena))
+            .transpose()?;
+        self.bind_window_function(kind, args, partition_by, order_by, frame, arena)
+    }
+
+    fn bind_window_frame_sql(
+        &mut self,
+        frame: &SqlWindowFrame,
+        arena: &mut PlanArena,
+    ) -> Result<WindowFrame, DatabaseError> {
+        let units = match frame.units {
+            SqlWindowFrameUnits::Rows => WindowFrameUnits::Rows,
+            SqlWindowFrameUnits::Range => WindowFrameUnits::Range,
+            SqlWindowFrameUnits::Groups => WindowFrameUnits::Groups,
+        };
+        let start = self.bind_window_frame_bound(&frame.start_bound, arena)?;
+        let end = match &frame.end_bound {
+            Some(bound) => self.bind_window_frame_bound(bound, arena)?,
+            None => WindowFrameBound::CurrentRow,
+        };
+        Ok(WindowFrame { units, start, end })
+    }
+
+    fn bind_window_frame_bound(
+        &mut self,
+        bound: &SqlWindowFrameBound,
+        arena: &mut PlanArena,
+    ) -> Result<WindowFrameBound, DatabaseError> {
+        let mut offset = |expr: &Expr| {
+            let mut offset = self.bind_expr(expr, arena)?;
+            ConstantCalculator::new(arena).visit(&mut offset)?;
+            let ScalarExpression::Constant(value) = offset else {
+                return Err(DatabaseError::InvalidValue(format!(
+                    "window frame offset must be a non-negative constant: {expr}"
+                )));
+            };
+            Ok(value)
+        };
+        Ok(match bound {
+            SqlWindowFrameBound::CurrentRow => WindowFrameBound::CurrentRow,
+            SqlWindowFrameBound::Preceding(None) => WindowFrameBound::UnboundedPreceding,
+            SqlWindowFrameBound::Preceding(Some(expr)) => {
+                WindowFrameBound::Preceding(offset(expr)?)
+            }
+            SqlWindowFrameBound::Following(None) => WindowFrameBound::UnboundedFollowing,
+            SqlWindowFrameBound::Following(Some(expr)) => {
+                WindowFrameBound::Following(offset(expr)?)
+            }
+        })
+    }
+
+    fn bind_named_windows(
+        definitions: &[NamedWindowDefinition],
+    ) -> Result<HashMap<String, WindowSpec>, DatabaseError> {
+        let mut named_windows = HashMap::with_capacity(definitions.len());
+        for NamedWindowDefinition(name, window) in definitions {
+            let spec = match window {
+                NamedWindowExpr::NamedWindow(base) => named_window(base, &named_windows)?.clone(),
+                NamedWindowExpr::WindowSpec(spec) => resolve_window_spec(spec, &named_windows)?,
+            };
+            if named_windows
+                .insert(lower_ident(name).into_owned(), spec)
+                .is_some()
+            {
+                return Err(DatabaseError::UnsupportedStmt(format!(
+                    "window `{name}` is defined more than once"
+                )));
+            }
+        }
+        Ok(named_windows)
     }
 
     pub fn bind_set_expr(
@@ -2569,6 +3993,7 @@ impl<'a, 'parent, T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<
         };
         let left_plan = {
             let mut left_binder = Binder::new(self.context.fork(), self.args, self.parent);
+            left_binder.ancestors = self.ancestors.clone();
             let plan = left_binder.bind_set_expr(left, arena)?;
             if left_binder.context.has_outer_refs() {
                 self.context.mark_outer_ref();
@@ -2578,6 +4003,7 @@ impl<'a, 'parent, T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<
 
         let right_plan = {
             let mut right_binder = Binder::new(self.context.fork(), self.args, self.parent);
+            right_binder.ancestors = self.ancestors.clone();
             let plan = right_binder.bind_set_expr(right, arena)?;
             if right_binder.context.has_outer_refs() {
                 self.context.mark_outer_ref();
@@ -2607,16 +4033,6 @@ impl<'a, 'parent, T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<
             qualify,
             ..
         } = select;
-        if !named_window.is_empty() {
-            return Err(DatabaseError::UnsupportedStmt(
-                "named windows are not supported".to_string(),
-            ));
-        }
-        if qualify.is_some() {
-            return Err(DatabaseError::UnsupportedStmt(
-                "QUALIFY is not supported".to_string(),
-            ));
-        }
         let has_hint = |expected: &str| {
             optimizer_hint.as_ref().is_some_and(|hint| {
                 hint.text
@@ -2626,21 +4042,29 @@ impl<'a, 'parent, T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<
         };
         let force_spill = has_hint("FORCE_AGG_SPILL");
         let force_nested_loop = has_hint("FORCE_NEST_LOOP_JOIN");
+        let force_merge_join = has_hint("FORCE_MERGE_JOIN");
         if force_spill && !cfg!(feature = "spill") {
             return Err(DatabaseError::UnsupportedStmt(
                 "FORCE_AGG_SPILL requires the `spill` feature".to_string(),
             ));
         }
-        let previous_options = (self.force_spill, self.force_nested_loop);
+        let named_windows = Self::bind_named_windows(named_window)?;
+        let previous_options = (
+            self.force_spill,
+            self.force_nested_loop,
+            self.force_merge_join,
+            mem::replace(&mut self.named_windows, named_windows),
+        );
         self.force_spill = force_spill;
         self.force_nested_loop = force_nested_loop;
+        self.force_merge_join = force_merge_join;
         let result = (|| {
             Ok(self
                 .build_plan(arena)
                 .from_sql(from)?
                 .select_list_from_sql(projection)?
                 .where_sql(selection.as_ref())?
-                .aggregate_sql(group_by, having.as_ref(), orderby)?
+                .aggregate_sql(group_by, having.as_ref(), qualify.as_ref(), orderby)?
                 .having()?
                 .window()?
                 .distinct_sql(distinct.as_ref())?
@@ -2649,7 +4073,12 @@ impl<'a, 'parent, T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<
                 .select_into_sql(into.as_ref())?
                 .finish())
         })();
-        (self.force_spill, self.force_nested_loop) = previous_options;
+        (
+            self.force_spill,
+            self.force_nested_loop,
+            self.force_merge_join,
+            self.named_windows,
+        ) = previous_options;
         result
     }
 
@@ -2839,8 +4268,8 @@ impl<'a, 'parent, T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<
     ) -> Result<LogicalPlan, DatabaseError> {
         let origin_step = self.context.step_now();
 
-        if let Some(_with) = &query.with {
-            // TODO support with clause.
+        if let Some(with) = &query.with {
+            self.bind_with(with, arena)?;
         }
 
         let order_by_exprs = if let Some(order_by) = &query.order_by {
@@ -2887,6 +4316,111 @@ impl<'a, 'parent, T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<
         Ok(plan)
     }
 
+    fn bind_with(&mut self, with: &With, arena: &mut PlanArena) -> Result<(), DatabaseError> {
+        for cte in &with.cte_tables {
+            let TableAliasInput { name, columns } = sql_table_alias(cte.alias.clone());
+            let recursive_body = match cte.query.body.as_ref() {
+                SetExpr::SetOperation {
+                    op: SetOperator::Union,
+                    set_quantifier,
+                    left,
+                    right,
+                } if with.recursive => Some((set_quantifier, left, right)),
+                _ => None,
+            };
+            let plan = if let Some((set_quantifier, left, right)) = recursive_body {
+                if cte.query.order_by.is_some() || cte.query.limit_clause.is_some() {
+                    return Err(DatabaseError::UnsupportedStmt(
+                        "ORDER BY or LIMIT in a recursive query".to_string(),
+                    ));
+                }
+                self.bind_recursive_cte_query(&name, &columns, set_quantifier, left, right, arena)?
+            } else {
+                let mut binder = Binder::new(self.context.fork(), self.args, self.parent);
+                binder.ancestors = self.ancestors.clone();
+                let plan = binder.bind_query(&cte.query, arena)?;
+                if binder.context.has_outer_refs() {
+                    self.context.mark_outer_ref();
+                }
+                plan
+            };
+            self.context
+                .add_cte(name, CommonTableExpr { plan, columns });
+        }
+        Ok(())
+    }
+
+    fn bind_recursive_cte_query(
+        &mut self,
+        name: &TableName,
+        columns: &[String],
+        set_quantifier: &SetQuantifier,
+        left: &SetExpr,
+        right: &SetExpr,
+        arena: &mut PlanArena,
+    ) -> Result<LogicalPlan, DatabaseError> {
+        let is_all = match set_quantifier {
+            SetQuantifier::All => true,
+            SetQuantifier::Distinct | SetQuantifier::None => false,
+            SetQuantifier::ByName | SetQuantifier::AllByName | SetQuantifier::DistinctByName => {
+                return Err(DatabaseError::UnsupportedStmt(
+                    "set quantifier BY NAME is not supported".to_string(),
+                ))
+            }
+        };
+        let mut anchor_plan = {
+            let mut anchor_binder = Binder::new(self.context.fork(), self.args, self.parent);
+            anchor_binder.ancestors = self.ancestors.clone();
+            let plan = anchor_binder.bind_set_expr(left, arena)?;
+            if anchor_binder.context.has_outer_refs() {
+                self.context.mark_outer_ref();
+            }
+            plan
+        };
+        let working_table = arena.temp_table();
+        let working_scan =
+            self.bind_cte_working_table(working_table.clone(), &mut anchor_plan, arena);
+
+        let recursive_plan = {
+            let mut recursive_context = self.context.fork();
+            recursive_context.add_cte(
+                name.clone(),
+                CommonTableExpr {
+                    plan: working_scan,
+                    columns: columns.to_vec(),
+                },
+            );
+            let mut recursive_binder = Binder::new(recursive_context, self.args, self.parent);
+            recursive_binder.ancestors = self.ancestors.clone();
+            let plan = recursive_binder.bind_set_expr(right, arena)?;
+            if recursive_binder.context.has_outer_refs() {
+                self.context.mark_outer_ref();
+            }
+            plan
+        };
+
+        match recursive_plan.cte_reference_count(&working_table) {
+            0 => self.bind_set_operation_plans(
+                SetOperatorKind::Union,
+                is_all,
+                anchor_plan,
+                recursive_plan,
+                arena,
+            ),
+            1 => {
+                if recursive_plan.aggregates_cte(&working_table) {
+                    return Err(DatabaseError::UnsupportedStmt(
+                        "aggregate functions in a recursive query's recursive term".to_string(),
+                    ));
+                }
+                self.bind_recursive_cte(working_table, is_all, anchor_plan, recursive_plan, arena)
+            }
+            _ => Err(DatabaseError::UnsupportedStmt(format!(
+                "recursive reference to query `{name}` must not appear more than once"
+            ))),
+        }
+    }
+
     fn bind_non_negative_limit_value(
         &mut self,
         expr: &Expr,
@@ -3019,6 +4553,43 @@ mod tests {
             command_type(&prepare("truncate table t")?)?,
             CommandType::DML
         );
+        assert_eq!(
+            command_type(&prepare("explain insert into t values (1)")?)?,
+            CommandType::DQL
+        );
+        assert_eq!(
+            command_type(&prepare("explain analyze insert into t values (1)")?)?,
+            CommandType::DML
+        );
+        assert_eq!(
+            command_type(&prepare("explain analyze select 1")?)?,
+            CommandType::DQL
+        );
+        let err = command_type(&prepare(
+            "explain analyze create table t (id int primary key)",
+        )?)
+        .unwrap_err();
+        assert_unsupported(err, "EXPLAIN ANALYZE");
+        assert_eq!(
+            command_type(&prepare("explain (format json) insert into t values (1)")?)?,
+            CommandType::DQL
+        );
+        assert_eq!(
+            command_type(&prepare(
+                "explain (analyze, format json) insert into t values (1)"
+            )?)?,
+            CommandType::DML
+        );
+        assert_eq!(
+            command_type(&prepare(
+                "explain (analyze false) insert into t values (1)"
+            )?)?,
+            CommandType::DQL
+        );
+        let err = command_type(&prepare("explain format graphviz select 1")?).unwrap_err();
+        assert_unsupported(err, "FORMAT GRAPHVIZ");
+        let err = command_type(&prepare("explain (costs) select 1")?).unwrap_err();
+        assert_unsupported(err, "costs");
 
         let err = command_type(&prepare("start transaction")?).unwrap_err();
         assert_unsupported(err, "START TRANSACTION");
@@ -3112,6 +4683,7 @@ mod tests {
         }];
         let scala_functions = Default::default();
         let table_functions = Default::default();
+        let aggregate_functions = Default::default();
         let transaction = tables.storage.transaction()?;
         let mut binder = Binder::new(
             BinderContext::new(
@@ -3120,6 +4692,7 @@ mod tests {
                 &transaction,
                 &scala_functions,
                 &table_functions,
+                &aggregate_functions,
             ),
             &[],
             None,
@@ -3175,6 +4748,26 @@ mod tests {
             .expect("query should contain a join");
 
         assert!(join.force_nested_loop);
+        assert!(!join.force_merge_join);
+        Ok(())
+    }
+
+    #[test]
+    fn force_merge_join_marks_join_operator() -> Result<(), DatabaseError> {
+        let tables = build_t1_table()?;
+        let plan =
+            tables.plan("select /*+ FORCE_MERGE_JOIN */ c1, c3 from t1 join t2 on c1 = c3")?;
+        let join = plan
+            .childrens
+            .iter()
+            .find_map(|plan| match &plan.operator {
+                Operator::Join(operator) => Some(operator),
+                _ => None,
+            })
+            .expect("query should contain a join");
+
+        assert!(join.force_merge_join);
+        assert!(!join.force_nested_loop);
         Ok(())
     }
 
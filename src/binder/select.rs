@@ -24,9 +24,12 @@ use crate::{
     },
     types::value::DataValue,
 };
+use std::borrow::Cow;
 use std::collections::HashSet;
 
-use super::{Binder, BinderContext, QueryBindStep, SetOperatorKind, Source, SubQueryType};
+use super::{
+    Binder, BinderContext, BoundSource, QueryBindStep, SetOperatorKind, Source, SubQueryType,
+};
 
 use crate::catalog::{ColumnRef, ColumnRelation, TableName};
 use crate::errors::DatabaseError;
@@ -34,9 +37,11 @@ use crate::execution::dql::join::joins_nullable;
 use crate::expression::visitor_mut::{walk_mut_expr, ExprVisitorMut, PositionShift};
 use crate::expression::{AliasType, BinaryOperator};
 use crate::iter_ext::Itertools;
+use crate::planner::operator::cte_scan::CteScanOperator;
 use crate::planner::operator::function_scan::FunctionScanOperator;
 use crate::planner::operator::insert::InsertOperator;
 use crate::planner::operator::join::JoinCondition;
+use crate::planner::operator::recursive_cte::RecursiveCteOperator;
 use crate::planner::operator::set_membership::{SetMembershipKind, SetMembershipOperator};
 use crate::planner::operator::sort::{SortField, SortOperator};
 use crate::planner::operator::union::UnionOperator;
@@ -68,6 +73,9 @@ impl<'a> ExprVisitorMut<'a> for RightSidePositionGlobalizer<'_, '_> {
     }
 }
 
+// The right input, predicates and parameters of a mark apply
+type MarkApplyInput = (LogicalPlan, Vec<ScalarExpression>, Vec<ScalarExpression>);
+
 struct AppendedRightOutput {
     column: ColumnRef,
     child_position: usize,
@@ -217,6 +225,7 @@ where
     plan: LogicalPlan,
     select_list: Vec<ScalarExpression>,
     having: Option<ScalarExpression>,
+    qualify: Option<ScalarExpression>,
     orderby: Option<Vec<SortField>>,
 }
 
@@ -229,6 +238,7 @@ where
     arena: &'s mut crate::planner::PlanArena<'arena>,
     plan: LogicalPlan,
     select_list: Vec<ScalarExpression>,
+    qualify: Option<ScalarExpression>,
     orderby: Option<Vec<SortField>>,
 }
 
@@ -272,8 +282,9 @@ where
     T: Transaction,
     A: AsRef<[(&'static str, DataValue)]>,
 {
+    binder: &'s mut Binder<'a, 'b, T, A>,
+    arena: &'s mut crate::planner::PlanArena<'arena>,
     plan: LogicalPlan,
-    _marker: std::marker::PhantomData<(&'s (), &'a (), &'b (), &'arena (), T, A)>,
 }
 
 pub(crate) struct BindPlanComplete {
@@ -364,6 +375,8 @@ where
             .filter_expr(None)?
             .aggregate(
                 vec![expr],
+                Vec::new(),
+                None,
                 None,
                 None::<Vec<SortField>>,
                 |_binder, _arena, order| Ok(order),
@@ -387,6 +400,8 @@ where
             .filter_expr(None)?
             .aggregate(
                 Vec::new(),
+                Vec::new(),
+                None,
                 None,
                 None::<Vec<SortField>>,
                 |_binder, _arena, order| Ok(order),
@@ -499,6 +514,13 @@ where
     }
 }
 
+fn correlated_select_list_in_aggregate() -> DatabaseError {
+    DatabaseError::UnsupportedStmt(
+        "correlated subqueries in the SELECT list of a query with GROUP BY or aggregates are not supported"
+            .to_string(),
+    )
+}
+
 impl<'s, 'a: 'b, 'b, 'arena, T, A> BindPlanFiltered<'s, 'a, 'b, 'arena, T, A>
 where
     T: Transaction,
@@ -507,7 +529,9 @@ where
     pub(crate) fn aggregate<O>(
         mut self,
         group_by: Vec<ScalarExpression>,
+        grouping_sets: Vec<Vec<usize>>,
         having: Option<ScalarExpression>,
+        mut qualify: Option<ScalarExpression>,
         orderby: Option<impl IntoIterator<Item = O>>,
         mut bind_sort_field: impl FnMut(
             &mut Binder<'a, 'b, T, A>,
@@ -519,7 +543,21 @@ where
             .extract_select_join(&mut self.select_list, self.arena);
         self.binder
             .extract_select_aggregate(&mut self.select_list)?;
+        // Tips: the select-list subqueries are applied above the aggregation, where the columns
+        // they correlate on are no longer available
+        let correlated_select_list = self
+            .binder
+            .context
+            .has_correlated_sub_query(QueryBindStep::Project);
+        if correlated_select_list
+            && (!group_by.is_empty()
+                || !grouping_sets.is_empty()
+                || !self.binder.context.agg_calls.is_empty())
+        {
+            return Err(correlated_select_list_in_aggregate());
+        }
 
+        let group_by_len = group_by.len();
         if !group_by.is_empty() {
             self.binder
                 .extract_group_by_aggregate_exprs(&mut self.select_list, group_by)?;
@@ -533,15 +571,30 @@ where
                 |binder, orderby| bind_sort_field(binder, self.arena, orderby),
             )?;
         }
+        if let Some(qualify) = &qualify {
+            self.binder.collect_aggregate_calls(qualify)?;
+        }
         if !self.binder.context.agg_calls.is_empty()
             || !self.binder.context.group_by_exprs.is_empty()
+            || !grouping_sets.is_empty()
         {
+            if correlated_select_list {
+                return Err(correlated_select_list_in_aggregate());
+            }
             let agg_calls = std::mem::take(&mut self.binder.context.agg_calls);
             let group_by_exprs = std::mem::take(&mut self.binder.context.group_by_exprs);
+            if !grouping_sets.is_empty() && group_by_exprs.len() != group_by_len {
+                return Err(DatabaseError::AggMiss(
+                    "in the GROUP BY clause the field must be in the select clause".to_string(),
+                ));
+            }
+            self.binder
+                .validate_grouping_calls(&agg_calls, &group_by_exprs)?;
             let output_exprs = self
                 .select_list
                 .iter_mut()
                 .chain(having_orderby.0.iter_mut())
+                .chain(qualify.iter_mut())
                 .chain(
                     having_orderby
                         .1
@@ -554,9 +607,9 @@ where
                 output_exprs,
                 self.arena,
             )?;
-            self.plan = self
-                .binder
-                .bind_aggregate(self.plan, agg_calls, group_by_exprs)?;
+            self.plan =
+                self.binder
+                    .bind_aggregate(self.plan, agg_calls, group_by_exprs, grouping_sets)?;
         }
 
         Ok(BindPlanAggregated {
@@ -565,6 +618,7 @@ where
             plan: self.plan,
             select_list: self.select_list,
             having: having_orderby.0,
+            qualify,
             orderby: having_orderby.1,
         })
     }
@@ -587,6 +641,7 @@ where
             arena: self.arena,
             plan: self.plan,
             select_list: self.select_list,
+            qualify: self.qualify,
             orderby: self.orderby,
         })
     }
@@ -604,6 +659,7 @@ where
             self.plan,
             &mut self.select_list,
             &mut self.orderby,
+            self.qualify,
             self.arena,
         )?;
 
@@ -627,6 +683,19 @@ where
         distinct: bool,
     ) -> Result<BindPlanDistinct<'s, 'a, 'b, 'arena, T, A>, DatabaseError> {
         if distinct {
+            // DISTINCT compares the outputs of SELECT list subqueries, so they are joined first
+            let exprs = self
+                .select_list
+                .iter_mut()
+                .chain(
+                    self.orderby
+                        .iter_mut()
+                        .flat_map(|fields| fields.iter_mut().map(|field| &mut field.expr)),
+                )
+                .collect();
+            self.plan = self
+                .binder
+                .bind_select_list_subqueries(self.plan, exprs, self.arena)?;
             let distinct_outputs = self.select_list.clone();
             self.binder.bind_distinct_output_exprs(
                 &distinct_outputs,
@@ -686,8 +755,9 @@ where
         }
 
         Ok(BindPlanProjected {
+            binder: self.binder,
+            arena: self.arena,
             plan: self.plan,
-            _marker: std::marker::PhantomData,
         })
     }
 }
@@ -702,11 +772,15 @@ where
         table_name: Option<TableName>,
     ) -> Result<BindPlanComplete, DatabaseError> {
         if let Some(table_name) = table_name {
+            let checks = self.binder.bind_table_checks(&table_name, self.arena)?;
             self.plan = LogicalPlan::new(
                 Operator::Insert(InsertOperator {
                     table_name,
                     is_overwrite: false,
                     is_mapping_by_name: true,
+                    on_conflict: None,
+                    returning: vec![],
+                    checks,
                 }),
                 Childrens::Only(Box::new(self.plan)),
             )
@@ -733,7 +807,7 @@ impl<'a: 'b, 'b, T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'
         }
     }
 
-    fn is_temp_alias_projection(
+    pub(super) fn is_temp_alias_projection(
         exprs: &[ScalarExpression],
         arena: &crate::planner::PlanArena,
     ) -> bool {
@@ -778,11 +852,31 @@ impl<'a: 'b, 'b, T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'
     pub(crate) fn resolve_source_columns_in_scope<'context>(
         context: &'context BinderContext<'a, T>,
         table_name: &str,
+    ) -> Result<(&'context Source<'a>, usize), DatabaseError> {
+        Self::resolve_source_columns_in_scope_by(context, table_name, BoundSource::matches_name)
+    }
+
+    /// Like `resolve_source_columns_in_scope`, but an aliased source is only found by its
+    /// alias, as a qualifier written in the query must: `t.id` in `from t t2` refers to an
+    /// outer `t`.
+    pub(crate) fn resolve_visible_source_columns_in_scope<'context>(
+        context: &'context BinderContext<'a, T>,
+        table_name: &str,
+    ) -> Result<(&'context Source<'a>, usize), DatabaseError> {
+        Self::resolve_source_columns_in_scope_by(context, table_name, |bound_source, name| {
+            bound_source.visible_name().as_ref() == name
+        })
+    }
+
+    fn resolve_source_columns_in_scope_by<'context>(
+        context: &'context BinderContext<'a, T>,
+        table_name: &str,
+        matches: impl Fn(&BoundSource<'a>, &str) -> bool,
     ) -> Result<(&'context Source<'a>, usize), DatabaseError> {
         let mut position_offset = 0;
 
         for bound_source in &context.bind_table {
-            if bound_source.matches_name(table_name) {
+            if matches(bound_source, table_name) {
                 return Ok((&bound_source.source, position_offset));
             }
 
@@ -869,7 +963,7 @@ impl<'a: 'b, 'b, T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'
         Ok(())
     }
 
-    fn rebind_split_scope_positions(
+    pub(super) fn rebind_split_scope_positions(
         expr: &mut ScalarExpression,
         left_schema: &Schema,
         right_schema: &Schema,
@@ -883,7 +977,7 @@ impl<'a: 'b, 'b, T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'
         .visit(expr)
     }
 
-    fn build_join_from_split_scope_predicates(
+    pub(super) fn build_join_from_split_scope_predicates(
         &self,
         mut children: LogicalPlan,
         mut plan: LogicalPlan,
@@ -933,6 +1027,7 @@ impl<'a: 'b, 'b, T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'
             join_condition,
             join_ty,
             self.force_nested_loop,
+            self.force_merge_join,
         ))
     }
 
@@ -1190,6 +1285,25 @@ impl<'a: 'b, 'b, T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'
         alias: Option<TableAliasInput>,
         arena: &mut crate::planner::PlanArena,
     ) -> Result<LogicalPlan, DatabaseError> {
+        if let Some(cte) = self.context.cte(&table_name) {
+            let alias = match alias {
+                Some(alias) if !alias.columns.is_empty() => alias,
+                Some(alias) => TableAliasInput {
+                    name: alias.name,
+                    columns: cte.columns.clone(),
+                },
+                None => TableAliasInput {
+                    name: table_name,
+                    columns: cte.columns.clone(),
+                },
+            };
+            return self.bind_derived_source(
+                LogicalPlan::clone(&cte.plan),
+                Some(alias),
+                join_type,
+                arena,
+            );
+        }
         let table_alias = alias.as_ref().map(|alias| alias.name.clone());
 
         let with_pk = self.is_scan_with_pk(&table_name);
@@ -1283,6 +1397,88 @@ impl<'a: 'b, 'b, T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'
         Ok(plan)
     }
 
+    pub(crate) fn bind_cte_working_table(
+        &mut self,
+        table_name: TableName,
+        anchor_plan: &mut LogicalPlan,
+        arena: &mut crate::planner::PlanArena,
+    ) -> LogicalPlan {
+        let anchor_schema = anchor_plan.output_schema(arena).clone();
+        let schema_ref = anchor_schema
+            .into_iter()
+            .map(|column| {
+                let column_catalog = arena.column(column);
+                let mut working_column = column_catalog.clone();
+                working_column.set_ref_table(
+                    table_name.clone(),
+                    column_catalog.id().unwrap_or_default(),
+                    true,
+                );
+                arena.alloc_column(working_column)
+            })
+            .collect_vec();
+
+        CteScanOperator::build(table_name, schema_ref)
+    }
+
+    pub(crate) fn bind_recursive_cte(
+        &mut self,
+        table_name: TableName,
+        is_all: bool,
+        mut anchor_plan: LogicalPlan,
+        mut recursive_plan: LogicalPlan,
+        arena: &mut crate::planner::PlanArena,
+    ) -> Result<LogicalPlan, DatabaseError> {
+        let anchor_schema = anchor_plan.output_schema(arena).clone();
+        let recursive_schema = recursive_plan.output_schema(arena).clone();
+
+        if anchor_schema.len() != recursive_schema.len() {
+            return Err(DatabaseError::MisMatch(
+                "the lens on the left",
+                "the lens on the right",
+            ));
+        }
+        // Tips: the working table keeps the anchor types, so every iteration is cast back to them
+        if anchor_schema
+            .iter()
+            .zip(recursive_schema.iter())
+            .any(|(left, right)| arena.column(*left).datatype() != arena.column(*right).datatype())
+        {
+            let mut cast_exprs = Vec::with_capacity(recursive_schema.len());
+            for (position, (left, right)) in anchor_schema
+                .iter()
+                .zip(recursive_schema.iter())
+                .enumerate()
+            {
+                let left_type = arena.column(*left).datatype().clone();
+                let expr = ScalarExpression::column_expr(*right, position);
+                if &left_type != arena.column(*right).datatype() {
+                    cast_exprs.push(ScalarExpression::type_cast(
+                        expr,
+                        Cow::Owned(left_type),
+                        arena,
+                    )?);
+                } else {
+                    cast_exprs.push(expr);
+                }
+            }
+            recursive_plan = LogicalPlan::new(
+                Operator::Project(ProjectOperator { exprs: cast_exprs }),
+                Childrens::Only(Box::new(recursive_plan)),
+            );
+        }
+        let recursive_schema = recursive_plan.output_schema(arena).clone();
+
+        Ok(RecursiveCteOperator::build(
+            table_name,
+            is_all,
+            anchor_schema,
+            recursive_schema,
+            anchor_plan,
+            recursive_plan,
+        ))
+    }
+
     pub(crate) fn bind_table_function_source(
         &mut self,
         expr: ScalarExpression,
@@ -1344,22 +1540,20 @@ impl<'a: 'b, 'b, T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'
 
         let mut pushed_alias_columns = false;
 
-        for alias_column in context
+        let alias_columns = context
             .expr_aliases
             .keys()
             .filter_map(|(alias_table, alias_column)| {
                 matches!(alias_table.as_deref(), Some(alias) if alias == table_name.as_ref())
                     .then_some(alias_column.as_str())
             })
-        {
-            let Some((position, column)) = source
-                .schema()
-                .iter()
-                .enumerate()
-                .find(|(_, column)| arena.column(**column).name() == alias_column)
-            else {
+            .collect::<HashSet<_>>();
+
+        // Tips: walk the source schema rather than the alias map, so `*` keeps the projection order
+        for (position, column) in source.schema().iter().enumerate() {
+            if !alias_columns.contains(arena.column(*column).name()) {
                 continue;
-            };
+            }
             if !fn_not_on_using(column) {
                 continue;
             }
@@ -1411,6 +1605,7 @@ impl<'a: 'b, 'b, T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'
             on,
             join_type,
             self.force_nested_loop,
+            self.force_merge_join,
         ))
     }
 
@@ -1423,7 +1618,30 @@ impl<'a: 'b, 'b, T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'
         self.context.step(QueryBindStep::Where);
 
         if let Some(sub_queries) = self.context.sub_queries_at_now() {
-            let mut uses_mark_apply = None;
+            if sub_queries.iter().all(|sub_query| {
+                matches!(
+                    sub_query,
+                    SubQueryType::SubQuery {
+                        correlated: false,
+                        ..
+                    }
+                )
+            }) {
+                for sub_query in sub_queries {
+                    let SubQueryType::SubQuery { plan, .. } = sub_query else {
+                        unreachable!("only uncorrelated scalar subqueries are matched above")
+                    };
+                    children = self.build_join_from_split_scope_predicates(
+                        children,
+                        plan,
+                        JoinType::Inner,
+                        std::iter::once(predicate.clone()),
+                        true,
+                        arena,
+                    )?;
+                }
+                return Ok(children);
+            }
             for sub_query in sub_queries {
                 match sub_query {
                     SubQueryType::ExistsSubQuery {
@@ -1431,15 +1649,8 @@ impl<'a: 'b, 'b, T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'
                         correlated,
                         output_column,
                     } => {
-                        if matches!(uses_mark_apply, Some(false)) {
-                            return Err(DatabaseError::UnsupportedStmt(
-                                "mixed EXISTS/IN with other WHERE subqueries is not supported yet"
-                                    .to_string(),
-                            ));
-                        }
-                        uses_mark_apply = Some(true);
                         let left_schema = children.output_schema(arena).clone();
-                        let (plan, predicates) = Self::prepare_mark_apply(
+                        let (plan, predicates, parameters) = Self::prepare_mark_apply(
                             &mut predicate,
                             &output_column,
                             left_schema.as_ref(),
@@ -1455,6 +1666,7 @@ impl<'a: 'b, 'b, T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'
                             output_column,
                             predicates,
                         );
+                        Self::set_mark_apply_parameters(&mut children, parameters);
                     }
                     SubQueryType::QuantifiedSubQuery {
                         quantifier,
@@ -1464,19 +1676,12 @@ impl<'a: 'b, 'b, T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'
                         predicate: mut quantified_predicate,
                         ..
                     } => {
-                        if matches!(uses_mark_apply, Some(false)) {
-                            return Err(DatabaseError::UnsupportedStmt(
-                                "mixed EXISTS/IN with other WHERE subqueries is not supported yet"
-                                    .to_string(),
-                            ));
-                        }
-                        uses_mark_apply = Some(true);
                         if correlated {
                             quantified_predicate =
                                 Self::rewrite_correlated_quantified_predicate(quantified_predicate);
                         }
                         let left_schema = children.output_schema(arena).clone();
-                        let (plan, predicates) = Self::prepare_mark_apply(
+                        let (plan, predicates, parameters) = Self::prepare_mark_apply(
                             &mut predicate,
                             &output_column,
                             left_schema.as_ref(),
@@ -1493,53 +1698,52 @@ impl<'a: 'b, 'b, T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'
                             output_column,
                             predicates,
                         );
+                        Self::set_mark_apply_parameters(&mut children, parameters);
                     }
-                    SubQueryType::SubQuery { plan, correlated } => {
-                        if matches!(uses_mark_apply, Some(true)) {
-                            return Err(DatabaseError::UnsupportedStmt(
-                                "mixed EXISTS/IN with other WHERE subqueries is not supported yet"
-                                    .to_string(),
-                            ));
-                        }
-                        uses_mark_apply = Some(false);
-                        if correlated {
-                            return Err(DatabaseError::UnsupportedStmt(
-                                "correlated scalar subqueries in WHERE are not supported"
-                                    .to_string(),
-                            ));
-                        }
-                        children = self.build_join_from_split_scope_predicates(
-                            children,
-                            plan,
-                            JoinType::Inner,
-                            std::iter::once(predicate.clone()),
-                            true,
+                    SubQueryType::SubQuery {
+                        mut plan,
+                        correlated,
+                    } => {
+                        let left_len = children.output_schema(arena).len();
+                        let right_schema = plan.output_schema(arena).clone();
+                        RightSidePositionGlobalizer {
+                            right_schema: &right_schema,
+                            left_len,
                             arena,
-                        )?;
+                        }
+                        .visit(&mut predicate)?;
+                        children = if correlated {
+                            self.bind_correlated_scalar_subquery(children, plan, arena)?
+                        } else {
+                            ScalarApplyOperator::build(children, plan)
+                        };
                     }
                 }
             }
-            if matches!(uses_mark_apply, Some(true)) {
-                let passthrough_exprs = children
-                    .output_schema(arena)
-                    .iter()
-                    .cloned()
-                    .enumerate()
-                    .map(|(position, column)| ScalarExpression::column_expr(column, position))
-                    .collect();
-                let filter = FilterOperator::build(predicate, children, false);
-                return Ok(LogicalPlan::new(
-                    Operator::Project(ProjectOperator {
-                        exprs: passthrough_exprs,
-                    }),
-                    Childrens::Only(Box::new(filter)),
-                ));
-            }
-            return Ok(children);
+            let passthrough_exprs = children
+                .output_schema(arena)
+                .iter()
+                .cloned()
+                .enumerate()
+                .map(|(position, column)| ScalarExpression::column_expr(column, position))
+                .collect();
+            let filter = FilterOperator::build(predicate, children, false);
+            return Ok(LogicalPlan::new(
+                Operator::Project(ProjectOperator {
+                    exprs: passthrough_exprs,
+                }),
+                Childrens::Only(Box::new(filter)),
+            ));
         }
         Ok(FilterOperator::build(predicate, children, false))
     }
 
+    fn set_mark_apply_parameters(plan: &mut LogicalPlan, parameters: Vec<ScalarExpression>) {
+        if let Operator::MarkApply(op) = &mut plan.operator {
+            op.set_parameters(parameters);
+        }
+    }
+
     fn ensure_mark_apply_right_outputs(
         plan: &mut LogicalPlan,
         predicates: &[ScalarExpression],
@@ -1598,7 +1802,7 @@ impl<'a: 'b, 'b, T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'
         preserve_projection: bool,
         mut apply_predicates: Vec<ScalarExpression>,
         arena: &mut crate::planner::PlanArena,
-    ) -> Result<(LogicalPlan, Vec<ScalarExpression>), DatabaseError> {
+    ) -> Result<MarkApplyInput, DatabaseError> {
         let left_len = left_schema.len();
         MarkerPositionGlobalizer {
             output_column,
@@ -1607,12 +1811,30 @@ impl<'a: 'b, 'b, T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'
         }
         .visit(predicate)?;
 
-        let (mut plan, correlated_filters) = if correlated {
-            Self::prepare_correlated_subquery_plan(plan, left_schema, preserve_projection, arena)?
+        let mut parameters = Vec::new();
+        let mut plan = if !correlated {
+            plan
+        } else if Self::can_pull_up_correlated_filters(
+            &plan,
+            left_schema,
+            preserve_projection,
+            !preserve_projection,
+            arena,
+        )? {
+            let (plan, correlated_filters) = Self::prepare_correlated_subquery_plan(
+                plan,
+                left_schema,
+                preserve_projection,
+                arena,
+            )?;
+            apply_predicates.extend(correlated_filters);
+            plan
         } else {
-            (plan, Vec::new())
+            // Correlation inside joins, set operations or aggregates stays in the subquery,
+            // which is then re-executed with the outer columns of every row
+            parameters = Self::correlated_parameters(&plan, left_schema, arena)?;
+            plan
         };
-        apply_predicates.extend(correlated_filters);
 
         if correlated {
             let appended_right_outputs =
@@ -1635,7 +1857,7 @@ impl<'a: 'b, 'b, T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'
             .visit(expr)?;
         }
 
-        Ok((plan, apply_predicates))
+        Ok((plan, apply_predicates, parameters))
     }
 
     fn rewrite_correlated_quantified_predicate(predicate: ScalarExpression) -> ScalarExpression {
@@ -1665,19 +1887,26 @@ impl<'a: 'b, 'b, T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'
         }
     }
 
-    fn plan_has_correlated_refs(
-        plan: &LogicalPlan,
+    pub(super) fn operator_has_correlated_refs(
+        operator: &Operator,
         left_schema: &Schema,
         arena: &mut crate::planner::PlanArena,
     ) -> Result<bool, DatabaseError> {
-        if !plan
-            .operator
-            .visit_referenced_columns(arena, &mut |arena, column| {
+        Ok(
+            !operator.visit_referenced_columns(arena, &mut |arena, column| {
                 !left_schema
                     .iter()
                     .any(|left| arena.same_column(*left, *column))
-            })?
-        {
+            })?,
+        )
+    }
+
+    pub(super) fn plan_has_correlated_refs(
+        plan: &LogicalPlan,
+        left_schema: &Schema,
+        arena: &mut crate::planner::PlanArena,
+    ) -> Result<bool, DatabaseError> {
+        if Self::operator_has_correlated_refs(&plan.operator, left_schema, arena)? {
             return Ok(true);
         }
 
@@ -1694,7 +1923,7 @@ impl<'a: 'b, 'b, T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'
         }
     }
 
-    fn expr_has_correlated_refs(
+    pub(super) fn expr_has_correlated_refs(
         expr: &ScalarExpression,
         left_schema: &Schema,
         arena: &mut crate::planner::PlanArena,
@@ -1733,7 +1962,7 @@ impl<'a: 'b, 'b, T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'
             })
     }
 
-    fn prepare_correlated_subquery_plan(
+    pub(super) fn prepare_correlated_subquery_plan(
         plan: LogicalPlan,
         left_schema: &Schema,
         preserve_projection: bool,
@@ -1866,46 +2095,83 @@ impl<'a: 'b, 'b, T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'
 
     pub(crate) fn bind_project(
         &mut self,
-        mut children: LogicalPlan,
+        children: LogicalPlan,
         mut select_list: Vec<ScalarExpression>,
         arena: &mut crate::planner::PlanArena,
+    ) -> Result<LogicalPlan, DatabaseError> {
+        let children =
+            self.bind_select_list_subqueries(children, select_list.iter_mut().collect(), arena)?;
+
+        Ok(Self::build_project_plan(children, select_list))
+    }
+
+    /// Joins the scalar subqueries of the SELECT list to `children`. `exprs` are the SELECT list
+    /// and the other expressions above the join that read the subquery outputs, they are
+    /// rebound to the joined schema.
+    pub(crate) fn bind_select_list_subqueries(
+        &mut self,
+        mut children: LogicalPlan,
+        mut exprs: Vec<&mut ScalarExpression>,
+        arena: &mut crate::planner::PlanArena,
     ) -> Result<LogicalPlan, DatabaseError> {
         self.context.step(QueryBindStep::Project);
 
-        if let Some(sub_queries) = self.context.sub_queries_at_now() {
-            for sub_query in sub_queries {
-                let SubQueryType::SubQuery {
-                    mut plan,
-                    correlated,
-                } = sub_query
-                else {
-                    return Err(DatabaseError::UnsupportedStmt(
-                        "only scalar subqueries are supported in SELECT list".to_string(),
-                    ));
-                };
+        let Some(sub_queries) = self.context.sub_queries_at_now() else {
+            return Ok(children);
+        };
+        // Subqueries are joined beneath `ORDER BY`, since a decorrelated join does not keep
+        // the order of its left input; each of them yields exactly one row per left row.
+        let mut sort = match children.operator {
+            Operator::Sort(_) => {
+                let sort = children.take();
+                children = (*sort.childrens).pop_only();
+                Some(sort.operator)
+            }
+            _ => None,
+        };
+        for sub_query in sub_queries {
+            let SubQueryType::SubQuery {
+                mut plan,
+                correlated,
+            } = sub_query
+            else {
+                return Err(DatabaseError::UnsupportedStmt(
+                    "only scalar subqueries are supported in SELECT list".to_string(),
+                ));
+            };
 
-                if correlated {
-                    return Err(DatabaseError::UnsupportedStmt(
-                        "correlated scalar subqueries in SELECT list are not supported".to_string(),
-                    ));
+            let left_len = children.output_schema(arena).len();
+            let right_schema = plan.output_schema(arena);
+            // sort keys may name a subquery by its alias in the SELECT list
+            let sort_exprs = match &mut sort {
+                Some(Operator::Sort(op)) => {
+                    Some(op.sort_fields.iter_mut().map(|field| &mut field.expr))
                 }
-
-                let left_len = children.output_schema(arena).len();
-                let right_schema = plan.output_schema(arena);
-                for expr in select_list.iter_mut() {
-                    RightSidePositionGlobalizer {
-                        right_schema,
-                        left_len,
-                        arena,
-                    }
-                    .visit(expr)?;
+                _ => None,
+            };
+            for expr in exprs
+                .iter_mut()
+                .map(|expr| &mut **expr)
+                .chain(sort_exprs.into_iter().flatten())
+            {
+                RightSidePositionGlobalizer {
+                    right_schema,
+                    left_len,
+                    arena,
                 }
-
-                children = ScalarApplyOperator::build(children, plan);
+                .visit(expr)?;
             }
-        }
 
-        Ok(Self::build_project_plan(children, select_list))
+            children = if correlated {
+                self.bind_correlated_scalar_subquery(children, plan, arena)?
+            } else {
+                ScalarApplyOperator::build(children, plan)
+            };
+        }
+        if let Some(sort) = sort {
+            children = LogicalPlan::new(sort, Childrens::Only(Box::new(children)));
+        }
+        Ok(children)
     }
 
     pub(crate) fn bind_sort(
@@ -2275,7 +2541,7 @@ mod tests {
     use crate::errors::DatabaseError;
     use crate::expression::visitor_mut::ExprVisitorMut;
     use crate::expression::{AliasType, ScalarExpression};
-    use crate::planner::operator::join::{JoinCondition, JoinType};
+    use crate::planner::operator::join::{JoinCondition, JoinOperator, JoinType};
     use crate::planner::operator::mark_apply::{
         MarkApplyKind, MarkApplyOperator, MarkApplyQuantifier,
     };
@@ -2457,6 +2723,60 @@ mod tests {
         Ok(())
     }
 
+    fn find_operator<'a>(
+        plan: &'a LogicalPlan,
+        f: &impl Fn(&Operator) -> bool,
+    ) -> Option<&'a LogicalPlan> {
+        if f(&plan.operator) {
+            return Some(plan);
+        }
+        plan.childrens
+            .iter()
+            .find_map(|child| find_operator(child, f))
+    }
+
+    #[test]
+    fn test_with_clause_binds_cte_as_derived_source() -> Result<(), DatabaseError> {
+        let table_states = build_t1_table()?;
+        let plan = table_states.plan(
+            "with c(a) as (select c1 from t1) select x.a, y.a from c x join c y on x.a = y.a",
+        )?;
+        let Some((join_type, _)) = find_join(&plan) else {
+            panic!("expected cte self join to introduce a join")
+        };
+        assert_eq!(*join_type, JoinType::Inner);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_with_recursive_binds_recursive_cte() -> Result<(), DatabaseError> {
+        let table_states = build_t1_table()?;
+        let plan = table_states.plan(
+            "with recursive r(n) as (select c1 from t1 union all select n + 1 from r where n < 3) select n from r",
+        )?;
+        let Some(recursive) = find_operator(&plan, &|op| matches!(op, Operator::RecursiveCte(_)))
+        else {
+            panic!("expected recursive cte")
+        };
+        let Operator::RecursiveCte(op) = &recursive.operator else {
+            unreachable!()
+        };
+        assert!(op.is_all);
+        let Childrens::Twins { right, .. } = recursive.childrens.as_ref() else {
+            panic!("recursive cte should have anchor and recursive inputs")
+        };
+        assert!(right.references_cte(&op.table_name));
+
+        let plan = table_states.plan(
+            "with recursive r(n) as (select c1 from t1 union select c3 from t2) select n from r",
+        )?;
+        assert!(find_operator(&plan, &|op| matches!(op, Operator::RecursiveCte(_))).is_none());
+        assert!(find_operator(&plan, &|op| matches!(op, Operator::Union(_))).is_some());
+
+        Ok(())
+    }
+
     #[test]
     fn test_in_subquery_in_where_binds_as_mark_apply() -> Result<(), DatabaseError> {
         let table_states = build_t1_table()?;
@@ -2523,6 +2843,79 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_correlated_scalar_aggregate_binds_as_left_join() -> Result<(), DatabaseError> {
+        let table_states = build_t1_table()?;
+        let plan =
+            table_states.plan("select c1, (select count(*) from t2 where c3 = c1) from t1")?;
+        let join = find_operator(&plan, &|op| {
+            matches!(
+                op,
+                Operator::Join(JoinOperator {
+                    join_type: JoinType::LeftOuter,
+                    ..
+                })
+            )
+        })
+        .expect("correlated scalar aggregate should become a left join");
+        let Childrens::Twins { right, .. } = join.childrens.as_ref() else {
+            unreachable!()
+        };
+        let aggregate = find_operator(right, &|op| matches!(op, Operator::Aggregate(_)))
+            .expect("the aggregate should be grouped by the correlated column");
+        let Operator::Aggregate(op) = &aggregate.operator else {
+            unreachable!()
+        };
+        assert_eq!(op.groupby_exprs.len(), 1);
+        assert!(find_operator(&plan, &|op| matches!(op, Operator::ScalarApply(_))).is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_correlated_scalar_subquery_binds_as_parameterized_apply() -> Result<(), DatabaseError> {
+        let table_states = build_t1_table()?;
+        let plan = table_states
+            .plan("select * from t1 where c2 = (select c4 from t2 where c3 > c1 limit 1)")?;
+        let apply = find_operator(&plan, &|op| matches!(op, Operator::ScalarApply(_)))
+            .expect("correlated scalar subquery should bind as scalar apply");
+        let Operator::ScalarApply(op) = &apply.operator else {
+            unreachable!()
+        };
+        assert_eq!(op.parameters.len(), 1);
+
+        let plan = table_states.plan(
+            "select * from t1 where exists (select 1 from t2 where c3 = c1) \
+             and c2 > (select max(c4) from t2)",
+        )?;
+        assert!(find_operator(&plan, &|op| matches!(op, Operator::MarkApply(_))).is_some());
+        assert!(find_operator(&plan, &|op| matches!(op, Operator::ScalarApply(_))).is_some());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_lateral_binds_as_apply_only_when_correlated() -> Result<(), DatabaseError> {
+        let table_states = build_t1_table()?;
+        let plan = table_states.plan(
+            "select * from t1 left join lateral (select c4 from t2 where c3 = c1) s on s.c4 > c2",
+        )?;
+        let apply = find_operator(&plan, &|op| matches!(op, Operator::LateralApply(_)))
+            .expect("correlated lateral subquery should bind as lateral apply");
+        let Operator::LateralApply(op) = &apply.operator else {
+            unreachable!()
+        };
+        assert_eq!(op.join_type, JoinType::LeftOuter);
+        assert_eq!(op.parameters.len(), 1);
+        assert!(op.filter.is_some());
+
+        let plan = table_states.plan("select * from t1, lateral (select c4 from t2) s")?;
+        assert!(find_operator(&plan, &|op| matches!(op, Operator::LateralApply(_))).is_none());
+        assert!(find_top_join(&plan).is_some());
+
+        Ok(())
+    }
+
     fn find_top_join(plan: &LogicalPlan) -> Option<&LogicalPlan> {
         if matches!(plan.operator, Operator::Join(_)) {
             return Some(plan);
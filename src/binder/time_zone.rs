@@ -0,0 +1,158 @@
+// Copyright 2024 KipData/KiteSQL
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::binder::Binder;
+use crate::errors::DatabaseError;
+use crate::planner::operator::values::ValuesOperator;
+use crate::planner::operator::Operator;
+use crate::planner::{Childrens, LogicalPlan, PlanArena};
+use crate::storage::Transaction;
+use crate::types::timezone::{session_time_zone, TimeZone};
+use crate::types::value::{DataValue, Utf8Type};
+use crate::types::CharLengthUnits;
+use chrono::FixedOffset;
+use sqlparser::ast::{
+    ContextModifier, Expr, Ident, ObjectName, Set, Statement, UnaryOperator, Value,
+};
+
+fn is_time_zone_name(name: &ObjectName) -> bool {
+    match name.0.as_slice() {
+        [part] => part
+            .as_ident()
+            .is_some_and(|ident| ident.value.eq_ignore_ascii_case("timezone")),
+        _ => false,
+    }
+}
+
+/// `SET TIME ZONE ...` or `SET timezone = ...`.
+pub(crate) fn is_time_zone_set(set: &Set) -> bool {
+    match set {
+        Set::SetTimeZone { .. } => true,
+        Set::SingleAssignment { variable, .. } => is_time_zone_name(variable),
+        _ => false,
+    }
+}
+
+/// `SHOW TIME ZONE` or `SHOW timezone`.
+pub(crate) fn is_time_zone_variable(variable: &[Ident]) -> bool {
+    match variable {
+        [name] => name.value.eq_ignore_ascii_case("timezone"),
+        [time, zone] => {
+            time.value.eq_ignore_ascii_case("time") && zone.value.eq_ignore_ascii_case("zone")
+        }
+        _ => false,
+    }
+}
+
+/// Resolves the time zone `stmt` switches the session to, `None` when it is not a time zone
+/// `SET`. `DEFAULT` and `LOCAL` go back to `default`, and a bare number is an offset in hours.
+///
+/// `SET LOCAL` only lasts until the end of the transaction, so outside an explicit one it
+/// changes nothing, as in PostgreSQL.
+pub(crate) fn time_zone_assignment(
+    stmt: &Statement,
+    default: TimeZone,
+    in_transaction: bool,
+) -> Result<Option<TimeZone>, DatabaseError> {
+    let (local, value) = match stmt {
+        Statement::Set(Set::SetTimeZone { local, value }) => (*local, value),
+        Statement::Set(Set::SingleAssignment {
+            scope,
+            variable,
+            values,
+            ..
+        }) if is_time_zone_name(variable) => {
+            let [value] = values.as_slice() else {
+                return Err(DatabaseError::InvalidValue(
+                    "SET TIME ZONE takes exactly one value".to_string(),
+                ));
+            };
+            (matches!(scope, Some(ContextModifier::Local)), value)
+        }
+        _ => return Ok(None),
+    };
+    let hours = |number: &str, negative: bool| {
+        number
+            .parse::<f64>()
+            .ok()
+            .and_then(|hours| {
+                let seconds = (hours * 3600.0).round() as i32;
+                FixedOffset::east_opt(if negative { -seconds } else { seconds })
+            })
+            .map(TimeZone::Fixed)
+            .ok_or_else(|| {
+                DatabaseError::InvalidValue(format!("invalid time zone offset: {value}"))
+            })
+    };
+    let time_zone = match value {
+        Expr::Identifier(ident)
+            if ident.quote_style.is_none()
+                && (ident.value.eq_ignore_ascii_case("default")
+                    || ident.value.eq_ignore_ascii_case("local")) =>
+        {
+            default
+        }
+        Expr::Identifier(ident) => TimeZone::parse(&ident.value)?,
+        Expr::Value(value) => match &value.value {
+            Value::SingleQuotedString(name) | Value::DoubleQuotedString(name) => {
+                TimeZone::parse(name)?
+            }
+            Value::Number(number, _) => hours(number, false)?,
+            _ => return Err(DatabaseError::UnsupportedStmt(stmt.to_string())),
+        },
+        Expr::UnaryOp {
+            op: op @ (UnaryOperator::Plus | UnaryOperator::Minus),
+            expr,
+        } => match expr.as_ref() {
+            Expr::Value(value) => match &value.value {
+                Value::Number(number, _) => hours(number, matches!(op, UnaryOperator::Minus))?,
+                _ => return Err(DatabaseError::UnsupportedStmt(stmt.to_string())),
+            },
+            _ => return Err(DatabaseError::UnsupportedStmt(stmt.to_string())),
+        },
+        _ => return Err(DatabaseError::UnsupportedStmt(stmt.to_string())),
+    };
+
+    Ok((!local || in_transaction).then_some(time_zone))
+}
+
+impl<T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'_, '_, T, A> {
+    /// The zone itself is switched by the caller before binding, so there is nothing to run.
+    pub(crate) fn bind_set_time_zone(&mut self) -> Result<LogicalPlan, DatabaseError> {
+        Ok(LogicalPlan::new(
+            Operator::Values(ValuesOperator {
+                rows: Vec::new(),
+                schema_ref: Vec::new(),
+            }),
+            Childrens::None,
+        ))
+    }
+
+    pub(crate) fn bind_show_time_zone(
+        &mut self,
+        arena: &mut PlanArena,
+    ) -> Result<LogicalPlan, DatabaseError> {
+        Ok(LogicalPlan::new(
+            Operator::Values(ValuesOperator {
+                rows: vec![vec![DataValue::Utf8 {
+                    value: session_time_zone().to_string(),
+                    ty: Utf8Type::Variable(None),
+                    unit: CharLengthUnits::Characters,
+                }]],
+                schema_ref: vec![arena.alloc_dummy("TIMEZONE")],
+            }),
+            Childrens::None,
+        ))
+    }
+}
@@ -18,7 +18,7 @@ use crate::errors::DatabaseError;
 use crate::expression::ScalarExpression;
 use crate::planner::operator::update::UpdateOperator;
 use crate::planner::operator::Operator;
-use crate::planner::{Childrens, LogicalPlan};
+use crate::planner::{Childrens, LogicalPlan, PlanArena};
 use crate::storage::Transaction;
 use crate::types::value::DataValue;
 
@@ -28,11 +28,17 @@ impl<T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'_, '_, T, A>
         table_name: TableName,
         value_exprs: Vec<(ColumnRef, ScalarExpression)>,
         input: LogicalPlan,
+        returning: Vec<ScalarExpression>,
+        arena: &mut PlanArena,
     ) -> Result<LogicalPlan, DatabaseError> {
+        let checks = self.bind_table_checks(&table_name, arena)?;
+
         Ok(LogicalPlan::new(
             Operator::Update(UpdateOperator {
                 table_name,
                 value_exprs,
+                returning,
+                checks,
             }),
             Childrens::Only(Box::new(input)),
         ))
@@ -16,8 +16,12 @@ use super::{Binder, QueryBindStep};
 use crate::catalog::{ColumnCatalog, ColumnDesc, ColumnRef};
 use crate::errors::DatabaseError;
 use crate::expression::visitor_mut::{walk_mut_expr, ExprVisitorMut};
-use crate::expression::window::{WindowCall, WindowFunction, WindowFunctionKind, WindowSpec};
+use crate::expression::window::{
+    WindowCall, WindowFrame, WindowFrameBound, WindowFrameUnits, WindowFunction,
+    WindowFunctionKind, WindowSpec,
+};
 use crate::expression::ScalarExpression;
+use crate::planner::operator::filter::FilterOperator;
 use crate::planner::operator::sort::SortField;
 use crate::planner::operator::sort::SortOperator;
 use crate::planner::operator::window::WindowOperator;
@@ -26,6 +30,9 @@ use crate::planner::{Childrens, LogicalPlan, PlanArena};
 use crate::storage::Transaction;
 use crate::types::value::DataValue;
 use crate::types::LogicalType;
+use std::borrow::Cow;
+use std::mem;
+use std::ops::RangeInclusive;
 
 struct WindowCollector<'a, 'p> {
     arena: &'a mut PlanArena<'p>,
@@ -89,9 +96,116 @@ struct WindowGroup {
     partition_by: Vec<ScalarExpression>,
     order_by: Vec<SortField>,
     functions: Vec<WindowFunction>,
+    frames: Vec<Option<WindowFrame>>,
     output_columns: Vec<ColumnRef>,
 }
 
+fn bound_rank(bound: &WindowFrameBound) -> usize {
+    match bound {
+        WindowFrameBound::UnboundedPreceding => 0,
+        WindowFrameBound::Preceding(_) => 1,
+        WindowFrameBound::CurrentRow => 2,
+        WindowFrameBound::Following(_) => 3,
+        WindowFrameBound::UnboundedFollowing => 4,
+    }
+}
+
+/// Checks `frame` against the `ORDER BY` of its window and casts the offsets to the type they
+/// are measured in.
+fn bind_window_frame(
+    mut frame: WindowFrame,
+    order_by: &[SortField],
+    arena: &PlanArena,
+) -> Result<WindowFrame, DatabaseError> {
+    if frame.start == WindowFrameBound::UnboundedFollowing {
+        return Err(DatabaseError::UnsupportedStmt(
+            "window frame cannot start at UNBOUNDED FOLLOWING".to_string(),
+        ));
+    }
+    if frame.end == WindowFrameBound::UnboundedPreceding {
+        return Err(DatabaseError::UnsupportedStmt(
+            "window frame cannot end at UNBOUNDED PRECEDING".to_string(),
+        ));
+    }
+    if bound_rank(&frame.start) > bound_rank(&frame.end) {
+        return Err(DatabaseError::UnsupportedStmt(format!(
+            "window frame cannot start at {} and end at {}",
+            frame.start, frame.end
+        )));
+    }
+    if frame.units == WindowFrameUnits::Groups && order_by.is_empty() {
+        return Err(DatabaseError::UnsupportedStmt(
+            "GROUPS window frames require ORDER BY".to_string(),
+        ));
+    }
+    let offset_ty = match frame.units {
+        WindowFrameUnits::Rows | WindowFrameUnits::Groups => LogicalType::Bigint,
+        WindowFrameUnits::Range => {
+            let has_offset = [&frame.start, &frame.end].into_iter().any(|bound| {
+                matches!(
+                    bound,
+                    WindowFrameBound::Preceding(_) | WindowFrameBound::Following(_)
+                )
+            });
+            if !has_offset {
+                return Ok(frame);
+            }
+            let ty = match order_by {
+                [field] => field.expr.return_type(arena).into_owned(),
+                _ => LogicalType::SqlNull,
+            };
+            if !ty.is_numeric() {
+                return Err(DatabaseError::UnsupportedStmt(
+                    "RANGE window frames with an offset require exactly one numeric ORDER BY column"
+                        .to_string(),
+                ));
+            }
+            ty
+        }
+    };
+    let zero = DataValue::Int32(0).cast(&offset_ty)?;
+    for bound in [&mut frame.start, &mut frame.end] {
+        let (WindowFrameBound::Preceding(offset) | WindowFrameBound::Following(offset)) = bound
+        else {
+            continue;
+        };
+        let value = offset.clone().cast(&offset_ty)?;
+        if value.is_null() || value < zero {
+            return Err(DatabaseError::InvalidValue(format!(
+                "window frame offset must be a non-negative constant: {offset}"
+            )));
+        }
+        *offset = value;
+    }
+    Ok(frame)
+}
+
+fn check_arg_count(
+    args: &[ScalarExpression],
+    expected: RangeInclusive<usize>,
+    name: &'static str,
+    expected_name: &'static str,
+) -> Result<(), DatabaseError> {
+    if !expected.contains(&args.len()) {
+        return Err(DatabaseError::MisMatch(name, expected_name));
+    }
+    Ok(())
+}
+
+/// Casts the argument at `index`, if it is present, to `ty`.
+fn cast_args(
+    mut args: Vec<ScalarExpression>,
+    index: usize,
+    ty: &LogicalType,
+    arena: &PlanArena,
+) -> Result<Vec<ScalarExpression>, DatabaseError> {
+    if index < args.len() {
+        let arg = mem::replace(&mut args[index], ScalarExpression::Empty);
+        args[index] = ScalarExpression::type_cast(arg, Cow::Borrowed(ty), arena)?;
+    }
+    Ok(args)
+}
+
 impl<T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'_, '_, T, A> {
     pub(crate) fn bind_window_function(
         &mut self,
@@ -99,14 +213,15 @@ impl<T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'_, '_, T, A>
         args: Vec<ScalarExpression>,
         partition_by: Vec<ScalarExpression>,
         order_by: Vec<SortField>,
+        frame: Option<WindowFrame>,
         arena: &mut PlanArena,
     ) -> Result<ScalarExpression, DatabaseError> {
         if !matches!(
             self.context.step_now(),
-            QueryBindStep::Project | QueryBindStep::Sort
+            QueryBindStep::Project | QueryBindStep::Sort | QueryBindStep::Qualify
         ) {
             return Err(DatabaseError::UnsupportedStmt(
-                "window functions are only allowed in SELECT and ORDER BY".to_string(),
+                "window functions are only allowed in SELECT, QUALIFY and ORDER BY".to_string(),
             ));
         }
         for expr in args
@@ -121,33 +236,76 @@ impl<T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'_, '_, T, A>
             }
         }
 
+        let frame = frame
+            .map(|frame| bind_window_frame(frame, &order_by, arena))
+            .transpose()?;
+
         let (args, ty) = match kind {
             WindowFunctionKind::RowNumber
             | WindowFunctionKind::Rank
             | WindowFunctionKind::DenseRank => {
-                if !args.is_empty() {
-                    return Err(DatabaseError::MisMatch(
-                        "number of ranking function parameters",
-                        "0",
-                    ));
-                }
+                check_arg_count(&args, 0..=0, "number of ranking function parameters", "0")?;
                 (args, LogicalType::Bigint)
             }
-            WindowFunctionKind::Aggregate(agg_kind) => {
-                let ScalarExpression::AggCall { args, ty, .. } =
-                    self.bind_aggregate_function(agg_kind, args, false, arena)?
+            WindowFunctionKind::PercentRank | WindowFunctionKind::CumeDist => {
+                check_arg_count(&args, 0..=0, "number of ranking function parameters", "0")?;
+                (args, LogicalType::Double)
+            }
+            WindowFunctionKind::Ntile => {
+                check_arg_count(&args, 1..=1, "number of ntile() parameters", "1")?;
+                (
+                    cast_args(args, 0, &LogicalType::Bigint, arena)?,
+                    LogicalType::Bigint,
+                )
+            }
+            WindowFunctionKind::Lag { .. } | WindowFunctionKind::Lead { .. } => {
+                check_arg_count(&args, 1..=3, "number of lag()/lead() parameters", "1 to 3")?;
+                let ty = args[0].return_type(arena).into_owned();
+                let args = cast_args(args, 1, &LogicalType::Bigint, arena)?;
+                (cast_args(args, 2, &ty, arena)?, ty)
+            }
+            WindowFunctionKind::FirstValue | WindowFunctionKind::LastValue => {
+                check_arg_count(&args, 1..=1, "number of value function parameters", "1")?;
+                let ty = args[0].return_type(arena).into_owned();
+                (args, ty)
+            }
+            WindowFunctionKind::NthValue => {
+                check_arg_count(&args, 2..=2, "number of nth_value() parameters", "2")?;
+                let ty = args[0].return_type(arena).into_owned();
+                (cast_args(args, 1, &LogicalType::Bigint, arena)?, ty)
+            }
+            WindowFunctionKind::Aggregate(ref agg_kind) => {
+                let ScalarExpression::AggCall { args, ty, .. } = self.bind_aggregate_function(
+                    agg_kind.clone(),
+                    args,
+                    false,
+                    None,
+                    Vec::new(),
+                    arena,
+                )?
                 else {
                     unreachable!()
                 };
                 (args, ty)
             }
         };
+        // Functions that do not read the frame ignore it, and the default frame is left implicit.
+        let frame = frame.filter(|frame| {
+            kind.uses_frame()
+                && *frame
+                    != WindowFrame {
+                        units: WindowFrameUnits::Range,
+                        start: WindowFrameBound::UnboundedPreceding,
+                        end: WindowFrameBound::CurrentRow,
+                    }
+        });
 
         Ok(ScalarExpression::WindowCall(WindowCall {
             function: WindowFunction { kind, args, ty },
             spec: WindowSpec {
                 partition_by,
                 order_by,
+                frame: frame.map(Box::new),
             },
         }))
     }
@@ -157,13 +315,15 @@ impl<T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'_, '_, T, A>
         mut children: LogicalPlan,
         select_list: &mut [ScalarExpression],
         order_by: &mut Option<Vec<SortField>>,
+        qualify: Option<ScalarExpression>,
         arena: &mut PlanArena,
     ) -> Result<LogicalPlan, DatabaseError> {
+        let mut qualify = qualify;
         let mut collector = WindowCollector {
             arena,
             windows: Vec::new(),
         };
-        for expr in select_list.iter_mut() {
+        for expr in select_list.iter_mut().chain(qualify.as_mut()) {
             collector.visit(expr)?;
         }
         if let Some(order_by) = order_by.as_mut() {
@@ -172,6 +332,11 @@ impl<T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'_, '_, T, A>
             }
         }
         if collector.windows.is_empty() {
+            if qualify.is_some() {
+                return Err(DatabaseError::UnsupportedStmt(
+                    "QUALIFY requires a window function".to_string(),
+                ));
+            }
             return Ok(children);
         }
         let windows = collector.windows;
@@ -186,12 +351,14 @@ impl<T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'_, '_, T, A>
 
             if let Some(index) = group_index {
                 groups[index].functions.push(function);
+                groups[index].frames.push(spec.frame.map(|frame| *frame));
                 groups[index].output_columns.push(output_column);
             } else {
                 groups.push(WindowGroup {
                     partition_by: spec.partition_by,
                     order_by: spec.order_by,
                     functions: vec![function],
+                    frames: vec![spec.frame.map(|frame| *frame)],
                     output_columns: vec![output_column],
                 });
             }
@@ -203,6 +370,7 @@ impl<T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'_, '_, T, A>
         };
         for expr in select_list
             .iter_mut()
+            .chain(qualify.as_mut())
             .chain(order_by.iter_mut().flatten().map(|field| &mut field.expr))
         {
             output_binder.visit(expr)?;
@@ -229,11 +397,15 @@ impl<T: Transaction, A: AsRef<[(&'static str, DataValue)]>> Binder<'_, '_, T, A>
                     sort_fields,
                     partition_by_len,
                     functions: group.functions,
+                    frames: group.frames,
                     output_columns: group.output_columns,
                 }),
                 Childrens::Only(Box::new(children)),
             );
         }
+        if let Some(predicate) = qualify {
+            children = FilterOperator::build(predicate, children, false);
+        }
         self.context.step(QueryBindStep::Window);
         Ok(children)
     }
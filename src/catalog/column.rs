@@ -225,6 +225,16 @@ mod tests {
     }
 }
 
+/// Whether an identity column accepts values supplied by an `INSERT`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, ReferenceSerialization)]
+pub enum ColumnIdentity {
+    /// `GENERATED ALWAYS AS IDENTITY`: every value comes from the sequence.
+    Always,
+    /// `GENERATED BY DEFAULT AS IDENTITY` and `AUTO_INCREMENT`: the sequence fills in
+    /// values that are omitted or `NULL`.
+    ByDefault,
+}
+
 /// The descriptor of a column.
 #[derive(Debug, Clone, PartialEq, Eq, Hash, ReferenceSerialization)]
 pub struct ColumnDesc {
@@ -232,6 +242,7 @@ pub struct ColumnDesc {
     primary: Option<usize>,
     is_unique: bool,
     pub(crate) default: Option<ScalarExpression>,
+    identity: Option<ColumnIdentity>,
 }
 
 impl ColumnDesc {
@@ -254,6 +265,7 @@ impl ColumnDesc {
             primary,
             is_unique,
             default,
+            identity: None,
         })
     }
 
@@ -276,4 +288,23 @@ impl ColumnDesc {
     pub(crate) fn set_unique(&mut self) {
         self.is_unique = true
     }
+
+    pub(crate) fn identity(&self) -> Option<ColumnIdentity> {
+        self.identity
+    }
+
+    /// The sequence an identity column draws its values from.
+    pub(crate) fn identity_sequence(&self) -> Option<&str> {
+        self.identity?;
+        match &self.default {
+            Some(ScalarExpression::Sequence { name, .. }) => Some(name),
+            _ => None,
+        }
+    }
+
+    /// Marks the column as an identity column. Its sequence is created with the table,
+    /// which also sets the column default to draw from it.
+    pub fn set_identity(&mut self, identity: ColumnIdentity) {
+        self.identity = Some(identity)
+    }
 }
@@ -18,5 +18,6 @@ pub(crate) use self::column::*;
 pub(crate) use self::table::*;
 
 pub mod column;
+pub mod sequence;
 pub mod table;
 pub mod view;
@@ -0,0 +1,362 @@
+// Copyright 2024 KipData/KiteSQL
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::catalog::TableName;
+use crate::errors::DatabaseError;
+use crate::types::LogicalType;
+use kite_sql_serde_macros::ReferenceSerialization;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, PoisonError};
+
+/// Number of values a transaction reserves at once when a sequence declares no `CACHE`.
+pub(crate) const DEFAULT_SEQUENCE_CACHE: i64 = 32;
+
+/// The name of the sequence generating the identity column `column_name` of `table_name`.
+pub(crate) fn identity_sequence_name(table_name: &str, column_name: &str) -> String {
+    format!("{table_name}_{column_name}_seq")
+}
+
+/// A persisted sequence. Values are handed out in blocks of `cache` values: reserving a
+/// block writes the advanced `next` back to the store, and the values of the block are
+/// then served from the [`SequenceCache`] without touching the stored record.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, ReferenceSerialization)]
+pub struct Sequence {
+    pub(crate) name: String,
+    pub(crate) increment: i64,
+    pub(crate) min_value: i64,
+    pub(crate) max_value: i64,
+    pub(crate) start: i64,
+    pub(crate) cache: i64,
+    /// The first value no block holds yet, `None` once the sequence is exhausted.
+    pub(crate) next: Option<i64>,
+    /// Identifies the block reserved last, cached blocks are only served while it matches.
+    pub(crate) block: u64,
+    /// The table of the identity column the sequence generates values for.
+    pub(crate) owned_by: Option<TableName>,
+}
+
+/// Values of a reserved block that have not been handed out yet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct SequenceBlock {
+    next: i64,
+    remaining: i64,
+    increment: i64,
+}
+
+impl SequenceBlock {
+    pub(crate) fn take(&mut self) -> Option<i64> {
+        if self.remaining == 0 {
+            return None;
+        }
+        let value = self.next;
+        self.remaining -= 1;
+        if self.remaining > 0 {
+            self.next += self.increment;
+        }
+        Some(value)
+    }
+}
+
+impl Sequence {
+    /// Builds a sequence from the `CREATE SEQUENCE` options, defaulting them the way
+    /// PostgreSQL does for the value range of `ty`.
+    pub(crate) fn new(
+        name: String,
+        ty: &LogicalType,
+        increment: Option<i64>,
+        min_value: Option<i64>,
+        max_value: Option<i64>,
+        start: Option<i64>,
+        cache: Option<i64>,
+    ) -> Result<Self, DatabaseError> {
+        let (type_min, type_max) = Self::type_range(ty)?;
+        let increment = increment.unwrap_or(1);
+        if increment == 0 {
+            return Err(DatabaseError::InvalidValue(format!(
+                "INCREMENT of sequence `{name}` must not be zero"
+            )));
+        }
+        let min_value = min_value.unwrap_or(if increment > 0 { 1 } else { type_min });
+        let max_value = max_value.unwrap_or(if increment > 0 { type_max } else { -1 });
+        if min_value < type_min || max_value > type_max || min_value >= max_value {
+            return Err(DatabaseError::InvalidValue(format!(
+                "MINVALUE ({min_value}) must be less than MAXVALUE ({max_value}) and both within the range of {ty} for sequence `{name}`"
+            )));
+        }
+        let start = start.unwrap_or(if increment > 0 { min_value } else { max_value });
+        if start < min_value || start > max_value {
+            return Err(DatabaseError::InvalidValue(format!(
+                "START value ({start}) of sequence `{name}` must lie between MINVALUE ({min_value}) and MAXVALUE ({max_value})"
+            )));
+        }
+        let cache = cache.unwrap_or(DEFAULT_SEQUENCE_CACHE);
+        if cache < 1 {
+            return Err(DatabaseError::InvalidValue(format!(
+                "CACHE ({cache}) of sequence `{name}` must be greater than zero"
+            )));
+        }
+
+        Ok(Sequence {
+            name,
+            increment,
+            min_value,
+            max_value,
+            start,
+            cache,
+            next: Some(start),
+            block: 0,
+            owned_by: None,
+        })
+    }
+
+    /// The range of values a sequence generating `ty` can hold.
+    pub(crate) fn type_range(ty: &LogicalType) -> Result<(i64, i64), DatabaseError> {
+        Ok(match ty {
+            LogicalType::Tinyint => (i8::MIN as i64, i8::MAX as i64),
+            LogicalType::UTinyint => (0, u8::MAX as i64),
+            LogicalType::Smallint => (i16::MIN as i64, i16::MAX as i64),
+            LogicalType::USmallint => (0, u16::MAX as i64),
+            LogicalType::Integer => (i32::MIN as i64, i32::MAX as i64),
+            LogicalType::UInteger => (0, u32::MAX as i64),
+            LogicalType::Bigint => (i64::MIN, i64::MAX),
+            LogicalType::UBigint => (0, i64::MAX),
+            ty => {
+                return Err(DatabaseError::InvalidValue(format!(
+                    "sequences can only generate integer values, not {ty}"
+                )))
+            }
+        })
+    }
+
+    pub(crate) fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Reserves the next block of values under the identifier `block`, advancing `next`
+    /// past it. The caller persists the sequence to make the reservation durable.
+    pub(crate) fn reserve(&mut self, block: u64) -> Result<SequenceBlock, DatabaseError> {
+        let next = self
+            .next
+            .ok_or_else(|| DatabaseError::SequenceExhausted(self.name.clone()))?;
+        let (increment, next_wide) = (self.increment as i128, next as i128);
+        let available = if increment > 0 {
+            (self.max_value as i128 - next_wide) / increment + 1
+        } else {
+            (next_wide - self.min_value as i128) / -increment + 1
+        };
+        let remaining = available.min(self.cache as i128);
+        let advanced = next_wide + remaining * increment;
+
+        self.next = (self.min_value as i128..=self.max_value as i128)
+            .contains(&advanced)
+            .then_some(advanced as i64);
+        self.block = block;
+
+        Ok(SequenceBlock {
+            next,
+            remaining: remaining as i64,
+            increment: self.increment,
+        })
+    }
+}
+
+#[derive(Debug)]
+struct CachedBlock {
+    block: u64,
+    next: Option<i64>,
+    values: SequenceBlock,
+}
+
+/// Blocks of sequence values shared by the transactions of a database, together with
+/// the value `nextval` returned last for each sequence, which `currval` reports.
+///
+/// A transaction reserving a block publishes it before it commits, so a cached block is
+/// only served while the stored sequence still names it: the reservation of a transaction
+/// that rolled back, or lost a write conflict to another reservation, is never visible.
+#[derive(Debug, Default)]
+pub struct SequenceCache {
+    blocks: Mutex<HashMap<String, CachedBlock>>,
+    last_values: Mutex<HashMap<String, i64>>,
+    block_ids: AtomicU64,
+}
+
+impl SequenceCache {
+    pub(crate) fn next_block_id(&self) -> u64 {
+        self.block_ids.fetch_add(1, Ordering::Relaxed) + 1
+    }
+
+    /// Takes a value from the cached block of `sequence` when the stored record still
+    /// refers to that block.
+    pub(crate) fn take(&self, sequence: &Sequence) -> Option<i64> {
+        let mut blocks = self.blocks.lock().unwrap_or_else(PoisonError::into_inner);
+        let cached = blocks.get_mut(&sequence.name)?;
+        if cached.block != sequence.block || cached.next != sequence.next {
+            blocks.remove(&sequence.name);
+            return None;
+        }
+        cached.values.take()
+    }
+
+    /// Publishes the rest of the block `sequence` was just advanced past.
+    pub(crate) fn publish(&self, sequence: &Sequence, values: SequenceBlock) {
+        self.blocks
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(
+                sequence.name.clone(),
+                CachedBlock {
+                    block: sequence.block,
+                    next: sequence.next,
+                    values,
+                },
+            );
+    }
+
+    pub(crate) fn forget(&self, name: &str) {
+        self.blocks
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(name);
+        self.last_values
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(name);
+    }
+
+    pub(crate) fn set_last_value(&self, name: &str, value: i64) {
+        self.last_values
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(name.to_string(), value);
+    }
+
+    pub(crate) fn last_value(&self, name: &str) -> Result<i64, DatabaseError> {
+        self.last_values
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(name)
+            .copied()
+            .ok_or_else(|| DatabaseError::SequenceNotCalled(name.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::catalog::sequence::{Sequence, SequenceCache};
+    use crate::errors::DatabaseError;
+    use crate::types::LogicalType;
+
+    #[test]
+    fn test_sequence_reserve_blocks() -> Result<(), DatabaseError> {
+        let mut sequence = Sequence::new(
+            "s".to_string(),
+            &LogicalType::Bigint,
+            Some(2),
+            None,
+            Some(12),
+            Some(3),
+            Some(3),
+        )?;
+        let mut block = sequence.reserve(1)?;
+        assert_eq!(sequence.next, Some(9));
+        assert_eq!(block.take(), Some(3));
+        assert_eq!(block.take(), Some(5));
+        assert_eq!(block.take(), Some(7));
+        assert_eq!(block.take(), None);
+
+        let mut block = sequence.reserve(2)?;
+        assert_eq!(sequence.next, None);
+        assert_eq!(block.take(), Some(9));
+        assert_eq!(block.take(), Some(11));
+        assert_eq!(block.take(), None);
+        assert!(matches!(
+            sequence.reserve(3),
+            Err(DatabaseError::SequenceExhausted(_))
+        ));
+
+        let mut descending = Sequence::new(
+            "d".to_string(),
+            &LogicalType::Tinyint,
+            Some(-50),
+            None,
+            None,
+            None,
+            None,
+        )?;
+        assert_eq!(descending.next, Some(-1));
+        let mut block = descending.reserve(1)?;
+        assert_eq!(block.take(), Some(-1));
+        assert_eq!(block.take(), Some(-51));
+        assert_eq!(block.take(), Some(-101));
+        assert_eq!(block.take(), None);
+        assert_eq!(descending.next, None);
+
+        assert!(Sequence::new(
+            "bad".to_string(),
+            &LogicalType::Integer,
+            Some(0),
+            None,
+            None,
+            None,
+            None
+        )
+        .is_err());
+        assert!(Sequence::new(
+            "bad".to_string(),
+            &LogicalType::Varchar(None, crate::types::CharLengthUnits::Characters),
+            None,
+            None,
+            None,
+            None,
+            None
+        )
+        .is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_sequence_cache_serves_current_block() -> Result<(), DatabaseError> {
+        let cache = SequenceCache::default();
+        let mut sequence = Sequence::new(
+            "s".to_string(),
+            &LogicalType::Integer,
+            None,
+            None,
+            None,
+            None,
+            Some(3),
+        )?;
+        let stored = sequence.clone();
+        let mut block = sequence.reserve(cache.next_block_id())?;
+        assert_eq!(block.take(), Some(1));
+        cache.publish(&sequence, block);
+
+        // a reservation that was never stored is not served
+        assert_eq!(cache.take(&stored), None);
+        cache.publish(&sequence, block);
+        assert_eq!(cache.take(&sequence), Some(2));
+        assert_eq!(cache.take(&sequence), Some(3));
+        assert_eq!(cache.take(&sequence), None);
+
+        assert!(cache.last_value("s").is_err());
+        cache.set_last_value("s", 3);
+        assert_eq!(cache.last_value("s")?, 3);
+        cache.forget("s");
+        assert!(cache.last_value("s").is_err());
+
+        Ok(())
+    }
+}
@@ -15,9 +15,10 @@
 use crate::catalog::{ColumnCatalog, ColumnRef, ColumnRelation};
 use crate::errors::DatabaseError;
 use crate::expression::ScalarExpression;
+use crate::function::json_path::json_path_type;
 use crate::iter_ext::Itertools;
 use crate::planner::{MetaArena, PlanArena};
-use crate::types::index::{IndexMeta, IndexMetaRef, IndexType};
+use crate::types::index::{IndexMeta, IndexMetaRef, IndexPathStep, IndexType};
 use crate::types::tuple::Schema;
 use crate::types::{ColumnId, LogicalType};
 use kite_sql_serde_macros::ReferenceSerialization;
@@ -39,6 +40,9 @@ pub struct TableCatalog {
     primary_keys: Vec<(usize, ColumnRef)>,
     primary_key_indices: Vec<usize>,
     primary_key_type: LogicalType,
+    checks: Vec<CheckConstraint>,
+    foreign_keys: Vec<ForeignKey>,
+    referenced_by: Vec<TableName>,
 }
 
 pub(crate) struct DmlTableSnapshot<'a> {
@@ -46,12 +50,48 @@ pub(crate) struct DmlTableSnapshot<'a> {
     pub(crate) primary_key_indices: &'a [usize],
     pub(crate) columns_len: usize,
     pub(crate) index_metas: Vec<(IndexMetaRef, Vec<ScalarExpression>)>,
+    /// Whether written rows have foreign keys to check or referencing rows to act on.
+    pub(crate) has_references: bool,
+}
+
+/// A `CHECK` constraint, kept as the SQL text of its expression and bound against
+/// the table columns by each statement that writes the table.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, ReferenceSerialization)]
+pub struct CheckConstraint {
+    pub(crate) name: String,
+    pub(crate) expr: String,
+}
+
+/// What a foreign key does to the referencing rows when the referenced row is deleted
+/// or its key is updated; `NO ACTION` is treated as `RESTRICT`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, ReferenceSerialization)]
+pub enum ReferentialAction {
+    Restrict,
+    Cascade,
+    SetNull,
+    SetDefault,
+}
+
+/// A `FOREIGN KEY` constraint, naming the referencing columns of the table and the
+/// primary key or unique column of the referenced table they point at.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, ReferenceSerialization)]
+pub struct ForeignKey {
+    pub(crate) name: String,
+    pub(crate) columns: Vec<String>,
+    pub(crate) referenced_table: TableName,
+    pub(crate) referenced_columns: Vec<String>,
+    pub(crate) on_delete: ReferentialAction,
+    pub(crate) on_update: ReferentialAction,
 }
 
 //TODO: can add some like Table description and other information as attributes
 #[derive(Debug, Clone, PartialEq, ReferenceSerialization)]
 pub struct TableMeta {
     pub(crate) table_name: TableName,
+    pub(crate) checks: Vec<CheckConstraint>,
+    pub(crate) foreign_keys: Vec<ForeignKey>,
+    /// Tables holding a foreign key that references this table.
+    pub(crate) referenced_by: Vec<TableName>,
 }
 
 impl TableCatalog {
@@ -66,7 +106,9 @@ impl TableCatalog {
     ) -> Option<IndexMetaRef> {
         self.indexes.iter().copied().find(|meta| {
             let meta = arena.index(*meta);
-            matches!(meta.ty, IndexType::Unique) && &meta.column_ids[0] == col_id
+            matches!(meta.ty, IndexType::Unique)
+                && &meta.column_ids[0] == col_id
+                && meta.json_path.is_empty()
         })
     }
 
@@ -85,6 +127,10 @@ impl TableCatalog {
             .map(|(_, i)| self.column_refs[*i])
     }
 
+    pub(crate) fn get_column_position(&self, name: &str) -> Option<usize> {
+        self.column_idxs.get(name).map(|(_, i)| *i)
+    }
+
     #[allow(dead_code)]
     pub(crate) fn contains_column(&self, name: &str) -> bool {
         self.column_idxs.contains_key(name)
@@ -114,7 +160,41 @@ impl TableCatalog {
         &self.primary_key_type
     }
 
-    #[cfg(feature = "copy")]
+    pub(crate) fn checks(&self) -> &[CheckConstraint] {
+        &self.checks
+    }
+
+    pub(crate) fn foreign_keys(&self) -> &[ForeignKey] {
+        &self.foreign_keys
+    }
+
+    pub(crate) fn has_constraint(&self, name: &str) -> bool {
+        self.checks.iter().any(|check| check.name == name)
+            || self
+                .foreign_keys
+                .iter()
+                .any(|foreign_key| foreign_key.name == name)
+    }
+
+    pub(crate) fn referenced_by(&self) -> &[TableName] {
+        &self.referenced_by
+    }
+
+    pub(crate) fn meta(&self) -> TableMeta {
+        TableMeta {
+            table_name: self.name.clone(),
+            checks: self.checks.clone(),
+            foreign_keys: self.foreign_keys.clone(),
+            referenced_by: self.referenced_by.clone(),
+        }
+    }
+
+    pub(crate) fn set_meta(&mut self, meta: TableMeta) {
+        self.checks = meta.checks;
+        self.foreign_keys = meta.foreign_keys;
+        self.referenced_by = meta.referenced_by;
+    }
+
     pub(crate) fn primary_key_indices(&self) -> &[usize] {
         &self.primary_key_indices
     }
@@ -138,6 +218,7 @@ impl TableCatalog {
             primary_key_indices: &self.primary_key_indices,
             columns_len: self.columns_len(),
             index_metas,
+            has_references: !self.foreign_keys.is_empty() || !self.referenced_by.is_empty(),
         })
     }
 
@@ -176,6 +257,7 @@ impl TableCatalog {
         name: String,
         column_ids: Vec<ColumnId>,
         ty: IndexType,
+        json_path: Vec<IndexPathStep>,
         arena: &mut impl MetaArena,
     ) -> Result<IndexMetaRef, DatabaseError> {
         for index in self.indexes.iter() {
@@ -201,7 +283,9 @@ impl TableCatalog {
                 .clone();
             val_tys.push(val_ty)
         }
-        let value_ty = if val_tys.len() == 1 {
+        let value_ty = if let Some(ty) = json_path_type(&json_path) {
+            ty
+        } else if val_tys.len() == 1 {
             val_tys.pop().unwrap()
         } else {
             LogicalType::Tuple(val_tys)
@@ -215,6 +299,7 @@ impl TableCatalog {
             value_ty,
             name,
             ty,
+            json_path,
         };
         let index_ref = arena.alloc_index(index);
         self.indexes.push(index_ref);
@@ -238,6 +323,9 @@ impl TableCatalog {
             primary_keys: vec![],
             primary_key_indices: Default::default(),
             primary_key_type: LogicalType::SqlNull,
+            checks: vec![],
+            foreign_keys: vec![],
+            referenced_by: vec![],
         };
         for col_catalog in columns.into_iter() {
             let _ = table_catalog.add_column(col_catalog, arena).unwrap();
@@ -305,6 +393,9 @@ impl TableCatalog {
             primary_keys,
             primary_key_indices,
             primary_key_type,
+            checks: vec![],
+            foreign_keys: vec![],
+            referenced_by: vec![],
         })
     }
 
@@ -321,12 +412,15 @@ impl TableCatalog {
             .map(|index| source_arena.index(*index).clone())
             .collect_vec();
 
-        Self::reload(
+        let mut table = Self::reload(
             self.name.clone(),
             column_catalogs.into_iter(),
             index_metas.into_iter(),
             source_arena.table_arena_cell().borrow_mut(),
-        )
+        )?;
+        table.set_meta(self.meta());
+
+        Ok(table)
     }
 
     fn build_primary_keys(
@@ -359,7 +453,12 @@ impl TableCatalog {
 
 impl TableMeta {
     pub(crate) fn empty(table_name: TableName) -> Self {
-        TableMeta { table_name }
+        TableMeta {
+            table_name,
+            checks: vec![],
+            foreign_keys: vec![],
+            referenced_by: vec![],
+        }
     }
 }
 
@@ -15,23 +15,71 @@
 #[cfg(feature = "parser")]
 pub use crate::binder::{prepare, prepare_all, Statement};
 use crate::binder::{Binder, BinderContext};
+use crate::catalog::sequence::SequenceCache;
 use crate::catalog::TableName;
 use crate::errors::DatabaseError;
 use crate::execution::{build_write, DDLApply, ExecArena, ExecutionContext, Executor};
+use crate::expression::function::aggregate::AggregateFunctionImpl;
 use crate::expression::function::scala::ScalarFunctionImpl;
 use crate::expression::function::table::{
     ArcTableFunctionImpl, TableFunctionCatalog, TableFunctionImpl,
 };
 use crate::expression::function::FunctionSummary;
+use crate::function::abs::Abs;
+#[cfg(feature = "time")]
+use crate::function::at_time_zone::AtTimeZone;
+use crate::function::ceil::Ceil;
 use crate::function::char_length::CharLength;
+use crate::function::concat::Concat;
+use crate::function::concat_ws::ConcatWs;
 #[cfg(feature = "time")]
 use crate::function::current_date::CurrentDate;
 #[cfg(feature = "time")]
 use crate::function::current_timestamp::CurrentTimeStamp;
+#[cfg(feature = "time")]
+use crate::function::date_add::{DateAdd, DateDiff};
+#[cfg(feature = "time")]
+use crate::function::date_part::DatePart;
+#[cfg(feature = "time")]
+use crate::function::date_trunc::DateTrunc;
+#[cfg(feature = "digest")]
+use crate::function::digest::{Digest, DigestAlgorithm};
+use crate::function::exp::Exp;
+use crate::function::extremum::Extremum;
+use crate::function::floor::Floor;
+use crate::function::json_array_length::JsonArrayLength;
+use crate::function::json_each::JsonEach;
+use crate::function::json_path::JsonPath;
+use crate::function::json_set::JsonSet;
+use crate::function::json_typeof::JsonTypeof;
+use crate::function::left_right::LeftRight;
+use crate::function::ln::Ln;
+use crate::function::log::Log;
 use crate::function::lower::Lower;
+use crate::function::modulo::Modulo;
 use crate::function::numbers::Numbers;
 use crate::function::octet_length::OctetLength;
+use crate::function::pad::Pad;
+use crate::function::power::Power;
+use crate::function::repeat::Repeat;
+use crate::function::replace::Replace;
+use crate::function::reverse::Reverse;
+use crate::function::round::Round;
+use crate::function::sign::Sign;
+use crate::function::split_part::SplitPart;
+use crate::function::sqrt::Sqrt;
+use crate::function::starts_with::StartsWith;
+#[cfg(feature = "time")]
+use crate::function::temporal_types;
+#[cfg(feature = "time")]
+use crate::function::to_char::{Strftime, ToChar};
+#[cfg(feature = "time")]
+use crate::function::to_date::ToDate;
+use crate::function::trim::Trim;
+use crate::function::unnest::Unnest;
 use crate::function::upper::Upper;
+use crate::function::{fractional_types, numeric_types, Side};
+use crate::optimizer::core::cardinality::CardinalityEstimator;
 use crate::optimizer::core::statistics_meta::StatisticMetaLoader;
 use crate::optimizer::heuristic::batch::HepBatchStrategy;
 use crate::optimizer::heuristic::optimizer::HepOptimizerPipeline;
@@ -39,6 +87,7 @@ use crate::optimizer::rule::implementation::ImplementationRuleImpl;
 use crate::optimizer::rule::normalization::NormalizationRuleImpl;
 #[cfg(feature = "orm")]
 use crate::orm::FromQueryRow;
+use crate::planner::explain::ExplainNode;
 use crate::planner::operator::Operator;
 use crate::planner::{LogicalPlan, PlanArena, TableArenaCell};
 #[cfg(all(not(target_arch = "wasm32"), feature = "lmdb"))]
@@ -51,6 +100,8 @@ use crate::storage::{
     CheckpointableStorage, StatisticsMetaCache, Storage, TableCache, Transaction,
     TransactionIsolationLevel, ViewCache,
 };
+#[cfg(feature = "time")]
+use crate::types::timezone::{session_time_zone, TimeZone};
 use crate::types::tuple::{Schema, SchemaView, Tuple};
 use crate::types::value::DataValue;
 use std::collections::{HashMap, HashSet};
@@ -59,15 +110,19 @@ use std::mem;
 use std::path::Path;
 use std::path::PathBuf;
 use std::sync::Arc;
+#[cfg(feature = "time")]
+use std::sync::RwLock;
 
 pub(crate) type ScalaFunctions = HashMap<FunctionSummary, Arc<dyn ScalarFunctionImpl>>;
 pub(crate) type TableFunctions = HashMap<FunctionSummary, TableFunctionCatalog>;
+pub(crate) type AggregateFunctions = HashMap<FunctionSummary, Arc<dyn AggregateFunctionImpl>>;
 
 pub enum CatalogKind {
     Table(crate::catalog::TableName),
     View(crate::catalog::TableName),
     ScalarFunction(Arc<dyn ScalarFunctionImpl>),
     TableFunction(Arc<dyn TableFunctionImpl>),
+    AggregateFunction(Arc<dyn AggregateFunctionImpl>),
 }
 
 pub(crate) trait BindSource {
@@ -104,10 +159,13 @@ pub struct DataBaseBuilder {
     path: PathBuf,
     histogram_buckets: Option<usize>,
     transaction_isolation: Option<TransactionIsolationLevel>,
+    aggregate_functions: Vec<Arc<dyn AggregateFunctionImpl>>,
     #[cfg(all(not(target_arch = "wasm32"), feature = "rocksdb"))]
     storage_config: StorageConfig,
     #[cfg(all(not(target_arch = "wasm32"), feature = "lmdb"))]
     lmdb_config: LmdbConfig,
+    #[cfg(feature = "time")]
+    time_zone: TimeZone,
 }
 
 impl DataBaseBuilder {
@@ -135,10 +193,13 @@ impl DataBaseBuilder {
             path,
             histogram_buckets: None,
             transaction_isolation: None,
+            aggregate_functions: Vec::new(),
             #[cfg(all(not(target_arch = "wasm32"), feature = "rocksdb"))]
             storage_config: Default::default(),
             #[cfg(all(not(target_arch = "wasm32"), feature = "lmdb"))]
             lmdb_config: Default::default(),
+            #[cfg(feature = "time")]
+            time_zone: TimeZone::UTC,
         }
     }
 
@@ -154,6 +215,13 @@ impl DataBaseBuilder {
         self
     }
 
+    /// Registers a user-defined aggregate function, usable wherever the built-in
+    /// aggregates are, including `GROUP BY` and window aggregates.
+    pub fn register_aggregate_function(mut self, function: Arc<dyn AggregateFunctionImpl>) -> Self {
+        self.aggregate_functions.push(function);
+        self
+    }
+
     /// Enables or disables RocksDB statistics collection.
     #[cfg(all(
         not(target_arch = "wasm32"),
@@ -208,9 +276,16 @@ impl DataBaseBuilder {
         self
     }
 
+    /// Sets the time zone sessions start in, which `SET TIME ZONE DEFAULT` returns to.
+    #[cfg(feature = "time")]
+    pub fn time_zone(mut self, time_zone: TimeZone) -> Self {
+        self.time_zone = time_zone;
+        self
+    }
+
     /// Builds a database using a custom storage implementation.
     pub fn build_with_storage<T: Storage>(self, storage: T) -> Result<Database<T>, DatabaseError> {
-        Self::_build::<T>(storage, self.histogram_buckets, self.transaction_isolation)
+        self._build::<T>(storage)
     }
 
     /// Builds a database for the current target platform.
@@ -218,15 +293,15 @@ impl DataBaseBuilder {
     pub fn build(self) -> Result<Database<MemoryStorage>, DatabaseError> {
         let storage = MemoryStorage::new();
 
-        Self::_build::<MemoryStorage>(storage, self.histogram_buckets, self.transaction_isolation)
+        self._build::<MemoryStorage>(storage)
     }
 
     /// Builds a RocksDB-backed database.
     #[cfg(all(not(target_arch = "wasm32"), feature = "rocksdb"))]
-    pub fn build_rocksdb(self) -> Result<Database<RocksStorage>, DatabaseError> {
-        let storage = RocksStorage::with_config(self.path, self.storage_config)?;
+    pub fn build_rocksdb(mut self) -> Result<Database<RocksStorage>, DatabaseError> {
+        let storage = RocksStorage::with_config(mem::take(&mut self.path), self.storage_config)?;
 
-        Self::_build::<RocksStorage>(storage, self.histogram_buckets, self.transaction_isolation)
+        self._build::<RocksStorage>(storage)
     }
 
     /// Builds an in-memory database.
@@ -235,35 +310,36 @@ impl DataBaseBuilder {
     pub fn build_in_memory(self) -> Result<Database<MemoryStorage>, DatabaseError> {
         let storage = MemoryStorage::new();
 
-        Self::_build::<MemoryStorage>(storage, self.histogram_buckets, self.transaction_isolation)
+        self._build::<MemoryStorage>(storage)
     }
 
     /// Builds a LMDB-backed database.
     #[cfg(all(not(target_arch = "wasm32"), feature = "lmdb"))]
-    pub fn build_lmdb(self) -> Result<Database<LmdbStorage>, DatabaseError> {
-        let storage = LmdbStorage::with_config(self.path, self.lmdb_config)?;
+    pub fn build_lmdb(mut self) -> Result<Database<LmdbStorage>, DatabaseError> {
+        let storage = LmdbStorage::with_config(mem::take(&mut self.path), self.lmdb_config)?;
 
-        Self::_build::<LmdbStorage>(storage, self.histogram_buckets, self.transaction_isolation)
+        self._build::<LmdbStorage>(storage)
     }
 
     #[cfg(all(not(target_arch = "wasm32"), feature = "rocksdb"))]
     /// Builds a RocksDB-backed database that uses optimistic transactions.
     #[cfg(all(not(target_arch = "wasm32"), feature = "rocksdb"))]
-    pub fn build_optimistic(self) -> Result<Database<OptimisticRocksStorage>, DatabaseError> {
-        let storage = OptimisticRocksStorage::with_config(self.path, self.storage_config)?;
+    pub fn build_optimistic(mut self) -> Result<Database<OptimisticRocksStorage>, DatabaseError> {
+        let storage =
+            OptimisticRocksStorage::with_config(mem::take(&mut self.path), self.storage_config)?;
 
-        Self::_build::<OptimisticRocksStorage>(
-            storage,
-            self.histogram_buckets,
-            self.transaction_isolation,
-        )
+        self._build::<OptimisticRocksStorage>(storage)
     }
 
-    fn _build<T: Storage>(
-        storage: T,
-        histogram_buckets: Option<usize>,
-        transaction_isolation: Option<TransactionIsolationLevel>,
-    ) -> Result<Database<T>, DatabaseError> {
+    fn _build<T: Storage>(self, storage: T) -> Result<Database<T>, DatabaseError> {
+        let DataBaseBuilder {
+            histogram_buckets,
+            transaction_isolation,
+            aggregate_functions,
+            #[cfg(feature = "time")]
+            time_zone,
+            ..
+        } = self;
         if matches!(histogram_buckets, Some(0)) {
             return Err(DatabaseError::InvalidValue(
                 "histogram buckets must be >= 1".to_string(),
@@ -280,12 +356,16 @@ impl DataBaseBuilder {
         let mut state = State {
             scala_functions: Default::default(),
             table_functions: Default::default(),
+            aggregate_functions: Default::default(),
             meta_cache,
             table_cache,
             view_cache,
+            sequence_cache: SequenceCache::default(),
             table_arena,
             optimizer_pipeline: default_optimizer_pipeline(),
             histogram_buckets,
+            #[cfg(feature = "time")]
+            time_zone,
             _p: Default::default(),
         };
 
@@ -294,15 +374,98 @@ impl DataBaseBuilder {
         #[cfg(feature = "time")]
         state.load_scalar_function(CurrentDate::new());
         #[cfg(feature = "time")]
-        state.load_scalar_function(CurrentTimeStamp::new());
+        state.load_scalar_function(CurrentTimeStamp::new("current_timestamp".to_string()));
+        #[cfg(feature = "time")]
+        state.load_scalar_function(CurrentTimeStamp::new("now".to_string()));
         state.load_scalar_function(Lower::new());
         state.load_scalar_function(OctetLength::new());
         state.load_scalar_function(Upper::new());
+        // math
+        for ty in numeric_types() {
+            state.load_scalar_function(Abs::new(ty.clone()));
+            state.load_scalar_function(Modulo::new(ty.clone()));
+            state.load_scalar_function(Sign::new(ty));
+        }
+        for ty in fractional_types() {
+            state.load_scalar_function(Ceil::new("ceil".to_string(), ty.clone()));
+            state.load_scalar_function(Ceil::new("ceiling".to_string(), ty.clone()));
+            state.load_scalar_function(Floor::new(ty.clone()));
+            state.load_scalar_function(Round::new(ty.clone(), false));
+            state.load_scalar_function(Round::new(ty, true));
+        }
+        for ty in Extremum::types() {
+            state.load_scalar_function(Extremum::greatest(ty.clone()));
+            state.load_scalar_function(Extremum::least(ty));
+        }
+        state.load_scalar_function(Exp::new());
+        state.load_scalar_function(Ln::new());
+        state.load_scalar_function(Log::new(false));
+        state.load_scalar_function(Log::new(true));
+        state.load_scalar_function(Power::new("power".to_string()));
+        state.load_scalar_function(Power::new("pow".to_string()));
+        state.load_scalar_function(Sqrt::new());
+        // string
+        state.load_scalar_function(Concat::new());
+        state.load_scalar_function(ConcatWs::new());
+        #[cfg(feature = "digest")]
+        {
+            state.load_scalar_function(Digest::new(DigestAlgorithm::Md5));
+            state.load_scalar_function(Digest::new(DigestAlgorithm::Sha256));
+        }
+        for side in [Side::Left, Side::Right] {
+            state.load_scalar_function(LeftRight::new(side));
+            state.load_scalar_function(Pad::new(side, false));
+            state.load_scalar_function(Pad::new(side, true));
+            state.load_scalar_function(Trim::new(side, false));
+            state.load_scalar_function(Trim::new(side, true));
+        }
+        state.load_scalar_function(Repeat::new());
+        state.load_scalar_function(Replace::new());
+        state.load_scalar_function(Reverse::new());
+        state.load_scalar_function(SplitPart::new());
+        state.load_scalar_function(StartsWith::new());
+        // date/time
+        #[cfg(feature = "time")]
+        {
+            for ty in DatePart::types() {
+                state.load_scalar_function(DatePart::new("date_part", ty.clone()));
+                state.load_scalar_function(DatePart::new("extract", ty));
+            }
+            for ty in temporal_types() {
+                state.load_scalar_function(DateTrunc::new(ty.clone()));
+                state.load_scalar_function(DateAdd::new(ty.clone(), true));
+                state.load_scalar_function(DateAdd::new(ty.clone(), false));
+                state.load_scalar_function(ToChar::new(ty.clone()));
+                state.load_scalar_function(Strftime::new(ty.clone()));
+                state.load_scalar_function(AtTimeZone::new(ty));
+            }
+            state.load_scalar_function(DateDiff::new());
+            state.load_scalar_function(ToDate::new(false));
+            state.load_scalar_function(ToDate::new(true));
+        }
+        // json
+        for function in JsonPath::all() {
+            state.load_scalar_function(function);
+        }
+        for ty in JsonSet::value_types() {
+            state.load_scalar_function(JsonSet::new(ty));
+        }
+        state.load_scalar_function(JsonArrayLength::new(false));
+        state.load_scalar_function(JsonArrayLength::new(true));
+        state.load_scalar_function(JsonTypeof::new());
         state.load_table_function(Numbers::new())?;
+        state.load_table_function(JsonEach::new(false))?;
+        state.load_table_function(JsonEach::new(true))?;
+        state.load_table_function(Unnest::new())?;
+        for function in aggregate_functions {
+            state.load_aggregate_function(function);
+        }
 
         Ok(Database {
             storage,
             transaction_isolation,
+            #[cfg(feature = "time")]
+            time_zone: RwLock::new(time_zone),
             state,
         })
     }
@@ -360,9 +523,12 @@ fn default_optimizer_pipeline() -> HepOptimizerPipeline {
             ],
         )
         .after_batch(
-            "Parameterize Mark Apply".to_string(),
-            HepBatchStrategy::once_topdown(),
-            vec![NormalizationRuleImpl::ParameterizeMarkApply],
+            "Parameterize Index Probes".to_string(),
+            HepBatchStrategy::fix_point_topdown(10),
+            vec![
+                NormalizationRuleImpl::ParameterizeMarkApply,
+                NormalizationRuleImpl::ParameterizeIndexJoin,
+            ],
         )
         .after_batch(
             "Expression Remapper".to_string(),
@@ -414,12 +580,16 @@ fn default_optimizer_pipeline() -> HepOptimizerPipeline {
 pub(crate) struct State<S> {
     scala_functions: ScalaFunctions,
     table_functions: TableFunctions,
+    aggregate_functions: AggregateFunctions,
     meta_cache: StatisticsMetaCache,
     table_cache: TableCache,
     view_cache: ViewCache,
+    sequence_cache: SequenceCache,
     table_arena: TableArenaCell,
     optimizer_pipeline: HepOptimizerPipeline,
     histogram_buckets: Option<usize>,
+    #[cfg(feature = "time")]
+    time_zone: TimeZone,
     _p: PhantomData<S>,
 }
 
@@ -430,6 +600,9 @@ impl<S: Storage> State<S> {
     fn table_functions(&self) -> &TableFunctions {
         &self.table_functions
     }
+    fn aggregate_functions(&self) -> &AggregateFunctions {
+        &self.aggregate_functions
+    }
     pub(crate) fn meta_cache(&self) -> &StatisticsMetaCache {
         &self.meta_cache
     }
@@ -442,12 +615,21 @@ impl<S: Storage> State<S> {
     pub(crate) fn table_arena(&self) -> &TableArenaCell {
         &self.table_arena
     }
+    #[cfg(feature = "time")]
+    pub(crate) fn default_time_zone(&self) -> TimeZone {
+        self.time_zone
+    }
 
     fn load_scalar_function(&mut self, function: Arc<dyn ScalarFunctionImpl>) {
         self.scala_functions
             .insert(function.summary().clone(), function);
     }
 
+    fn load_aggregate_function(&mut self, function: Arc<dyn AggregateFunctionImpl>) {
+        self.aggregate_functions
+            .insert(function.summary().clone(), function);
+    }
+
     fn load_table_function(
         &mut self,
         function: Arc<dyn TableFunctionImpl>,
@@ -516,6 +698,7 @@ impl<S: Storage> State<S> {
                 transaction,
                 self.scala_functions(),
                 self.table_functions(),
+                self.aggregate_functions(),
             ),
             &params,
             None,
@@ -536,6 +719,16 @@ impl<S: Storage> State<S> {
         Ok((best_plan, plan_arena))
     }
 
+    /// Describes an optimized plan as an [`ExplainNode`] tree.
+    pub(crate) fn explain_node(
+        &self,
+        mut plan: LogicalPlan,
+        arena: &mut PlanArena,
+    ) -> Result<ExplainNode, DatabaseError> {
+        let loader = StatisticMetaLoader::new(self.meta_cache());
+        plan.explain_node(arena, &CardinalityEstimator::new(&loader))
+    }
+
     pub(crate) fn execute<'a, 'txn, A, F>(
         &'a self,
         transaction: &'a mut S::TransactionType<'txn>,
@@ -566,8 +759,10 @@ impl<S: Storage> State<S> {
                 &self.table_cache,
                 &self.view_cache,
                 &self.meta_cache,
+                &self.sequence_cache,
                 &self.scala_functions,
                 &self.table_functions,
+                &self.aggregate_functions,
             );
             let root = build_write(&mut arena, &mut plan_arena, plan, read_context, transaction);
             let executor = Executor::new(arena, root);
@@ -604,9 +799,11 @@ impl<S: Storage> State<S> {
         let State {
             scala_functions,
             table_functions,
+            aggregate_functions,
             meta_cache,
             table_cache,
             view_cache,
+            sequence_cache,
             table_arena,
             optimizer_pipeline,
             histogram_buckets,
@@ -620,6 +817,7 @@ impl<S: Storage> State<S> {
                 transaction,
                 scala_functions,
                 table_functions,
+                aggregate_functions,
             ),
             &params,
             None,
@@ -642,8 +840,10 @@ impl<S: Storage> State<S> {
             table_cache,
             view_cache,
             meta_cache,
+            sequence_cache,
             scala_functions,
             table_functions,
+            aggregate_functions,
         );
         let root = build_write(&mut arena, &mut plan_arena, plan, cache, transaction);
         let executor = Executor::new(arena, root);
@@ -656,6 +856,8 @@ impl<S: Storage> State<S> {
 pub struct Database<S: Storage> {
     pub(crate) storage: S,
     pub(crate) transaction_isolation: TransactionIsolationLevel,
+    #[cfg(feature = "time")]
+    pub(crate) time_zone: RwLock<TimeZone>,
     pub(crate) state: State<S>,
 }
 
@@ -681,6 +883,11 @@ impl DDLApply {
                 }
                 catalog_changed = true;
             }
+            DDLApply::UpdateTableMeta { meta } => {
+                if let Some(table) = state.table_cache.get_mut(&meta.table_name) {
+                    table.set_meta(meta);
+                }
+            }
             DDLApply::DropTable { name } => {
                 state.table_cache.remove(&name);
                 state
@@ -730,6 +937,8 @@ impl<S: Storage> Database<S> {
             &mut PlanArena<'a>,
         ) -> Result<LogicalPlan, DatabaseError>,
     {
+        #[cfg(feature = "time")]
+        let _scope = self.time_zone().enter();
         let transaction = Box::into_raw(Box::new(
             self.storage
                 .transaction_with_isolation(self.transaction_isolation)?,
@@ -783,6 +992,10 @@ impl<S: Storage> Database<S> {
                 self.state.load_scalar_function(function);
                 Ok(())
             }
+            CatalogKind::AggregateFunction(function) => {
+                self.state.load_aggregate_function(function);
+                Ok(())
+            }
             CatalogKind::TableFunction(function) => {
                 self.state.load_table_function(function)?;
                 self.state.recycle_table_arena()?;
@@ -823,6 +1036,7 @@ impl<S: Storage> Database<S> {
                         &self.state.table_arena,
                         &self.state.scala_functions,
                         &self.state.table_functions,
+                        &self.state.aggregate_functions,
                         name.clone(),
                     )?
                     .ok_or(DatabaseError::ViewNotFound)?;
@@ -845,6 +1059,8 @@ impl<S: Storage> Database<S> {
         Ok(DBTransaction {
             inner: transaction,
             state: &self.state,
+            #[cfg(feature = "time")]
+            time_zone: self.time_zone(),
         })
     }
 
@@ -863,6 +1079,22 @@ impl<S: Storage> Database<S> {
     pub fn transaction_isolation(&self) -> TransactionIsolationLevel {
         self.transaction_isolation
     }
+
+    /// Returns the session time zone `TIMESTAMP WITH TIME ZONE` values are read and shown in.
+    #[cfg(feature = "time")]
+    pub fn time_zone(&self) -> TimeZone {
+        *self.time_zone.read().unwrap_or_else(|err| err.into_inner())
+    }
+
+    /// Switches the session time zone, as `SET TIME ZONE` does. Transactions already open
+    /// keep the zone they started with.
+    #[cfg(feature = "time")]
+    pub fn set_time_zone(&self, time_zone: TimeZone) {
+        *self
+            .time_zone
+            .write()
+            .unwrap_or_else(|err| err.into_inner()) = time_zone;
+    }
 }
 
 impl<'a, S: Storage> BindSource for &'a Database<S> {
@@ -877,6 +1109,8 @@ impl<'a, S: Storage> BindSource for &'a Database<S> {
             &mut PlanArena<'_>,
         ) -> Result<LogicalPlan, DatabaseError>,
     {
+        #[cfg(feature = "time")]
+        let _scope = self.time_zone().enter();
         let transaction = Box::into_raw(Box::new(
             self.storage
                 .transaction_with_isolation(self.transaction_isolation)?,
@@ -910,6 +1144,8 @@ impl<'a, S: Storage> BindSource for &'a Database<S> {
             &mut PlanArena<'_>,
         ) -> Result<LogicalPlan, DatabaseError>,
     {
+        #[cfg(feature = "time")]
+        let _scope = self.time_zone().enter();
         let mut transaction = self
             .storage
             .transaction_with_isolation(self.transaction_isolation)?;
@@ -946,6 +1182,13 @@ pub trait ResultIter {
     /// Finishes consuming the iterator and flushes any remaining work.
     fn done(self) -> Result<(), DatabaseError>;
 
+    #[cfg(feature = "time")]
+    /// The session time zone of the statement, which its timestamps with time zone are shown
+    /// in. Values kept past `next_tuple` are shown in it with
+    /// [`DataValue::display_in`](crate::types::value::DataValue::display_in) and read with the
+    /// ORM's `FromDataValue::from_data_value_in`.
+    fn time_zone(&self) -> TimeZone;
+
     #[cfg(feature = "orm")]
     /// Converts this iterator into a typed ORM iterator.
     ///
@@ -1066,12 +1309,19 @@ impl<S: Storage> ResultIter for DatabaseIter<'_, S> {
     fn done(self) -> Result<(), DatabaseError> {
         DatabaseIter::done(self)
     }
+
+    #[cfg(feature = "time")]
+    fn time_zone(&self) -> TimeZone {
+        unsafe { (*self.inner).time_zone }
+    }
 }
 
 /// Explicit transaction handle created by [`Database::new_transaction`].
 pub struct DBTransaction<'a, S: Storage + 'a> {
     pub(crate) inner: S::TransactionType<'a>,
     pub(crate) state: &'a State<S>,
+    #[cfg(feature = "time")]
+    pub(crate) time_zone: TimeZone,
 }
 
 impl<'txn, S: Storage> DBTransaction<'txn, S> {
@@ -1081,6 +1331,18 @@ impl<'txn, S: Storage> DBTransaction<'txn, S> {
 
         Ok(())
     }
+
+    /// Returns the time zone of this transaction, which starts as the database's.
+    #[cfg(feature = "time")]
+    pub fn time_zone(&self) -> TimeZone {
+        self.time_zone
+    }
+
+    /// Switches the time zone for the rest of this transaction only.
+    #[cfg(feature = "time")]
+    pub fn set_time_zone(&mut self, time_zone: TimeZone) {
+        self.time_zone = time_zone;
+    }
 }
 
 impl<'a, 'txn, S: Storage> BindSource for &'a mut DBTransaction<'txn, S> {
@@ -1095,6 +1357,8 @@ impl<'a, 'txn, S: Storage> BindSource for &'a mut DBTransaction<'txn, S> {
             &mut PlanArena<'_>,
         ) -> Result<LogicalPlan, DatabaseError>,
     {
+        #[cfg(feature = "time")]
+        let _scope = self.time_zone.enter();
         let transaction = std::ptr::from_mut(&mut self.inner);
         let (schema, plan_arena, executor) =
             self.state
@@ -1116,6 +1380,8 @@ impl<'a, 'txn, S: Storage> BindSource for &'a mut DBTransaction<'txn, S> {
             &mut PlanArena<'_>,
         ) -> Result<LogicalPlan, DatabaseError>,
     {
+        #[cfg(feature = "time")]
+        let _scope = self.time_zone.enter();
         self.inner.begin_statement_scope()?;
         let (plan, mut arena) = self.state.build_plan(params, &self.inner, build)?;
         Ok(plan.explain(&mut arena, 0))
@@ -1130,6 +1396,8 @@ pub struct TransactionIter<'a, T: Transaction + 'a> {
     transaction: *mut T,
     statement_scope_active: bool,
     ddl_apply: Vec<DDLApply>,
+    #[cfg(feature = "time")]
+    time_zone: TimeZone,
 }
 
 impl<'a, T: Transaction + 'a> TransactionIter<'a, T> {
@@ -1146,6 +1414,8 @@ impl<'a, T: Transaction + 'a> TransactionIter<'a, T> {
             transaction,
             statement_scope_active: true,
             ddl_apply: Vec::new(),
+            #[cfg(feature = "time")]
+            time_zone: session_time_zone(),
         }
     }
 
@@ -1180,6 +1450,9 @@ impl<'a, T: Transaction + 'a> TransactionIter<'a, T> {
         let Some(executor) = self.executor.as_mut() else {
             return Ok(None);
         };
+        // rows are produced lazily, after the statement's planning scope has ended
+        #[cfg(feature = "time")]
+        let _scope = self.time_zone.enter();
         let executor_ptr = std::ptr::from_mut(executor);
         let plan_arena = self
             .plan_arena
@@ -1239,15 +1512,20 @@ impl<T: Transaction> ResultIter for TransactionIter<'_, T> {
     fn done(self) -> Result<(), DatabaseError> {
         TransactionIter::done(self)
     }
+
+    #[cfg(feature = "time")]
+    fn time_zone(&self) -> TimeZone {
+        self.time_zone
+    }
 }
 
 #[cfg(all(test, not(target_arch = "wasm32")))]
 pub(crate) mod test {
     use crate::binder::{Binder, BinderContext};
     use crate::catalog::{ColumnCatalog, ColumnDesc};
-    #[cfg(feature = "unsafe_txdb_checkpoint")]
-    use crate::db::CatalogKind;
-    use crate::db::{DataBaseBuilder, DatabaseError, ResultIter};
+    use crate::db::{CatalogKind, DataBaseBuilder, DatabaseError, ResultIter};
+    use crate::expression::function::aggregate::AggregateFunctionImpl;
+    use crate::expression::function::FunctionSummary;
     use crate::expression::ScalarExpression;
     use crate::planner::operator::join::JoinCondition;
     use crate::planner::operator::Operator;
@@ -1264,7 +1542,6 @@ pub(crate) mod test {
     use std::sync::atomic::AtomicUsize;
     #[cfg(feature = "unsafe_txdb_checkpoint")]
     use std::sync::atomic::Ordering;
-    #[cfg(feature = "unsafe_txdb_checkpoint")]
     use std::sync::Arc;
     #[cfg(feature = "unsafe_txdb_checkpoint")]
     use std::thread;
@@ -1434,6 +1711,115 @@ pub(crate) mod test {
         Ok(())
     }
 
+    /// The product of integers, keeping the running product as its state.
+    #[derive(Debug)]
+    struct Product {
+        summary: FunctionSummary,
+    }
+
+    impl AggregateFunctionImpl for Product {
+        fn init(&self) -> Result<DataValue, DatabaseError> {
+            Ok(DataValue::Int64(1))
+        }
+
+        fn update(&self, state: &mut DataValue, args: &[DataValue]) -> Result<(), DatabaseError> {
+            if let (DataValue::Int64(product), DataValue::Int32(value)) = (state, &args[0]) {
+                *product *= *value as i64;
+            }
+            Ok(())
+        }
+
+        fn merge(&self, state: &mut DataValue, other: DataValue) -> Result<(), DatabaseError> {
+            if let (DataValue::Int64(product), DataValue::Int64(other)) = (state, other) {
+                *product *= other;
+            }
+            Ok(())
+        }
+
+        fn finalize(&self, state: &DataValue) -> Result<DataValue, DatabaseError> {
+            Ok(state.clone())
+        }
+
+        fn return_type(&self) -> &LogicalType {
+            &LogicalType::Bigint
+        }
+
+        fn summary(&self) -> &FunctionSummary {
+            &self.summary
+        }
+    }
+
+    fn product() -> Arc<dyn AggregateFunctionImpl> {
+        Arc::new(Product {
+            summary: FunctionSummary {
+                name: "product".into(),
+                arg_types: vec![LogicalType::Integer],
+            },
+        })
+    }
+
+    #[test]
+    fn test_udaf() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        {
+            let mut kite_sql = DataBaseBuilder::path(temp_dir.path())
+                .register_aggregate_function(product())
+                .build_rocksdb()?;
+            kite_sql.ddl("create table t1(id int primary key, c1 int, c2 int)")?;
+            kite_sql
+                .run("insert into t1 values (1, 1, 2), (2, 1, 3), (3, 2, 4), (4, 2, null)")?
+                .done()?;
+            kite_sql.ddl("create view v1 as select c1, product(c2) as p from t1 group by c1")?;
+
+            let mut iter =
+                kite_sql.run("select c1, product(c2) from t1 group by c1 order by c1")?;
+            assert_eq!(
+                next_values(&mut iter)?,
+                Some(vec![DataValue::Int32(1), DataValue::Int64(6)])
+            );
+            assert_eq!(
+                next_values(&mut iter)?,
+                Some(vec![DataValue::Int32(2), DataValue::Int64(4)])
+            );
+            assert!(next_values(&mut iter)?.is_none());
+            iter.done()?;
+
+            #[cfg(feature = "spill")]
+            {
+                let mut iter = kite_sql.run(
+                    "select /*+ FORCE_AGG_SPILL */ c1, product(c2) from t1 group by c1 order by c1",
+                )?;
+                assert_eq!(
+                    next_values(&mut iter)?,
+                    Some(vec![DataValue::Int32(1), DataValue::Int64(6)])
+                );
+                assert_eq!(
+                    next_values(&mut iter)?,
+                    Some(vec![DataValue::Int32(2), DataValue::Int64(4)])
+                );
+                iter.done()?;
+            }
+        }
+
+        // views using the function decode against the functions registered on the builder
+        let mut kite_sql = DataBaseBuilder::path(temp_dir.path())
+            .register_aggregate_function(product())
+            .build_rocksdb()?;
+        kite_sql.load(CatalogKind::Table("t1".to_string().into()))?;
+        kite_sql.load(CatalogKind::View("v1".to_string().into()))?;
+        let mut iter = kite_sql.run("select p from v1 order by c1")?;
+        assert_eq!(next_values(&mut iter)?, Some(vec![DataValue::Int64(6)]));
+        assert_eq!(next_values(&mut iter)?, Some(vec![DataValue::Int64(4)]));
+        iter.done()?;
+
+        let mut kite_sql = DataBaseBuilder::path(temp_dir.path()).build_rocksdb()?;
+        kite_sql.load(CatalogKind::Table("t1".to_string().into()))?;
+        assert!(kite_sql
+            .load(CatalogKind::View("v1".to_string().into()))
+            .is_err());
+        Ok(())
+    }
+
     #[test]
     fn test_join_on_alias_right_key_is_localized() -> Result<(), DatabaseError> {
         let temp_dir = TempDir::new().expect("unable to create temporary working directory");
@@ -1453,6 +1839,7 @@ pub(crate) mod test {
                 &transaction,
                 kite_sql.state.scala_functions(),
                 kite_sql.state.table_functions(),
+                kite_sql.state.aggregate_functions(),
             ),
             &[],
             None,
@@ -1536,6 +1923,7 @@ pub(crate) mod test {
                 &transaction,
                 kite_sql.state.scala_functions(),
                 kite_sql.state.table_functions(),
+                kite_sql.state.aggregate_functions(),
             ),
             &[],
             None,
@@ -2070,6 +2458,31 @@ pub(crate) mod test {
         Ok(())
     }
 
+    #[test]
+    fn test_recursive_cte_iteration_limit() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let kite_sql = DataBaseBuilder::path(temp_dir.path()).build_rocksdb()?;
+
+        let err = (|| {
+            let mut iter = kite_sql.run(
+                "with recursive r(n) as (select 1 union all select n from r) select n from r",
+            )?;
+            while iter.next_tuple(|_, _| ())?.is_some() {}
+            iter.done()
+        })()
+        .expect_err("endless recursion should be rejected");
+        assert!(matches!(err, DatabaseError::RecursionLimitExceeded(_)));
+
+        let mut iter = kite_sql
+            .run("with recursive r(n) as (select 1 union select n from r) select n from r")?;
+        let mut rows = 0;
+        while iter.next_tuple(|_, _| rows += 1)?.is_some() {}
+        iter.done()?;
+        assert_eq!(rows, 1);
+
+        Ok(())
+    }
+
     #[test]
     fn test_bind_function_error_with_span() -> Result<(), DatabaseError> {
         let temp_dir = TempDir::new().expect("unable to create temporary working directory");
@@ -2145,6 +2558,114 @@ pub(crate) mod test {
         Ok(())
     }
 
+    #[cfg(feature = "time")]
+    #[test]
+    fn test_session_time_zone() -> Result<(), DatabaseError> {
+        use crate::types::timezone::TimeZone;
+
+        fn first_text<I: ResultIter>(mut iter: I) -> Result<Option<String>, DatabaseError> {
+            let text = iter.next_tuple(|_, tuple| tuple.values[0].to_string())?;
+            iter.done()?;
+            Ok(text)
+        }
+
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let tokyo = TimeZone::parse("Asia/Tokyo")?;
+        let mut kite_sql = DataBaseBuilder::path(temp_dir.path())
+            .time_zone(tokyo)
+            .build_rocksdb()?;
+        kite_sql.ddl("create table t_tz (a int primary key, b timestamptz)")?;
+        kite_sql
+            .run("insert into t_tz values (1, '2024-01-01 09:00:00')")?
+            .done()?;
+        assert_eq!(kite_sql.time_zone(), tokyo);
+        assert_eq!(
+            first_text(kite_sql.run("select b from t_tz")?)?.as_deref(),
+            Some("2024-01-01 09:00:00+09")
+        );
+
+        // a transaction starts in the database's zone and keeps its own changes
+        let mut tx = kite_sql.new_transaction()?;
+        tx.run("set time zone 'Europe/London'")?.done()?;
+        assert_eq!(tx.time_zone(), TimeZone::parse("Europe/London")?);
+        assert_eq!(kite_sql.time_zone(), tokyo);
+        assert_eq!(
+            first_text(tx.run("select b from t_tz")?)?.as_deref(),
+            Some("2024-01-01 00:00:00+00")
+        );
+        tx.run("set local time zone 'America/Sao_Paulo'")?.done()?;
+        assert_eq!(
+            first_text(tx.run("select b from t_tz")?)?.as_deref(),
+            Some("2023-12-31 21:00:00-03")
+        );
+        tx.run("set time zone default")?.done()?;
+        assert_eq!(tx.time_zone(), tokyo);
+        tx.commit()?;
+
+        kite_sql.run("set timezone = '-03:30'")?.done()?;
+        assert_eq!(kite_sql.time_zone(), TimeZone::parse("-03:30")?);
+        // `SET LOCAL` outside of a transaction has nothing to apply to
+        kite_sql.run("set local time zone 'UTC'")?.done()?;
+        assert_eq!(kite_sql.time_zone(), TimeZone::parse("-03:30")?);
+        assert_eq!(
+            first_text(kite_sql.run("show time zone")?)?.as_deref(),
+            Some("-03:30")
+        );
+        kite_sql.set_time_zone(TimeZone::UTC);
+        assert_eq!(
+            first_text(kite_sql.run("select b from t_tz")?)?.as_deref(),
+            Some("2024-01-01 00:00:00+00")
+        );
+        assert!(kite_sql.run("set time zone 'Atlantis/Lost'").is_err());
+        assert_eq!(kite_sql.time_zone(), TimeZone::UTC);
+
+        Ok(())
+    }
+
+    #[cfg(feature = "time")]
+    #[test]
+    fn test_time_zone_of_values_kept_past_next_tuple() -> Result<(), DatabaseError> {
+        use crate::types::timezone::TimeZone;
+
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let kolkata = TimeZone::parse("Asia/Kolkata")?;
+        let kite_sql = DataBaseBuilder::path(temp_dir.path())
+            .time_zone(kolkata)
+            .build_rocksdb()?;
+        let mut iter = kite_sql.run(
+            "select cast('2024-01-15 08:30:00+00' as timestamptz), \
+             array[cast('2024-01-15 08:30:00+00' as timestamptz)]",
+        )?;
+        let time_zone = iter.time_zone();
+        let values = iter
+            .next_tuple(|_, tuple| tuple.values.clone())?
+            .expect("one row");
+        iter.done()?;
+
+        assert_eq!(time_zone, kolkata);
+        // the statement is over, so the thread no longer has its session time zone
+        assert_eq!(values[0].to_string(), "2024-01-15 08:30:00+00");
+        assert_eq!(
+            values[0].display_in(time_zone).to_string(),
+            "2024-01-15 14:00:00+05:30"
+        );
+        assert_eq!(
+            values[1].display_in(time_zone).to_string(),
+            r#"{"2024-01-15 14:00:00+05:30"}"#
+        );
+        #[cfg(feature = "orm")]
+        {
+            use crate::orm::FromDataValue;
+            use chrono::{DateTime, FixedOffset};
+
+            let datetime =
+                DateTime::<FixedOffset>::from_data_value_in(values[0].clone(), time_zone)?;
+            assert_eq!(datetime.to_rfc3339(), "2024-01-15T14:00:00+05:30");
+        }
+
+        Ok(())
+    }
+
     #[test]
     fn test_transaction_run_multi_statement() -> Result<(), DatabaseError> {
         let temp_dir = TempDir::new().expect("unable to create temporary working directory");
@@ -2511,6 +3032,162 @@ pub(crate) mod test {
             total as i32
         );
 
+        Ok(())
+    }
+    #[test]
+    fn test_explain_analyze_reports_runtime_statistics() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let mut kite_sql = DataBaseBuilder::path(temp_dir.path()).build_rocksdb()?;
+
+        kite_sql.ddl("create table t_analyze(id int primary key, v int)")?;
+        kite_sql
+            .run("insert into t_analyze select number, number % 3 from table(numbers(200))")?
+            .done()?;
+        kite_sql.analyze("t_analyze")?;
+
+        let explain = |sql: &str| -> Result<String, DatabaseError> {
+            let mut iter = kite_sql.run(sql)?;
+            let plan = match next_values(&mut iter)?.as_deref() {
+                Some([DataValue::Utf8 { value, .. }]) => value.clone(),
+                other => panic!("expected a single plan row, got {other:?}"),
+            };
+            iter.done()?;
+            Ok(plan)
+        };
+
+        let plan = explain("explain select id from t_analyze where id < 5")?;
+        assert!(!plan.contains("actual rows"), "{plan}");
+
+        let plan = explain("explain analyze select id from t_analyze where id < 5")?;
+        assert!(
+            plan.contains("(estimated rows: 5, actual rows: 5, loops: 1, time: "),
+            "{plan}"
+        );
+        assert!(plan.contains("keys read: 5)"), "{plan}");
+
+        let plan = explain("explain analyze select v, count(*) from t_analyze group by v")?;
+        assert!(plan.contains("actual rows: 3, loops: 1"), "{plan}");
+        assert!(plan.contains("actual rows: 200, loops: 1"), "{plan}");
+        assert!(plan.contains("keys read: 200)"), "{plan}");
+
+        // the statement is executed, so a DML statement applies its changes
+        let plan = explain("explain analyze delete from t_analyze where id >= 5")?;
+        assert!(plan.starts_with("Delete"), "{plan}");
+        assert!(plan.contains("actual rows: 1, loops: 1"), "{plan}");
+        assert_eq!(
+            read_single_i32(kite_sql.run("select count(*) from t_analyze")?)?,
+            5
+        );
+
+        Ok(())
+    }
+
+    #[cfg(feature = "time")]
+    #[test]
+    fn test_now_minus_interval_uses_index() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let mut kite_sql = DataBaseBuilder::path(temp_dir.path()).build_rocksdb()?;
+
+        kite_sql.ddl("create table t_events(id int primary key, ts timestamp)")?;
+        kite_sql.ddl("create index t_events_ts_index on t_events(ts)")?;
+        kite_sql
+            .run(
+                "insert into t_events select number, now() - cast(cast(number as varchar) || ' hours' as interval) from table(numbers(200))",
+            )?
+            .done()?;
+        kite_sql.analyze("t_events")?;
+
+        // `now()` is folded before the range is detached, so the filter becomes an index range
+        let mut iter =
+            kite_sql.run("explain select id from t_events where ts >= now() - interval '1 day'")?;
+        let plan = match next_values(&mut iter)?.as_deref() {
+            Some([DataValue::Utf8 { value, .. }]) => value.clone(),
+            other => panic!("expected a single plan row, got {other:?}"),
+        };
+        iter.done()?;
+        assert!(plan.contains("IndexScan By"), "{plan}");
+        assert!(!plan.contains("Filter"), "{plan}");
+
+        let mut iter =
+            kite_sql.run("select count(*) from t_events where ts >= now() - interval '1 day'")?;
+        assert!(matches!(
+            next_values(&mut iter)?.as_deref(),
+            Some([DataValue::Int32(24 | 25)])
+        ));
+        iter.done()?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_explain_plan_structured_tree() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let mut kite_sql = DataBaseBuilder::path(temp_dir.path()).build_rocksdb()?;
+
+        kite_sql.ddl("create table t_plan_l(id int primary key, v int)")?;
+        kite_sql.ddl("create table t_plan_r(id int primary key, w int)")?;
+        kite_sql
+            .run("insert into t_plan_l select number, number % 3 from table(numbers(200))")?
+            .done()?;
+        kite_sql
+            .run("insert into t_plan_r select number, number from table(numbers(100))")?
+            .done()?;
+        kite_sql.analyze("t_plan_l")?;
+        kite_sql.analyze("t_plan_r")?;
+
+        let sql = "select v, w from t_plan_l join t_plan_r on t_plan_l.v = t_plan_r.id where w > 1";
+        let plan = kite_sql.explain_plan(sql)?;
+        assert_eq!(plan.operator, "Project");
+        assert_eq!(plan.physical_option.as_deref(), Some("Project"));
+        assert_eq!(plan.columns, vec!["t_plan_l.v", "t_plan_r.w"]);
+        assert!(plan.actual.is_none());
+
+        let join = &plan.children[0].children[0];
+        assert_eq!(join.operator, "Join");
+        assert_eq!(join.physical_option.as_deref(), Some("HashJoin"));
+        assert_eq!(join.predicates, vec!["t_plan_r.id = t_plan_l.v"]);
+        assert!(join.estimated_cost.is_some(), "{join:?}");
+
+        let filter = &join.children[0];
+        assert_eq!(filter.predicates, vec!["(t_plan_r.w > 1)"]);
+        let (scan_r, scan_l) = (&filter.children[0], &join.children[1]);
+        assert_eq!(scan_r.operator, "TableScan");
+        assert_eq!(scan_r.estimated_rows, Some(100.0));
+        assert_eq!(scan_l.columns, vec!["t_plan_l.v"]);
+        assert_eq!(scan_l.estimated_cost, Some(200));
+
+        let mut iter = kite_sql.run(format!("explain (format json) {sql}"))?;
+        let json = match next_values(&mut iter)?.as_deref() {
+            Some([DataValue::Utf8 { value, .. }]) => value.clone(),
+            other => panic!("expected a single plan row, got {other:?}"),
+        };
+        iter.done()?;
+        assert_eq!(json, plan.to_json());
+        assert!(
+            json.starts_with(r#"{"operator":"Project","detail":"Projection"#),
+            "{json}"
+        );
+        assert!(
+            json.contains(r#""predicates":["t_plan_r.id = t_plan_l.v"]"#),
+            "{json}"
+        );
+
+        let mut iter = kite_sql.run(format!("explain analyze format json {sql}"))?;
+        let json = match next_values(&mut iter)?.as_deref() {
+            Some([DataValue::Utf8 { value, .. }]) => value.clone(),
+            other => panic!("expected a single plan row, got {other:?}"),
+        };
+        iter.done()?;
+        assert!(
+            json.contains(r#""actual":{"rows":98,"loops":1,"time_ms":"#),
+            "{json}"
+        );
+        assert!(json.contains(r#","keys_read":200}"#), "{json}");
+
+        let mut transaction = kite_sql.new_transaction()?;
+        assert_eq!(transaction.explain_plan(sql)?, plan);
+        transaction.commit()?;
+
         Ok(())
     }
 }
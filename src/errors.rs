@@ -66,25 +66,36 @@ pub enum DatabaseError {
         span: Option<SqlErrorSpan>,
     },
     ChannelClose,
+    CheckViolation {
+        constraint: String,
+    },
     ColumnsEmpty,
     ColumnIdNotFound(String),
     ColumnNotFound {
         name: String,
         span: Option<SqlErrorSpan>,
     },
+    ConflictTargetNotFound(String),
+    ConstraintNotFound(String),
     #[cfg(feature = "copy")]
     Csv(csv::Error),
     DefaultNotColumnRef,
     DefaultNotExist,
+    DivisionByZero,
     DuplicateColumn(String),
+    DuplicateConstraint(String),
     DuplicateSourceHash(String),
     DuplicateIndex(String),
     DuplicatePrimaryKey,
     DuplicateUniqueValue,
+    ForeignKeyViolation {
+        constraint: String,
+    },
     FunctionNotFound {
         name: String,
         span: Option<SqlErrorSpan>,
     },
+    GeneratedAlways(String),
     EmptyPlan,
     EmptyStatement,
     EvaluatorNotFound,
@@ -102,6 +113,7 @@ pub enum DatabaseError {
     InvalidType,
     InvalidValue(String),
     IO(std::io::Error),
+    LockTimeout(&'static str),
     MisMatch(&'static str, &'static str),
     NeedNullAbleOrDefault,
     ParametersNotFound {
@@ -123,15 +135,25 @@ pub enum DatabaseError {
     ParserSql(ParserError),
     PrimaryKeyNotFound,
     PrimaryKeyTooManyLayers,
+    RecursionLimitExceeded(usize),
     #[cfg(all(not(target_arch = "wasm32"), feature = "lmdb"))]
     Lmdb(lmdb::Error),
     #[cfg(all(not(target_arch = "wasm32"), feature = "rocksdb"))]
     RocksDB(rocksdb::Error),
+    SequenceExhausted(String),
+    SequenceExists(String),
+    SequenceNotCalled(String),
+    SequenceNotFound(String),
     SharedNotAlign,
     SourceNotFound,
     TableExists,
     TableNotFound,
+    TableReferenced {
+        table: String,
+        referencing: String,
+    },
     TransactionAlreadyExists,
+    TransactionConflict,
     #[cfg(feature = "decimal")]
     TryFromDecimal(rust_decimal::Error),
     TryFromInt(TryFromIntError),
@@ -156,6 +178,9 @@ impl fmt::Display for DatabaseError {
                 write!(f, "cast fail: {from} -> {to}{}", format_sql_error_loc(span))
             }
             Self::ChannelClose => f.write_str("channel close"),
+            Self::CheckViolation { constraint } => {
+                write!(f, "row violates check constraint: `{constraint}`")
+            }
             Self::ColumnsEmpty => f.write_str("columns empty"),
             Self::ColumnIdNotFound(value) => write!(f, "column id: `{value}` not found"),
             Self::ColumnNotFound { name, span } => {
@@ -165,6 +190,11 @@ impl fmt::Display for DatabaseError {
                     format_sql_error_loc(span)
                 )
             }
+            Self::ConflictTargetNotFound(value) => write!(
+                f,
+                "no primary key or unique index matches the ON CONFLICT target: `{value}`"
+            ),
+            Self::ConstraintNotFound(value) => write!(f, "constraint: `{value}` not found"),
             #[cfg(feature = "copy")]
             Self::Csv(err) => write!(f, "csv error: {err}"),
             Self::DefaultNotColumnRef => {
@@ -172,14 +202,21 @@ impl fmt::Display for DatabaseError {
             }
             Self::DefaultNotExist => f.write_str("default does not exist"),
             Self::DuplicateColumn(value) => write!(f, "column: `{value}` already exists"),
+            Self::DuplicateConstraint(value) => {
+                write!(f, "constraint: `{value}` already exists")
+            }
             Self::DuplicateSourceHash(value) => {
                 write!(f, "table or view: `{value}` hash already exists")
             }
             Self::DuplicateIndex(value) => write!(f, "index: `{value}` already exists"),
+            Self::DivisionByZero => f.write_str("division by zero"),
             Self::DuplicatePrimaryKey => f.write_str("duplicate primary key"),
             Self::DuplicateUniqueValue => {
                 f.write_str("the column has been declared unique and the value already exists")
             }
+            Self::ForeignKeyViolation { constraint } => {
+                write!(f, "row violates foreign key constraint: `{constraint}`")
+            }
             Self::FunctionNotFound { name, span } => {
                 write!(
                     f,
@@ -187,6 +224,10 @@ impl fmt::Display for DatabaseError {
                     format_sql_error_loc(span)
                 )
             }
+            Self::GeneratedAlways(value) => write!(
+                f,
+                "column: `{value}` is generated always as identity and cannot be assigned"
+            ),
             Self::EmptyPlan => f.write_str("empty plan"),
             Self::EmptyStatement => f.write_str("sql statement is empty"),
             Self::EvaluatorNotFound => f.write_str("evaluator not found"),
@@ -204,6 +245,7 @@ impl fmt::Display for DatabaseError {
             Self::InvalidType => f.write_str("invalid type"),
             Self::InvalidValue(value) => write!(f, "invalid value: {value}"),
             Self::IO(err) => write!(f, "io: {err}"),
+            Self::LockTimeout(value) => write!(f, "lock timeout: {value}"),
             Self::MisMatch(left, right) => write!(f, "{left} and {right} do not match"),
             Self::NeedNullAbleOrDefault => {
                 f.write_str("add column must be nullable or specify a default value")
@@ -229,17 +271,40 @@ impl fmt::Display for DatabaseError {
             Self::PrimaryKeyTooManyLayers => {
                 f.write_str("primaryKey only allows single or multiple values")
             }
+            Self::RecursionLimitExceeded(limit) => {
+                write!(
+                    f,
+                    "recursive query exceeded the limit of {limit} iterations"
+                )
+            }
             #[cfg(all(not(target_arch = "wasm32"), feature = "lmdb"))]
             Self::Lmdb(err) => write!(f, "lmdb: {err}"),
             #[cfg(all(not(target_arch = "wasm32"), feature = "rocksdb"))]
             Self::RocksDB(err) => write!(f, "rocksdb: {err}"),
+            Self::SequenceExhausted(value) => {
+                write!(f, "sequence: `{value}` has no values left")
+            }
+            Self::SequenceExists(value) => write!(f, "sequence: `{value}` already exists"),
+            Self::SequenceNotCalled(value) => {
+                write!(f, "currval of sequence: `{value}` is not yet defined")
+            }
+            Self::SequenceNotFound(value) => write!(f, "sequence: `{value}` not found"),
             Self::SharedNotAlign => {
                 f.write_str("the number of caches cannot be divisible by the number of shards")
             }
             Self::SourceNotFound => f.write_str("the table or view not found"),
             Self::TableExists => f.write_str("the table already exists"),
             Self::TableNotFound => f.write_str("the table not found"),
+            Self::TableReferenced { table, referencing } => {
+                write!(
+                    f,
+                    "table: `{table}` is referenced by a foreign key of table: `{referencing}`"
+                )
+            }
             Self::TransactionAlreadyExists => f.write_str("transaction already exists"),
+            Self::TransactionConflict => {
+                f.write_str("transaction conflict: the data was modified by another transaction")
+            }
             #[cfg(feature = "decimal")]
             Self::TryFromDecimal(err) => write!(f, "try from decimal: {err}"),
             Self::TryFromInt(err) => write!(f, "try from int: {err}"),
@@ -653,14 +718,32 @@ mod tests {
                 },
                 "cast fail: Integer -> Varchar(None, CHARACTERS)",
             ),
+            (
+                DatabaseError::CheckViolation {
+                    constraint: "t_a_check".into(),
+                },
+                "row violates check constraint: `t_a_check`",
+            ),
             (
                 DatabaseError::ColumnIdNotFound("7".into()),
                 "column id: `7` not found",
             ),
+            (
+                DatabaseError::ConflictTargetNotFound("name".into()),
+                "no primary key or unique index matches the ON CONFLICT target: `name`",
+            ),
+            (
+                DatabaseError::ConstraintNotFound("t_check".into()),
+                "constraint: `t_check` not found",
+            ),
             (
                 DatabaseError::DuplicateColumn("id".into()),
                 "column: `id` already exists",
             ),
+            (
+                DatabaseError::DuplicateConstraint("t_check".into()),
+                "constraint: `t_check` already exists",
+            ),
             (
                 DatabaseError::DuplicateSourceHash("v".into()),
                 "table or view: `v` hash already exists",
@@ -669,6 +752,16 @@ mod tests {
                 DatabaseError::DuplicateIndex("idx".into()),
                 "index: `idx` already exists",
             ),
+            (
+                DatabaseError::ForeignKeyViolation {
+                    constraint: "t_a_fkey".into(),
+                },
+                "row violates foreign key constraint: `t_a_fkey`",
+            ),
+            (
+                DatabaseError::GeneratedAlways("id".into()),
+                "column: `id` is generated always as identity and cannot be assigned",
+            ),
             (
                 DatabaseError::Incomparable(LogicalType::Integer, LogicalType::Boolean),
                 "can not compare two types: Integer and Boolean",
@@ -677,14 +770,33 @@ mod tests {
                 DatabaseError::InvalidValue("NaN".into()),
                 "invalid value: NaN",
             ),
+            (
+                DatabaseError::LockTimeout("the database is in use"),
+                "lock timeout: the database is in use",
+            ),
             (
                 DatabaseError::MisMatch("left", "right"),
                 "left and right do not match",
             ),
+            (
+                DatabaseError::RecursionLimitExceeded(1000),
+                "recursive query exceeded the limit of 1000 iterations",
+            ),
+            (
+                DatabaseError::TableReferenced {
+                    table: "users".into(),
+                    referencing: "orders".into(),
+                },
+                "table: `users` is referenced by a foreign key of table: `orders`",
+            ),
             (
                 DatabaseError::TupleIdNotFound(DataValue::Int32(3)),
                 "tuple id: 3 not found",
             ),
+            (
+                DatabaseError::TransactionConflict,
+                "transaction conflict: the data was modified by another transaction",
+            ),
             (
                 DatabaseError::TooManyBuckets(8, 3),
                 "there are more buckets: 8 than elements: 3",
@@ -721,6 +833,7 @@ mod tests {
                 "default cannot be a column related to the table",
             ),
             (DatabaseError::DefaultNotExist, "default does not exist"),
+            (DatabaseError::DivisionByZero, "division by zero"),
             (DatabaseError::DuplicatePrimaryKey, "duplicate primary key"),
             (DatabaseError::EmptyPlan, "empty plan"),
             (DatabaseError::EmptyStatement, "sql statement is empty"),
@@ -745,6 +858,22 @@ mod tests {
                 DatabaseError::SharedNotAlign,
                 "the number of caches cannot be divisible by the number of shards",
             ),
+            (
+                DatabaseError::SequenceExhausted("s".into()),
+                "sequence: `s` has no values left",
+            ),
+            (
+                DatabaseError::SequenceExists("s".into()),
+                "sequence: `s` already exists",
+            ),
+            (
+                DatabaseError::SequenceNotCalled("s".into()),
+                "currval of sequence: `s` is not yet defined",
+            ),
+            (
+                DatabaseError::SequenceNotFound("s".into()),
+                "sequence: `s` not found",
+            ),
             (DatabaseError::SourceNotFound, "the table or view not found"),
             (DatabaseError::TableExists, "the table already exists"),
             (DatabaseError::TableNotFound, "the table not found"),
@@ -0,0 +1,115 @@
+// Copyright 2024 KipData/KiteSQL
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::visit_table_in_batches;
+use crate::errors::DatabaseError;
+use crate::execution::dml::check_constraints;
+use crate::execution::{
+    DDLApply, ExecArena, ExecId, ExecNode, ExecutionContext, ExecutorNode, WriteExecutor,
+};
+use crate::iter_ext::Itertools;
+use crate::planner::operator::alter_table::add_constraint::AddConstraintOperator;
+use crate::storage::Transaction;
+use crate::types::tuple_builder::TupleBuilder;
+
+pub struct AddConstraint {
+    op: Option<AddConstraintOperator>,
+}
+
+impl From<AddConstraintOperator> for AddConstraint {
+    fn from(op: AddConstraintOperator) -> Self {
+        Self { op: Some(op) }
+    }
+}
+
+impl<'a, T: Transaction + 'a> WriteExecutor<'a, T> for AddConstraint {
+    type Input = Self;
+
+    fn into_executor(
+        input: Self::Input,
+        arena: &mut ExecArena<'a, T>,
+        _plan_arena: &mut crate::planner::PlanArena<'a>,
+        _: ExecutionContext<'_>,
+        _: &T,
+    ) -> ExecId {
+        let executor = input;
+        arena.push(ExecNode::AddConstraint(executor))
+    }
+}
+
+impl<'a, T: Transaction + 'a> ExecutorNode<'a, T> for AddConstraint {
+    fn next_tuple(
+        &mut self,
+        arena: &mut ExecArena<'a, T>,
+        plan_arena: &mut crate::planner::PlanArena<'a>,
+    ) -> Result<(), DatabaseError> {
+        let table_cache = arena.table_cache();
+        let Some(AddConstraintOperator {
+            table_name,
+            check,
+            predicate,
+        }) = self.op.take()
+        else {
+            arena.finish();
+            return Ok(());
+        };
+
+        let (schema, pk_ty, mut checks) = {
+            let table_catalog = arena
+                .transaction()
+                .table(table_cache, table_name.clone())?
+                .ok_or(DatabaseError::TableNotFound)?;
+            if table_catalog.has_constraint(&check.name) {
+                return Err(DatabaseError::DuplicateConstraint(check.name));
+            }
+            (
+                table_catalog.columns().copied().collect_vec(),
+                table_catalog.primary_keys_type().clone(),
+                table_catalog.checks().to_vec(),
+            )
+        };
+        let predicates = [(check.name.clone(), predicate)];
+        {
+            let mut state = arena.local_state(plan_arena);
+            let plan_arena = state.plan_arena;
+            let (transaction, table_codec) = state.transaction_codec_mut();
+            visit_table_in_batches(
+                transaction,
+                table_codec,
+                &table_name,
+                &pk_ty,
+                schema.len(),
+                || {
+                    schema
+                        .iter()
+                        .map(|column| plan_arena.column(*column).datatype().serializable())
+                },
+                |tuple| check_constraints(&predicates, tuple),
+            )?;
+        }
+        checks.push(check);
+        {
+            let (transaction, table_codec) = arena.transaction_codec_mut();
+            let meta =
+                transaction.update_table_meta(table_codec, plan_arena, &table_name, |meta| {
+                    meta.checks = checks
+                })?;
+            arena.push_ddl_apply(DDLApply::UpdateTableMeta { meta });
+        }
+
+        TupleBuilder::build_result_into(arena.result_tuple_mut(), "1".to_string());
+        arena.resume();
+        Ok(())
+    }
+}
@@ -18,6 +18,7 @@ use crate::execution::{
     ExecutorNode, WriteExecutor,
 };
 use crate::expression::ScalarExpression;
+use crate::function::json_path::json_path_expr;
 use crate::planner::operator::create_index::CreateIndexOperator;
 use crate::planner::LogicalPlan;
 use crate::storage::Transaction;
@@ -79,6 +80,7 @@ impl<'a, T: Transaction + 'a> ExecutorNode<'a, T> for CreateIndex {
             columns,
             if_not_exists,
             ty,
+            json_path,
         }) = self.op.take()
         else {
             arena.finish();
@@ -107,6 +109,10 @@ impl<'a, T: Transaction + 'a> ExecutorNode<'a, T> for CreateIndex {
                 })
             })
             .unzip();
+        let column_exprs = column_exprs
+            .into_iter()
+            .map(|expr| json_path_expr(expr, &json_path))
+            .collect::<Result<Vec<_>, _>>()?;
         let index_id_result = {
             let (transaction, table_codec) = arena.transaction_codec_mut();
             let (table, index_id) = transaction.add_index_meta(
@@ -116,6 +122,7 @@ impl<'a, T: Transaction + 'a> ExecutorNode<'a, T> for CreateIndex {
                 index_name,
                 column_ids,
                 ty,
+                json_path,
             )?;
             arena.push_ddl_apply(DDLApply::upsert_table(table, false));
             Ok(index_id)
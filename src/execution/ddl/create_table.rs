@@ -54,6 +54,9 @@ impl<'a, T: Transaction + 'a> ExecutorNode<'a, T> for CreateTable {
         let Some(CreateTableOperator {
             table_name,
             columns,
+            checks,
+            foreign_keys,
+            sequences,
             if_not_exists,
         }) = self.op.take()
         else {
@@ -69,9 +72,30 @@ impl<'a, T: Transaction + 'a> ExecutorNode<'a, T> for CreateTable {
             columns,
             if_not_exists,
         )?;
+        if table.is_some() {
+            for sequence in sequences.iter() {
+                transaction.create_sequence(table_codec, plan_arena, sequence, false)?;
+            }
+        }
+        let (table, metas) = match table {
+            Some(_) if !checks.is_empty() || !foreign_keys.is_empty() => {
+                let (table, metas) = transaction.add_table_constraints(
+                    table_codec,
+                    plan_arena,
+                    &table_name,
+                    checks,
+                    foreign_keys,
+                )?;
+                (Some(table), metas)
+            }
+            table => (table, Vec::new()),
+        };
         if let Some(table) = table {
             arena.push_ddl_apply(DDLApply::upsert_table(table, false));
         }
+        for meta in metas {
+            arena.push_ddl_apply(DDLApply::UpdateTableMeta { meta });
+        }
 
         TupleBuilder::build_result_into(arena.result_tuple_mut(), format!("{table_name}"));
         arena.resume();
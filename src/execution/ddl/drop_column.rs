@@ -74,7 +74,13 @@ impl<'a, T: Transaction + 'a> ExecutorNode<'a, T> for DropColumn {
                 .enumerate()
                 .find_map(|(index, column)| {
                     let column = plan_arena.column(*column);
-                    (column.name() == column_name).then(|| (index, column.desc().is_primary()))
+                    (column.name() == column_name).then(|| {
+                        (
+                            index,
+                            column.desc().is_primary(),
+                            column.desc().identity_sequence().map(str::to_string),
+                        )
+                    })
                 });
             (
                 table_catalog.columns().copied().collect_vec(),
@@ -82,7 +88,7 @@ impl<'a, T: Transaction + 'a> ExecutorNode<'a, T> for DropColumn {
                 column_info,
             )
         };
-        if let Some((column_index, is_primary)) = column_info {
+        if let Some((column_index, is_primary, sequence_name)) = column_info {
             if is_primary {
                 return Err(DatabaseError::invalid_column(
                     "drop of primary key column is not allowed.".to_owned(),
@@ -121,8 +127,14 @@ impl<'a, T: Transaction + 'a> ExecutorNode<'a, T> for DropColumn {
                 let (transaction, table_codec) = arena.transaction_codec_mut();
                 let table =
                     transaction.drop_column(table_codec, plan_arena, &table_name, &column_name)?;
+                if let Some(sequence_name) = &sequence_name {
+                    transaction.drop_sequence(table_codec, plan_arena, sequence_name, true)?;
+                }
                 arena.push_ddl_apply(DDLApply::upsert_table(table, true));
             }
+            if let Some(sequence_name) = sequence_name {
+                arena.context().sequence_cache().forget(&sequence_name);
+            }
 
             TupleBuilder::build_result_into(arena.result_tuple_mut(), "1".to_string());
             arena.resume();
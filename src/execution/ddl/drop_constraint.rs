@@ -0,0 +1,132 @@
+// Copyright 2024 KipData/KiteSQL
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::errors::DatabaseError;
+use crate::execution::{
+    DDLApply, ExecArena, ExecId, ExecNode, ExecutionContext, ExecutorNode, WriteExecutor,
+};
+use crate::planner::operator::alter_table::drop_constraint::DropConstraintOperator;
+use crate::storage::Transaction;
+use crate::types::tuple_builder::TupleBuilder;
+
+pub struct DropConstraint {
+    op: Option<DropConstraintOperator>,
+}
+
+impl From<DropConstraintOperator> for DropConstraint {
+    fn from(op: DropConstraintOperator) -> Self {
+        Self { op: Some(op) }
+    }
+}
+
+impl<'a, T: Transaction + 'a> WriteExecutor<'a, T> for DropConstraint {
+    type Input = Self;
+
+    fn into_executor(
+        input: Self::Input,
+        arena: &mut ExecArena<'a, T>,
+        _plan_arena: &mut crate::planner::PlanArena<'a>,
+        _: ExecutionContext<'_>,
+        _: &T,
+    ) -> ExecId {
+        let executor = input;
+        arena.push(ExecNode::DropConstraint(executor))
+    }
+}
+
+impl<'a, T: Transaction + 'a> ExecutorNode<'a, T> for DropConstraint {
+    fn next_tuple(
+        &mut self,
+        arena: &mut ExecArena<'a, T>,
+        plan_arena: &mut crate::planner::PlanArena<'a>,
+    ) -> Result<(), DatabaseError> {
+        let table_cache = arena.table_cache();
+        let Some(DropConstraintOperator {
+            table_name,
+            constraint_name,
+            if_exists,
+        }) = self.op.take()
+        else {
+            arena.finish();
+            return Ok(());
+        };
+
+        let table = arena
+            .transaction()
+            .table(table_cache, table_name.clone())?
+            .ok_or(DatabaseError::TableNotFound)?;
+        let check_position = table
+            .checks()
+            .iter()
+            .position(|check| check.name == constraint_name);
+        let foreign_key = table
+            .foreign_keys()
+            .iter()
+            .position(|foreign_key| foreign_key.name == constraint_name)
+            .map(|position| {
+                let referenced_table = table.foreign_keys()[position].referenced_table.clone();
+                let still_referenced =
+                    table
+                        .foreign_keys()
+                        .iter()
+                        .enumerate()
+                        .any(|(i, foreign_key)| {
+                            i != position && foreign_key.referenced_table == referenced_table
+                        });
+                (position, referenced_table, still_referenced)
+            });
+
+        let (transaction, table_codec) = arena.transaction_codec_mut();
+        let mut metas = Vec::with_capacity(2);
+        if let Some(position) = check_position {
+            metas.push(transaction.update_table_meta(
+                table_codec,
+                plan_arena,
+                &table_name,
+                |meta| {
+                    meta.checks.remove(position);
+                },
+            )?);
+        } else if let Some((position, referenced_table, still_referenced)) = foreign_key {
+            metas.push(transaction.update_table_meta(
+                table_codec,
+                plan_arena,
+                &table_name,
+                |meta| {
+                    meta.foreign_keys.remove(position);
+                },
+            )?);
+            if !still_referenced {
+                metas.push(transaction.update_table_meta(
+                    table_codec,
+                    plan_arena,
+                    &referenced_table,
+                    |meta| meta.referenced_by.retain(|name| name != &table_name),
+                )?);
+            }
+        } else if if_exists {
+            arena.finish();
+            return Ok(());
+        } else {
+            return Err(DatabaseError::ConstraintNotFound(constraint_name));
+        }
+        for meta in metas {
+            arena.push_ddl_apply(DDLApply::UpdateTableMeta { meta });
+        }
+
+        TupleBuilder::build_result_into(arena.result_tuple_mut(), "1".to_string());
+        arena.resume();
+        Ok(())
+    }
+}
@@ -0,0 +1,104 @@
+// Copyright 2024 KipData/KiteSQL
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::errors::DatabaseError;
+use crate::execution::{
+    ExecArena, ExecId, ExecNode, ExecutionContext, ExecutorNode, WriteExecutor,
+};
+use crate::planner::operator::drop_sequence::DropSequenceOperator;
+use crate::storage::Transaction;
+use crate::types::tuple_builder::TupleBuilder;
+
+pub struct DropSequence {
+    op: Option<DropSequenceOperator>,
+}
+
+impl From<DropSequenceOperator> for DropSequence {
+    fn from(op: DropSequenceOperator) -> Self {
+        DropSequence { op: Some(op) }
+    }
+}
+
+impl<'a, T: Transaction + 'a> WriteExecutor<'a, T> for DropSequence {
+    type Input = Self;
+
+    fn into_executor(
+        input: Self::Input,
+        arena: &mut ExecArena<'a, T>,
+        _plan_arena: &mut crate::planner::PlanArena<'a>,
+        _: ExecutionContext<'_>,
+        _: &T,
+    ) -> ExecId {
+        let executor = input;
+        arena.push(ExecNode::DropSequence(executor))
+    }
+}
+
+impl<'a, T: Transaction + 'a> ExecutorNode<'a, T> for DropSequence {
+    fn next_tuple(
+        &mut self,
+        arena: &mut ExecArena<'a, T>,
+        plan_arena: &mut crate::planner::PlanArena<'a>,
+    ) -> Result<(), DatabaseError> {
+        let Some(DropSequenceOperator {
+            sequence_name,
+            if_exists,
+        }) = self.op.take()
+        else {
+            arena.finish();
+            return Ok(());
+        };
+        let sequence_cache = arena.context().sequence_cache();
+        let (transaction, table_codec) = arena.transaction_codec_mut();
+        if let Some(table_name) = transaction
+            .sequence(table_codec, plan_arena, &sequence_name)?
+            .and_then(|sequence| sequence.owned_by)
+        {
+            return Err(DatabaseError::UnsupportedStmt(format!(
+                "sequence `{sequence_name}` generates an identity column of table `{table_name}` and is dropped with it"
+            )));
+        }
+        // Tips: a default still calling the sequence would fail every later insert
+        let mut table_names = Vec::new();
+        let mut tables = transaction.tables(table_codec)?;
+        while let Some(meta) = tables.try_next(plan_arena)? {
+            table_names.push(meta.table_name);
+        }
+        drop(tables);
+        for table_name in table_names {
+            let Some(table) = transaction.load_table(table_codec, plan_arena, table_name)? else {
+                continue;
+            };
+            for column in table.columns() {
+                let column = plan_arena.column(*column);
+                if let Some(default) = &column.desc().default {
+                    if default.references_sequence(&sequence_name)? {
+                        return Err(DatabaseError::UnsupportedStmt(format!(
+                            "sequence `{sequence_name}` is used by the default of column `{}` of table `{}`",
+                            column.name(),
+                            table.name()
+                        )));
+                    }
+                }
+            }
+        }
+        if transaction.drop_sequence(table_codec, plan_arena, &sequence_name, if_exists)? {
+            sequence_cache.forget(&sequence_name);
+        }
+
+        TupleBuilder::build_result_into(arena.result_tuple_mut(), sequence_name);
+        arena.resume();
+        Ok(())
+    }
+}
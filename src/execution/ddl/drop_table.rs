@@ -16,6 +16,7 @@ use crate::errors::DatabaseError;
 use crate::execution::{
     DDLApply, ExecArena, ExecId, ExecNode, ExecutionContext, ExecutorNode, WriteExecutor,
 };
+use crate::iter_ext::Itertools;
 use crate::planner::operator::drop_table::DropTableOperator;
 use crate::storage::Transaction;
 use crate::types::tuple_builder::TupleBuilder;
@@ -60,8 +61,52 @@ impl<'a, T: Transaction + 'a> ExecutorNode<'a, T> for DropTable {
             return Ok(());
         };
 
+        let (transaction, table_codec) = arena.transaction_codec_mut();
+        let mut referenced_tables = Vec::new();
+        if let Some(meta) = transaction.table_meta(table_codec, plan_arena, &table_name)? {
+            if let Some(referencing) = meta.referenced_by.iter().find(|name| **name != table_name) {
+                return Err(DatabaseError::TableReferenced {
+                    table: table_name.to_string(),
+                    referencing: referencing.to_string(),
+                });
+            }
+            for foreign_key in meta.foreign_keys {
+                if foreign_key.referenced_table != table_name
+                    && !referenced_tables.contains(&foreign_key.referenced_table)
+                {
+                    referenced_tables.push(foreign_key.referenced_table);
+                }
+            }
+        }
+        // Unregister the table from the tables it references before dropping it.
+        for referenced_table in referenced_tables {
+            let (transaction, table_codec) = arena.transaction_codec_mut();
+            let meta = transaction.update_table_meta(
+                table_codec,
+                plan_arena,
+                &referenced_table,
+                |meta| meta.referenced_by.retain(|name| name != &table_name),
+            )?;
+            arena.push_ddl_apply(DDLApply::UpdateTableMeta { meta });
+        }
+        let sequence_names = arena
+            .transaction()
+            .table(arena.table_cache(), table_name.clone())?
+            .map(|table| {
+                table
+                    .columns()
+                    .filter_map(|column| plan_arena.column(*column).desc().identity_sequence())
+                    .map(str::to_string)
+                    .collect_vec()
+            })
+            .unwrap_or_default();
+        let sequence_cache = arena.context().sequence_cache();
         let (transaction, table_codec) = arena.transaction_codec_mut();
         if transaction.drop_table(table_codec, plan_arena, table_name.clone(), if_exists)? {
+            for sequence_name in sequence_names {
+                transaction.drop_sequence(table_codec, plan_arena, &sequence_name, true)?;
+                sequence_cache.forget(&sequence_name);
+            }
             arena.push_ddl_apply(DDLApply::DropTable {
                 name: table_name.clone(),
             });
@@ -13,12 +13,16 @@
 // limitations under the License.
 
 pub mod add_column;
+pub(crate) mod add_constraint;
 pub(crate) mod change_column;
 pub(crate) mod create_index;
+pub(crate) mod create_sequence;
 pub(crate) mod create_table;
 pub(crate) mod create_view;
 pub(crate) mod drop_column;
+pub(crate) mod drop_constraint;
 pub(crate) mod drop_index;
+pub(crate) mod drop_sequence;
 pub(crate) mod drop_table;
 pub(crate) mod drop_view;
 pub(crate) mod truncate;
@@ -55,6 +55,15 @@ impl<'a, T: Transaction + 'a> ExecutorNode<'a, T> for Truncate {
             arena.finish();
             return Ok(());
         };
+        let (transaction, table_codec) = arena.transaction_codec_mut();
+        if let Some(meta) = transaction.table_meta(table_codec, plan_arena, &table_name)? {
+            if let Some(referencing) = meta.referenced_by.iter().find(|name| **name != table_name) {
+                return Err(DatabaseError::TableReferenced {
+                    table: table_name.to_string(),
+                    referencing: referencing.to_string(),
+                });
+            }
+        }
         let mut state = arena.local_state(plan_arena);
         let (transaction, table_codec) = state.transaction_codec_mut();
         transaction.drop_data(table_codec, &table_name)?;
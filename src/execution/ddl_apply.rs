@@ -13,7 +13,7 @@
 // limitations under the License.
 
 use crate::catalog::view::View;
-use crate::catalog::{TableCatalog, TableName};
+use crate::catalog::{TableCatalog, TableMeta, TableName};
 use crate::optimizer::core::statistics_meta::StatisticsMeta;
 use crate::types::index::IndexId;
 
@@ -22,6 +22,10 @@ pub(crate) enum DDLApply {
         table: TableCatalog,
         clear_statistics: bool,
     },
+    /// Replaces the constraints of a cached table whose columns and indexes are unchanged.
+    UpdateTableMeta {
+        meta: TableMeta,
+    },
     DropTable {
         name: TableName,
     },
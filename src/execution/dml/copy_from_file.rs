@@ -13,13 +13,19 @@
 // limitations under the License.
 
 use crate::binder::copy::FileFormat;
+use crate::catalog::ColumnIdentity;
 use crate::errors::DatabaseError;
+use crate::execution::dml::check_constraints;
+use crate::execution::dml::foreign_key::check_references;
 use crate::execution::{
-    ExecArena, ExecId, ExecNode, ExecutionContext, ExecutorNode, WriteExecutor,
+    resolve_sequence_calls, with_projection_tmp_value, ExecArena, ExecId, ExecNode,
+    ExecutionContext, ExecutorNode, WriteExecutor,
 };
 use crate::iter_ext::Itertools;
 use crate::planner::operator::copy_from_file::CopyFromFileOperator;
 use crate::storage::Transaction;
+use crate::types::index::Index;
+use crate::types::tuple::Tuple;
 use crate::types::tuple_builder::TupleBuilder;
 use std::fs::File;
 use std::io::BufReader;
@@ -74,6 +80,10 @@ impl<'a, T: Transaction + 'a> ExecutorNode<'a, T> for CopyFromFile {
             .table(table_cache, op.table.clone())?
             .ok_or(DatabaseError::TableNotFound)?;
         let table_name = table.name().to_string();
+        let (index_metas, has_references) = {
+            let snapshot = table.dml_snapshot(plan_arena)?;
+            (snapshot.index_metas, snapshot.has_references)
+        };
 
         let file = File::open(op.source.path)?;
         let mut buf_reader = BufReader::new(file);
@@ -91,6 +101,17 @@ impl<'a, T: Transaction + 'a> ExecutorNode<'a, T> for CopyFromFile {
                 .from_reader(&mut buf_reader),
         };
 
+        // empty identity fields are generated like omitted values of an `INSERT`
+        let identity_columns = op
+            .schema_ref
+            .iter()
+            .enumerate()
+            .filter_map(|(i, column)| {
+                let desc = plan_arena.column(*column).desc();
+                let identity = desc.identity()?;
+                Some((i, identity, desc.default.clone()?))
+            })
+            .collect_vec();
         let column_count = op.schema_ref.len();
         let tuple_builder = TupleBuilder::new(column_types, Some(table.primary_key_indices()));
         let mut size = 0_usize;
@@ -104,10 +125,42 @@ impl<'a, T: Transaction + 'a> ExecutorNode<'a, T> for CopyFromFile {
                 return Err(DatabaseError::MisMatch("columns", "values"));
             }
 
-            let chunk = tuple_builder.build_with_row(record.iter())?;
+            let mut chunk = tuple_builder.build_with_row(record.iter())?;
+            for (i, identity, default) in identity_columns.iter() {
+                if !chunk.values[*i].is_null() {
+                    if *identity == ColumnIdentity::Always {
+                        let column = plan_arena.column(op.schema_ref[*i]);
+                        return Err(DatabaseError::GeneratedAlways(column.name().to_string()));
+                    }
+                    continue;
+                }
+                let mut default = default.clone();
+                resolve_sequence_calls(arena, plan_arena, &mut default)?;
+                chunk.values[*i] = default
+                    .eval::<&Tuple>(None)?
+                    .cast(plan_arena.column(op.schema_ref[*i]).datatype())?;
+                chunk.pk = Some(Tuple::primary_projection(
+                    table.primary_key_indices(),
+                    &chunk.values,
+                ));
+            }
+            check_constraints(&op.checks, &chunk)?;
+            let tuple_id = chunk.pk.as_ref().ok_or(DatabaseError::PrimaryKeyNotFound)?;
+            for (index_meta, exprs) in index_metas.iter() {
+                let index_meta = plan_arena.index(*index_meta);
+                with_projection_tmp_value(arena, Some(&chunk), exprs, |arena, value| {
+                    let mut state = arena.local_state(plan_arena);
+                    let (transaction, table_codec) = state.transaction_codec_mut();
+                    let index = Index::new(index_meta.id, &value, index_meta.ty);
+                    transaction.add_index(table_codec, &table_name, index, tuple_id)
+                })?;
+            }
             let mut state = arena.local_state(plan_arena);
             let (transaction, table_codec) = state.transaction_codec_mut();
             transaction.append_tuple(table_codec, &table_name, &chunk, &serializers, false)?;
+            if has_references {
+                check_references(arena, plan_arena, &op.table, None, &chunk)?;
+            }
             size += 1;
         }
 
@@ -179,6 +232,7 @@ mod tests {
                 },
             },
             schema_ref: columns,
+            checks: vec![],
         };
 
         let transaction = db.storage.transaction()?;
@@ -14,6 +14,8 @@
 
 use crate::catalog::TableName;
 use crate::errors::DatabaseError;
+use crate::execution::dml::foreign_key::apply_referential_actions;
+use crate::execution::dml::Returning;
 use crate::execution::{
     build_read, with_projection_tmp_value, ExecArena, ExecId, ExecNode, ExecutionContext,
     ExecutorNode, WriteExecutor,
@@ -22,20 +24,30 @@ use crate::planner::operator::delete::DeleteOperator;
 use crate::planner::LogicalPlan;
 use crate::storage::Transaction;
 use crate::types::index::Index;
-use crate::types::tuple_builder::TupleBuilder;
 
 pub struct Delete {
     table_name: TableName,
     input_plan: LogicalPlan,
     input: Option<ExecId>,
+    returning: Returning,
 }
 
 impl From<(DeleteOperator, LogicalPlan)> for Delete {
-    fn from((DeleteOperator { table_name, .. }, input): (DeleteOperator, LogicalPlan)) -> Self {
+    fn from(
+        (
+            DeleteOperator {
+                table_name,
+                returning,
+                ..
+            },
+            input,
+        ): (DeleteOperator, LogicalPlan),
+    ) -> Self {
         Delete {
             table_name,
             input_plan: input,
             input: None,
+            returning: Returning::new(returning),
         }
     }
 }
@@ -68,17 +80,20 @@ impl<'a, T: Transaction + 'a> ExecutorNode<'a, T> for Delete {
         arena: &mut ExecArena<'a, T>,
         plan_arena: &mut crate::planner::PlanArena<'a>,
     ) -> Result<(), DatabaseError> {
+        if self.returning.next_tuple(arena) {
+            return Ok(());
+        }
         let Some(input) = self.input.take() else {
             arena.finish();
             return Ok(());
         };
 
-        let index_templates = {
+        let (index_templates, is_referenced) = {
             let table = arena
                 .transaction()
                 .table(arena.table_cache(), self.table_name.clone())?
                 .ok_or(DatabaseError::TableNotFound)?;
-            table
+            let index_templates = table
                 .indexes()
                 .map(|index_meta| {
                     let index_meta = plan_arena.index(*index_meta);
@@ -88,7 +103,8 @@ impl<'a, T: Transaction + 'a> ExecutorNode<'a, T> for Delete {
                         index_meta.column_exprs(table, plan_arena)?,
                     ))
                 })
-                .collect::<Result<Vec<_>, DatabaseError>>()?
+                .collect::<Result<Vec<_>, DatabaseError>>()?;
+            (index_templates, !table.referenced_by().is_empty())
         };
         let mut deleted_count = 0;
 
@@ -113,11 +129,15 @@ impl<'a, T: Transaction + 'a> ExecutorNode<'a, T> for Delete {
             let mut state = arena.local_state(plan_arena);
             let (transaction, table_codec) = state.transaction_codec_mut();
             transaction.remove_tuple(table_codec, &self.table_name, &tuple_id)?;
+            if is_referenced {
+                let tuple = arena.result_tuple().clone();
+                apply_referential_actions(arena, plan_arena, &self.table_name, &tuple, None)?;
+            }
+            self.returning.push(arena.result_tuple())?;
             deleted_count += 1;
         }
 
-        TupleBuilder::build_result_into(arena.result_tuple_mut(), deleted_count.to_string());
-        arena.resume();
+        self.returning.complete(arena, deleted_count);
         Ok(())
     }
 }
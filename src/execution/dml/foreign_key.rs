@@ -0,0 +1,361 @@
+// Copyright 2024 KipData/KiteSQL
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::catalog::{ReferentialAction, TableCatalog, TableName};
+use crate::errors::DatabaseError;
+use crate::execution::{with_projection_tmp_value, ExecArena};
+use crate::expression::range_detacher::Range;
+use crate::iter_ext::Itertools;
+use crate::planner::PlanArena;
+use crate::storage::{Iter, Transaction};
+use crate::types::index::{Index, IndexType};
+use crate::types::tuple::Tuple;
+use crate::types::value::DataValue;
+use std::mem;
+
+fn cached_table<'a, T: Transaction + 'a>(
+    arena: &ExecArena<'a, T>,
+    table_name: &TableName,
+) -> Result<&'a TableCatalog, DatabaseError> {
+    arena
+        .transaction()
+        .table(arena.context().table_cache(), table_name.clone())?
+        .ok_or(DatabaseError::TableNotFound)
+}
+
+fn column_positions(table: &TableCatalog, columns: &[String]) -> Result<Vec<usize>, DatabaseError> {
+    columns
+        .iter()
+        .map(|name| {
+            table
+                .get_column_position(name)
+                .ok_or_else(|| DatabaseError::column_not_found(name.clone()))
+        })
+        .collect()
+}
+
+/// Projects the key at `positions` of `tuple`, cast to the types of the columns at
+/// `target_positions` of `target`; a key with a null part matches nothing.
+fn project_key(
+    tuple: &Tuple,
+    positions: &[usize],
+    target: &TableCatalog,
+    target_positions: &[usize],
+    plan_arena: &PlanArena,
+) -> Result<Option<DataValue>, DatabaseError> {
+    let mut values = Vec::with_capacity(positions.len());
+    for (position, target_position) in positions.iter().zip(target_positions) {
+        let value = &tuple.values[*position];
+        if value.is_null() {
+            return Ok(None);
+        }
+        let column = target
+            .column_ref(*target_position)
+            .ok_or(DatabaseError::PrimaryKeyNotFound)?;
+        values.push(value.clone().cast(plan_arena.column(column).datatype())?);
+    }
+    Ok(Some(if values.len() == 1 {
+        values.pop().expect("key has one value")
+    } else {
+        DataValue::Tuple(values, false)
+    }))
+}
+
+/// Rejects a written row whose foreign keys point at no row of the referenced table.
+/// Keys left unchanged since `old`, or with a null part, are not looked up.
+pub(crate) fn check_references<'a, T: Transaction + 'a>(
+    arena: &mut ExecArena<'a, T>,
+    plan_arena: &PlanArena<'a>,
+    table_name: &TableName,
+    old: Option<&Tuple>,
+    tuple: &Tuple,
+) -> Result<(), DatabaseError> {
+    let table = cached_table(arena, table_name)?;
+    for foreign_key in table.foreign_keys() {
+        let positions = column_positions(table, &foreign_key.columns)?;
+        if old.is_some_and(|old| {
+            positions
+                .iter()
+                .all(|position| old.values[*position] == tuple.values[*position])
+        }) {
+            continue;
+        }
+        let referenced = cached_table(arena, &foreign_key.referenced_table)?;
+        let referenced_positions = column_positions(referenced, &foreign_key.referenced_columns)?;
+        let Some(key) = project_key(
+            tuple,
+            &positions,
+            referenced,
+            &referenced_positions,
+            plan_arena,
+        )?
+        else {
+            continue;
+        };
+
+        let (transaction, table_codec) = arena.transaction_codec_mut();
+        let exists = if referenced
+            .primary_keys()
+            .iter()
+            .map(|(position, _)| *position)
+            .eq(referenced_positions.iter().copied())
+        {
+            transaction.contains_tuple(table_codec, referenced.name(), &key)?
+        } else {
+            let column = plan_arena.column(
+                referenced
+                    .column_ref(referenced_positions[0])
+                    .ok_or(DatabaseError::InvalidIndex)?,
+            );
+            let index = column
+                .id()
+                .and_then(|column_id| referenced.get_unique_index(&column_id, plan_arena))
+                .ok_or(DatabaseError::InvalidIndex)?;
+            let index = Index::new(plan_arena.index(index).id, &key, IndexType::Unique);
+            transaction
+                .get_unique_index(table_codec, referenced.name(), &index)?
+                .is_some()
+        };
+        if !exists {
+            return Err(DatabaseError::ForeignKeyViolation {
+                constraint: foreign_key.name.clone(),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Applies the `ON DELETE` actions of the foreign keys referencing `old`, a row just
+/// removed from the table, or with `new` the `ON UPDATE` actions of its updated key.
+///
+/// Rows changed by `CASCADE`, `SET NULL` and `SET DEFAULT` keep their indexes and
+/// their own referencing rows in sync; their `CHECK` constraints are not evaluated.
+pub(crate) fn apply_referential_actions<'a, T: Transaction + 'a>(
+    arena: &mut ExecArena<'a, T>,
+    plan_arena: &PlanArena<'a>,
+    table_name: &TableName,
+    old: &Tuple,
+    new: Option<&Tuple>,
+) -> Result<(), DatabaseError> {
+    let table = cached_table(arena, table_name)?;
+    for referencing in table.referenced_by() {
+        let child = cached_table(arena, referencing)?;
+        for foreign_key in child
+            .foreign_keys()
+            .iter()
+            .filter(|foreign_key| &foreign_key.referenced_table == table_name)
+        {
+            let referenced_positions = column_positions(table, &foreign_key.referenced_columns)?;
+            if new.is_some_and(|new| {
+                referenced_positions
+                    .iter()
+                    .all(|position| old.values[*position] == new.values[*position])
+            }) {
+                continue;
+            }
+            let positions = column_positions(child, &foreign_key.columns)?;
+            let Some(key) = project_key(old, &referenced_positions, child, &positions, plan_arena)?
+            else {
+                continue;
+            };
+            let rows = referencing_rows(arena, plan_arena, child, &positions, key)?;
+            if rows.is_empty() {
+                continue;
+            }
+            let action = match new {
+                Some(_) => foreign_key.on_update,
+                None => foreign_key.on_delete,
+            };
+
+            match (action, new) {
+                (ReferentialAction::Restrict, _) => {
+                    return Err(DatabaseError::ForeignKeyViolation {
+                        constraint: foreign_key.name.clone(),
+                    })
+                }
+                (ReferentialAction::Cascade, None) => {
+                    for row in rows {
+                        delete_row(arena, plan_arena, child, row)?;
+                    }
+                }
+                (action, new) => {
+                    for row in rows {
+                        let mut updated = row.clone();
+                        for (position, referenced_position) in
+                            positions.iter().zip(referenced_positions.iter())
+                        {
+                            let column = plan_arena.column(
+                                child
+                                    .column_ref(*position)
+                                    .ok_or(DatabaseError::PrimaryKeyNotFound)?,
+                            );
+                            let value = match (action, new) {
+                                (ReferentialAction::Cascade, Some(new)) => {
+                                    new.values[*referenced_position].clone()
+                                }
+                                (ReferentialAction::SetDefault, _) => {
+                                    column.default_value()?.unwrap_or(DataValue::Null)
+                                }
+                                _ => DataValue::Null,
+                            };
+                            if value.is_null() && !column.nullable() {
+                                return Err(DatabaseError::not_null_column(
+                                    column.name().to_string(),
+                                ));
+                            }
+                            updated.values[*position] = value.cast(column.datatype())?;
+                        }
+                        update_row(
+                            arena,
+                            plan_arena,
+                            child,
+                            row,
+                            updated,
+                            matches!(action, ReferentialAction::SetDefault),
+                        )?;
+                    }
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Reads the rows of `table` whose columns at `positions` equal `key` through the index
+/// created over them with the foreign key.
+fn referencing_rows<'a, T: Transaction + 'a>(
+    arena: &mut ExecArena<'a, T>,
+    plan_arena: &PlanArena<'a>,
+    table: &'a TableCatalog,
+    positions: &[usize],
+    key: DataValue,
+) -> Result<Vec<Tuple>, DatabaseError> {
+    let column_ids = positions
+        .iter()
+        .map(|position| {
+            table
+                .column_ref(*position)
+                .and_then(|column| plan_arena.column(column).id())
+        })
+        .collect::<Option<Vec<_>>>()
+        .ok_or(DatabaseError::InvalidIndex)?;
+    let index = table
+        .indexes()
+        .copied()
+        .find(|index| {
+            let index = plan_arena.index(*index);
+            index.column_ids == column_ids && index.json_path.is_empty()
+        })
+        .ok_or(DatabaseError::InvalidIndex)?;
+
+    let state = arena.local_state(plan_arena);
+    let mut iter = state.transaction().read_by_index(
+        state.context.table_cache(),
+        plan_arena,
+        table.name().clone(),
+        (None, None),
+        table.columns().copied().collect_vec(),
+        index,
+        Range::Eq(key),
+        true,
+        None,
+        None,
+    )?;
+    let mut rows = Vec::new();
+    let mut tuple = Tuple::default();
+    while iter.next_tuple_into(state.table_codec, &mut tuple)? {
+        rows.push(mem::take(&mut tuple));
+    }
+    Ok(rows)
+}
+
+fn delete_row<'a, T: Transaction + 'a>(
+    arena: &mut ExecArena<'a, T>,
+    plan_arena: &PlanArena<'a>,
+    table: &'a TableCatalog,
+    tuple: Tuple,
+) -> Result<(), DatabaseError> {
+    let tuple_id = tuple.pk.clone().ok_or(DatabaseError::PrimaryKeyNotFound)?;
+    for index_meta in table.indexes() {
+        let index_meta = plan_arena.index(*index_meta);
+        let exprs = index_meta.column_exprs(table, plan_arena)?;
+        with_projection_tmp_value(arena, Some(&tuple), &exprs, |arena, value| {
+            let (transaction, table_codec) = arena.transaction_codec_mut();
+            let index = Index::new(index_meta.id, &value, index_meta.ty);
+            transaction.del_index(table_codec, table.name(), &index, &tuple_id)
+        })?;
+    }
+    let (transaction, table_codec) = arena.transaction_codec_mut();
+    transaction.remove_tuple(table_codec, table.name(), &tuple_id)?;
+
+    apply_referential_actions(arena, plan_arena, table.name(), &tuple, None)
+}
+
+fn update_row<'a, T: Transaction + 'a>(
+    arena: &mut ExecArena<'a, T>,
+    plan_arena: &PlanArena<'a>,
+    table: &'a TableCatalog,
+    old: Tuple,
+    mut new: Tuple,
+    check_new: bool,
+) -> Result<(), DatabaseError> {
+    let old_pk = old.pk.clone().ok_or(DatabaseError::PrimaryKeyNotFound)?;
+    let new_pk = Tuple::primary_projection(table.primary_key_indices(), &new.values);
+    new.pk = Some(new_pk.clone());
+
+    let primary_key_changed = new_pk != old_pk;
+    if primary_key_changed {
+        let (transaction, table_codec) = arena.transaction_codec_mut();
+        transaction.remove_tuple(table_codec, table.name(), &old_pk)?;
+    }
+    for index_meta in table.indexes() {
+        let index_meta = plan_arena.index(*index_meta);
+        if matches!(index_meta.ty, IndexType::PrimaryKey { .. }) {
+            continue;
+        }
+        let exprs = index_meta.column_exprs(table, plan_arena)?;
+        let mut old_value = DataValue::Null;
+        with_projection_tmp_value(arena, Some(&old), &exprs, |_, value| {
+            old_value = value;
+            Ok(())
+        })?;
+        with_projection_tmp_value(arena, Some(&new), &exprs, |arena, value| {
+            if !primary_key_changed && old_value == value {
+                return Ok(());
+            }
+            let (transaction, table_codec) = arena.transaction_codec_mut();
+            let old_index = Index::new(index_meta.id, &old_value, index_meta.ty);
+            transaction.del_index(table_codec, table.name(), &old_index, &old_pk)?;
+            let new_index = Index::new(index_meta.id, &value, index_meta.ty);
+            transaction.add_index(table_codec, table.name(), new_index, &new_pk)
+        })?;
+    }
+    let serializers = table
+        .columns()
+        .map(|column| plan_arena.column(*column).datatype().serializable())
+        .collect_vec();
+    let (transaction, table_codec) = arena.transaction_codec_mut();
+    transaction.append_tuple(
+        table_codec,
+        table.name(),
+        &new,
+        &serializers,
+        !primary_key_changed,
+    )?;
+
+    if check_new {
+        check_references(arena, plan_arena, table.name(), None, &new)?;
+    }
+    apply_referential_actions(arena, plan_arena, table.name(), &old, Some(&new))
+}
@@ -12,22 +12,26 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::catalog::TableName;
+use crate::catalog::{ColumnIdentity, TableName};
 use crate::errors::DatabaseError;
+use crate::execution::dml::foreign_key::{apply_referential_actions, check_references};
+use crate::execution::dml::{check_constraints, Returning};
 use crate::execution::{
-    build_read, with_projection_tmp_value, ExecArena, ExecId, ExecNode, ExecutionContext,
-    ExecutorNode, WriteExecutor,
+    build_read, resolve_sequence_calls, with_projection_tmp_value, ExecArena, ExecId, ExecNode,
+    ExecutionContext, ExecutorNode, WriteExecutor,
 };
+use crate::expression::ScalarExpression;
 use crate::iter_ext::Itertools;
-use crate::planner::operator::insert::InsertOperator;
-use crate::planner::LogicalPlan;
+use crate::planner::operator::insert::{ConflictAction, InsertOperator, OnConflict};
+use crate::planner::{LogicalPlan, PlanArena};
 use crate::storage::Transaction;
-use crate::types::index::Index;
+use crate::types::index::{Index, IndexId, IndexMetaRef, IndexType};
+use crate::types::serialize::TupleValueSerializableImpl;
 use crate::types::tuple::{Schema, Tuple};
-use crate::types::tuple_builder::TupleBuilder;
 use crate::types::value::DataValue;
 use crate::types::ColumnId;
 use std::collections::HashMap;
+use std::mem;
 
 pub struct Insert {
     table_name: TableName,
@@ -36,6 +40,9 @@ pub struct Insert {
     input: Option<ExecId>,
     is_overwrite: bool,
     is_mapping_by_name: bool,
+    on_conflict: Option<OnConflict>,
+    returning: Returning,
+    checks: Vec<(String, ScalarExpression)>,
 }
 
 impl From<(InsertOperator, LogicalPlan)> for Insert {
@@ -45,6 +52,9 @@ impl From<(InsertOperator, LogicalPlan)> for Insert {
                 table_name,
                 is_overwrite,
                 is_mapping_by_name,
+                on_conflict,
+                returning,
+                checks,
             },
             input,
         ): (InsertOperator, LogicalPlan),
@@ -56,6 +66,9 @@ impl From<(InsertOperator, LogicalPlan)> for Insert {
             input: None,
             is_overwrite,
             is_mapping_by_name,
+            on_conflict,
+            returning: Returning::new(returning),
+            checks,
         }
     }
 }
@@ -100,6 +113,135 @@ impl Insert {
             MappingKey::Id(column.id())
         }
     }
+
+    #[allow(clippy::too_many_arguments)]
+    fn find_conflict<'a, T: Transaction + 'a>(
+        &self,
+        arena: &mut ExecArena<'a, T>,
+        plan_arena: &PlanArena<'a>,
+        target: Option<IndexId>,
+        index_metas: &[(IndexMetaRef, Vec<ScalarExpression>)],
+        tuple: &Tuple,
+        deserializers: &[TupleValueSerializableImpl],
+        total_len: usize,
+    ) -> Result<Option<Tuple>, DatabaseError> {
+        for (index_meta, exprs) in index_metas {
+            let index_meta = plan_arena.index(*index_meta);
+            if target.is_some_and(|target| target != index_meta.id) {
+                continue;
+            }
+            let tuple_id = match index_meta.ty {
+                IndexType::PrimaryKey { .. } => tuple.pk.clone(),
+                IndexType::Unique => {
+                    let mut tuple_id = None;
+                    with_projection_tmp_value(arena, Some(tuple), exprs, |arena, value| {
+                        let mut state = arena.local_state(plan_arena);
+                        let (transaction, table_codec) = state.transaction_codec_mut();
+                        let index = Index::new(index_meta.id, &value, index_meta.ty);
+                        tuple_id =
+                            transaction.get_unique_index(table_codec, &self.table_name, &index)?;
+                        Ok(())
+                    })?;
+                    tuple_id
+                }
+                IndexType::Normal | IndexType::Composite => continue,
+            };
+            let Some(tuple_id) = tuple_id else {
+                continue;
+            };
+            let mut state = arena.local_state(plan_arena);
+            let (transaction, table_codec) = state.transaction_codec_mut();
+            if let Some(existing) = transaction.get_tuple(
+                table_codec,
+                &self.table_name,
+                &tuple_id,
+                deserializers,
+                total_len,
+            )? {
+                return Ok(Some(existing));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Applies `DO UPDATE` to the conflicting row, returning the row if it was written.
+    #[allow(clippy::too_many_arguments)]
+    fn update_conflict<'a, T: Transaction + 'a>(
+        &self,
+        arena: &mut ExecArena<'a, T>,
+        plan_arena: &PlanArena<'a>,
+        index_metas: &[(IndexMetaRef, Vec<ScalarExpression>)],
+        primary_key_indices: &[usize],
+        serializers: &[TupleValueSerializableImpl],
+        assignments: &[(usize, &ScalarExpression)],
+        predicate: Option<&ScalarExpression>,
+        existing: Tuple,
+        excluded: &Tuple,
+    ) -> Result<Option<Tuple>, DatabaseError> {
+        let columns_len = existing.values.len();
+        let old_pk = existing.pk.ok_or(DatabaseError::PrimaryKeyNotFound)?;
+        let mut values = existing.values;
+        values.extend_from_slice(&excluded.values);
+        let combined = Tuple::new(None, values);
+
+        if let Some(predicate) = predicate {
+            if !matches!(predicate.eval(Some(&combined))?, DataValue::Boolean(true)) {
+                return Ok(None);
+            }
+        }
+        let mut tuple = Tuple::new(None, combined.values[..columns_len].to_vec());
+        for (i, expr) in assignments {
+            tuple.values[*i] = expr.eval(Some(&combined))?;
+        }
+        check_constraints(&self.checks, &tuple)?;
+        let old = Tuple::new(
+            Some(old_pk.clone()),
+            combined.values[..columns_len].to_vec(),
+        );
+        let new_pk = Tuple::primary_projection(primary_key_indices, &tuple.values);
+        tuple.pk = Some(new_pk.clone());
+
+        let primary_key_changed = new_pk != old_pk;
+        if primary_key_changed {
+            let mut state = arena.local_state(plan_arena);
+            let (transaction, table_codec) = state.transaction_codec_mut();
+            transaction.remove_tuple(table_codec, &self.table_name, &old_pk)?;
+        }
+        for (index_meta, exprs) in index_metas {
+            let index_meta = plan_arena.index(*index_meta);
+            if matches!(index_meta.ty, IndexType::PrimaryKey { .. }) {
+                continue;
+            }
+            let mut old_value = DataValue::Null;
+            with_projection_tmp_value(arena, Some(&old), exprs, |_, value| {
+                old_value = value;
+                Ok(())
+            })?;
+            with_projection_tmp_value(arena, Some(&tuple), exprs, |arena, value| {
+                if !primary_key_changed && old_value == value {
+                    return Ok(());
+                }
+                let mut state = arena.local_state(plan_arena);
+                let (transaction, table_codec) = state.transaction_codec_mut();
+                let old_index = Index::new(index_meta.id, &old_value, index_meta.ty);
+                transaction.del_index(table_codec, &self.table_name, &old_index, &old_pk)?;
+                let new_index = Index::new(index_meta.id, &value, index_meta.ty);
+                transaction.add_index(table_codec, &self.table_name, new_index, &new_pk)
+            })?;
+        }
+        let mut state = arena.local_state(plan_arena);
+        let (transaction, table_codec) = state.transaction_codec_mut();
+        transaction.append_tuple(
+            table_codec,
+            &self.table_name,
+            &tuple,
+            serializers,
+            !primary_key_changed,
+        )?;
+        check_references(arena, plan_arena, &self.table_name, Some(&old), &tuple)?;
+        apply_referential_actions(arena, plan_arena, &self.table_name, &old, Some(&tuple))?;
+        Ok(Some(tuple))
+    }
 }
 
 impl<'a, T: Transaction + 'a> ExecutorNode<'a, T> for Insert {
@@ -108,6 +250,9 @@ impl<'a, T: Transaction + 'a> ExecutorNode<'a, T> for Insert {
         arena: &mut ExecArena<'a, T>,
         plan_arena: &mut crate::planner::PlanArena<'a>,
     ) -> Result<(), DatabaseError> {
+        if self.returning.next_tuple(arena) {
+            return Ok(());
+        }
         let Some(input) = self.input.take() else {
             arena.finish();
             return Ok(());
@@ -131,6 +276,39 @@ impl<'a, T: Transaction + 'a> ExecutorNode<'a, T> for Insert {
                 .iter()
                 .map(|column| plan_arena.column(*column).datatype().serializable())
                 .collect_vec();
+            let mut assignments = Vec::new();
+            if let Some(OnConflict {
+                action: ConflictAction::DoUpdate { value_exprs, .. },
+                ..
+            }) = &self.on_conflict
+            {
+                for (column, expr) in value_exprs {
+                    let column = plan_arena.column(*column);
+                    let position = table_snapshot
+                        .columns
+                        .iter()
+                        .position(|table_column| {
+                            plan_arena.column(*table_column).id() == column.id()
+                        })
+                        .ok_or_else(|| {
+                            DatabaseError::column_not_found(column.name().to_string())
+                        })?;
+                    assignments.push((position, expr));
+                }
+            }
+            // defaults calling sequences produce a new value for every row
+            let sequence_defaults = table_snapshot
+                .columns
+                .iter()
+                .map(|column| {
+                    let default = plan_arena.column(*column).desc().default.as_ref();
+                    Ok(match default {
+                        Some(expr) if expr.has_sequence_call()? => Some(expr.clone()),
+                        _ => None,
+                    })
+                })
+                .collect::<Result<Vec<_>, DatabaseError>>()?;
+            let mut generated = Vec::new();
             let mut tuple = Tuple::new(None, Vec::with_capacity(table_snapshot.columns_len));
             let mut inserted_count = 0;
 
@@ -142,29 +320,78 @@ impl<'a, T: Transaction + 'a> ExecutorNode<'a, T> for Insert {
                 }
 
                 tuple.values.clear();
-                for column in table_snapshot.columns.iter() {
+                for (i, column) in table_snapshot.columns.iter().enumerate() {
                     let column = plan_arena.column(*column);
-                    let mut value = {
-                        let mut value =
-                            tuple_map.remove(&Self::column_key(column, self.is_mapping_by_name));
-
-                        if value.is_none() {
-                            value = column.default_value()?;
+                    let value =
+                        tuple_map.remove(&Self::column_key(column, self.is_mapping_by_name));
+                    let value = match (column.desc().identity(), value) {
+                        (Some(ColumnIdentity::Always), Some(value)) if !value.is_null() => {
+                            return Err(DatabaseError::GeneratedAlways(column.name().to_string()))
+                        }
+                        (Some(_), value) if value.as_ref().is_none_or(DataValue::is_null) => {
+                            generated.push(i);
+                            DataValue::Null
                         }
-                        value.unwrap_or(DataValue::Null)
+                        (_, Some(value)) => value,
+                        (_, None) if sequence_defaults[i].is_some() => {
+                            generated.push(i);
+                            DataValue::Null
+                        }
+                        (_, None) => column.default_value()?.unwrap_or(DataValue::Null),
+                    };
+                    tuple.values.push(value)
+                }
+                drop(tuple_map);
+                for i in generated.drain(..) {
+                    let Some(mut expr) = sequence_defaults[i].clone() else {
+                        continue;
                     };
-                    value = value.cast(column.datatype())?;
+                    resolve_sequence_calls(arena, plan_arena, &mut expr)?;
+                    tuple.values[i] = expr.eval::<&Tuple>(None)?;
+                }
+                for (value, column) in tuple.values.iter_mut().zip(table_snapshot.columns.iter()) {
+                    let column = plan_arena.column(*column);
+                    *value = mem::replace(value, DataValue::Null).cast(column.datatype())?;
                     value.check_len(column.datatype())?;
                     if value.is_null() && !column.nullable() {
                         return Err(DatabaseError::not_null_column(column.name().to_string()));
                     }
-                    tuple.values.push(value)
                 }
                 tuple.pk = Some(Tuple::primary_projection(
                     table_snapshot.primary_key_indices,
                     &tuple.values,
                 ));
 
+                if let Some(on_conflict) = &self.on_conflict {
+                    if let Some(existing) = self.find_conflict(
+                        arena,
+                        plan_arena,
+                        on_conflict.target,
+                        &table_snapshot.index_metas,
+                        &tuple,
+                        &serializers,
+                        table_snapshot.columns_len,
+                    )? {
+                        if let ConflictAction::DoUpdate { predicate, .. } = &on_conflict.action {
+                            if let Some(tuple) = self.update_conflict(
+                                arena,
+                                plan_arena,
+                                &table_snapshot.index_metas,
+                                table_snapshot.primary_key_indices,
+                                &serializers,
+                                &assignments,
+                                predicate.as_ref(),
+                                existing,
+                                &tuple,
+                            )? {
+                                self.returning.push(&tuple)?;
+                                inserted_count += 1;
+                            }
+                        }
+                        continue;
+                    }
+                }
+                check_constraints(&self.checks, &tuple)?;
                 for (index_meta, exprs) in table_snapshot.index_metas.iter() {
                     let index_meta = plan_arena.index(*index_meta);
                     let tuple_id = tuple.pk.as_ref().ok_or(DatabaseError::PrimaryKeyNotFound)?;
@@ -184,16 +411,17 @@ impl<'a, T: Transaction + 'a> ExecutorNode<'a, T> for Insert {
                     &serializers,
                     self.is_overwrite,
                 )?;
+                if table_snapshot.has_references {
+                    check_references(arena, plan_arena, &self.table_name, None, &tuple)?;
+                }
+                self.returning.push(&tuple)?;
                 inserted_count += 1;
             }
 
-            TupleBuilder::build_result_into(arena.result_tuple_mut(), inserted_count.to_string());
-            arena.resume();
-            Ok(())
+            self.returning.complete(arena, inserted_count);
         } else {
-            TupleBuilder::build_result_into(arena.result_tuple_mut(), "0".to_string());
-            arena.resume();
-            Ok(())
+            self.returning.complete(arena, 0);
         }
+        Ok(())
     }
 }
@@ -18,5 +18,91 @@ pub(crate) mod copy_from_file;
 #[cfg(feature = "copy")]
 pub(crate) mod copy_to_file;
 pub(crate) mod delete;
+pub(crate) mod foreign_key;
 pub(crate) mod insert;
 pub(crate) mod update;
+
+use crate::errors::DatabaseError;
+use crate::execution::ExecArena;
+use crate::expression::ScalarExpression;
+use crate::storage::Transaction;
+use crate::types::tuple::Tuple;
+use crate::types::tuple_builder::TupleBuilder;
+use crate::types::value::DataValue;
+
+/// Evaluates `CHECK` constraints against a row in table column order; like SQL, a
+/// predicate that evaluates to `NULL` does not reject the row.
+pub(crate) fn check_constraints(
+    checks: &[(String, ScalarExpression)],
+    tuple: &Tuple,
+) -> Result<(), DatabaseError> {
+    for (name, predicate) in checks {
+        if matches!(predicate.eval(Some(tuple))?, DataValue::Boolean(false)) {
+            return Err(DatabaseError::CheckViolation {
+                constraint: name.clone(),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Collects `RETURNING` rows while a write runs and hands them out once it has
+/// completed, so an iterator dropped early never leaves a half-applied statement.
+#[derive(Default)]
+pub(crate) struct Returning {
+    exprs: Vec<ScalarExpression>,
+    rows: Vec<Tuple>,
+    pending: Option<std::vec::IntoIter<Tuple>>,
+}
+
+impl Returning {
+    pub(crate) fn new(exprs: Vec<ScalarExpression>) -> Self {
+        Returning {
+            exprs,
+            ..Default::default()
+        }
+    }
+
+    pub(crate) fn push(&mut self, tuple: &Tuple) -> Result<(), DatabaseError> {
+        if self.exprs.is_empty() {
+            return Ok(());
+        }
+        let values = self
+            .exprs
+            .iter()
+            .map(|expr| expr.eval(Some(tuple)))
+            .collect::<Result<Vec<_>, _>>()?;
+        self.rows.push(Tuple::new(None, values));
+        Ok(())
+    }
+
+    /// Emits the affected row count, or starts streaming the collected rows.
+    pub(crate) fn complete<'a, T: Transaction + 'a>(
+        &mut self,
+        arena: &mut ExecArena<'a, T>,
+        count: usize,
+    ) {
+        if self.exprs.is_empty() {
+            TupleBuilder::build_result_into(arena.result_tuple_mut(), count.to_string());
+            arena.resume();
+        } else {
+            self.pending = Some(std::mem::take(&mut self.rows).into_iter());
+            self.next_tuple(arena);
+        }
+    }
+
+    /// Produces the next collected row, returning `false` before the write has completed.
+    pub(crate) fn next_tuple<'a, T: Transaction + 'a>(
+        &mut self,
+        arena: &mut ExecArena<'a, T>,
+    ) -> bool {
+        let Some(pending) = self.pending.as_mut() else {
+            return false;
+        };
+        match pending.next() {
+            Some(tuple) => arena.produce_tuple(tuple),
+            None => arena.finish(),
+        }
+        true
+    }
+}
@@ -14,6 +14,8 @@
 
 use crate::catalog::{ColumnRef, TableName};
 use crate::errors::DatabaseError;
+use crate::execution::dml::foreign_key::{apply_referential_actions, check_references};
+use crate::execution::dml::{check_constraints, Returning};
 use crate::execution::{
     build_read, with_projection_tmp_value, ExecArena, ExecId, ExecNode, ExecutionContext,
     ExecutorNode, WriteExecutor,
@@ -25,7 +27,6 @@ use crate::planner::LogicalPlan;
 use crate::storage::Transaction;
 use crate::types::index::{Index, IndexMeta, IndexType};
 use crate::types::tuple::{Schema, Tuple};
-use crate::types::tuple_builder::TupleBuilder;
 use crate::types::ColumnId;
 use std::{
     collections::{HashMap, HashSet},
@@ -38,6 +39,8 @@ pub struct Update {
     input_schema: Schema,
     input_plan: LogicalPlan,
     input: Option<ExecId>,
+    returning: Returning,
+    checks: Vec<(String, ScalarExpression)>,
 }
 
 impl From<(UpdateOperator, LogicalPlan)> for Update {
@@ -46,6 +49,8 @@ impl From<(UpdateOperator, LogicalPlan)> for Update {
             UpdateOperator {
                 table_name,
                 value_exprs,
+                returning,
+                checks,
             },
             input,
         ): (UpdateOperator, LogicalPlan),
@@ -56,6 +61,8 @@ impl From<(UpdateOperator, LogicalPlan)> for Update {
             input_schema: Default::default(),
             input_plan: input,
             input: None,
+            returning: Returning::new(returning),
+            checks,
         }
     }
 }
@@ -107,6 +114,9 @@ impl<'a, T: Transaction + 'a> ExecutorNode<'a, T> for Update {
         arena: &mut ExecArena<'a, T>,
         plan_arena: &mut crate::planner::PlanArena<'a>,
     ) -> Result<(), DatabaseError> {
+        if self.returning.next_tuple(arena) {
+            return Ok(());
+        }
         let Some(input) = self.input.take() else {
             arena.finish();
             return Ok(());
@@ -154,6 +164,9 @@ impl<'a, T: Transaction + 'a> ExecutorNode<'a, T> for Update {
                     continue;
                 };
 
+                let old = table_snapshot
+                    .has_references
+                    .then(|| arena.result_tuple().clone());
                 let mut old_index_values = Vec::new();
                 for (index_offset, (index_meta, exprs)) in
                     table_snapshot.index_metas.iter().enumerate()
@@ -181,6 +194,7 @@ impl<'a, T: Transaction + 'a> ExecutorNode<'a, T> for Update {
                         arena.result_tuple_mut().values[i] = value;
                     }
                 }
+                check_constraints(&self.checks, arena.result_tuple())?;
 
                 let new_pk = Tuple::primary_projection(
                     table_snapshot.primary_key_indices,
@@ -230,16 +244,24 @@ impl<'a, T: Transaction + 'a> ExecutorNode<'a, T> for Update {
                     &serializers,
                     is_overwrite,
                 )?;
+                if let Some(old) = &old {
+                    check_references(arena, plan_arena, &self.table_name, Some(old), &tuple)?;
+                    apply_referential_actions(
+                        arena,
+                        plan_arena,
+                        &self.table_name,
+                        old,
+                        Some(&tuple),
+                    )?;
+                }
+                self.returning.push(&tuple)?;
                 updated_count += 1;
             }
 
-            TupleBuilder::build_result_into(arena.result_tuple_mut(), updated_count.to_string());
-            arena.resume();
-            Ok(())
+            self.returning.complete(arena, updated_count);
         } else {
-            TupleBuilder::build_result_into(arena.result_tuple_mut(), "0".to_string());
-            arena.resume();
-            Ok(())
+            self.returning.complete(arena, 0);
         }
+        Ok(())
     }
 }
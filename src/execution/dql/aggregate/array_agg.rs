@@ -0,0 +1,75 @@
+// Copyright 2024 KipData/KiteSQL
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::errors::DatabaseError;
+use crate::execution::dql::aggregate::Accumulator;
+use crate::types::value::DataValue;
+
+/// `ARRAY_AGG(value)` collects every value, NULLs included, into an array. It is `NULL`
+/// without input rows.
+pub struct ArrayAggAccumulator {
+    result: DataValue,
+}
+
+impl ArrayAggAccumulator {
+    pub fn new() -> Self {
+        Self {
+            result: DataValue::Null,
+        }
+    }
+}
+
+impl Accumulator for ArrayAggAccumulator {
+    fn update_value(&mut self, value: &DataValue) -> Result<(), DatabaseError> {
+        match &mut self.result {
+            DataValue::Array(values) => values.push(value.clone()),
+            result => *result = DataValue::Array(vec![value.clone()]),
+        }
+
+        Ok(())
+    }
+
+    fn result(&self) -> &DataValue {
+        &self.result
+    }
+
+    fn result_owned(self: Box<Self>) -> DataValue {
+        self.result
+    }
+}
+
+// GRCOV_EXCL_START
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn array_agg_results() -> Result<(), DatabaseError> {
+        let mut accumulator = ArrayAggAccumulator::new();
+        assert_eq!(accumulator.result(), &DataValue::Null);
+        for value in [DataValue::Int32(1), DataValue::Null, DataValue::Int32(3)] {
+            accumulator.update_value(&value)?;
+        }
+        assert_eq!(
+            Box::new(accumulator).result_owned(),
+            DataValue::Array(vec![
+                DataValue::Int32(1),
+                DataValue::Null,
+                DataValue::Int32(3)
+            ])
+        );
+        Ok(())
+    }
+}
+// GRCOV_EXCL_STOP
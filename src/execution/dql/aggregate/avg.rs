@@ -60,24 +60,7 @@ impl Accumulator for AvgAccumulator {
         let Some(acc) = &self.inner else {
             return Ok(());
         };
-        let mut value = Cow::Borrowed(acc.result());
-        let value_ty = value.logical_type();
-
-        if self.count == 0 {
-            return Ok(());
-        }
-        let quantity = if value_ty.is_signed_numeric() {
-            DataValue::Int64(self.count as i64)
-        } else {
-            DataValue::UInt32(self.count as u32)
-        };
-        let quantity_ty = quantity.logical_type();
-
-        if value_ty != quantity_ty {
-            value = Cow::Owned(value.into_owned().cast(&quantity_ty)?)
-        }
-        let evaluator = binary_create(Cow::Owned(quantity_ty), BinaryOperator::Divide)?;
-        self.result = evaluator.binary_eval(value.as_ref(), &quantity)?;
+        self.result = average(acc.result(), self.count)?;
         Ok(())
     }
 
@@ -90,6 +73,29 @@ impl Accumulator for AvgAccumulator {
     }
 }
 
+/// Divides `sum` by the number of values it adds up, NULL when there are none.
+pub(crate) fn average(sum: &DataValue, count: usize) -> Result<DataValue, DatabaseError> {
+    if count == 0 || sum.is_null() {
+        return Ok(DataValue::Null);
+    }
+    let mut value = Cow::Borrowed(sum);
+    let value_ty = value.logical_type();
+    let quantity = if value_ty.is_floating_point_numeric() {
+        DataValue::Float64((count as f64).into())
+    } else if value_ty.is_signed_numeric() {
+        DataValue::Int64(count as i64)
+    } else {
+        DataValue::UInt32(count as u32)
+    };
+    let quantity_ty = quantity.logical_type();
+
+    if value_ty != quantity_ty {
+        value = Cow::Owned(value.into_owned().cast(&quantity_ty)?)
+    }
+    let evaluator = binary_create(Cow::Owned(quantity_ty), BinaryOperator::Divide)?;
+    evaluator.binary_eval(value.as_ref(), &quantity)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -129,4 +135,18 @@ mod tests {
         );
         Ok(())
     }
+
+    #[test]
+    fn floating_point_results() -> Result<(), DatabaseError> {
+        let mut accumulator = AvgAccumulator::new();
+        for value in [0.4, 0.5, 0.6] {
+            accumulator.update_value(&DataValue::Float64(value.into()))?;
+        }
+        accumulator.evaluate()?;
+        assert_eq!(
+            Box::new(accumulator).result_owned(),
+            DataValue::Float64(0.5.into())
+        );
+        Ok(())
+    }
 }
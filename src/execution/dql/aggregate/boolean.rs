@@ -0,0 +1,82 @@
+// Copyright 2024 KipData/KiteSQL
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::errors::DatabaseError;
+use crate::execution::dql::aggregate::Accumulator;
+use crate::types::value::DataValue;
+
+/// `BOOL_AND` and `BOOL_OR` over the non-NULL values, NULL when there are none.
+pub struct BoolAccumulator {
+    result: DataValue,
+    is_and: bool,
+}
+
+impl BoolAccumulator {
+    pub fn new(is_and: bool) -> Self {
+        Self {
+            result: DataValue::Null,
+            is_and,
+        }
+    }
+}
+
+impl Accumulator for BoolAccumulator {
+    fn update_value(&mut self, value: &DataValue) -> Result<(), DatabaseError> {
+        let value = match value {
+            DataValue::Null => return Ok(()),
+            DataValue::Boolean(value) => *value,
+            _ => return Err(DatabaseError::InvalidType),
+        };
+        self.result = DataValue::Boolean(match self.result {
+            DataValue::Boolean(result) if self.is_and => result && value,
+            DataValue::Boolean(result) => result || value,
+            _ => value,
+        });
+
+        Ok(())
+    }
+
+    fn result(&self) -> &DataValue {
+        &self.result
+    }
+
+    fn result_owned(self: Box<Self>) -> DataValue {
+        self.result
+    }
+}
+
+// GRCOV_EXCL_START
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bool_and_or_results() -> Result<(), DatabaseError> {
+        let mut and = BoolAccumulator::new(true);
+        let mut or = BoolAccumulator::new(false);
+        assert_eq!(and.result(), &DataValue::Null);
+        for value in [
+            DataValue::Boolean(true),
+            DataValue::Null,
+            DataValue::Boolean(false),
+        ] {
+            and.update_value(&value)?;
+            or.update_value(&value)?;
+        }
+        assert_eq!(and.result(), &DataValue::Boolean(false));
+        assert_eq!(or.result(), &DataValue::Boolean(true));
+        Ok(())
+    }
+}
+// GRCOV_EXCL_STOP
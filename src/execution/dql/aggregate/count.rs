@@ -15,7 +15,6 @@
 use crate::errors::DatabaseError;
 use crate::execution::dql::aggregate::Accumulator;
 use crate::types::value::DataValue;
-use std::collections::HashSet;
 
 pub struct CountAccumulator {
     result: DataValue,
@@ -41,33 +40,12 @@ impl Accumulator for CountAccumulator {
         Ok(())
     }
 
-    fn result(&self) -> &DataValue {
-        &self.result
-    }
-
-    fn result_owned(self: Box<Self>) -> DataValue {
-        self.result
-    }
-}
-
-pub struct DistinctCountAccumulator {
-    distinct_values: HashSet<DataValue>,
-    result: DataValue,
-}
-
-impl DistinctCountAccumulator {
-    pub fn new() -> Self {
-        Self {
-            distinct_values: HashSet::default(),
-            result: DataValue::Int32(0),
-        }
-    }
-}
-
-impl Accumulator for DistinctCountAccumulator {
-    fn update_value(&mut self, value: &DataValue) -> Result<(), DatabaseError> {
-        if !value.is_null() && self.distinct_values.insert(value.clone()) {
-            self.result = DataValue::Int32(self.distinct_values.len() as i32);
+    fn update_values(&mut self, values: &[DataValue]) -> Result<(), DatabaseError> {
+        if values.iter().all(|value| !value.is_null()) {
+            let DataValue::Int32(result) = &mut self.result else {
+                unreachable!()
+            };
+            *result += 1;
         }
 
         Ok(())
@@ -99,13 +77,11 @@ mod tests {
     }
 
     #[test]
-    fn distinct_count_results() -> Result<(), DatabaseError> {
-        let mut accumulator = DistinctCountAccumulator::new();
-        for value in [DataValue::Null, 1.into(), 1.into(), 2.into()] {
-            accumulator.update_value(&value)?;
-        }
-        assert_eq!(accumulator.result(), &DataValue::Int32(2));
-        assert_eq!(Box::new(accumulator).result_owned(), DataValue::Int32(2));
+    fn count_rows_without_nulls() -> Result<(), DatabaseError> {
+        let mut accumulator = CountAccumulator::new();
+        accumulator.update_values(&[1.into(), 2.into()])?;
+        accumulator.update_values(&[1.into(), DataValue::Null])?;
+        assert_eq!(accumulator.result(), &DataValue::Int32(1));
         Ok(())
     }
 }
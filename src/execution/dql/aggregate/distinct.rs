@@ -0,0 +1,118 @@
+// Copyright 2024 KipData/KiteSQL
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::errors::DatabaseError;
+use crate::execution::dql::aggregate::Accumulator;
+use crate::types::value::DataValue;
+use std::collections::HashSet;
+
+/// Feeds every distinct value, or distinct row of values of a multi-argument call, to the
+/// wrapped accumulator once.
+pub struct DistinctAccumulator {
+    distinct_values: HashSet<DataValue>,
+    inner: Box<dyn Accumulator>,
+}
+
+impl DistinctAccumulator {
+    pub fn new(inner: Box<dyn Accumulator>) -> Self {
+        Self {
+            distinct_values: HashSet::default(),
+            inner,
+        }
+    }
+}
+
+impl Accumulator for DistinctAccumulator {
+    fn update_value(&mut self, value: &DataValue) -> Result<(), DatabaseError> {
+        if !self.distinct_values.contains(value) {
+            self.distinct_values.insert(value.clone());
+            self.inner.update_value(value)?;
+        }
+
+        Ok(())
+    }
+
+    fn update_values(&mut self, values: &[DataValue]) -> Result<(), DatabaseError> {
+        if self
+            .distinct_values
+            .insert(DataValue::Tuple(values.to_vec(), false))
+        {
+            self.inner.update_values(values)?;
+        }
+
+        Ok(())
+    }
+
+    fn evaluate(&mut self) -> Result<(), DatabaseError> {
+        self.inner.evaluate()
+    }
+
+    fn result(&self) -> &DataValue {
+        self.inner.result()
+    }
+
+    fn result_owned(self: Box<Self>) -> DataValue {
+        self.inner.result_owned()
+    }
+}
+
+// GRCOV_EXCL_START
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+    use super::*;
+    use crate::execution::dql::aggregate::count::CountAccumulator;
+    use crate::execution::dql::aggregate::sum::SumAccumulator;
+    use crate::types::LogicalType;
+    use std::borrow::Cow;
+
+    #[test]
+    fn distinct_count_results() -> Result<(), DatabaseError> {
+        let mut accumulator = DistinctAccumulator::new(Box::new(CountAccumulator::new()));
+        for value in [DataValue::Null, 1.into(), 1.into(), 2.into()] {
+            accumulator.update_value(&value)?;
+        }
+        assert_eq!(accumulator.result(), &DataValue::Int32(2));
+        assert_eq!(Box::new(accumulator).result_owned(), DataValue::Int32(2));
+        Ok(())
+    }
+
+    #[test]
+    fn distinct_sum_results() -> Result<(), DatabaseError> {
+        let mut accumulator = DistinctAccumulator::new(Box::new(SumAccumulator::new(
+            Cow::Borrowed(&LogicalType::Integer),
+        )?));
+        for value in [DataValue::Null, 2.into(), 2.into(), 3.into()] {
+            accumulator.update_value(&value)?;
+        }
+        assert_eq!(accumulator.result(), &DataValue::Int32(5));
+        assert_eq!(Box::new(accumulator).result_owned(), DataValue::Int32(5));
+        Ok(())
+    }
+
+    #[test]
+    fn distinct_count_of_rows() -> Result<(), DatabaseError> {
+        let mut accumulator = DistinctAccumulator::new(Box::new(CountAccumulator::new()));
+        for values in [
+            [1.into(), 1.into()],
+            [1.into(), 2.into()],
+            [1.into(), 1.into()],
+            [2.into(), DataValue::Null],
+        ] {
+            accumulator.update_values(&values)?;
+        }
+        assert_eq!(accumulator.result(), &DataValue::Int32(2));
+        Ok(())
+    }
+}
+// GRCOV_EXCL_STOP
@@ -0,0 +1,97 @@
+// Copyright 2024 KipData/KiteSQL
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::errors::DatabaseError;
+use crate::execution::dql::aggregate::Accumulator;
+use crate::expression::ScalarExpression;
+use crate::types::value::DataValue;
+
+/// `GROUPING(expr, ...)` does not read any rows, its result is fixed by the grouping set of the
+/// group: one bit per argument, from the most significant, set when the argument is rolled up.
+pub struct GroupingAccumulator {
+    result: DataValue,
+}
+
+impl GroupingAccumulator {
+    /// Every argument is grouped by a plain `GROUP BY`.
+    pub fn new() -> Self {
+        Self {
+            result: DataValue::Int32(0),
+        }
+    }
+
+    pub fn for_set(
+        args: &[ScalarExpression],
+        groupby_exprs: &[ScalarExpression],
+        set: &[usize],
+    ) -> Result<Self, DatabaseError> {
+        let mut mask = 0;
+        for arg in args {
+            let Some(position) = groupby_exprs
+                .iter()
+                .position(|expr| expr.unpack_alias_ref() == arg.unpack_alias_ref())
+            else {
+                return Err(DatabaseError::AggMiss(format!(
+                    "argument `{arg}` of grouping() must appear in the GROUP BY clause"
+                )));
+            };
+            mask = (mask << 1) | i32::from(!set.contains(&position));
+        }
+        Ok(Self {
+            result: DataValue::Int32(mask),
+        })
+    }
+}
+
+impl Accumulator for GroupingAccumulator {
+    fn update_value(&mut self, _: &DataValue) -> Result<(), DatabaseError> {
+        Ok(())
+    }
+
+    fn update_values(&mut self, _: &[DataValue]) -> Result<(), DatabaseError> {
+        Ok(())
+    }
+
+    fn result(&self) -> &DataValue {
+        &self.result
+    }
+
+    fn result_owned(self: Box<Self>) -> DataValue {
+        self.result
+    }
+}
+
+// GRCOV_EXCL_START
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn grouping_marks_rolled_up_arguments() -> Result<(), DatabaseError> {
+        let exprs: Vec<ScalarExpression> = vec![1_i32.into(), 2_i32.into(), 3_i32.into()];
+        let args = [exprs[0].clone(), exprs[2].clone()];
+
+        let grouping = |set: &[usize]| -> Result<DataValue, DatabaseError> {
+            Ok(Box::new(GroupingAccumulator::for_set(&args, &exprs, set)?).result_owned())
+        };
+        assert_eq!(grouping(&[0, 1, 2])?, DataValue::Int32(0));
+        assert_eq!(grouping(&[0, 1])?, DataValue::Int32(1));
+        assert_eq!(grouping(&[1, 2])?, DataValue::Int32(2));
+        assert_eq!(grouping(&[])?, DataValue::Int32(3));
+        assert_eq!(GroupingAccumulator::new().result(), &DataValue::Int32(0));
+        assert!(GroupingAccumulator::for_set(&[4_i32.into()], &exprs, &[]).is_err());
+        Ok(())
+    }
+}
+// GRCOV_EXCL_STOP
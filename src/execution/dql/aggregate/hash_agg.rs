@@ -14,7 +14,8 @@
 
 use crate::errors::DatabaseError;
 use crate::execution::dql::aggregate::{
-    create_accumulators, update_accumulators, write_aggregate_output, Accumulator,
+    create_accumulators, create_grouping_set_accumulators, grouping_set_keys, update_accumulators,
+    write_aggregate_output, Accumulator,
 };
 use crate::execution::{
     build_read, ExecArena, ExecId, ExecNode, ExecutionContext, ExecutorNode, ReadExecutor,
@@ -32,6 +33,7 @@ type HashAggOutput = HashMapIntoIter<Vec<DataValue>, Vec<Box<dyn Accumulator>>>;
 pub struct HashAggExecutor {
     agg_calls: Vec<ScalarExpression>,
     groupby_exprs: Vec<ScalarExpression>,
+    grouping_sets: Vec<Vec<usize>>,
     input: ExecId,
     output: Option<HashAggOutput>,
 }
@@ -44,6 +46,7 @@ impl<'a, T: Transaction + 'a> ReadExecutor<'a, T> for HashAggExecutor {
             AggregateOperator {
                 agg_calls,
                 groupby_exprs,
+                grouping_sets,
                 ..
             },
             input,
@@ -57,6 +60,7 @@ impl<'a, T: Transaction + 'a> ReadExecutor<'a, T> for HashAggExecutor {
         arena.push(ExecNode::HashAgg(HashAggExecutor {
             agg_calls,
             groupby_exprs,
+            grouping_sets,
             input,
             output: None,
         }))
@@ -72,31 +76,72 @@ impl<'a, T: Transaction + 'a> ExecutorNode<'a, T> for HashAggExecutor {
         if self.output.is_none() {
             let mut group_hash_accs: HashMap<Vec<DataValue>, Vec<Box<dyn Accumulator>>> =
                 HashMap::new();
-            let mut group_keys = Vec::with_capacity(self.groupby_exprs.len());
-
-            while arena.next_tuple(self.input, plan_arena)? {
-                let tuple = arena.result_tuple();
-                group_keys.clear();
-                for expr in &self.groupby_exprs {
-                    group_keys.push(expr.eval(Some(tuple))?);
+            if !self.grouping_sets.is_empty() {
+                let mut is_empty = true;
+                while arena.next_tuple(self.input, plan_arena)? {
+                    let tuple = arena.result_tuple();
+                    is_empty = false;
+                    for (i, group_keys) in
+                        grouping_set_keys(&self.groupby_exprs, &self.grouping_sets, tuple)?
+                    {
+                        if let Some(accs) = group_hash_accs.get_mut(group_keys.as_slice()) {
+                            update_accumulators(accs, &self.agg_calls, tuple)?;
+                        } else {
+                            let mut accs = create_grouping_set_accumulators(
+                                &self.agg_calls,
+                                &self.groupby_exprs,
+                                &self.grouping_sets[i],
+                            )?;
+                            update_accumulators(&mut accs, &self.agg_calls, tuple)?;
+                            group_hash_accs.insert(group_keys, accs);
+                        }
+                    }
+                }
+                // Like an aggregate without GROUP BY, an empty grouping set has one group even
+                // when there are no rows
+                if is_empty {
+                    for (i, set) in self.grouping_sets.iter().enumerate() {
+                        if set.is_empty() {
+                            let mut group_keys = vec![DataValue::Null; self.groupby_exprs.len()];
+                            group_keys.push(DataValue::UInt32(i as u32));
+                            let accs = create_grouping_set_accumulators(
+                                &self.agg_calls,
+                                &self.groupby_exprs,
+                                set,
+                            )?;
+                            group_hash_accs.insert(group_keys, accs);
+                        }
+                    }
                 }
+            } else {
+                let mut group_keys = Vec::with_capacity(self.groupby_exprs.len());
+                while arena.next_tuple(self.input, plan_arena)? {
+                    let tuple = arena.result_tuple();
+                    group_keys.clear();
+                    for expr in &self.groupby_exprs {
+                        group_keys.push(expr.eval(Some(tuple))?);
+                    }
 
-                if let Some(accs) = group_hash_accs.get_mut(group_keys.as_slice()) {
-                    update_accumulators(accs, &self.agg_calls, tuple)?;
-                } else {
-                    let mut accs = create_accumulators(&self.agg_calls)?;
-                    update_accumulators(&mut accs, &self.agg_calls, tuple)?;
-                    group_hash_accs.insert(group_keys.clone(), accs);
+                    if let Some(accs) = group_hash_accs.get_mut(group_keys.as_slice()) {
+                        update_accumulators(accs, &self.agg_calls, tuple)?;
+                    } else {
+                        let mut accs = create_accumulators(&self.agg_calls)?;
+                        update_accumulators(&mut accs, &self.agg_calls, tuple)?;
+                        group_hash_accs.insert(group_keys.clone(), accs);
+                    }
                 }
             }
 
             self.output = Some(group_hash_accs.into_iter());
         }
 
-        let Some((group_keys, accs)) = self.output.as_mut().and_then(Iterator::next) else {
+        let Some((mut group_keys, accs)) = self.output.as_mut().and_then(Iterator::next) else {
             arena.finish();
             return Ok(());
         };
+        if !self.grouping_sets.is_empty() {
+            group_keys.pop();
+        }
 
         write_aggregate_output(arena.result_tuple_mut(), accs, group_keys)?;
         arena.resume();
@@ -177,10 +222,13 @@ mod test {
         let plan = LogicalPlan::new(
             Operator::Aggregate(AggregateOperator {
                 groupby_exprs: vec![ScalarExpression::column_expr(t1_schema[0], 0)],
+                grouping_sets: Vec::new(),
                 agg_calls: vec![ScalarExpression::AggCall {
                     distinct: false,
                     kind: AggKind::Sum,
                     args: vec![ScalarExpression::column_expr(t1_schema[1], 1)],
+                    filter: None,
+                    order_by: Vec::new(),
                     ty: LogicalType::Integer,
                 }],
                 is_distinct: false,
@@ -0,0 +1,155 @@
+// Copyright 2024 KipData/KiteSQL
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::errors::DatabaseError;
+use crate::execution::dql::aggregate::Accumulator;
+use crate::types::json::Json;
+use crate::types::value::DataValue;
+use std::collections::BTreeMap;
+
+/// `JSON_AGG(value)` collects every value, NULLs included as JSON `null`, into an array.
+pub struct JsonAggAccumulator {
+    result: DataValue,
+}
+
+impl JsonAggAccumulator {
+    pub fn new() -> Self {
+        Self {
+            result: DataValue::Null,
+        }
+    }
+}
+
+impl Accumulator for JsonAggAccumulator {
+    fn update_value(&mut self, value: &DataValue) -> Result<(), DatabaseError> {
+        let value = Json::from_data_value(value);
+        match &mut self.result {
+            DataValue::Json(Json::Array(values)) => values.push(value),
+            result => *result = DataValue::Json(Json::Array(vec![value])),
+        }
+
+        Ok(())
+    }
+
+    fn result(&self) -> &DataValue {
+        &self.result
+    }
+
+    fn result_owned(self: Box<Self>) -> DataValue {
+        self.result
+    }
+}
+
+/// `JSON_OBJECT_AGG(key, value)` collects the pairs into an object, a later value replaces
+/// an earlier one of the same key.
+pub struct JsonObjectAggAccumulator {
+    result: DataValue,
+}
+
+impl JsonObjectAggAccumulator {
+    pub fn new() -> Self {
+        Self {
+            result: DataValue::Null,
+        }
+    }
+}
+
+impl Accumulator for JsonObjectAggAccumulator {
+    fn update_value(&mut self, _value: &DataValue) -> Result<(), DatabaseError> {
+        Err(DatabaseError::MisMatch(
+            "number of json_object_agg() parameters",
+            "2",
+        ))
+    }
+
+    fn update_values(&mut self, values: &[DataValue]) -> Result<(), DatabaseError> {
+        let [key, value] = values else {
+            return Err(DatabaseError::MisMatch(
+                "number of json_object_agg() parameters",
+                "2",
+            ));
+        };
+        let key = match key {
+            DataValue::Null => {
+                return Err(DatabaseError::InvalidValue(
+                    "field name must not be null".to_string(),
+                ))
+            }
+            DataValue::Utf8 { value, .. } => value.clone(),
+            key => key.to_string(),
+        };
+        let value = Json::from_data_value(value);
+        match &mut self.result {
+            DataValue::Json(Json::Object(fields)) => {
+                fields.insert(key, value);
+            }
+            result => *result = DataValue::Json(Json::Object(BTreeMap::from([(key, value)]))),
+        }
+
+        Ok(())
+    }
+
+    fn result(&self) -> &DataValue {
+        &self.result
+    }
+
+    fn result_owned(self: Box<Self>) -> DataValue {
+        self.result
+    }
+}
+
+// GRCOV_EXCL_START
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn json_agg_results() -> Result<(), DatabaseError> {
+        let mut accumulator = JsonAggAccumulator::new();
+        assert_eq!(accumulator.result(), &DataValue::Null);
+        for value in [
+            DataValue::Int32(1),
+            DataValue::Null,
+            DataValue::from("a".to_string()),
+        ] {
+            accumulator.update_value(&value)?;
+        }
+        assert_eq!(
+            Box::new(accumulator).result_owned(),
+            DataValue::Json(Json::parse(r#"[1, null, "a"]"#)?)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn json_object_agg_results() -> Result<(), DatabaseError> {
+        let mut accumulator = JsonObjectAggAccumulator::new();
+        for (key, value) in [
+            (DataValue::from("a".to_string()), DataValue::Int32(1)),
+            (DataValue::Int32(2), DataValue::Boolean(true)),
+            (DataValue::from("a".to_string()), DataValue::Null),
+        ] {
+            accumulator.update_values(&[key, value])?;
+        }
+        assert!(accumulator
+            .update_values(&[DataValue::Null, DataValue::Int32(1)])
+            .is_err());
+        assert_eq!(
+            Box::new(accumulator).result_owned(),
+            DataValue::Json(Json::parse(r#"{"2": true, "a": null}"#)?)
+        );
+        Ok(())
+    }
+}
+// GRCOV_EXCL_STOP
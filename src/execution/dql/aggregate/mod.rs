@@ -12,20 +12,40 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-mod avg;
+mod array_agg;
+pub(crate) mod avg;
+mod boolean;
 mod count;
+mod distinct;
+mod grouping;
 pub mod hash_agg;
+mod json_agg;
 mod min_max;
+mod ordered;
 pub mod simple_agg;
+#[cfg(feature = "spill")]
+pub mod sort_grouping_sets;
 pub mod stream_agg;
 pub mod stream_distinct;
+mod string_agg;
 mod sum;
+mod user_defined;
+mod variance;
 
 use crate::errors::DatabaseError;
+use crate::execution::dql::aggregate::array_agg::ArrayAggAccumulator;
 use crate::execution::dql::aggregate::avg::AvgAccumulator;
-use crate::execution::dql::aggregate::count::{CountAccumulator, DistinctCountAccumulator};
+use crate::execution::dql::aggregate::boolean::BoolAccumulator;
+use crate::execution::dql::aggregate::count::CountAccumulator;
+use crate::execution::dql::aggregate::distinct::DistinctAccumulator;
+use crate::execution::dql::aggregate::grouping::GroupingAccumulator;
+use crate::execution::dql::aggregate::json_agg::{JsonAggAccumulator, JsonObjectAggAccumulator};
 use crate::execution::dql::aggregate::min_max::MinMaxAccumulator;
-use crate::execution::dql::aggregate::sum::{DistinctSumAccumulator, SumAccumulator};
+use crate::execution::dql::aggregate::ordered::OrderedAccumulator;
+use crate::execution::dql::aggregate::string_agg::StringAggAccumulator;
+use crate::execution::dql::aggregate::sum::SumAccumulator;
+use crate::execution::dql::aggregate::user_defined::UserDefinedAccumulator;
+use crate::execution::dql::aggregate::variance::VarianceAccumulator;
 use crate::expression::agg::AggKind;
 use crate::expression::ScalarExpression;
 use crate::iter_ext::Itertools;
@@ -40,6 +60,13 @@ pub trait Accumulator {
     /// updates the accumulator's state from a vector of arrays.
     fn update_value(&mut self, value: &DataValue) -> Result<(), DatabaseError>;
 
+    /// updates the accumulator's state from the arguments of a multi-argument call.
+    fn update_values(&mut self, _values: &[DataValue]) -> Result<(), DatabaseError> {
+        Err(DatabaseError::UnsupportedStmt(
+            "currently aggregate functions only support a single Column as a parameter".to_string(),
+        ))
+    }
+
     /// evaluates its result based on its current state.
     fn evaluate(&mut self) -> Result<(), DatabaseError> {
         Ok(())
@@ -51,18 +78,27 @@ pub trait Accumulator {
 }
 
 pub(crate) fn create_accumulator(
-    kind: AggKind,
+    kind: &AggKind,
     ty: &crate::types::LogicalType,
-    distinct: bool,
 ) -> Result<Box<dyn Accumulator>, DatabaseError> {
-    Ok(match (kind, distinct) {
-        (AggKind::Count, false) => Box::new(CountAccumulator::new()),
-        (AggKind::Count, true) => Box::new(DistinctCountAccumulator::new()),
-        (AggKind::Sum, false) => Box::new(SumAccumulator::new(Cow::Borrowed(ty))?),
-        (AggKind::Sum, true) => Box::new(DistinctSumAccumulator::new(ty)?),
-        (AggKind::Min, _) => Box::new(MinMaxAccumulator::new(false)),
-        (AggKind::Max, _) => Box::new(MinMaxAccumulator::new(true)),
-        (AggKind::Avg, _) => Box::new(AvgAccumulator::new()),
+    Ok(match kind {
+        AggKind::Count => Box::new(CountAccumulator::new()),
+        AggKind::Sum => Box::new(SumAccumulator::new(Cow::Borrowed(ty))?),
+        AggKind::Min => Box::new(MinMaxAccumulator::new(false)),
+        AggKind::Max => Box::new(MinMaxAccumulator::new(true)),
+        AggKind::Avg => Box::new(AvgAccumulator::new()),
+        AggKind::StringAgg => Box::new(StringAggAccumulator::new()),
+        AggKind::BoolAnd => Box::new(BoolAccumulator::new(true)),
+        AggKind::BoolOr => Box::new(BoolAccumulator::new(false)),
+        AggKind::StddevPop => Box::new(VarianceAccumulator::new(false, true)),
+        AggKind::StddevSamp => Box::new(VarianceAccumulator::new(true, true)),
+        AggKind::VarPop => Box::new(VarianceAccumulator::new(false, false)),
+        AggKind::VarSamp => Box::new(VarianceAccumulator::new(true, false)),
+        AggKind::Grouping => Box::new(GroupingAccumulator::new()),
+        AggKind::JsonAgg => Box::new(JsonAggAccumulator::new()),
+        AggKind::JsonObjectAgg => Box::new(JsonObjectAggAccumulator::new()),
+        AggKind::ArrayAgg => Box::new(ArrayAggAccumulator::new()),
+        AggKind::UserDefined(function) => Box::new(UserDefinedAccumulator::new(function.clone())?),
     })
 }
 
@@ -74,33 +110,111 @@ pub(crate) fn create_accumulators(
         .iter()
         .map(|expr| {
             let ScalarExpression::AggCall {
-                kind, ty, distinct, ..
+                kind,
+                ty,
+                distinct,
+                args,
+                order_by,
+                ..
             } = expr
             else {
                 unreachable!("create_accumulators called with non-aggregate expression {expr}")
             };
-            create_accumulator(*kind, ty, *distinct)
+            let mut accumulator = create_accumulator(kind, ty)?;
+            if *distinct && kind.allow_distinct() {
+                accumulator = Box::new(DistinctAccumulator::new(accumulator));
+            }
+            if !order_by.is_empty() {
+                accumulator = Box::new(OrderedAccumulator::new(
+                    accumulator,
+                    args.len(),
+                    order_by.clone(),
+                ));
+            }
+            Ok(accumulator)
         })
         .try_collect()
 }
 
+/// Creates the accumulators of a group in grouping set `set`, whose `GROUPING()` calls report the
+/// group keys that the set rolls up.
+pub(crate) fn create_grouping_set_accumulators(
+    agg_calls: &[ScalarExpression],
+    groupby_exprs: &[ScalarExpression],
+    set: &[usize],
+) -> Result<Vec<Box<dyn Accumulator>>, DatabaseError> {
+    let mut accs = create_accumulators(agg_calls)?;
+    for (acc, expr) in accs.iter_mut().zip(agg_calls) {
+        if let ScalarExpression::AggCall {
+            kind: AggKind::Grouping,
+            args,
+            ..
+        } = expr
+        {
+            *acc = Box::new(GroupingAccumulator::for_set(args, groupby_exprs, set)?);
+        }
+    }
+    Ok(accs)
+}
+
+/// Evaluates the group keys of every grouping set of `tuple`, the keys outside a set are NULL and
+/// the set's position follows them so that equal keys of different sets stay apart.
+pub(crate) fn grouping_set_keys<'a>(
+    groupby_exprs: &[ScalarExpression],
+    grouping_sets: &'a [Vec<usize>],
+    tuple: &Tuple,
+) -> Result<impl Iterator<Item = (usize, Vec<DataValue>)> + 'a, DatabaseError> {
+    let values: Vec<_> = groupby_exprs
+        .iter()
+        .map(|expr| expr.eval(Some(tuple)))
+        .try_collect()?;
+    Ok(grouping_sets.iter().enumerate().map(move |(i, set)| {
+        let mut keys = vec![DataValue::Null; values.len() + 1];
+        for position in set {
+            keys[*position] = values[*position].clone();
+        }
+        keys[values.len()] = DataValue::UInt32(i as u32);
+        (i, keys)
+    }))
+}
+
+/// Feeds `tuple` to the accumulators of the calls whose `FILTER` it passes, a call with `ORDER BY`
+/// also receives its sort keys after the arguments.
 pub(crate) fn update_accumulators(
     accs: &mut [Box<dyn Accumulator>],
     agg_calls: &[ScalarExpression],
     tuple: &Tuple,
 ) -> Result<(), DatabaseError> {
     for (acc, expr) in accs.iter_mut().zip(agg_calls.iter()) {
-        let ScalarExpression::AggCall { args, .. } = expr else {
+        let ScalarExpression::AggCall {
+            kind,
+            args,
+            filter,
+            order_by,
+            ..
+        } = expr
+        else {
             unreachable!()
         };
-        if args.len() > 1 {
-            return Err(DatabaseError::UnsupportedStmt(
-                "currently aggregate functions only support a single Column as a parameter"
-                    .to_string(),
-            ));
+        if matches!(kind, AggKind::Grouping) {
+            continue;
+        }
+        if let Some(filter) = filter {
+            if !matches!(filter.eval(Some(tuple))?, DataValue::Boolean(true)) {
+                continue;
+            }
+        }
+        match args.as_slice() {
+            [arg] if order_by.is_empty() => acc.update_value(&arg.eval(Some(tuple))?)?,
+            _ => {
+                let values: Vec<_> = args
+                    .iter()
+                    .chain(order_by.iter().map(|field| &field.expr))
+                    .map(|expr| expr.eval(Some(tuple)))
+                    .try_collect()?;
+                acc.update_values(&values)?;
+            }
         }
-        let value = args[0].eval(Some(tuple))?;
-        acc.update_value(&value)?;
     }
     Ok(())
 }
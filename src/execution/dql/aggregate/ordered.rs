@@ -0,0 +1,106 @@
+// Copyright 2024 KipData/KiteSQL
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::errors::DatabaseError;
+use crate::execution::dql::aggregate::Accumulator;
+use crate::execution::dql::sort::compare_sort_keys;
+use crate::planner::operator::sort::SortField;
+use crate::types::value::DataValue;
+use std::slice;
+
+/// Buffers the rows of a call with `ORDER BY`, each row being the arguments followed by the sort
+/// keys, and feeds them to the wrapped accumulator in that order when the result is evaluated.
+pub struct OrderedAccumulator {
+    sort_fields: Vec<SortField>,
+    arg_len: usize,
+    rows: Vec<Vec<DataValue>>,
+    inner: Box<dyn Accumulator>,
+}
+
+impl OrderedAccumulator {
+    pub fn new(inner: Box<dyn Accumulator>, arg_len: usize, sort_fields: Vec<SortField>) -> Self {
+        Self {
+            sort_fields,
+            arg_len,
+            rows: Vec::new(),
+            inner,
+        }
+    }
+}
+
+impl Accumulator for OrderedAccumulator {
+    fn update_value(&mut self, value: &DataValue) -> Result<(), DatabaseError> {
+        self.update_values(slice::from_ref(value))
+    }
+
+    fn update_values(&mut self, values: &[DataValue]) -> Result<(), DatabaseError> {
+        self.rows.push(values.to_vec());
+        Ok(())
+    }
+
+    fn evaluate(&mut self) -> Result<(), DatabaseError> {
+        let arg_len = self.arg_len;
+        self.rows.sort_by(|row_1, row_2| {
+            compare_sort_keys(
+                &self.sort_fields,
+                row_1[arg_len..].iter(),
+                row_2[arg_len..].iter(),
+            )
+        });
+        for row in self.rows.drain(..) {
+            if let [value] = &row[..arg_len] {
+                self.inner.update_value(value)?;
+            } else {
+                self.inner.update_values(&row[..arg_len])?;
+            }
+        }
+        self.inner.evaluate()
+    }
+
+    fn result(&self) -> &DataValue {
+        self.inner.result()
+    }
+
+    fn result_owned(self: Box<Self>) -> DataValue {
+        self.inner.result_owned()
+    }
+}
+
+// GRCOV_EXCL_START
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+    use super::*;
+    use crate::execution::dql::aggregate::string_agg::StringAggAccumulator;
+    use crate::expression::ScalarExpression;
+
+    #[test]
+    fn feeds_rows_in_order() -> Result<(), DatabaseError> {
+        let mut accumulator = OrderedAccumulator::new(
+            Box::new(StringAggAccumulator::new()),
+            2,
+            vec![SortField::new(ScalarExpression::Empty, false, false)],
+        );
+        for (value, key) in [("a", 1), ("b", 3), ("c", 2)] {
+            accumulator.update_values(&[
+                DataValue::from(value.to_string()),
+                DataValue::from(",".to_string()),
+                key.into(),
+            ])?;
+        }
+        accumulator.evaluate()?;
+        assert_eq!(accumulator.result(), &DataValue::from("b,c,a".to_string()));
+        Ok(())
+    }
+}
+// GRCOV_EXCL_STOP
@@ -13,7 +13,7 @@
 // limitations under the License.
 
 use crate::errors::DatabaseError;
-use crate::execution::dql::aggregate::create_accumulators;
+use crate::execution::dql::aggregate::{create_accumulators, update_accumulators};
 use crate::execution::{
     build_read, ExecArena, ExecId, ExecNode, ExecutionContext, ExecutorNode, ReadExecutor,
 };
@@ -60,21 +60,7 @@ impl<'a, T: Transaction + 'a> ExecutorNode<'a, T> for SimpleAggExecutor {
         let mut accs = create_accumulators(&self.agg_calls)?;
 
         while arena.next_tuple(self.input, plan_arena)? {
-            let tuple = arena.result_tuple();
-            for (acc, expr) in accs.iter_mut().zip(self.agg_calls.iter()) {
-                let ScalarExpression::AggCall { args, .. } = expr else {
-                    unreachable!()
-                };
-                if args.len() > 1 {
-                    return Err(DatabaseError::UnsupportedStmt(
-                        "currently aggregate functions only support a single Column as a parameter"
-                            .to_string(),
-                    ));
-                }
-
-                let value = args[0].eval(Some(tuple))?;
-                acc.update_value(&value)?;
-            }
+            update_accumulators(&mut accs, &self.agg_calls, arena.result_tuple())?;
         }
 
         let output = arena.result_tuple_mut();
@@ -0,0 +1,290 @@
+// Copyright 2024 KipData/KiteSQL
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::errors::DatabaseError;
+use crate::execution::dql::aggregate::{
+    create_grouping_set_accumulators, grouping_set_keys, update_accumulators,
+    write_aggregate_output, Accumulator,
+};
+use crate::execution::dql::external_sort::sort_rows;
+use crate::execution::spill::{SortRow, SpillReader};
+use crate::execution::{
+    build_read, ExecArena, ExecId, ExecNode, ExecutionContext, ExecutorNode, ReadExecutor,
+};
+use crate::expression::ScalarExpression;
+use crate::planner::operator::aggregate::AggregateOperator;
+use crate::planner::operator::sort::SortField;
+use crate::planner::LogicalPlan;
+use crate::storage::Transaction;
+use crate::types::tuple::Tuple;
+use crate::types::value::DataValue;
+use std::mem;
+
+type Group = (Vec<DataValue>, Vec<Box<dyn Accumulator>>);
+
+// A grouping set only streams over input sorted by its own keys, and one sort order cannot serve
+// all sets of a CUBE. Every input row is therefore expanded once per set into the external sort,
+// keyed by the set's group keys and position, and the sorted rows aggregate like a stream.
+pub struct SortGroupingSetsExecutor {
+    agg_calls: Vec<ScalarExpression>,
+    groupby_exprs: Vec<ScalarExpression>,
+    grouping_sets: Vec<Vec<usize>>,
+    rows: Option<SpillReader<SortRow>>,
+    group: Option<Group>,
+    empty_sets: Vec<usize>,
+    input: ExecId,
+}
+
+impl<'a, T: Transaction + 'a> ReadExecutor<'a, T> for SortGroupingSetsExecutor {
+    type Input = (AggregateOperator, LogicalPlan);
+
+    fn into_executor(
+        (
+            AggregateOperator {
+                agg_calls,
+                groupby_exprs,
+                grouping_sets,
+                ..
+            },
+            input,
+        ): Self::Input,
+        arena: &mut ExecArena<'a, T>,
+        plan_arena: &mut crate::planner::PlanArena<'a>,
+        cache: ExecutionContext<'_>,
+        transaction: &T,
+    ) -> ExecId {
+        let input = build_read(arena, plan_arena, input, cache, transaction);
+        arena.push(ExecNode::SortGroupingSets(SortGroupingSetsExecutor {
+            agg_calls,
+            groupby_exprs,
+            grouping_sets,
+            rows: None,
+            group: None,
+            empty_sets: Vec::new(),
+            input,
+        }))
+    }
+}
+
+impl SortGroupingSetsExecutor {
+    fn sort_input<'a, T: Transaction + 'a>(
+        &self,
+        arena: &mut ExecArena<'a, T>,
+        plan_arena: &mut crate::planner::PlanArena<'a>,
+    ) -> Result<(SpillReader<SortRow>, bool), DatabaseError> {
+        // Only the directions of the fields are read, the sort values are the expanded keys
+        let sort_fields = self
+            .groupby_exprs
+            .iter()
+            .cloned()
+            .chain([ScalarExpression::Constant(DataValue::Null)])
+            .map(|expr| SortField::new(expr, true, false))
+            .collect::<Vec<_>>();
+        let mut expanded = Vec::with_capacity(self.grouping_sets.len());
+        let mut is_empty = true;
+        let rows = sort_rows(&sort_fields, || loop {
+            if let Some(row) = expanded.pop() {
+                return Ok(Some(row));
+            }
+            if !arena.next_tuple(self.input, plan_arena)? {
+                return Ok(None);
+            }
+            is_empty = false;
+            let tuple = mem::take(arena.result_tuple_mut());
+            for (_, sort_values) in
+                grouping_set_keys(&self.groupby_exprs, &self.grouping_sets, &tuple)?
+            {
+                expanded.push(SortRow {
+                    sort_values,
+                    tuple: tuple.clone(),
+                });
+            }
+        })?;
+        Ok((rows, is_empty))
+    }
+}
+
+fn write_group(output: &mut Tuple, (mut group_keys, accs): Group) -> Result<(), DatabaseError> {
+    group_keys.pop();
+    write_aggregate_output(output, accs, group_keys)
+}
+
+impl<'a, T: Transaction + 'a> ExecutorNode<'a, T> for SortGroupingSetsExecutor {
+    fn next_tuple(
+        &mut self,
+        arena: &mut ExecArena<'a, T>,
+        plan_arena: &mut crate::planner::PlanArena<'a>,
+    ) -> Result<(), DatabaseError> {
+        if self.rows.is_none() {
+            let (rows, is_empty) = self.sort_input(arena, plan_arena)?;
+            // Like an aggregate without GROUP BY, an empty grouping set has one group even when
+            // there are no rows
+            if is_empty {
+                self.empty_sets = (0..self.grouping_sets.len())
+                    .rev()
+                    .filter(|i| self.grouping_sets[*i].is_empty())
+                    .collect();
+            }
+            self.rows = Some(rows);
+        }
+        let Some(rows) = self.rows.as_mut() else {
+            unreachable!("grouping set rows are sorted above")
+        };
+
+        loop {
+            let Some(SortRow { sort_values, tuple }) = rows.next().transpose()? else {
+                if let Some(group) = self.group.take() {
+                    write_group(arena.result_tuple_mut(), group)?;
+                } else if let Some(i) = self.empty_sets.pop() {
+                    let mut group_keys = vec![DataValue::Null; self.groupby_exprs.len() + 1];
+                    group_keys[self.groupby_exprs.len()] = DataValue::UInt32(i as u32);
+                    let accs = create_grouping_set_accumulators(
+                        &self.agg_calls,
+                        &self.groupby_exprs,
+                        &self.grouping_sets[i],
+                    )?;
+                    write_group(arena.result_tuple_mut(), (group_keys, accs))?;
+                } else {
+                    arena.finish();
+                    return Ok(());
+                }
+                arena.resume();
+                return Ok(());
+            };
+
+            if let Some((group_keys, accs)) = &mut self.group {
+                if group_keys == &sort_values {
+                    update_accumulators(accs, &self.agg_calls, &tuple)?;
+                    continue;
+                }
+            }
+            let Some(DataValue::UInt32(i)) = sort_values.last() else {
+                unreachable!("expanded rows end with their grouping set")
+            };
+            let mut accs = create_grouping_set_accumulators(
+                &self.agg_calls,
+                &self.groupby_exprs,
+                &self.grouping_sets[*i as usize],
+            )?;
+            update_accumulators(&mut accs, &self.agg_calls, &tuple)?;
+            if let Some(group) = self.group.replace((sort_values, accs)) {
+                write_group(arena.result_tuple_mut(), group)?;
+                arena.resume();
+                return Ok(());
+            }
+        }
+    }
+}
+
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+    use super::SortGroupingSetsExecutor;
+    use crate::catalog::{ColumnCatalog, ColumnDesc};
+    use crate::errors::DatabaseError;
+    use crate::execution::{empty_context, execute_input, try_collect};
+    use crate::expression::agg::AggKind;
+    use crate::expression::ScalarExpression;
+    use crate::planner::operator::aggregate::AggregateOperator;
+    use crate::planner::operator::values::ValuesOperator;
+    use crate::planner::operator::Operator;
+    use crate::planner::{Childrens, LogicalPlan};
+    use crate::storage::memory::MemoryStorage;
+    use crate::storage::Storage;
+    use crate::types::value::DataValue;
+    use crate::types::LogicalType;
+
+    fn rollup_rows(rows: Vec<Vec<DataValue>>) -> Result<Vec<Vec<DataValue>>, DatabaseError> {
+        let table_arena = crate::planner::TableArenaCell::default();
+        let mut plan_arena = crate::planner::PlanArena::new(&table_arena);
+        let desc = ColumnDesc::new(LogicalType::Integer, None, false, None)?;
+        let columns = ["group", "value"]
+            .map(|name| {
+                plan_arena.alloc_column(ColumnCatalog::new(name.to_string(), true, desc.clone()))
+            })
+            .to_vec();
+        let input = LogicalPlan::new(
+            Operator::Values(ValuesOperator {
+                rows,
+                schema_ref: columns.clone(),
+            }),
+            Childrens::None,
+        );
+        let group = ScalarExpression::column_expr(columns[0], 0);
+        let operator = AggregateOperator {
+            groupby_exprs: vec![group.clone()],
+            grouping_sets: vec![vec![0], vec![]],
+            agg_calls: vec![
+                ScalarExpression::AggCall {
+                    distinct: false,
+                    kind: AggKind::Sum,
+                    args: vec![ScalarExpression::column_expr(columns[1], 1)],
+                    filter: None,
+                    order_by: Vec::new(),
+                    ty: LogicalType::Integer,
+                },
+                ScalarExpression::AggCall {
+                    distinct: false,
+                    kind: AggKind::Grouping,
+                    args: vec![group],
+                    filter: None,
+                    order_by: Vec::new(),
+                    ty: LogicalType::Integer,
+                },
+            ],
+            is_distinct: false,
+            force_spill: true,
+        };
+        let table_cache = crate::storage::TableCache::default();
+        let view_cache = crate::storage::ViewCache::default();
+        let meta_cache = crate::storage::StatisticsMetaCache::default();
+        let storage = MemoryStorage::new();
+        let transaction = storage.transaction()?;
+
+        let rows = try_collect(execute_input::<_, SortGroupingSetsExecutor>(
+            (operator, input),
+            empty_context(&table_cache, &view_cache, &meta_cache),
+            plan_arena,
+            &transaction,
+        ))?;
+        Ok(rows.into_iter().map(|row| row.values).collect())
+    }
+
+    #[test]
+    fn aggregates_spilled_grouping_sets() -> Result<(), DatabaseError> {
+        // Three rows per input row outgrow a single in-memory spill segment
+        let rows = (0..3000)
+            .map(|i| vec![DataValue::Int32(i % 3), DataValue::Int32(1)])
+            .collect();
+
+        assert_eq!(
+            rollup_rows(rows)?,
+            vec![
+                vec![1000.into(), 0.into(), 0.into()],
+                vec![1000.into(), 0.into(), 1.into()],
+                vec![1000.into(), 0.into(), 2.into()],
+                vec![3000.into(), 1.into(), DataValue::Null],
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn empty_input_returns_empty_grouping_sets() -> Result<(), DatabaseError> {
+        assert_eq!(
+            rollup_rows(Vec::new())?,
+            vec![vec![DataValue::Null, 1.into(), DataValue::Null]]
+        );
+        Ok(())
+    }
+}
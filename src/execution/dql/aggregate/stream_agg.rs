@@ -156,17 +156,22 @@ mod tests {
         let value = ScalarExpression::column_expr(columns[1], 1);
         let operator = AggregateOperator {
             groupby_exprs: vec![ScalarExpression::column_expr(columns[0], 0)],
+            grouping_sets: Vec::new(),
             agg_calls: vec![
                 ScalarExpression::AggCall {
                     distinct: false,
                     kind: AggKind::Sum,
                     args: vec![value.clone()],
+                    filter: None,
+                    order_by: Vec::new(),
                     ty: LogicalType::Integer,
                 },
                 ScalarExpression::AggCall {
                     distinct: false,
                     kind: AggKind::Count,
                     args: vec![value],
+                    filter: None,
+                    order_by: Vec::new(),
                     ty: LogicalType::Integer,
                 },
             ],
@@ -214,6 +219,7 @@ mod tests {
         );
         let operator = AggregateOperator {
             groupby_exprs: vec![ScalarExpression::column_expr(column, 0)],
+            grouping_sets: Vec::new(),
             agg_calls: Vec::new(),
             is_distinct: false,
             force_spill: false,
@@ -162,6 +162,7 @@ mod tests {
         );
         let agg = AggregateOperator {
             groupby_exprs: vec![ScalarExpression::column_expr(schema_ref[0], 0)],
+            grouping_sets: Vec::new(),
             agg_calls: vec![],
             is_distinct: true,
             force_spill: false,
@@ -219,6 +220,7 @@ mod tests {
                 ScalarExpression::column_expr(schema_ref[0], 0),
                 ScalarExpression::column_expr(schema_ref[1], 1),
             ],
+            grouping_sets: Vec::new(),
             agg_calls: vec![],
             is_distinct: true,
             force_spill: false,
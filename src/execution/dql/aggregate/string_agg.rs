@@ -0,0 +1,103 @@
+// Copyright 2024 KipData/KiteSQL
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::errors::DatabaseError;
+use crate::execution::dql::aggregate::Accumulator;
+use crate::types::value::DataValue;
+
+/// `STRING_AGG(value, delimiter)` joins the non-NULL values, each preceded by the delimiter of
+/// its own row except for the first one.
+pub struct StringAggAccumulator {
+    result: DataValue,
+}
+
+impl StringAggAccumulator {
+    pub fn new() -> Self {
+        Self {
+            result: DataValue::Null,
+        }
+    }
+}
+
+impl Accumulator for StringAggAccumulator {
+    fn update_value(&mut self, _value: &DataValue) -> Result<(), DatabaseError> {
+        Err(DatabaseError::MisMatch(
+            "number of string_agg() parameters",
+            "2",
+        ))
+    }
+
+    fn update_values(&mut self, values: &[DataValue]) -> Result<(), DatabaseError> {
+        let [value, delimiter] = values else {
+            return Err(DatabaseError::MisMatch(
+                "number of string_agg() parameters",
+                "2",
+            ));
+        };
+        let Some(value) = value.utf8() else {
+            return Ok(());
+        };
+        match &mut self.result {
+            DataValue::Utf8 { value: result, .. } => {
+                if let Some(delimiter) = delimiter.utf8() {
+                    result.push_str(delimiter);
+                }
+                result.push_str(value);
+            }
+            result => *result = DataValue::from(value.to_string()),
+        }
+
+        Ok(())
+    }
+
+    fn result(&self) -> &DataValue {
+        &self.result
+    }
+
+    fn result_owned(self: Box<Self>) -> DataValue {
+        self.result
+    }
+}
+
+// GRCOV_EXCL_START
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn string_agg_results() -> Result<(), DatabaseError> {
+        let mut accumulator = StringAggAccumulator::new();
+        assert_eq!(accumulator.result(), &DataValue::Null);
+        for (value, delimiter) in [
+            (
+                DataValue::from("a".to_string()),
+                DataValue::from(",".to_string()),
+            ),
+            (DataValue::Null, DataValue::from(",".to_string())),
+            (
+                DataValue::from("b".to_string()),
+                DataValue::from(";".to_string()),
+            ),
+            (DataValue::from("c".to_string()), DataValue::Null),
+        ] {
+            accumulator.update_values(&[value, delimiter])?;
+        }
+        assert_eq!(
+            Box::new(accumulator).result_owned(),
+            DataValue::from("a;bc".to_string())
+        );
+        Ok(())
+    }
+}
+// GRCOV_EXCL_STOP
@@ -19,7 +19,6 @@ use crate::types::evaluator::{binary_create, BinaryEvaluatorRef};
 use crate::types::value::DataValue;
 use crate::types::LogicalType;
 use std::borrow::Cow;
-use std::collections::HashSet;
 
 pub struct SumAccumulator {
     result: DataValue,
@@ -59,39 +58,6 @@ impl Accumulator for SumAccumulator {
     }
 }
 
-pub struct DistinctSumAccumulator {
-    distinct_values: HashSet<DataValue>,
-    inner: SumAccumulator,
-}
-
-impl DistinctSumAccumulator {
-    pub fn new(ty: &LogicalType) -> Result<Self, DatabaseError> {
-        Ok(Self {
-            distinct_values: HashSet::default(),
-            inner: SumAccumulator::new(Cow::Borrowed(ty))?,
-        })
-    }
-}
-
-impl Accumulator for DistinctSumAccumulator {
-    fn update_value(&mut self, value: &DataValue) -> Result<(), DatabaseError> {
-        if !self.distinct_values.contains(value) {
-            self.distinct_values.insert(value.clone());
-            self.inner.update_value(value)?;
-        }
-
-        Ok(())
-    }
-
-    fn result(&self) -> &DataValue {
-        self.inner.result()
-    }
-
-    fn result_owned(self: Box<Self>) -> DataValue {
-        self.inner.result
-    }
-}
-
 // GRCOV_EXCL_START
 #[cfg(all(test, not(target_arch = "wasm32")))]
 mod tests {
@@ -107,16 +73,5 @@ mod tests {
         assert_eq!(Box::new(accumulator).result_owned(), DataValue::Int32(5));
         Ok(())
     }
-
-    #[test]
-    fn distinct_sum_results() -> Result<(), DatabaseError> {
-        let mut accumulator = DistinctSumAccumulator::new(&LogicalType::Integer)?;
-        for value in [DataValue::Null, 2.into(), 2.into(), 3.into()] {
-            accumulator.update_value(&value)?;
-        }
-        assert_eq!(accumulator.result(), &DataValue::Int32(5));
-        assert_eq!(Box::new(accumulator).result_owned(), DataValue::Int32(5));
-        Ok(())
-    }
 }
 // GRCOV_EXCL_STOP
@@ -0,0 +1,60 @@
+// Copyright 2024 KipData/KiteSQL
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::errors::DatabaseError;
+use crate::execution::dql::aggregate::Accumulator;
+use crate::expression::function::aggregate::ArcAggregateFunctionImpl;
+use crate::types::value::DataValue;
+use std::slice;
+
+pub struct UserDefinedAccumulator {
+    function: ArcAggregateFunctionImpl,
+    state: DataValue,
+    result: DataValue,
+}
+
+impl UserDefinedAccumulator {
+    pub fn new(function: ArcAggregateFunctionImpl) -> Result<Self, DatabaseError> {
+        let state = function.init()?;
+
+        Ok(Self {
+            function,
+            state,
+            result: DataValue::Null,
+        })
+    }
+}
+
+impl Accumulator for UserDefinedAccumulator {
+    fn update_value(&mut self, value: &DataValue) -> Result<(), DatabaseError> {
+        self.update_values(slice::from_ref(value))
+    }
+
+    fn update_values(&mut self, values: &[DataValue]) -> Result<(), DatabaseError> {
+        self.function.update(&mut self.state, values)
+    }
+
+    fn evaluate(&mut self) -> Result<(), DatabaseError> {
+        self.result = self.function.finalize(&self.state)?;
+        Ok(())
+    }
+
+    fn result(&self) -> &DataValue {
+        &self.result
+    }
+
+    fn result_owned(self: Box<Self>) -> DataValue {
+        self.result
+    }
+}
@@ -0,0 +1,112 @@
+// Copyright 2024 KipData/KiteSQL
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::errors::DatabaseError;
+use crate::execution::dql::aggregate::Accumulator;
+use crate::types::value::DataValue;
+
+/// `VAR_POP`, `VAR_SAMP`, `STDDEV_POP` and `STDDEV_SAMP` over DOUBLE values, using Welford's
+/// online algorithm so that the state stays numerically stable.
+pub struct VarianceAccumulator {
+    count: u64,
+    mean: f64,
+    m2: f64,
+    sample: bool,
+    stddev: bool,
+    result: DataValue,
+}
+
+impl VarianceAccumulator {
+    pub fn new(sample: bool, stddev: bool) -> Self {
+        Self {
+            count: 0,
+            mean: 0.0,
+            m2: 0.0,
+            sample,
+            stddev,
+            result: DataValue::Null,
+        }
+    }
+}
+
+impl Accumulator for VarianceAccumulator {
+    fn update_value(&mut self, value: &DataValue) -> Result<(), DatabaseError> {
+        let value = match value {
+            DataValue::Null => return Ok(()),
+            DataValue::Float64(value) => value.0,
+            _ => return Err(DatabaseError::InvalidType),
+        };
+        self.count += 1;
+        let delta = value - self.mean;
+        self.mean += delta / self.count as f64;
+        self.m2 += delta * (value - self.mean);
+
+        Ok(())
+    }
+
+    fn evaluate(&mut self) -> Result<(), DatabaseError> {
+        let divisor = if self.sample {
+            self.count.saturating_sub(1)
+        } else {
+            self.count
+        };
+        self.result = if divisor == 0 {
+            DataValue::Null
+        } else {
+            let variance = self.m2 / divisor as f64;
+            DataValue::from(if self.stddev {
+                variance.sqrt()
+            } else {
+                variance
+            })
+        };
+        Ok(())
+    }
+
+    fn result(&self) -> &DataValue {
+        &self.result
+    }
+
+    fn result_owned(self: Box<Self>) -> DataValue {
+        self.result
+    }
+}
+
+// GRCOV_EXCL_START
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+    use super::*;
+
+    fn variance(sample: bool, stddev: bool, values: &[f64]) -> Result<DataValue, DatabaseError> {
+        let mut accumulator = VarianceAccumulator::new(sample, stddev);
+        for value in values {
+            accumulator.update_value(&DataValue::from(*value))?;
+        }
+        accumulator.update_value(&DataValue::Null)?;
+        accumulator.evaluate()?;
+        Ok(Box::new(accumulator).result_owned())
+    }
+
+    #[test]
+    fn variance_results() -> Result<(), DatabaseError> {
+        let values = [2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0];
+        assert_eq!(variance(false, false, &values)?, DataValue::from(4.0));
+        assert_eq!(variance(false, true, &values)?, DataValue::from(2.0));
+        assert_eq!(variance(true, false, &values)?, DataValue::from(32.0 / 7.0));
+        assert_eq!(variance(true, false, &[1.0])?, DataValue::Null);
+        assert_eq!(variance(false, false, &[])?, DataValue::Null);
+        Ok(())
+    }
+}
+// GRCOV_EXCL_STOP
@@ -0,0 +1,70 @@
+// Copyright 2024 KipData/KiteSQL
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use crate::errors::DatabaseError;
+use crate::execution::{ExecArena, ExecId, ExecNode, ExecutionContext, ExecutorNode, ReadExecutor};
+use crate::planner::operator::cte_scan::CteScanOperator;
+use crate::storage::Transaction;
+use crate::types::tuple::Tuple;
+use std::sync::Arc;
+
+pub struct CteScan {
+    op: CteScanOperator,
+    tuples: Arc<Vec<Tuple>>,
+    position: usize,
+}
+
+impl From<CteScanOperator> for CteScan {
+    fn from(op: CteScanOperator) -> Self {
+        CteScan {
+            op,
+            tuples: Arc::new(Vec::new()),
+            position: 0,
+        }
+    }
+}
+
+impl<'a, T: Transaction + 'a> ReadExecutor<'a, T> for CteScan {
+    type Input = Self;
+
+    fn into_executor(
+        input: Self::Input,
+        arena: &mut ExecArena<'a, T>,
+        _plan_arena: &mut crate::planner::PlanArena<'a>,
+        _: ExecutionContext<'_>,
+        _: &T,
+    ) -> ExecId {
+        let mut executor = input;
+        // Tips: snapshot the working table so later iterations cannot change rows under this scan
+        if let Some(tuples) = arena.cte_table(&executor.op.table_name) {
+            executor.tuples = tuples;
+        }
+        arena.push(ExecNode::CteScan(executor))
+    }
+}
+
+impl<'a, T: Transaction + 'a> ExecutorNode<'a, T> for CteScan {
+    fn next_tuple(
+        &mut self,
+        arena: &mut ExecArena<'a, T>,
+        _plan_arena: &mut crate::planner::PlanArena<'a>,
+    ) -> Result<(), DatabaseError> {
+        let Some(tuple) = self.tuples.get(self.position) else {
+            arena.finish();
+            return Ok(());
+        };
+        self.position += 1;
+        arena.produce_tuple(tuple.clone());
+        Ok(())
+    }
+}
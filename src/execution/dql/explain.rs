@@ -13,21 +13,47 @@
 // limitations under the License.
 
 use crate::errors::DatabaseError;
+use crate::execution::profile::ExecProfile;
 use crate::execution::{ExecArena, ExecId, ExecNode, ExecutionContext, ExecutorNode, ReadExecutor};
-use crate::planner::LogicalPlan;
-use crate::storage::Transaction;
+use crate::optimizer::core::cardinality::CardinalityEstimator;
+use crate::optimizer::core::statistics_meta::StatisticMetaLoader;
+use crate::planner::explain::{ExplainActual, ExplainNode};
+use crate::planner::operator::explain::{ExplainFormat, ExplainOperator};
+use crate::planner::operator::Operator;
+use crate::planner::{LogicalPlan, PlanArena};
+use crate::storage::{StatisticsMetaCache, Transaction};
 use crate::types::value::{DataValue, Utf8Type};
 use crate::types::CharLengthUnits;
+use std::collections::HashMap;
+use std::fmt::Write;
 
 pub struct Explain {
+    format: ExplainFormat,
     plan: LogicalPlan,
+    /// The executor of `plan` when it is run by `EXPLAIN ANALYZE`.
+    input: Option<ExecId>,
     emitted: bool,
 }
 
-impl From<LogicalPlan> for Explain {
-    fn from(plan: LogicalPlan) -> Self {
+impl From<(ExplainOperator, LogicalPlan)> for Explain {
+    fn from((op, plan): (ExplainOperator, LogicalPlan)) -> Self {
         Explain {
+            format: op.format,
             plan,
+            input: None,
+            emitted: false,
+        }
+    }
+}
+
+impl Explain {
+    /// `plan` must be numbered by [`LogicalPlan::assign_explain_ids`] and `input` built from it
+    /// with profiling enabled.
+    pub(crate) fn analyze(op: ExplainOperator, plan: LogicalPlan, input: ExecId) -> Self {
+        Explain {
+            format: op.format,
+            plan,
+            input: Some(input),
             emitted: false,
         }
     }
@@ -39,7 +65,7 @@ impl<'a, T: Transaction + 'a> ReadExecutor<'a, T> for Explain {
     fn into_executor(
         input: Self::Input,
         arena: &mut ExecArena<'a, T>,
-        _plan_arena: &mut crate::planner::PlanArena<'a>,
+        _plan_arena: &mut PlanArena<'a>,
         _: ExecutionContext<'_>,
         _: &T,
     ) -> ExecId {
@@ -52,14 +78,36 @@ impl<'a, T: Transaction + 'a> ExecutorNode<'a, T> for Explain {
     fn next_tuple(
         &mut self,
         arena: &mut ExecArena<'a, T>,
-        plan_arena: &mut crate::planner::PlanArena<'a>,
+        plan_arena: &mut PlanArena<'a>,
     ) -> Result<(), DatabaseError> {
         if self.emitted {
             arena.finish();
             return Ok(());
         }
 
-        let plan = self.plan.explain(plan_arena, 0);
+        if let Some(input) = self.input {
+            while arena.next_tuple(input, plan_arena)? {}
+        }
+        let plan = match self.format {
+            ExplainFormat::Json => {
+                let loader = StatisticMetaLoader::new(arena.context().meta_cache());
+                let mut node = self
+                    .plan
+                    .explain_node(plan_arena, &CardinalityEstimator::new(&loader))?;
+                if let Some(profile) = self.input.and(arena.profile()) {
+                    attach_actual(&mut node, &self.plan, profile);
+                }
+                node.to_json()
+            }
+            ExplainFormat::Text if self.input.is_some() => {
+                let estimates =
+                    estimate_rows(&self.plan, arena.context().meta_cache(), plan_arena)?;
+                let profile = arena.profile().expect("explain analyze profile enabled");
+                self.plan
+                    .explain_with(plan_arena, 0, &|plan| annotate(plan, profile, &estimates))
+            }
+            ExplainFormat::Text => self.plan.explain(plan_arena, 0),
+        };
         let output = arena.result_tuple_mut();
         output.pk = None;
         output.values.clear();
@@ -74,3 +122,88 @@ impl<'a, T: Transaction + 'a> ExecutorNode<'a, T> for Explain {
         Ok(())
     }
 }
+
+/// Row counts the optimizer expects from each operator, keyed by its explain id.
+fn estimate_rows(
+    plan: &LogicalPlan,
+    meta_cache: &StatisticsMetaCache,
+    plan_arena: &PlanArena,
+) -> Result<HashMap<usize, f64>, DatabaseError> {
+    fn collect(
+        plan: &LogicalPlan,
+        estimator: &CardinalityEstimator,
+        plan_arena: &PlanArena,
+        estimates: &mut HashMap<usize, f64>,
+    ) -> Result<(), DatabaseError> {
+        if let (Some(explain_id), Some(rows)) =
+            (plan.explain_id, estimator.plan_rows(plan, plan_arena)?)
+        {
+            estimates.insert(explain_id, rows);
+        }
+        for child in plan.childrens.iter() {
+            collect(child, estimator, plan_arena, estimates)?;
+        }
+        Ok(())
+    }
+
+    let loader = StatisticMetaLoader::new(meta_cache);
+    let estimator = CardinalityEstimator::new(&loader);
+    let mut estimates = HashMap::new();
+    collect(plan, &estimator, plan_arena, &mut estimates)?;
+
+    Ok(estimates)
+}
+
+/// Copies the runtime statistics of every operator of `plan` onto its node.
+fn attach_actual(node: &mut ExplainNode, plan: &LogicalPlan, profile: &ExecProfile) {
+    if let Some(stats) = plan
+        .explain_id
+        .and_then(|explain_id| profile.stats(explain_id))
+    {
+        node.actual = Some(ExplainActual {
+            rows: stats.rows,
+            loops: stats.loops,
+            elapsed: stats.elapsed,
+            keys_read: matches!(plan.operator, Operator::TableScan(_)).then_some(stats.keys_read),
+        });
+    }
+    for (child_node, child) in node.children.iter_mut().zip(plan.childrens.iter()) {
+        attach_actual(child_node, child, profile);
+    }
+}
+
+fn annotate(
+    plan: &LogicalPlan,
+    profile: &ExecProfile,
+    estimates: &HashMap<usize, f64>,
+) -> Option<String> {
+    let explain_id = plan.explain_id?;
+    let mut annotation = String::new();
+
+    if let Some(rows) = estimates.get(&explain_id) {
+        let _ = write!(annotation, "estimated rows: {rows:.0}, ");
+    }
+    let stats = profile.stats(explain_id).filter(|stats| stats.loops > 0);
+    let Some(stats) = stats else {
+        annotation.push_str("never executed");
+        return Some(annotation);
+    };
+    let _ = write!(
+        annotation,
+        "actual rows: {}, loops: {}",
+        stats.rows, stats.loops
+    );
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        let _ = write!(
+            annotation,
+            ", time: {:.3}ms",
+            stats.elapsed.as_secs_f64() * 1000.0
+        );
+    }
+    if matches!(plan.operator, Operator::TableScan(_)) {
+        let _ = write!(annotation, ", keys read: {}", stats.keys_read);
+    }
+
+    Some(annotation)
+}
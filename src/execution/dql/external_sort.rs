@@ -89,17 +89,32 @@ impl<'a, T: Transaction + 'a> ExecutorNode<'a, T> for ExternalSort {
             // pass. By default R <= 1,024 and B is about 1 MiB; one oversized row makes this a
             // soft bound.
             let sort_fields = &self.sort_fields;
-            let mut rows = SpillVec::new().on_flush(move |rows| sort_segment(sort_fields, rows));
-            let mut runs = Vec::new();
-            while arena.next_tuple(self.input, plan_arena)? {
-                let tuple = mem::take(arena.result_tuple_mut());
-                if let Some(segment) = rows.push(SortRow::new(sort_fields, tuple)?)? {
-                    runs.push(Run::new(segment, 1));
+            let input = self.input;
+            self.rows = Some(sort_rows(sort_fields, || {
+                if !arena.next_tuple(input, plan_arena)? {
+                    return Ok(None);
                 }
-            }
-            self.rows = Some(finish_sort(rows, runs, &self.sort_fields, MERGE_FAN_IN)?);
+                let tuple = mem::take(arena.result_tuple_mut());
+                SortRow::new(sort_fields, tuple).map(Some)
+            })?);
+        }
+    }
+}
+
+/// Sorts the rows pulled from `next_row` by their `sort_values`, spilling sorted runs once they
+/// outgrow the memory budget and merging them after the last row.
+pub(crate) fn sort_rows(
+    sort_fields: &[SortField],
+    mut next_row: impl FnMut() -> Result<Option<SortRow>, DatabaseError>,
+) -> Result<SpillReader<SortRow>, DatabaseError> {
+    let mut rows = SpillVec::new().on_flush(move |rows| sort_segment(sort_fields, rows));
+    let mut runs = Vec::new();
+    while let Some(row) = next_row()? {
+        if let Some(segment) = rows.push(row)? {
+            runs.push(Run::new(segment, 1));
         }
     }
+    finish_sort(rows, runs, sort_fields, MERGE_FAN_IN)
 }
 
 #[inline]
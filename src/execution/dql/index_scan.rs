@@ -110,13 +110,11 @@ impl<'a, T: Transaction + 'a> ExecutorNode<'a, T> for IndexScan<'a, T> {
             )?);
         }
 
+        let iter = self.iter.as_mut().expect("index scan iterator initialized");
         let state = arena.local_state(plan_arena);
-        if self
-            .iter
-            .as_mut()
-            .expect("index scan iterator initialized")
-            .next_tuple_into(state.table_codec, &mut state.result.tuple)?
-        {
+        let has_next = iter.next_tuple_into(state.table_codec, &mut state.result.tuple)?;
+        arena.add_keys_read(iter.take_keys_read());
+        if has_next {
             arena.resume();
         } else {
             arena.finish();
@@ -499,6 +499,7 @@ mod test {
                 },
                 join_type: JoinType::Inner,
                 force_nested_loop: false,
+                force_merge_join: false,
             }),
             Childrens::Twins {
                 left: Box::new(left),
@@ -557,6 +558,7 @@ mod test {
                 },
                 join_type: JoinType::LeftOuter,
                 force_nested_loop: false,
+                force_merge_join: false,
             }),
             Childrens::Twins {
                 left: Box::new(left),
@@ -621,6 +623,7 @@ mod test {
                 },
                 join_type: JoinType::RightOuter,
                 force_nested_loop: false,
+                force_merge_join: false,
             }),
             Childrens::Twins {
                 left: Box::new(left),
@@ -720,6 +723,7 @@ mod test {
                 },
                 join_type: JoinType::RightOuter,
                 force_nested_loop: false,
+                force_merge_join: false,
             }),
             Childrens::Twins {
                 left: Box::new(left),
@@ -774,6 +778,7 @@ mod test {
                 },
                 join_type: JoinType::Full,
                 force_nested_loop: false,
+                force_merge_join: false,
             }),
             Childrens::Twins {
                 left: Box::new(left),
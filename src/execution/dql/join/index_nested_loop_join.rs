@@ -0,0 +1,258 @@
+// Copyright 2024 KipData/KiteSQL
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Defines the index nested loop join executor, it supports [`JoinType::Inner`] and
+//! [`JoinType::LeftOuter`].
+//!
+//! Semi and anti joins are not join types of the planner: `EXISTS`, `NOT EXISTS` and `IN`
+//! are bound to a [`MarkApply`](crate::planner::operator::mark_apply::MarkApplyOperator), which
+//! probes the same indexes through its parameterized right input instead.
+
+use std::collections::Bound;
+use std::mem;
+
+use crate::catalog::ColumnRef;
+use crate::errors::DatabaseError;
+use crate::execution::{
+    build_read, ExecArena, ExecId, ExecNode, ExecutionContext, ExecutorNode, ReadExecutor,
+};
+use crate::expression::ScalarExpression;
+use crate::planner::operator::join::{JoinCondition, JoinOperator, JoinProbe, JoinType};
+use crate::planner::operator::{Operator, PhysicalOption, PlanImpl};
+use crate::planner::{LogicalPlan, PlanArena};
+use crate::storage::Transaction;
+use crate::types::index::{IndexLookup, RuntimeIndexProbe};
+use crate::types::tuple::{SplitTupleRef, Tuple};
+use crate::types::value::DataValue;
+
+/// IndexNestedLoopJoin looks up the matching right rows of every left row through an index.
+///
+/// The right input reads its index with [`IndexLookup::Probe`], and the lookup for each left
+/// row is handed to it as a [`RuntimeIndexProbe`] before the right input is rebuilt. Equi keys
+/// and the join filter are still checked for every probed row.
+pub struct IndexNestedLoopJoin {
+    right_input_plan: LogicalPlan,
+    ty: JoinType,
+    on_left_keys: Vec<ScalarExpression>,
+    on_right_keys: Vec<ScalarExpression>,
+    filter: Option<ScalarExpression>,
+    probe: Option<JoinProbe>,
+    right_len: usize,
+    left_input: ExecId,
+    active_left: Option<ActiveLeftState>,
+}
+
+struct ActiveLeftState {
+    left_tuple: Tuple,
+    /// `None` when the probe of the left row contains `NULL`, which matches nothing.
+    right_input: Option<ExecId>,
+    /// Depth of the runtime probe stack before the probe of this left row was pushed.
+    probe_depth: Option<usize>,
+    has_matched: bool,
+}
+
+impl<'a, T: Transaction + 'a> ReadExecutor<'a, T> for IndexNestedLoopJoin {
+    type Input = (JoinOperator, LogicalPlan, LogicalPlan);
+
+    fn into_executor(
+        (op, mut left_input, mut right_input): Self::Input,
+        arena: &mut ExecArena<'a, T>,
+        plan_arena: &mut PlanArena<'a>,
+        cache: ExecutionContext<'_>,
+        transaction: &T,
+    ) -> ExecId {
+        let left_len = left_input.output_schema(plan_arena).len();
+        let right_len = right_input.output_schema(plan_arena).len();
+        let probe = probed_column(&right_input, plan_arena)
+            .and_then(|column| op.index_probe(column, left_len, plan_arena));
+        let ((on_left_keys, on_right_keys), filter) = match op.on {
+            JoinCondition::On { on, filter } => (on.into_iter().unzip(), filter),
+            JoinCondition::None => ((vec![], vec![]), None),
+        };
+        let left_input = build_read(arena, plan_arena, left_input.take(), cache, transaction);
+
+        arena.push(ExecNode::IndexNestedLoopJoin(IndexNestedLoopJoin {
+            right_input_plan: right_input,
+            ty: op.join_type,
+            on_left_keys,
+            on_right_keys,
+            filter,
+            probe,
+            right_len,
+            left_input,
+            active_left: None,
+        }))
+    }
+}
+
+impl<'a, T: Transaction + 'a> ExecutorNode<'a, T> for IndexNestedLoopJoin {
+    fn next_tuple(
+        &mut self,
+        arena: &mut ExecArena<'a, T>,
+        plan_arena: &mut PlanArena<'a>,
+    ) -> Result<(), DatabaseError> {
+        loop {
+            let Some(mut active_left) = self.active_left.take() else {
+                if !arena.next_tuple(self.left_input, plan_arena)? {
+                    arena.finish();
+                    return Ok(());
+                }
+                let left_tuple = mem::take(arena.result_tuple_mut());
+                self.active_left = Some(self.probe_right_input(arena, plan_arena, left_tuple)?);
+                continue;
+            };
+
+            if let Some(right_input) = active_left.right_input {
+                loop {
+                    let has_next = arena.next_tuple(right_input, plan_arena)?;
+                    if let Some(depth) = active_left.probe_depth.take() {
+                        // the index scan consumes its probe on the first pull
+                        while arena.runtime_probe_depth() > depth {
+                            let _ = arena.pop_runtime_probe();
+                        }
+                    }
+                    if !has_next {
+                        break;
+                    }
+                    let right_tuple = mem::take(arena.result_tuple_mut());
+                    if self.is_matched(&active_left.left_tuple, &right_tuple)? {
+                        active_left.has_matched = true;
+                        let tuple = Self::join_tuple(&active_left.left_tuple, right_tuple);
+                        self.active_left = Some(active_left);
+                        arena.produce_tuple(tuple);
+                        return Ok(());
+                    }
+                }
+            }
+
+            if matches!(self.ty, JoinType::LeftOuter) && !active_left.has_matched {
+                let right_tuple = Tuple::new(None, vec![DataValue::Null; self.right_len]);
+                arena.produce_tuple(Self::join_tuple(&active_left.left_tuple, right_tuple));
+                return Ok(());
+            }
+        }
+    }
+}
+
+impl IndexNestedLoopJoin {
+    fn probe_right_input<'a, T: Transaction + 'a>(
+        &self,
+        arena: &mut ExecArena<'a, T>,
+        plan_arena: &mut PlanArena<'a>,
+        left_tuple: Tuple,
+    ) -> Result<ActiveLeftState, DatabaseError> {
+        let probe = self.probe.as_ref().ok_or(DatabaseError::InvalidIndex)?;
+        let mut active_left = ActiveLeftState {
+            left_tuple,
+            right_input: None,
+            probe_depth: None,
+            has_matched: false,
+        };
+        let Some(runtime_probe) = Self::runtime_probe(probe, &active_left.left_tuple)? else {
+            return Ok(active_left);
+        };
+        active_left.probe_depth = Some(arena.runtime_probe_depth());
+        arena.push_runtime_probe(runtime_probe);
+
+        let cache = arena.context();
+        let transaction = arena.transaction();
+        active_left.right_input = Some(build_read(
+            arena,
+            plan_arena,
+            self.right_input_plan.clone(),
+            cache,
+            transaction,
+        ));
+        Ok(active_left)
+    }
+
+    fn runtime_probe(
+        probe: &JoinProbe,
+        left_tuple: &Tuple,
+    ) -> Result<Option<RuntimeIndexProbe>, DatabaseError> {
+        let eval_bound = |bound: &Bound<ScalarExpression>| -> Result<_, DatabaseError> {
+            Ok(match bound {
+                Bound::Included(expr) => Bound::Included(expr.eval(Some(left_tuple))?),
+                Bound::Excluded(expr) => Bound::Excluded(expr.eval(Some(left_tuple))?),
+                Bound::Unbounded => Bound::Unbounded,
+            })
+        };
+        let is_null = |bound: &Bound<DataValue>| matches!(bound, Bound::Included(value) | Bound::Excluded(value) if value.is_null());
+
+        Ok(match probe {
+            JoinProbe::Eq(expr) => {
+                let value = expr.eval(Some(left_tuple))?;
+                (!value.is_null()).then_some(RuntimeIndexProbe::Eq(value))
+            }
+            JoinProbe::Scope { min, max } => {
+                let (min, max) = (eval_bound(min)?, eval_bound(max)?);
+                (!is_null(&min) && !is_null(&max)).then_some(RuntimeIndexProbe::Scope { min, max })
+            }
+        })
+    }
+
+    fn is_matched(&self, left_tuple: &Tuple, right_tuple: &Tuple) -> Result<bool, DatabaseError> {
+        for (left_expr, right_expr) in self.on_left_keys.iter().zip(self.on_right_keys.iter()) {
+            let left_value = left_expr.eval(Some(left_tuple))?;
+            if left_value.is_null() || left_value != right_expr.eval(Some(right_tuple))? {
+                return Ok(false);
+            }
+        }
+        let Some(filter) = &self.filter else {
+            return Ok(true);
+        };
+        match filter.eval(Some(SplitTupleRef::new(left_tuple, right_tuple)))? {
+            DataValue::Boolean(true) => Ok(true),
+            DataValue::Boolean(false) | DataValue::Null => Ok(false),
+            _ => Err(DatabaseError::InvalidType),
+        }
+    }
+
+    fn join_tuple(left_tuple: &Tuple, right_tuple: Tuple) -> Tuple {
+        let mut values = Vec::with_capacity(left_tuple.values.len() + right_tuple.values.len());
+        values.extend(left_tuple.values.iter().cloned());
+        values.extend(right_tuple.values);
+        Tuple::new(left_tuple.pk.clone().or(right_tuple.pk), values)
+    }
+}
+
+/// The right column looked up by the probed index scan below `plan`.
+fn probed_column(plan: &LogicalPlan, arena: &PlanArena) -> Option<ColumnRef> {
+    match &plan.operator {
+        Operator::TableScan(scan_op) => {
+            let Some(PhysicalOption {
+                plan: PlanImpl::IndexScan(index_info),
+                ..
+            }) = &plan.physical_option
+            else {
+                return None;
+            };
+            if index_info.lookup != Some(IndexLookup::Probe) {
+                return None;
+            }
+            let column_id = *arena.index(index_info.meta).column_ids.first()?;
+            scan_op
+                .columns
+                .iter()
+                .find(|column| arena.column(**column).id() == Some(column_id))
+                .copied()
+        }
+        Operator::Filter(_) | Operator::Project(_) => plan
+            .childrens
+            .iter()
+            .next()
+            .and_then(|child| probed_column(child, arena)),
+        _ => None,
+    }
+}
@@ -16,7 +16,9 @@ use crate::planner::operator::join::JoinType;
 
 mod hash;
 pub(crate) mod hash_join;
+pub(crate) mod index_nested_loop_join;
 pub(crate) mod nested_loop_join;
+pub(crate) mod sort_merge_join;
 
 pub(crate) struct RowBitmap {
     blocks: Vec<usize>,
@@ -634,6 +634,7 @@ mod test {
                 },
                 join_type: JoinType::Inner,
                 force_nested_loop: false,
+                force_merge_join: false,
             }),
             Childrens::Twins {
                 left: Box::new(left),
@@ -687,6 +688,7 @@ mod test {
                 },
                 join_type: JoinType::LeftOuter,
                 force_nested_loop: false,
+                force_merge_join: false,
             }),
             Childrens::Twins {
                 left: Box::new(left),
@@ -769,6 +771,7 @@ mod test {
                 },
                 join_type: JoinType::Cross,
                 force_nested_loop: false,
+                force_merge_join: false,
             }),
             Childrens::Twins {
                 left: Box::new(left),
@@ -822,6 +825,7 @@ mod test {
                 },
                 join_type: JoinType::Cross,
                 force_nested_loop: false,
+                force_merge_join: false,
             }),
             Childrens::Twins {
                 left: Box::new(left),
@@ -890,6 +894,7 @@ mod test {
                 },
                 join_type: JoinType::Cross,
                 force_nested_loop: false,
+                force_merge_join: false,
             }),
             Childrens::Twins {
                 left: Box::new(left),
@@ -933,6 +938,7 @@ mod test {
                 },
                 join_type: JoinType::RightOuter,
                 force_nested_loop: false,
+                force_merge_join: false,
             }),
             Childrens::Twins {
                 left: Box::new(left),
@@ -1010,6 +1016,7 @@ mod test {
                 },
                 join_type: JoinType::Full,
                 force_nested_loop: false,
+                force_merge_join: false,
             }),
             Childrens::Twins {
                 left: Box::new(left),
@@ -1154,6 +1161,7 @@ mod test {
                 },
                 join_type: JoinType::RightOuter,
                 force_nested_loop: false,
+                force_merge_join: false,
             }),
             Childrens::Twins {
                 left: Box::new(left),
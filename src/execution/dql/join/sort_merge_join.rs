@@ -0,0 +1,311 @@
+// Copyright 2024 KipData/KiteSQL
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Defines the sort-merge join executor, it supports [`JoinType::Inner`], [`JoinType::LeftOuter`],
+//! [`JoinType::RightOuter`] and [`JoinType::Full`].
+
+use std::cmp::Ordering;
+use std::collections::VecDeque;
+use std::mem;
+
+use crate::errors::DatabaseError;
+use crate::execution::dql::sort::compare_sort_keys;
+use crate::execution::{
+    build_read, ExecArena, ExecId, ExecNode, ExecutionContext, ExecutorNode, ReadExecutor,
+};
+use crate::expression::ScalarExpression;
+use crate::planner::operator::join::{JoinCondition, JoinOperator, JoinType};
+use crate::planner::operator::sort::SortField;
+use crate::planner::{LogicalPlan, PlanArena};
+use crate::storage::Transaction;
+use crate::types::tuple::{SplitTupleRef, Tuple};
+use crate::types::value::DataValue;
+
+/// SortMergeJoin merges two inputs that are both sorted ascending, with nulls last, on their
+/// equi keys.
+///
+/// Only the right rows sharing the current key are buffered, so memory stays bounded by the
+/// largest group of equal keys instead of the whole build side of a hash join.
+pub struct SortMergeJoin {
+    ty: JoinType,
+    on_left_keys: Vec<ScalarExpression>,
+    on_right_keys: Vec<ScalarExpression>,
+    key_fields: Vec<SortField>,
+    filter: Option<ScalarExpression>,
+    left_len: usize,
+    right_len: usize,
+    left_input: ExecId,
+    right_input: ExecId,
+    left: Cursor,
+    right: Cursor,
+    group: RightGroup,
+    output: VecDeque<Tuple>,
+}
+
+/// The current row of an input together with its evaluated join keys.
+#[derive(Default)]
+struct Cursor {
+    row: Option<(Tuple, Vec<DataValue>)>,
+    is_done: bool,
+}
+
+/// The buffered right rows sharing `key`.
+#[derive(Default)]
+struct RightGroup {
+    key: Vec<DataValue>,
+    tuples: Vec<Tuple>,
+    matched: Vec<bool>,
+}
+
+impl<'a, T: Transaction + 'a> ReadExecutor<'a, T> for SortMergeJoin {
+    type Input = (JoinOperator, LogicalPlan, LogicalPlan);
+
+    fn into_executor(
+        (op, mut left_input, mut right_input): Self::Input,
+        arena: &mut ExecArena<'a, T>,
+        plan_arena: &mut PlanArena<'a>,
+        cache: ExecutionContext<'_>,
+        transaction: &T,
+    ) -> ExecId {
+        let left_len = left_input.output_schema(plan_arena).len();
+        let right_len = right_input.output_schema(plan_arena).len();
+        let ((on_left_keys, on_right_keys), filter): ((Vec<_>, Vec<_>), _) = match op.on {
+            JoinCondition::On { on, filter } => (on.into_iter().unzip(), filter),
+            JoinCondition::None => ((vec![], vec![]), None),
+        };
+        let key_fields = on_left_keys
+            .iter()
+            .cloned()
+            .map(|expr| SortField::new(expr, true, false))
+            .collect();
+        let left_input = build_read(arena, plan_arena, left_input.take(), cache, transaction);
+        let right_input = build_read(arena, plan_arena, right_input.take(), cache, transaction);
+
+        arena.push(ExecNode::SortMergeJoin(SortMergeJoin {
+            ty: op.join_type,
+            on_left_keys,
+            on_right_keys,
+            key_fields,
+            filter,
+            left_len,
+            right_len,
+            left_input,
+            right_input,
+            left: Cursor::default(),
+            right: Cursor::default(),
+            group: RightGroup::default(),
+            output: VecDeque::new(),
+        }))
+    }
+}
+
+impl<'a, T: Transaction + 'a> ExecutorNode<'a, T> for SortMergeJoin {
+    fn next_tuple(
+        &mut self,
+        arena: &mut ExecArena<'a, T>,
+        plan_arena: &mut PlanArena<'a>,
+    ) -> Result<(), DatabaseError> {
+        let (left_outer, right_outer) = match self.ty {
+            JoinType::Inner => (false, false),
+            JoinType::LeftOuter => (true, false),
+            JoinType::RightOuter => (false, true),
+            JoinType::Full => (true, true),
+            JoinType::Cross => {
+                return Err(DatabaseError::UnsupportedStmt(
+                    "Cross join should not be executed by SortMergeJoin".to_string(),
+                ))
+            }
+        };
+
+        loop {
+            if let Some(tuple) = self.output.pop_front() {
+                arena.produce_tuple(tuple);
+                return Ok(());
+            }
+            Self::fetch(
+                &mut self.left,
+                self.left_input,
+                &self.on_left_keys,
+                arena,
+                plan_arena,
+            )?;
+            Self::fetch(
+                &mut self.right,
+                self.right_input,
+                &self.on_right_keys,
+                arena,
+                plan_arena,
+            )?;
+
+            if !self.group.tuples.is_empty() {
+                let same_key = self
+                    .left
+                    .row
+                    .as_ref()
+                    .is_some_and(|(_, key)| self.compare_keys(key, &self.group.key).is_eq());
+                if same_key {
+                    let (left_tuple, _) = self.left.row.take().unwrap();
+                    self.join_group(left_tuple, left_outer)?;
+                } else {
+                    let group = mem::take(&mut self.group);
+                    if right_outer {
+                        for (tuple, matched) in group.tuples.into_iter().zip(group.matched) {
+                            if !matched {
+                                self.output.push_back(self.pad_left(tuple));
+                            }
+                        }
+                    }
+                }
+                continue;
+            }
+
+            match (self.left.row.take(), self.right.row.take()) {
+                (None, None) => {
+                    arena.finish();
+                    return Ok(());
+                }
+                (Some((left_tuple, _)), None) => {
+                    if left_outer {
+                        self.output.push_back(self.pad_right(left_tuple));
+                    }
+                }
+                (None, Some((right_tuple, _))) => {
+                    if right_outer {
+                        self.output.push_back(self.pad_left(right_tuple));
+                    }
+                }
+                (Some((left_tuple, left_key)), Some((right_tuple, right_key))) => {
+                    match self.compare_keys(&left_key, &right_key) {
+                        Ordering::Equal if !left_key.iter().any(DataValue::is_null) => {
+                            self.group.key = right_key;
+                            self.group.tuples.push(right_tuple);
+                            self.collect_group(arena, plan_arena)?;
+                            self.left.row = Some((left_tuple, left_key));
+                        }
+                        // `NULL` keys sort last and never match
+                        Ordering::Less | Ordering::Equal => {
+                            if left_outer {
+                                self.output.push_back(self.pad_right(left_tuple));
+                            }
+                            self.right.row = Some((right_tuple, right_key));
+                        }
+                        Ordering::Greater => {
+                            if right_outer {
+                                self.output.push_back(self.pad_left(right_tuple));
+                            }
+                            self.left.row = Some((left_tuple, left_key));
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl SortMergeJoin {
+    fn fetch<'a, T: Transaction + 'a>(
+        cursor: &mut Cursor,
+        input: ExecId,
+        on_keys: &[ScalarExpression],
+        arena: &mut ExecArena<'a, T>,
+        plan_arena: &mut PlanArena<'a>,
+    ) -> Result<(), DatabaseError> {
+        if cursor.row.is_some() || cursor.is_done {
+            return Ok(());
+        }
+        if !arena.next_tuple(input, plan_arena)? {
+            cursor.is_done = true;
+            return Ok(());
+        }
+        let tuple = mem::take(arena.result_tuple_mut());
+        let key = on_keys
+            .iter()
+            .map(|expr| expr.eval(Some(&tuple)))
+            .collect::<Result<Vec<_>, _>>()?;
+        cursor.row = Some((tuple, key));
+        Ok(())
+    }
+
+    /// Buffers the following right rows whose keys equal the key of the group.
+    fn collect_group<'a, T: Transaction + 'a>(
+        &mut self,
+        arena: &mut ExecArena<'a, T>,
+        plan_arena: &mut PlanArena<'a>,
+    ) -> Result<(), DatabaseError> {
+        loop {
+            Self::fetch(
+                &mut self.right,
+                self.right_input,
+                &self.on_right_keys,
+                arena,
+                plan_arena,
+            )?;
+            match self.right.row.take() {
+                Some((tuple, key)) if self.compare_keys(&key, &self.group.key).is_eq() => {
+                    self.group.tuples.push(tuple);
+                }
+                row => {
+                    self.right.row = row;
+                    break;
+                }
+            }
+        }
+        self.group.matched = vec![false; self.group.tuples.len()];
+        Ok(())
+    }
+
+    fn join_group(&mut self, left_tuple: Tuple, left_outer: bool) -> Result<(), DatabaseError> {
+        let mut has_matched = false;
+        for (right_tuple, matched) in self.group.tuples.iter().zip(self.group.matched.iter_mut()) {
+            if let Some(filter) = &self.filter {
+                match filter.eval(Some(SplitTupleRef::new(&left_tuple, right_tuple)))? {
+                    DataValue::Boolean(true) => (),
+                    DataValue::Boolean(false) | DataValue::Null => continue,
+                    _ => return Err(DatabaseError::InvalidType),
+                }
+            }
+            has_matched = true;
+            *matched = true;
+            self.output
+                .push_back(Self::join_tuple(&left_tuple, right_tuple.clone()));
+        }
+        if left_outer && !has_matched {
+            self.output.push_back(self.pad_right(left_tuple));
+        }
+        Ok(())
+    }
+
+    fn compare_keys(&self, left: &[DataValue], right: &[DataValue]) -> Ordering {
+        compare_sort_keys(&self.key_fields, left.iter(), right.iter())
+    }
+
+    fn pad_right(&self, left_tuple: Tuple) -> Tuple {
+        Self::join_tuple(
+            &left_tuple,
+            Tuple::new(None, vec![DataValue::Null; self.right_len]),
+        )
+    }
+
+    fn pad_left(&self, right_tuple: Tuple) -> Tuple {
+        let left_tuple = Tuple::new(None, vec![DataValue::Null; self.left_len]);
+        Self::join_tuple(&left_tuple, right_tuple)
+    }
+
+    fn join_tuple(left_tuple: &Tuple, right_tuple: Tuple) -> Tuple {
+        let mut values = Vec::with_capacity(left_tuple.values.len() + right_tuple.values.len());
+        values.extend(left_tuple.values.iter().cloned());
+        values.extend(right_tuple.values);
+        Tuple::new(left_tuple.pk.clone().or(right_tuple.pk), values)
+    }
+}
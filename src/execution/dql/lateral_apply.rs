@@ -0,0 +1,136 @@
+// Copyright 2024 KipData/KiteSQL
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::errors::DatabaseError;
+use crate::execution::dql::scalar_apply::bind_apply_parameters;
+use crate::execution::{
+    build_read, ExecArena, ExecId, ExecNode, ExecutionContext, ExecutorNode, ReadExecutor,
+};
+use crate::planner::operator::join::JoinType;
+use crate::planner::operator::lateral_apply::LateralApplyOperator;
+use crate::planner::LogicalPlan;
+use crate::storage::Transaction;
+use crate::types::tuple::{SplitTupleRef, Tuple};
+use crate::types::value::DataValue;
+use std::mem;
+
+struct LateralProbe {
+    left_tuple: Tuple,
+    right_input: ExecId,
+    matched: bool,
+}
+
+pub struct LateralApply {
+    op: LateralApplyOperator,
+    left_input: ExecId,
+    right_input_plan: LogicalPlan,
+    right_len: usize,
+    probe: Option<LateralProbe>,
+}
+
+impl<'a, T: Transaction + 'a> ReadExecutor<'a, T> for LateralApply {
+    type Input = (LateralApplyOperator, LogicalPlan, LogicalPlan);
+
+    fn into_executor(
+        (op, left_input, mut right_input): Self::Input,
+        arena: &mut ExecArena<'a, T>,
+        plan_arena: &mut crate::planner::PlanArena<'a>,
+        cache: ExecutionContext<'_>,
+        transaction: &T,
+    ) -> ExecId {
+        let right_len = right_input.output_schema(plan_arena).len();
+        let left_input = build_read(arena, plan_arena, left_input, cache, transaction);
+        arena.push(ExecNode::LateralApply(Self {
+            op,
+            left_input,
+            right_input_plan: right_input,
+            right_len,
+            probe: None,
+        }))
+    }
+}
+
+impl<'a, T: Transaction + 'a> ExecutorNode<'a, T> for LateralApply {
+    fn next_tuple(
+        &mut self,
+        arena: &mut ExecArena<'a, T>,
+        plan_arena: &mut crate::planner::PlanArena<'a>,
+    ) -> Result<(), DatabaseError> {
+        loop {
+            if let Some(probe) = self.probe.as_mut() {
+                if arena.next_tuple(probe.right_input, plan_arena)? {
+                    let right_tuple = mem::take(arena.result_tuple_mut());
+                    if !Self::filter_matched(&self.op, &probe.left_tuple, &right_tuple)? {
+                        continue;
+                    }
+                    probe.matched = true;
+
+                    let mut values = probe.left_tuple.values.clone();
+                    values.extend(right_tuple.values);
+                    arena.produce_tuple(Tuple::new(None, values));
+                    return Ok(());
+                }
+                let LateralProbe {
+                    mut left_tuple,
+                    matched,
+                    ..
+                } = self.probe.take().expect("lateral probe initialized");
+                if !matched && self.op.join_type == JoinType::LeftOuter {
+                    left_tuple
+                        .values
+                        .extend((0..self.right_len).map(|_| DataValue::Null));
+                    arena.produce_tuple(left_tuple);
+                    return Ok(());
+                }
+                continue;
+            }
+            if !arena.next_tuple(self.left_input, plan_arena)? {
+                arena.finish();
+                return Ok(());
+            }
+            let left_tuple = mem::take(arena.result_tuple_mut());
+            let plan = bind_apply_parameters(
+                &self.right_input_plan,
+                &self.op.parameters,
+                &left_tuple,
+                plan_arena,
+            )?;
+            let cache = arena.context();
+            let transaction = arena.transaction();
+            let right_input = build_read(arena, plan_arena, plan, cache, transaction);
+            self.probe = Some(LateralProbe {
+                left_tuple,
+                right_input,
+                matched: false,
+            });
+        }
+    }
+}
+
+impl LateralApply {
+    fn filter_matched(
+        op: &LateralApplyOperator,
+        left_tuple: &Tuple,
+        right_tuple: &Tuple,
+    ) -> Result<bool, DatabaseError> {
+        let Some(filter) = &op.filter else {
+            return Ok(true);
+        };
+        match filter.eval(Some(SplitTupleRef::new(left_tuple, right_tuple)))? {
+            DataValue::Boolean(matched) => Ok(matched),
+            DataValue::Null => Ok(false),
+            _ => Err(DatabaseError::InvalidType),
+        }
+    }
+}
@@ -13,6 +13,7 @@
 // limitations under the License.
 
 use crate::errors::DatabaseError;
+use crate::execution::dql::scalar_apply::bind_apply_parameters;
 use crate::execution::{
     build_read, ExecArena, ExecId, ExecNode, ExecutionContext, ExecutorNode, ReadExecutor,
 };
@@ -100,6 +101,7 @@ impl MarkApply {
         &self,
         arena: &mut ExecArena<'a, T>,
         plan_arena: &mut crate::planner::PlanArena<'a>,
+        left_tuple: &Tuple,
         param_value: Option<DataValue>,
         f: impl FnOnce(
             &mut ExecArena<'a, T>,
@@ -115,16 +117,14 @@ impl MarkApply {
 
         let cache = arena.context();
         let transaction = arena.transaction();
-        let result = {
-            let right_input = build_read(
-                arena,
-                plan_arena,
-                self.right_input_plan.clone(),
-                cache,
-                transaction,
-            );
-            f(arena, plan_arena, right_input)
-        };
+        let result = bind_apply_parameters(
+            &self.right_input_plan,
+            &self.op.parameters,
+            left_tuple,
+            plan_arena,
+        )
+        .map(|right_input_plan| build_read(arena, plan_arena, right_input_plan, cache, transaction))
+        .and_then(|right_input| f(arena, plan_arena, right_input));
 
         let depth_after = arena.runtime_probe_depth();
         debug_assert!(
@@ -158,6 +158,7 @@ impl MarkApply {
             MarkApplyKind::Exists => self.with_right_input(
                 arena,
                 plan_arena,
+                left_tuple,
                 self.parameterized_probe_value(left_tuple)?,
                 |arena, plan_arena, right_input| {
                     while arena.next_tuple(right_input, plan_arena)? {
@@ -176,6 +177,7 @@ impl MarkApply {
                         if self.with_right_input(
                             arena,
                             plan_arena,
+                            left_tuple,
                             Some(probe_value),
                             |arena, plan_arena, right_input| {
                                 while arena.next_tuple(right_input, plan_arena)? {
@@ -196,6 +198,7 @@ impl MarkApply {
                         if self.with_right_input(
                             arena,
                             plan_arena,
+                            left_tuple,
                             Some(DataValue::Null),
                             |arena, plan_arena, right_input| {
                                 while arena.next_tuple(right_input, plan_arena)? {
@@ -217,18 +220,28 @@ impl MarkApply {
                     }
                 }
 
-                self.with_right_input(arena, plan_arena, None, |arena, plan_arena, right_input| {
-                    self.scan_quantified_right_input(
-                        arena,
-                        plan_arena,
-                        right_input,
-                        MarkApplyQuantifier::Any,
-                        left_tuple,
-                    )
-                })
+                self.with_right_input(
+                    arena,
+                    plan_arena,
+                    left_tuple,
+                    None,
+                    |arena, plan_arena, right_input| {
+                        self.scan_quantified_right_input(
+                            arena,
+                            plan_arena,
+                            right_input,
+                            MarkApplyQuantifier::Any,
+                            left_tuple,
+                        )
+                    },
+                )
             }
-            MarkApplyKind::Quantified(MarkApplyQuantifier::All) => {
-                self.with_right_input(arena, plan_arena, None, |arena, plan_arena, right_input| {
+            MarkApplyKind::Quantified(MarkApplyQuantifier::All) => self.with_right_input(
+                arena,
+                plan_arena,
+                left_tuple,
+                None,
+                |arena, plan_arena, right_input| {
                     self.scan_quantified_right_input(
                         arena,
                         plan_arena,
@@ -236,8 +249,8 @@ impl MarkApply {
                         MarkApplyQuantifier::All,
                         left_tuple,
                     )
-                })
-            }
+                },
+            ),
         }
     }
 
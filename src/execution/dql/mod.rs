@@ -13,6 +13,7 @@
 // limitations under the License.
 
 pub(crate) mod aggregate;
+pub(crate) mod cte_scan;
 pub(crate) mod describe;
 pub(crate) mod dummy;
 pub(crate) mod explain;
@@ -22,9 +23,11 @@ pub(crate) mod filter;
 pub(crate) mod function_scan;
 pub(crate) mod index_scan;
 pub(crate) mod join;
+pub(crate) mod lateral_apply;
 pub(crate) mod limit;
 pub(crate) mod mark_apply;
 pub(crate) mod projection;
+pub(crate) mod recursive_cte;
 pub(crate) mod scalar_apply;
 pub(crate) mod scalar_subquery;
 pub(crate) mod seq_scan;
@@ -14,7 +14,8 @@
 
 use crate::errors::DatabaseError;
 use crate::execution::{
-    build_read, ExecArena, ExecId, ExecNode, ExecutionContext, ExecutorNode, ReadExecutor,
+    build_read, resolve_sequence_calls, ExecArena, ExecId, ExecNode, ExecutionContext,
+    ExecutorNode, ReadExecutor,
 };
 use crate::expression::ScalarExpression;
 use crate::planner::operator::project::ProjectOperator;
@@ -23,6 +24,7 @@ use crate::storage::Transaction;
 
 pub struct Projection {
     exprs: Vec<ScalarExpression>,
+    has_sequence_call: bool,
     input: ExecId,
 }
 
@@ -37,7 +39,14 @@ impl<'a, T: Transaction + 'a> ReadExecutor<'a, T> for Projection {
         transaction: &T,
     ) -> ExecId {
         let input = build_read(arena, plan_arena, input, cache, transaction);
-        arena.push(ExecNode::Projection(Projection { exprs, input }))
+        let has_sequence_call = exprs
+            .iter()
+            .any(|expr| expr.has_sequence_call().is_ok_and(|found| found));
+        arena.push(ExecNode::Projection(Projection {
+            exprs,
+            has_sequence_call,
+            input,
+        }))
     }
 }
 
@@ -52,10 +61,22 @@ impl<'a, T: Transaction + 'a> ExecutorNode<'a, T> for Projection {
             return Ok(());
         }
 
+        // sequence calls produce a new value for every row
+        let resolved_exprs;
+        let exprs = if self.has_sequence_call {
+            let mut exprs = self.exprs.clone();
+            for expr in exprs.iter_mut() {
+                resolve_sequence_calls(arena, plan_arena, expr)?;
+            }
+            resolved_exprs = exprs;
+            &resolved_exprs
+        } else {
+            &self.exprs
+        };
         arena.with_projection_tmp(|arena, projection_tmp| {
             let tuple = arena.result_tuple();
-            projection_tmp.reserve(self.exprs.len());
-            for expr in self.exprs.iter() {
+            projection_tmp.reserve(exprs.len());
+            for expr in exprs.iter() {
                 projection_tmp.push(expr.eval(Some(tuple))?);
             }
             std::mem::swap(&mut arena.result_tuple_mut().values, projection_tmp);
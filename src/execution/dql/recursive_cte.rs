@@ -0,0 +1,130 @@
+// Copyright 2024 KipData/KiteSQL
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use crate::catalog::TableName;
+use crate::errors::DatabaseError;
+use crate::execution::{
+    build_read, ExecArena, ExecId, ExecNode, ExecutionContext, ExecutorNode, ReadExecutor,
+};
+use crate::planner::operator::recursive_cte::{RecursiveCteOperator, MAX_RECURSIVE_CTE_ITERATIONS};
+use crate::planner::LogicalPlan;
+use crate::storage::Transaction;
+use crate::types::tuple::Tuple;
+use std::collections::HashSet;
+use std::mem;
+use std::ops::Range;
+use std::sync::Arc;
+
+pub struct RecursiveCte {
+    table_name: TableName,
+    is_all: bool,
+    anchor_plan: LogicalPlan,
+    recursive_plan: LogicalPlan,
+    input: ExecId,
+    // the executors of the anchor or the last recursive run, dropped before the next run
+    input_nodes: Range<ExecId>,
+    // Tips: `UNION` keeps every emitted row so that cycles stop producing new work
+    seen: HashSet<Tuple>,
+    working: Vec<Tuple>,
+    iterations: usize,
+}
+
+impl From<(RecursiveCteOperator, LogicalPlan, LogicalPlan)> for RecursiveCte {
+    fn from(
+        (op, anchor_plan, recursive_plan): (RecursiveCteOperator, LogicalPlan, LogicalPlan),
+    ) -> Self {
+        RecursiveCte {
+            table_name: op.table_name,
+            is_all: op.is_all,
+            anchor_plan,
+            recursive_plan,
+            input: 0,
+            input_nodes: 0..0,
+            seen: HashSet::new(),
+            working: Vec::new(),
+            iterations: 0,
+        }
+    }
+}
+
+impl<'a, T: Transaction + 'a> ReadExecutor<'a, T> for RecursiveCte {
+    type Input = Self;
+
+    fn into_executor(
+        input: Self::Input,
+        arena: &mut ExecArena<'a, T>,
+        plan_arena: &mut crate::planner::PlanArena<'a>,
+        cache: ExecutionContext<'_>,
+        transaction: &T,
+    ) -> ExecId {
+        let mut executor = input;
+        let start = arena.next_id();
+        executor.input = build_read(
+            arena,
+            plan_arena,
+            executor.anchor_plan.take(),
+            cache,
+            transaction,
+        );
+        executor.input_nodes = start..arena.next_id();
+        arena.push(ExecNode::RecursiveCte(executor))
+    }
+}
+
+impl<'a, T: Transaction + 'a> ExecutorNode<'a, T> for RecursiveCte {
+    fn next_tuple(
+        &mut self,
+        arena: &mut ExecArena<'a, T>,
+        plan_arena: &mut crate::planner::PlanArena<'a>,
+    ) -> Result<(), DatabaseError> {
+        loop {
+            if arena.next_tuple(self.input, plan_arena)? {
+                let tuple = arena.result_tuple_mut();
+                tuple.pk = None;
+                if !self.is_all && !self.seen.insert(tuple.clone()) {
+                    continue;
+                }
+                self.working.push(tuple.clone());
+                arena.resume();
+                return Ok(());
+            }
+            if self.working.is_empty() {
+                arena.finish();
+                return Ok(());
+            }
+            if self.iterations >= MAX_RECURSIVE_CTE_ITERATIONS {
+                return Err(DatabaseError::RecursionLimitExceeded(
+                    MAX_RECURSIVE_CTE_ITERATIONS,
+                ));
+            }
+            self.iterations += 1;
+
+            arena.set_cte_table(
+                self.table_name.clone(),
+                Arc::new(mem::take(&mut self.working)),
+            );
+            arena.reclaim(mem::replace(&mut self.input_nodes, 0..0));
+            let cache = arena.context();
+            let transaction = arena.transaction();
+            let start = arena.next_id();
+            self.input = build_read(
+                arena,
+                plan_arena,
+                self.recursive_plan.clone(),
+                cache,
+                transaction,
+            );
+            self.input_nodes = start..arena.next_id();
+        }
+    }
+}
@@ -12,38 +12,122 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::catalog::ColumnRef;
 use crate::errors::DatabaseError;
 use crate::execution::{
     build_read, ExecArena, ExecId, ExecNode, ExecutionContext, ExecutorNode, ReadExecutor,
 };
+use crate::expression::visitor_mut::{walk_mut_expr, ExprVisitorMut};
+use crate::expression::ScalarExpression;
 use crate::planner::operator::scalar_apply::ScalarApplyOperator;
-use crate::planner::LogicalPlan;
+use crate::planner::operator::visitor_mut::{OperatorExprVisitorMut, OperatorVisitorMut};
+use crate::planner::{Childrens, LogicalPlan, PlanArena};
 use crate::storage::Transaction;
 use crate::types::tuple::Tuple;
+use crate::types::value::DataValue;
 use std::mem;
 
+enum RightInput {
+    Uncorrelated {
+        input: ExecId,
+        cached: Option<Tuple>,
+    },
+    Parameterized {
+        parameters: Vec<ScalarExpression>,
+        plan: Box<LogicalPlan>,
+    },
+}
+
 pub struct ScalarApply {
     left_input: ExecId,
-    right_input: ExecId,
-    cached_right: Option<Tuple>,
+    right_input: RightInput,
+}
+
+struct ParameterBinder<'a, 'p> {
+    parameters: Vec<(ColumnRef, DataValue)>,
+    arena: &'a PlanArena<'p>,
+}
+
+impl ExprVisitorMut<'_> for ParameterBinder<'_, '_> {
+    fn visit(&mut self, expr: &mut ScalarExpression) -> Result<(), DatabaseError> {
+        if let ScalarExpression::ColumnRef { column, .. } = expr {
+            if let Some((_, value)) = self
+                .parameters
+                .iter()
+                .find(|(parameter, _)| self.arena.same_column(*parameter, *column))
+            {
+                *expr = ScalarExpression::Constant(value.clone());
+            }
+            return Ok(());
+        }
+        walk_mut_expr(self, expr)
+    }
+}
+
+impl ParameterBinder<'_, '_> {
+    fn bind_plan(&mut self, plan: &mut LogicalPlan) -> Result<(), DatabaseError> {
+        OperatorExprVisitorMut::new(self).visit_operator(&mut plan.operator)?;
+        match plan.childrens.as_mut() {
+            Childrens::Only(child) => self.bind_plan(child),
+            Childrens::Twins { left, right } => {
+                self.bind_plan(left)?;
+                self.bind_plan(right)
+            }
+            Childrens::None => Ok(()),
+        }
+    }
+}
+
+/// Clones a correlated right input with every reference to a parameter column
+/// replaced by the value of that column in `left_tuple`.
+pub(crate) fn bind_apply_parameters(
+    plan: &LogicalPlan,
+    parameters: &[ScalarExpression],
+    left_tuple: &Tuple,
+    arena: &PlanArena,
+) -> Result<LogicalPlan, DatabaseError> {
+    let mut plan = plan.clone();
+    if parameters.is_empty() {
+        return Ok(plan);
+    }
+    let parameters = parameters
+        .iter()
+        .map(|expr| {
+            let ScalarExpression::ColumnRef { column, .. } = expr.unpack_alias_ref() else {
+                return Err(DatabaseError::InvalidType);
+            };
+            Ok((*column, expr.eval(Some(left_tuple))?))
+        })
+        .collect::<Result<Vec<_>, DatabaseError>>()?;
+    ParameterBinder { parameters, arena }.bind_plan(&mut plan)?;
+    Ok(plan)
 }
 
 impl<'a, T: Transaction + 'a> ReadExecutor<'a, T> for ScalarApply {
     type Input = (ScalarApplyOperator, LogicalPlan, LogicalPlan);
 
     fn into_executor(
-        (_, left_input, right_input): Self::Input,
+        (ScalarApplyOperator { parameters }, left_input, right_input): Self::Input,
         arena: &mut ExecArena<'a, T>,
         plan_arena: &mut crate::planner::PlanArena<'a>,
         cache: ExecutionContext<'_>,
         transaction: &T,
     ) -> ExecId {
         let left_input = build_read(arena, plan_arena, left_input, cache, transaction);
-        let right_input = build_read(arena, plan_arena, right_input, cache, transaction);
+        let right_input = if parameters.is_empty() {
+            RightInput::Uncorrelated {
+                input: build_read(arena, plan_arena, right_input, cache, transaction),
+                cached: None,
+            }
+        } else {
+            RightInput::Parameterized {
+                parameters,
+                plan: Box::new(right_input),
+            }
+        };
         arena.push(ExecNode::ScalarApply(Self {
             left_input,
             right_input,
-            cached_right: None,
         }))
     }
 }
@@ -54,21 +138,41 @@ impl<'a, T: Transaction + 'a> ExecutorNode<'a, T> for ScalarApply {
         arena: &mut ExecArena<'a, T>,
         plan_arena: &mut crate::planner::PlanArena<'a>,
     ) -> Result<(), DatabaseError> {
-        Self::load_right_once(&mut self.cached_right, self.right_input, arena, plan_arena)?;
-
-        let right_tuple = self
-            .cached_right
-            .as_ref()
-            .expect("scalar apply right tuple initialized");
-        if !arena.next_tuple(self.left_input, plan_arena)? {
-            arena.finish();
-            return Ok(());
+        match &mut self.right_input {
+            RightInput::Uncorrelated { input, cached } => {
+                Self::load_right_once(cached, *input, arena, plan_arena)?;
+
+                let right_tuple = cached
+                    .as_ref()
+                    .expect("scalar apply right tuple initialized");
+                if !arena.next_tuple(self.left_input, plan_arena)? {
+                    arena.finish();
+                    return Ok(());
+                }
+                arena
+                    .result_tuple_mut()
+                    .values
+                    .extend(right_tuple.values.iter().cloned());
+                arena.resume();
+            }
+            RightInput::Parameterized { parameters, plan } => {
+                if !arena.next_tuple(self.left_input, plan_arena)? {
+                    arena.finish();
+                    return Ok(());
+                }
+                let mut left_tuple = mem::take(arena.result_tuple_mut());
+                let plan = bind_apply_parameters(plan, parameters, &left_tuple, plan_arena)?;
+                let cache = arena.context();
+                let transaction = arena.transaction();
+                let right_input = build_read(arena, plan_arena, plan, cache, transaction);
+                let mut right_tuple = None;
+                Self::load_right_once(&mut right_tuple, right_input, arena, plan_arena)?;
+                if let Some(right_tuple) = right_tuple {
+                    left_tuple.values.extend(right_tuple.values);
+                }
+                arena.produce_tuple(left_tuple);
+            }
         }
-        arena
-            .result_tuple_mut()
-            .values
-            .extend(right_tuple.values.iter().cloned());
-        arena.resume();
         Ok(())
     }
 }
@@ -163,7 +267,7 @@ mod tests {
         let (table_cache, view_cache, meta_cache, _temp_dir, storage) = build_test_storage()?;
         let transaction = storage.transaction()?;
         let tuples = try_collect(execute_input::<_, ScalarApply>(
-            (ScalarApplyOperator, left, right),
+            (ScalarApplyOperator::default(), left, right),
             crate::execution::empty_context(&table_cache, &view_cache, &meta_cache),
             plan_arena,
             &transaction,
@@ -204,7 +308,7 @@ mod tests {
         let (table_cache, view_cache, meta_cache, _temp_dir, storage) = build_test_storage()?;
         let transaction = storage.transaction()?;
         let tuples = try_collect(execute_input::<_, ScalarApply>(
-            (ScalarApplyOperator, left, right),
+            (ScalarApplyOperator::default(), left, right),
             crate::execution::empty_context(&table_cache, &view_cache, &meta_cache),
             plan_arena,
             &transaction,
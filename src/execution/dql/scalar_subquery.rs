@@ -20,6 +20,7 @@ use crate::planner::operator::scalar_subquery::ScalarSubqueryOperator;
 use crate::planner::LogicalPlan;
 use crate::storage::Transaction;
 use crate::types::value::DataValue;
+use std::mem;
 
 pub struct ScalarSubquery {
     input: ExecId,
@@ -71,13 +72,14 @@ impl<'a, T: Transaction + 'a> ExecutorNode<'a, T> for ScalarSubquery {
             return Ok(());
         }
 
+        let first = mem::take(arena.result_tuple_mut());
         if arena.next_tuple(self.input, plan_arena)? {
             return Err(DatabaseError::InvalidValue(
                 "scalar subquery returned more than one row".to_string(),
             ));
         }
 
-        arena.resume();
+        arena.produce_tuple(first);
         Ok(())
     }
 }
@@ -52,6 +52,7 @@ impl<'a, T: Transaction + 'a> ExecutorNode<'a, T> for ShowViews<'a, T> {
                 plan_arena.table_arena_cell(),
                 context.scala_functions(),
                 context.table_functions(),
+                context.aggregate_functions(),
             )?);
         }
 
@@ -25,9 +25,10 @@ use crate::types::tuple::Tuple;
 use crate::types::value::DataValue;
 use std::mem;
 
+mod frame;
 mod function;
 
-use function::WindowFunction;
+use function::{Scope, WindowFunction};
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 enum Retention {
@@ -51,18 +52,27 @@ struct WindowState {
     partition_rows: usize,
     peer_start: usize,
     peer_index: usize,
+    /// The first row of every peer group of a retained partition.
+    peer_starts: Vec<usize>,
 }
 
 impl WindowState {
-    fn begin_partition(&mut self) {
+    fn begin_partition(&mut self, retention: Retention) {
         self.partition_rows = 0;
         self.peer_start = 0;
         self.peer_index = 0;
+        self.peer_starts.clear();
+        if retention == Retention::Partition {
+            self.peer_starts.push(0);
+        }
     }
 
-    fn begin_peer(&mut self) {
+    fn begin_peer(&mut self, retention: Retention) {
         self.peer_start = self.partition_rows;
         self.peer_index += 1;
+        if retention == Retention::Partition {
+            self.peer_starts.push(self.peer_start);
+        }
     }
 }
 
@@ -91,22 +101,32 @@ impl<'a, T: Transaction + 'a> ReadExecutor<'a, T> for Window {
             sort_fields,
             partition_by_len,
             functions: window_functions,
+            frames,
             ..
         } = operator;
         let has_aggregate = window_functions
             .iter()
             .any(|function| matches!(function.kind, WindowFunctionKind::Aggregate(_)));
-        let retention = if !has_aggregate {
+        let order_by = &sort_fields[partition_by_len..];
+        let functions: Vec<_> = window_functions
+            .into_iter()
+            .zip(frames)
+            .map(|(function, frame)| {
+                function::new(function.kind, function.args, function.ty, frame, order_by)
+            })
+            .collect();
+        let retention = if functions
+            .iter()
+            .any(|function| function.scope() != Scope::Peer)
+        {
+            Retention::Partition
+        } else if !has_aggregate {
             Retention::Row
         } else if sort_fields.len() == partition_by_len {
             Retention::Partition
         } else {
             Retention::Peer
         };
-        let functions = window_functions
-            .into_iter()
-            .map(|function| function::new(function.kind, function.args, function.ty))
-            .collect();
         arena.push(ExecNode::Window(Window {
             state: WindowState::default(),
             retention,
@@ -157,13 +177,31 @@ impl Window {
         }
         let len = self.state.buffered.len();
         for (slot, function) in self.functions.iter_mut().enumerate() {
-            function.evaluate(
-                &mut self.state.buffered,
-                0..len,
-                self.state.peer_start,
-                self.state.peer_index,
-                output_offset + slot,
-            )?;
+            if self.retention != Retention::Partition || function.scope() == Scope::Partition {
+                function.evaluate(
+                    &mut self.state.buffered,
+                    0..len,
+                    self.state.peer_start,
+                    self.state.peer_index,
+                    output_offset + slot,
+                )?;
+                continue;
+            }
+            for (peer_index, &peer_start) in self.state.peer_starts.iter().enumerate() {
+                let peer_end = self
+                    .state
+                    .peer_starts
+                    .get(peer_index + 1)
+                    .copied()
+                    .unwrap_or(len);
+                function.evaluate(
+                    &mut self.state.buffered,
+                    peer_start..peer_end,
+                    peer_start,
+                    peer_index,
+                    output_offset + slot,
+                )?;
+            }
         }
         self.state.buffered.reverse();
         Ok(())
@@ -186,10 +224,10 @@ impl Window {
 
         match boundary {
             Some(Boundary::Partition) => {
-                self.state.begin_partition();
+                self.state.begin_partition(self.retention);
                 self.reset_functions()?;
             }
-            Some(Boundary::Peer) => self.state.begin_peer(),
+            Some(Boundary::Peer) => self.state.begin_peer(self.retention),
             None => {}
         }
 
@@ -245,6 +283,7 @@ mod tests {
     use super::*;
     use crate::catalog::ColumnRef;
     use crate::expression::agg::AggKind;
+    use crate::expression::window::{WindowFrame, WindowFrameBound, WindowFrameUnits};
     use crate::expression::ScalarExpression;
     use crate::types::LogicalType;
 
@@ -284,8 +323,16 @@ mod tests {
                     WindowFunctionKind::RowNumber,
                     Vec::new(),
                     LogicalType::Bigint,
+                    None,
+                    &[],
+                ),
+                function::new(
+                    WindowFunctionKind::Rank,
+                    Vec::new(),
+                    LogicalType::Bigint,
+                    None,
+                    &[],
                 ),
-                function::new(WindowFunctionKind::Rank, Vec::new(), LogicalType::Bigint),
             ],
         );
         for (value, expected) in [(10, [1_i64, 1]), (10, [2, 1]), (20, [3, 3])] {
@@ -306,6 +353,8 @@ mod tests {
                 WindowFunctionKind::Aggregate(AggKind::Sum),
                 vec![column(1)],
                 LogicalType::Integer,
+                None,
+                &[],
             )],
         );
         window.eval(tuple(&[1, 10]), None)?;
@@ -326,6 +375,8 @@ mod tests {
                 WindowFunctionKind::Aggregate(AggKind::Sum),
                 vec![column(1)],
                 LogicalType::Integer,
+                None,
+                &[],
             )],
         );
         window.eval(tuple(&[1, 3]), None)?;
@@ -335,5 +386,65 @@ mod tests {
         assert!(window.state.pending.is_some());
         Ok(())
     }
+
+    #[test]
+    fn framed_functions_see_whole_partition() -> Result<(), DatabaseError> {
+        let sort_fields = vec![column(0).asc(), column(1).asc()];
+        let frame = WindowFrame {
+            units: WindowFrameUnits::Rows,
+            start: WindowFrameBound::Preceding(DataValue::Int64(1)),
+            end: WindowFrameBound::Following(DataValue::Int64(1)),
+        };
+        let mut window = window(
+            Retention::Partition,
+            sort_fields.clone(),
+            1,
+            vec![
+                function::new(
+                    WindowFunctionKind::Aggregate(AggKind::Sum),
+                    vec![column(1)],
+                    LogicalType::Integer,
+                    Some(frame),
+                    &sort_fields[1..],
+                ),
+                function::new(
+                    WindowFunctionKind::Aggregate(AggKind::Sum),
+                    vec![column(1)],
+                    LogicalType::Integer,
+                    None,
+                    &sort_fields[1..],
+                ),
+                function::new(
+                    WindowFunctionKind::Rank,
+                    Vec::new(),
+                    LogicalType::Bigint,
+                    None,
+                    &sort_fields[1..],
+                ),
+            ],
+        );
+        for value in [1, 2, 2, 4] {
+            assert!(!window.eval(tuple(&[1, value]), None)?);
+        }
+        window.eval_functions()?;
+        let rows = window
+            .state
+            .buffered
+            .drain(..)
+            .rev()
+            .map(|(_, row)| row.values[2..].to_vec())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            rows,
+            [(3, 1, 1), (5, 5, 2), (8, 5, 2), (6, 9, 4)]
+                .map(|(framed, running, rank): (i32, i32, i64)| vec![
+                    DataValue::from(framed),
+                    DataValue::from(running),
+                    DataValue::Int64(rank),
+                ])
+                .to_vec()
+        );
+        Ok(())
+    }
 }
 // GRCOV_EXCL_STOP
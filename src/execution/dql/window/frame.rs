@@ -0,0 +1,533 @@
+// Copyright 2024 KipData/KiteSQL
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::errors::DatabaseError;
+use crate::execution::dql::aggregate::avg::average;
+use crate::expression::window::{WindowFrame, WindowFrameBound, WindowFrameUnits};
+use crate::expression::BinaryOperator;
+use crate::planner::operator::sort::SortField;
+use crate::types::evaluator::{binary_create, BinaryEvaluatorRef};
+use crate::types::tuple::Tuple;
+use crate::types::value::DataValue;
+use crate::types::LogicalType;
+use std::borrow::Cow;
+use std::collections::VecDeque;
+use std::ops::Range;
+
+/// A `RANGE` offset bound, which may fall outside of the domain of the `ORDER BY` type.
+enum Target {
+    Below,
+    Value(DataValue),
+    Above,
+}
+
+/// Whether `value` is ordered before `target`, or at the same position when `inclusive`.
+fn before(value: &DataValue, target: &Target, asc: bool, inclusive: bool) -> bool {
+    match target {
+        Target::Below => !asc,
+        Target::Above => asc,
+        Target::Value(target) => {
+            (if asc { value < target } else { value > target }) || (inclusive && value == target)
+        }
+    }
+}
+
+struct PeerGroups {
+    starts: Vec<usize>,
+    of_row: Vec<usize>,
+    len: usize,
+}
+
+impl PeerGroups {
+    fn new(keys: &[Vec<DataValue>]) -> Self {
+        let mut starts = Vec::new();
+        let mut of_row = Vec::with_capacity(keys.len());
+        for (index, key) in keys.iter().enumerate() {
+            if index == 0 || keys[index - 1] != *key {
+                starts.push(index);
+            }
+            of_row.push(starts.len() - 1);
+        }
+        PeerGroups {
+            starts,
+            of_row,
+            len: keys.len(),
+        }
+    }
+
+    fn start(&self, group: usize) -> usize {
+        self.starts[group]
+    }
+
+    fn end(&self, group: usize) -> usize {
+        self.starts.get(group + 1).copied().unwrap_or(self.len)
+    }
+}
+
+/// Evaluates `RANGE` offsets against the single `ORDER BY` key of a partition.
+struct RangeOffsets {
+    asc: bool,
+    /// The positions of the rows whose key is not NULL, NULLs are sorted to one end.
+    not_null: Range<usize>,
+    plus: Option<BinaryEvaluatorRef>,
+    minus: Option<BinaryEvaluatorRef>,
+}
+
+impl RangeOffsets {
+    fn new(field: &SortField, keys: &[Vec<DataValue>]) -> Self {
+        let start = keys
+            .iter()
+            .position(|key| !key[0].is_null())
+            .unwrap_or(keys.len());
+        let end = keys
+            .iter()
+            .rposition(|key| !key[0].is_null())
+            .map_or(start, |position| position + 1);
+        RangeOffsets {
+            asc: field.asc,
+            not_null: start..end,
+            plus: None,
+            minus: None,
+        }
+    }
+
+    fn target(
+        &mut self,
+        value: &DataValue,
+        offset: &DataValue,
+        preceding: bool,
+    ) -> Result<Target, DatabaseError> {
+        let upward = preceding != self.asc;
+        let evaluator = if upward {
+            &mut self.plus
+        } else {
+            &mut self.minus
+        };
+        let evaluator = match evaluator {
+            Some(evaluator) => evaluator,
+            None => evaluator.insert(binary_create(
+                Cow::Owned(offset.logical_type()),
+                if upward {
+                    BinaryOperator::Plus
+                } else {
+                    BinaryOperator::Minus
+                },
+            )?),
+        };
+        match evaluator.binary_eval(value, offset) {
+            Ok(target) => Ok(Target::Value(target)),
+            Err(DatabaseError::OverFlow) if upward => Ok(Target::Above),
+            Err(DatabaseError::OverFlow) => Ok(Target::Below),
+            Err(err) => Err(err),
+        }
+    }
+
+    fn bound(
+        &mut self,
+        keys: &[Vec<DataValue>],
+        index: usize,
+        offset: &DataValue,
+        preceding: bool,
+        is_start: bool,
+    ) -> Result<usize, DatabaseError> {
+        let target = self.target(&keys[index][0], offset, preceding)?;
+        let not_null = &keys[self.not_null.clone()];
+
+        Ok(self.not_null.start
+            + not_null.partition_point(|key| before(&key[0], &target, self.asc, !is_start)))
+    }
+}
+
+fn row_offset(offset: &DataValue) -> Result<i64, DatabaseError> {
+    offset
+        .i64()
+        .ok_or_else(|| DatabaseError::InvalidValue(format!("window frame offset: {offset}")))
+}
+
+/// Returns the frame of every row of a sorted partition as a range of row positions, a frame
+/// that ends before it starts is empty.
+///
+/// The starts and the ends of the frames never decrease from one row to the next, which lets
+/// the sliding aggregates below move over the partition once.
+pub(super) fn frame_ranges(
+    frame: &WindowFrame,
+    order_by: &[SortField],
+    rows: &[(usize, Tuple)],
+) -> Result<Vec<Range<usize>>, DatabaseError> {
+    let len = rows.len();
+    let keys = if frame.units == WindowFrameUnits::Rows {
+        Vec::new()
+    } else {
+        rows.iter()
+            .map(|(_, row)| {
+                order_by
+                    .iter()
+                    .map(|field| field.expr.eval(Some(row)))
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .collect::<Result<Vec<_>, _>>()?
+    };
+    let groups = PeerGroups::new(&keys);
+    let mut range_offsets = (frame.units == WindowFrameUnits::Range)
+        .then(|| {
+            order_by
+                .first()
+                .map(|field| RangeOffsets::new(field, &keys))
+        })
+        .flatten();
+
+    let mut bound = |bound: &WindowFrameBound, index: usize, is_start: bool| {
+        let (offset, preceding) = match bound {
+            WindowFrameBound::UnboundedPreceding => return Ok(0),
+            WindowFrameBound::UnboundedFollowing => return Ok(len),
+            WindowFrameBound::CurrentRow => {
+                return Ok(match (frame.units, is_start) {
+                    (WindowFrameUnits::Rows, true) => index,
+                    (WindowFrameUnits::Rows, false) => index + 1,
+                    (_, true) => groups.start(groups.of_row[index]),
+                    (_, false) => groups.end(groups.of_row[index]),
+                })
+            }
+            WindowFrameBound::Preceding(offset) => (offset, true),
+            WindowFrameBound::Following(offset) => (offset, false),
+        };
+        match frame.units {
+            WindowFrameUnits::Rows => {
+                let offset = row_offset(offset)?;
+                let position = if preceding {
+                    index as i64 - offset
+                } else {
+                    index as i64 + offset
+                } + i64::from(!is_start);
+                Ok(position.clamp(0, len as i64) as usize)
+            }
+            WindowFrameUnits::Groups => {
+                let offset = row_offset(offset)?;
+                let group = groups.of_row[index] as i64;
+                let group = if preceding {
+                    group - offset
+                } else {
+                    group + offset
+                };
+                Ok(if group < 0 {
+                    0
+                } else if group >= groups.starts.len() as i64 {
+                    len
+                } else if is_start {
+                    groups.start(group as usize)
+                } else {
+                    groups.end(group as usize)
+                })
+            }
+            WindowFrameUnits::Range => {
+                let Some(range_offsets) = range_offsets.as_mut() else {
+                    unreachable!("RANGE offsets require an ORDER BY key")
+                };
+                if keys[index][0].is_null() {
+                    let group = groups.of_row[index];
+                    return Ok(if is_start {
+                        groups.start(group)
+                    } else {
+                        groups.end(group)
+                    });
+                }
+                range_offsets.bound(&keys, index, offset, preceding, is_start)
+            }
+        }
+    };
+
+    (0..len)
+        .map(|index| {
+            let start = bound(&frame.start, index, true)?;
+            let end = bound(&frame.end, index, false)?;
+            Ok(start..end.max(start))
+        })
+        .collect()
+}
+
+/// SUM and AVG over sliding frames: values entering the frame are added to a running
+/// sum and values leaving it are subtracted again. Only exact for integers and decimals,
+/// floating point frames are summed from scratch.
+pub(super) struct SlidingSum {
+    ty: Option<LogicalType>,
+    plus: Option<BinaryEvaluatorRef>,
+    minus: Option<BinaryEvaluatorRef>,
+    sum: DataValue,
+    count: usize,
+}
+
+impl SlidingSum {
+    /// Sums values as `ty`, or as the type of the values when `None`.
+    pub(super) fn new(ty: Option<LogicalType>) -> Self {
+        SlidingSum {
+            ty,
+            plus: None,
+            minus: None,
+            sum: DataValue::Null,
+            count: 0,
+        }
+    }
+
+    fn evaluator<'a>(
+        evaluator: &'a mut Option<BinaryEvaluatorRef>,
+        ty: &Option<LogicalType>,
+        value: &DataValue,
+        op: BinaryOperator,
+    ) -> Result<&'a BinaryEvaluatorRef, DatabaseError> {
+        Ok(match evaluator {
+            Some(evaluator) => evaluator,
+            None => {
+                let ty = match ty {
+                    Some(ty) => Cow::Borrowed(ty),
+                    None => Cow::Owned(value.logical_type()),
+                };
+                evaluator.insert(binary_create(ty, op)?)
+            }
+        })
+    }
+
+    pub(super) fn add(&mut self, value: &DataValue) -> Result<(), DatabaseError> {
+        if value.is_null() {
+            return Ok(());
+        }
+        self.sum = if self.sum.is_null() {
+            value.clone()
+        } else {
+            Self::evaluator(&mut self.plus, &self.ty, value, BinaryOperator::Plus)?
+                .binary_eval(&self.sum, value)?
+        };
+        self.count += 1;
+        Ok(())
+    }
+
+    pub(super) fn remove(&mut self, value: &DataValue) -> Result<(), DatabaseError> {
+        if value.is_null() {
+            return Ok(());
+        }
+        self.count -= 1;
+        self.sum = if self.count == 0 {
+            DataValue::Null
+        } else {
+            Self::evaluator(&mut self.minus, &self.ty, value, BinaryOperator::Minus)?
+                .binary_eval(&self.sum, value)?
+        };
+        Ok(())
+    }
+
+    pub(super) fn sum(&self) -> DataValue {
+        self.sum.clone()
+    }
+
+    pub(super) fn avg(&self) -> Result<DataValue, DatabaseError> {
+        average(&self.sum, self.count)
+    }
+}
+
+/// MIN and MAX over sliding frames, keeping the positions of the values that can still become
+/// the result in a deque ordered from the best value to the worst.
+pub(super) struct SlidingMinMax {
+    is_max: bool,
+    candidates: VecDeque<usize>,
+}
+
+impl SlidingMinMax {
+    pub(super) fn new(is_max: bool) -> Self {
+        SlidingMinMax {
+            is_max,
+            candidates: VecDeque::new(),
+        }
+    }
+
+    pub(super) fn add(&mut self, values: &[DataValue], position: usize) {
+        let value = &values[position];
+        if value.is_null() {
+            return;
+        }
+        while let Some(last) = self.candidates.back() {
+            let last = &values[*last];
+            if (self.is_max && last > value) || (!self.is_max && last < value) {
+                break;
+            }
+            self.candidates.pop_back();
+        }
+        self.candidates.push_back(position);
+    }
+
+    pub(super) fn remove_before(&mut self, position: usize) {
+        while self
+            .candidates
+            .front()
+            .is_some_and(|candidate| *candidate < position)
+        {
+            self.candidates.pop_front();
+        }
+    }
+
+    pub(super) fn result(&self, values: &[DataValue]) -> DataValue {
+        self.candidates
+            .front()
+            .map_or(DataValue::Null, |position| values[*position].clone())
+    }
+}
+
+// GRCOV_EXCL_START
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+    use super::*;
+    use crate::catalog::ColumnRef;
+    use crate::expression::ScalarExpression;
+
+    fn rows(keys: &[Option<i32>]) -> Vec<(usize, Tuple)> {
+        keys.iter()
+            .enumerate()
+            .map(|(index, key)| {
+                let key = key.map_or(DataValue::Null, DataValue::Int32);
+                (index, Tuple::new(None, vec![key]))
+            })
+            .collect()
+    }
+
+    fn frame(
+        units: WindowFrameUnits,
+        start: WindowFrameBound,
+        end: WindowFrameBound,
+    ) -> WindowFrame {
+        WindowFrame { units, start, end }
+    }
+
+    fn order_by(asc: bool) -> Vec<SortField> {
+        vec![SortField::new(
+            ScalarExpression::column_expr(ColumnRef::new(1), 0),
+            asc,
+            false,
+        )]
+    }
+
+    #[test]
+    fn rows_frames() -> Result<(), DatabaseError> {
+        let rows = rows(&[Some(1), Some(2), Some(3), Some(4)]);
+        let trailing = frame(
+            WindowFrameUnits::Rows,
+            WindowFrameBound::Preceding(DataValue::Int64(2)),
+            WindowFrameBound::CurrentRow,
+        );
+        assert_eq!(
+            frame_ranges(&trailing, &[], &rows)?,
+            vec![0..1, 0..2, 0..3, 1..4]
+        );
+        let leading = frame(
+            WindowFrameUnits::Rows,
+            WindowFrameBound::Following(DataValue::Int64(1)),
+            WindowFrameBound::UnboundedFollowing,
+        );
+        assert_eq!(
+            frame_ranges(&leading, &[], &rows)?,
+            vec![1..4, 2..4, 3..4, 4..4]
+        );
+        let empty = frame(
+            WindowFrameUnits::Rows,
+            WindowFrameBound::Preceding(DataValue::Int64(3)),
+            WindowFrameBound::Preceding(DataValue::Int64(2)),
+        );
+        assert_eq!(
+            frame_ranges(&empty, &[], &rows)?,
+            vec![0..0, 0..0, 0..1, 0..2]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn groups_frames() -> Result<(), DatabaseError> {
+        let rows = rows(&[Some(1), Some(1), Some(2), Some(3), Some(3)]);
+        let frame = frame(
+            WindowFrameUnits::Groups,
+            WindowFrameBound::Preceding(DataValue::Int64(1)),
+            WindowFrameBound::CurrentRow,
+        );
+        assert_eq!(
+            frame_ranges(&frame, &order_by(true), &rows)?,
+            vec![0..2, 0..2, 0..3, 2..5, 2..5]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn range_frames() -> Result<(), DatabaseError> {
+        let rows = rows(&[Some(1), Some(2), Some(4), Some(5), Some(9), None]);
+        let frame = frame(
+            WindowFrameUnits::Range,
+            WindowFrameBound::Preceding(DataValue::Int32(2)),
+            WindowFrameBound::Following(DataValue::Int32(1)),
+        );
+        assert_eq!(
+            frame_ranges(&frame, &order_by(true), &rows)?,
+            vec![0..2, 0..2, 1..4, 2..4, 4..5, 5..6]
+        );
+
+        let rows = self::rows(&[Some(9), Some(5), Some(4), Some(2), Some(1)]);
+        assert_eq!(
+            frame_ranges(&frame, &order_by(false), &rows)?,
+            vec![0..1, 1..3, 1..3, 2..5, 3..5]
+        );
+
+        let rows = self::rows(&[Some(i32::MAX - 1), Some(i32::MAX)]);
+        assert_eq!(
+            frame_ranges(&frame, &order_by(true), &rows)?,
+            vec![0..2, 0..2]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn sliding_aggregates() -> Result<(), DatabaseError> {
+        let values = [3, 1, 4, 1, 5]
+            .into_iter()
+            .map(DataValue::Int32)
+            .chain([DataValue::Null])
+            .collect::<Vec<_>>();
+        let mut sum = SlidingSum::new(None);
+        let mut max = SlidingMinMax::new(true);
+        let mut min = SlidingMinMax::new(false);
+        let mut results = Vec::new();
+        for position in 0..values.len() {
+            sum.add(&values[position])?;
+            max.add(&values, position);
+            min.add(&values, position);
+            if position >= 2 {
+                sum.remove(&values[position - 2])?;
+                max.remove_before(position - 1);
+                min.remove_before(position - 1);
+            }
+            results.push((
+                sum.sum(),
+                sum.avg()?,
+                max.result(&values),
+                min.result(&values),
+            ));
+        }
+        assert_eq!(
+            results,
+            vec![
+                (3.into(), 3.0.into(), 3.into(), 3.into()),
+                (4.into(), 2.0.into(), 3.into(), 1.into()),
+                (5.into(), 2.5.into(), 4.into(), 1.into()),
+                (5.into(), 2.5.into(), 4.into(), 1.into()),
+                (6.into(), 3.0.into(), 5.into(), 1.into()),
+                (5.into(), 5.0.into(), 5.into(), 5.into()),
+            ]
+        );
+        Ok(())
+    }
+}
+// GRCOV_EXCL_STOP
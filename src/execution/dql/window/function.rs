@@ -14,19 +14,39 @@
 
 use crate::errors::DatabaseError;
 use crate::execution::dql::aggregate::{create_accumulator, Accumulator};
+use crate::execution::dql::window::frame::{frame_ranges, SlidingMinMax, SlidingSum};
 use crate::expression::agg::AggKind;
-use crate::expression::window::WindowFunctionKind;
+use crate::expression::function::aggregate::ArcAggregateFunctionImpl;
+use crate::expression::window::{
+    WindowFrame, WindowFrameBound, WindowFrameUnits, WindowFunctionKind,
+};
 use crate::expression::ScalarExpression;
+use crate::planner::operator::sort::SortField;
 use crate::types::tuple::Tuple;
 use crate::types::value::DataValue;
 use crate::types::LogicalType;
 use std::ops::Range;
 
+/// When a window function can be evaluated.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) enum Scope {
+    /// Once per peer group, as soon as the group is complete.
+    Peer,
+    /// Once per peer group, after the whole partition has been buffered.
+    PartitionPeer,
+    /// Once over the whole buffered partition.
+    Partition,
+}
+
 pub(super) trait WindowFunction {
     fn reset(&mut self) -> Result<(), DatabaseError> {
         Ok(())
     }
 
+    fn scope(&self) -> Scope {
+        Scope::Peer
+    }
+
     fn evaluate(
         &mut self,
         rows: &mut [(usize, Tuple)],
@@ -83,13 +103,13 @@ impl WindowFunction for Rank {
 struct Aggregate {
     kind: AggKind,
     ty: LogicalType,
-    arg: ScalarExpression,
+    args: Vec<ScalarExpression>,
     accumulator: Option<Box<dyn Accumulator>>,
 }
 
 impl WindowFunction for Aggregate {
     fn reset(&mut self) -> Result<(), DatabaseError> {
-        self.accumulator = Some(create_accumulator(self.kind, &self.ty, false)?);
+        self.accumulator = Some(create_accumulator(&self.kind, &self.ty)?);
         Ok(())
     }
 
@@ -105,7 +125,16 @@ impl WindowFunction for Aggregate {
             unreachable!()
         };
         for (_, row) in &rows[peer.clone()] {
-            accumulator.update_value(&self.arg.eval(Some(row))?)?;
+            if let [arg] = self.args.as_slice() {
+                accumulator.update_value(&arg.eval(Some(row))?)?;
+            } else {
+                let values = self
+                    .args
+                    .iter()
+                    .map(|arg| arg.eval(Some(row)))
+                    .collect::<Result<Vec<_>, _>>()?;
+                accumulator.update_values(&values)?;
+            }
         }
         accumulator.evaluate()?;
         let result = accumulator.result();
@@ -116,25 +145,457 @@ impl WindowFunction for Aggregate {
     }
 }
 
+/// Folds every peer group into a fresh state and merges it into the running state of the
+/// partition, so that all peers see the same result.
+struct UserDefinedAggregate {
+    function: ArcAggregateFunctionImpl,
+    args: Vec<ScalarExpression>,
+    state: DataValue,
+}
+
+impl WindowFunction for UserDefinedAggregate {
+    fn reset(&mut self) -> Result<(), DatabaseError> {
+        self.state = self.function.init()?;
+        Ok(())
+    }
+
+    fn evaluate(
+        &mut self,
+        rows: &mut [(usize, Tuple)],
+        peer: Range<usize>,
+        _peer_start: usize,
+        _peer_index: usize,
+        output_position: usize,
+    ) -> Result<(), DatabaseError> {
+        let mut peer_state = self.function.init()?;
+        let mut values = Vec::with_capacity(self.args.len());
+        for (_, row) in &rows[peer.clone()] {
+            values.clear();
+            for arg in &self.args {
+                values.push(arg.eval(Some(row))?);
+            }
+            self.function.update(&mut peer_state, &values)?;
+        }
+        self.function.merge(&mut self.state, peer_state)?;
+        let result = self.function.finalize(&self.state)?;
+        for (_, row) in &mut rows[peer] {
+            row.values[output_position] = result.clone();
+        }
+        Ok(())
+    }
+}
+
+/// `PERCENT_RANK` and `CUME_DIST`, which relate the peer group to the size of the partition.
+struct Distribution {
+    cume: bool,
+}
+
+impl WindowFunction for Distribution {
+    fn scope(&self) -> Scope {
+        Scope::PartitionPeer
+    }
+
+    fn evaluate(
+        &mut self,
+        rows: &mut [(usize, Tuple)],
+        peer: Range<usize>,
+        peer_start: usize,
+        _peer_index: usize,
+        output_position: usize,
+    ) -> Result<(), DatabaseError> {
+        let partition_rows = rows.len() as f64;
+        let result = if self.cume {
+            peer.end as f64 / partition_rows
+        } else if rows.len() > 1 {
+            peer_start as f64 / (partition_rows - 1.0)
+        } else {
+            0.0
+        };
+        for (_, row) in &mut rows[peer] {
+            row.values[output_position] = DataValue::Float64(result.into());
+        }
+        Ok(())
+    }
+}
+
+fn positive_arg(name: &str, value: DataValue) -> Result<Option<i64>, DatabaseError> {
+    match value.i64() {
+        Some(value) if value <= 0 => Err(DatabaseError::InvalidValue(format!(
+            "argument of {name} must be greater than zero: {value}"
+        ))),
+        value => Ok(value),
+    }
+}
+
+struct Ntile {
+    buckets: ScalarExpression,
+}
+
+impl WindowFunction for Ntile {
+    fn scope(&self) -> Scope {
+        Scope::Partition
+    }
+
+    fn evaluate(
+        &mut self,
+        rows: &mut [(usize, Tuple)],
+        peer: Range<usize>,
+        _peer_start: usize,
+        _peer_index: usize,
+        output_position: usize,
+    ) -> Result<(), DatabaseError> {
+        let rows = &mut rows[peer];
+        let len = rows.len() as i64;
+        for (position, (_, row)) in rows.iter_mut().enumerate() {
+            let Some(buckets) = positive_arg("ntile", self.buckets.eval(Some(&*row))?)? else {
+                row.values[output_position] = DataValue::Null;
+                continue;
+            };
+            // The first `len % buckets` buckets hold one row more than the others.
+            let (size, larger) = (len / buckets, len % buckets);
+            let position = position as i64;
+            let bucket = if position < larger * (size + 1) {
+                position / (size + 1)
+            } else {
+                larger + (position - larger * (size + 1)) / size
+            };
+            row.values[output_position] = DataValue::Int64(bucket + 1);
+        }
+        Ok(())
+    }
+}
+
+/// `LAG` and `LEAD`, a negative offset reads in the opposite direction.
+struct Offset {
+    lead: bool,
+    ignore_nulls: bool,
+    args: Vec<ScalarExpression>,
+}
+
+impl WindowFunction for Offset {
+    fn scope(&self) -> Scope {
+        Scope::Partition
+    }
+
+    fn evaluate(
+        &mut self,
+        rows: &mut [(usize, Tuple)],
+        peer: Range<usize>,
+        _peer_start: usize,
+        _peer_index: usize,
+        output_position: usize,
+    ) -> Result<(), DatabaseError> {
+        let rows = &mut rows[peer];
+        let values = rows
+            .iter()
+            .map(|(_, row)| self.args[0].eval(Some(row)))
+            .collect::<Result<Vec<_>, _>>()?;
+        let not_null = if self.ignore_nulls {
+            (0..values.len())
+                .filter(|position| !values[*position].is_null())
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        for (position, (_, row)) in rows.iter_mut().enumerate() {
+            let offset = match self.args.get(1) {
+                Some(offset) => offset.eval(Some(&*row))?.i64(),
+                None => Some(1),
+            };
+            let Some(offset) = offset else {
+                row.values[output_position] = DataValue::Null;
+                continue;
+            };
+            let forward = self.lead == (offset >= 0);
+            let distance = offset.unsigned_abs() as usize;
+            let target = if distance == 0 {
+                Some(position)
+            } else if self.ignore_nulls {
+                // The `distance`-th value that is not NULL before or after the row.
+                if forward {
+                    let after = not_null.partition_point(|candidate| *candidate <= position);
+                    not_null.get(after + distance - 1).copied()
+                } else {
+                    let before = not_null.partition_point(|candidate| *candidate < position);
+                    before.checked_sub(distance).map(|index| not_null[index])
+                }
+            } else if forward {
+                position
+                    .checked_add(distance)
+                    .filter(|target| *target < values.len())
+            } else {
+                position.checked_sub(distance)
+            };
+            row.values[output_position] = match (target, self.args.get(2)) {
+                (Some(target), _) => values[target].clone(),
+                (None, Some(default)) => default.eval(Some(&*row))?,
+                (None, None) => DataValue::Null,
+            };
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Copy)]
+enum ValuePosition {
+    First,
+    Last,
+    Nth,
+}
+
+/// `FIRST_VALUE`, `LAST_VALUE` and `NTH_VALUE` over the frame of every row.
+struct Value {
+    position: ValuePosition,
+    args: Vec<ScalarExpression>,
+    frame: WindowFrame,
+    order_by: Vec<SortField>,
+}
+
+impl WindowFunction for Value {
+    fn scope(&self) -> Scope {
+        Scope::Partition
+    }
+
+    fn evaluate(
+        &mut self,
+        rows: &mut [(usize, Tuple)],
+        peer: Range<usize>,
+        _peer_start: usize,
+        _peer_index: usize,
+        output_position: usize,
+    ) -> Result<(), DatabaseError> {
+        let rows = &mut rows[peer];
+        let frames = frame_ranges(&self.frame, &self.order_by, rows)?;
+        let mut results = Vec::with_capacity(rows.len());
+        for ((_, row), frame) in rows.iter().zip(frames) {
+            let target = match self.position {
+                _ if frame.is_empty() => None,
+                ValuePosition::First => Some(frame.start),
+                ValuePosition::Last => Some(frame.end - 1),
+                ValuePosition::Nth => positive_arg("nth_value", self.args[1].eval(Some(row))?)?
+                    .map(|nth| frame.start.saturating_add(nth as usize - 1))
+                    .filter(|target| *target < frame.end),
+            };
+            results.push(match target {
+                Some(target) => self.args[0].eval(Some(&rows[target].1))?,
+                None => DataValue::Null,
+            });
+        }
+        for ((_, row), result) in rows.iter_mut().zip(results) {
+            row.values[output_position] = result;
+        }
+        Ok(())
+    }
+}
+
+/// An aggregate over an explicit frame, the frames of a partition are computed up front and
+/// walked with a sliding state: prefix counts for COUNT, running sums for SUM and AVG, a
+/// monotonic deque for MIN and MAX. Other aggregates cannot retract values and are recomputed for every frame.
+struct FramedAggregate {
+    kind: AggKind,
+    ty: LogicalType,
+    args: Vec<ScalarExpression>,
+    frame: WindowFrame,
+    order_by: Vec<SortField>,
+}
+
+impl FramedAggregate {
+    fn recompute(
+        &self,
+        rows: &[(usize, Tuple)],
+        frames: &[Range<usize>],
+    ) -> Result<Vec<DataValue>, DatabaseError> {
+        let mut arg_values = Vec::with_capacity(rows.len());
+        for (_, row) in rows {
+            arg_values.push(
+                self.args
+                    .iter()
+                    .map(|arg| arg.eval(Some(row)))
+                    .collect::<Result<Vec<_>, _>>()?,
+            );
+        }
+        frames
+            .iter()
+            .map(|frame| {
+                let mut accumulator = create_accumulator(&self.kind, &self.ty)?;
+                for values in &arg_values[frame.clone()] {
+                    if let [value] = values.as_slice() {
+                        accumulator.update_value(value)?;
+                    } else {
+                        accumulator.update_values(values)?;
+                    }
+                }
+                accumulator.evaluate()?;
+                Ok(accumulator.result_owned())
+            })
+            .collect()
+    }
+
+    fn sliding(
+        &self,
+        rows: &[(usize, Tuple)],
+        frames: &[Range<usize>],
+    ) -> Result<Vec<DataValue>, DatabaseError> {
+        let Some(arg) = self.args.first() else {
+            unreachable!()
+        };
+        let values = rows
+            .iter()
+            .map(|(_, row)| arg.eval(Some(row)))
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut results = Vec::with_capacity(frames.len());
+        let (mut start, mut end) = (0, 0);
+
+        match &self.kind {
+            AggKind::Count => {
+                let mut counts = Vec::with_capacity(values.len() + 1);
+                counts.push(0);
+                for value in &values {
+                    counts.push(counts[counts.len() - 1] + usize::from(!value.is_null()));
+                }
+                for frame in frames {
+                    results.push(DataValue::Int32(
+                        (counts[frame.end] - counts[frame.start]) as i32,
+                    ));
+                }
+            }
+            AggKind::Sum | AggKind::Avg => {
+                let is_sum = matches!(self.kind, AggKind::Sum);
+                // subtracting the values leaving a frame again loses precision on floating point
+                if (is_sum && self.ty.is_floating_point_numeric())
+                    || values
+                        .iter()
+                        .any(|value| matches!(value, DataValue::Float32(_) | DataValue::Float64(_)))
+                {
+                    return self.recompute(rows, frames);
+                }
+                let mut sum = SlidingSum::new(is_sum.then(|| self.ty.clone()));
+                for frame in frames {
+                    while start < frame.start.min(end) {
+                        sum.remove(&values[start])?;
+                        start += 1;
+                    }
+                    start = start.max(frame.start);
+                    end = end.max(start);
+                    while end < frame.end {
+                        sum.add(&values[end])?;
+                        end += 1;
+                    }
+                    results.push(if is_sum { sum.sum() } else { sum.avg()? });
+                }
+            }
+            AggKind::Min | AggKind::Max => {
+                let mut min_max = SlidingMinMax::new(matches!(self.kind, AggKind::Max));
+                for frame in frames {
+                    min_max.remove_before(frame.start);
+                    end = end.max(frame.start);
+                    while end < frame.end {
+                        min_max.add(&values, end);
+                        end += 1;
+                    }
+                    results.push(min_max.result(&values));
+                }
+            }
+            _ => unreachable!(),
+        }
+        Ok(results)
+    }
+}
+
+impl WindowFunction for FramedAggregate {
+    fn scope(&self) -> Scope {
+        Scope::Partition
+    }
+
+    fn evaluate(
+        &mut self,
+        rows: &mut [(usize, Tuple)],
+        peer: Range<usize>,
+        _peer_start: usize,
+        _peer_index: usize,
+        output_position: usize,
+    ) -> Result<(), DatabaseError> {
+        let rows = &mut rows[peer];
+        let frames = frame_ranges(&self.frame, &self.order_by, rows)?;
+        let results = match &self.kind {
+            AggKind::Count | AggKind::Sum | AggKind::Avg | AggKind::Min | AggKind::Max => {
+                self.sliding(rows, &frames)?
+            }
+            _ => self.recompute(rows, &frames)?,
+        };
+        for ((_, row), result) in rows.iter_mut().zip(results) {
+            row.values[output_position] = result;
+        }
+        Ok(())
+    }
+}
+
+/// Creates the function evaluating `kind`, `frame` is the explicit frame of a function that
+/// reads one and `order_by` the `ORDER BY` of its window.
 pub(super) fn new(
     kind: WindowFunctionKind,
     args: Vec<ScalarExpression>,
     ty: LogicalType,
+    frame: Option<WindowFrame>,
+    order_by: &[SortField],
 ) -> Box<dyn WindowFunction> {
-    match kind {
-        WindowFunctionKind::RowNumber => Box::new(RowNumber),
-        WindowFunctionKind::Rank => Box::new(Rank { dense: false }),
-        WindowFunctionKind::DenseRank => Box::new(Rank { dense: true }),
-        WindowFunctionKind::Aggregate(kind) => {
-            let Some(arg) = args.into_iter().next() else {
+    let value = |position, args, frame: Option<WindowFrame>| -> Box<dyn WindowFunction> {
+        Box::new(Value {
+            position,
+            args,
+            frame: frame.unwrap_or(WindowFrame {
+                units: WindowFrameUnits::Range,
+                start: WindowFrameBound::UnboundedPreceding,
+                end: WindowFrameBound::CurrentRow,
+            }),
+            order_by: order_by.to_vec(),
+        })
+    };
+    match (kind, frame) {
+        (WindowFunctionKind::Aggregate(kind), Some(frame)) => Box::new(FramedAggregate {
+            kind,
+            ty,
+            args,
+            frame,
+            order_by: order_by.to_vec(),
+        }),
+        (WindowFunctionKind::RowNumber, _) => Box::new(RowNumber),
+        (WindowFunctionKind::Rank, _) => Box::new(Rank { dense: false }),
+        (WindowFunctionKind::DenseRank, _) => Box::new(Rank { dense: true }),
+        (WindowFunctionKind::PercentRank, _) => Box::new(Distribution { cume: false }),
+        (WindowFunctionKind::CumeDist, _) => Box::new(Distribution { cume: true }),
+        (WindowFunctionKind::Ntile, _) => {
+            let Some(buckets) = args.into_iter().next() else {
                 unreachable!()
             };
-            Box::new(Aggregate {
-                kind,
-                ty,
-                arg,
-                accumulator: None,
+            Box::new(Ntile { buckets })
+        }
+        (WindowFunctionKind::Lag { ignore_nulls }, _) => Box::new(Offset {
+            lead: false,
+            ignore_nulls,
+            args,
+        }),
+        (WindowFunctionKind::Lead { ignore_nulls }, _) => Box::new(Offset {
+            lead: true,
+            ignore_nulls,
+            args,
+        }),
+        (WindowFunctionKind::FirstValue, frame) => value(ValuePosition::First, args, frame),
+        (WindowFunctionKind::LastValue, frame) => value(ValuePosition::Last, args, frame),
+        (WindowFunctionKind::NthValue, frame) => value(ValuePosition::Nth, args, frame),
+        (WindowFunctionKind::Aggregate(AggKind::UserDefined(function)), None) => {
+            Box::new(UserDefinedAggregate {
+                function,
+                args,
+                state: DataValue::Null,
             })
         }
+        (WindowFunctionKind::Aggregate(kind), None) => Box::new(Aggregate {
+            kind,
+            ty,
+            args,
+            accumulator: None,
+        }),
     }
 }
@@ -16,23 +16,32 @@ pub(crate) mod ddl;
 mod ddl_apply;
 pub(crate) mod dml;
 pub(crate) mod dql;
+pub(crate) mod profile;
 #[cfg(feature = "spill")]
 pub(crate) mod spill;
 
 pub(crate) use ddl_apply::DDLApply;
 
 use self::ddl::add_column::AddColumn;
+use self::ddl::add_constraint::AddConstraint;
 use self::ddl::change_column::ChangeColumn;
 use self::dql::join::nested_loop_join::NestedLoopJoin;
+use self::dql::lateral_apply::LateralApply;
 use self::dql::mark_apply::MarkApply;
 use self::dql::scalar_apply::ScalarApply;
-use crate::db::{ScalaFunctions, TableFunctions};
+use self::profile::ExecProfile;
+use crate::catalog::sequence::SequenceCache;
+use crate::catalog::TableName;
+use crate::db::{AggregateFunctions, ScalaFunctions, TableFunctions};
 use crate::errors::DatabaseError;
 use crate::execution::ddl::create_index::CreateIndex;
+use crate::execution::ddl::create_sequence::CreateSequence;
 use crate::execution::ddl::create_table::CreateTable;
 use crate::execution::ddl::create_view::CreateView;
 use crate::execution::ddl::drop_column::DropColumn;
+use crate::execution::ddl::drop_constraint::DropConstraint;
 use crate::execution::ddl::drop_index::DropIndex;
+use crate::execution::ddl::drop_sequence::DropSequence;
 use crate::execution::ddl::drop_table::DropTable;
 use crate::execution::ddl::drop_view::DropView;
 use crate::execution::ddl::truncate::Truncate;
@@ -46,8 +55,11 @@ use crate::execution::dml::insert::Insert;
 use crate::execution::dml::update::Update;
 use crate::execution::dql::aggregate::hash_agg::HashAggExecutor;
 use crate::execution::dql::aggregate::simple_agg::SimpleAggExecutor;
+#[cfg(feature = "spill")]
+use crate::execution::dql::aggregate::sort_grouping_sets::SortGroupingSetsExecutor;
 use crate::execution::dql::aggregate::stream_agg::StreamAggExecutor;
 use crate::execution::dql::aggregate::stream_distinct::StreamDistinctExecutor;
+use crate::execution::dql::cte_scan::CteScan;
 use crate::execution::dql::describe::Describe;
 use crate::execution::dql::dummy::Dummy;
 use crate::execution::dql::explain::Explain;
@@ -57,8 +69,11 @@ use crate::execution::dql::filter::Filter;
 use crate::execution::dql::function_scan::FunctionScan;
 use crate::execution::dql::index_scan::IndexScan;
 use crate::execution::dql::join::hash_join::HashJoin;
+use crate::execution::dql::join::index_nested_loop_join::IndexNestedLoopJoin;
+use crate::execution::dql::join::sort_merge_join::SortMergeJoin;
 use crate::execution::dql::limit::Limit;
 use crate::execution::dql::projection::Projection;
+use crate::execution::dql::recursive_cte::RecursiveCte;
 use crate::execution::dql::scalar_subquery::ScalarSubquery;
 use crate::execution::dql::seq_scan::SeqScan;
 use crate::execution::dql::set_membership::SetMembership;
@@ -69,7 +84,8 @@ use crate::execution::dql::top_k::TopK;
 use crate::execution::dql::union::Union;
 use crate::execution::dql::values::Values;
 use crate::execution::dql::window::Window;
-use crate::expression::ScalarExpression;
+use crate::expression::visitor_mut::{walk_mut_expr, ExprVisitorMut};
+use crate::expression::{ScalarExpression, SequenceFunction};
 use crate::planner::operator::join::JoinCondition;
 use crate::planner::operator::{Operator, PhysicalOption, PlanImpl};
 use crate::planner::{LogicalPlan, PlanArena};
@@ -78,14 +94,19 @@ use crate::storage::{StatisticsMetaCache, TableCache, Transaction, ViewCache};
 use crate::types::index::RuntimeIndexProbe;
 use crate::types::tuple::{Tuple, TupleLike};
 use crate::types::value::DataValue;
+use std::collections::HashMap;
+use std::ops::Range;
+use std::sync::Arc;
 
 #[derive(Clone, Copy)]
 pub(crate) struct ExecutionContext<'a> {
     table_cache: &'a TableCache,
     view_cache: &'a ViewCache,
     meta_cache: &'a StatisticsMetaCache,
+    sequence_cache: &'a SequenceCache,
     scala_functions: &'a ScalaFunctions,
     table_functions: &'a TableFunctions,
+    aggregate_functions: &'a AggregateFunctions,
 }
 
 impl<'a> ExecutionContext<'a> {
@@ -93,15 +114,19 @@ impl<'a> ExecutionContext<'a> {
         table_cache: &'a TableCache,
         view_cache: &'a ViewCache,
         meta_cache: &'a StatisticsMetaCache,
+        sequence_cache: &'a SequenceCache,
         scala_functions: &'a ScalaFunctions,
         table_functions: &'a TableFunctions,
+        aggregate_functions: &'a AggregateFunctions,
     ) -> Self {
         Self {
             table_cache,
             view_cache,
             meta_cache,
+            sequence_cache,
             scala_functions,
             table_functions,
+            aggregate_functions,
         }
     }
 
@@ -109,6 +134,14 @@ impl<'a> ExecutionContext<'a> {
         self.table_cache
     }
 
+    pub(crate) fn meta_cache(self) -> &'a StatisticsMetaCache {
+        self.meta_cache
+    }
+
+    pub(crate) fn sequence_cache(self) -> &'a SequenceCache {
+        self.sequence_cache
+    }
+
     pub(crate) fn scala_functions(self) -> &'a ScalaFunctions {
         self.scala_functions
     }
@@ -117,10 +150,15 @@ impl<'a> ExecutionContext<'a> {
         self.table_functions
     }
 
+    pub(crate) fn aggregate_functions(self) -> &'a AggregateFunctions {
+        self.aggregate_functions
+    }
+
     fn is_same_context(&self, other: ExecutionContext<'_>) -> bool {
         std::ptr::eq(self.table_cache, other.table_cache)
             && std::ptr::eq(self.view_cache, other.view_cache)
             && std::ptr::eq(self.meta_cache, other.meta_cache)
+            && std::ptr::eq(self.sequence_cache, other.sequence_cache)
             && std::ptr::eq(self.scala_functions, other.scala_functions)
             && std::ptr::eq(self.table_functions, other.table_functions)
     }
@@ -168,6 +206,7 @@ impl<'a, T: Transaction + 'a> Executor<'a, T> {
 #[allow(clippy::large_enum_variant)]
 pub(crate) enum ExecNode<'a, T: Transaction + 'a> {
     AddColumn(AddColumn),
+    AddConstraint(AddConstraint),
     Analyze(Analyze),
     ChangeColumn(ChangeColumn),
     #[cfg(feature = "copy")]
@@ -175,12 +214,16 @@ pub(crate) enum ExecNode<'a, T: Transaction + 'a> {
     #[cfg(feature = "copy")]
     CopyToFile(CopyToFile),
     CreateIndex(CreateIndex),
+    CreateSequence(CreateSequence),
     CreateTable(CreateTable),
     CreateView(CreateView),
+    CteScan(CteScan),
     Delete(Delete),
     Describe(Describe),
     DropColumn(DropColumn),
+    DropConstraint(DropConstraint),
     DropIndex(DropIndex),
+    DropSequence(DropSequence),
     DropTable(DropTable),
     DropView(DropView),
     Dummy(Dummy),
@@ -191,12 +234,15 @@ pub(crate) enum ExecNode<'a, T: Transaction + 'a> {
     FunctionScan(FunctionScan),
     HashAgg(HashAggExecutor),
     HashJoin(HashJoin),
+    IndexNestedLoopJoin(IndexNestedLoopJoin),
     IndexScan(IndexScan<'a, T>),
     Insert(Insert),
+    LateralApply(LateralApply),
     Limit(Limit),
     MarkApply(MarkApply),
     NestedLoopJoin(NestedLoopJoin),
     Projection(Projection),
+    RecursiveCte(RecursiveCte),
     ScalarApply(ScalarApply),
     ScalarSubquery(ScalarSubquery),
     SetMembership(SetMembership),
@@ -205,6 +251,9 @@ pub(crate) enum ExecNode<'a, T: Transaction + 'a> {
     ShowViews(ShowViews<'a, T>),
     SimpleAgg(SimpleAggExecutor),
     Sort(Sort),
+    #[cfg(feature = "spill")]
+    SortGroupingSets(SortGroupingSetsExecutor),
+    SortMergeJoin(SortMergeJoin),
     StreamAgg(StreamAggExecutor),
     StreamDistinct(StreamDistinctExecutor),
     TopK(TopK),
@@ -234,6 +283,9 @@ impl<'a, T: Transaction + 'a> ExecNode<'a, T> {
             ExecNode::AddColumn(exec) => {
                 <AddColumn as ExecutorNode<'a, T>>::next_tuple(exec, arena, plan_arena)
             }
+            ExecNode::AddConstraint(exec) => {
+                <AddConstraint as ExecutorNode<'a, T>>::next_tuple(exec, arena, plan_arena)
+            }
             ExecNode::Analyze(exec) => {
                 <Analyze as ExecutorNode<'a, T>>::next_tuple(exec, arena, plan_arena)
             }
@@ -251,12 +303,18 @@ impl<'a, T: Transaction + 'a> ExecNode<'a, T> {
             ExecNode::CreateIndex(exec) => {
                 <CreateIndex as ExecutorNode<'a, T>>::next_tuple(exec, arena, plan_arena)
             }
+            ExecNode::CreateSequence(exec) => {
+                <CreateSequence as ExecutorNode<'a, T>>::next_tuple(exec, arena, plan_arena)
+            }
             ExecNode::CreateTable(exec) => {
                 <CreateTable as ExecutorNode<'a, T>>::next_tuple(exec, arena, plan_arena)
             }
             ExecNode::CreateView(exec) => {
                 <CreateView as ExecutorNode<'a, T>>::next_tuple(exec, arena, plan_arena)
             }
+            ExecNode::CteScan(exec) => {
+                <CteScan as ExecutorNode<'a, T>>::next_tuple(exec, arena, plan_arena)
+            }
             ExecNode::Delete(exec) => {
                 <Delete as ExecutorNode<'a, T>>::next_tuple(exec, arena, plan_arena)
             }
@@ -266,9 +324,15 @@ impl<'a, T: Transaction + 'a> ExecNode<'a, T> {
             ExecNode::DropColumn(exec) => {
                 <DropColumn as ExecutorNode<'a, T>>::next_tuple(exec, arena, plan_arena)
             }
+            ExecNode::DropConstraint(exec) => {
+                <DropConstraint as ExecutorNode<'a, T>>::next_tuple(exec, arena, plan_arena)
+            }
             ExecNode::DropIndex(exec) => {
                 <DropIndex as ExecutorNode<'a, T>>::next_tuple(exec, arena, plan_arena)
             }
+            ExecNode::DropSequence(exec) => {
+                <DropSequence as ExecutorNode<'a, T>>::next_tuple(exec, arena, plan_arena)
+            }
             ExecNode::DropTable(exec) => {
                 <DropTable as ExecutorNode<'a, T>>::next_tuple(exec, arena, plan_arena)
             }
@@ -297,12 +361,18 @@ impl<'a, T: Transaction + 'a> ExecNode<'a, T> {
             ExecNode::HashJoin(exec) => {
                 <HashJoin as ExecutorNode<'a, T>>::next_tuple(exec, arena, plan_arena)
             }
+            ExecNode::IndexNestedLoopJoin(exec) => {
+                <IndexNestedLoopJoin as ExecutorNode<'a, T>>::next_tuple(exec, arena, plan_arena)
+            }
             ExecNode::IndexScan(exec) => {
                 <IndexScan<'a, T> as ExecutorNode<'a, T>>::next_tuple(exec, arena, plan_arena)
             }
             ExecNode::Insert(exec) => {
                 <Insert as ExecutorNode<'a, T>>::next_tuple(exec, arena, plan_arena)
             }
+            ExecNode::LateralApply(exec) => {
+                <LateralApply as ExecutorNode<'a, T>>::next_tuple(exec, arena, plan_arena)
+            }
             ExecNode::Limit(exec) => {
                 <Limit as ExecutorNode<'a, T>>::next_tuple(exec, arena, plan_arena)
             }
@@ -315,6 +385,9 @@ impl<'a, T: Transaction + 'a> ExecNode<'a, T> {
             ExecNode::Projection(exec) => {
                 <Projection as ExecutorNode<'a, T>>::next_tuple(exec, arena, plan_arena)
             }
+            ExecNode::RecursiveCte(exec) => {
+                <RecursiveCte as ExecutorNode<'a, T>>::next_tuple(exec, arena, plan_arena)
+            }
             ExecNode::ScalarApply(exec) => {
                 <ScalarApply as ExecutorNode<'a, T>>::next_tuple(exec, arena, plan_arena)
             }
@@ -339,6 +412,15 @@ impl<'a, T: Transaction + 'a> ExecNode<'a, T> {
             ExecNode::Sort(exec) => {
                 <Sort as ExecutorNode<'a, T>>::next_tuple(exec, arena, plan_arena)
             }
+            #[cfg(feature = "spill")]
+            ExecNode::SortGroupingSets(exec) => {
+                <SortGroupingSetsExecutor as ExecutorNode<'a, T>>::next_tuple(
+                    exec, arena, plan_arena,
+                )
+            }
+            ExecNode::SortMergeJoin(exec) => {
+                <SortMergeJoin as ExecutorNode<'a, T>>::next_tuple(exec, arena, plan_arena)
+            }
             ExecNode::StreamAgg(exec) => {
                 <StreamAggExecutor as ExecutorNode<'a, T>>::next_tuple(exec, arena, plan_arena)
             }
@@ -376,7 +458,9 @@ pub(crate) struct ExecArena<'a, T: Transaction + 'a> {
     context: Option<ExecutionContext<'a>>,
     transaction: *mut T,
     runtime_probe_stack: Vec<RuntimeIndexProbe>,
+    cte_tables: HashMap<TableName, Arc<Vec<Tuple>>>,
     ddl_apply: Vec<DDLApply>,
+    profile: Option<ExecProfile>,
 }
 
 pub(crate) struct ExecArenaLocalState<'b, 'a, T: Transaction + 'a> {
@@ -424,9 +508,55 @@ impl<'a, T: Transaction + 'a> ExecArena<'a, T> {
             context: None,
             transaction: std::ptr::null_mut(),
             runtime_probe_stack: Vec::new(),
+            cte_tables: HashMap::new(),
             ddl_apply: Vec::new(),
+            profile: None,
+        }
+    }
+}
+
+/// Replaces the `nextval`/`currval` calls of `expr` with the values they produce, leaving
+/// an expression that evaluates like any other for the current row.
+pub(crate) fn resolve_sequence_calls<'a, T: Transaction + 'a>(
+    arena: &mut ExecArena<'a, T>,
+    plan_arena: &mut PlanArena<'a>,
+    expr: &mut ScalarExpression,
+) -> Result<(), DatabaseError> {
+    struct SequenceResolver<'b, 'a, T> {
+        transaction: &'b mut T,
+        table_codec: &'b mut TableCodec,
+        plan_arena: &'b mut PlanArena<'a>,
+        cache: &'b SequenceCache,
+    }
+
+    impl<T: Transaction> ExprVisitorMut<'_> for SequenceResolver<'_, '_, T> {
+        fn visit(&mut self, expr: &mut ScalarExpression) -> Result<(), DatabaseError> {
+            let ScalarExpression::Sequence { function, name } = expr else {
+                return walk_mut_expr(self, expr);
+            };
+            let value = match function {
+                SequenceFunction::NextVal => self.transaction.next_sequence_value(
+                    self.table_codec,
+                    self.plan_arena,
+                    self.cache,
+                    name,
+                )?,
+                SequenceFunction::CurrVal => self.cache.last_value(name)?,
+            };
+            *expr = ScalarExpression::Constant(DataValue::Int64(value));
+            Ok(())
         }
     }
+
+    let cache = arena.context().sequence_cache();
+    let (transaction, table_codec) = arena.transaction_codec_mut();
+    SequenceResolver {
+        transaction,
+        table_codec,
+        plan_arena,
+        cache,
+    }
+    .visit(expr)
 }
 
 pub(crate) fn with_projection_tmp_value<'a, T: Transaction + 'a>(
@@ -476,6 +606,21 @@ impl<'a, T: Transaction + 'a> ExecArena<'a, T> {
         id
     }
 
+    /// The id the next pushed executor gets.
+    pub(crate) fn next_id(&self) -> ExecId {
+        self.nodes.len()
+    }
+
+    /// Drops the executors in `ids`, built by one run of a subtree that is built again. Their
+    /// slots are freed for the rebuild when nothing was pushed after them.
+    pub(crate) fn reclaim(&mut self, ids: Range<ExecId>) {
+        if ids.end == self.nodes.len() {
+            self.nodes.truncate(ids.start);
+        } else {
+            self.nodes[ids].fill_with(|| ExecNode::Empty);
+        }
+    }
+
     pub(crate) fn push_ddl_apply(&mut self, apply: DDLApply) {
         self.ddl_apply.push(apply);
     }
@@ -538,6 +683,36 @@ impl<'a, T: Transaction + 'a> ExecArena<'a, T> {
         self.runtime_probe_stack.len()
     }
 
+    pub(crate) fn set_cte_table(&mut self, table_name: TableName, tuples: Arc<Vec<Tuple>>) {
+        self.cte_tables.insert(table_name, tuples);
+    }
+
+    pub(crate) fn cte_table(&self, table_name: &TableName) -> Option<Arc<Vec<Tuple>>> {
+        self.cte_tables.get(table_name).cloned()
+    }
+
+    /// Starts collecting runtime statistics for the `operators` numbered by
+    /// [`LogicalPlan::assign_explain_ids`].
+    pub(crate) fn enable_profile(&mut self, operators: usize) {
+        self.profile = Some(ExecProfile::new(operators));
+    }
+
+    pub(crate) fn profile(&self) -> Option<&ExecProfile> {
+        self.profile.as_ref()
+    }
+
+    fn bind_profile(&mut self, exec_id: ExecId, explain_id: Option<usize>) {
+        if let (Some(profile), Some(explain_id)) = (self.profile.as_mut(), explain_id) {
+            profile.bind(exec_id, explain_id);
+        }
+    }
+
+    pub(crate) fn add_keys_read(&mut self, keys: usize) {
+        if let Some(profile) = self.profile.as_mut() {
+            profile.add_keys_read(keys);
+        }
+    }
+
     #[inline]
     pub(crate) fn result_tuple(&self) -> &Tuple {
         &self.result.tuple
@@ -583,15 +758,20 @@ impl<'a, T: Transaction + 'a> ExecArena<'a, T> {
         plan_arena: &mut PlanArena<'a>,
     ) -> Result<bool, DatabaseError> {
         self.result.status = None;
+        let span = self.profile.as_mut().and_then(|profile| profile.enter(id));
         let mut node = std::mem::replace(&mut self.nodes[id], ExecNode::Empty);
         let result = node.next_tuple(self, plan_arena);
         self.nodes[id] = node;
         result?;
 
-        match self.result.status.unwrap_or(ExecStatus::End) {
-            ExecStatus::Continue => Ok(true),
-            ExecStatus::End => Ok(false),
+        let has_next = match self.result.status.unwrap_or(ExecStatus::End) {
+            ExecStatus::Continue => true,
+            ExecStatus::End => false,
+        };
+        if let (Some(profile), Some(span)) = (self.profile.as_mut(), span) {
+            profile.exit(span, has_next);
         }
+        Ok(has_next)
     }
 }
 
@@ -626,6 +806,22 @@ pub(crate) fn build_read<'a, T>(
     cache: ExecutionContext<'_>,
     transaction: &T,
 ) -> ExecId
+where
+    T: Transaction + 'a,
+{
+    let explain_id = plan.explain_id;
+    let exec_id = build_read_operator(arena, plan_arena, plan, cache, transaction);
+    arena.bind_profile(exec_id, explain_id);
+    exec_id
+}
+
+fn build_read_operator<'a, T>(
+    arena: &mut ExecArena<'a, T>,
+    plan_arena: &mut PlanArena<'a>,
+    plan: LogicalPlan,
+    cache: ExecutionContext<'_>,
+    transaction: &T,
+) -> ExecId
 where
     T: Transaction + 'a,
 {
@@ -647,7 +843,7 @@ where
         Operator::Aggregate(op) => {
             let input = childrens.pop_only();
 
-            if op.groupby_exprs.is_empty() {
+            if op.groupby_exprs.is_empty() && op.grouping_sets.is_empty() {
                 <SimpleAggExecutor as ReadExecutor<'a, T>>::into_executor(
                     (op, input),
                     arena,
@@ -672,6 +868,25 @@ where
                     cache,
                     transaction,
                 )
+            } else if matches!(
+                physical_option,
+                Some(PhysicalOption {
+                    plan: PlanImpl::SortGroupingSets,
+                    ..
+                })
+            ) {
+                #[cfg(feature = "spill")]
+                {
+                    <SortGroupingSetsExecutor as ReadExecutor<'a, T>>::into_executor(
+                        (op, input),
+                        arena,
+                        plan_arena,
+                        cache,
+                        transaction,
+                    )
+                }
+                #[cfg(not(feature = "spill"))]
+                unreachable!("grouping sets are only sorted with the `spill` feature")
             } else if matches!(
                 physical_option,
                 Some(PhysicalOption {
@@ -713,6 +928,16 @@ where
                 transaction,
             )
         }
+        Operator::LateralApply(op) => {
+            let (left, right) = childrens.pop_twins();
+            <LateralApply as ReadExecutor<'a, T>>::into_executor(
+                (op, left, right),
+                arena,
+                plan_arena,
+                cache,
+                transaction,
+            )
+        }
         Operator::MarkApply(op) => {
             let (left, right) = childrens.pop_twins();
             <MarkApply as ReadExecutor<'a, T>>::into_executor(
@@ -723,6 +948,42 @@ where
                 transaction,
             )
         }
+        Operator::Join(op)
+            if matches!(
+                physical_option,
+                Some(PhysicalOption {
+                    plan: PlanImpl::IndexNestLoopJoin,
+                    ..
+                })
+            ) =>
+        {
+            let (left, right) = childrens.pop_twins();
+            <IndexNestedLoopJoin as ReadExecutor<'a, T>>::into_executor(
+                (op, left, right),
+                arena,
+                plan_arena,
+                cache,
+                transaction,
+            )
+        }
+        Operator::Join(op)
+            if matches!(
+                physical_option,
+                Some(PhysicalOption {
+                    plan: PlanImpl::SortMergeJoin,
+                    ..
+                })
+            ) =>
+        {
+            let (left, right) = childrens.pop_twins();
+            <SortMergeJoin as ReadExecutor<'a, T>>::into_executor(
+                (op, left, right),
+                arena,
+                plan_arena,
+                cache,
+                transaction,
+            )
+        }
         Operator::Join(op) => {
             let use_hash_join = matches!(
                 &op.on,
@@ -870,8 +1131,8 @@ where
             cache,
             transaction,
         ),
-        Operator::Explain => <Explain as ReadExecutor<'a, T>>::into_executor(
-            Explain::from(childrens.pop_only()),
+        Operator::Explain(op) if !op.analyze => <Explain as ReadExecutor<'a, T>>::into_executor(
+            Explain::from((op, childrens.pop_only())),
             arena,
             plan_arena,
             cache,
@@ -891,6 +1152,23 @@ where
             cache,
             transaction,
         ),
+        Operator::RecursiveCte(op) => {
+            let (left, right) = childrens.pop_twins();
+            <RecursiveCte as ReadExecutor<'a, T>>::into_executor(
+                RecursiveCte::from((op, left, right)),
+                arena,
+                plan_arena,
+                cache,
+                transaction,
+            )
+        }
+        Operator::CteScan(op) => <CteScan as ReadExecutor<'a, T>>::into_executor(
+            CteScan::from(op),
+            arena,
+            plan_arena,
+            cache,
+            transaction,
+        ),
         Operator::SetMembership(op) => {
             let (left, right) = childrens.pop_twins();
             <SetMembership as ReadExecutor<'a, T>>::into_executor(
@@ -916,7 +1194,35 @@ where
     T: Transaction + 'a,
 {
     arena.init_context(cache, transaction);
-    let transaction_ref: &T = transaction;
+    build_write_plan(arena, plan_arena, plan, cache, transaction)
+}
+
+fn build_write_plan<'a, T>(
+    arena: &mut ExecArena<'a, T>,
+    plan_arena: &mut PlanArena<'a>,
+    plan: LogicalPlan,
+    cache: ExecutionContext<'a>,
+    transaction: &'a T,
+) -> ExecId
+where
+    T: Transaction + 'a,
+{
+    let explain_id = plan.explain_id;
+    let exec_id = build_write_operator(arena, plan_arena, plan, cache, transaction);
+    arena.bind_profile(exec_id, explain_id);
+    exec_id
+}
+
+fn build_write_operator<'a, T>(
+    arena: &mut ExecArena<'a, T>,
+    plan_arena: &mut PlanArena<'a>,
+    plan: LogicalPlan,
+    cache: ExecutionContext<'a>,
+    transaction: &'a T,
+) -> ExecId
+where
+    T: Transaction + 'a,
+{
     let LogicalPlan {
         operator,
         childrens,
@@ -933,7 +1239,7 @@ where
                 arena,
                 plan_arena,
                 cache,
-                transaction_ref,
+                transaction,
             )
         }
         Operator::Update(op) => {
@@ -944,7 +1250,7 @@ where
                 arena,
                 plan_arena,
                 cache,
-                transaction_ref,
+                transaction,
             )
         }
         Operator::Delete(op) => {
@@ -955,7 +1261,7 @@ where
                 arena,
                 plan_arena,
                 cache,
-                transaction_ref,
+                transaction,
             )
         }
         Operator::AddColumn(op) => <AddColumn as WriteExecutor<'a, T>>::into_executor(
@@ -963,28 +1269,42 @@ where
             arena,
             plan_arena,
             cache,
-            transaction_ref,
+            transaction,
         ),
         Operator::ChangeColumn(op) => <ChangeColumn as WriteExecutor<'a, T>>::into_executor(
             ChangeColumn::from(op),
             arena,
             plan_arena,
             cache,
-            transaction_ref,
+            transaction,
         ),
         Operator::DropColumn(op) => <DropColumn as WriteExecutor<'a, T>>::into_executor(
             DropColumn::from(op),
             arena,
             plan_arena,
             cache,
-            transaction_ref,
+            transaction,
+        ),
+        Operator::AddConstraint(op) => <AddConstraint as WriteExecutor<'a, T>>::into_executor(
+            AddConstraint::from(op),
+            arena,
+            plan_arena,
+            cache,
+            transaction,
+        ),
+        Operator::DropConstraint(op) => <DropConstraint as WriteExecutor<'a, T>>::into_executor(
+            DropConstraint::from(op),
+            arena,
+            plan_arena,
+            cache,
+            transaction,
         ),
         Operator::CreateTable(op) => <CreateTable as WriteExecutor<'a, T>>::into_executor(
             CreateTable::from(op),
             arena,
             plan_arena,
             cache,
-            transaction_ref,
+            transaction,
         ),
         Operator::CreateIndex(op) => {
             let input = childrens.pop_only();
@@ -994,7 +1314,7 @@ where
                 arena,
                 plan_arena,
                 cache,
-                transaction_ref,
+                transaction,
             )
         }
         Operator::CreateView(op) => <CreateView as WriteExecutor<'a, T>>::into_executor(
@@ -1002,35 +1322,49 @@ where
             arena,
             plan_arena,
             cache,
-            transaction_ref,
+            transaction,
+        ),
+        Operator::CreateSequence(op) => <CreateSequence as WriteExecutor<'a, T>>::into_executor(
+            CreateSequence::from(op),
+            arena,
+            plan_arena,
+            cache,
+            transaction,
         ),
         Operator::DropTable(op) => <DropTable as WriteExecutor<'a, T>>::into_executor(
             DropTable::from(op),
             arena,
             plan_arena,
             cache,
-            transaction_ref,
+            transaction,
         ),
         Operator::DropView(op) => <DropView as WriteExecutor<'a, T>>::into_executor(
             DropView::from(op),
             arena,
             plan_arena,
             cache,
-            transaction_ref,
+            transaction,
         ),
         Operator::DropIndex(op) => <DropIndex as WriteExecutor<'a, T>>::into_executor(
             DropIndex::from(op),
             arena,
             plan_arena,
             cache,
-            transaction_ref,
+            transaction,
+        ),
+        Operator::DropSequence(op) => <DropSequence as WriteExecutor<'a, T>>::into_executor(
+            DropSequence::from(op),
+            arena,
+            plan_arena,
+            cache,
+            transaction,
         ),
         Operator::Truncate(op) => <Truncate as WriteExecutor<'a, T>>::into_executor(
             Truncate::from(op),
             arena,
             plan_arena,
             cache,
-            transaction_ref,
+            transaction,
         ),
         #[cfg(feature = "copy")]
         Operator::CopyFromFile(op) => <CopyFromFile as WriteExecutor<'a, T>>::into_executor(
@@ -1038,7 +1372,7 @@ where
             arena,
             plan_arena,
             cache,
-            transaction_ref,
+            transaction,
         ),
         #[cfg(feature = "copy")]
         Operator::CopyToFile(op) => {
@@ -1049,7 +1383,7 @@ where
                 arena,
                 plan_arena,
                 cache,
-                transaction_ref,
+                transaction,
             )
         }
         Operator::Analyze(op) => {
@@ -1060,13 +1394,27 @@ where
                 arena,
                 plan_arena,
                 cache,
-                transaction_ref,
+                transaction,
+            )
+        }
+        Operator::Explain(op) if op.analyze => {
+            let mut input = childrens.pop_only();
+            arena.enable_profile(input.assign_explain_ids());
+            let plan = input.clone();
+            let input = build_write_plan(arena, plan_arena, input, cache, transaction);
+
+            <Explain as ReadExecutor<'a, T>>::into_executor(
+                Explain::analyze(op, plan, input),
+                arena,
+                plan_arena,
+                cache,
+                transaction,
             )
         }
         operator => {
             let mut plan = LogicalPlan::new(operator, *childrens);
             plan.physical_option = physical_option;
-            build_read(arena, plan_arena, plan, cache, transaction_ref)
+            build_read_operator(arena, plan_arena, plan, cache, transaction)
         }
     }
 }
@@ -1079,6 +1427,10 @@ mod test_utils {
         std::sync::LazyLock::new(ScalaFunctions::default);
     static EMPTY_TABLE_FUNCTIONS: std::sync::LazyLock<TableFunctions> =
         std::sync::LazyLock::new(TableFunctions::default);
+    static EMPTY_AGGREGATE_FUNCTIONS: std::sync::LazyLock<AggregateFunctions> =
+        std::sync::LazyLock::new(AggregateFunctions::default);
+    static EMPTY_SEQUENCE_CACHE: std::sync::LazyLock<SequenceCache> =
+        std::sync::LazyLock::new(SequenceCache::default);
 
     pub(crate) fn empty_context<'a>(
         table_cache: &'a TableCache,
@@ -1089,8 +1441,10 @@ mod test_utils {
             table_cache,
             view_cache,
             meta_cache,
+            &EMPTY_SEQUENCE_CACHE,
             &EMPTY_SCALA_FUNCTIONS,
             &EMPTY_TABLE_FUNCTIONS,
+            &EMPTY_AGGREGATE_FUNCTIONS,
         )
     }
 
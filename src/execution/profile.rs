@@ -0,0 +1,100 @@
+// Copyright 2024 KipData/KiteSQL
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Runtime statistics collected for every operator of a plan run by `EXPLAIN ANALYZE`.
+
+use crate::execution::ExecId;
+use std::time::Duration;
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::Instant;
+
+#[derive(Debug, Default, Clone, Copy)]
+pub(crate) struct OperatorStats {
+    /// Rows produced over all loops.
+    pub(crate) rows: usize,
+    /// How many times an executor was built for the operator.
+    pub(crate) loops: usize,
+    /// Time spent inside the operator, including its children.
+    pub(crate) elapsed: Duration,
+    pub(crate) keys_read: usize,
+}
+
+/// Maps executors back to the plan operators they were built from, identified by
+/// [`LogicalPlan::explain_id`](crate::planner::LogicalPlan), and accumulates their statistics.
+#[derive(Debug, Default)]
+pub(crate) struct ExecProfile {
+    exec_operators: Vec<Option<usize>>,
+    stats: Vec<OperatorStats>,
+    current: Option<usize>,
+}
+
+pub(crate) struct ProfileSpan {
+    operator: usize,
+    parent: Option<usize>,
+    #[cfg(not(target_arch = "wasm32"))]
+    started: Instant,
+}
+
+impl ExecProfile {
+    pub(crate) fn new(operators: usize) -> Self {
+        Self {
+            exec_operators: Vec::new(),
+            stats: vec![OperatorStats::default(); operators],
+            current: None,
+        }
+    }
+
+    pub(crate) fn bind(&mut self, exec_id: ExecId, operator: usize) {
+        if self.exec_operators.len() <= exec_id {
+            self.exec_operators.resize(exec_id + 1, None);
+        }
+        self.exec_operators[exec_id] = Some(operator);
+        self.stats[operator].loops += 1;
+    }
+
+    pub(crate) fn enter(&mut self, exec_id: ExecId) -> Option<ProfileSpan> {
+        let operator = self.exec_operators.get(exec_id).copied().flatten()?;
+
+        Some(ProfileSpan {
+            operator,
+            parent: self.current.replace(operator),
+            #[cfg(not(target_arch = "wasm32"))]
+            started: Instant::now(),
+        })
+    }
+
+    pub(crate) fn exit(&mut self, span: ProfileSpan, produced: bool) {
+        let stats = &mut self.stats[span.operator];
+
+        if produced {
+            stats.rows += 1;
+        }
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            stats.elapsed += span.started.elapsed();
+        }
+        self.current = span.parent;
+    }
+
+    /// Charges keys read from storage to the operator currently being executed.
+    pub(crate) fn add_keys_read(&mut self, keys: usize) {
+        if let Some(operator) = self.current {
+            self.stats[operator].keys_read += keys;
+        }
+    }
+
+    pub(crate) fn stats(&self, operator: usize) -> Option<&OperatorStats> {
+        self.stats.get(operator)
+    }
+}
@@ -12,15 +12,33 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::expression::function::aggregate::ArcAggregateFunctionImpl;
 use kite_sql_serde_macros::ReferenceSerialization;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, ReferenceSerialization)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, ReferenceSerialization)]
 pub enum AggKind {
     Avg,
     Max,
     Min,
     Sum,
     Count,
+    /// `STRING_AGG(value, delimiter)`, also bound from MySQL's `GROUP_CONCAT`.
+    StringAgg,
+    BoolAnd,
+    BoolOr,
+    StddevPop,
+    StddevSamp,
+    VarPop,
+    VarSamp,
+    /// `GROUPING(expr, ...)`, a bit mask of the arguments rolled up by the current grouping set.
+    Grouping,
+    /// `JSON_AGG(value)`, the values as a JSON array.
+    JsonAgg,
+    /// `JSON_OBJECT_AGG(key, value)`, the pairs as a JSON object.
+    JsonObjectAgg,
+    /// `ARRAY_AGG(value)`, the values as an array.
+    ArrayAgg,
+    UserDefined(ArcAggregateFunctionImpl),
 }
 
 impl AggKind {
@@ -31,27 +49,49 @@ impl AggKind {
             "min" => Some(Self::Min),
             "sum" => Some(Self::Sum),
             "count" => Some(Self::Count),
+            "string_agg" | "group_concat" => Some(Self::StringAgg),
+            "bool_and" => Some(Self::BoolAnd),
+            "bool_or" => Some(Self::BoolOr),
+            "stddev_pop" => Some(Self::StddevPop),
+            "stddev" | "stddev_samp" => Some(Self::StddevSamp),
+            "var_pop" => Some(Self::VarPop),
+            "variance" | "var_samp" => Some(Self::VarSamp),
+            "grouping" => Some(Self::Grouping),
+            "json_agg" => Some(Self::JsonAgg),
+            "json_object_agg" => Some(Self::JsonObjectAgg),
+            "array_agg" => Some(Self::ArrayAgg),
             _ => None,
         }
     }
 
-    pub(crate) fn name(self) -> &'static str {
+    pub(crate) fn name(&self) -> &str {
         match self {
             Self::Avg => "avg",
             Self::Max => "max",
             Self::Min => "min",
             Self::Sum => "sum",
             Self::Count => "count",
+            Self::StringAgg => "string_agg",
+            Self::BoolAnd => "bool_and",
+            Self::BoolOr => "bool_or",
+            Self::StddevPop => "stddev_pop",
+            Self::StddevSamp => "stddev_samp",
+            Self::VarPop => "var_pop",
+            Self::VarSamp => "var_samp",
+            Self::Grouping => "grouping",
+            Self::JsonAgg => "json_agg",
+            Self::JsonObjectAgg => "json_object_agg",
+            Self::ArrayAgg => "array_agg",
+            Self::UserDefined(function) => &function.summary().name,
         }
     }
 
+    /// Whether `DISTINCT` changes the result, it is a no-op for MIN, MAX and the boolean
+    /// aggregates.
     pub fn allow_distinct(&self) -> bool {
-        match self {
-            AggKind::Avg => false,
-            AggKind::Max => false,
-            AggKind::Min => false,
-            AggKind::Sum => true,
-            AggKind::Count => true,
-        }
+        !matches!(
+            self,
+            AggKind::Max | AggKind::Min | AggKind::BoolAnd | AggKind::BoolOr | AggKind::Grouping
+        )
     }
 }
@@ -0,0 +1,249 @@
+// Copyright 2024 KipData/KiteSQL
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::errors::DatabaseError;
+use crate::expression::BinaryOperator;
+use crate::types::evaluator::BinaryEvaluatorRef;
+use crate::types::value::DataValue;
+use kite_sql_serde_macros::ReferenceSerialization;
+
+/// The array operations of [`ScalarExpression::Array`](crate::expression::ScalarExpression::Array).
+/// They are generic over the element type, so unlike the registered scalar functions they are
+/// bound directly: the binder casts the arguments to a common array type beforehand.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, ReferenceSerialization)]
+pub enum ArrayFunction {
+    /// `ARRAY[a, b, ...]`
+    Construct,
+    /// `array[index]`, 1-based.
+    Element,
+    /// `array_length(array [, dimension])`
+    Length,
+    /// `array_append(array, element)`
+    Append,
+    /// `left @> right`, also used for `right <@ left`.
+    Contains,
+    /// `value <op> ANY(array)`
+    Any {
+        op: BinaryOperator,
+        evaluator: BinaryEvaluatorRef,
+    },
+    /// `value <op> ALL(array)`
+    All {
+        op: BinaryOperator,
+        evaluator: BinaryEvaluatorRef,
+    },
+}
+
+impl ArrayFunction {
+    pub(crate) fn eval(&self, mut values: Vec<DataValue>) -> Result<DataValue, DatabaseError> {
+        Ok(match self {
+            ArrayFunction::Construct => DataValue::Array(values),
+            ArrayFunction::Element => match (&values[0], &values[1]) {
+                (DataValue::Array(elements), DataValue::Int64(index)) => usize::try_from(*index)
+                    .ok()
+                    .and_then(|index| index.checked_sub(1))
+                    .and_then(|index| elements.get(index).cloned())
+                    .unwrap_or(DataValue::Null),
+                _ => DataValue::Null,
+            },
+            ArrayFunction::Length => {
+                let dimension = match values.get(1) {
+                    None => 1,
+                    Some(DataValue::Int64(dimension)) => *dimension,
+                    Some(_) => return Ok(DataValue::Null),
+                };
+                let mut value = &values[0];
+                for _ in 1..dimension {
+                    value = match value {
+                        DataValue::Array(elements) => elements.first().unwrap_or(&DataValue::Null),
+                        _ => &DataValue::Null,
+                    };
+                }
+                match value {
+                    DataValue::Array(elements) if dimension > 0 && !elements.is_empty() => {
+                        DataValue::Int32(elements.len() as i32)
+                    }
+                    _ => DataValue::Null,
+                }
+            }
+            ArrayFunction::Append => {
+                let element = values.pop().unwrap();
+                match values.pop().unwrap() {
+                    DataValue::Array(mut elements) => {
+                        elements.push(element);
+                        DataValue::Array(elements)
+                    }
+                    _ => DataValue::Array(vec![element]),
+                }
+            }
+            ArrayFunction::Contains => match (&values[0], &values[1]) {
+                (DataValue::Array(left), DataValue::Array(right)) => DataValue::Boolean(
+                    right
+                        .iter()
+                        .all(|value| !value.is_null() && left.contains(value)),
+                ),
+                _ => DataValue::Null,
+            },
+            ArrayFunction::Any { evaluator, .. } => {
+                Self::quantify(evaluator, &values[0], &values[1], true)?
+            }
+            ArrayFunction::All { evaluator, .. } => {
+                Self::quantify(evaluator, &values[0], &values[1], false)?
+            }
+        })
+    }
+
+    /// Compares `value` with every element, stopping at the first comparison equal to `any`.
+    /// Otherwise the result is `NULL` if a comparison was unknown, and `!any` if none was.
+    fn quantify(
+        evaluator: &BinaryEvaluatorRef,
+        value: &DataValue,
+        array: &DataValue,
+        any: bool,
+    ) -> Result<DataValue, DatabaseError> {
+        let DataValue::Array(elements) = array else {
+            return Ok(DataValue::Null);
+        };
+        let mut saw_null = false;
+
+        for element in elements {
+            match evaluator.binary_eval(value, element)? {
+                DataValue::Boolean(result) if result == any => {
+                    return Ok(DataValue::Boolean(any));
+                }
+                DataValue::Boolean(_) => (),
+                _ => saw_null = true,
+            }
+        }
+        Ok(if saw_null {
+            DataValue::Null
+        } else {
+            DataValue::Boolean(!any)
+        })
+    }
+
+    pub(crate) fn output_name(&self, args: &[String]) -> String {
+        match self {
+            ArrayFunction::Construct => format!("array[{}]", args.join(", ")),
+            ArrayFunction::Element => format!("{}[{}]", args[0], args[1]),
+            ArrayFunction::Length => format!("array_length({})", args.join(", ")),
+            ArrayFunction::Append => format!("array_append({})", args.join(", ")),
+            ArrayFunction::Contains => format!("({} @> {})", args[0], args[1]),
+            ArrayFunction::Any { op, .. } => format!("({} {} any({}))", args[0], op, args[1]),
+            ArrayFunction::All { op, .. } => format!("({} {} all({}))", args[0], op, args[1]),
+        }
+    }
+}
+
+// GRCOV_EXCL_START
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod test {
+    use super::*;
+    use crate::types::evaluator::binary_create;
+    use crate::types::LogicalType;
+    use std::borrow::Cow;
+
+    fn int_array(values: &[Option<i32>]) -> DataValue {
+        DataValue::Array(
+            values
+                .iter()
+                .map(|value| value.map(DataValue::Int32).unwrap_or(DataValue::Null))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn test_array_functions() -> Result<(), DatabaseError> {
+        let array = int_array(&[Some(1), None, Some(3)]);
+
+        assert_eq!(
+            ArrayFunction::Element.eval(vec![array.clone(), DataValue::Int64(3)])?,
+            DataValue::Int32(3)
+        );
+        for index in [0, 4, -1] {
+            assert_eq!(
+                ArrayFunction::Element.eval(vec![array.clone(), DataValue::Int64(index)])?,
+                DataValue::Null
+            );
+        }
+        assert_eq!(
+            ArrayFunction::Length.eval(vec![array.clone()])?,
+            DataValue::Int32(3)
+        );
+        assert_eq!(
+            ArrayFunction::Length.eval(vec![
+                DataValue::Array(vec![int_array(&[Some(1), Some(2)])]),
+                DataValue::Int64(2)
+            ])?,
+            DataValue::Int32(2)
+        );
+        assert_eq!(
+            ArrayFunction::Length.eval(vec![int_array(&[])])?,
+            DataValue::Null
+        );
+        assert_eq!(
+            ArrayFunction::Append.eval(vec![DataValue::Null, DataValue::Int32(1)])?,
+            int_array(&[Some(1)])
+        );
+        assert_eq!(
+            ArrayFunction::Contains.eval(vec![array.clone(), int_array(&[Some(3), Some(1)])])?,
+            DataValue::Boolean(true)
+        );
+        assert_eq!(
+            ArrayFunction::Contains.eval(vec![array.clone(), int_array(&[None])])?,
+            DataValue::Boolean(false)
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_array_quantifiers() -> Result<(), DatabaseError> {
+        let evaluator = binary_create(Cow::Owned(LogicalType::Integer), BinaryOperator::Eq)?;
+        let any = ArrayFunction::Any {
+            op: BinaryOperator::Eq,
+            evaluator: evaluator.clone(),
+        };
+        let all = ArrayFunction::All {
+            op: BinaryOperator::Eq,
+            evaluator,
+        };
+        let array = int_array(&[Some(1), None]);
+
+        assert_eq!(
+            any.eval(vec![DataValue::Int32(1), array.clone()])?,
+            DataValue::Boolean(true)
+        );
+        assert_eq!(
+            any.eval(vec![DataValue::Int32(2), array.clone()])?,
+            DataValue::Null
+        );
+        assert_eq!(
+            any.eval(vec![DataValue::Int32(2), int_array(&[])])?,
+            DataValue::Boolean(false)
+        );
+        assert_eq!(
+            all.eval(vec![DataValue::Int32(2), array.clone()])?,
+            DataValue::Boolean(false)
+        );
+        assert_eq!(all.eval(vec![DataValue::Int32(1), array])?, DataValue::Null);
+        assert_eq!(
+            all.eval(vec![DataValue::Null, int_array(&[])])?,
+            DataValue::Boolean(true)
+        );
+
+        Ok(())
+    }
+}
+// GRCOV_EXCL_STOP
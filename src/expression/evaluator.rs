@@ -251,6 +251,14 @@ impl ScalarExpression {
                 value.cast(inner.return_type())
             }
             ScalarExpression::Empty => unreachable!(),
+            ScalarExpression::Sequence { function, .. } => Err(DatabaseError::UnsupportedStmt(
+                format!("{function}() can only be used in a SELECT list or a column default"),
+            )),
+            ScalarExpression::Array { function, args, .. } => function.eval(
+                args.iter()
+                    .map(|arg| arg.eval(tuple))
+                    .collect::<Result<_, _>>()?,
+            ),
             ScalarExpression::If {
                 condition,
                 left_expr,
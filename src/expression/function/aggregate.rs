@@ -0,0 +1,70 @@
+// Copyright 2024 KipData/KiteSQL
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::errors::DatabaseError;
+use crate::expression::function::FunctionSummary;
+use crate::types::value::DataValue;
+use crate::types::LogicalType;
+use std::fmt::Debug;
+use std::hash::{Hash, Hasher};
+use std::ops::Deref;
+use std::sync::Arc;
+
+#[derive(Debug, Clone)]
+pub struct ArcAggregateFunctionImpl(pub Arc<dyn AggregateFunctionImpl>);
+
+impl Deref for ArcAggregateFunctionImpl {
+    type Target = dyn AggregateFunctionImpl;
+
+    fn deref(&self) -> &Self::Target {
+        self.0.as_ref()
+    }
+}
+
+impl PartialEq for ArcAggregateFunctionImpl {
+    fn eq(&self, other: &Self) -> bool {
+        self.summary() == other.summary()
+    }
+}
+
+impl Eq for ArcAggregateFunctionImpl {}
+
+impl Hash for ArcAggregateFunctionImpl {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.summary().hash(state);
+    }
+}
+
+/// A user-defined aggregate function.
+///
+/// The intermediate state of a group is a plain [`DataValue`] (e.g. a `DataValue::Tuple` of
+/// partial sums), so it can be written to spill files like any other value. Every row, NULL
+/// arguments included, is passed to [`update`](AggregateFunctionImpl::update).
+pub trait AggregateFunctionImpl: Debug + Send + Sync {
+    /// Returns the state of an empty group.
+    fn init(&self) -> Result<DataValue, DatabaseError>;
+
+    /// Folds the arguments of one row into `state`.
+    fn update(&self, state: &mut DataValue, args: &[DataValue]) -> Result<(), DatabaseError>;
+
+    /// Combines the partial state `other` into `state`.
+    fn merge(&self, state: &mut DataValue, other: DataValue) -> Result<(), DatabaseError>;
+
+    /// Produces the result of a group, may be called repeatedly for running aggregates.
+    fn finalize(&self, state: &DataValue) -> Result<DataValue, DatabaseError>;
+
+    fn return_type(&self) -> &LogicalType;
+
+    fn summary(&self) -> &FunctionSummary;
+}
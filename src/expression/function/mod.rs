@@ -16,6 +16,7 @@ use crate::types::LogicalType;
 use kite_sql_serde_macros::ReferenceSerialization;
 use std::sync::Arc;
 
+pub mod aggregate;
 pub mod scala;
 pub mod table;
 
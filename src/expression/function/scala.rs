@@ -49,7 +49,7 @@ pub struct ScalarFunction {
 
 impl PartialEq for ScalarFunction {
     fn eq(&self, other: &Self) -> bool {
-        self.summary() == other.summary()
+        self.summary() == other.summary() && self.args == other.args
     }
 }
 
@@ -73,6 +73,18 @@ pub trait ScalarFunctionImpl: Debug + Send + Sync {
     fn return_type(&self) -> &LogicalType;
 
     fn summary(&self) -> &FunctionSummary;
+
+    /// Whether the last of `summary().arg_types` may repeat, as in `concat(a, b, ...)`.
+    fn is_variadic(&self) -> bool {
+        false
+    }
+
+    /// Whether a call with constant arguments yields the same value for the whole statement,
+    /// so the planner may evaluate it up front, e.g. to turn `ts >= now() - interval '1 day'`
+    /// into an index range.
+    fn is_constant_foldable(&self) -> bool {
+        false
+    }
 }
 
 impl ScalarFunction {
@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::catalog::ColumnCatalog;
 use crate::errors::DatabaseError;
 use crate::expression::function::FunctionSummary;
 use crate::expression::ScalarExpression;
@@ -68,6 +69,12 @@ pub trait TableFunctionImpl: Debug + Send + Sync {
     fn summary(&self) -> &FunctionSummary;
 
     fn output_schema_into(&self, table_arena: &mut TableArena, schema: &mut Schema);
+
+    /// Output columns the binder allocates for every call, for functions that leave the
+    /// registered schema empty so they take no column ids in the table arena.
+    fn bound_output_columns(&self) -> Vec<ColumnCatalog> {
+        Vec::new()
+    }
 }
 
 impl TableFunction {
@@ -13,6 +13,7 @@
 // limitations under the License.
 
 use self::agg::AggKind;
+use self::array::ArrayFunction;
 use crate::catalog::{ColumnCatalog, ColumnDesc, ColumnRef};
 use crate::errors::DatabaseError;
 use crate::expression::function::scala::ScalarFunction;
@@ -23,8 +24,8 @@ use crate::iter_ext::Itertools;
 use crate::planner::operator::sort::SortField;
 use crate::planner::{MetaArena, PlanArena};
 use crate::types::evaluator::{
-    binary_create, cast_create, unary_create, BinaryEvaluatorRef, CastEvaluatorRef,
-    UnaryEvaluatorRef,
+    binary_create, cast_create, temporal_binary_create, unary_create, BinaryEvaluatorRef,
+    CastEvaluatorRef, UnaryEvaluatorRef,
 };
 use crate::types::value::DataValue;
 use crate::types::{CharLengthUnits, LogicalType};
@@ -38,6 +39,7 @@ use std::sync::Arc;
 use std::{fmt, mem};
 
 pub mod agg;
+pub mod array;
 mod evaluator;
 pub mod function;
 pub mod range_detacher;
@@ -100,6 +102,10 @@ pub enum ScalarExpression {
         distinct: bool,
         kind: AggKind,
         args: Vec<ScalarExpression>,
+        /// `FILTER (WHERE ...)`, only rows for which it is true are aggregated.
+        filter: Option<Box<ScalarExpression>>,
+        /// `ORDER BY` inside the call, the order in which rows are fed to the aggregate.
+        order_by: Vec<SortField>,
         ty: LogicalType,
     },
     In {
@@ -159,6 +165,33 @@ pub enum ScalarExpression {
         ty: LogicalType,
     },
     WindowCall(window::WindowCall),
+    /// `nextval('name')` or `currval('name')`, which read and advance stored sequences and
+    /// are therefore resolved by the executors that own the transaction.
+    Sequence {
+        function: SequenceFunction,
+        name: String,
+    },
+    /// Array construction, subscripts, containment and `ANY`/`ALL` over an array.
+    Array {
+        function: ArrayFunction,
+        args: Vec<ScalarExpression>,
+        ty: LogicalType,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, ReferenceSerialization)]
+pub enum SequenceFunction {
+    NextVal,
+    CurrVal,
+}
+
+impl fmt::Display for SequenceFunction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SequenceFunction::NextVal => f.write_str("nextval"),
+            SequenceFunction::CurrVal => f.write_str("currval"),
+        }
+    }
 }
 
 impl From<DataValue> for ScalarExpression {
@@ -339,6 +372,10 @@ impl ExprVisitorMut<'_> for BindEvaluator<'_, '_> {
 
         let left_ty = left_expr.return_type(self.arena).into_owned();
         let right_ty = right_expr.return_type(self.arena).into_owned();
+        if let Some((temporal_evaluator, _)) = temporal_binary_create(&left_ty, &right_ty, op) {
+            *evaluator = Some(temporal_evaluator);
+            return Ok(());
+        }
         let ty = LogicalType::max_logical_type(&left_ty, &right_ty)?;
         let fn_cast =
             |expr: &mut ScalarExpression, ty: &LogicalType| -> Result<(), DatabaseError> {
@@ -369,6 +406,8 @@ impl ExprVisitor<'_> for HasCountStar {
         _distinct: bool,
         _kind: &'_ AggKind,
         args: &'_ [ScalarExpression],
+        _filter: Option<&'_ ScalarExpression>,
+        _order_by: &'_ [SortField],
         _ty: &'_ LogicalType,
     ) -> Result<(), DatabaseError> {
         if args.len() == 1 {
@@ -515,6 +554,8 @@ impl ScalarExpression {
                 Cow::Owned(LogicalType::Varchar(None, CharLengthUnits::Characters))
             }
             ScalarExpression::Position { .. } => Cow::Owned(LogicalType::Integer),
+            ScalarExpression::Sequence { .. } => Cow::Owned(LogicalType::Bigint),
+            ScalarExpression::Array { ty, .. } => Cow::Borrowed(ty),
             ScalarExpression::Trim { .. } => {
                 Cow::Owned(LogicalType::Varchar(None, CharLengthUnits::Characters))
             }
@@ -678,6 +719,8 @@ impl ScalarExpression {
                 _distinct: bool,
                 _kind: &'a AggKind,
                 args: &'a [ScalarExpression],
+                _filter: Option<&'a ScalarExpression>,
+                _order_by: &'a [SortField],
                 _ty: &'a LogicalType,
             ) -> Result<(), DatabaseError> {
                 for arg in args {
@@ -717,6 +760,58 @@ impl ScalarExpression {
         Ok(checker.0)
     }
 
+    pub fn has_sequence_call(&self) -> Result<bool, DatabaseError> {
+        struct SequenceCallChecker(bool);
+
+        impl<'a> ExprVisitor<'a> for SequenceCallChecker {
+            fn visit(&mut self, expr: &'a ScalarExpression) -> Result<(), DatabaseError> {
+                if !self.0 {
+                    walk_expr(self, expr)?;
+                }
+                Ok(())
+            }
+
+            fn visit_sequence(
+                &mut self,
+                _function: &'a SequenceFunction,
+                _name: &'a String,
+            ) -> Result<(), DatabaseError> {
+                self.0 = true;
+                Ok(())
+            }
+        }
+
+        let mut checker = SequenceCallChecker(false);
+        checker.visit(self)?;
+        Ok(checker.0)
+    }
+
+    pub fn references_sequence(&self, sequence_name: &str) -> Result<bool, DatabaseError> {
+        struct SequenceReferenceChecker<'b>(&'b str, bool);
+
+        impl<'a> ExprVisitor<'a> for SequenceReferenceChecker<'_> {
+            fn visit(&mut self, expr: &'a ScalarExpression) -> Result<(), DatabaseError> {
+                if !self.1 {
+                    walk_expr(self, expr)?;
+                }
+                Ok(())
+            }
+
+            fn visit_sequence(
+                &mut self,
+                _function: &'a SequenceFunction,
+                name: &'a String,
+            ) -> Result<(), DatabaseError> {
+                self.1 |= name == self.0;
+                Ok(())
+            }
+        }
+
+        let mut checker = SequenceReferenceChecker(sequence_name, false);
+        checker.visit(self)?;
+        Ok(checker.1)
+    }
+
     fn output_name_by<N: fmt::Display>(&self, fn_display: &impl Fn(ColumnRef) -> N) -> String {
         match self {
             ScalarExpression::Constant(value) => format!("{value}"),
@@ -757,12 +852,22 @@ impl ScalarExpression {
                 args,
                 kind,
                 distinct,
+                filter,
+                order_by,
                 ..
             } => {
-                let args_str = args
+                let mut args_str = args
                     .iter()
                     .map(|expr| expr.output_name_by(fn_display))
                     .join(", ");
+                if !order_by.is_empty() {
+                    args_str.push_str(" order by ");
+                    args_str.push_str(&order_by.iter().map(ToString::to_string).join(", "));
+                }
+                let filter = filter
+                    .as_ref()
+                    .map(|filter| format!(" filter (where {})", filter.output_name_by(fn_display)))
+                    .unwrap_or_default();
                 let op = |allow_distinct, distinct| {
                     if allow_distinct && distinct {
                         "distinct "
@@ -770,12 +875,13 @@ impl ScalarExpression {
                         ""
                     }
                 };
-                format!(
-                    "{:?}({}{})",
-                    kind,
-                    op(kind.allow_distinct(), *distinct),
-                    args_str
-                )
+                let distinct = op(kind.allow_distinct(), *distinct);
+                match kind {
+                    AggKind::UserDefined(_) => {
+                        format!("{}({}{}){}", kind.name(), distinct, args_str, filter)
+                    }
+                    kind => format!("{kind:?}({distinct}{args_str}){filter}"),
+                }
             }
             ScalarExpression::WindowCall(window) => {
                 let args = window
@@ -808,7 +914,18 @@ impl ScalarExpression {
                             .join(", ")
                     ));
                 }
-                format!("{function}({args}) over ({})", spec.join(" "))
+                if let Some(frame) = &window.spec.frame {
+                    spec.push(frame.to_string().to_lowercase());
+                }
+                let null_treatment = match window.function.kind {
+                    window::WindowFunctionKind::Lag { ignore_nulls: true }
+                    | window::WindowFunctionKind::Lead { ignore_nulls: true } => " ignore nulls",
+                    _ => "",
+                };
+                format!(
+                    "{function}({args}){null_treatment} over ({})",
+                    spec.join(" ")
+                )
             }
             ScalarExpression::In {
                 args,
@@ -898,6 +1015,13 @@ impl ScalarExpression {
                 )
             }
             ScalarExpression::Empty => unreachable!(),
+            ScalarExpression::Sequence { function, name } => format!("{function}('{name}')"),
+            ScalarExpression::Array { function, args, .. } => function.output_name(
+                &args
+                    .iter()
+                    .map(|expr| expr.output_name_by(fn_display))
+                    .collect_vec(),
+            ),
             ScalarExpression::Tuple(args) => {
                 let args_str = args
                     .iter()
@@ -1107,7 +1231,7 @@ impl fmt::Display for UnaryOperator {
 mod test {
     use crate::catalog::{ColumnCatalog, ColumnDesc};
     use crate::db::test::build_table;
-    use crate::db::{ScalaFunctions, TableFunctions};
+    use crate::db::{AggregateFunctions, ScalaFunctions, TableFunctions};
     use crate::errors::DatabaseError;
     use crate::expression::agg::AggKind;
     use crate::expression::function::scala::{
@@ -1121,6 +1245,7 @@ mod test {
     use crate::expression::{AliasType, BinaryOperator, ScalarExpression, UnaryOperator};
     use crate::function::current_date::CurrentDate;
     use crate::function::numbers::Numbers;
+    use crate::planner::operator::sort::SortField;
     use crate::planner::{PlanArena, TableArenaCell};
     use crate::serdes::{ReferenceDecodeContext, ReferenceSerialization, ReferenceTables};
     use crate::storage::rocksdb::RocksStorage;
@@ -1199,6 +1324,7 @@ mod test {
         let current_date = CurrentDate::new();
         scala_functions.insert(current_date.summary().clone(), current_date);
         let mut table_functions = TableFunctions::default();
+        let aggregate_functions = AggregateFunctions::default();
         let numbers = Numbers::new();
         let mut schema = Vec::new();
         numbers.output_schema_into(table_arena.borrow_mut(), &mut schema);
@@ -1225,6 +1351,7 @@ mod test {
             Some((&transaction, &table_cache)),
             &scala_functions,
             &table_functions,
+            &aggregate_functions,
         );
 
         fn_assert(
@@ -1378,6 +1505,8 @@ mod test {
                 distinct: true,
                 kind: AggKind::Avg,
                 args: vec![ScalarExpression::Empty],
+                filter: Some(Box::new(ScalarExpression::Empty)),
+                order_by: vec![SortField::new(ScalarExpression::Empty, false, true)],
                 ty: LogicalType::Double,
             },
             Some(&context),
@@ -1611,6 +1740,7 @@ mod test {
                 spec: WindowSpec {
                     partition_by: vec![ScalarExpression::Constant(2.into())],
                     order_by: vec![ScalarExpression::Constant(3.into()).desc()],
+                    frame: None,
                 },
             }),
             Some(&context),
@@ -15,8 +15,10 @@
 use crate::catalog::ColumnRef;
 use crate::errors::DatabaseError;
 use crate::expression::{BinaryOperator, ScalarExpression};
+use crate::function::json_path::split_json_path;
 use crate::iter_ext::Itertools;
 use crate::planner::PlanArena;
+use crate::types::index::IndexPathStep;
 use crate::types::value::DataValue;
 use crate::types::{ColumnId, LogicalType};
 use kite_sql_serde_macros::ReferenceSerialization;
@@ -215,6 +217,8 @@ impl Range {
 pub struct RangeDetacher<'a, 'p> {
     table_name: &'a str,
     column_id: &'a ColumnId,
+    /// The JSON path over the column and the type of its values, see [`Self::with_json_path`].
+    json_path: Option<(&'a [IndexPathStep], &'a LogicalType)>,
     arena: &'a PlanArena<'p>,
 }
 
@@ -227,10 +231,33 @@ impl<'a, 'p> RangeDetacher<'a, 'p> {
         Self {
             table_name,
             column_id,
+            json_path: None,
             arena,
         }
     }
 
+    /// Detaches the ranges of a JSON path over the column, as an index on `(doc ->> 'name')`
+    /// stores, instead of those of the column itself. An empty path keeps the column.
+    pub(crate) fn with_json_path(
+        mut self,
+        json_path: &'a [IndexPathStep],
+        value_ty: &'a LogicalType,
+    ) -> Self {
+        if !json_path.is_empty() {
+            self.json_path = Some((json_path, value_ty));
+        }
+        self
+    }
+
+    /// The column `expr` compares, through the JSON path when there is one.
+    fn unpack_target(&self, expr: &ScalarExpression) -> Option<ColumnRef> {
+        let Some((json_path, _)) = self.json_path else {
+            return expr.unpack_bound_col(false).map(|(column, _)| column);
+        };
+        split_json_path(expr)
+            .and_then(|(column, steps)| (steps.as_slice() == json_path).then_some(column))
+    }
+
     pub(crate) fn detach(
         &mut self,
         expr: &ScalarExpression,
@@ -242,17 +269,15 @@ impl<'a, 'p> RangeDetacher<'a, 'p> {
                 op,
                 ..
             } => {
-                if let (Some(col), Some(val)) = (
-                    left_expr.unpack_bound_col(false).map(|(column, _)| column),
-                    right_expr.unpack_val(),
-                ) {
+                if let (Some(col), Some(val)) =
+                    (self.unpack_target(left_expr), right_expr.unpack_val())
+                {
                     return self
                         .new_range(*op, col, val, false)
                         .map(|range| range.map(DetachedPredicate::consumed));
-                } else if let (Some(val), Some(col)) = (
-                    left_expr.unpack_val(),
-                    right_expr.unpack_bound_col(false).map(|(column, _)| column),
-                ) {
+                } else if let (Some(val), Some(col)) =
+                    (left_expr.unpack_val(), self.unpack_target(right_expr))
+                {
                     return self
                         .new_range(*op, col, val, true)
                         .map(|range| range.map(DetachedPredicate::consumed));
@@ -314,7 +339,7 @@ impl<'a, 'p> RangeDetacher<'a, 'p> {
                 self.detach(expr)?
             }
             ScalarExpression::IsNull { expr, negated, .. } => match expr.as_ref() {
-                ScalarExpression::ColumnRef { column, .. } => {
+                ScalarExpression::ColumnRef { column, .. } if self.json_path.is_none() => {
                     let column = self.arena.column(*column);
                     if let (Some(col_id), Some(col_table)) = (column.id(), column.table_name()) {
                         if &col_id == self.column_id && col_table.as_ref() == self.table_name {
@@ -332,6 +357,7 @@ impl<'a, 'p> RangeDetacher<'a, 'p> {
                     None
                 }
                 ScalarExpression::Constant(_)
+                | ScalarExpression::ColumnRef { .. }
                 | ScalarExpression::Alias { .. }
                 | ScalarExpression::TypeCast { .. }
                 | ScalarExpression::IsNull { .. }
@@ -349,7 +375,9 @@ impl<'a, 'p> RangeDetacher<'a, 'p> {
                 | ScalarExpression::NullIf { .. }
                 | ScalarExpression::Coalesce { .. }
                 | ScalarExpression::CaseWhen { .. }
-                | ScalarExpression::WindowCall(_) => None,
+                | ScalarExpression::WindowCall(_)
+                | ScalarExpression::Sequence { .. }
+                | ScalarExpression::Array { .. } => None,
                 ScalarExpression::Tuple(_)
                 | ScalarExpression::TableFunction(_)
                 | ScalarExpression::Empty => unreachable!(),
@@ -370,7 +398,9 @@ impl<'a, 'p> RangeDetacher<'a, 'p> {
             | ScalarExpression::NullIf { .. }
             | ScalarExpression::Coalesce { .. }
             | ScalarExpression::CaseWhen { .. }
-            | ScalarExpression::WindowCall(_) => None,
+            | ScalarExpression::WindowCall(_)
+            | ScalarExpression::Sequence { .. }
+            | ScalarExpression::Array { .. } => None,
             ScalarExpression::TableFunction(_) | ScalarExpression::Empty => unreachable!(),
         })
     }
@@ -829,6 +859,11 @@ impl<'a, 'p> RangeDetacher<'a, 'p> {
         if !self._is_belong(col) || column.id() != Some(*self.column_id) {
             return Ok(None);
         }
+        // a path may be missing from any document, so its values are always nullable
+        let (ty, nullable) = match self.json_path {
+            Some((_, value_ty)) => (value_ty, true),
+            None => (column.datatype(), column.nullable()),
+        };
         if val.is_null() {
             return Ok(match op {
                 BinaryOperator::Spaceship => Some(Range::Eq(DataValue::Null)),
@@ -841,7 +876,7 @@ impl<'a, 'p> RangeDetacher<'a, 'p> {
                 _ => None,
             });
         }
-        val = val.cast(column.datatype())?;
+        val = val.cast(ty)?;
         if is_flip {
             op = match op {
                 BinaryOperator::Gt => BinaryOperator::Lt,
@@ -854,7 +889,7 @@ impl<'a, 'p> RangeDetacher<'a, 'p> {
         Ok(match op {
             BinaryOperator::Gt => Some(Range::Scope {
                 min: Bound::Excluded(val.clone()),
-                max: if column.nullable() {
+                max: if nullable {
                     Bound::Excluded(DataValue::Null)
                 } else {
                     Bound::Unbounded
@@ -866,7 +901,7 @@ impl<'a, 'p> RangeDetacher<'a, 'p> {
             }),
             BinaryOperator::GtEq => Some(Range::Scope {
                 min: Bound::Included(val.clone()),
-                max: if column.nullable() {
+                max: if nullable {
                     Bound::Excluded(DataValue::Null)
                 } else {
                     Bound::Unbounded
@@ -14,10 +14,11 @@
 
 use crate::catalog::ColumnRef;
 use crate::errors::DatabaseError;
+use crate::expression::function::scala::ScalarFunction;
 use crate::expression::visitor_mut::{walk_mut_expr, ExprVisitorMut};
 use crate::expression::{BinaryOperator, ScalarExpression, UnaryOperator};
 use crate::planner::PlanArena;
-use crate::types::evaluator::{binary_create, unary_create};
+use crate::types::evaluator::{binary_create, temporal_binary_create, unary_create};
 use crate::types::value::DataValue;
 use crate::types::LogicalType;
 use std::borrow::Cow;
@@ -81,9 +82,8 @@ impl ExprVisitorMut<'_> for ConstantCalculator<'_, '_> {
                 right_expr,
                 ..
             } => {
-                let left_ty = left_expr.return_type(self.arena);
-                let right_ty = right_expr.return_type(self.arena);
-                let ty = LogicalType::max_logical_type(&left_ty, &right_ty)?.into_owned();
+                let left_ty = left_expr.return_type(self.arena).into_owned();
+                let right_ty = right_expr.return_type(self.arena).into_owned();
                 self.visit(left_expr)?;
                 self.visit(right_expr)?;
 
@@ -92,6 +92,12 @@ impl ExprVisitorMut<'_> for ConstantCalculator<'_, '_> {
                     ScalarExpression::Constant(right_val),
                 ) = (left_expr.as_mut(), right_expr.as_mut())
                 {
+                    if let Some((evaluator, _)) = temporal_binary_create(&left_ty, &right_ty, op) {
+                        let value = evaluator.binary_eval(left_val, right_val)?;
+                        let _ = mem::replace(expr, ScalarExpression::Constant(value));
+                        return Ok(());
+                    }
+                    let ty = LogicalType::max_logical_type(&left_ty, &right_ty)?.into_owned();
                     let evaluator = binary_create(Cow::Borrowed(&ty), *op)?;
 
                     *left_val = mem::replace(left_val, DataValue::Null).cast(&ty)?;
@@ -110,6 +116,27 @@ impl ExprVisitorMut<'_> for ConstantCalculator<'_, '_> {
                     let _ = mem::replace(expr, ScalarExpression::Constant(casted));
                 }
             }
+            ScalarExpression::Array { function, args, .. } => {
+                for arg in args.iter_mut() {
+                    self.visit(arg)?;
+                }
+                if args
+                    .iter()
+                    .all(|arg| matches!(arg, ScalarExpression::Constant(_)))
+                {
+                    let values = args
+                        .iter_mut()
+                        .map(|arg| match arg {
+                            ScalarExpression::Constant(value) => {
+                                mem::replace(value, DataValue::Null)
+                            }
+                            _ => unreachable!(),
+                        })
+                        .collect();
+                    let value = function.eval(values)?;
+                    let _ = mem::replace(expr, ScalarExpression::Constant(value));
+                }
+            }
             _ => walk_mut_expr(self, expr)?,
         }
 
@@ -179,7 +206,16 @@ impl ExprVisitorMut<'_> for Simplify {
                     return Ok(());
                 }
 
-                if Self::is_arithmetic(op) {
+                // Month steps are not invertible (`Jan 31 + 1 month` clamps to February's last
+                // day), so date/time arithmetic is never moved to the other side.
+                let is_temporal = matches!(
+                    ty,
+                    LogicalType::Date
+                        | LogicalType::DateTime
+                        | LogicalType::TimeStamp(_, _)
+                        | LogicalType::Interval
+                );
+                if Self::is_arithmetic(op) && !is_temporal {
                     match (
                         left_expr.unpack_bound_col(false),
                         right_expr.unpack_bound_col(false),
@@ -583,6 +619,11 @@ impl ScalarExpression {
             } => {
                 let mut left = left_expr.unpack_val()?;
                 let mut right = right_expr.unpack_val()?;
+                if let Some((evaluator, _)) =
+                    temporal_binary_create(&left.logical_type(), &right.logical_type(), op)
+                {
+                    return evaluator.binary_eval(&left, &right).ok();
+                }
                 left = left.cast(ty).ok()?;
                 right = right.cast(ty).ok()?;
                 if let Some(evaluator) = evaluator {
@@ -594,6 +635,15 @@ impl ScalarExpression {
                 }
                 .ok()
             }
+            ScalarExpression::ScalaFunction(ScalarFunction { args, inner })
+                if inner.is_constant_foldable() =>
+            {
+                let args = args
+                    .iter()
+                    .map(|arg| arg.unpack_val().map(ScalarExpression::Constant))
+                    .collect::<Option<Vec<_>>>()?;
+                inner.eval(&args, None).ok()
+            }
             _ => None,
         }
     }
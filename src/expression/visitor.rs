@@ -19,7 +19,10 @@ use crate::expression::function::scala::ScalarFunction;
 use crate::expression::function::table::TableFunction;
 use crate::expression::window::WindowCall;
 use crate::expression::TrimWhereField;
-use crate::expression::{AliasType, BinaryOperator, ScalarExpression, UnaryOperator};
+use crate::expression::{
+    AliasType, ArrayFunction, BinaryOperator, ScalarExpression, SequenceFunction, UnaryOperator,
+};
+use crate::planner::operator::sort::SortField;
 use crate::types::evaluator::{BinaryEvaluatorRef, CastEvaluatorRef, UnaryEvaluatorRef};
 use crate::types::value::DataValue;
 use crate::types::LogicalType;
@@ -92,10 +95,16 @@ pub trait ExprVisitor<'a>: Sized {
         _distinct: bool,
         _kind: &'a AggKind,
         args: &'a [ScalarExpression],
+        filter: Option<&'a ScalarExpression>,
+        order_by: &'a [SortField],
         _ty: &'a LogicalType,
     ) -> Result<(), DatabaseError> {
-        for arg in args {
-            self.visit(arg)?;
+        for expr in args
+            .iter()
+            .chain(filter)
+            .chain(order_by.iter().map(|field| &field.expr))
+        {
+            self.visit(expr)?;
         }
         Ok(())
     }
@@ -180,6 +189,26 @@ pub trait ExprVisitor<'a>: Sized {
         Ok(())
     }
 
+    fn visit_sequence(
+        &mut self,
+        _function: &'a SequenceFunction,
+        _name: &'a String,
+    ) -> Result<(), DatabaseError> {
+        Ok(())
+    }
+
+    fn visit_array(
+        &mut self,
+        _function: &'a ArrayFunction,
+        args: &'a [ScalarExpression],
+        _ty: &'a LogicalType,
+    ) -> Result<(), DatabaseError> {
+        for arg in args {
+            self.visit(arg)?;
+        }
+        Ok(())
+    }
+
     fn visit_reference(
         &mut self,
         expr: &'a ScalarExpression,
@@ -310,8 +339,10 @@ pub fn walk_expr<'a, V: ExprVisitor<'a>>(
             distinct,
             kind,
             args,
+            filter,
+            order_by,
             ty,
-        } => visitor.visit_agg(*distinct, kind, args, ty),
+        } => visitor.visit_agg(*distinct, kind, args, filter.as_deref(), order_by, ty),
         ScalarExpression::In {
             negated,
             expr,
@@ -335,6 +366,8 @@ pub fn walk_expr<'a, V: ExprVisitor<'a>>(
             trim_where,
         } => visitor.visit_trim(expr, trim_what_expr.as_deref(), trim_where.as_ref()),
         ScalarExpression::Empty => visitor.visit_empty(),
+        ScalarExpression::Sequence { function, name } => visitor.visit_sequence(function, name),
+        ScalarExpression::Array { function, args, ty } => visitor.visit_array(function, args, ty),
         ScalarExpression::Tuple(exprs) => visitor.visit_tuple(exprs),
         ScalarExpression::ScalaFunction(scalar_function) => {
             visitor.visit_scala_function(scalar_function)
@@ -19,7 +19,10 @@ use crate::expression::function::scala::ScalarFunction;
 use crate::expression::function::table::TableFunction;
 use crate::expression::window::WindowCall;
 use crate::expression::TrimWhereField;
-use crate::expression::{AliasType, BinaryOperator, ScalarExpression, UnaryOperator};
+use crate::expression::{
+    AliasType, ArrayFunction, BinaryOperator, ScalarExpression, SequenceFunction, UnaryOperator,
+};
+use crate::planner::operator::sort::SortField;
 use crate::types::evaluator::{BinaryEvaluatorRef, CastEvaluatorRef, UnaryEvaluatorRef};
 use crate::types::value::DataValue;
 use crate::types::LogicalType;
@@ -115,10 +118,16 @@ pub trait ExprVisitorMut<'a>: Sized {
         _distinct: bool,
         _kind: &'a mut AggKind,
         args: &'a mut [ScalarExpression],
+        filter: Option<&'a mut ScalarExpression>,
+        order_by: &'a mut [SortField],
         _ty: &'a mut LogicalType,
     ) -> Result<(), DatabaseError> {
-        for arg in args {
-            self.visit(arg)?;
+        for expr in args
+            .iter_mut()
+            .chain(filter)
+            .chain(order_by.iter_mut().map(|field| &mut field.expr))
+        {
+            self.visit(expr)?;
         }
         Ok(())
     }
@@ -203,6 +212,26 @@ pub trait ExprVisitorMut<'a>: Sized {
         Ok(())
     }
 
+    fn visit_sequence(
+        &mut self,
+        _function: &'a mut SequenceFunction,
+        _name: &'a mut String,
+    ) -> Result<(), DatabaseError> {
+        Ok(())
+    }
+
+    fn visit_array(
+        &mut self,
+        _function: &'a mut ArrayFunction,
+        args: &'a mut [ScalarExpression],
+        _ty: &'a mut LogicalType,
+    ) -> Result<(), DatabaseError> {
+        for arg in args {
+            self.visit(arg)?;
+        }
+        Ok(())
+    }
+
     fn visit_reference(
         &mut self,
         expr: &'a mut ScalarExpression,
@@ -335,8 +364,10 @@ pub fn walk_mut_expr<'a, V: ExprVisitorMut<'a>>(
             distinct,
             kind,
             args,
+            filter,
+            order_by,
             ty,
-        } => visitor.visit_agg(*distinct, kind, args, ty),
+        } => visitor.visit_agg(*distinct, kind, args, filter.as_deref_mut(), order_by, ty),
         ScalarExpression::In {
             negated,
             expr,
@@ -360,6 +391,8 @@ pub fn walk_mut_expr<'a, V: ExprVisitorMut<'a>>(
             trim_where,
         } => visitor.visit_trim(expr, trim_what_expr, trim_where),
         ScalarExpression::Empty => visitor.visit_empty(),
+        ScalarExpression::Sequence { function, name } => visitor.visit_sequence(function, name),
+        ScalarExpression::Array { function, args, ty } => visitor.visit_array(function, args, ty),
         ScalarExpression::Tuple(exprs) => visitor.visit_tuple(exprs),
         ScalarExpression::ScalaFunction(scalar_function) => {
             visitor.visit_scala_function(scalar_function)
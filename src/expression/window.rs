@@ -12,17 +12,34 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::errors::DatabaseError;
 use crate::expression::agg::AggKind;
 use crate::expression::ScalarExpression;
 use crate::planner::operator::sort::SortField;
+use crate::types::value::DataValue;
 use crate::types::LogicalType;
 use kite_sql_serde_macros::ReferenceSerialization;
+use std::fmt;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, ReferenceSerialization)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, ReferenceSerialization)]
 pub enum WindowFunctionKind {
     RowNumber,
     Rank,
     DenseRank,
+    PercentRank,
+    CumeDist,
+    Ntile,
+    /// `LAG(value [, offset [, default]])`, `IGNORE NULLS` skips rows whose value is NULL.
+    Lag {
+        ignore_nulls: bool,
+    },
+    /// `LEAD(value [, offset [, default]])`, `IGNORE NULLS` skips rows whose value is NULL.
+    Lead {
+        ignore_nulls: bool,
+    },
+    FirstValue,
+    LastValue,
+    NthValue,
     Aggregate(AggKind),
 }
 
@@ -32,18 +49,63 @@ impl WindowFunctionKind {
             "row_number" => Some(Self::RowNumber),
             "rank" => Some(Self::Rank),
             "dense_rank" => Some(Self::DenseRank),
-            name => AggKind::from_name(name).map(Self::Aggregate),
+            "percent_rank" => Some(Self::PercentRank),
+            "cume_dist" => Some(Self::CumeDist),
+            "ntile" => Some(Self::Ntile),
+            "lag" => Some(Self::Lag {
+                ignore_nulls: false,
+            }),
+            "lead" => Some(Self::Lead {
+                ignore_nulls: false,
+            }),
+            "first_value" => Some(Self::FirstValue),
+            "last_value" => Some(Self::LastValue),
+            "nth_value" => Some(Self::NthValue),
+            name => AggKind::from_name(name)
+                .filter(|kind| !matches!(kind, AggKind::Grouping))
+                .map(Self::Aggregate),
         }
     }
 
-    pub(crate) fn name(self) -> &'static str {
+    pub(crate) fn name(&self) -> &str {
         match self {
             Self::RowNumber => "row_number",
             Self::Rank => "rank",
             Self::DenseRank => "dense_rank",
+            Self::PercentRank => "percent_rank",
+            Self::CumeDist => "cume_dist",
+            Self::Ntile => "ntile",
+            Self::Lag { .. } => "lag",
+            Self::Lead { .. } => "lead",
+            Self::FirstValue => "first_value",
+            Self::LastValue => "last_value",
+            Self::NthValue => "nth_value",
             Self::Aggregate(kind) => kind.name(),
         }
     }
+
+    /// Applies `IGNORE NULLS`, which only `LAG` and `LEAD` accept.
+    pub(crate) fn with_ignore_nulls(self, ignore_nulls: bool) -> Result<Self, DatabaseError> {
+        Ok(match self {
+            Self::Lag { .. } => Self::Lag { ignore_nulls },
+            Self::Lead { .. } => Self::Lead { ignore_nulls },
+            _ if ignore_nulls => {
+                return Err(DatabaseError::UnsupportedStmt(
+                    "IGNORE NULLS is only supported for LAG and LEAD".to_string(),
+                ))
+            }
+            kind => kind,
+        })
+    }
+
+    /// Whether the function reads the window frame, the others only depend on the partition
+    /// and its order.
+    pub(crate) fn uses_frame(&self) -> bool {
+        matches!(
+            self,
+            Self::FirstValue | Self::LastValue | Self::NthValue | Self::Aggregate(_)
+        )
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, ReferenceSerialization)]
@@ -53,10 +115,66 @@ pub struct WindowFunction {
     pub ty: LogicalType,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, ReferenceSerialization)]
+pub enum WindowFrameUnits {
+    Rows,
+    Range,
+    Groups,
+}
+
+/// A frame bound, the offsets are non-negative: a row count for `ROWS`, a peer group count
+/// for `GROUPS` and a value of the `ORDER BY` type for `RANGE`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, ReferenceSerialization)]
+pub enum WindowFrameBound {
+    UnboundedPreceding,
+    Preceding(DataValue),
+    CurrentRow,
+    Following(DataValue),
+    UnboundedFollowing,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, ReferenceSerialization)]
+pub struct WindowFrame {
+    pub units: WindowFrameUnits,
+    pub start: WindowFrameBound,
+    pub end: WindowFrameBound,
+}
+
+impl fmt::Display for WindowFrameUnits {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WindowFrameUnits::Rows => f.write_str("ROWS"),
+            WindowFrameUnits::Range => f.write_str("RANGE"),
+            WindowFrameUnits::Groups => f.write_str("GROUPS"),
+        }
+    }
+}
+
+impl fmt::Display for WindowFrameBound {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WindowFrameBound::UnboundedPreceding => f.write_str("UNBOUNDED PRECEDING"),
+            WindowFrameBound::Preceding(offset) => write!(f, "{offset} PRECEDING"),
+            WindowFrameBound::CurrentRow => f.write_str("CURRENT ROW"),
+            WindowFrameBound::Following(offset) => write!(f, "{offset} FOLLOWING"),
+            WindowFrameBound::UnboundedFollowing => f.write_str("UNBOUNDED FOLLOWING"),
+        }
+    }
+}
+
+impl fmt::Display for WindowFrame {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} BETWEEN {} AND {}", self.units, self.start, self.end)
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash, ReferenceSerialization)]
 pub struct WindowSpec {
     pub partition_by: Vec<ScalarExpression>,
     pub order_by: Vec<SortField>,
+    /// `None` is the default frame: the whole partition without `ORDER BY`, otherwise
+    /// `RANGE BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW`.
+    pub frame: Option<Box<WindowFrame>>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, ReferenceSerialization)]
@@ -0,0 +1,99 @@
+// Copyright 2024 KipData/KiteSQL
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::errors::DatabaseError;
+use crate::expression::function::scala::{FuncMonotonicity, ScalarFunctionImpl};
+use crate::expression::function::FunctionSummary;
+use crate::expression::ScalarExpression;
+use crate::function::eval_utf8;
+use crate::types::evaluator::interval::to_instant;
+use crate::types::timezone::TimeZone;
+use crate::types::tuple::TupleLike;
+use crate::types::value::DataValue;
+use crate::types::{CharLengthUnits, LogicalType};
+use std::sync::Arc;
+
+/// PostgreSQL's `timezone(zone, source)`, which `source AT TIME ZONE zone` binds to. A
+/// timestamp with time zone becomes the wall clock time in `zone`, a timestamp without time
+/// zone, or a date, is read as wall clock time in `zone`.
+#[derive(Debug)]
+pub(crate) struct AtTimeZone {
+    summary: FunctionSummary,
+    return_type: LogicalType,
+}
+
+impl AtTimeZone {
+    pub(crate) fn new(ty: LogicalType) -> Arc<Self> {
+        let return_type = match ty {
+            LogicalType::TimeStamp(precision, zone) => LogicalType::TimeStamp(precision, !zone),
+            _ => LogicalType::TimeStamp(None, true),
+        };
+        Arc::new(Self {
+            summary: FunctionSummary {
+                name: "timezone".into(),
+                arg_types: vec![LogicalType::Varchar(None, CharLengthUnits::Characters), ty],
+            },
+            return_type,
+        })
+    }
+}
+
+impl ScalarFunctionImpl for AtTimeZone {
+    fn eval(
+        &self,
+        exprs: &[ScalarExpression],
+        tuples: Option<&dyn TupleLike>,
+    ) -> Result<DataValue, DatabaseError> {
+        let Some(zone) = eval_utf8(&exprs[0], tuples)? else {
+            return Ok(DataValue::Null);
+        };
+        let zone = TimeZone::parse(&zone)?;
+        let value = exprs[1].eval(tuples)?;
+        if value.is_null() {
+            return Ok(DataValue::Null);
+        }
+        let datetime = to_instant(&value).ok_or(DatabaseError::InvalidType)?;
+        let (datetime, precision, with_zone) = match value {
+            DataValue::Time64(_, precision, true) => (zone.to_local(&datetime), precision, false),
+            DataValue::Time64(_, precision, false) => (zone.to_utc(&datetime), precision, true),
+            _ => (zone.to_utc(&datetime), 0, true),
+        };
+        let datetime = datetime.and_utc();
+        if precision == 9 && datetime.timestamp_nanos_opt().is_none() {
+            return Err(DatabaseError::OverFlow);
+        }
+
+        Ok(DataValue::Time64(
+            DataValue::timestamp_precision(datetime, precision),
+            precision,
+            with_zone,
+        ))
+    }
+
+    fn monotonicity(&self) -> Option<FuncMonotonicity> {
+        None
+    }
+
+    fn return_type(&self) -> &LogicalType {
+        &self.return_type
+    }
+
+    fn summary(&self) -> &FunctionSummary {
+        &self.summary
+    }
+
+    fn is_constant_foldable(&self) -> bool {
+        true
+    }
+}
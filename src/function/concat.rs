@@ -0,0 +1,73 @@
+// Copyright 2024 KipData/KiteSQL
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::errors::DatabaseError;
+use crate::expression::function::scala::{FuncMonotonicity, ScalarFunctionImpl};
+use crate::expression::function::FunctionSummary;
+use crate::expression::ScalarExpression;
+use crate::function::{eval_utf8, utf8_value};
+use crate::types::tuple::TupleLike;
+use crate::types::value::DataValue;
+use crate::types::CharLengthUnits;
+use crate::types::LogicalType;
+use std::sync::Arc;
+
+/// `concat(...)`: joins its arguments, skipping `NULL`s.
+#[derive(Debug)]
+pub(crate) struct Concat {
+    summary: FunctionSummary,
+}
+
+impl Concat {
+    pub(crate) fn new() -> Arc<Self> {
+        Arc::new(Self {
+            summary: FunctionSummary {
+                name: "concat".into(),
+                arg_types: vec![LogicalType::Varchar(None, CharLengthUnits::Characters)],
+            },
+        })
+    }
+}
+
+impl ScalarFunctionImpl for Concat {
+    fn eval(
+        &self,
+        exprs: &[ScalarExpression],
+        tuples: Option<&dyn TupleLike>,
+    ) -> Result<DataValue, DatabaseError> {
+        let mut result = String::new();
+        for expr in exprs {
+            if let Some(value) = eval_utf8(expr, tuples)? {
+                result.push_str(&value);
+            }
+        }
+        Ok(utf8_value(result))
+    }
+
+    fn monotonicity(&self) -> Option<FuncMonotonicity> {
+        None
+    }
+
+    fn return_type(&self) -> &LogicalType {
+        &LogicalType::Varchar(None, CharLengthUnits::Characters)
+    }
+
+    fn summary(&self) -> &FunctionSummary {
+        &self.summary
+    }
+
+    fn is_variadic(&self) -> bool {
+        true
+    }
+}
@@ -0,0 +1,85 @@
+// Copyright 2024 KipData/KiteSQL
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::errors::DatabaseError;
+use crate::expression::function::scala::{FuncMonotonicity, ScalarFunctionImpl};
+use crate::expression::function::FunctionSummary;
+use crate::expression::ScalarExpression;
+use crate::function::{eval_utf8, utf8_value};
+use crate::types::tuple::TupleLike;
+use crate::types::value::DataValue;
+use crate::types::CharLengthUnits;
+use crate::types::LogicalType;
+use std::sync::Arc;
+
+/// `concat_ws(separator, ...)`: joins the non-`NULL` arguments with `separator`.
+#[derive(Debug)]
+pub(crate) struct ConcatWs {
+    summary: FunctionSummary,
+}
+
+impl ConcatWs {
+    pub(crate) fn new() -> Arc<Self> {
+        Arc::new(Self {
+            summary: FunctionSummary {
+                name: "concat_ws".into(),
+                arg_types: vec![
+                    LogicalType::Varchar(None, CharLengthUnits::Characters),
+                    LogicalType::Varchar(None, CharLengthUnits::Characters),
+                ],
+            },
+        })
+    }
+}
+
+impl ScalarFunctionImpl for ConcatWs {
+    fn eval(
+        &self,
+        exprs: &[ScalarExpression],
+        tuples: Option<&dyn TupleLike>,
+    ) -> Result<DataValue, DatabaseError> {
+        let Some(separator) = eval_utf8(&exprs[0], tuples)? else {
+            return Ok(DataValue::Null);
+        };
+        let mut result = String::new();
+        let mut first = true;
+        for expr in &exprs[1..] {
+            let Some(value) = eval_utf8(expr, tuples)? else {
+                continue;
+            };
+            if !first {
+                result.push_str(&separator);
+            }
+            first = false;
+            result.push_str(&value);
+        }
+        Ok(utf8_value(result))
+    }
+
+    fn monotonicity(&self) -> Option<FuncMonotonicity> {
+        None
+    }
+
+    fn return_type(&self) -> &LogicalType {
+        &LogicalType::Varchar(None, CharLengthUnits::Characters)
+    }
+
+    fn summary(&self) -> &FunctionSummary {
+        &self.summary
+    }
+
+    fn is_variadic(&self) -> bool {
+        true
+    }
+}
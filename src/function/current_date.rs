@@ -52,7 +52,7 @@ impl ScalarFunctionImpl for CurrentDate {
     }
 
     fn monotonicity(&self) -> Option<FuncMonotonicity> {
-        todo!()
+        None
     }
 
     fn return_type(&self) -> &LogicalType {
@@ -62,4 +62,8 @@ impl ScalarFunctionImpl for CurrentDate {
     fn summary(&self) -> &FunctionSummary {
         &self.summary
     }
+
+    fn is_constant_foldable(&self) -> bool {
+        true
+    }
 }
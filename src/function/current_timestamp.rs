@@ -29,10 +29,8 @@ pub(crate) struct CurrentTimeStamp {
 }
 
 impl CurrentTimeStamp {
-    #[allow(unused_mut)]
-    pub(crate) fn new() -> Arc<Self> {
-        let function_name = "current_timestamp".to_string();
-
+    /// `now()` is registered as an alias of `current_timestamp()`.
+    pub(crate) fn new(function_name: String) -> Arc<Self> {
         Arc::new(Self {
             summary: FunctionSummary {
                 name: function_name.into(),
@@ -52,7 +50,7 @@ impl ScalarFunctionImpl for CurrentTimeStamp {
     }
 
     fn monotonicity(&self) -> Option<FuncMonotonicity> {
-        todo!()
+        None
     }
 
     fn return_type(&self) -> &LogicalType {
@@ -62,4 +60,8 @@ impl ScalarFunctionImpl for CurrentTimeStamp {
     fn summary(&self) -> &FunctionSummary {
         &self.summary
     }
+
+    fn is_constant_foldable(&self) -> bool {
+        true
+    }
 }
@@ -0,0 +1,138 @@
+// Copyright 2024 KipData/KiteSQL
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::errors::DatabaseError;
+use crate::expression::function::scala::{FuncMonotonicity, ScalarFunctionImpl};
+use crate::expression::function::FunctionSummary;
+use crate::expression::ScalarExpression;
+use crate::types::evaluator::interval::{
+    temporal_minus_interval_binary_eval, temporal_plus_interval_binary_eval,
+};
+use crate::types::tuple::TupleLike;
+use crate::types::value::DataValue;
+use crate::types::LogicalType;
+use chrono::Datelike;
+use std::sync::Arc;
+
+/// MySQL's `date_add(source, interval)` and `date_sub(source, interval)`, the same as
+/// `source + interval` and `source - interval`.
+#[derive(Debug)]
+pub(crate) struct DateAdd {
+    summary: FunctionSummary,
+    return_type: LogicalType,
+    is_add: bool,
+}
+
+impl DateAdd {
+    pub(crate) fn new(ty: LogicalType, is_add: bool) -> Arc<Self> {
+        let function_name = if is_add { "date_add" } else { "date_sub" };
+        Arc::new(Self {
+            summary: FunctionSummary {
+                name: function_name.into(),
+                arg_types: vec![ty.clone(), LogicalType::Interval],
+            },
+            return_type: ty,
+            is_add,
+        })
+    }
+}
+
+impl ScalarFunctionImpl for DateAdd {
+    fn eval(
+        &self,
+        exprs: &[ScalarExpression],
+        tuples: Option<&dyn TupleLike>,
+    ) -> Result<DataValue, DatabaseError> {
+        let value = exprs[0].eval(tuples)?;
+        let interval = exprs[1].eval(tuples)?;
+        if self.is_add {
+            temporal_plus_interval_binary_eval(&value, &interval)
+        } else {
+            temporal_minus_interval_binary_eval(&value, &interval)
+        }
+    }
+
+    fn monotonicity(&self) -> Option<FuncMonotonicity> {
+        None
+    }
+
+    fn return_type(&self) -> &LogicalType {
+        &self.return_type
+    }
+
+    fn summary(&self) -> &FunctionSummary {
+        &self.summary
+    }
+
+    fn is_constant_foldable(&self) -> bool {
+        true
+    }
+}
+
+/// MySQL's `datediff(end, start)`, the number of days from `start` to `end`. Timestamps are
+/// cast to their dates first.
+#[derive(Debug)]
+pub(crate) struct DateDiff {
+    summary: FunctionSummary,
+}
+
+impl DateDiff {
+    pub(crate) fn new() -> Arc<Self> {
+        Arc::new(Self {
+            summary: FunctionSummary {
+                name: "datediff".into(),
+                arg_types: vec![LogicalType::Date, LogicalType::Date],
+            },
+        })
+    }
+}
+
+impl ScalarFunctionImpl for DateDiff {
+    fn eval(
+        &self,
+        exprs: &[ScalarExpression],
+        tuples: Option<&dyn TupleLike>,
+    ) -> Result<DataValue, DatabaseError> {
+        let days = |expr: &ScalarExpression| -> Result<Option<i32>, DatabaseError> {
+            Ok(match expr.eval(tuples)? {
+                DataValue::Null => None,
+                DataValue::Date32(days) => Some(days),
+                value => value
+                    .cast(&LogicalType::Date)?
+                    .date()
+                    .map(|date| date.num_days_from_ce()),
+            })
+        };
+        let (Some(end), Some(start)) = (days(&exprs[0])?, days(&exprs[1])?) else {
+            return Ok(DataValue::Null);
+        };
+        Ok(DataValue::Int64(end as i64 - start as i64))
+    }
+
+    fn monotonicity(&self) -> Option<FuncMonotonicity> {
+        None
+    }
+
+    fn return_type(&self) -> &LogicalType {
+        &LogicalType::Bigint
+    }
+
+    fn summary(&self) -> &FunctionSummary {
+        &self.summary
+    }
+
+    fn is_constant_foldable(&self) -> bool {
+        true
+    }
+}
@@ -0,0 +1,251 @@
+// Copyright 2024 KipData/KiteSQL
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::errors::DatabaseError;
+use crate::expression::function::scala::{FuncMonotonicity, ScalarFunctionImpl};
+use crate::expression::function::FunctionSummary;
+use crate::expression::ScalarExpression;
+use crate::function::{eval_utf8, f64_value};
+use crate::types::evaluator::interval::{to_instant, to_naive_datetime};
+use crate::types::interval::{Interval, NANOS_PER_HOUR, NANOS_PER_MINUTE, NANOS_PER_SECOND};
+use crate::types::tuple::TupleLike;
+use crate::types::value::DataValue;
+use crate::types::{CharLengthUnits, LogicalType};
+use chrono::{Datelike, NaiveDateTime, NaiveTime, Timelike};
+use std::sync::Arc;
+
+/// A field of a date, time or interval, as named in `EXTRACT`, `date_part` and `date_trunc`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum DateField {
+    Century,
+    Decade,
+    Year,
+    IsoYear,
+    Quarter,
+    Month,
+    Week,
+    Day,
+    DayOfWeek,
+    IsoDayOfWeek,
+    DayOfYear,
+    Hour,
+    Minute,
+    Second,
+    Millisecond,
+    Microsecond,
+    Epoch,
+}
+
+impl DateField {
+    pub(crate) fn parse(field: &str) -> Result<Self, DatabaseError> {
+        Ok(match field.trim().to_lowercase().as_str() {
+            "century" | "centuries" => DateField::Century,
+            "decade" | "decades" => DateField::Decade,
+            "year" | "years" => DateField::Year,
+            "isoyear" => DateField::IsoYear,
+            "quarter" => DateField::Quarter,
+            "month" | "months" => DateField::Month,
+            "week" | "weeks" | "isoweek" => DateField::Week,
+            "day" | "days" => DateField::Day,
+            "dow" | "dayofweek" => DateField::DayOfWeek,
+            "isodow" => DateField::IsoDayOfWeek,
+            "doy" | "dayofyear" => DateField::DayOfYear,
+            "hour" | "hours" => DateField::Hour,
+            "minute" | "minutes" => DateField::Minute,
+            "second" | "seconds" => DateField::Second,
+            "millisecond" | "milliseconds" => DateField::Millisecond,
+            "microsecond" | "microseconds" => DateField::Microsecond,
+            "epoch" => DateField::Epoch,
+            _ => {
+                return Err(DatabaseError::InvalidValue(format!(
+                    "unsupported date/time field: {field}"
+                )))
+            }
+        })
+    }
+
+    fn unsupported(&self, ty: &str) -> DatabaseError {
+        DatabaseError::InvalidValue(format!(
+            "date/time field {self:?} is not supported for {ty}"
+        ))
+    }
+
+    fn of_datetime(&self, datetime: &NaiveDateTime) -> f64 {
+        let year = datetime.year();
+        match self {
+            DateField::Century => {
+                if year > 0 {
+                    ((year - 1) / 100 + 1) as f64
+                } else {
+                    (year / 100 - 1) as f64
+                }
+            }
+            DateField::Decade => year.div_euclid(10) as f64,
+            DateField::Year => year as f64,
+            DateField::IsoYear => datetime.iso_week().year() as f64,
+            DateField::Quarter => ((datetime.month() - 1) / 3 + 1) as f64,
+            DateField::Month => datetime.month() as f64,
+            DateField::Week => datetime.iso_week().week() as f64,
+            DateField::Day => datetime.day() as f64,
+            DateField::DayOfWeek => datetime.weekday().num_days_from_sunday() as f64,
+            DateField::IsoDayOfWeek => datetime.weekday().number_from_monday() as f64,
+            DateField::DayOfYear => datetime.ordinal() as f64,
+            DateField::Epoch => {
+                let datetime = datetime.and_utc();
+                datetime.timestamp() as f64
+                    + datetime.timestamp_subsec_nanos() as f64 / NANOS_PER_SECOND as f64
+            }
+            field => field.of_time(&datetime.time()),
+        }
+    }
+
+    fn of_time(&self, time: &NaiveTime) -> f64 {
+        let nanos = time.nanosecond() as f64;
+        match self {
+            DateField::Hour => time.hour() as f64,
+            DateField::Minute => time.minute() as f64,
+            DateField::Second => time.second() as f64 + nanos / 1e9,
+            DateField::Millisecond => time.second() as f64 * 1e3 + nanos / 1e6,
+            DateField::Microsecond => time.second() as f64 * 1e6 + nanos / 1e3,
+            DateField::Epoch => time.num_seconds_from_midnight() as f64 + nanos / 1e9,
+            _ => unreachable!("date fields are handled by `of_datetime`"),
+        }
+    }
+
+    /// Intervals are split into months, days and a time of day, the epoch counts a month
+    /// as 30 days.
+    fn of_interval(&self, interval: &Interval) -> Result<f64, DatabaseError> {
+        let sub_minute_nanos = interval.nanos % NANOS_PER_MINUTE;
+        Ok(match self {
+            DateField::Century => (interval.months / 1200) as f64,
+            DateField::Decade => (interval.months / 120) as f64,
+            DateField::Year => (interval.months / 12) as f64,
+            DateField::Quarter => ((interval.months % 12) / 3 + 1) as f64,
+            DateField::Month => (interval.months % 12) as f64,
+            DateField::Day => interval.days as f64,
+            DateField::Hour => (interval.nanos / NANOS_PER_HOUR) as f64,
+            DateField::Minute => ((interval.nanos % NANOS_PER_HOUR) / NANOS_PER_MINUTE) as f64,
+            DateField::Second => sub_minute_nanos as f64 / 1e9,
+            DateField::Millisecond => sub_minute_nanos as f64 / 1e6,
+            DateField::Microsecond => sub_minute_nanos as f64 / 1e3,
+            DateField::Epoch => interval.total_nanos() as f64 / 1e9,
+            field => return Err(field.unsupported("interval")),
+        })
+    }
+
+    pub(crate) fn of_value(&self, value: &DataValue) -> Result<Option<f64>, DatabaseError> {
+        Ok(Some(match value {
+            DataValue::Null => return Ok(None),
+            DataValue::Interval(interval) => self.of_interval(interval)?,
+            DataValue::Time32(value, precision) => {
+                if !matches!(
+                    self,
+                    DateField::Hour
+                        | DateField::Minute
+                        | DateField::Second
+                        | DateField::Millisecond
+                        | DateField::Microsecond
+                        | DateField::Epoch
+                ) {
+                    return Err(self.unsupported("time"));
+                }
+                let (secs, nanos) = DataValue::unpack_time(*value, *precision);
+                let time = NaiveTime::from_num_seconds_from_midnight_opt(secs, nanos)
+                    .ok_or(DatabaseError::OverFlow)?;
+                self.of_time(&time)
+            }
+            // the epoch of a timestamp with time zone counts from the UTC instant, not from
+            // its wall clock time
+            DataValue::Time64(..) if matches!(self, DateField::Epoch) => {
+                let datetime = to_instant(value).ok_or(DatabaseError::OverFlow)?;
+                self.of_datetime(&datetime)
+            }
+            value => {
+                let datetime = to_naive_datetime(value).ok_or(DatabaseError::InvalidType)?;
+                self.of_datetime(&datetime)
+            }
+        }))
+    }
+}
+
+/// `date_part(field, source)` returns the field as a double, with fractional seconds.
+/// `extract(field, source)` is what `EXTRACT(field FROM source)` binds to and returns whole
+/// numbers, the binder routes `EXTRACT(EPOCH FROM ...)` to `date_part` instead.
+#[derive(Debug)]
+pub(crate) struct DatePart {
+    summary: FunctionSummary,
+    return_type: LogicalType,
+}
+
+impl DatePart {
+    pub(crate) fn new(function_name: &str, ty: LogicalType) -> Arc<Self> {
+        let return_type = if function_name == "extract" {
+            LogicalType::Bigint
+        } else {
+            LogicalType::Double
+        };
+        Arc::new(Self {
+            summary: FunctionSummary {
+                name: function_name.into(),
+                arg_types: vec![LogicalType::Varchar(None, CharLengthUnits::Characters), ty],
+            },
+            return_type,
+        })
+    }
+
+    /// The types a field can be taken from: dates, timestamps, times and intervals.
+    pub(crate) fn types() -> Vec<LogicalType> {
+        let mut types = crate::function::temporal_types();
+        types.push(LogicalType::Time(None));
+        types.extend((0..5).map(|precision| LogicalType::Time(Some(precision))));
+        types.push(LogicalType::Interval);
+        types
+    }
+}
+
+impl ScalarFunctionImpl for DatePart {
+    fn eval(
+        &self,
+        exprs: &[ScalarExpression],
+        tuples: Option<&dyn TupleLike>,
+    ) -> Result<DataValue, DatabaseError> {
+        let Some(field) = eval_utf8(&exprs[0], tuples)? else {
+            return Ok(DataValue::Null);
+        };
+        let field = DateField::parse(&field)?;
+        let Some(value) = field.of_value(&exprs[1].eval(tuples)?)? else {
+            return Ok(DataValue::Null);
+        };
+        if self.return_type == LogicalType::Bigint {
+            return Ok(DataValue::Int64(value.trunc() as i64));
+        }
+        f64_value(value)
+    }
+
+    fn monotonicity(&self) -> Option<FuncMonotonicity> {
+        None
+    }
+
+    fn return_type(&self) -> &LogicalType {
+        &self.return_type
+    }
+
+    fn summary(&self) -> &FunctionSummary {
+        &self.summary
+    }
+
+    fn is_constant_foldable(&self) -> bool {
+        true
+    }
+}
@@ -0,0 +1,117 @@
+// Copyright 2024 KipData/KiteSQL
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::errors::DatabaseError;
+use crate::expression::function::scala::{FuncMonotonicity, ScalarFunctionImpl};
+use crate::expression::function::FunctionSummary;
+use crate::expression::ScalarExpression;
+use crate::function::date_part::DateField;
+use crate::function::eval_utf8;
+use crate::types::evaluator::interval::{from_naive_datetime, to_naive_datetime};
+use crate::types::tuple::TupleLike;
+use crate::types::value::DataValue;
+use crate::types::{CharLengthUnits, LogicalType};
+use chrono::{Datelike, Days, NaiveDate, NaiveDateTime, NaiveTime, Timelike};
+use std::sync::Arc;
+
+/// `date_trunc(field, source)` zeroes every part of `source` below `field`, weeks start on
+/// Monday. The result keeps the type of `source`.
+#[derive(Debug)]
+pub(crate) struct DateTrunc {
+    summary: FunctionSummary,
+    return_type: LogicalType,
+}
+
+impl DateTrunc {
+    pub(crate) fn new(ty: LogicalType) -> Arc<Self> {
+        Arc::new(Self {
+            summary: FunctionSummary {
+                name: "date_trunc".into(),
+                arg_types: vec![
+                    LogicalType::Varchar(None, CharLengthUnits::Characters),
+                    ty.clone(),
+                ],
+            },
+            return_type: ty,
+        })
+    }
+}
+
+fn truncate(field: DateField, datetime: NaiveDateTime) -> Result<NaiveDateTime, DatabaseError> {
+    let date = datetime.date();
+    let time = datetime.time();
+    let year = date.year();
+    let first_day = |year: i32, month: u32| {
+        NaiveDate::from_ymd_opt(year, month, 1).map(|date| date.and_time(NaiveTime::MIN))
+    };
+    let truncated = match field {
+        DateField::Microsecond => datetime.with_nanosecond(time.nanosecond() / 1_000 * 1_000),
+        DateField::Millisecond => {
+            datetime.with_nanosecond(time.nanosecond() / 1_000_000 * 1_000_000)
+        }
+        DateField::Second => datetime.with_nanosecond(0),
+        DateField::Minute => date.and_hms_opt(time.hour(), time.minute(), 0),
+        DateField::Hour => date.and_hms_opt(time.hour(), 0, 0),
+        DateField::Day => Some(date.and_time(NaiveTime::MIN)),
+        DateField::Week => date
+            .checked_sub_days(Days::new(date.weekday().num_days_from_monday() as u64))
+            .map(|date| date.and_time(NaiveTime::MIN)),
+        DateField::Month => first_day(year, date.month()),
+        DateField::Quarter => first_day(year, (date.month() - 1) / 3 * 3 + 1),
+        DateField::Year => first_day(year, 1),
+        DateField::Decade => first_day(year - year.rem_euclid(10), 1),
+        DateField::Century => first_day((year - 1).div_euclid(100) * 100 + 1, 1),
+        field => {
+            return Err(DatabaseError::InvalidValue(format!(
+                "date/time field {field:?} is not supported by date_trunc"
+            )))
+        }
+    };
+    truncated.ok_or(DatabaseError::OverFlow)
+}
+
+impl ScalarFunctionImpl for DateTrunc {
+    fn eval(
+        &self,
+        exprs: &[ScalarExpression],
+        tuples: Option<&dyn TupleLike>,
+    ) -> Result<DataValue, DatabaseError> {
+        let Some(field) = eval_utf8(&exprs[0], tuples)? else {
+            return Ok(DataValue::Null);
+        };
+        let field = DateField::parse(&field)?;
+        let value = exprs[1].eval(tuples)?;
+        if value.is_null() {
+            return Ok(DataValue::Null);
+        }
+        let datetime = to_naive_datetime(&value).ok_or(DatabaseError::InvalidType)?;
+        from_naive_datetime(&value, truncate(field, datetime)?)
+    }
+
+    fn monotonicity(&self) -> Option<FuncMonotonicity> {
+        None
+    }
+
+    fn return_type(&self) -> &LogicalType {
+        &self.return_type
+    }
+
+    fn summary(&self) -> &FunctionSummary {
+        &self.summary
+    }
+
+    fn is_constant_foldable(&self) -> bool {
+        true
+    }
+}
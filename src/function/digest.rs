@@ -0,0 +1,89 @@
+// Copyright 2024 KipData/KiteSQL
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::errors::DatabaseError;
+use crate::expression::function::scala::{FuncMonotonicity, ScalarFunctionImpl};
+use crate::expression::function::FunctionSummary;
+use crate::expression::ScalarExpression;
+use crate::function::{eval_utf8, utf8_value};
+use crate::types::tuple::TupleLike;
+use crate::types::value::DataValue;
+use crate::types::CharLengthUnits;
+use crate::types::LogicalType;
+use md5::Md5;
+use sha2::{Digest as _, Sha256};
+use std::fmt::Write;
+use std::sync::Arc;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum DigestAlgorithm {
+    Md5,
+    Sha256,
+}
+
+/// `md5(s)` and `sha256(s)`: the lowercase hex digest of the UTF-8 bytes of `s`.
+#[derive(Debug)]
+pub(crate) struct Digest {
+    summary: FunctionSummary,
+    algorithm: DigestAlgorithm,
+}
+
+impl Digest {
+    pub(crate) fn new(algorithm: DigestAlgorithm) -> Arc<Self> {
+        let function_name = match algorithm {
+            DigestAlgorithm::Md5 => "md5",
+            DigestAlgorithm::Sha256 => "sha256",
+        };
+        Arc::new(Self {
+            summary: FunctionSummary {
+                name: function_name.into(),
+                arg_types: vec![LogicalType::Varchar(None, CharLengthUnits::Characters)],
+            },
+            algorithm,
+        })
+    }
+}
+
+impl ScalarFunctionImpl for Digest {
+    fn eval(
+        &self,
+        exprs: &[ScalarExpression],
+        tuples: Option<&dyn TupleLike>,
+    ) -> Result<DataValue, DatabaseError> {
+        let Some(value) = eval_utf8(&exprs[0], tuples)? else {
+            return Ok(DataValue::Null);
+        };
+        let bytes = match self.algorithm {
+            DigestAlgorithm::Md5 => Md5::digest(value.as_bytes()).to_vec(),
+            DigestAlgorithm::Sha256 => Sha256::digest(value.as_bytes()).to_vec(),
+        };
+        let mut hex = String::with_capacity(bytes.len() * 2);
+        for byte in bytes {
+            let _ = write!(hex, "{byte:02x}");
+        }
+        Ok(utf8_value(hex))
+    }
+
+    fn monotonicity(&self) -> Option<FuncMonotonicity> {
+        None
+    }
+
+    fn return_type(&self) -> &LogicalType {
+        &LogicalType::Varchar(None, CharLengthUnits::Characters)
+    }
+
+    fn summary(&self) -> &FunctionSummary {
+        &self.summary
+    }
+}
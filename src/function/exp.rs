@@ -0,0 +1,64 @@
+// Copyright 2024 KipData/KiteSQL
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::errors::DatabaseError;
+use crate::expression::function::scala::{FuncMonotonicity, ScalarFunctionImpl};
+use crate::expression::function::FunctionSummary;
+use crate::expression::ScalarExpression;
+use crate::function::{eval_f64, f64_value};
+use crate::types::tuple::TupleLike;
+use crate::types::value::DataValue;
+use crate::types::LogicalType;
+use std::sync::Arc;
+
+#[derive(Debug)]
+pub(crate) struct Exp {
+    summary: FunctionSummary,
+}
+
+impl Exp {
+    pub(crate) fn new() -> Arc<Self> {
+        Arc::new(Self {
+            summary: FunctionSummary {
+                name: "exp".into(),
+                arg_types: vec![LogicalType::Double],
+            },
+        })
+    }
+}
+
+impl ScalarFunctionImpl for Exp {
+    fn eval(
+        &self,
+        exprs: &[ScalarExpression],
+        tuples: Option<&dyn TupleLike>,
+    ) -> Result<DataValue, DatabaseError> {
+        let Some(value) = eval_f64(&exprs[0], tuples)? else {
+            return Ok(DataValue::Null);
+        };
+        f64_value(value.exp())
+    }
+
+    fn monotonicity(&self) -> Option<FuncMonotonicity> {
+        None
+    }
+
+    fn return_type(&self) -> &LogicalType {
+        &LogicalType::Double
+    }
+
+    fn summary(&self) -> &FunctionSummary {
+        &self.summary
+    }
+}
@@ -0,0 +1,101 @@
+// Copyright 2024 KipData/KiteSQL
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::errors::DatabaseError;
+use crate::expression::function::scala::{FuncMonotonicity, ScalarFunctionImpl};
+use crate::expression::function::FunctionSummary;
+use crate::expression::ScalarExpression;
+use crate::types::tuple::TupleLike;
+use crate::types::value::DataValue;
+use crate::types::LogicalType;
+use std::cmp::Ordering;
+use std::sync::Arc;
+
+/// `greatest(...)` and `least(...)`: the largest or smallest non-`NULL` argument, `NULL`
+/// only when every argument is.
+#[derive(Debug)]
+pub(crate) struct Extremum {
+    summary: FunctionSummary,
+    return_type: LogicalType,
+    ordering: Ordering,
+}
+
+impl Extremum {
+    pub(crate) fn greatest(ty: LogicalType) -> Arc<Self> {
+        Self::new("greatest", Ordering::Greater, ty)
+    }
+
+    pub(crate) fn least(ty: LogicalType) -> Arc<Self> {
+        Self::new("least", Ordering::Less, ty)
+    }
+
+    fn new(function_name: &str, ordering: Ordering, ty: LogicalType) -> Arc<Self> {
+        Arc::new(Self {
+            summary: FunctionSummary {
+                name: function_name.into(),
+                arg_types: vec![ty.clone()],
+            },
+            return_type: ty,
+            ordering,
+        })
+    }
+
+    /// The types the overloads are registered for.
+    pub(crate) fn types() -> Vec<LogicalType> {
+        let mut types = crate::function::numeric_types();
+        types.push(LogicalType::Varchar(
+            None,
+            crate::types::CharLengthUnits::Characters,
+        ));
+        #[cfg(feature = "time")]
+        types.extend([LogicalType::Date, LogicalType::DateTime]);
+        types
+    }
+}
+
+impl ScalarFunctionImpl for Extremum {
+    fn eval(
+        &self,
+        exprs: &[ScalarExpression],
+        tuples: Option<&dyn TupleLike>,
+    ) -> Result<DataValue, DatabaseError> {
+        let mut result = DataValue::Null;
+        for expr in exprs {
+            let value = expr.eval(tuples)?;
+            if value.is_null() {
+                continue;
+            }
+            if result.is_null() || value.partial_cmp(&result) == Some(self.ordering) {
+                result = value;
+            }
+        }
+        Ok(result)
+    }
+
+    fn monotonicity(&self) -> Option<FuncMonotonicity> {
+        None
+    }
+
+    fn return_type(&self) -> &LogicalType {
+        &self.return_type
+    }
+
+    fn summary(&self) -> &FunctionSummary {
+        &self.summary
+    }
+
+    fn is_variadic(&self) -> bool {
+        true
+    }
+}
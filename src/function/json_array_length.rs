@@ -0,0 +1,89 @@
+// Copyright 2024 KipData/KiteSQL
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::errors::DatabaseError;
+use crate::expression::function::scala::{FuncMonotonicity, ScalarFunctionImpl};
+use crate::expression::function::FunctionSummary;
+use crate::expression::ScalarExpression;
+use crate::function::{eval_json, eval_utf8};
+use crate::types::json::{parse_json_path, Json};
+use crate::types::tuple::TupleLike;
+use crate::types::value::DataValue;
+use crate::types::{CharLengthUnits, LogicalType};
+use std::sync::Arc;
+
+/// `json_array_length(json [, path])`: the number of elements of the array, or of the array
+/// at the `$.key[0]` path, which is `NULL` when the path is missing. Other values are an
+/// error.
+#[derive(Debug)]
+pub(crate) struct JsonArrayLength {
+    summary: FunctionSummary,
+}
+
+impl JsonArrayLength {
+    pub(crate) fn new(with_path: bool) -> Arc<Self> {
+        let mut arg_types = vec![LogicalType::Json];
+        if with_path {
+            arg_types.push(LogicalType::Varchar(None, CharLengthUnits::Characters));
+        }
+        Arc::new(Self {
+            summary: FunctionSummary {
+                name: "json_array_length".into(),
+                arg_types,
+            },
+        })
+    }
+}
+
+impl ScalarFunctionImpl for JsonArrayLength {
+    fn eval(
+        &self,
+        exprs: &[ScalarExpression],
+        tuples: Option<&dyn TupleLike>,
+    ) -> Result<DataValue, DatabaseError> {
+        let Some(json) = eval_json(&exprs[0], tuples)? else {
+            return Ok(DataValue::Null);
+        };
+        let json = match exprs.get(1) {
+            Some(expr) => match eval_utf8(expr, tuples)? {
+                Some(path) => json.get_path(&parse_json_path(&path)?),
+                None => None,
+            },
+            None => Some(&json),
+        };
+        match json {
+            None => Ok(DataValue::Null),
+            Some(Json::Array(elements)) => Ok(DataValue::Int64(elements.len() as i64)),
+            Some(json) => Err(DatabaseError::InvalidValue(format!(
+                "cannot get array length of a non-array: {json}"
+            ))),
+        }
+    }
+
+    fn monotonicity(&self) -> Option<FuncMonotonicity> {
+        None
+    }
+
+    fn return_type(&self) -> &LogicalType {
+        &LogicalType::Bigint
+    }
+
+    fn summary(&self) -> &FunctionSummary {
+        &self.summary
+    }
+
+    fn is_constant_foldable(&self) -> bool {
+        true
+    }
+}
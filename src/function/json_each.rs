@@ -0,0 +1,145 @@
+// Copyright 2024 KipData/KiteSQL
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::catalog::ColumnCatalog;
+use crate::catalog::ColumnDesc;
+use crate::errors::DatabaseError;
+use crate::expression::function::table::TableFunctionImpl;
+use crate::expression::function::FunctionSummary;
+use crate::expression::ScalarExpression;
+use crate::function::{eval_json, utf8_value};
+use crate::planner::TableArena;
+use crate::types::json::{push_json_path, Json, JsonPathStep};
+use crate::types::tuple::Schema;
+use crate::types::tuple::Tuple;
+use crate::types::value::DataValue;
+use crate::types::{CharLengthUnits, LogicalType};
+use std::sync::Arc;
+
+/// `json_each(json)` returns a `key`, `value` and `type` row for each member of an object or
+/// element of an array, whose key is its index. A scalar is a single row without a key.
+///
+/// `json_tree(json)` walks the whole document depth first, starting with the document
+/// itself, and adds the `$.key[0]` `path` of each value.
+///
+/// The output columns are allocated when a call is bound, so the registered schema stays empty.
+#[derive(Debug)]
+pub(crate) struct JsonEach {
+    summary: FunctionSummary,
+    is_tree: bool,
+}
+
+impl JsonEach {
+    pub(crate) fn new(is_tree: bool) -> Arc<Self> {
+        Arc::new(Self {
+            summary: FunctionSummary {
+                name: if is_tree { "json_tree" } else { "json_each" }.into(),
+                arg_types: vec![LogicalType::Json],
+            },
+            is_tree,
+        })
+    }
+
+    fn row(key: Option<String>, value: &Json, path: Option<String>) -> Tuple {
+        let mut values = vec![
+            key.map_or(DataValue::Null, utf8_value),
+            DataValue::Json(value.clone()),
+            utf8_value(value.type_name().to_string()),
+        ];
+        values.extend(path.map(utf8_value));
+        Tuple::new(None, values)
+    }
+
+    fn children(json: &Json) -> Vec<(JsonPathStep, &Json)> {
+        match json {
+            Json::Array(elements) => elements
+                .iter()
+                .enumerate()
+                .map(|(i, element)| (JsonPathStep::Index(i as i64), element))
+                .collect(),
+            Json::Object(members) => members
+                .iter()
+                .map(|(key, value)| (JsonPathStep::Key(key.clone()), value))
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    fn key(step: &JsonPathStep) -> String {
+        match step {
+            JsonPathStep::Key(key) => key.clone(),
+            JsonPathStep::Index(index) => index.to_string(),
+        }
+    }
+
+    fn tree(rows: &mut Vec<Tuple>, key: Option<String>, json: &Json, path: String) {
+        rows.push(Self::row(key, json, Some(path.clone())));
+        for (step, child) in Self::children(json) {
+            let mut child_path = path.clone();
+            push_json_path(&mut child_path, &step);
+            Self::tree(rows, Some(Self::key(&step)), child, child_path);
+        }
+    }
+}
+
+impl TableFunctionImpl for JsonEach {
+    fn eval(
+        &self,
+        args: &[ScalarExpression],
+    ) -> Result<Box<dyn Iterator<Item = Result<Tuple, DatabaseError>>>, DatabaseError> {
+        let mut rows = Vec::new();
+        if let Some(json) = eval_json(&args[0], None)? {
+            if self.is_tree {
+                Self::tree(&mut rows, None, &json, "$".to_string());
+            } else if matches!(json, Json::Array(_) | Json::Object(_)) {
+                for (step, child) in Self::children(&json) {
+                    rows.push(Self::row(Some(Self::key(&step)), child, None));
+                }
+            } else {
+                rows.push(Self::row(None, &json, None));
+            }
+        }
+
+        Ok(Box::new(rows.into_iter().map(Ok))
+            as Box<dyn Iterator<Item = Result<Tuple, DatabaseError>>>)
+    }
+
+    fn summary(&self) -> &FunctionSummary {
+        &self.summary
+    }
+
+    fn output_schema_into(&self, _: &mut TableArena, _: &mut Schema) {}
+
+    fn bound_output_columns(&self) -> Vec<ColumnCatalog> {
+        let varchar = LogicalType::Varchar(None, CharLengthUnits::Characters);
+        let mut columns = vec![
+            ("key", varchar.clone()),
+            ("value", LogicalType::Json),
+            ("type", varchar.clone()),
+        ];
+        if self.is_tree {
+            columns.push(("path", varchar));
+        }
+        columns
+            .into_iter()
+            .map(|(name, ty)| {
+                ColumnCatalog::new(
+                    name.to_string(),
+                    true,
+                    ColumnDesc::new(ty, None, false, None).unwrap(),
+                )
+            })
+            .collect()
+    }
+}
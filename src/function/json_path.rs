@@ -0,0 +1,214 @@
+// Copyright 2024 KipData/KiteSQL
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::catalog::ColumnRef;
+use crate::errors::DatabaseError;
+use crate::expression::function::scala::{
+    ArcScalarFunctionImpl, FuncMonotonicity, ScalarFunction, ScalarFunctionImpl,
+};
+use crate::expression::function::FunctionSummary;
+use crate::expression::ScalarExpression;
+use crate::function::{eval_i64, eval_json, eval_utf8, utf8_value};
+use crate::types::index::IndexPathStep;
+use crate::types::json::parse_json_path;
+use crate::types::tuple::TupleLike;
+use crate::types::value::DataValue;
+use crate::types::{CharLengthUnits, LogicalType};
+use std::sync::Arc;
+
+/// How a [`JsonPath`] function finds the value it returns.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum JsonPathKind {
+    /// `json -> 'key'`, `json_object_field(json, key)`
+    ObjectField,
+    /// `json -> 0`, `json_array_element(json, index)`
+    ArrayElement,
+    /// `json #> '{a,0}'`, `json_extract_path(json, VARIADIC path)`
+    ExtractPath,
+    /// `json_extract(json, '$.a[0]')`
+    Extract,
+}
+
+/// The functions behind the `->`, `->>`, `#>` and `#>>` operators, and `json_extract`. They
+/// return `NULL` when the document has no value at the path, and the `_text` variants
+/// return the value as text, `NULL` for a JSON `null`.
+#[derive(Debug)]
+pub(crate) struct JsonPath {
+    summary: FunctionSummary,
+    kind: JsonPathKind,
+    as_text: bool,
+}
+
+impl JsonPath {
+    pub(crate) fn new(kind: JsonPathKind, as_text: bool) -> Arc<Self> {
+        let name = match kind {
+            JsonPathKind::ObjectField => "json_object_field",
+            JsonPathKind::ArrayElement => "json_array_element",
+            JsonPathKind::ExtractPath => "json_extract_path",
+            JsonPathKind::Extract => "json_extract",
+        };
+        let path_type = match kind {
+            JsonPathKind::ArrayElement => LogicalType::Bigint,
+            _ => LogicalType::Varchar(None, CharLengthUnits::Characters),
+        };
+        Arc::new(Self {
+            summary: FunctionSummary {
+                name: if as_text {
+                    format!("{name}_text").into()
+                } else {
+                    name.into()
+                },
+                arg_types: vec![LogicalType::Json, path_type],
+            },
+            kind,
+            as_text,
+        })
+    }
+
+    /// Every function of this kind, as registered with the database.
+    pub(crate) fn all() -> Vec<Arc<Self>> {
+        vec![
+            JsonPath::new(JsonPathKind::ObjectField, false),
+            JsonPath::new(JsonPathKind::ObjectField, true),
+            JsonPath::new(JsonPathKind::ArrayElement, false),
+            JsonPath::new(JsonPathKind::ArrayElement, true),
+            JsonPath::new(JsonPathKind::ExtractPath, false),
+            JsonPath::new(JsonPathKind::ExtractPath, true),
+            JsonPath::new(JsonPathKind::Extract, false),
+        ]
+    }
+
+    fn by_name(name: &str) -> Option<Arc<Self>> {
+        Self::all()
+            .into_iter()
+            .find(|function| function.summary.name.as_ref() == name)
+    }
+}
+
+/// Splits `expr` into the column it reads and the chain of path functions applied to it,
+/// `None` unless it is such a chain with constant paths.
+pub(crate) fn split_json_path(expr: &ScalarExpression) -> Option<(ColumnRef, Vec<IndexPathStep>)> {
+    let mut steps = Vec::new();
+    let mut expr = expr;
+    loop {
+        match expr.unpack_alias_ref() {
+            ScalarExpression::ColumnRef { column, .. } if !steps.is_empty() => {
+                steps.reverse();
+                return Some((*column, steps));
+            }
+            ScalarExpression::ScalaFunction(ScalarFunction { args, inner })
+                if JsonPath::by_name(&inner.summary().name).is_some() =>
+            {
+                steps.push(IndexPathStep {
+                    function: inner.summary().name.to_string(),
+                    args: args[1..]
+                        .iter()
+                        .map(ScalarExpression::unpack_val)
+                        .collect::<Option<_>>()?,
+                });
+                expr = &args[0];
+            }
+            _ => return None,
+        }
+    }
+}
+
+/// Applies the path functions of `json_path` to `expr`, the inverse of [`split_json_path`].
+pub(crate) fn json_path_expr(
+    expr: ScalarExpression,
+    json_path: &[IndexPathStep],
+) -> Result<ScalarExpression, DatabaseError> {
+    json_path.iter().try_fold(expr, |expr, step| {
+        let function = JsonPath::by_name(&step.function)
+            .ok_or_else(|| DatabaseError::function_not_found(step.function.clone()))?;
+        let mut args = Vec::with_capacity(step.args.len() + 1);
+        args.push(expr);
+        args.extend(step.args.iter().cloned().map(ScalarExpression::Constant));
+        Ok(ScalarExpression::ScalaFunction(ScalarFunction {
+            args,
+            inner: ArcScalarFunctionImpl(function),
+        }))
+    })
+}
+
+/// The type of the values an index on `json_path` stores.
+pub(crate) fn json_path_type(json_path: &[IndexPathStep]) -> Option<LogicalType> {
+    let step = json_path.last()?;
+    JsonPath::by_name(&step.function).map(|function| function.return_type().clone())
+}
+
+impl ScalarFunctionImpl for JsonPath {
+    fn eval(
+        &self,
+        exprs: &[ScalarExpression],
+        tuples: Option<&dyn TupleLike>,
+    ) -> Result<DataValue, DatabaseError> {
+        let Some(json) = eval_json(&exprs[0], tuples)? else {
+            return Ok(DataValue::Null);
+        };
+        let value = match self.kind {
+            JsonPathKind::ObjectField => match eval_utf8(&exprs[1], tuples)? {
+                Some(key) => json.get_key(&key),
+                None => None,
+            },
+            JsonPathKind::ArrayElement => match eval_i64(&exprs[1], tuples)? {
+                Some(index) => json.get_index(index),
+                None => None,
+            },
+            JsonPathKind::ExtractPath => {
+                let path = exprs[1..]
+                    .iter()
+                    .map(|expr| eval_utf8(expr, tuples))
+                    .collect::<Result<Option<Vec<_>>, _>>()?;
+                match path {
+                    Some(path) => json.get_text_path(&path),
+                    None => None,
+                }
+            }
+            JsonPathKind::Extract => match eval_utf8(&exprs[1], tuples)? {
+                Some(path) => json.get_path(&parse_json_path(&path)?),
+                None => None,
+            },
+        };
+        Ok(match value {
+            None => DataValue::Null,
+            Some(value) if self.as_text => value.to_text().map_or(DataValue::Null, utf8_value),
+            Some(value) => DataValue::Json(value.clone()),
+        })
+    }
+
+    fn monotonicity(&self) -> Option<FuncMonotonicity> {
+        None
+    }
+
+    fn return_type(&self) -> &LogicalType {
+        if self.as_text {
+            &LogicalType::Varchar(None, CharLengthUnits::Characters)
+        } else {
+            &LogicalType::Json
+        }
+    }
+
+    fn summary(&self) -> &FunctionSummary {
+        &self.summary
+    }
+
+    fn is_variadic(&self) -> bool {
+        self.kind == JsonPathKind::ExtractPath
+    }
+
+    fn is_constant_foldable(&self) -> bool {
+        true
+    }
+}
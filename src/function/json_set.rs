@@ -0,0 +1,94 @@
+// Copyright 2024 KipData/KiteSQL
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::errors::DatabaseError;
+use crate::expression::function::scala::{FuncMonotonicity, ScalarFunctionImpl};
+use crate::expression::function::FunctionSummary;
+use crate::expression::ScalarExpression;
+use crate::function::{eval_json, eval_utf8};
+use crate::types::json::{parse_json_path, Json};
+use crate::types::tuple::TupleLike;
+use crate::types::value::DataValue;
+use crate::types::{CharLengthUnits, LogicalType};
+use std::sync::Arc;
+
+/// `json_set(json, path, value)`: replaces the value at the `$.key[0]` path, adding it when
+/// only its last key or index is missing. SQL values become their JSON counterparts, so
+/// strings are stored as JSON strings and `NULL` as a JSON `null`.
+#[derive(Debug)]
+pub(crate) struct JsonSet {
+    summary: FunctionSummary,
+}
+
+impl JsonSet {
+    pub(crate) fn new(value_type: LogicalType) -> Arc<Self> {
+        Arc::new(Self {
+            summary: FunctionSummary {
+                name: "json_set".into(),
+                arg_types: vec![
+                    LogicalType::Json,
+                    LogicalType::Varchar(None, CharLengthUnits::Characters),
+                    value_type,
+                ],
+            },
+        })
+    }
+
+    /// The value types `json_set` is overloaded for.
+    pub(crate) fn value_types() -> Vec<LogicalType> {
+        vec![
+            LogicalType::Json,
+            LogicalType::Boolean,
+            LogicalType::Bigint,
+            LogicalType::Double,
+            #[cfg(feature = "decimal")]
+            LogicalType::Decimal(None, None),
+            LogicalType::Varchar(None, CharLengthUnits::Characters),
+        ]
+    }
+}
+
+impl ScalarFunctionImpl for JsonSet {
+    fn eval(
+        &self,
+        exprs: &[ScalarExpression],
+        tuples: Option<&dyn TupleLike>,
+    ) -> Result<DataValue, DatabaseError> {
+        let (Some(mut json), Some(path)) =
+            (eval_json(&exprs[0], tuples)?, eval_utf8(&exprs[1], tuples)?)
+        else {
+            return Ok(DataValue::Null);
+        };
+        let value = Json::from_data_value(&exprs[2].eval(tuples)?);
+        json.set_path(&parse_json_path(&path)?, value);
+
+        Ok(DataValue::Json(json))
+    }
+
+    fn monotonicity(&self) -> Option<FuncMonotonicity> {
+        None
+    }
+
+    fn return_type(&self) -> &LogicalType {
+        &LogicalType::Json
+    }
+
+    fn summary(&self) -> &FunctionSummary {
+        &self.summary
+    }
+
+    fn is_constant_foldable(&self) -> bool {
+        true
+    }
+}
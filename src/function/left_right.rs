@@ -0,0 +1,87 @@
+// Copyright 2024 KipData/KiteSQL
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::errors::DatabaseError;
+use crate::expression::function::scala::{FuncMonotonicity, ScalarFunctionImpl};
+use crate::expression::function::FunctionSummary;
+use crate::expression::ScalarExpression;
+use crate::function::{eval_i64, eval_utf8, utf8_value, Side};
+use crate::types::tuple::TupleLike;
+use crate::types::value::DataValue;
+use crate::types::CharLengthUnits;
+use crate::types::LogicalType;
+use std::sync::Arc;
+
+/// `left(s, n)` and `right(s, n)`: the first or last `n` characters of `s`. A negative `n`
+/// keeps all but the last or first `|n|` characters.
+#[derive(Debug)]
+pub(crate) struct LeftRight {
+    summary: FunctionSummary,
+    side: Side,
+}
+
+impl LeftRight {
+    pub(crate) fn new(side: Side) -> Arc<Self> {
+        let function_name = match side {
+            Side::Left => "left",
+            Side::Right => "right",
+        };
+        Arc::new(Self {
+            summary: FunctionSummary {
+                name: function_name.into(),
+                arg_types: vec![
+                    LogicalType::Varchar(None, CharLengthUnits::Characters),
+                    LogicalType::Bigint,
+                ],
+            },
+            side,
+        })
+    }
+}
+
+impl ScalarFunctionImpl for LeftRight {
+    fn eval(
+        &self,
+        exprs: &[ScalarExpression],
+        tuples: Option<&dyn TupleLike>,
+    ) -> Result<DataValue, DatabaseError> {
+        let (Some(value), Some(n)) = (eval_utf8(&exprs[0], tuples)?, eval_i64(&exprs[1], tuples)?)
+        else {
+            return Ok(DataValue::Null);
+        };
+        let value_len = value.chars().count() as i64;
+        let len = if n >= 0 {
+            n.min(value_len)
+        } else {
+            (value_len + n).max(0)
+        } as usize;
+
+        Ok(utf8_value(match self.side {
+            Side::Left => value.chars().take(len).collect(),
+            Side::Right => value.chars().skip(value_len as usize - len).collect(),
+        }))
+    }
+
+    fn monotonicity(&self) -> Option<FuncMonotonicity> {
+        None
+    }
+
+    fn return_type(&self) -> &LogicalType {
+        &LogicalType::Varchar(None, CharLengthUnits::Characters)
+    }
+
+    fn summary(&self) -> &FunctionSummary {
+        &self.summary
+    }
+}
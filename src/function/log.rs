@@ -0,0 +1,85 @@
+// Copyright 2024 KipData/KiteSQL
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::errors::DatabaseError;
+use crate::expression::function::scala::{FuncMonotonicity, ScalarFunctionImpl};
+use crate::expression::function::FunctionSummary;
+use crate::expression::ScalarExpression;
+use crate::function::eval_f64;
+use crate::function::ln::check_logarithm_arg;
+use crate::types::tuple::TupleLike;
+use crate::types::value::DataValue;
+use crate::types::LogicalType;
+use std::sync::Arc;
+
+/// `log(x)` is the base 10 logarithm, `log(b, x)` the base `b` one.
+#[derive(Debug)]
+pub(crate) struct Log {
+    summary: FunctionSummary,
+}
+
+impl Log {
+    pub(crate) fn new(with_base: bool) -> Arc<Self> {
+        let arg_types = if with_base {
+            vec![LogicalType::Double, LogicalType::Double]
+        } else {
+            vec![LogicalType::Double]
+        };
+        Arc::new(Self {
+            summary: FunctionSummary {
+                name: "log".into(),
+                arg_types,
+            },
+        })
+    }
+}
+
+impl ScalarFunctionImpl for Log {
+    fn eval(
+        &self,
+        exprs: &[ScalarExpression],
+        tuples: Option<&dyn TupleLike>,
+    ) -> Result<DataValue, DatabaseError> {
+        let mut values = Vec::with_capacity(exprs.len());
+        for expr in exprs {
+            let Some(value) = eval_f64(expr, tuples)? else {
+                return Ok(DataValue::Null);
+            };
+            values.push(check_logarithm_arg(value)?);
+        }
+        let value = match values.as_slice() {
+            [value] => value.log10(),
+            [base, value] => {
+                if *base == 1.0 {
+                    return Err(DatabaseError::DivisionByZero);
+                }
+                value.ln() / base.ln()
+            }
+            _ => unreachable!("log() is bound with one or two arguments"),
+        };
+        Ok(DataValue::Float64(value.into()))
+    }
+
+    fn monotonicity(&self) -> Option<FuncMonotonicity> {
+        None
+    }
+
+    fn return_type(&self) -> &LogicalType {
+        &LogicalType::Double
+    }
+
+    fn summary(&self) -> &FunctionSummary {
+        &self.summary
+    }
+}
@@ -12,12 +12,193 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+pub(crate) mod abs;
+#[cfg(feature = "time")]
+pub(crate) mod at_time_zone;
+pub(crate) mod ceil;
 pub(crate) mod char_length;
+pub(crate) mod concat;
+pub(crate) mod concat_ws;
 #[cfg(feature = "time")]
 pub(crate) mod current_date;
 #[cfg(feature = "time")]
 pub(crate) mod current_timestamp;
+#[cfg(feature = "time")]
+pub(crate) mod date_add;
+#[cfg(feature = "time")]
+pub(crate) mod date_part;
+#[cfg(feature = "time")]
+pub(crate) mod date_trunc;
+#[cfg(feature = "digest")]
+pub(crate) mod digest;
+pub(crate) mod exp;
+pub(crate) mod extremum;
+pub(crate) mod floor;
+pub(crate) mod json_array_length;
+pub(crate) mod json_each;
+pub(crate) mod json_path;
+pub(crate) mod json_set;
+pub(crate) mod json_typeof;
+pub(crate) mod left_right;
+pub(crate) mod ln;
+pub(crate) mod log;
 pub(crate) mod lower;
+pub(crate) mod modulo;
 pub(crate) mod numbers;
 pub(crate) mod octet_length;
+pub(crate) mod pad;
+pub(crate) mod power;
+pub(crate) mod repeat;
+pub(crate) mod replace;
+pub(crate) mod reverse;
+pub(crate) mod round;
+pub(crate) mod sign;
+pub(crate) mod split_part;
+pub(crate) mod sqrt;
+pub(crate) mod starts_with;
+#[cfg(feature = "time")]
+pub(crate) mod to_char;
+#[cfg(feature = "time")]
+pub(crate) mod to_date;
+pub(crate) mod trim;
+pub(crate) mod unnest;
 pub(crate) mod upper;
+
+use crate::errors::DatabaseError;
+use crate::expression::ScalarExpression;
+use crate::types::json::Json;
+use crate::types::tuple::TupleLike;
+use crate::types::value::{DataValue, Utf8Type};
+use crate::types::{CharLengthUnits, LogicalType};
+
+/// Which end of a string `lpad`/`rpad`, `ltrim`/`rtrim` and `left`/`right` work on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Side {
+    Left,
+    Right,
+}
+
+/// The longest string, in bytes, `repeat`, `lpad` and `rpad` build: PostgreSQL's 1 GiB field limit.
+pub(crate) const MAX_STRING_LEN: usize = (1 << 30) - 1;
+
+/// Rejects a `function_name` result of `len` bytes longer than [`MAX_STRING_LEN`].
+pub(crate) fn check_string_len(
+    function_name: &str,
+    len: Option<usize>,
+) -> Result<(), DatabaseError> {
+    match len {
+        Some(len) if len <= MAX_STRING_LEN => Ok(()),
+        _ => Err(DatabaseError::InvalidValue(format!(
+            "{function_name}() requested length too large, the limit is {MAX_STRING_LEN} bytes"
+        ))),
+    }
+}
+
+/// The numeric types math functions returning their argument type are overloaded for.
+pub(crate) fn numeric_types() -> Vec<LogicalType> {
+    vec![
+        LogicalType::Tinyint,
+        LogicalType::UTinyint,
+        LogicalType::Smallint,
+        LogicalType::USmallint,
+        LogicalType::Integer,
+        LogicalType::UInteger,
+        LogicalType::Bigint,
+        LogicalType::UBigint,
+        LogicalType::Float,
+        LogicalType::Double,
+        #[cfg(feature = "decimal")]
+        LogicalType::Decimal(None, None),
+    ]
+}
+
+/// The types math functions producing fractional results are overloaded for.
+pub(crate) fn fractional_types() -> Vec<LogicalType> {
+    vec![
+        LogicalType::Double,
+        #[cfg(feature = "decimal")]
+        LogicalType::Decimal(None, None),
+    ]
+}
+
+/// The date and timestamp types date/time functions are overloaded for, so every timestamp
+/// keeps its precision and zone.
+#[cfg(feature = "time")]
+pub(crate) fn temporal_types() -> Vec<LogicalType> {
+    let mut types = vec![LogicalType::Date, LogicalType::DateTime];
+    for zone in [false, true] {
+        for precision in [None, Some(3), Some(6), Some(9)] {
+            types.push(LogicalType::TimeStamp(precision, zone));
+        }
+    }
+    types
+}
+
+// The binder casts arguments to the declared argument types, so the helpers below only
+// fall back to a cast for callers that evaluate a function without binding it.
+
+pub(crate) fn eval_utf8(
+    expr: &ScalarExpression,
+    tuple: Option<&dyn TupleLike>,
+) -> Result<Option<String>, DatabaseError> {
+    Ok(match expr.eval(tuple)? {
+        DataValue::Null => None,
+        DataValue::Utf8 { value, .. } => Some(value),
+        value => value
+            .cast(&LogicalType::Varchar(None, CharLengthUnits::Characters))?
+            .utf8()
+            .map(str::to_string),
+    })
+}
+
+pub(crate) fn eval_json(
+    expr: &ScalarExpression,
+    tuple: Option<&dyn TupleLike>,
+) -> Result<Option<Json>, DatabaseError> {
+    Ok(match expr.eval(tuple)? {
+        DataValue::Null => None,
+        DataValue::Json(value) => Some(value),
+        value => match value.cast(&LogicalType::Json)? {
+            DataValue::Json(value) => Some(value),
+            _ => None,
+        },
+    })
+}
+
+pub(crate) fn eval_i64(
+    expr: &ScalarExpression,
+    tuple: Option<&dyn TupleLike>,
+) -> Result<Option<i64>, DatabaseError> {
+    Ok(match expr.eval(tuple)? {
+        DataValue::Null => None,
+        DataValue::Int64(value) => Some(value),
+        value => value.cast(&LogicalType::Bigint)?.i64(),
+    })
+}
+
+pub(crate) fn eval_f64(
+    expr: &ScalarExpression,
+    tuple: Option<&dyn TupleLike>,
+) -> Result<Option<f64>, DatabaseError> {
+    Ok(match expr.eval(tuple)? {
+        DataValue::Null => None,
+        DataValue::Float64(value) => Some(value.0),
+        value => value.cast(&LogicalType::Double)?.double(),
+    })
+}
+
+pub(crate) fn utf8_value(value: String) -> DataValue {
+    DataValue::Utf8 {
+        value,
+        ty: Utf8Type::Variable(None),
+        unit: CharLengthUnits::Characters,
+    }
+}
+
+/// Wraps a `f64` result, rejecting the infinities a finite input overflowed into.
+pub(crate) fn f64_value(value: f64) -> Result<DataValue, DatabaseError> {
+    if value.is_infinite() {
+        return Err(DatabaseError::OverFlow);
+    }
+    Ok(DataValue::Float64(value.into()))
+}
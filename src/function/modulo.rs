@@ -0,0 +1,110 @@
+// Copyright 2024 KipData/KiteSQL
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::errors::DatabaseError;
+use crate::expression::function::scala::{FuncMonotonicity, ScalarFunctionImpl};
+use crate::expression::function::FunctionSummary;
+use crate::expression::ScalarExpression;
+use crate::types::tuple::TupleLike;
+use crate::types::value::DataValue;
+use crate::types::LogicalType;
+use std::sync::Arc;
+
+/// `mod(x, y)`: the remainder of `x / y`, taking the sign of `x`.
+#[derive(Debug)]
+pub(crate) struct Modulo {
+    summary: FunctionSummary,
+    return_type: LogicalType,
+}
+
+impl Modulo {
+    pub(crate) fn new(ty: LogicalType) -> Arc<Self> {
+        Arc::new(Self {
+            summary: FunctionSummary {
+                name: "mod".into(),
+                arg_types: vec![ty.clone(), ty.clone()],
+            },
+            return_type: ty,
+        })
+    }
+}
+
+macro_rules! checked_rem {
+    ($variant:ident, $left:expr, $right:expr) => {{
+        if $right == 0 {
+            return Err(DatabaseError::DivisionByZero);
+        }
+        DataValue::$variant($left.checked_rem($right).ok_or(DatabaseError::OverFlow)?)
+    }};
+}
+
+impl ScalarFunctionImpl for Modulo {
+    fn eval(
+        &self,
+        exprs: &[ScalarExpression],
+        tuples: Option<&dyn TupleLike>,
+    ) -> Result<DataValue, DatabaseError> {
+        let left = exprs[0].eval(tuples)?;
+        let right = exprs[1].eval(tuples)?;
+
+        Ok(match (left, right) {
+            (DataValue::Null, _) | (_, DataValue::Null) => DataValue::Null,
+            (DataValue::Int8(l), DataValue::Int8(r)) => checked_rem!(Int8, l, r),
+            (DataValue::Int16(l), DataValue::Int16(r)) => checked_rem!(Int16, l, r),
+            (DataValue::Int32(l), DataValue::Int32(r)) => checked_rem!(Int32, l, r),
+            (DataValue::Int64(l), DataValue::Int64(r)) => checked_rem!(Int64, l, r),
+            (DataValue::UInt8(l), DataValue::UInt8(r)) => checked_rem!(UInt8, l, r),
+            (DataValue::UInt16(l), DataValue::UInt16(r)) => checked_rem!(UInt16, l, r),
+            (DataValue::UInt32(l), DataValue::UInt32(r)) => checked_rem!(UInt32, l, r),
+            (DataValue::UInt64(l), DataValue::UInt64(r)) => checked_rem!(UInt64, l, r),
+            (DataValue::Float32(l), DataValue::Float32(r)) => {
+                if r.0 == 0.0 {
+                    return Err(DatabaseError::DivisionByZero);
+                }
+                DataValue::Float32((l.0 % r.0).into())
+            }
+            (DataValue::Float64(l), DataValue::Float64(r)) => {
+                if r.0 == 0.0 {
+                    return Err(DatabaseError::DivisionByZero);
+                }
+                DataValue::Float64((l.0 % r.0).into())
+            }
+            #[cfg(feature = "decimal")]
+            (DataValue::Decimal(l), DataValue::Decimal(r)) => {
+                if r.is_zero() {
+                    return Err(DatabaseError::DivisionByZero);
+                }
+                DataValue::Decimal(l.checked_rem(r).ok_or(DatabaseError::OverFlow)?)
+            }
+            _ => {
+                return Err(DatabaseError::MisMatch(
+                    "the arguments of mod()",
+                    "two values of the same numeric type",
+                ))
+            }
+        })
+    }
+
+    fn monotonicity(&self) -> Option<FuncMonotonicity> {
+        None
+    }
+
+    fn return_type(&self) -> &LogicalType {
+        &self.return_type
+    }
+
+    fn summary(&self) -> &FunctionSummary {
+        &self.summary
+    }
+}
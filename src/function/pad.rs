@@ -0,0 +1,104 @@
+// Copyright 2024 KipData/KiteSQL
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::errors::DatabaseError;
+use crate::expression::function::scala::{FuncMonotonicity, ScalarFunctionImpl};
+use crate::expression::function::FunctionSummary;
+use crate::expression::ScalarExpression;
+use crate::function::{check_string_len, eval_i64, eval_utf8, utf8_value, Side};
+use crate::types::tuple::TupleLike;
+use crate::types::value::DataValue;
+use crate::types::CharLengthUnits;
+use crate::types::LogicalType;
+use std::sync::Arc;
+
+/// `lpad(s, len [, fill])` and `rpad(s, len [, fill])`: pads `s` to `len` characters with
+/// `fill` (a space by default), or truncates it when it is longer.
+#[derive(Debug)]
+pub(crate) struct Pad {
+    summary: FunctionSummary,
+    side: Side,
+}
+
+impl Pad {
+    pub(crate) fn new(side: Side, with_fill: bool) -> Arc<Self> {
+        let function_name = match side {
+            Side::Left => "lpad",
+            Side::Right => "rpad",
+        };
+        let mut arg_types = vec![
+            LogicalType::Varchar(None, CharLengthUnits::Characters),
+            LogicalType::Bigint,
+        ];
+        if with_fill {
+            arg_types.push(LogicalType::Varchar(None, CharLengthUnits::Characters));
+        }
+        Arc::new(Self {
+            summary: FunctionSummary {
+                name: function_name.into(),
+                arg_types,
+            },
+            side,
+        })
+    }
+}
+
+impl ScalarFunctionImpl for Pad {
+    fn eval(
+        &self,
+        exprs: &[ScalarExpression],
+        tuples: Option<&dyn TupleLike>,
+    ) -> Result<DataValue, DatabaseError> {
+        let (Some(value), Some(len)) =
+            (eval_utf8(&exprs[0], tuples)?, eval_i64(&exprs[1], tuples)?)
+        else {
+            return Ok(DataValue::Null);
+        };
+        let fill = match exprs.get(2) {
+            Some(expr) => match eval_utf8(expr, tuples)? {
+                Some(fill) => fill,
+                None => return Ok(DataValue::Null),
+            },
+            None => " ".to_string(),
+        };
+        let len = len.max(0) as usize;
+        // Tips: like PostgreSQL, budget every character at the widest UTF-8 encoding
+        check_string_len(&self.summary.name, len.checked_mul(4))?;
+        let value_len = value.chars().count();
+        if value_len >= len {
+            return Ok(utf8_value(value.chars().take(len).collect()));
+        }
+        if fill.is_empty() {
+            return Ok(utf8_value(value));
+        }
+        let padding = fill.chars().cycle().take(len - value_len);
+
+        Ok(utf8_value(match self.side {
+            Side::Left => padding.chain(value.chars()).collect(),
+            Side::Right => value.chars().chain(padding).collect(),
+        }))
+    }
+
+    fn monotonicity(&self) -> Option<FuncMonotonicity> {
+        None
+    }
+
+    fn return_type(&self) -> &LogicalType {
+        &LogicalType::Varchar(None, CharLengthUnits::Characters)
+    }
+
+    fn summary(&self) -> &FunctionSummary {
+        &self.summary
+    }
+}
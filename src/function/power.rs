@@ -0,0 +1,77 @@
+// Copyright 2024 KipData/KiteSQL
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::errors::DatabaseError;
+use crate::expression::function::scala::{FuncMonotonicity, ScalarFunctionImpl};
+use crate::expression::function::FunctionSummary;
+use crate::expression::ScalarExpression;
+use crate::function::{eval_f64, f64_value};
+use crate::types::tuple::TupleLike;
+use crate::types::value::DataValue;
+use crate::types::LogicalType;
+use std::sync::Arc;
+
+#[derive(Debug)]
+pub(crate) struct Power {
+    summary: FunctionSummary,
+}
+
+impl Power {
+    pub(crate) fn new(function_name: String) -> Arc<Self> {
+        Arc::new(Self {
+            summary: FunctionSummary {
+                name: function_name.into(),
+                arg_types: vec![LogicalType::Double, LogicalType::Double],
+            },
+        })
+    }
+}
+
+impl ScalarFunctionImpl for Power {
+    fn eval(
+        &self,
+        exprs: &[ScalarExpression],
+        tuples: Option<&dyn TupleLike>,
+    ) -> Result<DataValue, DatabaseError> {
+        let (Some(base), Some(exponent)) =
+            (eval_f64(&exprs[0], tuples)?, eval_f64(&exprs[1], tuples)?)
+        else {
+            return Ok(DataValue::Null);
+        };
+        if base == 0.0 && exponent < 0.0 {
+            return Err(DatabaseError::InvalidValue(
+                "zero raised to a negative power is undefined".to_string(),
+            ));
+        }
+        if base < 0.0 && exponent.fract() != 0.0 {
+            return Err(DatabaseError::InvalidValue(
+                "a negative number raised to a non-integer power yields a complex result"
+                    .to_string(),
+            ));
+        }
+        f64_value(base.powf(exponent))
+    }
+
+    fn monotonicity(&self) -> Option<FuncMonotonicity> {
+        None
+    }
+
+    fn return_type(&self) -> &LogicalType {
+        &LogicalType::Double
+    }
+
+    fn summary(&self) -> &FunctionSummary {
+        &self.summary
+    }
+}
@@ -0,0 +1,72 @@
+// Copyright 2024 KipData/KiteSQL
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::errors::DatabaseError;
+use crate::expression::function::scala::{FuncMonotonicity, ScalarFunctionImpl};
+use crate::expression::function::FunctionSummary;
+use crate::expression::ScalarExpression;
+use crate::function::{check_string_len, eval_i64, eval_utf8, utf8_value};
+use crate::types::tuple::TupleLike;
+use crate::types::value::DataValue;
+use crate::types::CharLengthUnits;
+use crate::types::LogicalType;
+use std::sync::Arc;
+
+#[derive(Debug)]
+pub(crate) struct Repeat {
+    summary: FunctionSummary,
+}
+
+impl Repeat {
+    pub(crate) fn new() -> Arc<Self> {
+        Arc::new(Self {
+            summary: FunctionSummary {
+                name: "repeat".into(),
+                arg_types: vec![
+                    LogicalType::Varchar(None, CharLengthUnits::Characters),
+                    LogicalType::Bigint,
+                ],
+            },
+        })
+    }
+}
+
+impl ScalarFunctionImpl for Repeat {
+    fn eval(
+        &self,
+        exprs: &[ScalarExpression],
+        tuples: Option<&dyn TupleLike>,
+    ) -> Result<DataValue, DatabaseError> {
+        let (Some(value), Some(count)) =
+            (eval_utf8(&exprs[0], tuples)?, eval_i64(&exprs[1], tuples)?)
+        else {
+            return Ok(DataValue::Null);
+        };
+        let count = count.max(0) as usize;
+        check_string_len("repeat", value.len().checked_mul(count))?;
+        Ok(utf8_value(value.repeat(count)))
+    }
+
+    fn monotonicity(&self) -> Option<FuncMonotonicity> {
+        None
+    }
+
+    fn return_type(&self) -> &LogicalType {
+        &LogicalType::Varchar(None, CharLengthUnits::Characters)
+    }
+
+    fn summary(&self) -> &FunctionSummary {
+        &self.summary
+    }
+}
@@ -0,0 +1,118 @@
+// Copyright 2024 KipData/KiteSQL
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::errors::DatabaseError;
+use crate::expression::function::scala::{FuncMonotonicity, ScalarFunctionImpl};
+use crate::expression::function::FunctionSummary;
+use crate::expression::ScalarExpression;
+use crate::function::eval_i64;
+use crate::types::tuple::TupleLike;
+use crate::types::value::DataValue;
+use crate::types::LogicalType;
+#[cfg(feature = "decimal")]
+use rust_decimal::{Decimal, RoundingStrategy};
+use std::sync::Arc;
+
+/// `round(x [, scale])`: rounds half away from zero to `scale` decimal places, which may
+/// be negative to round to tens, hundreds and so on.
+#[derive(Debug)]
+pub(crate) struct Round {
+    summary: FunctionSummary,
+    return_type: LogicalType,
+}
+
+impl Round {
+    pub(crate) fn new(ty: LogicalType, with_scale: bool) -> Arc<Self> {
+        let mut arg_types = vec![ty.clone()];
+        if with_scale {
+            arg_types.push(LogicalType::Bigint);
+        }
+        Arc::new(Self {
+            summary: FunctionSummary {
+                name: "round".into(),
+                arg_types,
+            },
+            return_type: ty,
+        })
+    }
+}
+
+fn round_f64(value: f64, scale: i64) -> f64 {
+    let scale = scale.clamp(-308, 308) as i32;
+    let factor = 10f64.powi(scale.abs());
+    if scale >= 0 {
+        let scaled = value * factor;
+        // the value has no digits beyond what an f64 holds at this scale
+        if !scaled.is_finite() {
+            return value;
+        }
+        scaled.round() / factor
+    } else {
+        (value / factor).round() * factor
+    }
+}
+
+#[cfg(feature = "decimal")]
+fn round_decimal(value: Decimal, scale: i64) -> Result<Decimal, DatabaseError> {
+    if scale >= 0 {
+        return Ok(value.round_dp_with_strategy(
+            scale.min(Decimal::MAX_SCALE as i64) as u32,
+            RoundingStrategy::MidpointAwayFromZero,
+        ));
+    }
+    if -scale > Decimal::MAX_SCALE as i64 {
+        return Ok(Decimal::ZERO);
+    }
+    let factor = Decimal::from_i128_with_scale(10i128.pow(-scale as u32), 0);
+    (value / factor)
+        .round_dp_with_strategy(0, RoundingStrategy::MidpointAwayFromZero)
+        .checked_mul(factor)
+        .ok_or(DatabaseError::OverFlow)
+}
+
+impl ScalarFunctionImpl for Round {
+    fn eval(
+        &self,
+        exprs: &[ScalarExpression],
+        tuples: Option<&dyn TupleLike>,
+    ) -> Result<DataValue, DatabaseError> {
+        let value = exprs[0].eval(tuples)?;
+        let scale = match exprs.get(1) {
+            Some(expr) => match eval_i64(expr, tuples)? {
+                Some(scale) => scale,
+                None => return Ok(DataValue::Null),
+            },
+            None => 0,
+        };
+
+        Ok(match value {
+            DataValue::Float64(v) => DataValue::Float64(round_f64(v.0, scale).into()),
+            #[cfg(feature = "decimal")]
+            DataValue::Decimal(v) => DataValue::Decimal(round_decimal(v, scale)?),
+            value => value,
+        })
+    }
+
+    fn monotonicity(&self) -> Option<FuncMonotonicity> {
+        None
+    }
+
+    fn return_type(&self) -> &LogicalType {
+        &self.return_type
+    }
+
+    fn summary(&self) -> &FunctionSummary {
+        &self.summary
+    }
+}
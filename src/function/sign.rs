@@ -0,0 +1,91 @@
+// Copyright 2024 KipData/KiteSQL
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::errors::DatabaseError;
+use crate::expression::function::scala::{FuncMonotonicity, ScalarFunctionImpl};
+use crate::expression::function::FunctionSummary;
+use crate::expression::ScalarExpression;
+use crate::types::tuple::TupleLike;
+use crate::types::value::DataValue;
+use crate::types::LogicalType;
+use std::sync::Arc;
+
+/// `sign(x)`: -1, 0 or 1 in the type of `x`.
+#[derive(Debug)]
+pub(crate) struct Sign {
+    summary: FunctionSummary,
+    return_type: LogicalType,
+}
+
+impl Sign {
+    pub(crate) fn new(ty: LogicalType) -> Arc<Self> {
+        Arc::new(Self {
+            summary: FunctionSummary {
+                name: "sign".into(),
+                arg_types: vec![ty.clone()],
+            },
+            return_type: ty,
+        })
+    }
+}
+
+fn float_sign(v: f64) -> f64 {
+    if v == 0.0 || v.is_nan() {
+        v
+    } else {
+        v.signum()
+    }
+}
+
+impl ScalarFunctionImpl for Sign {
+    fn eval(
+        &self,
+        exprs: &[ScalarExpression],
+        tuples: Option<&dyn TupleLike>,
+    ) -> Result<DataValue, DatabaseError> {
+        Ok(match exprs[0].eval(tuples)? {
+            DataValue::Int8(v) => DataValue::Int8(v.signum()),
+            DataValue::Int16(v) => DataValue::Int16(v.signum()),
+            DataValue::Int32(v) => DataValue::Int32(v.signum()),
+            DataValue::Int64(v) => DataValue::Int64(v.signum()),
+            DataValue::UInt8(v) => DataValue::UInt8(v.min(1)),
+            DataValue::UInt16(v) => DataValue::UInt16(v.min(1)),
+            DataValue::UInt32(v) => DataValue::UInt32(v.min(1)),
+            DataValue::UInt64(v) => DataValue::UInt64(v.min(1)),
+            DataValue::Float32(v) => DataValue::Float32((float_sign(v.0 as f64) as f32).into()),
+            DataValue::Float64(v) => DataValue::Float64(float_sign(v.0).into()),
+            #[cfg(feature = "decimal")]
+            DataValue::Decimal(v) => DataValue::Decimal(if v.is_zero() {
+                v
+            } else if v.is_sign_negative() {
+                rust_decimal::Decimal::NEGATIVE_ONE
+            } else {
+                rust_decimal::Decimal::ONE
+            }),
+            value => value,
+        })
+    }
+
+    fn monotonicity(&self) -> Option<FuncMonotonicity> {
+        None
+    }
+
+    fn return_type(&self) -> &LogicalType {
+        &self.return_type
+    }
+
+    fn summary(&self) -> &FunctionSummary {
+        &self.summary
+    }
+}
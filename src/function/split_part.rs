@@ -0,0 +1,97 @@
+// Copyright 2024 KipData/KiteSQL
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::errors::DatabaseError;
+use crate::expression::function::scala::{FuncMonotonicity, ScalarFunctionImpl};
+use crate::expression::function::FunctionSummary;
+use crate::expression::ScalarExpression;
+use crate::function::{eval_i64, eval_utf8, utf8_value};
+use crate::types::tuple::TupleLike;
+use crate::types::value::DataValue;
+use crate::types::CharLengthUnits;
+use crate::types::LogicalType;
+use std::sync::Arc;
+
+/// `split_part(s, delimiter, n)`: the `n`th field of `s` split on `delimiter`, counting
+/// from 1, or from the end when `n` is negative. Missing fields are empty.
+#[derive(Debug)]
+pub(crate) struct SplitPart {
+    summary: FunctionSummary,
+}
+
+impl SplitPart {
+    pub(crate) fn new() -> Arc<Self> {
+        Arc::new(Self {
+            summary: FunctionSummary {
+                name: "split_part".into(),
+                arg_types: vec![
+                    LogicalType::Varchar(None, CharLengthUnits::Characters),
+                    LogicalType::Varchar(None, CharLengthUnits::Characters),
+                    LogicalType::Bigint,
+                ],
+            },
+        })
+    }
+}
+
+impl ScalarFunctionImpl for SplitPart {
+    fn eval(
+        &self,
+        exprs: &[ScalarExpression],
+        tuples: Option<&dyn TupleLike>,
+    ) -> Result<DataValue, DatabaseError> {
+        let (Some(value), Some(delimiter), Some(n)) = (
+            eval_utf8(&exprs[0], tuples)?,
+            eval_utf8(&exprs[1], tuples)?,
+            eval_i64(&exprs[2], tuples)?,
+        ) else {
+            return Ok(DataValue::Null);
+        };
+        if n == 0 {
+            return Err(DatabaseError::InvalidValue(
+                "field position must not be zero".to_string(),
+            ));
+        }
+        let fields = if value.is_empty() {
+            Vec::new()
+        } else if delimiter.is_empty() {
+            vec![value.as_str()]
+        } else {
+            value.split(delimiter.as_str()).collect()
+        };
+        let index = if n > 0 {
+            usize::try_from(n - 1).ok()
+        } else {
+            usize::try_from(fields.len() as i64 + n).ok()
+        };
+        let field = index
+            .and_then(|index| fields.get(index))
+            .copied()
+            .unwrap_or_default();
+
+        Ok(utf8_value(field.to_string()))
+    }
+
+    fn monotonicity(&self) -> Option<FuncMonotonicity> {
+        None
+    }
+
+    fn return_type(&self) -> &LogicalType {
+        &LogicalType::Varchar(None, CharLengthUnits::Characters)
+    }
+
+    fn summary(&self) -> &FunctionSummary {
+        &self.summary
+    }
+}
@@ -0,0 +1,274 @@
+// Copyright 2024 KipData/KiteSQL
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::errors::DatabaseError;
+use crate::expression::function::scala::{FuncMonotonicity, ScalarFunctionImpl};
+use crate::expression::function::FunctionSummary;
+use crate::expression::ScalarExpression;
+use crate::function::{eval_utf8, utf8_value};
+use crate::types::evaluator::interval::to_naive_datetime;
+use crate::types::tuple::TupleLike;
+use crate::types::value::DataValue;
+use crate::types::{CharLengthUnits, LogicalType};
+use chrono::format::{Item, StrftimeItems};
+use chrono::{DateTime, Utc};
+use std::fmt::Write;
+use std::sync::Arc;
+
+/// The PostgreSQL template patterns `to_char`, `to_date` and `to_timestamp` understand,
+/// longest first, with the `strftime` specifier each one stands for.
+const TEMPLATE_PATTERNS: [(&str, &str); 18] = [
+    ("HH24", "%H"),
+    ("HH12", "%I"),
+    ("HH", "%I"),
+    ("MI", "%M"),
+    ("SS", "%S"),
+    ("MS", "%3f"),
+    ("US", "%6f"),
+    ("AM", "%p"),
+    ("PM", "%p"),
+    ("YYYY", "%Y"),
+    ("YY", "%y"),
+    ("MONTH", "%B"),
+    ("MON", "%b"),
+    ("MM", "%m"),
+    ("DAY", "%A"),
+    ("DY", "%a"),
+    ("DDD", "%j"),
+    ("DD", "%d"),
+];
+
+/// How a pattern producing a name, such as `Month` or `AM`, is capitalized.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Case {
+    Upper,
+    Capitalized,
+    Lower,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) enum TemplatePart {
+    Literal(String),
+    Pattern { specifier: &'static str, case: Case },
+}
+
+/// Splits a PostgreSQL `to_char` template into patterns and literal text. Patterns match
+/// case-insensitively, double-quoted text is copied as is.
+pub(crate) fn parse_template(template: &str) -> Result<Vec<TemplatePart>, DatabaseError> {
+    let mut parts = Vec::new();
+    let mut literal = String::new();
+    let mut rest = template;
+
+    'outer: while let Some(char) = rest.chars().next() {
+        if char == '"' {
+            let Some(end) = rest[1..].find('"') else {
+                return Err(DatabaseError::InvalidValue(format!(
+                    "unterminated quoted text in format: {template}"
+                )));
+            };
+            literal.push_str(&rest[1..end + 1]);
+            rest = &rest[end + 2..];
+            continue;
+        }
+        for (pattern, specifier) in TEMPLATE_PATTERNS {
+            let Some(matched) = rest.get(..pattern.len()) else {
+                continue;
+            };
+            if !matched.eq_ignore_ascii_case(pattern) {
+                continue;
+            }
+            let case = if matched.chars().all(|char| char.is_ascii_uppercase()) {
+                Case::Upper
+            } else if matched.starts_with(|char: char| char.is_ascii_uppercase()) {
+                Case::Capitalized
+            } else {
+                Case::Lower
+            };
+            if !literal.is_empty() {
+                parts.push(TemplatePart::Literal(std::mem::take(&mut literal)));
+            }
+            parts.push(TemplatePart::Pattern { specifier, case });
+            rest = &rest[pattern.len()..];
+            continue 'outer;
+        }
+        literal.push(char);
+        rest = &rest[char.len_utf8()..];
+    }
+    if !literal.is_empty() {
+        parts.push(TemplatePart::Literal(literal));
+    }
+    Ok(parts)
+}
+
+/// The `strftime` format equivalent to a parsed template, used to parse text with it.
+pub(crate) fn template_to_strftime(parts: &[TemplatePart]) -> String {
+    parts
+        .iter()
+        .map(|part| match part {
+            TemplatePart::Literal(literal) => literal.replace('%', "%%"),
+            TemplatePart::Pattern { specifier, .. } => specifier.to_string(),
+        })
+        .collect()
+}
+
+fn eval_datetime(
+    expr: &ScalarExpression,
+    tuples: Option<&dyn TupleLike>,
+) -> Result<Option<DateTime<Utc>>, DatabaseError> {
+    let value = expr.eval(tuples)?;
+    if value.is_null() {
+        return Ok(None);
+    }
+    let datetime = to_naive_datetime(&value).ok_or(DatabaseError::InvalidType)?;
+    Ok(Some(datetime.and_utc()))
+}
+
+/// Formats with `chrono`, reporting specifiers it cannot render instead of panicking.
+fn format_items<'a>(
+    datetime: &DateTime<Utc>,
+    items: impl Iterator<Item = Item<'a>> + Clone,
+    format: &str,
+) -> Result<String, DatabaseError> {
+    let mut formatted = String::new();
+    write!(formatted, "{}", datetime.format_with_items(items))
+        .map_err(|_| DatabaseError::InvalidValue(format!("invalid date format: {format}")))?;
+    Ok(formatted)
+}
+
+/// PostgreSQL's `to_char(source, template)`.
+#[derive(Debug)]
+pub(crate) struct ToChar {
+    summary: FunctionSummary,
+}
+
+impl ToChar {
+    pub(crate) fn new(ty: LogicalType) -> Arc<Self> {
+        Arc::new(Self {
+            summary: FunctionSummary {
+                name: "to_char".into(),
+                arg_types: vec![ty, LogicalType::Varchar(None, CharLengthUnits::Characters)],
+            },
+        })
+    }
+}
+
+impl ScalarFunctionImpl for ToChar {
+    fn eval(
+        &self,
+        exprs: &[ScalarExpression],
+        tuples: Option<&dyn TupleLike>,
+    ) -> Result<DataValue, DatabaseError> {
+        let (Some(datetime), Some(template)) = (
+            eval_datetime(&exprs[0], tuples)?,
+            eval_utf8(&exprs[1], tuples)?,
+        ) else {
+            return Ok(DataValue::Null);
+        };
+        let mut formatted = String::new();
+        for part in parse_template(&template)? {
+            match part {
+                TemplatePart::Literal(literal) => formatted.push_str(&literal),
+                TemplatePart::Pattern { specifier, case } => {
+                    let value = format_items(&datetime, StrftimeItems::new(specifier), &template)?;
+                    match case {
+                        Case::Upper => formatted.push_str(&value.to_uppercase()),
+                        Case::Lower => formatted.push_str(&value.to_lowercase()),
+                        Case::Capitalized => {
+                            let mut chars = value.chars();
+                            if let Some(first) = chars.next() {
+                                formatted.push(first.to_ascii_uppercase());
+                                formatted.push_str(&chars.as_str().to_lowercase());
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        Ok(utf8_value(formatted))
+    }
+
+    fn monotonicity(&self) -> Option<FuncMonotonicity> {
+        None
+    }
+
+    fn return_type(&self) -> &LogicalType {
+        &LogicalType::Varchar(None, CharLengthUnits::Characters)
+    }
+
+    fn summary(&self) -> &FunctionSummary {
+        &self.summary
+    }
+
+    fn is_constant_foldable(&self) -> bool {
+        true
+    }
+}
+
+/// SQLite's `strftime(format, source)`, taking `chrono`'s `%` specifiers.
+#[derive(Debug)]
+pub(crate) struct Strftime {
+    summary: FunctionSummary,
+}
+
+impl Strftime {
+    pub(crate) fn new(ty: LogicalType) -> Arc<Self> {
+        Arc::new(Self {
+            summary: FunctionSummary {
+                name: "strftime".into(),
+                arg_types: vec![LogicalType::Varchar(None, CharLengthUnits::Characters), ty],
+            },
+        })
+    }
+}
+
+impl ScalarFunctionImpl for Strftime {
+    fn eval(
+        &self,
+        exprs: &[ScalarExpression],
+        tuples: Option<&dyn TupleLike>,
+    ) -> Result<DataValue, DatabaseError> {
+        let (Some(format), Some(datetime)) = (
+            eval_utf8(&exprs[0], tuples)?,
+            eval_datetime(&exprs[1], tuples)?,
+        ) else {
+            return Ok(DataValue::Null);
+        };
+        // `StrftimeItems` keeps yielding `Item::Error` once it hits a bad specifier
+        let items = StrftimeItems::new(&format)
+            .map(|item| (item != Item::Error).then_some(item))
+            .collect::<Option<Vec<_>>>()
+            .ok_or_else(|| DatabaseError::InvalidValue(format!("invalid date format: {format}")))?;
+        Ok(utf8_value(format_items(
+            &datetime,
+            items.into_iter(),
+            &format,
+        )?))
+    }
+
+    fn monotonicity(&self) -> Option<FuncMonotonicity> {
+        None
+    }
+
+    fn return_type(&self) -> &LogicalType {
+        &LogicalType::Varchar(None, CharLengthUnits::Characters)
+    }
+
+    fn summary(&self) -> &FunctionSummary {
+        &self.summary
+    }
+
+    fn is_constant_foldable(&self) -> bool {
+        true
+    }
+}
@@ -0,0 +1,117 @@
+// Copyright 2024 KipData/KiteSQL
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::errors::DatabaseError;
+use crate::expression::function::scala::{FuncMonotonicity, ScalarFunctionImpl};
+use crate::expression::function::FunctionSummary;
+use crate::expression::ScalarExpression;
+use crate::function::eval_utf8;
+use crate::function::to_char::{parse_template, template_to_strftime};
+use crate::types::tuple::TupleLike;
+use crate::types::value::DataValue;
+use crate::types::{CharLengthUnits, LogicalType};
+use chrono::format::{parse, Parsed, StrftimeItems};
+use chrono::{Datelike, NaiveDateTime, NaiveTime};
+use std::sync::Arc;
+
+/// PostgreSQL's `to_date(text, template)` and `to_timestamp(text, template)`, parsing `text`
+/// with a `to_char` template. Missing months and days default to 1, missing times to
+/// midnight and a 12-hour clock without `AM`/`PM` to the morning.
+#[derive(Debug)]
+pub(crate) struct ToDate {
+    summary: FunctionSummary,
+    return_type: LogicalType,
+}
+
+impl ToDate {
+    pub(crate) fn new(is_timestamp: bool) -> Arc<Self> {
+        let (function_name, return_type) = if is_timestamp {
+            ("to_timestamp", LogicalType::TimeStamp(Some(6), false))
+        } else {
+            ("to_date", LogicalType::Date)
+        };
+        Arc::new(Self {
+            summary: FunctionSummary {
+                name: function_name.into(),
+                arg_types: vec![
+                    LogicalType::Varchar(None, CharLengthUnits::Characters),
+                    LogicalType::Varchar(None, CharLengthUnits::Characters),
+                ],
+            },
+            return_type,
+        })
+    }
+}
+
+fn parse_datetime(value: &str, template: &str) -> Result<NaiveDateTime, DatabaseError> {
+    let format = template_to_strftime(&parse_template(template)?);
+    let mut parsed = Parsed::new();
+    parse(&mut parsed, value, StrftimeItems::new(&format))?;
+
+    if parsed.ordinal().is_none() {
+        if parsed.month().is_none() {
+            parsed.set_month(1)?;
+        }
+        if parsed.day().is_none() {
+            parsed.set_day(1)?;
+        }
+    }
+    let time = if parsed.hour_mod_12().is_some() {
+        if parsed.hour_div_12().is_none() {
+            parsed.set_ampm(false)?;
+        }
+        if parsed.minute().is_none() {
+            parsed.set_minute(0)?;
+        }
+        parsed.to_naive_time()?
+    } else {
+        NaiveTime::MIN
+    };
+    Ok(parsed.to_naive_date()?.and_time(time))
+}
+
+impl ScalarFunctionImpl for ToDate {
+    fn eval(
+        &self,
+        exprs: &[ScalarExpression],
+        tuples: Option<&dyn TupleLike>,
+    ) -> Result<DataValue, DatabaseError> {
+        let (Some(value), Some(template)) =
+            (eval_utf8(&exprs[0], tuples)?, eval_utf8(&exprs[1], tuples)?)
+        else {
+            return Ok(DataValue::Null);
+        };
+        let datetime = parse_datetime(&value, &template)?;
+        Ok(match self.return_type {
+            LogicalType::Date => DataValue::Date32(datetime.num_days_from_ce()),
+            _ => DataValue::Time64(datetime.and_utc().timestamp_micros(), 6, false),
+        })
+    }
+
+    fn monotonicity(&self) -> Option<FuncMonotonicity> {
+        None
+    }
+
+    fn return_type(&self) -> &LogicalType {
+        &self.return_type
+    }
+
+    fn summary(&self) -> &FunctionSummary {
+        &self.summary
+    }
+
+    fn is_constant_foldable(&self) -> bool {
+        true
+    }
+}
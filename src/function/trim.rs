@@ -0,0 +1,92 @@
+// Copyright 2024 KipData/KiteSQL
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::errors::DatabaseError;
+use crate::expression::function::scala::{FuncMonotonicity, ScalarFunctionImpl};
+use crate::expression::function::FunctionSummary;
+use crate::expression::ScalarExpression;
+use crate::function::{eval_utf8, utf8_value, Side};
+use crate::types::tuple::TupleLike;
+use crate::types::value::DataValue;
+use crate::types::CharLengthUnits;
+use crate::types::LogicalType;
+use std::sync::Arc;
+
+/// `ltrim(s [, characters])` and `rtrim(s [, characters])`: removes any of `characters`
+/// (spaces by default) from one end of `s`.
+#[derive(Debug)]
+pub(crate) struct Trim {
+    summary: FunctionSummary,
+    side: Side,
+}
+
+impl Trim {
+    pub(crate) fn new(side: Side, with_characters: bool) -> Arc<Self> {
+        let function_name = match side {
+            Side::Left => "ltrim",
+            Side::Right => "rtrim",
+        };
+        let mut arg_types = vec![LogicalType::Varchar(None, CharLengthUnits::Characters)];
+        if with_characters {
+            arg_types.push(LogicalType::Varchar(None, CharLengthUnits::Characters));
+        }
+        Arc::new(Self {
+            summary: FunctionSummary {
+                name: function_name.into(),
+                arg_types,
+            },
+            side,
+        })
+    }
+}
+
+impl ScalarFunctionImpl for Trim {
+    fn eval(
+        &self,
+        exprs: &[ScalarExpression],
+        tuples: Option<&dyn TupleLike>,
+    ) -> Result<DataValue, DatabaseError> {
+        let Some(value) = eval_utf8(&exprs[0], tuples)? else {
+            return Ok(DataValue::Null);
+        };
+        let characters = match exprs.get(1) {
+            Some(expr) => match eval_utf8(expr, tuples)? {
+                Some(characters) => characters,
+                None => return Ok(DataValue::Null),
+            },
+            None => " ".to_string(),
+        };
+        let is_trimmed = |c: char| characters.contains(c);
+
+        Ok(utf8_value(
+            match self.side {
+                Side::Left => value.trim_start_matches(is_trimmed),
+                Side::Right => value.trim_end_matches(is_trimmed),
+            }
+            .to_string(),
+        ))
+    }
+
+    fn monotonicity(&self) -> Option<FuncMonotonicity> {
+        None
+    }
+
+    fn return_type(&self) -> &LogicalType {
+        &LogicalType::Varchar(None, CharLengthUnits::Characters)
+    }
+
+    fn summary(&self) -> &FunctionSummary {
+        &self.summary
+    }
+}
@@ -0,0 +1,68 @@
+// Copyright 2024 KipData/KiteSQL
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::errors::DatabaseError;
+use crate::expression::function::table::TableFunctionImpl;
+use crate::expression::function::FunctionSummary;
+use crate::expression::ScalarExpression;
+use crate::planner::TableArena;
+use crate::types::tuple::Schema;
+use crate::types::tuple::Tuple;
+use crate::types::value::DataValue;
+use std::sync::Arc;
+
+/// `unnest(array)` returns a row for each element of the array, `NULL` elements included.
+///
+/// Its single column takes the element type of the array, so the binder builds the output
+/// schema for every call and the registered schema stays empty.
+#[derive(Debug)]
+pub(crate) struct Unnest {
+    summary: FunctionSummary,
+}
+
+impl Unnest {
+    pub(crate) fn new() -> Arc<Self> {
+        Arc::new(Self {
+            summary: FunctionSummary {
+                name: "unnest".into(),
+                arg_types: Vec::new(),
+            },
+        })
+    }
+}
+
+impl TableFunctionImpl for Unnest {
+    fn eval(
+        &self,
+        args: &[ScalarExpression],
+    ) -> Result<Box<dyn Iterator<Item = Result<Tuple, DatabaseError>>>, DatabaseError> {
+        let elements = match args[0].eval::<&Tuple>(None)? {
+            DataValue::Array(elements) => elements,
+            _ => Vec::new(),
+        };
+
+        Ok(Box::new(
+            elements
+                .into_iter()
+                .map(|element| Ok(Tuple::new(None, vec![element]))),
+        )
+            as Box<dyn Iterator<Item = Result<Tuple, DatabaseError>>>)
+    }
+
+    fn summary(&self) -> &FunctionSummary {
+        &self.summary
+    }
+
+    fn output_schema_into(&self, _: &mut TableArena, _: &mut Schema) {}
+}
@@ -118,6 +118,8 @@ mod function;
 pub(crate) mod iter_ext;
 #[cfg(feature = "macros")]
 pub mod macros;
+#[cfg(all(not(target_arch = "wasm32"), feature = "net"))]
+pub mod net;
 mod optimizer;
 #[cfg(feature = "orm")]
 pub mod orm;
@@ -201,3 +201,95 @@ macro_rules! table_function {
         }
     };
 }
+
+/// # Examples
+///
+/// ```
+/// aggregate_function!(MyAggregateFunction::weighted_avg(LogicalType::Double, LogicalType::Double) -> LogicalType::Double => {
+///     init: || Ok(DataValue::Tuple(vec![DataValue::Float64(0.0.into()), DataValue::Float64(0.0.into())], false)),
+///     update: |state: &mut DataValue, value: DataValue, weight: DataValue| {
+///         let DataValue::Tuple(sums, _) = state else { unreachable!() };
+///         if let (Some(value), Some(weight)) = (value.double(), weight.double()) {
+///             sums[0] = DataValue::Float64((sums[0].double().unwrap() + value * weight).into());
+///             sums[1] = DataValue::Float64((sums[1].double().unwrap() + weight).into());
+///         }
+///         Ok(())
+///     },
+///     merge: |state: &mut DataValue, other: DataValue| { ... },
+///     finalize: |state: &DataValue| { ... },
+/// });
+///
+/// let mut kite_sql = DataBaseBuilder::path("./example")
+///     .register_aggregate_function(MyAggregateFunction::new())
+///     .build()?;
+/// ```
+#[macro_export]
+macro_rules! aggregate_function {
+    ($struct_name:ident::$function_name:ident($($arg_ty:expr),*) -> $return_ty:expr => {
+        init: $init:expr,
+        update: $update:expr,
+        merge: $merge:expr,
+        finalize: $finalize:expr $(,)?
+    }) => {
+        #[derive(Debug)]
+        pub(crate) struct $struct_name {
+            summary: ::kite_sql::expression::function::FunctionSummary
+        }
+
+        impl $struct_name {
+            #[allow(unused_mut)]
+            pub(crate) fn new() -> ::std::sync::Arc<Self> {
+                let function_name = stringify!($function_name).to_lowercase();
+
+                let mut arg_types = Vec::new();
+                $({
+                    arg_types.push($arg_ty);
+                })*
+
+                ::std::sync::Arc::new(Self {
+                    summary: ::kite_sql::expression::function::FunctionSummary {
+                        name: function_name.into(),
+                        arg_types
+                    }
+                })
+            }
+        }
+
+        impl ::kite_sql::expression::function::aggregate::AggregateFunctionImpl for $struct_name {
+            #[allow(clippy::redundant_closure_call)]
+            fn init(&self) -> Result<::kite_sql::types::value::DataValue, ::kite_sql::errors::DatabaseError> {
+                $init()
+            }
+
+            #[allow(unused_variables, clippy::redundant_closure_call)]
+            fn update(&self, state: &mut ::kite_sql::types::value::DataValue, args: &[::kite_sql::types::value::DataValue]) -> Result<(), ::kite_sql::errors::DatabaseError> {
+                let mut _index = 0;
+
+                $update(state, $({
+                    let value = args[_index].clone().cast(&$arg_ty)?;
+                    _index += 1;
+
+                    value
+                }, )*)
+            }
+
+            #[allow(clippy::redundant_closure_call)]
+            fn merge(&self, state: &mut ::kite_sql::types::value::DataValue, other: ::kite_sql::types::value::DataValue) -> Result<(), ::kite_sql::errors::DatabaseError> {
+                $merge(state, other)
+            }
+
+            #[allow(clippy::redundant_closure_call)]
+            fn finalize(&self, state: &::kite_sql::types::value::DataValue) -> Result<::kite_sql::types::value::DataValue, ::kite_sql::errors::DatabaseError> {
+                $finalize(state)
+            }
+
+            fn return_type(&self) -> &::kite_sql::types::LogicalType {
+                &$return_ty
+            }
+
+            fn summary(&self) -> &::kite_sql::expression::function::FunctionSummary {
+                &self.summary
+            }
+        }
+    };
+}
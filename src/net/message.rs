@@ -0,0 +1,414 @@
+// Copyright 2024 KipData/KiteSQL
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Framing of the PostgreSQL v3 frontend/backend protocol.
+
+use std::io::{self, Read, Write};
+
+const PROTOCOL_VERSION_3: i32 = 196_608;
+const SSL_REQUEST_CODE: i32 = 80_877_103;
+const GSSENC_REQUEST_CODE: i32 = 80_877_104;
+const CANCEL_REQUEST_CODE: i32 = 80_877_102;
+
+/// Messages larger than this are rejected instead of being buffered.
+const MAX_MESSAGE_LEN: usize = 1 << 30;
+/// Buffered output is written out once it grows past this size.
+const FLUSH_THRESHOLD: usize = 64 * 1024;
+
+pub(crate) enum StartupMessage {
+    Startup { params: Vec<(String, String)> },
+    SslRequest,
+    GssEncRequest,
+    CancelRequest,
+}
+
+pub(crate) enum FrontendMessage {
+    Query(String),
+    Parse {
+        name: String,
+        sql: String,
+        param_types: Vec<u32>,
+    },
+    Bind {
+        portal: String,
+        statement: String,
+        param_formats: Vec<i16>,
+        params: Vec<Option<Vec<u8>>>,
+        result_formats: Vec<i16>,
+    },
+    /// `Describe` of a prepared statement (`S`) or a portal (`P`).
+    Describe {
+        kind: u8,
+        name: String,
+    },
+    /// `Execute` of a portal, `max_rows` is `0` for no limit.
+    Execute {
+        portal: String,
+        max_rows: usize,
+    },
+    /// `Close` of a prepared statement (`S`) or a portal (`P`).
+    Close {
+        kind: u8,
+        name: String,
+    },
+    Sync,
+    Flush,
+    Terminate,
+    Unsupported(u8),
+}
+
+/// Whether the backend is idle, inside a transaction block or inside a failed one, as reported
+/// by `ReadyForQuery`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum TransactionStatus {
+    Idle,
+    InTransaction,
+    Failed,
+}
+
+pub(crate) struct Connection<C> {
+    stream: C,
+    out: Vec<u8>,
+}
+
+impl<C: Read + Write> Connection<C> {
+    pub(crate) fn new(stream: C) -> Self {
+        Self {
+            stream,
+            out: Vec::new(),
+        }
+    }
+
+    /// Reads the untyped first message of a connection, `None` when the client went away.
+    pub(crate) fn read_startup(&mut self) -> io::Result<Option<StartupMessage>> {
+        let Some(body) = self.read_body()? else {
+            return Ok(None);
+        };
+        let mut reader = Reader::new(&body);
+        let startup = match reader.i32()? {
+            PROTOCOL_VERSION_3 => {
+                let mut params = Vec::new();
+                loop {
+                    let key = reader.cstr()?;
+                    if key.is_empty() {
+                        break;
+                    }
+                    params.push((key, reader.cstr()?));
+                }
+                StartupMessage::Startup { params }
+            }
+            SSL_REQUEST_CODE => StartupMessage::SslRequest,
+            GSSENC_REQUEST_CODE => StartupMessage::GssEncRequest,
+            CANCEL_REQUEST_CODE => StartupMessage::CancelRequest,
+            code => {
+                return Err(invalid_data(format!(
+                    "unsupported protocol version {}.{}",
+                    code >> 16,
+                    code & 0xffff
+                )))
+            }
+        };
+        Ok(Some(startup))
+    }
+
+    /// Reads the next typed message, `None` when the client went away.
+    pub(crate) fn read_message(&mut self) -> io::Result<Option<FrontendMessage>> {
+        let mut tag = [0u8];
+        if !read_exact_or_eof(&mut self.stream, &mut tag)? {
+            return Ok(None);
+        }
+        let Some(body) = self.read_body()? else {
+            return Err(io::ErrorKind::UnexpectedEof.into());
+        };
+        let mut reader = Reader::new(&body);
+
+        let message = match tag[0] {
+            b'Q' => FrontendMessage::Query(reader.cstr()?),
+            b'P' => {
+                let name = reader.cstr()?;
+                let sql = reader.cstr()?;
+                let param_types = (0..reader.len16()?)
+                    .map(|_| reader.i32().map(|oid| oid as u32))
+                    .collect::<io::Result<_>>()?;
+                FrontendMessage::Parse {
+                    name,
+                    sql,
+                    param_types,
+                }
+            }
+            b'B' => {
+                let portal = reader.cstr()?;
+                let statement = reader.cstr()?;
+                let param_formats = (0..reader.len16()?)
+                    .map(|_| reader.i16())
+                    .collect::<io::Result<_>>()?;
+                let params = (0..reader.len16()?)
+                    .map(|_| match reader.i32()? {
+                        -1 => Ok(None),
+                        len => reader.bytes(len).map(|bytes| Some(bytes.to_vec())),
+                    })
+                    .collect::<io::Result<_>>()?;
+                let result_formats = (0..reader.len16()?)
+                    .map(|_| reader.i16())
+                    .collect::<io::Result<_>>()?;
+                FrontendMessage::Bind {
+                    portal,
+                    statement,
+                    param_formats,
+                    params,
+                    result_formats,
+                }
+            }
+            b'D' => FrontendMessage::Describe {
+                kind: reader.u8()?,
+                name: reader.cstr()?,
+            },
+            b'E' => {
+                let portal = reader.cstr()?;
+                // a negative limit means no limit as well
+                let max_rows = usize::try_from(reader.i32()?).unwrap_or(0);
+                FrontendMessage::Execute { portal, max_rows }
+            }
+            b'C' => FrontendMessage::Close {
+                kind: reader.u8()?,
+                name: reader.cstr()?,
+            },
+            b'S' => FrontendMessage::Sync,
+            b'H' => FrontendMessage::Flush,
+            b'X' => FrontendMessage::Terminate,
+            tag => FrontendMessage::Unsupported(tag),
+        };
+        Ok(Some(message))
+    }
+
+    /// Reads a length-prefixed message body.
+    fn read_body(&mut self) -> io::Result<Option<Vec<u8>>> {
+        let mut len = [0u8; 4];
+        if !read_exact_or_eof(&mut self.stream, &mut len)? {
+            return Ok(None);
+        }
+        let len = i32::from_be_bytes(len);
+        if !(4..=MAX_MESSAGE_LEN as i32).contains(&len) {
+            return Err(invalid_data(format!("invalid message length {len}")));
+        }
+        let mut body = vec![0u8; len as usize - 4];
+        self.stream.read_exact(&mut body)?;
+        Ok(Some(body))
+    }
+
+    /// Writes a single byte outside of any message, the answer to an `SSLRequest`.
+    pub(crate) fn write_byte(&mut self, byte: u8) -> io::Result<()> {
+        self.out.push(byte);
+        self.flush()
+    }
+
+    pub(crate) fn flush(&mut self) -> io::Result<()> {
+        self.stream.write_all(&self.out)?;
+        self.out.clear();
+        self.stream.flush()
+    }
+
+    pub(crate) fn flush_if_full(&mut self) -> io::Result<()> {
+        if self.out.len() >= FLUSH_THRESHOLD {
+            self.flush()?;
+        }
+        Ok(())
+    }
+
+    /// Buffers a message whose body is written by `f`.
+    pub(crate) fn message(&mut self, tag: u8, f: impl FnOnce(&mut Vec<u8>)) {
+        self.out.push(tag);
+        let start = self.out.len();
+        self.out.extend([0; 4]);
+        f(&mut self.out);
+        let len = (self.out.len() - start) as i32;
+        self.out[start..start + 4].copy_from_slice(&len.to_be_bytes());
+    }
+
+    pub(crate) fn authentication_ok(&mut self) {
+        self.message(b'R', |buf| buf.extend(0i32.to_be_bytes()));
+    }
+
+    pub(crate) fn parameter_status(&mut self, key: &str, value: &str) {
+        self.message(b'S', |buf| {
+            put_cstr(buf, key);
+            put_cstr(buf, value);
+        });
+    }
+
+    pub(crate) fn backend_key_data(&mut self, process_id: i32, secret_key: i32) {
+        self.message(b'K', |buf| {
+            buf.extend(process_id.to_be_bytes());
+            buf.extend(secret_key.to_be_bytes());
+        });
+    }
+
+    pub(crate) fn ready_for_query(&mut self, status: TransactionStatus) {
+        let status = match status {
+            TransactionStatus::Idle => b'I',
+            TransactionStatus::InTransaction => b'T',
+            TransactionStatus::Failed => b'E',
+        };
+        self.message(b'Z', |buf| buf.push(status));
+    }
+
+    pub(crate) fn command_complete(&mut self, tag: &str) {
+        self.message(b'C', |buf| put_cstr(buf, tag));
+    }
+
+    pub(crate) fn empty_query_response(&mut self) {
+        self.message(b'I', |_| ());
+    }
+
+    pub(crate) fn parse_complete(&mut self) {
+        self.message(b'1', |_| ());
+    }
+
+    pub(crate) fn bind_complete(&mut self) {
+        self.message(b'2', |_| ());
+    }
+
+    pub(crate) fn close_complete(&mut self) {
+        self.message(b'3', |_| ());
+    }
+
+    pub(crate) fn portal_suspended(&mut self) {
+        self.message(b's', |_| ());
+    }
+
+    pub(crate) fn no_data(&mut self) {
+        self.message(b'n', |_| ());
+    }
+
+    pub(crate) fn parameter_description(&mut self, types: &[u32]) {
+        self.message(b't', |buf| {
+            buf.extend((types.len() as i16).to_be_bytes());
+            for oid in types {
+                buf.extend(oid.to_be_bytes());
+            }
+        });
+    }
+
+    /// `fields` are `(name, type oid, type length, format)`.
+    pub(crate) fn row_description(&mut self, fields: &[(String, u32, i16, i16)]) {
+        self.message(b'T', |buf| {
+            buf.extend((fields.len() as i16).to_be_bytes());
+            for (name, oid, len, format) in fields {
+                put_cstr(buf, name);
+                // table oid and column attribute number
+                buf.extend(0i32.to_be_bytes());
+                buf.extend(0i16.to_be_bytes());
+                buf.extend(oid.to_be_bytes());
+                buf.extend(len.to_be_bytes());
+                // type modifier
+                buf.extend((-1i32).to_be_bytes());
+                buf.extend(format.to_be_bytes());
+            }
+        });
+    }
+
+    pub(crate) fn error_response(&mut self, code: &str, message: &str) {
+        self.notice(b'E', "ERROR", code, message);
+    }
+
+    pub(crate) fn warning(&mut self, code: &str, message: &str) {
+        self.notice(b'N', "WARNING", code, message);
+    }
+
+    fn notice(&mut self, tag: u8, severity: &str, code: &str, message: &str) {
+        self.message(tag, |buf| {
+            for (field, value) in [(b'S', severity), (b'V', severity), (b'C', code)] {
+                buf.push(field);
+                put_cstr(buf, value);
+            }
+            buf.push(b'M');
+            put_cstr(buf, message);
+            buf.push(0);
+        });
+    }
+}
+
+/// Reads exactly `buf.len()` bytes, `false` when the stream ended before the first one.
+fn read_exact_or_eof(stream: &mut impl Read, buf: &mut [u8]) -> io::Result<bool> {
+    let mut read = 0;
+    while read < buf.len() {
+        match stream.read(&mut buf[read..]) {
+            Ok(0) if read == 0 => return Ok(false),
+            Ok(0) => return Err(io::ErrorKind::UnexpectedEof.into()),
+            Ok(n) => read += n,
+            Err(err) if err.kind() == io::ErrorKind::Interrupted => (),
+            Err(err) => return Err(err),
+        }
+    }
+    Ok(true)
+}
+
+fn put_cstr(buf: &mut Vec<u8>, value: &str) {
+    // a NUL would end the string early on the client side
+    buf.extend(value.bytes().filter(|byte| *byte != 0));
+    buf.push(0);
+}
+
+fn invalid_data(message: impl Into<String>) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message.into())
+}
+
+struct Reader<'a> {
+    buf: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn new(buf: &'a [u8]) -> Self {
+        Self { buf }
+    }
+
+    fn bytes(&mut self, len: i32) -> io::Result<&'a [u8]> {
+        let len = usize::try_from(len).map_err(|_| invalid_data("negative field length"))?;
+        if self.buf.len() < len {
+            return Err(invalid_data("message is shorter than its fields"));
+        }
+        let (bytes, rest) = self.buf.split_at(len);
+        self.buf = rest;
+        Ok(bytes)
+    }
+
+    fn u8(&mut self) -> io::Result<u8> {
+        Ok(self.bytes(1)?[0])
+    }
+
+    fn i16(&mut self) -> io::Result<i16> {
+        Ok(i16::from_be_bytes(self.bytes(2)?.try_into().unwrap()))
+    }
+
+    fn i32(&mut self) -> io::Result<i32> {
+        Ok(i32::from_be_bytes(self.bytes(4)?.try_into().unwrap()))
+    }
+
+    /// A non-negative `i16` count of the following fields.
+    fn len16(&mut self) -> io::Result<usize> {
+        usize::try_from(self.i16()?).map_err(|_| invalid_data("negative field count"))
+    }
+
+    fn cstr(&mut self) -> io::Result<String> {
+        let end = self
+            .buf
+            .iter()
+            .position(|byte| *byte == 0)
+            .ok_or_else(|| invalid_data("unterminated string"))?;
+        let value = String::from_utf8(self.buf[..end].to_vec())
+            .map_err(|_| invalid_data("string is not valid UTF-8"))?;
+        self.buf = &self.buf[end + 1..];
+        Ok(value)
+    }
+}
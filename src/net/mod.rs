@@ -0,0 +1,540 @@
+// Copyright 2024 KipData/KiteSQL
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A PostgreSQL wire protocol (v3) server, so that clients such as `psql` or `tokio-postgres`
+//! can share one embedded database.
+//!
+//! Every connection is served by its own thread. Statements outside of a transaction block run
+//! in their own transaction, `BEGIN` opens a [`DBTransaction`](crate::db::DBTransaction) that
+//! lives until `COMMIT` or `ROLLBACK`. DDL and `ANALYZE` need exclusive access to the database:
+//! they wait for the statements and open transaction blocks of other connections to end and fail
+//! once [`Server::ddl_lock_timeout`] passed. Statements of other connections are not held up by
+//! a waiting DDL, an idle transaction block however makes every DDL time out until it ends.
+//!
+//! Clients are trusted, there is no authentication and no TLS.
+
+mod message;
+mod params;
+mod session;
+mod types;
+
+use crate::db::Database;
+use crate::net::session::Session;
+use crate::storage::Storage;
+use std::io::{self, Read, Write};
+use std::net::TcpListener;
+use std::sync::atomic::{AtomicI32, Ordering};
+use std::sync::{PoisonError, RwLock};
+use std::thread;
+use std::time::Duration;
+
+const DEFAULT_DDL_LOCK_TIMEOUT: Duration = Duration::from_secs(5);
+
+pub struct Server<S: Storage> {
+    database: RwLock<Database<S>>,
+    next_process_id: AtomicI32,
+    ddl_lock_timeout: Duration,
+}
+
+impl<S: Storage> Server<S> {
+    pub fn new(database: Database<S>) -> Self {
+        Self {
+            database: RwLock::new(database),
+            next_process_id: AtomicI32::new(1),
+            ddl_lock_timeout: DEFAULT_DDL_LOCK_TIMEOUT,
+        }
+    }
+
+    /// How long DDL and `ANALYZE` wait for exclusive access to the database before failing,
+    /// 5 seconds by default.
+    pub fn ddl_lock_timeout(mut self, timeout: Duration) -> Self {
+        self.ddl_lock_timeout = timeout;
+        self
+    }
+
+    /// Accepts connections from `listener` until accepting fails, serving each of them on
+    /// its own thread.
+    pub fn serve(&self, listener: TcpListener) -> io::Result<()>
+    where
+        Database<S>: Send + Sync,
+    {
+        thread::scope(|scope| {
+            for stream in listener.incoming() {
+                let stream = stream?;
+                scope.spawn(move || {
+                    let _ = stream.set_nodelay(true);
+                    // a failing connection only ends itself
+                    let _ = self.handle_connection(stream);
+                });
+            }
+            Ok(())
+        })
+    }
+
+    /// Serves a single client connection until it terminates.
+    pub fn handle_connection<C: Read + Write>(&self, stream: C) -> io::Result<()> {
+        let process_id = self.next_process_id.fetch_add(1, Ordering::Relaxed);
+
+        Session::new(&self.database, self.ddl_lock_timeout, stream).run(process_id)
+    }
+
+    pub fn into_inner(self) -> Database<S> {
+        self.database
+            .into_inner()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::DataBaseBuilder;
+    use crate::errors::DatabaseError;
+    use std::net::{SocketAddr, TcpStream};
+    use tempfile::TempDir;
+
+    struct Client {
+        stream: TcpStream,
+    }
+
+    impl Client {
+        fn connect(addr: SocketAddr) -> io::Result<Self> {
+            let mut stream = TcpStream::connect(addr)?;
+            // an SSL request is answered with `N` before the startup message
+            stream.write_all(&[0, 0, 0, 8, 4, 210, 22, 47])?;
+            let mut answer = [0];
+            stream.read_exact(&mut answer)?;
+            assert_eq!(answer[0], b'N');
+
+            let mut body = 196_608i32.to_be_bytes().to_vec();
+            body.extend(b"user\0kite\0application_name\0test\0\0");
+            stream.write_all(&(body.len() as i32 + 4).to_be_bytes())?;
+            stream.write_all(&body)?;
+            let mut client = Client { stream };
+            let messages = client.until_ready()?;
+            assert_eq!(messages.first().map(|(tag, _)| *tag), Some(b'R'));
+            assert!(messages
+                .iter()
+                .any(|(tag, body)| *tag == b'S' && body.starts_with(b"application_name\0test")));
+
+            Ok(client)
+        }
+
+        fn send(&mut self, tag: u8, body: &[u8]) -> io::Result<()> {
+            self.stream.write_all(&[tag])?;
+            self.stream
+                .write_all(&(body.len() as i32 + 4).to_be_bytes())?;
+            self.stream.write_all(body)
+        }
+
+        fn recv(&mut self) -> io::Result<(u8, Vec<u8>)> {
+            let mut header = [0; 5];
+            self.stream.read_exact(&mut header)?;
+            let len = i32::from_be_bytes(header[1..].try_into().unwrap()) as usize;
+            let mut body = vec![0; len - 4];
+            self.stream.read_exact(&mut body)?;
+            Ok((header[0], body))
+        }
+
+        fn until_ready(&mut self) -> io::Result<Vec<(u8, Vec<u8>)>> {
+            let mut messages = Vec::new();
+            loop {
+                let message = self.recv()?;
+                let ready = message.0 == b'Z';
+                messages.push(message);
+                if ready {
+                    return Ok(messages);
+                }
+            }
+        }
+
+        fn query(&mut self, sql: &str) -> io::Result<Vec<(u8, Vec<u8>)>> {
+            self.send(b'Q', &cstr(sql))?;
+            self.until_ready()
+        }
+
+        fn terminate(mut self) -> io::Result<()> {
+            self.send(b'X', &[])
+        }
+    }
+
+    fn cstr(value: &str) -> Vec<u8> {
+        let mut bytes = value.as_bytes().to_vec();
+        bytes.push(0);
+        bytes
+    }
+
+    fn tags(messages: &[(u8, Vec<u8>)]) -> Vec<u8> {
+        messages.iter().map(|(tag, _)| *tag).collect()
+    }
+
+    fn command_tags(messages: &[(u8, Vec<u8>)]) -> Vec<String> {
+        messages
+            .iter()
+            .filter(|(tag, _)| *tag == b'C')
+            .map(|(_, body)| String::from_utf8_lossy(&body[..body.len() - 1]).into_owned())
+            .collect()
+    }
+
+    fn error_codes(messages: &[(u8, Vec<u8>)]) -> Vec<String> {
+        messages
+            .iter()
+            .filter(|(tag, _)| *tag == b'E')
+            .filter_map(|(_, body)| {
+                body.split(|byte| *byte == 0)
+                    .find_map(|field| field.strip_prefix(b"C"))
+                    .map(|code| String::from_utf8_lossy(code).into_owned())
+            })
+            .collect()
+    }
+
+    /// The text values of the data rows, `None` for nulls.
+    fn rows(messages: &[(u8, Vec<u8>)]) -> Vec<Vec<Option<Vec<u8>>>> {
+        messages
+            .iter()
+            .filter(|(tag, _)| *tag == b'D')
+            .map(|(_, body)| {
+                let count = i16::from_be_bytes([body[0], body[1]]) as usize;
+                let mut pos = 2;
+                (0..count)
+                    .map(|_| {
+                        let len = i32::from_be_bytes(body[pos..pos + 4].try_into().unwrap());
+                        pos += 4;
+                        (len >= 0).then(|| {
+                            let value = body[pos..pos + len as usize].to_vec();
+                            pos += len as usize;
+                            value
+                        })
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn text_rows(messages: &[(u8, Vec<u8>)]) -> Vec<Vec<String>> {
+        rows(messages)
+            .into_iter()
+            .map(|row| {
+                row.into_iter()
+                    .map(|value| {
+                        value
+                            .map(|value| String::from_utf8(value).unwrap())
+                            .unwrap_or_else(|| "null".to_string())
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Serves `connections` consecutive or concurrent connections while `f` runs.
+    fn with_server(
+        connections: usize,
+        f: impl FnOnce(SocketAddr) -> io::Result<()>,
+    ) -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let server = Server::new(DataBaseBuilder::path(temp_dir.path()).build_rocksdb()?)
+            .ddl_lock_timeout(Duration::from_millis(200));
+        let listener = TcpListener::bind("127.0.0.1:0")?;
+        let addr = listener.local_addr()?;
+
+        thread::scope(|scope| {
+            let server = &server;
+            scope.spawn(move || {
+                for stream in listener.incoming().take(connections) {
+                    let stream = stream.expect("accept failed");
+                    scope.spawn(move || server.handle_connection(stream));
+                }
+            });
+            f(addr)
+        })?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_simple_query() -> Result<(), DatabaseError> {
+        with_server(2, |addr| {
+            let mut client = Client::connect(addr)?;
+            let messages = client.query(
+                "create table t1 (id int primary key, name varchar, score double); \
+                 insert into t1 values (1, 'a', 1.5), (2, null, 2.5);",
+            )?;
+            assert_eq!(command_tags(&messages), ["CREATE TABLE", "INSERT 0 2"]);
+            assert_eq!(messages.last().unwrap().1, b"I");
+
+            let messages = client.query("select id, name, score from t1 order by id")?;
+            assert_eq!(tags(&messages), b"TDDCZ");
+            let description = &messages[0].1;
+            assert_eq!(description[..2], 3i16.to_be_bytes());
+            assert!(description[2..].starts_with(b"id\0"));
+            assert_eq!(
+                text_rows(&messages),
+                [["1", "a", "1.5"], ["2", "null", "2.5"]]
+            );
+            assert_eq!(command_tags(&messages), ["SELECT 2"]);
+
+            let messages = client
+                .query("update t1 set score = 0 where id = 2; delete from t1 where id = 1")?;
+            assert_eq!(command_tags(&messages), ["UPDATE 1", "DELETE 1"]);
+
+            // an error discards the rest of the query string
+            let messages = client.query("select * from t_missing; delete from t1")?;
+            assert_eq!(tags(&messages), b"EZ");
+            assert_eq!(error_codes(&messages), ["42P01"]);
+            assert_eq!(tags(&client.query("")?), b"IZ");
+            client.terminate()?;
+
+            // a second connection shares the same database
+            let mut client = Client::connect(addr)?;
+            let messages = client.query("select count(*) from t1")?;
+            assert_eq!(text_rows(&messages), [["1"]]);
+            client.terminate()
+        })
+    }
+
+    #[test]
+    fn test_transaction_blocks() -> Result<(), DatabaseError> {
+        with_server(1, |addr| {
+            let mut client = Client::connect(addr)?;
+            client.query("create table t1 (id int primary key)")?;
+
+            let messages = client.query("begin; insert into t1 values (1);")?;
+            assert_eq!(command_tags(&messages), ["BEGIN", "INSERT 0 1"]);
+            assert_eq!(messages.last().unwrap().1, b"T");
+            assert_eq!(text_rows(&client.query("select id from t1")?), [["1"]]);
+            let messages = client.query("rollback")?;
+            assert_eq!(command_tags(&messages), ["ROLLBACK"]);
+            assert_eq!(messages.last().unwrap().1, b"I");
+            assert!(text_rows(&client.query("select id from t1")?).is_empty());
+
+            client.query("begin; insert into t1 values (2); commit;")?;
+            assert_eq!(text_rows(&client.query("select id from t1")?), [["2"]]);
+
+            // a failed transaction ignores everything up to its end, which rolls it back
+            client.query("begin; insert into t1 values (3);")?;
+            let messages = client.query("insert into t1 values (2)")?;
+            assert_eq!(error_codes(&messages), ["23505"]);
+            assert_eq!(messages.last().unwrap().1, b"E");
+            let messages = client.query("select id from t1")?;
+            assert_eq!(error_codes(&messages), ["25P02"]);
+            let messages = client.query("commit")?;
+            assert_eq!(command_tags(&messages), ["ROLLBACK"]);
+            assert_eq!(messages.last().unwrap().1, b"I");
+            assert_eq!(text_rows(&client.query("select id from t1")?), [["2"]]);
+
+            // DDL runs only outside of transaction blocks
+            let messages =
+                client.query("begin; create table t2 (id int primary key); rollback;")?;
+            assert_eq!(error_codes(&messages), ["0A000"]);
+            client.query("rollback")?;
+
+            let messages = client.query("commit")?;
+            assert_eq!(tags(&messages), b"NCZ");
+            client.terminate()
+        })
+    }
+
+    #[test]
+    fn test_ddl_lock_timeout() -> Result<(), DatabaseError> {
+        with_server(3, |addr| {
+            let mut idle = Client::connect(addr)?;
+            idle.query("create table t1 (id int primary key)")?;
+            idle.query("begin; insert into t1 values (1);")?;
+
+            // a waiting DDL does not hold up the statements of other connections
+            let mut ddl = Client::connect(addr)?;
+            ddl.send(b'Q', &cstr("create table t2 (id int primary key)"))?;
+            let mut client = Client::connect(addr)?;
+            assert_eq!(
+                text_rows(&client.query("select count(*) from t1")?),
+                [["0"]]
+            );
+
+            // an idle transaction block makes DDL time out until it ends
+            let messages = ddl.until_ready()?;
+            assert_eq!(error_codes(&messages), ["55P03"]);
+            idle.query("commit")?;
+            let messages = ddl.query("create table t2 (id int primary key)")?;
+            assert_eq!(command_tags(&messages), ["CREATE TABLE"]);
+
+            client.terminate()?;
+            idle.terminate()?;
+            ddl.terminate()
+        })
+    }
+
+    #[test]
+    fn test_extended_query() -> Result<(), DatabaseError> {
+        with_server(1, |addr| {
+            let mut client = Client::connect(addr)?;
+            client.query("create table t1 (id int primary key, name varchar)")?;
+
+            // parameter types are inferred from the columns they are inserted into
+            let mut parse = cstr("insert");
+            parse.extend(cstr("insert into t1 values ($1, $2)"));
+            parse.extend(0i16.to_be_bytes());
+            client.send(b'P', &parse)?;
+            client.send(b'D', &[b'S', b'i', b'n', b's', b'e', b'r', b't', 0])?;
+            for (id, name) in [(1i32, "a"), (2, "b")] {
+                let mut bind = cstr("");
+                bind.extend(cstr("insert"));
+                // the id is sent in the binary format, the name as text
+                bind.extend(2i16.to_be_bytes());
+                bind.extend(1i16.to_be_bytes());
+                bind.extend(0i16.to_be_bytes());
+                bind.extend(2i16.to_be_bytes());
+                bind.extend(4i32.to_be_bytes());
+                bind.extend(id.to_be_bytes());
+                bind.extend((name.len() as i32).to_be_bytes());
+                bind.extend(name.as_bytes());
+                bind.extend(0i16.to_be_bytes());
+                client.send(b'B', &bind)?;
+                let mut execute = cstr("");
+                execute.extend(0i32.to_be_bytes());
+                client.send(b'E', &execute)?;
+            }
+            client.send(b'S', &[])?;
+            let messages = client.until_ready()?;
+            assert_eq!(tags(&messages), b"1tn2C2CZ");
+            let mut types = 2i16.to_be_bytes().to_vec();
+            types.extend(23u32.to_be_bytes());
+            types.extend(1043u32.to_be_bytes());
+            assert_eq!(messages[1].1, types);
+            assert_eq!(command_tags(&messages), ["INSERT 0 1", "INSERT 0 1"]);
+
+            let mut parse = cstr("");
+            parse.extend(cstr("select id, name from t1 where id > $1 order by id"));
+            parse.extend(0i16.to_be_bytes());
+            client.send(b'P', &parse)?;
+            client.send(b'D', &[b'S', 0])?;
+            let mut bind = cstr("");
+            bind.extend(cstr(""));
+            bind.extend(0i16.to_be_bytes());
+            bind.extend(1i16.to_be_bytes());
+            bind.extend(1i32.to_be_bytes());
+            bind.extend(b"0");
+            // the id is returned in the binary format, the name as text
+            bind.extend(2i16.to_be_bytes());
+            bind.extend(1i16.to_be_bytes());
+            bind.extend(0i16.to_be_bytes());
+            client.send(b'B', &bind)?;
+            let mut execute = cstr("");
+            execute.extend(0i32.to_be_bytes());
+            client.send(b'E', &execute)?;
+            client.send(b'S', &[])?;
+            let messages = client.until_ready()?;
+            assert_eq!(tags(&messages), b"1tT2DDCZ");
+            assert_eq!(
+                rows(&messages),
+                [
+                    [Some(1i32.to_be_bytes().to_vec()), Some(b"a".to_vec())],
+                    [Some(2i32.to_be_bytes().to_vec()), Some(b"b".to_vec())]
+                ]
+            );
+            assert_eq!(command_tags(&messages), ["SELECT 2"]);
+
+            // an error skips the remaining messages up to `Sync`
+            let mut parse = cstr("");
+            parse.extend(cstr("select * from t_missing"));
+            parse.extend(0i16.to_be_bytes());
+            client.send(b'P', &parse)?;
+            client.send(b'D', &[b'S', 0])?;
+            client.send(b'S', &[])?;
+            let messages = client.until_ready()?;
+            assert_eq!(tags(&messages), b"1EZ");
+            assert_eq!(error_codes(&messages), ["42P01"]);
+            client.terminate()
+        })
+    }
+
+    #[test]
+    fn test_execute_row_limit() -> Result<(), DatabaseError> {
+        with_server(1, |addr| {
+            let mut client = Client::connect(addr)?;
+            client.query("create table t1 (id int primary key)")?;
+            let execute = |client: &mut Client, max_rows: i32| {
+                let mut execute = cstr("");
+                execute.extend(max_rows.to_be_bytes());
+                client.send(b'E', &execute)
+            };
+            let parse_and_bind = |client: &mut Client, sql: &str| {
+                let mut parse = cstr("");
+                parse.extend(cstr(sql));
+                parse.extend(0i16.to_be_bytes());
+                client.send(b'P', &parse)?;
+                let mut bind = cstr("");
+                bind.extend(cstr(""));
+                bind.extend([0; 6]);
+                client.send(b'B', &bind)
+            };
+
+            // a completed portal is not run again
+            parse_and_bind(&mut client, "insert into t1 values (1), (2), (3)")?;
+            execute(&mut client, 0)?;
+            execute(&mut client, 0)?;
+            client.send(b'S', &[])?;
+            let messages = client.until_ready()?;
+            assert_eq!(tags(&messages), b"12CCZ");
+            assert_eq!(command_tags(&messages), ["INSERT 0 3", "INSERT 0 0"]);
+
+            // the rows beyond the limit are sent by the following executes
+            parse_and_bind(&mut client, "select id from t1 order by id")?;
+            execute(&mut client, 2)?;
+            execute(&mut client, 2)?;
+            execute(&mut client, 2)?;
+            client.send(b'S', &[])?;
+            let messages = client.until_ready()?;
+            assert_eq!(tags(&messages), b"12DDsDCCZ");
+            assert_eq!(text_rows(&messages), [["1"], ["2"], ["3"]]);
+            assert_eq!(command_tags(&messages), ["SELECT 1", "SELECT 0"]);
+
+            // inside a transaction block the suspended query keeps running across `Sync`
+            client.query("begin")?;
+            parse_and_bind(&mut client, "select id from t1 order by id")?;
+            execute(&mut client, 1)?;
+            client.send(b'S', &[])?;
+            let messages = client.until_ready()?;
+            assert_eq!(tags(&messages), b"12DsZ");
+            assert_eq!(messages.last().unwrap().1, b"T");
+            execute(&mut client, 1)?;
+            client.send(b'S', &[])?;
+            assert_eq!(text_rows(&client.until_ready()?), [["2"]]);
+            // another statement needs the transaction, the portal keeps the rows left
+            assert_eq!(
+                text_rows(&client.query("select count(*) from t1")?),
+                [["3"]]
+            );
+            execute(&mut client, 0)?;
+            client.send(b'S', &[])?;
+            let messages = client.until_ready()?;
+            assert_eq!(text_rows(&messages), [["3"]]);
+            assert_eq!(command_tags(&messages), ["SELECT 1"]);
+            client.query("commit")?;
+
+            // outside of a transaction block the portal ends with the implicit transaction
+            parse_and_bind(&mut client, "select id from t1 order by id")?;
+            execute(&mut client, 1)?;
+            client.send(b'S', &[])?;
+            assert_eq!(tags(&client.until_ready()?), b"12DsZ");
+            execute(&mut client, 1)?;
+            client.send(b'S', &[])?;
+            assert_eq!(tags(&client.until_ready()?), b"EZ");
+
+            assert_eq!(
+                text_rows(&client.query("select count(*) from t1")?),
+                [["3"]]
+            );
+            client.terminate()
+        })
+    }
+}
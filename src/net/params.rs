@@ -0,0 +1,431 @@
+// Copyright 2024 KipData/KiteSQL
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Counts the `$n` parameters of a statement and infers their types for `ParameterDescription`.
+//!
+//! The binder only sees parameters once they are bound to values, so the types reported to
+//! clients come from the statement text: a parameter compared with, assigned to or inserted
+//! into a column takes the type of that column, a cast parameter the type it is cast to.
+
+use crate::catalog::TableName;
+use crate::errors::DatabaseError;
+use crate::planner::TableArena;
+use crate::storage::TableCache;
+use crate::types::{CharLengthUnits, LogicalType};
+use sqlparser::ast::{
+    AssignmentTarget, Expr, FromTable, Ident, JoinConstraint, JoinOperator, LimitClause,
+    ObjectName, Query, SelectItem, SetExpr, Statement, TableFactor, TableObject, TableWithJoins,
+    Value,
+};
+use sqlparser::dialect::GenericDialect;
+use sqlparser::tokenizer::{Token, Tokenizer};
+use std::collections::HashMap;
+use std::sync::{Mutex, PoisonError};
+
+/// The highest `$n` referenced by `sql`, `0` when it has no parameters.
+pub(crate) fn param_count(sql: &str) -> Result<usize, DatabaseError> {
+    let tokens = Tokenizer::new(&GenericDialect {}, sql)
+        .tokenize()
+        .map_err(|err| DatabaseError::InvalidValue(err.to_string()))?;
+
+    Ok(tokens
+        .iter()
+        .filter_map(|token| match token {
+            Token::Placeholder(name) => param_index(name),
+            _ => None,
+        })
+        .max()
+        .unwrap_or(0))
+}
+
+/// The 1-based index of a `$n` placeholder.
+fn param_index(name: &str) -> Option<usize> {
+    name.strip_prefix('$')?
+        .parse()
+        .ok()
+        .filter(|index| *index > 0)
+}
+
+/// The binder looks parameters up by `&'static str` name, so the names of `$1`..`$n` are
+/// leaked once and shared by every connection.
+pub(crate) fn param_name(index: usize) -> &'static str {
+    static NAMES: Mutex<Vec<&'static str>> = Mutex::new(Vec::new());
+
+    let mut names = NAMES.lock().unwrap_or_else(PoisonError::into_inner);
+    while names.len() < index {
+        let name = format!("${}", names.len() + 1);
+        names.push(Box::leak(name.into_boxed_str()));
+    }
+    names[index - 1]
+}
+
+/// Infers the types of the `$n` parameters of `statement` from the columns of the tables it
+/// references, keyed by the 1-based parameter index.
+pub(crate) fn infer_param_types(
+    statement: &Statement,
+    tables: &TableCache,
+    arena: &TableArena,
+) -> HashMap<usize, LogicalType> {
+    let mut inference = Inference {
+        tables,
+        arena,
+        scopes: Vec::new(),
+        types: HashMap::new(),
+    };
+    inference.statement(statement);
+    inference.types
+}
+
+struct Inference<'a> {
+    tables: &'a TableCache,
+    arena: &'a TableArena,
+    /// Tables visible to column references, innermost query last, as `(alias, table)`.
+    scopes: Vec<Vec<(TableName, TableName)>>,
+    types: HashMap<usize, LogicalType>,
+}
+
+impl Inference<'_> {
+    fn statement(&mut self, statement: &Statement) {
+        match statement {
+            Statement::Query(query) => self.query(query),
+            Statement::Explain { statement, .. } => self.statement(statement),
+            Statement::Insert(insert) => {
+                let TableObject::TableName(name) = &insert.table else {
+                    return;
+                };
+                let Some(table) = object_name(name) else {
+                    return;
+                };
+                let Some(source) = &insert.source else {
+                    return;
+                };
+                self.scopes.push(vec![(table.clone(), table.clone())]);
+                match source.body.as_ref() {
+                    SetExpr::Values(values) => {
+                        let columns = if insert.columns.is_empty() {
+                            self.table_columns(&table)
+                        } else {
+                            insert.columns.iter().map(lower_ident).collect()
+                        };
+                        for row in &values.rows {
+                            for (expr, column) in row.iter().zip(columns.iter()) {
+                                let ty = self.column_type(Some(&table), column);
+                                self.expr_typed(expr, ty);
+                            }
+                        }
+                    }
+                    _ => self.query(source),
+                }
+                self.returning(insert.returning.as_deref());
+                self.scopes.pop();
+            }
+            Statement::Update(update) => {
+                let mut scope = Vec::new();
+                Self::collect_tables(&update.table, &mut scope);
+                let target = scope.first().map(|(_, table)| table.clone());
+                self.scopes.push(scope);
+                for assignment in &update.assignments {
+                    let ty = match &assignment.target {
+                        AssignmentTarget::ColumnName(name) => name
+                            .0
+                            .last()
+                            .and_then(|part| part.as_ident())
+                            .and_then(|ident| {
+                                self.column_type(target.as_ref(), &lower_ident(ident))
+                            }),
+                        AssignmentTarget::Tuple(_) => None,
+                    };
+                    self.expr_typed(&assignment.value, ty);
+                }
+                self.opt_expr(update.selection.as_ref());
+                self.returning(update.returning.as_deref());
+                self.scopes.pop();
+            }
+            Statement::Delete(delete) => {
+                let (FromTable::WithFromKeyword(from) | FromTable::WithoutKeyword(from)) =
+                    &delete.from;
+                let mut scope = Vec::new();
+                for table in from {
+                    Self::collect_tables(table, &mut scope);
+                }
+                self.scopes.push(scope);
+                self.opt_expr(delete.selection.as_ref());
+                self.returning(delete.returning.as_deref());
+                self.scopes.pop();
+            }
+            _ => (),
+        }
+    }
+
+    fn query(&mut self, query: &Query) {
+        if let Some(with) = &query.with {
+            for cte in &with.cte_tables {
+                self.query(&cte.query);
+            }
+        }
+        self.set_expr(&query.body);
+        match &query.limit_clause {
+            Some(LimitClause::LimitOffset { limit, offset, .. }) => {
+                if let Some(limit) = limit {
+                    self.expr_typed(limit, Some(LogicalType::Bigint));
+                }
+                if let Some(offset) = offset {
+                    self.expr_typed(&offset.value, Some(LogicalType::Bigint));
+                }
+            }
+            Some(LimitClause::OffsetCommaLimit { offset, limit }) => {
+                self.expr_typed(offset, Some(LogicalType::Bigint));
+                self.expr_typed(limit, Some(LogicalType::Bigint));
+            }
+            None => (),
+        }
+    }
+
+    fn set_expr(&mut self, body: &SetExpr) {
+        match body {
+            SetExpr::Select(select) => {
+                let mut scope = Vec::new();
+                for table in &select.from {
+                    Self::collect_tables(table, &mut scope);
+                }
+                self.scopes.push(scope);
+                for table in &select.from {
+                    for join in &table.joins {
+                        if let Some(JoinConstraint::On(expr)) = join_constraint(&join.join_operator)
+                        {
+                            self.expr(expr);
+                        }
+                    }
+                }
+                self.returning(Some(&select.projection));
+                self.opt_expr(select.selection.as_ref());
+                self.opt_expr(select.having.as_ref());
+                self.scopes.pop();
+            }
+            SetExpr::Query(query) => self.query(query),
+            SetExpr::SetOperation { left, right, .. } => {
+                self.set_expr(left);
+                self.set_expr(right);
+            }
+            SetExpr::Values(values) => {
+                for row in &values.rows {
+                    for expr in row {
+                        self.expr(expr);
+                    }
+                }
+            }
+            _ => (),
+        }
+    }
+
+    fn returning(&mut self, items: Option<&[SelectItem]>) {
+        for item in items.unwrap_or_default() {
+            match item {
+                SelectItem::UnnamedExpr(expr) | SelectItem::ExprWithAlias { expr, .. } => {
+                    self.expr(expr)
+                }
+                _ => (),
+            }
+        }
+    }
+
+    fn opt_expr(&mut self, expr: Option<&Expr>) {
+        if let Some(expr) = expr {
+            self.expr(expr);
+        }
+    }
+
+    /// Visits `expr`, typing it as `ty` when it is a parameter.
+    fn expr_typed(&mut self, expr: &Expr, ty: Option<LogicalType>) {
+        match (placeholder(expr), ty) {
+            (Some(index), Some(ty)) => {
+                self.types.entry(index).or_insert(ty);
+            }
+            _ => self.expr(expr),
+        }
+    }
+
+    fn expr(&mut self, expr: &Expr) {
+        match expr {
+            Expr::BinaryOp { left, right, .. } => {
+                let left_ty = self.expr_type(left);
+                let right_ty = self.expr_type(right);
+                self.expr_typed(left, right_ty);
+                self.expr_typed(right, left_ty);
+            }
+            Expr::InList { expr, list, .. } => {
+                let ty = self.expr_type(expr);
+                self.expr(expr);
+                for item in list {
+                    self.expr_typed(item, ty.clone());
+                }
+            }
+            Expr::Between {
+                expr, low, high, ..
+            } => {
+                let ty = self.expr_type(expr);
+                self.expr(expr);
+                self.expr_typed(low, ty.clone());
+                self.expr_typed(high, ty);
+            }
+            Expr::Like { expr, pattern, .. } | Expr::ILike { expr, pattern, .. } => {
+                self.expr(expr);
+                self.expr_typed(
+                    pattern,
+                    Some(LogicalType::Varchar(None, CharLengthUnits::Characters)),
+                );
+            }
+            Expr::Cast {
+                expr, data_type, ..
+            } => {
+                let ty = LogicalType::try_from(data_type.clone()).ok();
+                self.expr_typed(expr, ty);
+            }
+            Expr::InSubquery { expr, subquery, .. } => {
+                self.expr(expr);
+                self.query(subquery);
+            }
+            Expr::Subquery(query)
+            | Expr::Exists {
+                subquery: query, ..
+            } => self.query(query),
+            Expr::Nested(expr)
+            | Expr::UnaryOp { expr, .. }
+            | Expr::IsNull(expr)
+            | Expr::IsNotNull(expr)
+            | Expr::IsTrue(expr)
+            | Expr::IsFalse(expr) => self.expr(expr),
+            _ => (),
+        }
+    }
+
+    /// The type of a column reference, looking through parentheses.
+    fn expr_type(&self, expr: &Expr) -> Option<LogicalType> {
+        match expr {
+            Expr::Identifier(ident) => self.column_type(None, &lower_ident(ident)),
+            Expr::CompoundIdentifier(idents) => match idents.as_slice() {
+                [table, column] => {
+                    let alias: TableName = lower_ident(table).into();
+                    let table = self
+                        .scopes
+                        .iter()
+                        .rev()
+                        .flatten()
+                        .find(|(name, _)| *name == alias)
+                        .map(|(_, table)| table.clone())?;
+                    self.column_type(Some(&table), &lower_ident(column))
+                }
+                _ => None,
+            },
+            Expr::Nested(expr) => self.expr_type(expr),
+            _ => None,
+        }
+    }
+
+    /// The type of `column` in `table`, or in the innermost visible table that has it.
+    fn column_type(&self, table: Option<&TableName>, column: &str) -> Option<LogicalType> {
+        let lookup = |table: &TableName| {
+            let table = self.tables.get(table)?;
+            let column = table.get_column_by_name(column)?;
+            Some(self.arena.column(column).datatype().clone())
+        };
+        match table {
+            Some(table) => lookup(table),
+            None => self
+                .scopes
+                .iter()
+                .rev()
+                .flatten()
+                .find_map(|(_, table)| lookup(table)),
+        }
+    }
+
+    fn table_columns(&self, table: &TableName) -> Vec<String> {
+        self.tables
+            .get(table)
+            .map(|table| {
+                table
+                    .columns()
+                    .map(|column| self.arena.column(*column).name().to_string())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    fn collect_tables(table: &TableWithJoins, scope: &mut Vec<(TableName, TableName)>) {
+        for relation in
+            std::iter::once(&table.relation).chain(table.joins.iter().map(|join| &join.relation))
+        {
+            if let TableFactor::Table { name, alias, .. } = relation {
+                if let Some(name) = object_name(name) {
+                    let alias = alias
+                        .as_ref()
+                        .map(|alias| lower_ident(&alias.name).into())
+                        .unwrap_or_else(|| name.clone());
+                    scope.push((alias, name));
+                }
+            }
+        }
+    }
+}
+
+fn placeholder(expr: &Expr) -> Option<usize> {
+    match expr {
+        Expr::Value(value) => match &value.value {
+            Value::Placeholder(name) => param_index(name),
+            _ => None,
+        },
+        Expr::Nested(expr) => placeholder(expr),
+        _ => None,
+    }
+}
+
+fn join_constraint(operator: &JoinOperator) -> Option<&JoinConstraint> {
+    match operator {
+        JoinOperator::Join(constraint)
+        | JoinOperator::Inner(constraint)
+        | JoinOperator::Left(constraint)
+        | JoinOperator::LeftOuter(constraint)
+        | JoinOperator::Right(constraint)
+        | JoinOperator::RightOuter(constraint)
+        | JoinOperator::FullOuter(constraint) => Some(constraint),
+        _ => None,
+    }
+}
+
+fn object_name(name: &ObjectName) -> Option<TableName> {
+    match name.0.as_slice() {
+        [part] => part.as_ident().map(|ident| lower_ident(ident).into()),
+        _ => None,
+    }
+}
+
+fn lower_ident(ident: &Ident) -> String {
+    ident.value.to_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_the_highest_parameter() -> Result<(), DatabaseError> {
+        assert_eq!(param_count("select 1")?, 0);
+        assert_eq!(param_count("select $2, '$3' from t where a = $1")?, 2);
+        assert_eq!(param_name(2), "$2");
+        assert!(std::ptr::eq(param_name(2), param_name(2)));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,1042 @@
+// Copyright 2024 KipData/KiteSQL
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! One client connection: the startup handshake, the simple and the extended query flows, and
+//! transaction blocks mapped onto [`DBTransaction`].
+
+#[cfg(feature = "time")]
+use crate::binder::time_zone::{is_time_zone_set, is_time_zone_variable, time_zone_assignment};
+use crate::binder::{command_type, prepare_all, CommandType};
+use crate::db::{DBTransaction, Database, ResultIter, State};
+use crate::errors::DatabaseError;
+use crate::net::message::{Connection, FrontendMessage, StartupMessage, TransactionStatus};
+use crate::net::params::{infer_param_types, param_count, param_name};
+use crate::net::types::{decode_param, encode_binary, encode_text, type_len, type_oid, TEXT};
+use crate::storage::{Storage, Transaction};
+#[cfg(feature = "time")]
+use crate::types::timezone::TimeZone;
+use crate::types::tuple::{SchemaView, Tuple};
+use crate::types::value::DataValue;
+use crate::types::LogicalType;
+use sqlparser::ast::Statement;
+use std::collections::{HashMap, VecDeque};
+use std::io::{self, Read, Write};
+use std::mem;
+use std::sync::{PoisonError, RwLock, RwLockWriteGuard, TryLockError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+const SERVER_VERSION: &str = "16.0";
+const DDL_LOCK_POLL_INTERVAL: Duration = Duration::from_millis(10);
+const TRANSACTION_ABORTED: &str =
+    "current transaction is aborted, commands ignored until end of transaction block";
+
+/// What the connection loop has to do after a step of the session.
+enum Flow {
+    Continue,
+    Begin,
+    Commit,
+    Rollback,
+    Terminate,
+}
+
+struct Prepared {
+    /// `None` for an empty query string.
+    statement: Option<Statement>,
+    param_types: Vec<u32>,
+}
+
+struct Portal {
+    statement: Option<Statement>,
+    params: Vec<(&'static str, DataValue)>,
+    result_formats: Vec<i16>,
+    state: PortalState,
+}
+
+/// How far the `Execute` messages got through a portal.
+///
+/// A query stopped at the row limit of an `Execute` keeps running in
+/// [`Session::stream_portal`] while the following messages only continue it, it is only
+/// `Suspended` here once it had to give way to another message.
+enum PortalState {
+    Ready,
+    /// Stopped at the row limit of an `Execute`. The statement already ran to completion, the
+    /// encoded rows it produced beyond the limit are sent by the following ones.
+    Suspended(VecDeque<Vec<u8>>),
+    /// Ran to completion, executing it again returns no rows.
+    Done,
+}
+
+pub(crate) struct Session<'a, S: Storage, C> {
+    database: &'a RwLock<Database<S>>,
+    ddl_lock_timeout: Duration,
+    conn: Connection<C>,
+    statements: HashMap<String, Prepared>,
+    portals: HashMap<String, Portal>,
+    /// Statements of a simple query that are still to be run.
+    pending: VecDeque<Statement>,
+    /// A message read while streaming a portal that ended the stream, it is handled next.
+    deferred: Option<FrontendMessage>,
+    /// Whether a simple query is in progress, it ends with `ReadyForQuery`.
+    in_simple_query: bool,
+    /// Set by an error in the extended query flow, messages are discarded up to `Sync`.
+    skip_until_sync: bool,
+    /// Set by an error inside a transaction block, only `COMMIT` or `ROLLBACK` may follow.
+    failed: bool,
+    /// The connection's own session time zone, `SET TIME ZONE` does not reach other
+    /// connections.
+    #[cfg(feature = "time")]
+    time_zone: TimeZone,
+}
+
+impl<'a, S: Storage, C: Read + Write> Session<'a, S, C> {
+    pub(crate) fn new(
+        database: &'a RwLock<Database<S>>,
+        ddl_lock_timeout: Duration,
+        stream: C,
+    ) -> Self {
+        Self {
+            database,
+            ddl_lock_timeout,
+            conn: Connection::new(stream),
+            statements: HashMap::new(),
+            portals: HashMap::new(),
+            pending: VecDeque::new(),
+            deferred: None,
+            in_simple_query: false,
+            skip_until_sync: false,
+            failed: false,
+            #[cfg(feature = "time")]
+            time_zone: database
+                .read()
+                .unwrap_or_else(PoisonError::into_inner)
+                .time_zone(),
+        }
+    }
+
+    pub(crate) fn run(mut self, process_id: i32) -> io::Result<()> {
+        if !self.startup(process_id)? {
+            return Ok(());
+        }
+        loop {
+            match self.step(None)? {
+                Flow::Begin => {
+                    let database = self.database.read().unwrap_or_else(PoisonError::into_inner);
+                    let mut transaction = match database.new_transaction() {
+                        Ok(transaction) => transaction,
+                        Err(err) => {
+                            self.fail(err, false)?;
+                            continue;
+                        }
+                    };
+                    #[cfg(feature = "time")]
+                    transaction.set_time_zone(self.time_zone);
+                    self.transaction_control("BEGIN", true)?;
+                    loop {
+                        match self.step(Some(&mut transaction))? {
+                            Flow::Commit => {
+                                let result = if self.failed {
+                                    drop(transaction);
+                                    Ok("ROLLBACK")
+                                } else {
+                                    transaction.commit().map(|()| "COMMIT")
+                                };
+                                self.failed = false;
+                                self.portals.clear();
+                                match result {
+                                    Ok(tag) => self.transaction_control(tag, false)?,
+                                    Err(err) => self.fail(err, false)?,
+                                }
+                                break;
+                            }
+                            Flow::Rollback => {
+                                drop(transaction);
+                                self.failed = false;
+                                self.portals.clear();
+                                self.transaction_control("ROLLBACK", false)?;
+                                break;
+                            }
+                            Flow::Terminate => return Ok(()),
+                            Flow::Continue | Flow::Begin => (),
+                        }
+                    }
+                }
+                Flow::Terminate => return Ok(()),
+                Flow::Continue | Flow::Commit | Flow::Rollback => (),
+            }
+        }
+    }
+
+    /// Answers encryption requests and accepts the startup message, `false` when the
+    /// connection should be closed instead.
+    fn startup(&mut self, process_id: i32) -> io::Result<bool> {
+        loop {
+            match self.conn.read_startup()? {
+                // neither TLS nor GSSAPI encryption is supported, the client may continue in
+                // plain text
+                Some(StartupMessage::SslRequest | StartupMessage::GssEncRequest) => {
+                    self.conn.write_byte(b'N')?
+                }
+                Some(StartupMessage::Startup { params }) => {
+                    self.conn.authentication_ok();
+                    #[cfg(feature = "time")]
+                    let time_zone = {
+                        if let Some(time_zone) = params
+                            .iter()
+                            .find(|(key, _)| key.eq_ignore_ascii_case("TimeZone"))
+                            .and_then(|(_, name)| TimeZone::parse(name).ok())
+                        {
+                            self.time_zone = time_zone;
+                        }
+                        self.time_zone.to_string()
+                    };
+                    #[cfg(not(feature = "time"))]
+                    let time_zone = "UTC".to_string();
+                    for (key, value) in [
+                        ("server_version", SERVER_VERSION),
+                        ("server_encoding", "UTF8"),
+                        ("client_encoding", "UTF8"),
+                        ("DateStyle", "ISO, MDY"),
+                        ("TimeZone", &time_zone),
+                        ("integer_datetimes", "on"),
+                        ("standard_conforming_strings", "on"),
+                    ] {
+                        self.conn.parameter_status(key, value);
+                    }
+                    if let Some((_, name)) =
+                        params.iter().find(|(key, _)| key == "application_name")
+                    {
+                        self.conn.parameter_status("application_name", name);
+                    }
+                    self.conn.backend_key_data(process_id, 0);
+                    self.conn.ready_for_query(TransactionStatus::Idle);
+                    self.conn.flush()?;
+                    return Ok(true);
+                }
+                // queries run to completion, there is nothing to cancel
+                Some(StartupMessage::CancelRequest) | None => return Ok(false),
+            }
+        }
+    }
+
+    /// Runs the next statement of a simple query or handles the next message.
+    fn step(&mut self, mut transaction: Option<&mut DBTransaction<'_, S>>) -> io::Result<Flow> {
+        let in_transaction = transaction.is_some();
+
+        if let Some(statement) = self.pending.pop_front() {
+            match self.run_statement(&statement, &[], &[], true, None, transaction.as_deref_mut()) {
+                Ok(Flow::Continue) => self.statement_done(in_transaction)?,
+                Ok(flow) => return Ok(flow),
+                Err(err) => self.fail(err, in_transaction)?,
+            }
+            return Ok(Flow::Continue);
+        }
+        let message = match self.deferred.take() {
+            Some(message) => Some(message),
+            None => self.conn.read_message()?,
+        };
+        let Some(message) = message else {
+            return Ok(Flow::Terminate);
+        };
+        if self.skip_until_sync
+            && !matches!(message, FrontendMessage::Sync | FrontendMessage::Terminate)
+        {
+            return Ok(Flow::Continue);
+        }
+
+        let result = match message {
+            FrontendMessage::Query(sql) => {
+                self.in_simple_query = true;
+                match prepare_all(&sql) {
+                    Ok(statements) => {
+                        self.pending.extend(statements);
+                        Ok(Flow::Continue)
+                    }
+                    Err(DatabaseError::EmptyStatement) => {
+                        self.conn.empty_query_response();
+                        self.statement_done(in_transaction)?;
+                        Ok(Flow::Continue)
+                    }
+                    Err(err) => Err(err),
+                }
+            }
+            FrontendMessage::Parse {
+                name,
+                sql,
+                param_types,
+            } => self
+                .parse(name, &sql, param_types, transaction.as_deref())
+                .map(|()| Flow::Continue),
+            FrontendMessage::Bind {
+                portal,
+                statement,
+                param_formats,
+                params,
+                result_formats,
+            } => self
+                .bind(portal, &statement, &param_formats, params, result_formats)
+                .map(|()| Flow::Continue),
+            FrontendMessage::Describe { kind, name } => self
+                .describe(kind, &name, transaction.as_deref_mut())
+                .map(|()| Flow::Continue),
+            FrontendMessage::Execute { portal, max_rows } => {
+                self.execute(&portal, max_rows, transaction)
+            }
+            FrontendMessage::Close { kind, name } => {
+                match kind {
+                    b'S' => self.statements.remove(&name).map(|_| ()),
+                    _ => self.portals.remove(&name).map(|_| ()),
+                };
+                self.conn.close_complete();
+                Ok(Flow::Continue)
+            }
+            FrontendMessage::Sync => {
+                self.skip_until_sync = false;
+                // portals end with their transaction, which is implicit outside of a block
+                if !in_transaction {
+                    self.portals.clear();
+                }
+                self.conn
+                    .ready_for_query(self.transaction_status(in_transaction));
+                self.conn.flush()?;
+                Ok(Flow::Continue)
+            }
+            FrontendMessage::Flush => {
+                self.conn.flush()?;
+                Ok(Flow::Continue)
+            }
+            FrontendMessage::Terminate => Ok(Flow::Terminate),
+            FrontendMessage::Unsupported(tag) => Err(DatabaseError::UnsupportedStmt(format!(
+                "protocol message `{}`",
+                tag as char
+            ))),
+        };
+        match result {
+            Ok(flow) => Ok(flow),
+            Err(err) => {
+                self.fail(err, in_transaction)?;
+                Ok(Flow::Continue)
+            }
+        }
+    }
+
+    fn parse(
+        &mut self,
+        name: String,
+        sql: &str,
+        declared_types: Vec<u32>,
+        transaction: Option<&DBTransaction<'_, S>>,
+    ) -> Result<(), DatabaseError> {
+        if !name.is_empty() && self.statements.contains_key(&name) {
+            return Err(DatabaseError::InvalidValue(format!(
+                "prepared statement \"{name}\" already exists"
+            )));
+        }
+        let statement = match prepare_all(sql) {
+            Ok(mut statements) if statements.len() == 1 => statements.pop(),
+            Ok(_) => {
+                return Err(DatabaseError::UnsupportedStmt(
+                    "cannot insert multiple commands into a prepared statement".to_string(),
+                ))
+            }
+            Err(DatabaseError::EmptyStatement) => None,
+            Err(err) => return Err(err),
+        };
+        let count = param_count(sql)?.max(declared_types.len());
+        let inferred = match (&statement, transaction) {
+            (Some(statement), Some(transaction)) => infer_types(statement, transaction.state),
+            (Some(statement), None) => {
+                let database = self.database.read().unwrap_or_else(PoisonError::into_inner);
+                infer_types(statement, &database.state)
+            }
+            (None, _) => HashMap::new(),
+        };
+        let param_types = (1..=count)
+            .map(|index| match declared_types.get(index - 1) {
+                Some(oid) if *oid != 0 => *oid,
+                _ => inferred.get(&index).map(type_oid).unwrap_or(TEXT),
+            })
+            .collect();
+
+        self.statements.insert(
+            name,
+            Prepared {
+                statement,
+                param_types,
+            },
+        );
+        self.conn.parse_complete();
+        Ok(())
+    }
+
+    fn bind(
+        &mut self,
+        portal: String,
+        statement: &str,
+        param_formats: &[i16],
+        params: Vec<Option<Vec<u8>>>,
+        result_formats: Vec<i16>,
+    ) -> Result<(), DatabaseError> {
+        let prepared = self.statements.get(statement).ok_or_else(|| {
+            DatabaseError::InvalidValue(format!(
+                "prepared statement \"{statement}\" does not exist"
+            ))
+        })?;
+        if params.len() != prepared.param_types.len() {
+            return Err(DatabaseError::InvalidValue(format!(
+                "bind message supplies {} parameters, but prepared statement \"{statement}\" requires {}",
+                params.len(),
+                prepared.param_types.len()
+            )));
+        }
+        // text timestamps without an offset are read in the session time zone
+        #[cfg(feature = "time")]
+        let _scope = self.time_zone.enter();
+        let params = params
+            .into_iter()
+            .enumerate()
+            .map(|(i, value)| {
+                let value = match value {
+                    Some(bytes) => decode_param(
+                        &bytes,
+                        prepared.param_types[i],
+                        format_code(param_formats, i) == 1,
+                    )?,
+                    None => DataValue::Null,
+                };
+                Ok((param_name(i + 1), value))
+            })
+            .collect::<Result<_, DatabaseError>>()?;
+
+        self.portals.insert(
+            portal,
+            Portal {
+                statement: prepared.statement.clone(),
+                params,
+                result_formats,
+                state: PortalState::Ready,
+            },
+        );
+        self.conn.bind_complete();
+        Ok(())
+    }
+
+    fn describe(
+        &mut self,
+        kind: u8,
+        name: &str,
+        transaction: Option<&mut DBTransaction<'_, S>>,
+    ) -> Result<(), DatabaseError> {
+        let (statement, params, result_formats, param_types) = if kind == b'S' {
+            let prepared = self.statements.get(name).ok_or_else(|| {
+                DatabaseError::InvalidValue(format!("prepared statement \"{name}\" does not exist"))
+            })?;
+            let params = (1..=prepared.param_types.len())
+                .map(|index| (param_name(index), DataValue::Null))
+                .collect::<Vec<_>>();
+            (
+                prepared.statement.clone(),
+                params,
+                Vec::new(),
+                Some(prepared.param_types.clone()),
+            )
+        } else {
+            let portal = self.portals.get(name).ok_or_else(|| {
+                DatabaseError::InvalidValue(format!("portal \"{name}\" does not exist"))
+            })?;
+            (
+                portal.statement.clone(),
+                portal.params.clone(),
+                portal.result_formats.clone(),
+                None,
+            )
+        };
+
+        // plan before answering so that a failing statement sends nothing but the error
+        let columns = match statement {
+            Some(statement) if returns_rows(&statement) => Some(match transaction {
+                Some(transaction) => plan_columns(transaction, &statement, &params)?,
+                None => {
+                    let database = self.database.read().unwrap_or_else(PoisonError::into_inner);
+                    let mut transaction = database.new_transaction()?;
+                    #[cfg(feature = "time")]
+                    transaction.set_time_zone(self.time_zone);
+                    plan_columns(&mut transaction, &statement, &params)?
+                }
+            }),
+            _ => None,
+        };
+        if let Some(param_types) = param_types {
+            self.conn.parameter_description(&param_types);
+        }
+        match columns {
+            Some(columns) => self.row_description(&columns, &result_formats),
+            None => self.conn.no_data(),
+        }
+        Ok(())
+    }
+
+    fn execute(
+        &mut self,
+        name: &str,
+        max_rows: usize,
+        transaction: Option<&mut DBTransaction<'_, S>>,
+    ) -> Result<Flow, DatabaseError> {
+        let portal = self.portals.get_mut(name).ok_or_else(|| {
+            DatabaseError::InvalidValue(format!("portal \"{name}\" does not exist"))
+        })?;
+        let Some(statement) = portal.statement.clone() else {
+            self.conn.empty_query_response();
+            return Ok(Flow::Continue);
+        };
+        match mem::replace(&mut portal.state, PortalState::Done) {
+            PortalState::Ready => (),
+            PortalState::Suspended(mut rows) => {
+                let count = match max_rows {
+                    0 => rows.len(),
+                    max_rows => rows.len().min(max_rows),
+                };
+                for row in rows.drain(..count) {
+                    self.conn.message(b'D', |buf| buf.extend(row));
+                    self.conn.flush_if_full()?;
+                }
+                self.suspend_or_complete(name, &statement, rows, count as u64);
+                return Ok(Flow::Continue);
+            }
+            PortalState::Done => {
+                self.conn.command_complete(&command_tag(&statement, 0));
+                return Ok(Flow::Continue);
+            }
+        }
+        let params = portal.params.clone();
+        let result_formats = portal.result_formats.clone();
+
+        self.run_statement(
+            &statement,
+            &params,
+            &result_formats,
+            false,
+            Some((name, max_rows)),
+            transaction,
+        )
+    }
+
+    /// Ends an `Execute` that sent `sent` rows of the portal `name`, which is suspended if
+    /// `rows` are left.
+    fn suspend_or_complete(
+        &mut self,
+        name: &str,
+        statement: &Statement,
+        rows: VecDeque<Vec<u8>>,
+        sent: u64,
+    ) {
+        if rows.is_empty() {
+            self.conn.command_complete(&command_tag(statement, sent));
+            return;
+        }
+        self.conn.portal_suspended();
+        if let Some(portal) = self.portals.get_mut(name) {
+            portal.state = PortalState::Suspended(rows);
+        }
+    }
+
+    /// Runs a statement and sends its rows and `CommandComplete`, the row description too when
+    /// `describe` is set. Transaction control is left to the connection loop.
+    ///
+    /// `portal` is the portal executed with its `max_rows` limit, only the first rows are sent
+    /// then and the portal is suspended instead of completing the command.
+    fn run_statement(
+        &mut self,
+        statement: &Statement,
+        params: &[(&'static str, DataValue)],
+        result_formats: &[i16],
+        describe: bool,
+        portal: Option<(&str, usize)>,
+        transaction: Option<&mut DBTransaction<'_, S>>,
+    ) -> Result<Flow, DatabaseError> {
+        match (statement, transaction.is_some()) {
+            (Statement::StartTransaction { .. }, false) => return Ok(Flow::Begin),
+            (Statement::Commit { .. }, true) => return Ok(Flow::Commit),
+            (Statement::Rollback { .. }, true) => return Ok(Flow::Rollback),
+            (Statement::StartTransaction { .. }, true) => {
+                self.conn
+                    .warning("25001", "there is already a transaction in progress");
+                self.conn.command_complete("BEGIN");
+                return Ok(Flow::Continue);
+            }
+            (Statement::Commit { .. } | Statement::Rollback { .. }, false) => {
+                self.conn
+                    .warning("25P01", "there is no transaction in progress");
+                self.conn.command_complete(match statement {
+                    Statement::Commit { .. } => "COMMIT",
+                    _ => "ROLLBACK",
+                });
+                return Ok(Flow::Continue);
+            }
+            _ => (),
+        }
+        if self.failed {
+            return Err(DatabaseError::UnsupportedStmt(
+                TRANSACTION_ABORTED.to_string(),
+            ));
+        }
+        #[cfg(feature = "time")]
+        if let Statement::Set(set) = statement {
+            if is_time_zone_set(set) {
+                let default = self
+                    .database
+                    .read()
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .state
+                    .default_time_zone();
+                // `SET LOCAL` only changes the zone of the transaction block it runs in
+                if let Some(time_zone) = time_zone_assignment(statement, default, false)? {
+                    self.time_zone = time_zone;
+                    self.conn
+                        .parameter_status("TimeZone", &time_zone.to_string());
+                }
+            }
+        }
+
+        match transaction {
+            Some(transaction) => {
+                let iter = transaction.execute(statement, params)?;
+                self.send_result(iter, statement, result_formats, describe, portal, true)?;
+            }
+            None if matches!(
+                command_type(statement)?,
+                CommandType::DDL | CommandType::Analyze
+            ) =>
+            {
+                let context = statement.to_string();
+                self.write_database()?
+                    .execute_mut(&context, params, |binder, arena| {
+                        binder.bind(statement, arena)
+                    })?;
+                self.conn.command_complete(&command_tag(statement, 0));
+            }
+            None => {
+                // an implicit transaction in the connection's time zone, `Database::execute`
+                // would read and `SET` the zone shared by all connections
+                let database = self.database.read().unwrap_or_else(PoisonError::into_inner);
+                let mut transaction = database.new_transaction()?;
+                #[cfg(feature = "time")]
+                transaction.set_time_zone(self.time_zone);
+                let iter = transaction.execute(statement, params)?;
+                self.send_result(iter, statement, result_formats, describe, portal, false)?;
+                transaction.commit()?;
+            }
+        }
+        Ok(Flow::Continue)
+    }
+
+    /// Waits for exclusive access to the database up to the DDL lock timeout.
+    ///
+    /// A blocking `write` would hold up every later reader of the lock while it waits, so the
+    /// lock is polled instead.
+    fn write_database(&self) -> Result<RwLockWriteGuard<'a, Database<S>>, DatabaseError> {
+        let deadline = Instant::now() + self.ddl_lock_timeout;
+        loop {
+            match self.database.try_write() {
+                Ok(guard) => return Ok(guard),
+                Err(TryLockError::Poisoned(err)) => return Ok(err.into_inner()),
+                Err(TryLockError::WouldBlock) if Instant::now() < deadline => {
+                    thread::sleep(DDL_LOCK_POLL_INTERVAL)
+                }
+                Err(TryLockError::WouldBlock) => {
+                    return Err(DatabaseError::LockTimeout(
+                        "the database is in use by other connections",
+                    ))
+                }
+            }
+        }
+    }
+
+    fn send_result(
+        &mut self,
+        mut iter: impl ResultIter,
+        statement: &Statement,
+        result_formats: &[i16],
+        describe: bool,
+        portal: Option<(&str, usize)>,
+        in_transaction: bool,
+    ) -> Result<(), DatabaseError> {
+        if !returns_rows(statement) {
+            // DML reports the number of affected rows as its only result
+            let count = iter
+                .next_tuple(|_, tuple| {
+                    tuple
+                        .values
+                        .first()
+                        .and_then(|value| value.to_string().parse().ok())
+                })?
+                .flatten()
+                .unwrap_or(0);
+            iter.done()?;
+            self.conn.command_complete(&command_tag(statement, count));
+            return Ok(());
+        }
+
+        let columns = iter.schema(schema_columns);
+        if describe {
+            self.row_description(&columns, result_formats);
+        }
+        let (name, max_rows) = portal.unwrap_or_default();
+        let (rows, completed) = self.send_rows(&mut iter, &columns, result_formats, max_rows)?;
+        if completed {
+            iter.done()?;
+            self.conn
+                .command_complete(&command_tag(statement, rows as u64));
+            return Ok(());
+        }
+        if matches!(statement, Statement::Query(_)) {
+            return self.stream_portal(
+                iter,
+                name,
+                statement,
+                &columns,
+                result_formats,
+                in_transaction,
+            );
+        }
+        // like in PostgreSQL, `RETURNING` runs to completion whatever the row limit
+        let rows = buffer_rows(iter, &columns, result_formats)?;
+        self.suspend_or_complete(name, statement, rows, max_rows as u64);
+        Ok(())
+    }
+
+    /// Sends up to `max_rows` rows of `iter`, all of them for `0`, and returns how many were
+    /// sent and whether `iter` ran out of rows.
+    fn send_rows(
+        &mut self,
+        iter: &mut impl ResultIter,
+        columns: &[(String, LogicalType)],
+        result_formats: &[i16],
+        max_rows: usize,
+    ) -> Result<(usize, bool), DatabaseError> {
+        let mut rows = 0;
+        while max_rows == 0 || rows < max_rows {
+            let conn = &mut self.conn;
+            let mut encoded = Ok(());
+            let produced = iter.next_tuple(|_, tuple| {
+                conn.message(b'D', |buf| {
+                    encoded = encode_row(tuple, columns, result_formats, buf);
+                })
+            })?;
+            encoded?;
+            if produced.is_none() {
+                return Ok((rows, true));
+            }
+            rows += 1;
+            self.conn.flush_if_full()?;
+        }
+        Ok((rows, false))
+    }
+
+    /// Suspends the portal `name` after an `Execute` stopped at its row limit and keeps its
+    /// query running, so that the following `Execute` messages of the portal pull their rows
+    /// from `iter` instead of holding the whole result in memory.
+    ///
+    /// The query holds on to its transaction meanwhile. It ends with the portal, or with the
+    /// implicit transaction at `Sync` outside of a transaction block. Any other message needs
+    /// the transaction, so the rows left are buffered in the portal and the message is handled
+    /// by the next step of the session.
+    fn stream_portal(
+        &mut self,
+        mut iter: impl ResultIter,
+        name: &str,
+        statement: &Statement,
+        columns: &[(String, LogicalType)],
+        result_formats: &[i16],
+        in_transaction: bool,
+    ) -> Result<(), DatabaseError> {
+        loop {
+            self.conn.portal_suspended();
+            let message = loop {
+                match self.conn.read_message()? {
+                    Some(FrontendMessage::Flush) => self.conn.flush()?,
+                    Some(FrontendMessage::Sync) if in_transaction => {
+                        self.conn.ready_for_query(TransactionStatus::InTransaction);
+                        self.conn.flush()?;
+                    }
+                    message => break message.unwrap_or(FrontendMessage::Terminate),
+                }
+            };
+            match message {
+                FrontendMessage::Execute { portal, max_rows } if portal == name => {
+                    let (rows, completed) =
+                        self.send_rows(&mut iter, columns, result_formats, max_rows)?;
+                    if completed {
+                        iter.done()?;
+                        self.conn
+                            .command_complete(&command_tag(statement, rows as u64));
+                        return Ok(());
+                    }
+                }
+                FrontendMessage::Close { kind, name: closed } if kind != b'S' && closed == name => {
+                    self.deferred = Some(FrontendMessage::Close { kind, name: closed });
+                    return Ok(());
+                }
+                message @ (FrontendMessage::Sync | FrontendMessage::Terminate) => {
+                    self.deferred = Some(message);
+                    return Ok(());
+                }
+                message => {
+                    let rows = buffer_rows(iter, columns, result_formats)?;
+                    if let Some(portal) = self.portals.get_mut(name) {
+                        portal.state = PortalState::Suspended(rows);
+                    }
+                    self.deferred = Some(message);
+                    return Ok(());
+                }
+            }
+        }
+    }
+
+    fn row_description(&mut self, columns: &[(String, LogicalType)], result_formats: &[i16]) {
+        let fields = columns
+            .iter()
+            .enumerate()
+            .map(|(i, (name, ty))| {
+                let oid = type_oid(ty);
+                (
+                    name.clone(),
+                    oid,
+                    type_len(oid),
+                    format_code(result_formats, i),
+                )
+            })
+            .collect::<Vec<_>>();
+        self.conn.row_description(&fields);
+    }
+
+    /// Completes `BEGIN`, `COMMIT` or `ROLLBACK` once the connection loop applied it.
+    fn transaction_control(&mut self, tag: &str, in_transaction: bool) -> io::Result<()> {
+        self.conn.command_complete(tag);
+        self.statement_done(in_transaction)
+    }
+
+    /// Ends a simple query after its last statement.
+    fn statement_done(&mut self, in_transaction: bool) -> io::Result<()> {
+        if self.in_simple_query && self.pending.is_empty() {
+            self.in_simple_query = false;
+            self.conn
+                .ready_for_query(self.transaction_status(in_transaction));
+            self.conn.flush()?;
+        }
+        Ok(())
+    }
+
+    /// Reports an error, aborting the transaction block, the rest of a simple query or the
+    /// extended query messages up to the next `Sync`.
+    fn fail(&mut self, err: DatabaseError, in_transaction: bool) -> io::Result<()> {
+        if let DatabaseError::IO(err) = &err {
+            // the connection itself is broken, there is nobody left to report to
+            if matches!(
+                err.kind(),
+                io::ErrorKind::BrokenPipe | io::ErrorKind::ConnectionReset
+            ) {
+                return Err(io::Error::new(err.kind(), err.to_string()));
+            }
+        }
+        self.conn.error_response(sqlstate(&err), &err.to_string());
+        self.failed |= in_transaction;
+        if self.in_simple_query {
+            self.pending.clear();
+            self.in_simple_query = false;
+            self.conn
+                .ready_for_query(self.transaction_status(in_transaction));
+            self.conn.flush()
+        } else {
+            self.skip_until_sync = true;
+            Ok(())
+        }
+    }
+
+    fn transaction_status(&self, in_transaction: bool) -> TransactionStatus {
+        match (in_transaction, self.failed) {
+            (false, _) => TransactionStatus::Idle,
+            (true, false) => TransactionStatus::InTransaction,
+            (true, true) => TransactionStatus::Failed,
+        }
+    }
+}
+
+fn infer_types<S: Storage>(statement: &Statement, state: &State<S>) -> HashMap<usize, LogicalType> {
+    infer_param_types(statement, state.table_cache(), state.table_arena().borrow())
+}
+
+/// Plans `statement` inside `transaction` without running it and returns its output columns.
+fn plan_columns<S: Storage>(
+    transaction: &mut DBTransaction<'_, S>,
+    statement: &Statement,
+    params: &[(&'static str, DataValue)],
+) -> Result<Vec<(String, LogicalType)>, DatabaseError> {
+    transaction.inner.begin_statement_scope()?;
+    let plan = transaction
+        .state
+        .build_plan(params, &transaction.inner, |binder, arena| {
+            binder.bind(statement, arena)
+        });
+    transaction.inner.end_statement_scope()?;
+    let (mut plan, mut arena) = plan?;
+    let schema = plan.take_schema(&mut arena);
+
+    Ok(schema_columns(&SchemaView::new(&schema, &arena)))
+}
+
+fn schema_columns(schema: &SchemaView<'_, '_>) -> Vec<(String, LogicalType)> {
+    schema
+        .iter()
+        .map(|column| (column.name().to_string(), column.datatype().clone()))
+        .collect()
+}
+
+/// Encodes the remaining rows of `iter` for a suspended portal.
+fn buffer_rows(
+    mut iter: impl ResultIter,
+    columns: &[(String, LogicalType)],
+    result_formats: &[i16],
+) -> Result<VecDeque<Vec<u8>>, DatabaseError> {
+    let mut rows = VecDeque::new();
+    while let Some(row) = iter.next_tuple(|_, tuple| {
+        let mut row = Vec::new();
+        encode_row(tuple, columns, result_formats, &mut row).map(|()| row)
+    })? {
+        rows.push_back(row?);
+    }
+    iter.done()?;
+    Ok(rows)
+}
+
+/// Writes the body of a `DataRow` message.
+fn encode_row(
+    tuple: &Tuple,
+    columns: &[(String, LogicalType)],
+    result_formats: &[i16],
+    buf: &mut Vec<u8>,
+) -> Result<(), DatabaseError> {
+    buf.extend((tuple.values.len() as i16).to_be_bytes());
+    for (i, value) in tuple.values.iter().enumerate() {
+        if value.is_null() {
+            buf.extend((-1i32).to_be_bytes());
+            continue;
+        }
+        let start = buf.len();
+        buf.extend([0; 4]);
+        if format_code(result_formats, i) == 1 {
+            let ty = columns
+                .get(i)
+                .map(|(_, ty)| ty)
+                .unwrap_or(&LogicalType::SqlNull);
+            encode_binary(value, ty, buf)?;
+        } else {
+            buf.extend(encode_text(value).as_bytes());
+        }
+        let len = (buf.len() - start - 4) as i32;
+        buf[start..start + 4].copy_from_slice(&len.to_be_bytes());
+    }
+    Ok(())
+}
+
+/// The format of the `i`th field: a single code applies to all fields, none means text.
+fn format_code(formats: &[i16], i: usize) -> i16 {
+    match formats {
+        [] => 0,
+        [format] => *format,
+        formats => formats.get(i).copied().unwrap_or(0),
+    }
+}
+
+fn returns_rows(statement: &Statement) -> bool {
+    match statement {
+        Statement::Query(_)
+        | Statement::Explain { .. }
+        | Statement::ExplainTable { .. }
+        | Statement::ShowTables { .. }
+        | Statement::ShowViews { .. } => true,
+        #[cfg(feature = "time")]
+        Statement::ShowVariable { variable } => is_time_zone_variable(variable),
+        Statement::Insert(insert) => insert.returning.is_some(),
+        Statement::Update(update) => update.returning.is_some(),
+        Statement::Delete(delete) => delete.returning.is_some(),
+        _ => false,
+    }
+}
+
+fn command_tag(statement: &Statement, rows: u64) -> String {
+    match statement {
+        Statement::Query(_)
+        | Statement::ExplainTable { .. }
+        | Statement::ShowTables { .. }
+        | Statement::ShowViews { .. } => format!("SELECT {rows}"),
+        Statement::Explain { .. } => "EXPLAIN".to_string(),
+        Statement::Insert(_) => format!("INSERT 0 {rows}"),
+        Statement::Update(_) => format!("UPDATE {rows}"),
+        Statement::Delete(_) => format!("DELETE {rows}"),
+        Statement::Copy { .. } => format!("COPY {rows}"),
+        Statement::CreateTable(_) => "CREATE TABLE".to_string(),
+        Statement::CreateIndex(_) => "CREATE INDEX".to_string(),
+        Statement::CreateView(_) => "CREATE VIEW".to_string(),
+        Statement::AlterTable(_) => "ALTER TABLE".to_string(),
+        Statement::Drop { object_type, .. } => format!("DROP {object_type}"),
+        Statement::Truncate(_) => "TRUNCATE TABLE".to_string(),
+        Statement::Analyze(_) => "ANALYZE".to_string(),
+        Statement::Set(_) => "SET".to_string(),
+        Statement::ShowVariable { .. } => "SHOW".to_string(),
+        _ => "OK".to_string(),
+    }
+}
+
+fn sqlstate(err: &DatabaseError) -> &'static str {
+    match err {
+        DatabaseError::ParserSql(_) | DatabaseError::EmptyStatement => "42601",
+        DatabaseError::UnsupportedStmt(message) if message == TRANSACTION_ABORTED => "25P02",
+        DatabaseError::UnsupportedStmt(_)
+        | DatabaseError::UnsupportedUnaryOperator(..)
+        | DatabaseError::UnsupportedBinaryOperator(..) => "0A000",
+        DatabaseError::TableNotFound
+        | DatabaseError::ViewNotFound
+        | DatabaseError::SourceNotFound
+        | DatabaseError::SequenceNotFound(_) => "42P01",
+        DatabaseError::TableExists
+        | DatabaseError::ViewExists
+        | DatabaseError::SequenceExists(_) => "42P07",
+        DatabaseError::ColumnNotFound { .. } | DatabaseError::InvalidColumn { .. } => "42703",
+        DatabaseError::FunctionNotFound { .. } => "42883",
+        DatabaseError::DuplicatePrimaryKey | DatabaseError::DuplicateUniqueValue => "23505",
+        DatabaseError::NotNull { .. } => "23502",
+        DatabaseError::CheckViolation { .. } => "23514",
+        DatabaseError::ForeignKeyViolation { .. } => "23503",
+        DatabaseError::TableReferenced { .. } => "2BP01",
+        DatabaseError::GeneratedAlways(_) => "428C9",
+        DatabaseError::SequenceExhausted(_) => "2200H",
+        DatabaseError::SequenceNotCalled(_) => "55000",
+        DatabaseError::DuplicateConstraint(_) => "42710",
+        DatabaseError::ConstraintNotFound(_) => "42704",
+        DatabaseError::ParametersNotFound { .. } => "08P01",
+        DatabaseError::CastFail { .. }
+        | DatabaseError::InvalidValue(_)
+        | DatabaseError::ParseBool(_)
+        | DatabaseError::ParseFloat(_)
+        | DatabaseError::ParseInt(_)
+        | DatabaseError::FromUtf8Error(_) => "22P02",
+        DatabaseError::OverFlow => "22003",
+        DatabaseError::DivisionByZero => "22012",
+        DatabaseError::TooLong => "22001",
+        DatabaseError::TransactionConflict => "40001",
+        DatabaseError::LockTimeout(_) => "55P03",
+        _ => "XX000",
+    }
+}
@@ -0,0 +1,469 @@
+// Copyright 2024 KipData/KiteSQL
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Maps [`LogicalType`] and [`DataValue`] onto PostgreSQL type oids and their text and binary
+//! encodings.
+
+use crate::errors::DatabaseError;
+use crate::types::value::{DataValue, Utf8Type};
+use crate::types::{CharLengthUnits, LogicalType};
+use std::fmt::Write;
+
+pub(crate) const BOOL: u32 = 16;
+pub(crate) const INT8: u32 = 20;
+pub(crate) const INT2: u32 = 21;
+pub(crate) const INT4: u32 = 23;
+pub(crate) const TEXT: u32 = 25;
+pub(crate) const JSON: u32 = 114;
+pub(crate) const FLOAT4: u32 = 700;
+pub(crate) const FLOAT8: u32 = 701;
+pub(crate) const UNKNOWN: u32 = 705;
+pub(crate) const BPCHAR: u32 = 1042;
+pub(crate) const VARCHAR: u32 = 1043;
+pub(crate) const DATE: u32 = 1082;
+pub(crate) const TIME: u32 = 1083;
+pub(crate) const TIMESTAMP: u32 = 1114;
+pub(crate) const TIMESTAMPTZ: u32 = 1184;
+pub(crate) const INTERVAL: u32 = 1186;
+pub(crate) const NUMERIC: u32 = 1700;
+pub(crate) const JSONB: u32 = 3802;
+
+/// Days between 0001-01-01, the epoch of [`DataValue::Date32`], and 2000-01-01.
+const PG_EPOCH_DAYS_FROM_CE: i32 = 730_120;
+/// Seconds between 1970-01-01 and 2000-01-01, the epoch of PostgreSQL timestamps.
+const PG_EPOCH_UNIX_SECS: i64 = 946_684_800;
+
+const NUMERIC_NEG: u16 = 0x4000;
+
+pub(crate) fn type_oid(ty: &LogicalType) -> u32 {
+    match ty {
+        LogicalType::Boolean => BOOL,
+        LogicalType::Tinyint | LogicalType::UTinyint | LogicalType::Smallint => INT2,
+        LogicalType::USmallint | LogicalType::Integer => INT4,
+        LogicalType::UInteger | LogicalType::Bigint => INT8,
+        LogicalType::UBigint | LogicalType::Decimal(..) => NUMERIC,
+        LogicalType::Float => FLOAT4,
+        LogicalType::Double => FLOAT8,
+        LogicalType::Char(..) => BPCHAR,
+        LogicalType::Varchar(..) => VARCHAR,
+        LogicalType::Date => DATE,
+        LogicalType::DateTime | LogicalType::TimeStamp(_, false) => TIMESTAMP,
+        LogicalType::TimeStamp(_, true) => TIMESTAMPTZ,
+        LogicalType::Time(_) => TIME,
+        LogicalType::Interval => INTERVAL,
+        LogicalType::Json => JSONB,
+        // arrays travel in their `{1,2}` text form
+        LogicalType::SqlNull | LogicalType::Tuple(_) | LogicalType::Array(_) => TEXT,
+    }
+}
+
+/// The `typlen` reported in a row description, `-1` for variable length types.
+pub(crate) fn type_len(oid: u32) -> i16 {
+    match oid {
+        BOOL => 1,
+        INT2 => 2,
+        INT4 | FLOAT4 | DATE => 4,
+        INT8 | FLOAT8 | TIME | TIMESTAMP | TIMESTAMPTZ => 8,
+        INTERVAL => 16,
+        _ => -1,
+    }
+}
+
+/// The type a parameter declared as `oid` is cast to, `None` when it stays a string.
+fn param_type(oid: u32) -> Option<LogicalType> {
+    Some(match oid {
+        BOOL => LogicalType::Boolean,
+        INT2 => LogicalType::Smallint,
+        INT4 => LogicalType::Integer,
+        INT8 => LogicalType::Bigint,
+        FLOAT4 => LogicalType::Float,
+        FLOAT8 => LogicalType::Double,
+        DATE => LogicalType::Date,
+        TIME => LogicalType::Time(None),
+        TIMESTAMP => LogicalType::TimeStamp(Some(6), false),
+        TIMESTAMPTZ => LogicalType::TimeStamp(Some(6), true),
+        #[cfg(feature = "decimal")]
+        NUMERIC => LogicalType::Decimal(None, None),
+        #[cfg(not(feature = "decimal"))]
+        NUMERIC => LogicalType::Double,
+        JSON | JSONB => LogicalType::Json,
+        _ => return None,
+    })
+}
+
+fn utf8(value: String) -> DataValue {
+    DataValue::Utf8 {
+        value,
+        ty: Utf8Type::Variable(None),
+        unit: CharLengthUnits::Characters,
+    }
+}
+
+pub(crate) fn encode_text(value: &DataValue) -> String {
+    match value {
+        DataValue::Boolean(value) => if *value { "t" } else { "f" }.to_string(),
+        value => value.to_string(),
+    }
+}
+
+/// Appends the binary encoding of `value`, a non-null value of a column typed `ty`.
+pub(crate) fn encode_binary(
+    value: &DataValue,
+    ty: &LogicalType,
+    buf: &mut Vec<u8>,
+) -> Result<(), DatabaseError> {
+    match type_oid(ty) {
+        BOOL => buf.push(value.clone().cast(&LogicalType::Boolean)?.is_true()? as u8),
+        INT2 => match value.clone().cast(&LogicalType::Smallint)? {
+            DataValue::Int16(value) => buf.extend(value.to_be_bytes()),
+            _ => return Err(DatabaseError::InvalidType),
+        },
+        INT4 => match value.clone().cast(&LogicalType::Integer)? {
+            DataValue::Int32(value) => buf.extend(value.to_be_bytes()),
+            _ => return Err(DatabaseError::InvalidType),
+        },
+        INT8 => match value.clone().cast(&LogicalType::Bigint)? {
+            DataValue::Int64(value) => buf.extend(value.to_be_bytes()),
+            _ => return Err(DatabaseError::InvalidType),
+        },
+        FLOAT4 => match value.clone().cast(&LogicalType::Float)? {
+            DataValue::Float32(value) => buf.extend(value.0.to_be_bytes()),
+            _ => return Err(DatabaseError::InvalidType),
+        },
+        FLOAT8 => match value.clone().cast(&LogicalType::Double)? {
+            DataValue::Float64(value) => buf.extend(value.0.to_be_bytes()),
+            _ => return Err(DatabaseError::InvalidType),
+        },
+        DATE => match value {
+            DataValue::Date32(days) => buf.extend((days - PG_EPOCH_DAYS_FROM_CE).to_be_bytes()),
+            _ => return Err(DatabaseError::InvalidType),
+        },
+        TIME => match value {
+            DataValue::Time32(value, precision) => {
+                let (secs, nanos) = DataValue::unpack_time(*value, *precision);
+                let micros = secs as i64 * 1_000_000 + nanos as i64 / 1_000;
+                buf.extend(micros.to_be_bytes())
+            }
+            _ => return Err(DatabaseError::InvalidType),
+        },
+        TIMESTAMP | TIMESTAMPTZ => {
+            let unix_micros = match value {
+                DataValue::Date64(secs) => secs * 1_000_000,
+                DataValue::Time64(value, precision, _) => match precision {
+                    0 => value * 1_000_000,
+                    3 => value * 1_000,
+                    9 => value.div_euclid(1_000),
+                    _ => *value,
+                },
+                _ => return Err(DatabaseError::InvalidType),
+            };
+            buf.extend((unix_micros - PG_EPOCH_UNIX_SECS * 1_000_000).to_be_bytes())
+        }
+        INTERVAL => match value {
+            DataValue::Interval(interval) => {
+                buf.extend((interval.nanos / 1_000).to_be_bytes());
+                buf.extend(interval.days.to_be_bytes());
+                buf.extend(interval.months.to_be_bytes())
+            }
+            _ => return Err(DatabaseError::InvalidType),
+        },
+        NUMERIC => encode_numeric(&value.to_string(), buf)?,
+        // the binary `jsonb` format is a version byte followed by the text
+        JSONB => {
+            buf.push(1);
+            buf.extend(value.to_string().as_bytes())
+        }
+        _ => buf.extend(encode_text(value).as_bytes()),
+    }
+    Ok(())
+}
+
+/// Encodes a plain decimal number such as `-12.340` as a PostgreSQL `numeric`: base 10000
+/// digits, the weight of the first digit, a sign and the display scale.
+fn encode_numeric(text: &str, buf: &mut Vec<u8>) -> Result<(), DatabaseError> {
+    let invalid = || DatabaseError::InvalidValue(format!("`{text}` is not a numeric"));
+    let (negative, digits) = match text.strip_prefix('-') {
+        Some(digits) => (true, digits),
+        None => (false, text),
+    };
+    let (int_part, frac_part) = digits.split_once('.').unwrap_or((digits, ""));
+    if !int_part
+        .bytes()
+        .chain(frac_part.bytes())
+        .all(|b| b.is_ascii_digit())
+    {
+        return Err(invalid());
+    }
+    let int_part = int_part.trim_start_matches('0');
+    let int_part = format!("{}{int_part}", "0".repeat((4 - int_part.len() % 4) % 4));
+    let frac_scale = frac_part.len();
+    let frac_part = format!("{frac_part}{}", "0".repeat((4 - frac_scale % 4) % 4));
+
+    let group = |chunk: &[u8]| {
+        chunk
+            .iter()
+            .fold(0i16, |group, digit| group * 10 + (digit - b'0') as i16)
+    };
+    let mut groups = int_part
+        .as_bytes()
+        .chunks(4)
+        .chain(frac_part.as_bytes().chunks(4))
+        .map(group)
+        .collect::<Vec<_>>();
+    let mut weight = (int_part.len() / 4) as i16 - 1;
+    let leading_zeros = groups.iter().take_while(|group| **group == 0).count();
+    groups.drain(..leading_zeros);
+    weight -= leading_zeros as i16;
+    while groups.last() == Some(&0) {
+        groups.pop();
+    }
+    if groups.is_empty() {
+        weight = 0;
+    }
+    let sign = if negative && !groups.is_empty() {
+        NUMERIC_NEG
+    } else {
+        0
+    };
+
+    buf.extend((groups.len() as i16).to_be_bytes());
+    buf.extend(weight.to_be_bytes());
+    buf.extend(sign.to_be_bytes());
+    buf.extend((frac_scale as u16).to_be_bytes());
+    for group in groups {
+        buf.extend(group.to_be_bytes());
+    }
+    Ok(())
+}
+
+fn decode_numeric(bytes: &[u8]) -> Result<String, DatabaseError> {
+    let invalid = || DatabaseError::InvalidValue("malformed binary numeric".to_string());
+    let read_i16 = |i: usize| {
+        bytes
+            .get(i * 2..i * 2 + 2)
+            .map(|b| i16::from_be_bytes([b[0], b[1]]))
+            .ok_or_else(invalid)
+    };
+    let ndigits = read_i16(0)?.max(0) as usize;
+    let weight = read_i16(1)? as isize;
+    let sign = read_i16(2)? as u16;
+    let dscale = read_i16(3)?.max(0) as usize;
+    let digits = (0..ndigits)
+        .map(|i| read_i16(4 + i))
+        .collect::<Result<Vec<_>, _>>()?;
+    let digit = |i: isize| {
+        usize::try_from(i)
+            .ok()
+            .and_then(|i| digits.get(i))
+            .copied()
+            .unwrap_or(0)
+    };
+    if sign != 0 && sign != NUMERIC_NEG {
+        return Err(DatabaseError::InvalidValue(
+            "NaN and infinite numerics are not supported".to_string(),
+        ));
+    }
+
+    let mut text = String::new();
+    if sign == NUMERIC_NEG {
+        text.push('-');
+    }
+    if weight < 0 {
+        text.push('0');
+    }
+    for i in 0..=weight {
+        if i == 0 {
+            let _ = write!(text, "{}", digit(i));
+        } else {
+            let _ = write!(text, "{:04}", digit(i));
+        }
+    }
+    if dscale > 0 {
+        let mut frac = String::new();
+        let mut i = weight + 1;
+        while frac.len() < dscale {
+            let _ = write!(frac, "{:04}", digit(i));
+            i += 1;
+        }
+        frac.truncate(dscale);
+        text.push('.');
+        text.push_str(&frac);
+    }
+    Ok(text)
+}
+
+/// Decodes a bind parameter declared as `oid`, in the text or the binary format.
+pub(crate) fn decode_param(
+    bytes: &[u8],
+    oid: u32,
+    binary: bool,
+) -> Result<DataValue, DatabaseError> {
+    let ty = param_type(oid);
+    if !binary {
+        let value = utf8(String::from_utf8(bytes.to_vec())?);
+        return match ty {
+            Some(LogicalType::Boolean) => Ok(DataValue::Boolean(match bytes {
+                b"t" | b"true" | b"on" | b"1" | b"yes" => true,
+                b"f" | b"false" | b"off" | b"0" | b"no" => false,
+                _ => return Err(DatabaseError::InvalidValue(value.to_string())),
+            })),
+            Some(ty) => value.cast(&ty),
+            None => Ok(value),
+        };
+    }
+    let fixed = |len: usize| -> Result<&[u8], DatabaseError> {
+        if bytes.len() == len {
+            Ok(bytes)
+        } else {
+            Err(DatabaseError::InvalidValue(format!(
+                "binary parameter of type {oid} must be {len} bytes, got {}",
+                bytes.len()
+            )))
+        }
+    };
+
+    Ok(match oid {
+        BOOL => DataValue::Boolean(fixed(1)?[0] != 0),
+        INT2 => DataValue::Int16(i16::from_be_bytes(fixed(2)?.try_into().unwrap())),
+        INT4 => DataValue::Int32(i32::from_be_bytes(fixed(4)?.try_into().unwrap())),
+        INT8 => DataValue::Int64(i64::from_be_bytes(fixed(8)?.try_into().unwrap())),
+        FLOAT4 => DataValue::Float32(f32::from_be_bytes(fixed(4)?.try_into().unwrap()).into()),
+        FLOAT8 => DataValue::Float64(f64::from_be_bytes(fixed(8)?.try_into().unwrap()).into()),
+        DATE => DataValue::Date32(
+            i32::from_be_bytes(fixed(4)?.try_into().unwrap()) + PG_EPOCH_DAYS_FROM_CE,
+        ),
+        TIME => {
+            let micros = i64::from_be_bytes(fixed(8)?.try_into().unwrap());
+            DataValue::Time32(u32::try_from(micros / 1_000_000)?, 0)
+        }
+        TIMESTAMP | TIMESTAMPTZ => DataValue::Time64(
+            i64::from_be_bytes(fixed(8)?.try_into().unwrap()) + PG_EPOCH_UNIX_SECS * 1_000_000,
+            6,
+            oid == TIMESTAMPTZ,
+        ),
+        NUMERIC => {
+            let value = utf8(decode_numeric(bytes)?);
+            match ty {
+                Some(ty) => value.cast(&ty)?,
+                None => value,
+            }
+        }
+        TEXT | VARCHAR | BPCHAR | UNKNOWN => utf8(String::from_utf8(bytes.to_vec())?),
+        JSON => utf8(String::from_utf8(bytes.to_vec())?).cast(&LogicalType::Json)?,
+        JSONB => match bytes.split_first() {
+            Some((1, text)) => utf8(String::from_utf8(text.to_vec())?).cast(&LogicalType::Json)?,
+            _ => {
+                return Err(DatabaseError::InvalidValue(
+                    "unsupported binary jsonb version".to_string(),
+                ))
+            }
+        },
+        oid => {
+            return Err(DatabaseError::UnsupportedStmt(format!(
+                "binary parameters of type oid {oid}"
+            )))
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn numeric_round_trips_through_the_binary_format() -> Result<(), DatabaseError> {
+        for text in [
+            "0",
+            "1",
+            "-12.340",
+            "12345.6",
+            "0.0001",
+            "100000000",
+            "-0.5",
+        ] {
+            let mut buf = Vec::new();
+            encode_numeric(text, &mut buf)?;
+            assert_eq!(decode_numeric(&buf)?, text);
+        }
+        let mut buf = Vec::new();
+        encode_numeric("12345.6", &mut buf)?;
+        // 2 digits, weight 1, positive, scale 1: 1 2345 . 6000
+        assert_eq!(buf, [0, 3, 0, 1, 0, 0, 0, 1, 0, 1, 0x09, 0x29, 0x17, 0x70]);
+        assert!(encode_numeric("1e5", &mut Vec::new()).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn params_are_cast_to_their_declared_types() -> Result<(), DatabaseError> {
+        assert_eq!(decode_param(b"42", INT4, false)?, DataValue::Int32(42));
+        assert_eq!(decode_param(b"t", BOOL, false)?, DataValue::Boolean(true));
+        assert_eq!(
+            decode_param(&7i64.to_be_bytes(), INT8, true)?,
+            DataValue::Int64(7)
+        );
+        assert_eq!(
+            decode_param(b"kite", UNKNOWN, false)?,
+            DataValue::Utf8 {
+                value: "kite".to_string(),
+                ty: Utf8Type::Variable(None),
+                unit: CharLengthUnits::Characters,
+            }
+        );
+        assert!(decode_param(&[0, 1], INT4, true).is_err());
+        assert_eq!(
+            decode_param(b"\x01{\"a\": 1}", JSONB, true)?.to_string(),
+            "{\"a\": 1}"
+        );
+        assert!(decode_param(b"{", JSON, false).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn values_are_encoded_for_their_column_types() -> Result<(), DatabaseError> {
+        let mut buf = Vec::new();
+        encode_binary(&DataValue::Int8(-2), &LogicalType::Tinyint, &mut buf)?;
+        assert_eq!(buf, (-2i16).to_be_bytes());
+
+        buf.clear();
+        encode_binary(
+            &DataValue::Date32(PG_EPOCH_DAYS_FROM_CE + 1),
+            &LogicalType::Date,
+            &mut buf,
+        )?;
+        assert_eq!(buf, 1i32.to_be_bytes());
+
+        buf.clear();
+        encode_binary(
+            &DataValue::Date64(PG_EPOCH_UNIX_SECS + 2),
+            &LogicalType::DateTime,
+            &mut buf,
+        )?;
+        assert_eq!(buf, 2_000_000i64.to_be_bytes());
+
+        assert_eq!(encode_text(&DataValue::Boolean(false)), "f");
+        assert_eq!(type_oid(&LogicalType::UBigint), NUMERIC);
+
+        buf.clear();
+        encode_binary(
+            &DataValue::Json(crate::types::json::Json::Bool(true)),
+            &LogicalType::Json,
+            &mut buf,
+        )?;
+        assert_eq!(buf, b"\x01true");
+
+        Ok(())
+    }
+}
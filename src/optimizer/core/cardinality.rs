@@ -0,0 +1,258 @@
+// Copyright 2024 KipData/KiteSQL
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::catalog::ColumnRef;
+use crate::errors::DatabaseError;
+use crate::expression::range_detacher::Range;
+use crate::expression::ScalarExpression;
+use crate::optimizer::core::statistics_meta::StatisticMetaLoader;
+use crate::planner::operator::join::{JoinCondition, JoinOperator, JoinType};
+use crate::planner::operator::table_scan::TableScanOperator;
+use crate::planner::operator::Operator;
+use crate::planner::{Childrens, LogicalPlan, PlanArena};
+use crate::types::index::{IndexLookup, IndexType};
+
+/// Selectivity assumed for predicates the statistics cannot answer.
+pub(crate) const DEFAULT_FILTER_SELECTIVITY: f64 = 1.0 / 3.0;
+
+/// Estimates how many rows a plan produces from the histograms, count-min sketches
+/// and top-n lists collected by `ANALYZE`.
+///
+/// An estimate is `None` as soon as a table below the plan has no statistics.
+pub(crate) struct CardinalityEstimator<'a, 'l> {
+    loader: &'a StatisticMetaLoader<'l>,
+}
+
+impl<'a, 'l> CardinalityEstimator<'a, 'l> {
+    pub(crate) fn new(loader: &'a StatisticMetaLoader<'l>) -> Self {
+        Self { loader }
+    }
+
+    pub(crate) fn plan_rows(
+        &self,
+        plan: &LogicalPlan,
+        arena: &PlanArena,
+    ) -> Result<Option<f64>, DatabaseError> {
+        let rows = match (&plan.operator, plan.childrens.as_ref()) {
+            (Operator::TableScan(scan_op), _) => return self.scan_rows(scan_op, arena),
+            (Operator::Filter(_), Childrens::Only(child)) => {
+                let Some(rows) = self.plan_rows(child, arena)? else {
+                    return Ok(None);
+                };
+                // ranges detached into the scan already account for the predicate
+                match &child.operator {
+                    Operator::TableScan(scan_op) if has_static_lookup(scan_op) => rows,
+                    _ => rows * DEFAULT_FILTER_SELECTIVITY,
+                }
+            }
+            (
+                Operator::Project(_) | Operator::Sort(_) | Operator::Window(_),
+                Childrens::Only(child),
+            )
+            | (
+                Operator::ScalarApply(_) | Operator::MarkApply(_) | Operator::LateralApply(_),
+                Childrens::Twins { left: child, .. },
+            ) => return self.plan_rows(child, arena),
+            (Operator::Limit(op), Childrens::Only(child)) => {
+                let Some(rows) = self.plan_rows(child, arena)? else {
+                    return Ok(None);
+                };
+                op.limit.map_or(rows, |limit| rows.min(limit as f64))
+            }
+            (Operator::TopK(op), Childrens::Only(child)) => {
+                let Some(rows) = self.plan_rows(child, arena)? else {
+                    return Ok(None);
+                };
+                rows.min(op.limit as f64)
+            }
+            (Operator::Aggregate(op), Childrens::Only(child)) => {
+                let Some(rows) = self.plan_rows(child, arena)? else {
+                    return Ok(None);
+                };
+                if !op.grouping_sets.is_empty() {
+                    rows * op.grouping_sets.len() as f64
+                } else if op.groupby_exprs.is_empty() {
+                    1.0
+                } else {
+                    rows
+                }
+            }
+            (Operator::Join(op), Childrens::Twins { left, right }) => {
+                return self.join_rows(op, left, right, arena)
+            }
+            (Operator::Values(op), _) => op.rows.len() as f64,
+            (Operator::Dummy, _) => 1.0,
+            _ => return Ok(None),
+        };
+        Ok(Some(rows))
+    }
+
+    /// Number of distinct values of `expr` in the table `plan` reads it from, known only for
+    /// columns covered by a single-column index with statistics.
+    pub(crate) fn distinct_values(
+        &self,
+        plan: &LogicalPlan,
+        expr: &ScalarExpression,
+        arena: &PlanArena,
+    ) -> Result<Option<f64>, DatabaseError> {
+        let ScalarExpression::ColumnRef { column, .. } = expr.unpack_alias_ref() else {
+            return Ok(None);
+        };
+        let (Some(scan_op), Some(column_id)) =
+            (find_scan(plan, *column), arena.column(*column).id())
+        else {
+            return Ok(None);
+        };
+        for index_info in scan_op.index_infos.iter() {
+            let index_meta = arena.index(index_info.meta);
+            if index_meta.column_ids.as_slice() != [column_id] || !index_meta.json_path.is_empty() {
+                continue;
+            }
+            if let Some(statistics_meta) = self.loader.load(&scan_op.table_name, index_meta.id)? {
+                return Ok(Some(
+                    statistics_meta.histogram().distinct_values_len() as f64
+                ));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Fraction of the cross product kept by an equi-join key, assuming every value of the
+    /// side with fewer distinct values finds a match. Without any distinct count the larger
+    /// input is assumed to be unique on the key.
+    pub(crate) fn equi_selectivity(
+        left_distinct: Option<f64>,
+        right_distinct: Option<f64>,
+        left_rows: f64,
+        right_rows: f64,
+    ) -> f64 {
+        let distinct = match (left_distinct, right_distinct) {
+            (Some(left), Some(right)) => left.max(right),
+            (Some(distinct), None) | (None, Some(distinct)) => distinct,
+            (None, None) => left_rows.max(right_rows),
+        };
+        1.0 / distinct.max(1.0)
+    }
+
+    fn join_rows(
+        &self,
+        op: &JoinOperator,
+        left: &LogicalPlan,
+        right: &LogicalPlan,
+        arena: &PlanArena,
+    ) -> Result<Option<f64>, DatabaseError> {
+        let (Some(left_rows), Some(right_rows)) =
+            (self.plan_rows(left, arena)?, self.plan_rows(right, arena)?)
+        else {
+            return Ok(None);
+        };
+        let mut rows = left_rows * right_rows;
+        if let JoinCondition::On { on, filter } = &op.on {
+            for (left_expr, right_expr) in on {
+                rows *= Self::equi_selectivity(
+                    self.distinct_values(left, left_expr, arena)?,
+                    self.distinct_values(right, right_expr, arena)?,
+                    left_rows,
+                    right_rows,
+                );
+            }
+            if filter.is_some() {
+                rows *= DEFAULT_FILTER_SELECTIVITY;
+            }
+        }
+
+        Ok(Some(match op.join_type {
+            JoinType::Inner | JoinType::Cross => rows,
+            JoinType::LeftOuter => rows.max(left_rows),
+            JoinType::RightOuter => rows.max(right_rows),
+            JoinType::Full => rows.max(left_rows + right_rows),
+        }))
+    }
+
+    fn scan_rows(
+        &self,
+        scan_op: &TableScanOperator,
+        arena: &PlanArena,
+    ) -> Result<Option<f64>, DatabaseError> {
+        let mut table_rows = None;
+        let mut range_rows: Option<usize> = None;
+
+        for index_info in scan_op.index_infos.iter() {
+            let index_meta = arena.index(index_info.meta);
+            if matches!(index_meta.ty, IndexType::PrimaryKey { .. }) {
+                table_rows = self
+                    .loader
+                    .load(&scan_op.table_name, index_meta.id)?
+                    .map(|statistics_meta| statistics_meta.histogram().values_len());
+            }
+            let Some(IndexLookup::Static(range)) = &index_info.lookup else {
+                continue;
+            };
+            if let Some(row_count) =
+                self.loader
+                    .collect_count(&scan_op.table_name, index_meta.id, range)?
+            {
+                let row_count = adjust_index_row_count(index_meta.ty, range, row_count);
+                range_rows = Some(range_rows.map_or(row_count, |rows| rows.min(row_count)));
+            }
+        }
+
+        Ok(range_rows.or(table_rows).map(|rows| rows as f64))
+    }
+}
+
+fn has_static_lookup(scan_op: &TableScanOperator) -> bool {
+    scan_op
+        .index_infos
+        .iter()
+        .any(|index_info| matches!(index_info.lookup, Some(IndexLookup::Static(_))))
+}
+
+fn find_scan(plan: &LogicalPlan, column: ColumnRef) -> Option<&TableScanOperator> {
+    if let Operator::TableScan(scan_op) = &plan.operator {
+        return scan_op.columns.contains(&column).then_some(scan_op);
+    }
+    plan.childrens
+        .iter()
+        .find_map(|child| find_scan(child, column))
+}
+
+pub(crate) fn adjust_index_row_count(
+    index_type: IndexType,
+    range: &Range,
+    row_count: usize,
+) -> usize {
+    let row_count = unique_eq_row_count(index_type, range).unwrap_or(row_count);
+    if row_count == 0 && !matches!(range, Range::Dummy) {
+        1
+    } else {
+        row_count
+    }
+}
+
+fn unique_eq_row_count(index_type: IndexType, range: &Range) -> Option<usize> {
+    match range {
+        Range::Dummy => Some(0),
+        Range::Eq(value)
+            if !value.is_null()
+                && matches!(index_type, IndexType::PrimaryKey { .. } | IndexType::Unique) =>
+        {
+            Some(1)
+        }
+        Range::SortedRanges(ranges) => ranges.iter().try_fold(0usize, |count, range| {
+            unique_eq_row_count(index_type, range).map(|row_count| count + row_count)
+        }),
+        _ => None,
+    }
+}
@@ -487,6 +487,12 @@ impl Histogram {
                         .double(),
                     _ => unreachable!(),
                 },
+                LogicalType::Interval => match value {
+                    DataValue::Interval(interval) => Some(interval.total_nanos() as f64),
+                    _ => unreachable!(),
+                },
+                // documents and arrays have no numeric position to interpolate between bucket bounds
+                LogicalType::Json | LogicalType::Array(_) => None,
 
                 LogicalType::SqlNull
                 | LogicalType::Boolean
@@ -721,6 +727,7 @@ mod tests {
             value_ty: LogicalType::Integer,
             name: "pk_c1".to_string(),
             ty: IndexType::PrimaryKey { is_multiple: false },
+            json_path: vec![],
         }
     }
 
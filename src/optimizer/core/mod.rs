@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+pub(crate) mod cardinality;
 pub(crate) mod cm_sketch;
 pub(crate) mod histogram;
 pub(crate) mod hll;
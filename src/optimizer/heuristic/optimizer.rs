@@ -21,6 +21,7 @@ use crate::optimizer::heuristic::batch::{
     HepBatch, HepBatchStep, HepBatchStrategy, HepLocalRewriteBatch, HepWholeTreePass,
 };
 use crate::optimizer::rule::implementation::{ImplementationRuleImpl, ImplementationRuleRootTag};
+use crate::optimizer::rule::join_enumeration::JoinEnumeration;
 use crate::optimizer::rule::normalization::{
     apply_annotated_post_rules, apply_scan_order_hint, constant_calculation_current,
     evaluator_bind_current, NormalizationRuleImpl, OrderHintKind, ScanOrderHint, WholeTreePassKind,
@@ -73,6 +74,12 @@ impl<'a> HepOptimizer<'a> {
         )?;
 
         if let Some(loader) = loader {
+            if self
+                .implementation_index
+                .contains(ImplementationRuleImpl::HashJoin)
+            {
+                JoinEnumeration::new(loader).apply(&mut self.plan, arena)?;
+            }
             if self.implementation_index.is_empty().not() {
                 let apply_no_sort_hints =
                     |_scan_op: &mut TableScanOperator, _arena: &PlanArena| Ok(());
@@ -258,17 +265,21 @@ impl<'a> HepOptimizer<'a> {
             let LogicalPlan {
                 operator,
                 physical_option,
+                estimated_cost,
                 ..
             } = plan;
-            if let Some(option) = implementation_index.direct_physical_option(operator) {
+            if matches!(operator, Operator::Join(_)) && physical_option.is_some() {
+                // already chosen by cost during join enumeration
+            } else if let Some(option) = implementation_index.direct_physical_option(operator) {
                 *physical_option = Some(option);
             } else {
                 let mut best_physical_option: BestPhysicalOption = None;
                 for rule in implementation_index.for_matching_operator(operator) {
                     rule.update_best_option(operator, arena, loader, &mut best_physical_option)?;
                 }
-                if let Some((option, _)) = best_physical_option {
+                if let Some((option, cost)) = best_physical_option {
                     *physical_option = Some(option);
+                    *estimated_cost = cost;
                 }
             }
         }
@@ -371,7 +382,9 @@ impl<'a> HepOptimizer<'a> {
         );
 
         match operator {
-            Operator::Aggregate(op) if !op.groupby_exprs.is_empty() => {
+            Operator::Aggregate(op)
+                if !op.groupby_exprs.is_empty() && op.grouping_sets.is_empty() =>
+            {
                 let child_stream_aggregate_hints =
                     |scan_op: &mut TableScanOperator, arena: &PlanArena| {
                         apply_scan_order_hint(
@@ -503,6 +516,12 @@ impl ImplementationRuleIndex {
             Operator::Join(join_op) if self.contains(ImplementationRuleImpl::HashJoin) => {
                 Some(PhysicalOption::new(join_op.plan_impl(), SortOption::None))
             }
+            Operator::LateralApply(_) if self.contains(ImplementationRuleImpl::LateralApply) => {
+                Some(PhysicalOption::new(
+                    PlanImpl::LateralApply,
+                    SortOption::None,
+                ))
+            }
             Operator::Limit(_) if self.contains(ImplementationRuleImpl::Limit) => {
                 Some(PhysicalOption::new(PlanImpl::Limit, SortOption::Follow))
             }
@@ -660,6 +679,7 @@ mod tests {
         )];
         let scala_functions = Default::default();
         let table_functions = Default::default();
+        let aggregate_functions = Default::default();
         let mut binder = Binder::new(
             BinderContext::new(
                 database.state.table_cache(),
@@ -667,6 +687,7 @@ mod tests {
                 &transaction,
                 &scala_functions,
                 &table_functions,
+                &aggregate_functions,
             ),
             &[],
             None,
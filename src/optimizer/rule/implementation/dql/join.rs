@@ -16,9 +16,15 @@ use crate::errors::DatabaseError;
 use crate::optimizer::core::pattern::{Pattern, PatternChildrenPredicate};
 use crate::optimizer::core::rule::{BestPhysicalOption, ImplementationRule, MatchPattern};
 use crate::optimizer::core::statistics_meta::StatisticMetaLoader;
-use crate::planner::operator::{Operator, PhysicalOption, SortOption};
+use crate::planner::operator::{Operator, PhysicalOption, PlanImpl, SortOption};
 use std::sync::LazyLock;
 
+/// Building a hash table costs more per row than probing it.
+const HASH_BUILD_WEIGHT: f64 = 2.0;
+/// Seeking into an index, and fetching the rows it finds, costs more per row than reading the
+/// next row of a scan.
+const INDEX_PROBE_WEIGHT: f64 = 4.0;
+
 static JOIN_PATTERN: LazyLock<Pattern> = LazyLock::new(|| Pattern {
     predicate: |op| matches!(op, Operator::Join(_)),
     children: PatternChildrenPredicate::None,
@@ -27,6 +33,67 @@ static JOIN_PATTERN: LazyLock<Pattern> = LazyLock::new(|| Pattern {
 #[derive(Clone)]
 pub struct JoinImplementation;
 
+/// Estimated rows and costs of both join inputs and of the join result.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct JoinCostInput {
+    pub(crate) left_rows: f64,
+    pub(crate) left_cost: f64,
+    pub(crate) right_rows: f64,
+    pub(crate) right_cost: f64,
+    pub(crate) output_rows: f64,
+    /// Whether an index on the right input can be probed with every left row.
+    pub(crate) right_index_probe: bool,
+}
+
+impl JoinImplementation {
+    /// Picks the cheapest physical join, including the cost of producing both inputs.
+    ///
+    /// `HashJoin` builds its table from the left input and probes it with the right one,
+    /// `NestLoopJoin` re-executes the right input for every left row and
+    /// `IndexNestLoopJoin` only reads the right rows an index lookup finds for it.
+    /// `SortMergeJoin` is only used when forced, as it is costed with sorting both inputs.
+    pub(crate) fn cheapest(
+        has_equi_keys: bool,
+        force_nested_loop: bool,
+        force_merge_join: bool,
+        input: &JoinCostInput,
+    ) -> (PlanImpl, f64) {
+        let nested_loop_cost =
+            input.left_cost + input.left_rows * input.right_cost + input.output_rows;
+        let mut cheapest = (PlanImpl::NestLoopJoin, nested_loop_cost);
+        if force_nested_loop {
+            return cheapest;
+        }
+        if force_merge_join && has_equi_keys {
+            let sort_cost = |rows: f64| rows * rows.max(2.0).log2();
+            let merge_cost = input.left_cost
+                + input.right_cost
+                + sort_cost(input.left_rows)
+                + sort_cost(input.right_rows)
+                + input.output_rows;
+            return (PlanImpl::SortMergeJoin, merge_cost);
+        }
+        if has_equi_keys {
+            let hash_cost = input.left_cost
+                + input.right_cost
+                + input.left_rows * HASH_BUILD_WEIGHT
+                + input.right_rows
+                + input.output_rows;
+            if hash_cost <= cheapest.1 {
+                cheapest = (PlanImpl::HashJoin, hash_cost);
+            }
+        }
+        if input.right_index_probe {
+            let index_cost =
+                input.left_cost + (input.left_rows + input.output_rows) * INDEX_PROBE_WEIGHT;
+            if index_cost < cheapest.1 {
+                cheapest = (PlanImpl::IndexNestLoopJoin, index_cost);
+            }
+        }
+        cheapest
+    }
+}
+
 impl MatchPattern for JoinImplementation {
     fn pattern(&self) -> &Pattern {
         &JOIN_PATTERN
@@ -53,3 +120,80 @@ impl ImplementationRule for JoinImplementation {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn input(left_rows: f64, right_rows: f64) -> JoinCostInput {
+        JoinCostInput {
+            left_rows,
+            left_cost: left_rows,
+            right_rows,
+            right_cost: right_rows,
+            output_rows: left_rows.min(right_rows),
+            right_index_probe: false,
+        }
+    }
+
+    #[test]
+    fn cheapest_join_prefers_hash_join_for_large_inputs() {
+        let (plan_impl, _) =
+            JoinImplementation::cheapest(true, false, false, &input(1000.0, 1000.0));
+        assert_eq!(plan_impl, PlanImpl::HashJoin);
+    }
+
+    #[test]
+    fn cheapest_join_prefers_nested_loop_for_a_single_outer_row() {
+        let (plan_impl, _) = JoinImplementation::cheapest(true, false, false, &input(1.0, 1000.0));
+        assert_eq!(plan_impl, PlanImpl::NestLoopJoin);
+    }
+
+    #[test]
+    fn cheapest_join_respects_missing_keys_and_hints() {
+        let (plan_impl, _) =
+            JoinImplementation::cheapest(false, false, false, &input(1000.0, 1000.0));
+        assert_eq!(plan_impl, PlanImpl::NestLoopJoin);
+        let (plan_impl, _) =
+            JoinImplementation::cheapest(true, true, false, &input(1000.0, 1000.0));
+        assert_eq!(plan_impl, PlanImpl::NestLoopJoin);
+        let (plan_impl, _) =
+            JoinImplementation::cheapest(true, false, true, &input(1000.0, 1000.0));
+        assert_eq!(plan_impl, PlanImpl::SortMergeJoin);
+        let (plan_impl, _) =
+            JoinImplementation::cheapest(false, false, true, &input(1000.0, 1000.0));
+        assert_eq!(plan_impl, PlanImpl::NestLoopJoin);
+        let (plan_impl, _) = JoinImplementation::cheapest(true, true, true, &input(1000.0, 1000.0));
+        assert_eq!(plan_impl, PlanImpl::NestLoopJoin);
+    }
+
+    #[test]
+    fn cheapest_join_probes_an_index_for_few_outer_rows() {
+        let probed = JoinCostInput {
+            right_index_probe: true,
+            ..input(10.0, 100_000.0)
+        };
+        let (plan_impl, _) = JoinImplementation::cheapest(true, false, false, &probed);
+        assert_eq!(plan_impl, PlanImpl::IndexNestLoopJoin);
+        let (plan_impl, _) = JoinImplementation::cheapest(false, false, false, &probed);
+        assert_eq!(plan_impl, PlanImpl::IndexNestLoopJoin);
+        let (plan_impl, _) = JoinImplementation::cheapest(true, true, false, &probed);
+        assert_eq!(plan_impl, PlanImpl::NestLoopJoin);
+
+        let probed = JoinCostInput {
+            right_index_probe: true,
+            ..input(100_000.0, 100.0)
+        };
+        let (plan_impl, _) = JoinImplementation::cheapest(true, false, false, &probed);
+        assert_eq!(plan_impl, PlanImpl::HashJoin);
+    }
+
+    #[test]
+    fn hash_join_builds_from_the_smaller_input() {
+        let (_, small_build) =
+            JoinImplementation::cheapest(true, false, false, &input(10.0, 1000.0));
+        let (_, large_build) =
+            JoinImplementation::cheapest(true, false, false, &input(1000.0, 10.0));
+        assert!(small_build < large_build);
+    }
+}
@@ -17,6 +17,7 @@ pub(crate) mod dummy;
 pub(crate) mod filter;
 pub(crate) mod function_scan;
 pub(crate) mod join;
+pub(crate) mod lateral_apply;
 pub(crate) mod limit;
 pub(crate) mod mark_apply;
 pub(crate) mod projection;
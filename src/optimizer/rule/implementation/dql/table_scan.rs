@@ -13,7 +13,7 @@
 // limitations under the License.
 
 use crate::errors::DatabaseError;
-use crate::expression::range_detacher::Range;
+use crate::optimizer::core::cardinality::adjust_index_row_count;
 use crate::optimizer::core::pattern::{Pattern, PatternChildrenPredicate};
 use crate::optimizer::core::rule::{BestPhysicalOption, ImplementationRule, MatchPattern};
 use crate::optimizer::core::statistics_meta::StatisticMetaLoader;
@@ -159,28 +159,3 @@ impl ImplementationRule for IndexScanImplementation {
         }
     }
 }
-
-fn adjust_index_row_count(index_type: IndexType, range: &Range, row_count: usize) -> usize {
-    let row_count = unique_eq_row_count(index_type, range).unwrap_or(row_count);
-    if row_count == 0 && !matches!(range, Range::Dummy) {
-        1
-    } else {
-        row_count
-    }
-}
-
-fn unique_eq_row_count(index_type: IndexType, range: &Range) -> Option<usize> {
-    match range {
-        Range::Dummy => Some(0),
-        Range::Eq(value)
-            if !value.is_null()
-                && matches!(index_type, IndexType::PrimaryKey { .. } | IndexType::Unique) =>
-        {
-            Some(1)
-        }
-        Range::SortedRanges(ranges) => ranges.iter().try_fold(0usize, |count, range| {
-            unique_eq_row_count(index_type, range).map(|row_count| count + row_count)
-        }),
-        _ => None,
-    }
-}
@@ -42,6 +42,7 @@ use crate::optimizer::rule::implementation::dql::dummy::DummyImplementation;
 use crate::optimizer::rule::implementation::dql::filter::FilterImplementation;
 use crate::optimizer::rule::implementation::dql::function_scan::FunctionScanImplementation;
 use crate::optimizer::rule::implementation::dql::join::JoinImplementation;
+use crate::optimizer::rule::implementation::dql::lateral_apply::LateralApplyImplementation;
 use crate::optimizer::rule::implementation::dql::limit::LimitImplementation;
 use crate::optimizer::rule::implementation::dql::mark_apply::MarkApplyImplementation;
 use crate::optimizer::rule::implementation::dql::projection::ProjectionImplementation;
@@ -63,6 +64,7 @@ pub enum ImplementationRuleRootTag {
     Dummy,
     Filter,
     Join,
+    LateralApply,
     Limit,
     MarkApply,
     Project,
@@ -99,6 +101,7 @@ impl ImplementationRuleRootTag {
             Operator::Dummy => Some(Self::Dummy),
             Operator::Filter(_) => Some(Self::Filter),
             Operator::Join(_) => Some(Self::Join),
+            Operator::LateralApply(_) => Some(Self::LateralApply),
             Operator::Limit(_) => Some(Self::Limit),
             Operator::MarkApply(_) => Some(Self::MarkApply),
             Operator::Project(_) => Some(Self::Project),
@@ -126,14 +129,20 @@ impl ImplementationRuleRootTag {
             Operator::Window(_) => Some(Self::Window),
             Operator::ShowTable
             | Operator::ShowView
-            | Operator::Explain
+            | Operator::Explain(_)
             | Operator::Describe(_)
             | Operator::SetMembership(_)
             | Operator::Union(_)
+            | Operator::RecursiveCte(_)
+            | Operator::CteScan(_)
             | Operator::CreateIndex(_)
             | Operator::CreateView(_)
+            | Operator::CreateSequence(_)
             | Operator::DropView(_)
-            | Operator::DropIndex(_) => None,
+            | Operator::DropSequence(_)
+            | Operator::DropIndex(_)
+            | Operator::AddConstraint(_)
+            | Operator::DropConstraint(_) => None,
         }
     }
 }
@@ -146,6 +155,7 @@ pub enum ImplementationRuleImpl {
     Dummy,
     Filter,
     HashJoin,
+    LateralApply,
     Limit,
     MarkApply,
     Projection,
@@ -184,6 +194,7 @@ impl MatchPattern for ImplementationRuleImpl {
             ImplementationRuleImpl::Dummy => DummyImplementation.pattern(),
             ImplementationRuleImpl::Filter => FilterImplementation.pattern(),
             ImplementationRuleImpl::HashJoin => JoinImplementation.pattern(),
+            ImplementationRuleImpl::LateralApply => LateralApplyImplementation.pattern(),
             ImplementationRuleImpl::Limit => LimitImplementation.pattern(),
             ImplementationRuleImpl::MarkApply => MarkApplyImplementation.pattern(),
             ImplementationRuleImpl::Projection => ProjectionImplementation.pattern(),
@@ -223,6 +234,7 @@ impl ImplementationRuleImpl {
             ImplementationRuleImpl::Dummy => ImplementationRuleRootTag::Dummy,
             ImplementationRuleImpl::Filter => ImplementationRuleRootTag::Filter,
             ImplementationRuleImpl::HashJoin => ImplementationRuleRootTag::Join,
+            ImplementationRuleImpl::LateralApply => ImplementationRuleRootTag::LateralApply,
             ImplementationRuleImpl::Limit => ImplementationRuleRootTag::Limit,
             ImplementationRuleImpl::MarkApply => ImplementationRuleRootTag::MarkApply,
             ImplementationRuleImpl::Projection => ImplementationRuleRootTag::Project,
@@ -274,6 +286,7 @@ impl ImplementationRule for ImplementationRuleImpl {
             ImplementationRuleImpl::Dummy => update!(DummyImplementation),
             ImplementationRuleImpl::Filter => update!(FilterImplementation),
             ImplementationRuleImpl::HashJoin => update!(JoinImplementation),
+            ImplementationRuleImpl::LateralApply => update!(LateralApplyImplementation),
             ImplementationRuleImpl::Limit => update!(LimitImplementation),
             ImplementationRuleImpl::MarkApply => update!(MarkApplyImplementation),
             ImplementationRuleImpl::Projection => update!(ProjectionImplementation),
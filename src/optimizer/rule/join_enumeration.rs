@@ -0,0 +1,1079 @@
+// Copyright 2024 KipData/KiteSQL
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::catalog::ColumnRef;
+use crate::errors::DatabaseError;
+use crate::expression::visitor::ExprVisitor;
+use crate::expression::visitor_mut::ExprVisitorMut;
+use crate::expression::{BinaryOperator, ScalarExpression};
+use crate::optimizer::core::cardinality::{CardinalityEstimator, DEFAULT_FILTER_SELECTIVITY};
+use crate::optimizer::core::rule::NormalizationRule;
+use crate::optimizer::core::statistics_meta::StatisticMetaLoader;
+use crate::optimizer::rule::implementation::dql::join::{JoinCostInput, JoinImplementation};
+use crate::optimizer::rule::normalization::{
+    index_probe_column, probe_index_columns, reduce_filters, split_conjunctive_predicates,
+    PushPredicateIntoScan,
+};
+use crate::planner::operator::join::{JoinCondition, JoinOperator, JoinType};
+use crate::planner::operator::project::ProjectOperator;
+use crate::planner::operator::{Operator, PhysicalOption, PlanImpl, SortOption};
+use crate::planner::{Childrens, LogicalPlan, PlanArena};
+use crate::types::LogicalType;
+use std::collections::HashMap;
+
+/// Join graphs with at most this many relations are enumerated exhaustively,
+/// larger ones are ordered greedily.
+const DP_MAX_RELATIONS: usize = 10;
+/// Relations of a join graph are tracked in a `u64` bitset.
+const MAX_RELATIONS: usize = u64::BITS as usize;
+
+type RelationSet = u64;
+
+/// Cost-based join ordering.
+///
+/// Consecutive inner and cross joins, together with the filters sitting directly on top of
+/// them, are flattened into a join graph whose relations are the remaining subtrees. The
+/// cheapest join tree is searched with dynamic programming for small graphs and with greedy
+/// operator ordering beyond that, and every join gets the physical implementation it was
+/// costed with. Other joins keep their shape but still choose their implementation by cost.
+///
+/// Nothing changes unless every relation of a graph can be estimated from statistics.
+pub(crate) struct JoinEnumeration<'a, 'l> {
+    estimator: CardinalityEstimator<'a, 'l>,
+}
+
+impl<'a, 'l> JoinEnumeration<'a, 'l> {
+    pub(crate) fn new(loader: &'a StatisticMetaLoader<'l>) -> Self {
+        Self {
+            estimator: CardinalityEstimator::new(loader),
+        }
+    }
+
+    pub(crate) fn apply(
+        &self,
+        plan: &mut LogicalPlan,
+        arena: &mut PlanArena,
+    ) -> Result<(), DatabaseError> {
+        self.visit(plan, arena)?;
+        plan.reset_output_schema_cache_recursive();
+        Ok(())
+    }
+
+    fn visit(&self, plan: &mut LogicalPlan, arena: &mut PlanArena) -> Result<(), DatabaseError> {
+        if is_reorderable(plan) {
+            if let Some(order) = self.reorder(plan, arena)? {
+                order.restore(plan, arena)?;
+            }
+            return Ok(());
+        }
+        if let (Operator::Project(op), Childrens::Only(child)) =
+            (&mut plan.operator, plan.childrens.as_mut())
+        {
+            if is_reorderable(child) {
+                // a projection right above the cluster takes the reordered columns directly
+                // instead of stacking another projection that restores their order
+                if let Some(order) = self.reorder(child, arena)? {
+                    let mut permutation = PositionPermutation {
+                        positions: order.positions(),
+                    };
+                    for expr in op.exprs.iter_mut() {
+                        permutation.visit(expr)?;
+                    }
+                }
+                return Ok(());
+            }
+        }
+        match plan.childrens.as_mut() {
+            Childrens::Only(child) => self.visit(child, arena)?,
+            Childrens::Twins { left, right } => {
+                self.visit(left, arena)?;
+                self.visit(right, arena)?;
+            }
+            Childrens::None => (),
+        }
+        if matches!(plan.operator, Operator::Join(_)) {
+            self.choose_join_implementation(plan, arena)?;
+        }
+        Ok(())
+    }
+
+    fn choose_join_implementation(
+        &self,
+        plan: &mut LogicalPlan,
+        arena: &mut PlanArena,
+    ) -> Result<(), DatabaseError> {
+        let Some(output_rows) = self.estimator.plan_rows(plan, arena)? else {
+            return Ok(());
+        };
+        let (Operator::Join(op), Childrens::Twins { left, right }) =
+            (&plan.operator, plan.childrens.as_mut())
+        else {
+            return Ok(());
+        };
+        let (Some(left_rows), Some(right_rows)) = (
+            self.estimator.plan_rows(left, arena)?,
+            self.estimator.plan_rows(right, arena)?,
+        ) else {
+            return Ok(());
+        };
+        let left_len = left.output_schema(arena).len();
+        let has_equi_keys = op.join_type != JoinType::Cross
+            && matches!(&op.on, JoinCondition::On { on, .. } if !on.is_empty());
+        let (plan_impl, cost) = JoinImplementation::cheapest(
+            has_equi_keys,
+            op.force_nested_loop,
+            op.force_merge_join,
+            &JoinCostInput {
+                left_rows,
+                left_cost: left_rows,
+                right_rows,
+                right_cost: right_rows,
+                output_rows,
+                right_index_probe: index_probe_column(op, left_len, right, arena).is_some(),
+            },
+        );
+        plan.physical_option = Some(PhysicalOption::new(plan_impl, SortOption::None));
+        plan.estimated_cost = Some(cost.round() as usize);
+        Ok(())
+    }
+
+    /// Reorders the join cluster rooted at `plan`, returning the column order to restore when
+    /// the rebuilt cluster outputs its columns in a different order.
+    fn reorder(
+        &self,
+        plan: &mut LogicalPlan,
+        arena: &mut PlanArena,
+    ) -> Result<Option<ColumnOrder>, DatabaseError> {
+        let mut leaves = Vec::new();
+        let mut predicates = Vec::new();
+        let mut hints = JoinHints::default();
+        collect_cluster(plan, &mut leaves, &mut predicates, &mut hints)?;
+
+        for leaf in leaves.iter_mut() {
+            self.visit(leaf, arena)?;
+        }
+        let Some(cluster) = self.build_cluster(leaves, predicates, hints, arena)? else {
+            return Ok(None);
+        };
+        let Some(best) = cluster.graph.best_order() else {
+            return Ok(None);
+        };
+
+        let mut leaves = Vec::with_capacity(cluster.schemas.len());
+        take_leaves(plan.take(), &mut leaves);
+        let original = cluster.schemas.concat();
+        let (rebuilt, reordered) = cluster.rebuild(&best.tree, leaves, arena)?;
+        *plan = rebuilt;
+        Ok((original != reordered).then_some(ColumnOrder {
+            original,
+            reordered,
+        }))
+    }
+
+    fn build_cluster(
+        &self,
+        mut leaves: Vec<&mut LogicalPlan>,
+        predicates: Vec<ClusterExpr>,
+        hints: JoinHints,
+        arena: &mut PlanArena,
+    ) -> Result<Option<JoinCluster>, DatabaseError> {
+        if leaves.len() > MAX_RELATIONS {
+            return Ok(None);
+        }
+        let mut schemas = Vec::with_capacity(leaves.len());
+        let mut owners = HashMap::new();
+        let mut relations = Vec::with_capacity(leaves.len());
+        let mut probe_columns = Vec::with_capacity(leaves.len());
+        for (i, leaf) in leaves.iter_mut().enumerate() {
+            let schema = leaf.output_schema(arena).clone();
+            for column in schema.iter() {
+                if owners.insert(*column, i).is_some() {
+                    return Ok(None);
+                }
+            }
+            let Some(rows) = self.estimator.plan_rows(leaf, arena)? else {
+                return Ok(None);
+            };
+            schemas.push(schema);
+            relations.push(rows);
+            probe_columns.push(probe_index_columns(leaf, arena));
+        }
+
+        let mut cluster_predicates = Vec::with_capacity(predicates.len());
+        for predicate in predicates {
+            let Some(predicate) = ClusterPredicate::new(predicate, &owners, arena)? else {
+                return Ok(None);
+            };
+            if predicate.relations.count_ones() == 1 {
+                let i = predicate.relations.trailing_zeros() as usize;
+                relations[i] *= DEFAULT_FILTER_SELECTIVITY;
+                // the filter may become an index range of the scan, which a probe would replace
+                probe_columns[i].clear();
+            }
+            cluster_predicates.push(predicate);
+        }
+        let mut graph_predicates = Vec::new();
+        for predicate in cluster_predicates.iter() {
+            if predicate.relations.count_ones() < 2 {
+                continue;
+            }
+            let selectivity = match (&predicate.expr, predicate.sides) {
+                (ClusterExpr::Equi(left_expr, right_expr), Some((left, right))) => {
+                    CardinalityEstimator::equi_selectivity(
+                        self.side_distinct_values(left_expr, left, &leaves, arena)?,
+                        self.side_distinct_values(right_expr, right, &leaves, arena)?,
+                        Self::side_rows(left, &relations),
+                        Self::side_rows(right, &relations),
+                    )
+                }
+                _ => DEFAULT_FILTER_SELECTIVITY,
+            };
+            graph_predicates.push(GraphPredicate {
+                relations: predicate.relations,
+                sides: predicate.sides,
+                selectivity,
+                probes: probe_relations(&predicate.expr, &owners, &probe_columns, arena)?,
+            });
+        }
+
+        Ok(Some(JoinCluster {
+            schemas,
+            predicates: cluster_predicates,
+            hints,
+            graph: JoinGraph {
+                relations,
+                predicates: graph_predicates,
+                hints,
+            },
+        }))
+    }
+
+    fn side_distinct_values(
+        &self,
+        expr: &ScalarExpression,
+        side: RelationSet,
+        leaves: &[&mut LogicalPlan],
+        arena: &PlanArena,
+    ) -> Result<Option<f64>, DatabaseError> {
+        if side.count_ones() != 1 {
+            return Ok(None);
+        }
+        self.estimator
+            .distinct_values(leaves[side.trailing_zeros() as usize], expr, arena)
+    }
+
+    fn side_rows(side: RelationSet, relations: &[f64]) -> f64 {
+        relations_of(side).map(|i| relations[i]).fold(1.0, f64::max)
+    }
+}
+
+fn is_reorderable_join(plan: &LogicalPlan) -> bool {
+    matches!(
+        &plan.operator,
+        Operator::Join(op) if matches!(op.join_type, JoinType::Inner | JoinType::Cross)
+    )
+}
+
+fn is_reorderable(plan: &LogicalPlan) -> bool {
+    if is_reorderable_join(plan) {
+        return true;
+    }
+    matches!(&plan.operator, Operator::Filter(op) if !op.having)
+        && matches!(plan.childrens.as_ref(), Childrens::Only(child) if is_reorderable(child))
+}
+
+/// Join algorithm hints of a cluster, which apply to every join rebuilt from it.
+#[derive(Debug, Default, Clone, Copy)]
+struct JoinHints {
+    force_nested_loop: bool,
+    force_merge_join: bool,
+}
+
+enum ClusterExpr {
+    Equi(ScalarExpression, ScalarExpression),
+    Filter(ScalarExpression),
+}
+
+impl ClusterExpr {
+    fn into_filter(self) -> ScalarExpression {
+        match self {
+            ClusterExpr::Equi(left_expr, right_expr) => ScalarExpression::Binary {
+                op: BinaryOperator::Eq,
+                left_expr: Box::new(left_expr),
+                right_expr: Box::new(right_expr),
+                evaluator: None,
+                ty: LogicalType::Boolean,
+            },
+            ClusterExpr::Filter(expr) => expr,
+        }
+    }
+}
+
+/// Collects the relations and predicates of the join cluster rooted at `plan`, relations in
+/// the order their columns appear in the output of the cluster.
+fn collect_cluster<'p>(
+    plan: &'p mut LogicalPlan,
+    leaves: &mut Vec<&'p mut LogicalPlan>,
+    predicates: &mut Vec<ClusterExpr>,
+    hints: &mut JoinHints,
+) -> Result<(), DatabaseError> {
+    if !is_reorderable(plan) {
+        leaves.push(plan);
+        return Ok(());
+    }
+    match (&plan.operator, plan.childrens.as_mut()) {
+        (Operator::Filter(op), Childrens::Only(child)) => {
+            push_conjuncts(&op.predicate, predicates)?;
+            collect_cluster(child, leaves, predicates, hints)
+        }
+        (Operator::Join(op), Childrens::Twins { left, right }) => {
+            if let JoinCondition::On { on, filter } = &op.on {
+                predicates.extend(
+                    on.iter()
+                        .map(|(left, right)| ClusterExpr::Equi(left.clone(), right.clone())),
+                );
+                if let Some(filter) = filter {
+                    push_conjuncts(filter, predicates)?;
+                }
+            }
+            hints.force_nested_loop |= op.force_nested_loop;
+            hints.force_merge_join |= op.force_merge_join;
+            collect_cluster(left, leaves, predicates, hints)?;
+            collect_cluster(right, leaves, predicates, hints)
+        }
+        _ => unreachable!(),
+    }
+}
+
+fn push_conjuncts(
+    expr: &ScalarExpression,
+    predicates: &mut Vec<ClusterExpr>,
+) -> Result<(), DatabaseError> {
+    split_conjunctive_predicates(expr, &mut |expr| {
+        predicates.push(ClusterExpr::Filter(expr));
+        Ok(())
+    })
+}
+
+/// Takes the relations out of the cluster in the same order as [`collect_cluster`].
+fn take_leaves(plan: LogicalPlan, leaves: &mut Vec<Option<LogicalPlan>>) {
+    if !is_reorderable(&plan) {
+        leaves.push(Some(plan));
+        return;
+    }
+    match *plan.childrens {
+        Childrens::Only(child) => take_leaves(*child, leaves),
+        Childrens::Twins { left, right } => {
+            take_leaves(*left, leaves);
+            take_leaves(*right, leaves);
+        }
+        Childrens::None => unreachable!(),
+    }
+}
+
+struct RelationCollector<'a> {
+    owners: &'a HashMap<ColumnRef, usize>,
+    relations: RelationSet,
+    resolved: bool,
+}
+
+impl ExprVisitor<'_> for RelationCollector<'_> {
+    fn visit_column_ref(&mut self, column: &ColumnRef) -> Result<(), DatabaseError> {
+        match self.owners.get(column) {
+            Some(i) => self.relations |= 1 << i,
+            None => self.resolved = false,
+        }
+        Ok(())
+    }
+}
+
+fn collect_relations(
+    expr: &ScalarExpression,
+    owners: &HashMap<ColumnRef, usize>,
+) -> Result<Option<RelationSet>, DatabaseError> {
+    let mut collector = RelationCollector {
+        owners,
+        relations: 0,
+        resolved: true,
+    };
+    collector.visit(expr)?;
+    Ok(collector.resolved.then_some(collector.relations))
+}
+
+struct PositionBinder<'a> {
+    schema: &'a [ColumnRef],
+    missing: Option<ColumnRef>,
+}
+
+impl ExprVisitorMut<'_> for PositionBinder<'_> {
+    fn visit_column_ref(
+        &mut self,
+        column: &mut ColumnRef,
+        position: &mut usize,
+    ) -> Result<(), DatabaseError> {
+        match self.schema.iter().position(|candidate| candidate == column) {
+            Some(i) => *position = i,
+            None => self.missing = Some(*column),
+        }
+        Ok(())
+    }
+}
+
+struct PositionPermutation {
+    positions: Vec<usize>,
+}
+
+impl ExprVisitorMut<'_> for PositionPermutation {
+    fn visit_column_ref(
+        &mut self,
+        _column: &mut ColumnRef,
+        position: &mut usize,
+    ) -> Result<(), DatabaseError> {
+        *position = self.positions[*position];
+        Ok(())
+    }
+}
+
+fn bind_positions(
+    mut expr: ScalarExpression,
+    schema: &[ColumnRef],
+    arena: &PlanArena,
+) -> Result<ScalarExpression, DatabaseError> {
+    let mut binder = PositionBinder {
+        schema,
+        missing: None,
+    };
+    binder.visit(&mut expr)?;
+    if let Some(column) = binder.missing {
+        return Err(DatabaseError::column_not_found(
+            arena.column(column).name().to_string(),
+        ));
+    }
+    Ok(expr)
+}
+
+struct ClusterPredicate {
+    expr: ClusterExpr,
+    relations: RelationSet,
+    /// Relations referenced by each side of an equi-join key.
+    sides: Option<(RelationSet, RelationSet)>,
+}
+
+impl ClusterPredicate {
+    fn new(
+        expr: ClusterExpr,
+        owners: &HashMap<ColumnRef, usize>,
+        arena: &PlanArena,
+    ) -> Result<Option<Self>, DatabaseError> {
+        let expr = match expr {
+            ClusterExpr::Filter(ScalarExpression::Binary {
+                op: BinaryOperator::Eq,
+                left_expr,
+                right_expr,
+                ..
+            }) if left_expr.return_type(arena) == right_expr.return_type(arena) => {
+                ClusterExpr::Equi(*left_expr, *right_expr)
+            }
+            expr => expr,
+        };
+        let expr = match expr {
+            ClusterExpr::Equi(left_expr, right_expr) => {
+                let (Some(left), Some(right)) = (
+                    collect_relations(&left_expr, owners)?,
+                    collect_relations(&right_expr, owners)?,
+                ) else {
+                    return Ok(None);
+                };
+                if left != 0 && right != 0 && left & right == 0 {
+                    return Ok(Some(ClusterPredicate {
+                        expr: ClusterExpr::Equi(left_expr, right_expr),
+                        relations: left | right,
+                        sides: Some((left, right)),
+                    }));
+                }
+                ClusterExpr::Equi(left_expr, right_expr).into_filter()
+            }
+            ClusterExpr::Filter(expr) => expr,
+        };
+        let Some(relations) = collect_relations(&expr, owners)? else {
+            return Ok(None);
+        };
+        Ok(Some(ClusterPredicate {
+            expr: ClusterExpr::Filter(expr),
+            relations,
+            sides: None,
+        }))
+    }
+}
+
+/// Relations whose index an index nested-loop join can probe with `expr`: an equi key or a
+/// comparison between one of their indexed columns and an expression over other relations.
+fn probe_relations(
+    expr: &ClusterExpr,
+    owners: &HashMap<ColumnRef, usize>,
+    probe_columns: &[Vec<(ColumnRef, bool)>],
+    arena: &PlanArena,
+) -> Result<RelationSet, DatabaseError> {
+    let (sides, is_range) = match expr {
+        ClusterExpr::Equi(left_expr, right_expr) => ((left_expr, right_expr), false),
+        ClusterExpr::Filter(ScalarExpression::Binary {
+            op:
+                BinaryOperator::Gt | BinaryOperator::GtEq | BinaryOperator::Lt | BinaryOperator::LtEq,
+            left_expr,
+            right_expr,
+            ..
+        }) => ((left_expr.as_ref(), right_expr.as_ref()), true),
+        ClusterExpr::Filter(_) => return Ok(0),
+    };
+    let mut probes = 0;
+    for (probed, bound) in [sides, (sides.1, sides.0)] {
+        let ScalarExpression::ColumnRef { column, .. } = probed.unpack_alias_ref() else {
+            continue;
+        };
+        let Some(&i) = owners.get(column) else {
+            continue;
+        };
+        let is_indexed = probe_columns[i]
+            .iter()
+            .any(|(candidate, allows_range)| candidate == column && (*allows_range || !is_range));
+        if is_indexed
+            && collect_relations(bound, owners)?.is_some_and(|relations| relations & (1 << i) == 0)
+            && bound.return_type(arena).as_ref() == arena.column(*column).datatype()
+        {
+            probes |= 1 << i;
+        }
+    }
+    Ok(probes)
+}
+
+struct JoinCluster {
+    schemas: Vec<Vec<ColumnRef>>,
+    predicates: Vec<ClusterPredicate>,
+    hints: JoinHints,
+    graph: JoinGraph,
+}
+
+impl JoinCluster {
+    fn rebuild(
+        self,
+        tree: &JoinTree,
+        mut leaves: Vec<Option<LogicalPlan>>,
+        arena: &mut PlanArena,
+    ) -> Result<(LogicalPlan, Vec<ColumnRef>), DatabaseError> {
+        let JoinCluster {
+            schemas,
+            predicates,
+            hints,
+            ..
+        } = self;
+        let mut leaf_filters: Vec<Vec<ScalarExpression>> = vec![Vec::new(); leaves.len()];
+        let mut top_filters = Vec::new();
+        let mut join_predicates = Vec::new();
+        for predicate in predicates {
+            match predicate.relations.count_ones() {
+                0 => top_filters.push(predicate.expr.into_filter()),
+                1 => {
+                    let i = predicate.relations.trailing_zeros() as usize;
+                    leaf_filters[i].push(bind_positions(
+                        predicate.expr.into_filter(),
+                        &schemas[i],
+                        arena,
+                    )?);
+                }
+                _ => join_predicates.push(predicate),
+            }
+        }
+        for (leaf, filters) in leaves.iter_mut().zip(leaf_filters) {
+            if let Some(filter_op) = reduce_filters(filters, false) {
+                let child = leaf.take().expect("join relation taken twice");
+                let mut filter = LogicalPlan::new(
+                    Operator::Filter(filter_op),
+                    Childrens::Only(Box::new(child)),
+                );
+                // predicate pushdown has already run, so give the scan its index ranges here
+                PushPredicateIntoScan.apply(&mut filter, arena)?;
+                *leaf = Some(filter);
+            }
+        }
+
+        let mut builder = JoinTreeBuilder {
+            leaves,
+            schemas: &schemas,
+            predicates: join_predicates.into_iter().map(Some).collect(),
+            hints,
+            arena,
+        };
+        let (mut plan, _, schema) = builder.build(tree)?;
+        if let Some(mut filter_op) = reduce_filters(top_filters, false) {
+            filter_op.predicate = bind_positions(filter_op.predicate, &schema, builder.arena)?;
+            plan = LogicalPlan::new(Operator::Filter(filter_op), Childrens::Only(Box::new(plan)));
+        }
+
+        Ok((plan, schema))
+    }
+}
+
+/// The column order of a join cluster before and after it was reordered.
+struct ColumnOrder {
+    original: Vec<ColumnRef>,
+    reordered: Vec<ColumnRef>,
+}
+
+impl ColumnOrder {
+    /// The position in the reordered output of each column of the original output.
+    fn positions(&self) -> Vec<usize> {
+        self.original
+            .iter()
+            .map(|column| {
+                self.reordered
+                    .iter()
+                    .position(|candidate| candidate == column)
+                    .expect("join reordering keeps every column")
+            })
+            .collect()
+    }
+
+    /// Puts a projection on top of the reordered `plan` that outputs the original order.
+    fn restore(self, plan: &mut LogicalPlan, arena: &PlanArena) -> Result<(), DatabaseError> {
+        let exprs = self
+            .original
+            .into_iter()
+            .map(|column| {
+                bind_positions(
+                    ScalarExpression::column_expr(column, 0),
+                    &self.reordered,
+                    arena,
+                )
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        *plan = LogicalPlan::new(
+            Operator::Project(ProjectOperator { exprs }),
+            Childrens::Only(Box::new(plan.take())),
+        );
+        Ok(())
+    }
+}
+
+struct JoinTreeBuilder<'b, 'p> {
+    leaves: Vec<Option<LogicalPlan>>,
+    schemas: &'b [Vec<ColumnRef>],
+    predicates: Vec<Option<ClusterPredicate>>,
+    hints: JoinHints,
+    arena: &'b mut PlanArena<'p>,
+}
+
+impl JoinTreeBuilder<'_, '_> {
+    fn build(
+        &mut self,
+        tree: &JoinTree,
+    ) -> Result<(LogicalPlan, RelationSet, Vec<ColumnRef>), DatabaseError> {
+        let (left, right, plan_impl, cost) = match tree {
+            JoinTree::Leaf(i) => {
+                let plan = self.leaves[*i].take().expect("join relation taken twice");
+                return Ok((plan, 1 << i, self.schemas[*i].clone()));
+            }
+            JoinTree::Join {
+                left,
+                right,
+                plan_impl,
+                cost,
+            } => (left, right, plan_impl, cost),
+        };
+        let (left_plan, left_relations, left_schema) = self.build(left)?;
+        let (right_plan, right_relations, right_schema) = self.build(right)?;
+        let mut schema = left_schema;
+        let left_len = schema.len();
+        schema.extend(right_schema);
+
+        let mut on = Vec::new();
+        let mut filters = Vec::new();
+        for slot in self.predicates.iter_mut() {
+            if !slot.as_ref().is_some_and(|predicate| {
+                is_new_predicate(predicate.relations, left_relations, right_relations)
+            }) {
+                continue;
+            }
+            let Some(predicate) = slot.take() else {
+                continue;
+            };
+            match (predicate.expr, predicate.sides) {
+                (ClusterExpr::Equi(left_expr, right_expr), Some((left, right)))
+                    if is_subset(left, left_relations) && is_subset(right, right_relations) =>
+                {
+                    on.push((
+                        bind_positions(left_expr, &schema[..left_len], self.arena)?,
+                        bind_positions(right_expr, &schema[left_len..], self.arena)?,
+                    ));
+                }
+                (ClusterExpr::Equi(left_expr, right_expr), Some((left, right)))
+                    if is_subset(left, right_relations) && is_subset(right, left_relations) =>
+                {
+                    on.push((
+                        bind_positions(right_expr, &schema[..left_len], self.arena)?,
+                        bind_positions(left_expr, &schema[left_len..], self.arena)?,
+                    ));
+                }
+                (expr, _) => filters.push(bind_positions(expr.into_filter(), &schema, self.arena)?),
+            }
+        }
+
+        let filter = reduce_filters(filters, false).map(|filter_op| filter_op.predicate);
+        let (on, join_type) = if on.is_empty() && filter.is_none() {
+            (JoinCondition::None, JoinType::Cross)
+        } else {
+            (JoinCondition::On { on, filter }, JoinType::Inner)
+        };
+        let mut plan = JoinOperator::build(
+            left_plan,
+            right_plan,
+            on,
+            join_type,
+            self.hints.force_nested_loop,
+            self.hints.force_merge_join,
+        );
+        plan.physical_option = Some(PhysicalOption::new(plan_impl.clone(), SortOption::None));
+        plan.estimated_cost = Some(cost.round() as usize);
+
+        Ok((plan, left_relations | right_relations, schema))
+    }
+}
+
+fn is_subset(relations: RelationSet, of: RelationSet) -> bool {
+    relations & !of == 0
+}
+
+/// Whether a predicate over `relations` is first evaluable when `left` and `right` are joined.
+fn is_new_predicate(relations: RelationSet, left: RelationSet, right: RelationSet) -> bool {
+    is_subset(relations, left | right)
+        && !is_subset(relations, left)
+        && !is_subset(relations, right)
+}
+
+fn relations_of(relations: RelationSet) -> impl Iterator<Item = usize> {
+    (0..MAX_RELATIONS).filter(move |i| relations & (1 << i) != 0)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum JoinTree {
+    Leaf(usize),
+    Join {
+        left: Box<JoinTree>,
+        right: Box<JoinTree>,
+        plan_impl: PlanImpl,
+        cost: f64,
+    },
+}
+
+#[derive(Debug, Clone)]
+struct Candidate {
+    relations: RelationSet,
+    rows: f64,
+    cost: f64,
+    tree: JoinTree,
+}
+
+struct GraphPredicate {
+    relations: RelationSet,
+    sides: Option<(RelationSet, RelationSet)>,
+    selectivity: f64,
+    /// Relations an index nested-loop join can probe with this predicate when they are the
+    /// right input on their own.
+    probes: RelationSet,
+}
+
+/// Estimated relation sizes of a join cluster and the predicates connecting them.
+struct JoinGraph {
+    relations: Vec<f64>,
+    predicates: Vec<GraphPredicate>,
+    hints: JoinHints,
+}
+
+impl JoinGraph {
+    fn leaf(&self, i: usize) -> Candidate {
+        Candidate {
+            relations: 1 << i,
+            rows: self.relations[i],
+            cost: self.relations[i],
+            tree: JoinTree::Leaf(i),
+        }
+    }
+
+    /// Joins two disjoint candidates, unless no predicate connects them and cross products
+    /// are not allowed.
+    fn join(&self, left: &Candidate, right: &Candidate, allow_cross: bool) -> Option<Candidate> {
+        let mut rows = left.rows * right.rows;
+        let mut connected = false;
+        let mut has_equi_keys = false;
+        let mut right_index_probe = false;
+        for predicate in self.predicates.iter() {
+            if !is_new_predicate(predicate.relations, left.relations, right.relations) {
+                continue;
+            }
+            connected = true;
+            rows *= predicate.selectivity;
+            right_index_probe |=
+                right.relations.count_ones() == 1 && predicate.probes & right.relations != 0;
+            if let Some((l, r)) = predicate.sides {
+                has_equi_keys |= (is_subset(l, left.relations) && is_subset(r, right.relations))
+                    || (is_subset(l, right.relations) && is_subset(r, left.relations));
+            }
+        }
+        if !connected && !allow_cross {
+            return None;
+        }
+        let (plan_impl, cost) = JoinImplementation::cheapest(
+            has_equi_keys,
+            self.hints.force_nested_loop,
+            self.hints.force_merge_join,
+            &JoinCostInput {
+                left_rows: left.rows,
+                left_cost: left.cost,
+                right_rows: right.rows,
+                right_cost: right.cost,
+                output_rows: rows,
+                right_index_probe,
+            },
+        );
+
+        Some(Candidate {
+            relations: left.relations | right.relations,
+            rows,
+            cost,
+            tree: JoinTree::Join {
+                left: Box::new(left.tree.clone()),
+                right: Box::new(right.tree.clone()),
+                plan_impl,
+                cost,
+            },
+        })
+    }
+
+    fn best_order(&self) -> Option<Candidate> {
+        if self.relations.len() <= DP_MAX_RELATIONS {
+            self.dynamic_programming(false)
+                .or_else(|| self.dynamic_programming(true))
+        } else {
+            self.greedy()
+        }
+    }
+
+    /// Exhaustive search over join trees where one input of every join is a base relation.
+    fn dynamic_programming(&self, allow_cross: bool) -> Option<Candidate> {
+        let full: RelationSet = (1 << self.relations.len()) - 1;
+        let mut best: Vec<Option<Candidate>> = (0..=full).map(|_| None).collect();
+        for i in 0..self.relations.len() {
+            best[1 << i] = Some(self.leaf(i));
+        }
+        // every proper subset of a set is numerically smaller than the set itself
+        for set in 1..=full {
+            if set.count_ones() < 2 {
+                continue;
+            }
+            let mut best_of_set: Option<Candidate> = None;
+            for i in relations_of(set) {
+                let Some(rest) = &best[(set & !(1 << i)) as usize] else {
+                    continue;
+                };
+                let leaf = self.leaf(i);
+                let candidates = [
+                    self.join(rest, &leaf, allow_cross),
+                    self.join(&leaf, rest, allow_cross),
+                ];
+                for candidate in candidates.into_iter().flatten() {
+                    if best_of_set
+                        .as_ref()
+                        .is_none_or(|best| candidate.cost < best.cost)
+                    {
+                        best_of_set = Some(candidate);
+                    }
+                }
+            }
+            best[set as usize] = best_of_set;
+        }
+        best[full as usize].take()
+    }
+
+    /// Greedy operator ordering: repeatedly joins the pair of subtrees that is cheapest to
+    /// join, preferring pairs connected by a predicate.
+    fn greedy(&self) -> Option<Candidate> {
+        let mut trees = (0..self.relations.len())
+            .map(|i| self.leaf(i))
+            .collect::<Vec<_>>();
+        while trees.len() > 1 {
+            let mut best: Option<(usize, usize, Candidate)> = None;
+            for allow_cross in [false, true] {
+                for (i, left) in trees.iter().enumerate() {
+                    for (j, right) in trees.iter().enumerate() {
+                        if i == j {
+                            continue;
+                        }
+                        let Some(candidate) = self.join(left, right, allow_cross) else {
+                            continue;
+                        };
+                        if best
+                            .as_ref()
+                            .is_none_or(|(_, _, best)| candidate.cost < best.cost)
+                        {
+                            best = Some((i, j, candidate));
+                        }
+                    }
+                }
+                if best.is_some() {
+                    break;
+                }
+            }
+            let (i, j, candidate) = best?;
+            trees.swap_remove(i.max(j));
+            trees.swap_remove(i.min(j));
+            trees.push(candidate);
+        }
+        trees.pop()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn graph(relations: &[f64], edges: &[(usize, usize, f64)]) -> JoinGraph {
+        JoinGraph {
+            relations: relations.to_vec(),
+            predicates: edges
+                .iter()
+                .map(|(left, right, selectivity)| GraphPredicate {
+                    relations: (1 << left) | (1 << right),
+                    sides: Some((1 << left, 1 << right)),
+                    selectivity: *selectivity,
+                    probes: 0,
+                })
+                .collect(),
+            hints: JoinHints::default(),
+        }
+    }
+
+    fn leaves(tree: &JoinTree, order: &mut Vec<usize>) {
+        match tree {
+            JoinTree::Leaf(i) => order.push(*i),
+            JoinTree::Join { left, right, .. } => {
+                leaves(left, order);
+                leaves(right, order);
+            }
+        }
+    }
+
+    fn first_join(tree: &JoinTree) -> (usize, usize) {
+        match tree {
+            JoinTree::Join { left, right, .. } => match (left.as_ref(), right.as_ref()) {
+                (JoinTree::Leaf(l), JoinTree::Leaf(r)) => (*l.min(r), *l.max(r)),
+                (JoinTree::Leaf(_), inner) | (inner, _) => first_join(inner),
+            },
+            JoinTree::Leaf(_) => unreachable!(),
+        }
+    }
+
+    /// fact(0) joins three dimensions; dimension 3 is filtered down to a handful of rows.
+    fn star() -> JoinGraph {
+        graph(
+            &[100_000.0, 1_000.0, 1_000.0, 5.0],
+            &[
+                (0, 1, 1.0 / 1_000.0),
+                (0, 2, 1.0 / 1_000.0),
+                (0, 3, 1.0 / 1_000.0),
+            ],
+        )
+    }
+
+    #[test]
+    fn dynamic_programming_joins_the_most_selective_relation_first() {
+        let best = star().best_order().unwrap();
+        assert_eq!(first_join(&best.tree), (0, 3));
+    }
+
+    #[test]
+    fn greedy_matches_dynamic_programming_on_a_star() {
+        let graph = star();
+        let dp = graph.dynamic_programming(false).unwrap();
+        let greedy = graph.greedy().unwrap();
+        assert_eq!(first_join(&greedy.tree), first_join(&dp.tree));
+        assert!(greedy.cost >= dp.cost);
+    }
+
+    #[test]
+    fn enumeration_avoids_cross_products() {
+        // 0 - 1 - 2 chain written as (0, 2) first would be a cross product
+        let graph = graph(
+            &[10.0, 10_000.0, 10.0],
+            &[(0, 1, 1.0 / 10_000.0), (1, 2, 1.0 / 10_000.0)],
+        );
+        let best = graph.best_order().unwrap();
+        assert_ne!(first_join(&best.tree), (0, 2));
+    }
+
+    #[test]
+    fn disconnected_graphs_fall_back_to_cross_products() {
+        let graph = graph(&[10.0, 20.0, 30.0], &[(0, 1, 0.1)]);
+        let best = graph.best_order().unwrap();
+        let mut order = Vec::new();
+        leaves(&best.tree, &mut order);
+        order.sort();
+        assert_eq!(order, vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn hash_join_builds_from_the_smaller_relation() {
+        let graph = graph(&[10_000.0, 10.0], &[(0, 1, 1.0 / 10_000.0)]);
+        let best = graph.best_order().unwrap();
+        let JoinTree::Join {
+            left, plan_impl, ..
+        } = best.tree
+        else {
+            unreachable!()
+        };
+        assert_eq!(*left, JoinTree::Leaf(1));
+        assert_eq!(plan_impl, PlanImpl::HashJoin);
+    }
+
+    #[test]
+    fn indexed_relations_are_probed_by_few_outer_rows() {
+        let mut graph = graph(&[100_000.0, 10.0], &[(0, 1, 1.0 / 100_000.0)]);
+        graph.predicates[0].probes = 1 << 0;
+        let best = graph.best_order().unwrap();
+        let JoinTree::Join {
+            left,
+            right,
+            plan_impl,
+            ..
+        } = best.tree
+        else {
+            unreachable!()
+        };
+        assert_eq!((*left, *right), (JoinTree::Leaf(1), JoinTree::Leaf(0)));
+        assert_eq!(plan_impl, PlanImpl::IndexNestLoopJoin);
+    }
+
+    #[test]
+    fn large_graphs_are_ordered_greedily() {
+        let relations = (0..DP_MAX_RELATIONS + 2)
+            .map(|i| 10.0 * (i + 1) as f64)
+            .collect::<Vec<_>>();
+        let edges = (1..relations.len())
+            .map(|i| (i - 1, i, 0.01))
+            .collect::<Vec<_>>();
+        let best = graph(&relations, &edges).best_order().unwrap();
+        let mut order = Vec::new();
+        leaves(&best.tree, &mut order);
+        order.sort();
+        assert_eq!(order, (0..relations.len()).collect::<Vec<_>>());
+    }
+}
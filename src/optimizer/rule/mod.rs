@@ -13,4 +13,5 @@
 // limitations under the License.
 
 pub(crate) mod implementation;
+pub(crate) mod join_enumeration;
 pub(crate) mod normalization;
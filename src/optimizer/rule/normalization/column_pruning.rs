@@ -188,6 +188,29 @@ impl ColumnPruning {
                 Ok(())
             }
 
+            fn visit_recursive_cte(
+                &mut self,
+                op: &'a crate::planner::operator::recursive_cte::RecursiveCteOperator,
+            ) -> Result<(), DatabaseError> {
+                self.referenced_columns.extend(
+                    op.left_schema_ref
+                        .iter()
+                        .chain(&op._right_schema_ref)
+                        .copied(),
+                    self.arena,
+                );
+                Ok(())
+            }
+
+            fn visit_cte_scan(
+                &mut self,
+                op: &'a crate::planner::operator::cte_scan::CteScanOperator,
+            ) -> Result<(), DatabaseError> {
+                self.referenced_columns
+                    .extend(op.schema_ref.iter().copied(), self.arena);
+                Ok(())
+            }
+
             fn visit_delete(
                 &mut self,
                 op: &'a crate::planner::operator::delete::DeleteOperator,
@@ -383,6 +406,8 @@ impl ColumnPruning {
                             distinct: false,
                             kind: AggKind::Count,
                             args: vec![ScalarExpression::Constant(value)],
+                            filter: None,
+                            order_by: Vec::new(),
                             ty: LogicalType::Integer,
                         });
                         changed = true;
@@ -496,24 +521,45 @@ impl ColumnPruning {
             | Operator::Limit(_)
             | Operator::ScalarApply(_)
             | Operator::MarkApply(_)
+            | Operator::LateralApply(_)
             | Operator::ScalarSubquery(_)
             | Operator::Join(_)
             | Operator::Filter(_)
             | Operator::Union(_)
+            | Operator::RecursiveCte(_)
             | Operator::SetMembership(_)
             | Operator::TopK(_)
             | Operator::Window(_) => {
-                if matches!(operator, Operator::ScalarApply(_) | Operator::MarkApply(_)) {
+                if matches!(
+                    operator,
+                    Operator::ScalarApply(_) | Operator::MarkApply(_) | Operator::LateralApply(_)
+                ) {
                     let mut child_required = required_columns;
                     Self::extend_operator_referenced_columns(operator, &mut child_required, arena)?;
-                    changed |= Self::apply_twins(
-                        child_required,
-                        true,
-                        childrens,
-                        outcome,
-                        output_start,
-                        arena,
-                    )?;
+                    let is_parameterized = match operator {
+                        Operator::ScalarApply(op) => !op.parameters.is_empty(),
+                        Operator::MarkApply(op) => !op.parameters.is_empty(),
+                        Operator::LateralApply(op) => !op.parameters.is_empty(),
+                        _ => false,
+                    };
+                    if is_parameterized {
+                        // The right input still refers to left columns by their outer positions,
+                        // which pruning inside it would shift
+                        let Childrens::Twins { left, .. } = childrens else {
+                            unreachable!("apply operators have two children")
+                        };
+                        Self::_apply_appending(child_required, true, left, outcome, arena)?;
+                        changed |= outcome.changed;
+                    } else {
+                        changed |= Self::apply_twins(
+                            child_required,
+                            true,
+                            childrens,
+                            outcome,
+                            output_start,
+                            arena,
+                        )?;
+                    }
                     outcome.removed_positions.truncate(output_start);
                 } else if matches!(operator, Operator::Join(_)) {
                     let (old_left_outputs_len, left_removed_start, right_removed_start) = {
@@ -623,7 +669,10 @@ impl ColumnPruning {
                     } else {
                         outcome.removed_positions.truncate(output_start);
                     }
-                } else if matches!(operator, Operator::Union(_) | Operator::SetMembership(_)) {
+                } else if matches!(
+                    operator,
+                    Operator::Union(_) | Operator::RecursiveCte(_) | Operator::SetMembership(_)
+                ) {
                     let mut child_required = required_columns;
                     Self::extend_operator_referenced_columns(operator, &mut child_required, arena)?;
                     changed |= Self::apply_twins(
@@ -663,10 +712,13 @@ impl ColumnPruning {
                 }
             }
             // Last Operator
-            Operator::Dummy | Operator::Values(_) | Operator::FunctionScan(_) => {
+            Operator::Dummy
+            | Operator::Values(_)
+            | Operator::FunctionScan(_)
+            | Operator::CteScan(_) => {
                 outcome.removed_positions.truncate(output_start);
             }
-            Operator::Explain => {
+            Operator::Explain(_) => {
                 let child_start = outcome.removed_positions.len();
                 let child_changed = Self::apply_only_child(
                     required_columns,
@@ -721,8 +773,10 @@ impl ColumnPruning {
             Operator::CreateTable(_)
             | Operator::CreateIndex(_)
             | Operator::CreateView(_)
+            | Operator::CreateSequence(_)
             | Operator::DropTable(_)
             | Operator::DropView(_)
+            | Operator::DropSequence(_)
             | Operator::DropIndex(_)
             | Operator::Truncate(_)
             | Operator::ShowTable
@@ -730,6 +784,8 @@ impl ColumnPruning {
             | Operator::AddColumn(_)
             | Operator::ChangeColumn(_)
             | Operator::DropColumn(_)
+            | Operator::AddConstraint(_)
+            | Operator::DropConstraint(_)
             | Operator::Describe(_) => {
                 outcome.removed_positions.truncate(output_start);
             }
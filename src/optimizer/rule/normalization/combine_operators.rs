@@ -15,7 +15,7 @@
 use crate::errors::DatabaseError;
 use crate::expression::{AliasType, BinaryOperator, ScalarExpression};
 use crate::optimizer::core::rule::NormalizationRule;
-use crate::optimizer::plan_utils::{only_child_mut, replace_with_only_child};
+use crate::optimizer::plan_utils::{only_child, only_child_mut, replace_with_only_child};
 use crate::optimizer::rule::normalization::strip_alias;
 use crate::planner::operator::filter::FilterOperator;
 use crate::planner::operator::project::ProjectOperator;
@@ -31,6 +31,14 @@ fn is_passthrough_project(op: &ProjectOperator) -> bool {
         .all(|expr| matches!(strip_alias(expr), ScalarExpression::ColumnRef { .. }))
 }
 
+// A passthrough project that keeps every column at its input position
+fn is_identity_project(op: &ProjectOperator) -> bool {
+    op.exprs
+        .iter()
+        .enumerate()
+        .all(|(position, expr)| passthrough_source_position(expr) == Some(position))
+}
+
 fn passthrough_source_position(expr: &ScalarExpression) -> Option<usize> {
     match strip_alias(expr) {
         ScalarExpression::ColumnRef { position, .. } => Some(*position),
@@ -144,6 +152,18 @@ impl NormalizationRule for CombineFilter {
         plan: &mut LogicalPlan,
         _: &mut crate::planner::PlanArena,
     ) -> Result<bool, DatabaseError> {
+        // Only identity projects may sit between the filters, as the parent predicate is
+        // moved below them unchanged
+        let mut cursor = only_child(plan);
+        loop {
+            match cursor.map(|child| &child.operator) {
+                Some(Operator::Filter(_)) => break,
+                Some(Operator::Project(op)) if is_identity_project(op) => {
+                    cursor = cursor.and_then(only_child);
+                }
+                _ => return Ok(false),
+            }
+        }
         let parent_filter = match mem::replace(&mut plan.operator, Operator::Dummy) {
             Operator::Filter(op) => op,
             operator => {
@@ -184,7 +204,7 @@ impl NormalizationRule for CombineFilter {
 
                     return Ok(replace_with_only_child(plan));
                 }
-                Operator::Project(project_op) if is_passthrough_project(project_op) => {
+                Operator::Project(project_op) if is_identity_project(project_op) => {
                     if replace_with_only_child(cursor) {
                         continue;
                     }
@@ -227,6 +247,10 @@ impl NormalizationRule for CollapseGroupByAgg {
             let Operator::Aggregate(child_op) = &child.operator else {
                 return Ok(false);
             };
+            // Grouping sets may output the same keys once per set
+            if !op.grouping_sets.is_empty() || !child_op.grouping_sets.is_empty() {
+                return Ok(false);
+            }
             groupby_exprs_match(&op.groupby_exprs, &child_op.groupby_exprs, arena)
         };
 
@@ -248,10 +272,11 @@ mod tests {
     use crate::optimizer::heuristic::batch::HepBatchStrategy;
     use crate::optimizer::heuristic::optimizer::HepOptimizerPipeline;
     use crate::optimizer::rule::normalization::combine_operators::{
-        CollapseGroupByAgg, CollapseProject,
+        CollapseGroupByAgg, CollapseProject, CombineFilter,
     };
     use crate::optimizer::rule::normalization::NormalizationRuleImpl;
     use crate::planner::operator::aggregate::AggregateOperator;
+    use crate::planner::operator::filter::FilterOperator;
     use crate::planner::operator::project::ProjectOperator;
     use crate::planner::operator::Operator;
     use crate::planner::{Childrens, LogicalPlan, PlanArena};
@@ -400,6 +425,30 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_combine_filter_keeps_pruning_project() -> Result<(), DatabaseError> {
+        let table_arena = crate::planner::TableArenaCell::default();
+        let mut arena = PlanArena::new(&table_arena);
+        let project = LogicalPlan::new(
+            Operator::Project(ProjectOperator {
+                exprs: vec![
+                    column_expr(&mut arena, "c1", 0),
+                    column_expr(&mut arena, "c3", 2),
+                ],
+            }),
+            Childrens::Only(Box::new(LogicalPlan::new(Operator::Dummy, Childrens::None))),
+        );
+        let mut plan = FilterOperator::build(column_expr(&mut arena, "c3", 1), project, false);
+
+        assert!(!CombineFilter.apply(&mut plan, &mut arena)?);
+        assert!(matches!(plan.operator, Operator::Filter(_)));
+        assert!(matches!(
+            plan.childrens.pop_only().operator,
+            Operator::Project(_)
+        ));
+        Ok(())
+    }
+
     #[test]
     fn test_collapse_group_by_agg() -> Result<(), DatabaseError> {
         let table_state = build_t1_table()?;
@@ -437,11 +486,12 @@ mod tests {
             LogicalPlan::new(Operator::Dummy, Childrens::None),
             vec![],
             vec![column_expr(&mut arena, "c2", 1)],
+            Vec::new(),
             false,
             false,
         );
         let expr = column_expr(&mut arena, "c2", 0);
-        let mut plan = AggregateOperator::build(child, vec![], vec![expr], true, false);
+        let mut plan = AggregateOperator::build(child, vec![], vec![expr], Vec::new(), true, false);
 
         assert!(CollapseGroupByAgg.apply(&mut plan, &mut arena)?);
         let Operator::Aggregate(op) = &plan.operator else {
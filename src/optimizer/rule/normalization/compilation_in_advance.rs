@@ -40,8 +40,10 @@ impl EvaluatorBind {
                     plan.operator,
                     Operator::ScalarApply(_)
                         | Operator::MarkApply(_)
+                        | Operator::LateralApply(_)
                         | Operator::Join(_)
                         | Operator::Union(_)
+                        | Operator::RecursiveCte(_)
                         | Operator::SetMembership(_)
                 ) {
                     Self::_apply(right, arena)?;
@@ -145,6 +147,8 @@ mod tests {
             Operator::Update(UpdateOperator {
                 table_name: "t1".into(),
                 value_exprs: vec![(column, expr())],
+                returning: vec![],
+                checks: vec![("t1_check".to_string(), expr())],
             }),
         ];
 
@@ -166,7 +170,10 @@ mod tests {
                 Operator::Sort(op) => assert!(is_bound(&op.sort_fields[0].expr)),
                 Operator::TopK(op) => assert!(is_bound(&op.sort_fields[0].expr)),
                 Operator::MarkApply(op) => assert!(is_bound(&op.predicates()[0])),
-                Operator::Update(op) => assert!(is_bound(&op.value_exprs[0].1)),
+                Operator::Update(op) => {
+                    assert!(is_bound(&op.value_exprs[0].1));
+                    assert!(is_bound(&op.checks[0].1));
+                }
                 Operator::FunctionScan(op) => assert!(is_bound(&op.table_function.args[0])),
                 _ => unreachable!(),
             }
@@ -205,6 +212,7 @@ mod tests {
             Operator::Join(JoinOperator {
                 join_type: JoinType::Inner,
                 force_nested_loop: false,
+                force_merge_join: false,
                 on: JoinCondition::On {
                     on: vec![(expr(), expr())],
                     filter: Some(expr()),
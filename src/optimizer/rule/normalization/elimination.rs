@@ -15,7 +15,10 @@
 use crate::errors::DatabaseError;
 use crate::expression::ScalarExpression;
 use crate::optimizer::core::rule::NormalizationRule;
-use crate::optimizer::plan_utils::{only_child_mut, replace_with_only_child, wrap_child_with};
+use crate::optimizer::plan_utils::{
+    child_mut, only_child_mut, replace_with_only_child, wrap_child_with,
+};
+use crate::planner::operator::join::{JoinCondition, JoinType};
 use crate::planner::operator::limit::LimitOperator;
 use crate::planner::operator::sort::{SortField, SortOperator};
 use crate::planner::operator::table_scan::TableScanOperator;
@@ -253,7 +256,8 @@ impl NormalizationRule for UseStreamAggregate {
         let Operator::Aggregate(op) = &plan.operator else {
             return Ok(false);
         };
-        if op.groupby_exprs.is_empty() {
+        // Only one of several grouping sets could be contiguous in any input order
+        if op.groupby_exprs.is_empty() || !op.grouping_sets.is_empty() {
             return Ok(false);
         }
         if !matches!(
@@ -294,6 +298,28 @@ impl NormalizationRule for ForceSpillAggregate {
         _: &mut crate::planner::PlanArena,
     ) -> Result<bool, DatabaseError> {
         let (implementation, sort_fields) = match (&plan.operator, &plan.physical_option) {
+            (
+                Operator::Aggregate(op),
+                Some(PhysicalOption {
+                    plan: implementation,
+                    ..
+                }),
+            ) if op.force_spill
+                && !op.grouping_sets.is_empty()
+                && !matches!(implementation, PlanImpl::SortGroupingSets) =>
+            {
+                if !cfg!(feature = "spill") {
+                    return Err(DatabaseError::UnsupportedStmt(
+                        "FORCE_AGG_SPILL requires the `spill` feature".to_string(),
+                    ));
+                }
+                // The executor sorts its rows once expanded to every grouping set
+                plan.physical_option = Some(PhysicalOption::new(
+                    PlanImpl::SortGroupingSets,
+                    SortOption::None,
+                ));
+                return Ok(true);
+            }
             (
                 Operator::Aggregate(op),
                 Some(PhysicalOption {
@@ -331,6 +357,98 @@ impl NormalizationRule for ForceSpillAggregate {
     }
 }
 
+/// Uses a sort-merge join for an equi join whose inputs are both ordered on the join keys, and
+/// sorts the unordered inputs of a forced one.
+pub struct UseSortMergeJoin;
+
+impl NormalizationRule for UseSortMergeJoin {
+    fn apply(
+        &self,
+        plan: &mut LogicalPlan,
+        arena: &mut crate::planner::PlanArena,
+    ) -> Result<bool, DatabaseError> {
+        let (
+            Operator::Join(op),
+            Some(PhysicalOption {
+                plan: plan_impl @ (PlanImpl::HashJoin | PlanImpl::SortMergeJoin),
+                ..
+            }),
+        ) = (&plan.operator, &plan.physical_option)
+        else {
+            return Ok(false);
+        };
+        let JoinCondition::On { on, .. } = &op.on else {
+            return Ok(false);
+        };
+        let is_forced = *plan_impl == PlanImpl::SortMergeJoin;
+        if on.is_empty() || op.join_type == JoinType::Cross {
+            return Ok(false);
+        }
+        // keys are compared as they are, so both sides must share their types
+        if on
+            .iter()
+            .any(|(left, right)| left.return_type(arena) != right.return_type(arena))
+        {
+            if is_forced {
+                plan.physical_option =
+                    Some(PhysicalOption::new(PlanImpl::HashJoin, SortOption::None));
+                return Ok(true);
+            }
+            return Ok(false);
+        }
+        let (left_fields, right_fields): (Vec<_>, Vec<_>) = on
+            .iter()
+            .map(|(left, right)| {
+                (
+                    SortField::new(left.clone(), true, false),
+                    SortField::new(right.clone(), true, false),
+                )
+            })
+            .unzip();
+        let sort_option = if matches!(op.join_type, JoinType::Inner | JoinType::LeftOuter) {
+            SortOption::OrderBy {
+                fields: left_fields.clone(),
+                ignore_prefix_len: 0,
+            }
+        } else {
+            SortOption::None
+        };
+
+        let mut changed = false;
+        for (child_idx, sort_fields) in [left_fields, right_fields].into_iter().enumerate() {
+            let Some(child) = child_mut(plan, child_idx) else {
+                return Ok(false);
+            };
+            if ensure_order(child, &sort_fields, arena) {
+                continue;
+            }
+            if !is_forced {
+                return Ok(false);
+            }
+            let sort_option = SortOption::OrderBy {
+                fields: sort_fields.clone(),
+                ignore_prefix_len: 0,
+            };
+            if !wrap_child_with(
+                plan,
+                child_idx,
+                Operator::Sort(SortOperator { sort_fields }),
+            ) {
+                return Ok(false);
+            }
+            let sort = child_mut(plan, child_idx).expect("join child was wrapped with sort");
+            sort.physical_option = Some(PhysicalOption::new(PlanImpl::Sort, sort_option));
+            changed = true;
+        }
+        if !is_forced {
+            changed = true;
+        }
+
+        plan.physical_option = Some(PhysicalOption::new(PlanImpl::SortMergeJoin, sort_option));
+        Ok(changed)
+    }
+}
+
 pub(crate) fn apply_annotated_post_rules(
     plan: &mut LogicalPlan,
     arena: &mut crate::planner::PlanArena,
@@ -350,6 +468,9 @@ pub(crate) fn apply_annotated_post_rules(
     if ForceSpillAggregate.apply(plan, arena)? {
         changed = true;
     }
+    if UseSortMergeJoin.apply(plan, arena)? {
+        changed = true;
+    }
 
     Ok(changed)
 }
@@ -444,7 +565,8 @@ pub(crate) fn covers<T>(
 #[cfg(all(test, not(target_arch = "wasm32")))]
 mod tests {
     use super::{
-        EliminateIndexFilter, EliminateRedundantSort, ForceSpillAggregate, UseStreamAggregate,
+        EliminateIndexFilter, EliminateRedundantSort, ForceSpillAggregate, UseSortMergeJoin,
+        UseStreamAggregate,
     };
     use crate::catalog::{ColumnCatalog, TableName};
     use crate::errors::DatabaseError;
@@ -453,6 +575,7 @@ mod tests {
     use crate::optimizer::core::rule::NormalizationRule;
     use crate::planner::operator::aggregate::AggregateOperator;
     use crate::planner::operator::filter::FilterOperator;
+    use crate::planner::operator::join::{JoinCondition, JoinOperator, JoinType};
     use crate::planner::operator::sort::{SortField, SortOperator};
     use crate::planner::operator::table_scan::TableScanOperator;
     use crate::planner::operator::top_k::TopKOperator;
@@ -530,6 +653,7 @@ mod tests {
             ty: IndexType::PrimaryKey {
                 is_multiple: len > 1,
             },
+            json_path: vec![],
         });
         let index_info = IndexInfo {
             meta,
@@ -611,6 +735,7 @@ mod tests {
                 value_ty: LogicalType::Integer,
                 name: "idx".to_string(),
                 ty: IndexType::PrimaryKey { is_multiple: false },
+                json_path: vec![],
             }),
             sort_option: sort_option.clone(),
             lookup: None,
@@ -635,6 +760,7 @@ mod tests {
         let plan = LogicalPlan::new(
             Operator::Aggregate(AggregateOperator {
                 groupby_exprs: vec![ScalarExpression::column_expr(c1, 0)],
+                grouping_sets: Vec::new(),
                 agg_calls: vec![],
                 is_distinct: true,
                 force_spill: false,
@@ -1006,6 +1132,67 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn sort_merge_join_reuses_ordered_inputs_and_sorts_forced_ones() -> Result<(), DatabaseError> {
+        let table_arena = crate::planner::TableArenaCell::default();
+        let mut arena = crate::planner::PlanArena::new(&table_arena);
+        let left_field = make_sort_field_with_position(&mut arena, "a", 0);
+        let right_field = make_sort_field_with_position(&mut arena, "b", 0);
+        let ordered_leaf = |arena: &mut crate::planner::PlanArena, field: &SortField| {
+            let (index_info, sort_option) = build_index_info(arena, vec![field.clone()], 0);
+            let mut leaf = LogicalPlan::new(Operator::Dummy, Childrens::None);
+            leaf.physical_option = Some(PhysicalOption::new(
+                PlanImpl::IndexScan(Box::new(index_info)),
+                sort_option,
+            ));
+            leaf
+        };
+        let mut unordered_leaf = LogicalPlan::new(Operator::Dummy, Childrens::None);
+        unordered_leaf.physical_option =
+            Some(PhysicalOption::new(PlanImpl::SeqScan, SortOption::None));
+        let build_join = |left: LogicalPlan, right: LogicalPlan, plan_impl: PlanImpl| {
+            let mut plan = JoinOperator::build(
+                left,
+                right,
+                JoinCondition::On {
+                    on: vec![(left_field.expr.clone(), right_field.expr.clone())],
+                    filter: None,
+                },
+                JoinType::Full,
+                false,
+                false,
+            );
+            plan.physical_option = Some(PhysicalOption::new(plan_impl, SortOption::None));
+            plan
+        };
+        let plan_impl = |plan: &LogicalPlan| plan.physical_option.as_ref().unwrap().plan.clone();
+
+        let left = ordered_leaf(&mut arena, &left_field);
+        let mut plan = build_join(left, unordered_leaf.clone(), PlanImpl::HashJoin);
+        assert!(!UseSortMergeJoin.apply(&mut plan, &mut arena)?);
+        assert_eq!(plan_impl(&plan), PlanImpl::HashJoin);
+
+        let left = ordered_leaf(&mut arena, &left_field);
+        let right = ordered_leaf(&mut arena, &right_field);
+        let mut plan = build_join(left, right, PlanImpl::HashJoin);
+        assert!(UseSortMergeJoin.apply(&mut plan, &mut arena)?);
+        assert_eq!(plan_impl(&plan), PlanImpl::SortMergeJoin);
+
+        let left = ordered_leaf(&mut arena, &left_field);
+        let mut plan = build_join(left, unordered_leaf, PlanImpl::SortMergeJoin);
+        assert!(UseSortMergeJoin.apply(&mut plan, &mut arena)?);
+        assert!(!UseSortMergeJoin.apply(&mut plan, &mut arena)?);
+        let Childrens::Twins { left, right } = plan.childrens.as_ref() else {
+            unreachable!()
+        };
+        assert!(matches!(left.operator, Operator::Dummy));
+        assert!(matches!(
+            &right.operator,
+            Operator::Sort(SortOperator { sort_fields }) if sort_fields == &vec![right_field]
+        ));
+        Ok(())
+    }
+
     #[test]
     fn keep_sort_when_order_not_covered() -> Result<(), DatabaseError> {
         let table_arena = crate::planner::TableArenaCell::default();
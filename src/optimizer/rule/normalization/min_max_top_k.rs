@@ -33,14 +33,17 @@ impl NormalizationRule for MinMaxToTopK {
         let Operator::Aggregate(op) = &plan.operator else {
             return Ok(false);
         };
-        if !op.groupby_exprs.is_empty() || op.agg_calls.len() != 1 {
+        if !op.groupby_exprs.is_empty() || !op.grouping_sets.is_empty() || op.agg_calls.len() != 1 {
             return Ok(false);
         }
 
-        let ScalarExpression::AggCall { kind, args, .. } = &op.agg_calls[0] else {
+        let ScalarExpression::AggCall {
+            kind, args, filter, ..
+        } = &op.agg_calls[0]
+        else {
             return Ok(false);
         };
-        if args.len() != 1 {
+        if args.len() != 1 || filter.is_some() {
             return Ok(false);
         }
 
@@ -28,7 +28,7 @@ use crate::optimizer::rule::normalization::pushdown_limit::{
     LimitProjectTranspose, PushLimitIntoScan, PushLimitThroughJoin,
 };
 use crate::optimizer::rule::normalization::pushdown_predicates::{
-    PushJoinPredicateIntoScan, PushPredicateIntoScan, PushPredicateThroughJoin,
+    PushJoinPredicateIntoScan, PushPredicateThroughJoin,
 };
 use crate::optimizer::rule::normalization::simplification::ConstantCalculation;
 use crate::optimizer::rule::normalization::simplification::SimplifyFilter;
@@ -48,7 +48,12 @@ pub(crate) use compilation_in_advance::evaluator_bind_current;
 pub(crate) use elimination::{
     apply_annotated_post_rules, apply_scan_order_hint, OrderHintKind, ScanOrderHint,
 };
-pub(crate) use parameterized_index::ParameterizeMarkApply;
+pub(crate) use parameterized_index::{
+    index_probe_column, probe_index_columns, ParameterizeIndexJoin, ParameterizeMarkApply,
+};
+pub(crate) use pushdown_predicates::{
+    reduce_filters, split_conjunctive_predicates, PushPredicateIntoScan,
+};
 pub(crate) use simplification::constant_calculation_current;
 
 #[derive(Debug, Copy, Clone)]
@@ -75,6 +80,7 @@ pub enum NormalizationRuleImpl {
     MinMaxToTopK,
     TopK,
     ParameterizeMarkApply,
+    ParameterizeIndexJoin,
 }
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
@@ -103,7 +109,7 @@ impl NormalizationRuleRootTag {
         match operator {
             Operator::Aggregate(_) => Some(Self::Aggregate),
             Operator::MarkApply(_) => Some(Self::MarkApply),
-            Operator::ScalarApply(_) => Some(Self::Any),
+            Operator::ScalarApply(_) | Operator::LateralApply(_) => Some(Self::Any),
             Operator::Filter(_) => Some(Self::Filter),
             Operator::Join(_) => Some(Self::Join),
             Operator::Limit(_) => Some(Self::Limit),
@@ -115,7 +121,7 @@ impl NormalizationRuleRootTag {
             | Operator::Values(_)
             | Operator::ShowTable
             | Operator::ShowView
-            | Operator::Explain
+            | Operator::Explain(_)
             | Operator::Describe(_)
             | Operator::Insert(_)
             | Operator::Delete(_)
@@ -123,16 +129,22 @@ impl NormalizationRuleRootTag {
             | Operator::AddColumn(_)
             | Operator::ChangeColumn(_)
             | Operator::DropColumn(_)
+            | Operator::AddConstraint(_)
+            | Operator::DropConstraint(_)
             | Operator::CreateTable(_)
             | Operator::CreateIndex(_)
             | Operator::CreateView(_)
+            | Operator::CreateSequence(_)
             | Operator::DropTable(_)
             | Operator::DropView(_)
+            | Operator::DropSequence(_)
             | Operator::DropIndex(_)
             | Operator::Truncate(_)
             | Operator::FunctionScan(_)
             | Operator::Update(_)
             | Operator::Union(_)
+            | Operator::RecursiveCte(_)
+            | Operator::CteScan(_)
             | Operator::SetMembership(_)
             | Operator::Window(_) => None,
             #[cfg(feature = "copy")]
@@ -178,6 +190,7 @@ impl NormalizationRuleImpl {
             NormalizationRuleImpl::EvaluatorBind => NormalizationRuleRootTag::Any,
             NormalizationRuleImpl::MinMaxToTopK => NormalizationRuleRootTag::Aggregate,
             NormalizationRuleImpl::ParameterizeMarkApply => NormalizationRuleRootTag::MarkApply,
+            NormalizationRuleImpl::ParameterizeIndexJoin => NormalizationRuleRootTag::Join,
         }
     }
 }
@@ -215,6 +228,9 @@ impl NormalizationRule for NormalizationRuleImpl {
             NormalizationRuleImpl::ParameterizeMarkApply => {
                 ParameterizeMarkApply.apply(plan, arena)
             }
+            NormalizationRuleImpl::ParameterizeIndexJoin => {
+                ParameterizeIndexJoin.apply(plan, arena)
+            }
         }
     }
 }
@@ -16,9 +16,10 @@ use crate::catalog::ColumnRef;
 use crate::errors::DatabaseError;
 use crate::expression::{BinaryOperator, ScalarExpression};
 use crate::optimizer::core::rule::NormalizationRule;
+use crate::planner::operator::join::{JoinOperator, JoinProbe};
 use crate::planner::operator::mark_apply::{MarkApplyKind, MarkApplyQuantifier};
 use crate::planner::operator::table_scan::TableScanOperator;
-use crate::planner::operator::{Operator, PhysicalOption, PlanImpl};
+use crate::planner::operator::{Operator, PhysicalOption, PlanImpl, SortOption};
 use crate::planner::{Childrens, LogicalPlan};
 use crate::types::index::{IndexLookup, IndexType};
 use crate::types::tuple::Schema;
@@ -54,6 +55,130 @@ impl NormalizationRule for ParameterizeMarkApply {
     }
 }
 
+/// Turns the right input of a join costed as `IndexNestLoopJoin` into an index probe, or falls
+/// back to the join's default implementation when the probed index is no longer usable.
+///
+/// A rule is applied once per pass, so the joins below the matched one are handled along with it.
+pub(crate) struct ParameterizeIndexJoin;
+
+impl NormalizationRule for ParameterizeIndexJoin {
+    fn apply(
+        &self,
+        plan: &mut LogicalPlan,
+        arena: &mut crate::planner::PlanArena,
+    ) -> Result<bool, DatabaseError> {
+        Ok(parameterize_index_joins(plan, arena))
+    }
+}
+
+fn parameterize_index_joins(plan: &mut LogicalPlan, arena: &mut crate::planner::PlanArena) -> bool {
+    let changed = match plan.childrens.as_mut() {
+        Childrens::Only(child) => parameterize_index_joins(child, arena),
+        Childrens::Twins { left, right } => {
+            let left_changed = parameterize_index_joins(left, arena);
+            parameterize_index_joins(right, arena) || left_changed
+        }
+        Childrens::None => false,
+    };
+    if !matches!(
+        plan.physical_option,
+        Some(PhysicalOption {
+            plan: PlanImpl::IndexNestLoopJoin,
+            ..
+        })
+    ) {
+        return changed;
+    }
+    let (Operator::Join(op), Childrens::Twins { left, right }) =
+        (&plan.operator, plan.childrens.as_mut())
+    else {
+        return changed;
+    };
+    let left_len = left.output_schema(arena).len();
+    if is_index_probe(right) {
+        return changed;
+    }
+    let parameterized = index_probe_column(op, left_len, right, arena)
+        .is_some_and(|column| parameterize_right_subtree(right, &column, arena));
+    if !parameterized {
+        plan.physical_option = Some(PhysicalOption::new(op.plan_impl(), SortOption::None));
+    }
+    true
+}
+
+/// Column of the join's right input that an index nested-loop join can probe, preferring
+/// equality lookups over ranges.
+pub(crate) fn index_probe_column(
+    op: &JoinOperator,
+    left_len: usize,
+    right: &LogicalPlan,
+    arena: &crate::planner::PlanArena,
+) -> Option<ColumnRef> {
+    let mut range_column = None;
+    for (column, allows_range) in probe_index_columns(right, arena) {
+        match op.index_probe(column, left_len, arena) {
+            Some(JoinProbe::Eq(_)) => return Some(column),
+            Some(JoinProbe::Scope { .. }) if allows_range => {
+                range_column.get_or_insert(column);
+            }
+            _ => (),
+        }
+    }
+    range_column
+}
+
+/// Columns of `plan` that an index lookup can be parameterized on, and whether the index
+/// also serves range lookups. A range over the leading column of a composite index is not a
+/// key range, so only single-column indexes do.
+pub(crate) fn probe_index_columns(
+    plan: &LogicalPlan,
+    arena: &crate::planner::PlanArena,
+) -> Vec<(ColumnRef, bool)> {
+    let Some(scan_op) = probe_scan(plan) else {
+        return Vec::new();
+    };
+    scan_op
+        .columns
+        .iter()
+        .filter_map(|column| {
+            let position = pick_parameterized_index_position(scan_op, column, arena)?;
+            let index_meta = arena.index(scan_op.index_infos[position].meta);
+            Some((*column, index_meta.column_ids.len() == 1))
+        })
+        .collect()
+}
+
+/// Whether the scan below `plan` is already implemented as a parameterized index probe.
+fn is_index_probe(plan: &LogicalPlan) -> bool {
+    match (&plan.operator, plan.childrens.as_ref()) {
+        (Operator::TableScan(_), _) => matches!(
+            &plan.physical_option,
+            Some(PhysicalOption {
+                plan: PlanImpl::IndexScan(index_info),
+                ..
+            }) if index_info.lookup == Some(IndexLookup::Probe)
+        ),
+        (Operator::Filter(_) | Operator::Project(_), Childrens::Only(child)) => {
+            is_index_probe(child)
+        }
+        _ => false,
+    }
+}
+
+/// The scan below `plan` if every operator above it evaluates its input once per row and no
+/// index range was pushed into it, since the probe replaces the index the scan reads.
+fn probe_scan(plan: &LogicalPlan) -> Option<&TableScanOperator> {
+    match (&plan.operator, plan.childrens.as_ref()) {
+        (Operator::TableScan(scan_op), _) => (!scan_op
+            .index_infos
+            .iter()
+            .any(|index_info| matches!(index_info.lookup, Some(IndexLookup::Static(_)))))
+        .then_some(scan_op),
+        (Operator::Filter(_) | Operator::Project(_), Childrens::Only(child)) => probe_scan(child),
+        _ => None,
+    }
+}
+
 fn find_parameterized_probe(
     kind: MarkApplyKind,
     predicates: &[ScalarExpression],
@@ -211,6 +336,7 @@ fn pick_parameterized_index_position(
             let index_meta = arena.index(index_info.meta);
             index_meta.table_name == *table_name
                 && index_meta.column_ids.first().copied() == Some(column_id)
+                && index_meta.json_path.is_empty()
         })
         .min_by_key(|(_, index_info)| index_priority(arena.index(index_info.meta).ty))
         .map(|(position, _)| position)
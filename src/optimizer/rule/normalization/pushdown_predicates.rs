@@ -36,7 +36,7 @@ type ClassifiedJoinFilters = (
     usize,
 );
 
-fn split_conjunctive_predicates(
+pub(crate) fn split_conjunctive_predicates(
     expr: &ScalarExpression,
     f: &mut impl FnMut(ScalarExpression) -> Result<(), DatabaseError>,
 ) -> Result<(), DatabaseError> {
@@ -98,7 +98,7 @@ fn classify_join_filters(
 
 /// reduce filters into a filter, and then build a new LogicalFilter node with input child.
 /// if filters is empty, return the input child.
-fn reduce_filters(
+pub(crate) fn reduce_filters(
     filters: impl IntoIterator<Item = ScalarExpression>,
     having: bool,
 ) -> Option<FilterOperator> {
@@ -296,6 +296,7 @@ impl NormalizationRule for PushPredicateIntoScan {
                     &index_meta.column_ids[0],
                     arena,
                 )
+                .with_json_path(&index_meta.json_path, &index_meta.value_ty)
                 .detach(&filter_op.predicate)?,
                 IndexType::PrimaryKey { is_multiple: true } | IndexType::Composite => {
                     Self::composite_range(filter_op, *meta, ignore_prefix_len, arena)?
@@ -313,10 +314,13 @@ impl NormalizationRule for PushPredicateIntoScan {
             *covered_deserializers = None;
             *cover_mapping = None;
 
-            // try index covered
+            // try index covered, an index on a JSON path stores no column values
+            let index_meta = arena.index(*meta);
+            if !index_meta.json_path.is_empty() {
+                continue;
+            }
             let mut mapping_slots = vec![usize::MAX; scan_op.columns.len()];
             let mut needs_mapping = false;
-            let index_meta = arena.index(*meta);
             let index_column_types = match &index_meta.value_ty {
                 LogicalType::Tuple(tys) => tys,
                 ty => slice::from_ref(ty),
@@ -660,6 +664,7 @@ mod tests {
             ]),
             name: "idx_c1_c2_c3".to_string(),
             ty: IndexType::Composite,
+            json_path: vec![],
         });
         let predicate = and_predicate(
             and_predicate(
@@ -729,6 +734,7 @@ mod tests {
             ]),
             name: "idx_c1_c2_c3".to_string(),
             ty: IndexType::Composite,
+            json_path: vec![],
         });
         let predicate = and_predicate(
             and_predicate(
@@ -819,6 +825,7 @@ mod tests {
             ]),
             name: "idx_c2_c3_c1".to_string(),
             ty: IndexType::Composite,
+            json_path: vec![],
         });
         let index_meta_aligned = arena.alloc_index(IndexMeta {
             id: 1,
@@ -828,6 +835,7 @@ mod tests {
             value_ty: LogicalType::Tuple(vec![LogicalType::Integer, LogicalType::Integer]),
             name: "idx_c1_c2".to_string(),
             ty: IndexType::Composite,
+            json_path: vec![],
         });
 
         let scan_plan = LogicalPlan::new(
@@ -1,4 +1,7 @@
 use super::*;
+use crate::binder::check::check_constraint_name;
+use crate::binder::foreign_key::foreign_key_name;
+use crate::catalog::{CheckConstraint, ForeignKey, ReferentialAction};
 
 impl<S: Storage> Database<S> {
     fn table_catalog(&self, table_name: &str) -> Result<Option<TableCatalog>, DatabaseError> {
@@ -207,6 +210,13 @@ impl<S: Storage> Database<S> {
                     M::table_name(),
                 )));
             }
+            if column.desc().identity() != current_column.desc().identity() {
+                return Err(DatabaseError::InvalidValue(::std::format!(
+                    "ORM migration cannot automatically change auto increment on column `{}` of table `{}`",
+                    column.name(),
+                    M::table_name(),
+                )));
+            }
             if model_column_matches_catalog(column, current_column)? {
                 continue;
             }
@@ -410,8 +420,35 @@ fn execute_create_table<S: Storage, M: Model>(
     if_not_exists: bool,
 ) -> Result<(), DatabaseError> {
     let columns = M::columns().to_vec();
-    database.execute_mut("ORM CREATE TABLE", &[], move |binder, _| {
-        binder.bind_create_table(M::table_name().into(), columns, if_not_exists)
+    let mut checks = Vec::with_capacity(M::checks().len());
+    for (column, expr) in M::checks() {
+        checks.push(CheckConstraint {
+            name: check_constraint_name(M::table_name(), *column, &checks),
+            expr: expr.to_string(),
+        });
+    }
+    let mut foreign_keys = Vec::with_capacity(M::foreign_keys().len());
+    for (column, referenced_table, referenced_column) in M::foreign_keys() {
+        let columns = vec![column.to_string()];
+        foreign_keys.push(ForeignKey {
+            name: foreign_key_name(M::table_name(), &columns, &foreign_keys),
+            columns,
+            referenced_table: (*referenced_table).into(),
+            referenced_columns: vec![referenced_column.to_string()],
+            on_delete: ReferentialAction::Restrict,
+            on_update: ReferentialAction::Restrict,
+        });
+    }
+    database.execute_mut("ORM CREATE TABLE", &[], move |binder, arena| {
+        binder.bind_create_table(
+            M::table_name().into(),
+            columns,
+            checks,
+            foreign_keys,
+            Vec::new(),
+            if_not_exists,
+            arena,
+        )
     })
 }
 
@@ -440,6 +477,7 @@ fn execute_create_index<S: Storage>(
             table_name.into(),
             index_name,
             columns,
+            vec![],
             if_not_exists,
             unique,
             input,
@@ -503,7 +541,7 @@ fn execute_change_column<S: Storage>(
 ) -> Result<(), DatabaseError> {
     let old_column_name = old_column_name.to_string();
     let new_column_name = new_column_name.to_string();
-    database.execute_mut("ORM CHANGE COLUMN", &[], move |binder, _| {
+    database.execute_mut("ORM CHANGE COLUMN", &[], move |binder, arena| {
         binder.bind_change_column(
             table_name.into(),
             old_column_name,
@@ -511,6 +549,7 @@ fn execute_change_column<S: Storage>(
             data_type,
             default_change,
             not_null_change,
+            arena,
         )
     })
 }
@@ -521,8 +560,8 @@ fn execute_drop_column<S: Storage>(
     column_name: &str,
 ) -> Result<(), DatabaseError> {
     let column_name = column_name.to_string();
-    database.execute_mut("ORM DROP COLUMN", &[], move |binder, _| {
-        binder.bind_drop_column(table_name.into(), column_name, false)
+    database.execute_mut("ORM DROP COLUMN", &[], move |binder, arena| {
+        binder.bind_drop_column(table_name.into(), column_name, false, arena)
     })
 }
 
@@ -544,8 +583,8 @@ fn execute_drop_index<S: Storage>(
     if_exists: bool,
 ) -> Result<(), DatabaseError> {
     let index_name = index_name.to_string();
-    database.execute_mut("ORM DROP INDEX", &[], move |binder, _| {
-        binder.bind_drop_index(table_name.into(), index_name, if_exists)
+    database.execute_mut("ORM DROP INDEX", &[], move |binder, arena| {
+        binder.bind_drop_index(table_name.into(), index_name, if_exists, arena)
     })
 }
 
@@ -10,7 +10,10 @@ impl<S: Storage> Database<S> {
         self.analyze(M::table_name())
     }
 
-    /// Inserts a model into its backing table.
+    /// Inserts a model into its backing table and returns its primary key.
+    ///
+    /// A `#[model(auto_increment)]` field left at zero or `None` is filled in by
+    /// the column's sequence, and the returned key is the generated one.
     ///
     /// # Examples
     ///
@@ -28,9 +31,10 @@ impl<S: Storage> Database<S> {
     ///
     /// let database = DataBaseBuilder::path(".").build_in_memory().unwrap();
     /// database.create_table::<User>().unwrap();
-    /// database.insert(&User { id: 1, name: "Alice".to_string() }).unwrap();
+    /// let id = database.insert(&User { id: 1, name: "Alice".to_string() }).unwrap();
+    /// assert_eq!(id, 1);
     /// ```
-    pub fn insert<M: Model>(&self, model: &M) -> Result<(), DatabaseError> {
+    pub fn insert<M: Model>(&self, model: &M) -> Result<M::PrimaryKey, DatabaseError> {
         orm_insert::<_, M>(self, model)
     }
 
@@ -77,8 +81,8 @@ impl<'a, S: Storage> DBTransaction<'a, S> {
         orm_analyze::<_, M>(self)
     }
 
-    /// Inserts a model inside the current transaction.
-    pub fn insert<M: Model>(&mut self, model: &M) -> Result<(), DatabaseError> {
+    /// Inserts a model inside the current transaction and returns its primary key.
+    pub fn insert<M: Model>(&mut self, model: &M) -> Result<M::PrimaryKey, DatabaseError> {
         orm_insert::<_, M>(self, model)
     }
 
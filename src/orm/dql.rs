@@ -35,6 +35,24 @@ impl<S: Storage> Database<S> {
         explain_orm_context(self, build)
     }
 
+    /// Runs a binder-backed plan built inside a closure and explains it with the runtime
+    /// statistics of every operator, like `EXPLAIN ANALYZE`.
+    pub fn explain_analyze<F>(&self, build: F) -> Result<String, DatabaseError>
+    where
+        F: for<'ctx, 'bind, 'parent, 'arena> FnOnce(
+            &'ctx mut OrmContext<
+                'ctx,
+                'bind,
+                'parent,
+                'arena,
+                S::TransactionType<'_>,
+                &'static [(&'static str, DataValue)],
+            >,
+        ) -> Result<LogicalPlan, DatabaseError>,
+    {
+        explain_analyze_orm_context(self, build)
+    }
+
     /// Loads a single model by primary key.
     ///
     /// The key type is taken from `M::PrimaryKey`, so `database.get::<User>(&1)`
@@ -194,6 +212,24 @@ impl<'a, S: Storage> DBTransaction<'a, S> {
         explain_orm_context(self, build)
     }
 
+    /// Runs a binder-backed plan inside the current transaction and explains it with the
+    /// runtime statistics of every operator, like `EXPLAIN ANALYZE`.
+    pub fn explain_analyze<F>(&mut self, build: F) -> Result<String, DatabaseError>
+    where
+        F: for<'ctx, 'bind, 'parent, 'arena> FnOnce(
+            &'ctx mut OrmContext<
+                'ctx,
+                'bind,
+                'parent,
+                'arena,
+                S::TransactionType<'a>,
+                &'static [(&'static str, DataValue)],
+            >,
+        ) -> Result<LogicalPlan, DatabaseError>,
+    {
+        explain_analyze_orm_context(self, build)
+    }
+
     /// Loads a single model by primary key inside the current transaction.
     pub fn get<M: Model>(&mut self, key: &M::PrimaryKey) -> Result<Option<M>, DatabaseError> {
         orm_get::<_, M>(self, key)
@@ -2,7 +2,7 @@
 
 use crate::binder::{
     with_query_bind_step, BindPlanFrom, BindPlanSelectList, Binder, JoinConstraintInput,
-    QueryBindStep, SetOperatorKind, TableAliasInput,
+    QueryBindStep, SetOperatorKind, TableAliasInput, EXCLUDED_TABLE,
 };
 use crate::catalog::{ColumnCatalog, ColumnRef, TableCatalog, TableName};
 use crate::db::{
@@ -11,13 +11,19 @@ use crate::db::{
 use crate::errors::DatabaseError;
 pub use crate::expression::agg::AggKind;
 use crate::expression::window::WindowFunctionKind;
+pub use crate::expression::window::{WindowFrame, WindowFrameBound, WindowFrameUnits};
 use crate::expression::{self, AliasType, ScalarExpression};
 use crate::planner::operator::alter_table::change_column::{DefaultChange, NotNullChange};
+use crate::planner::operator::explain::ExplainFormat;
+use crate::planner::operator::insert::{ConflictAction, OnConflict};
 use crate::planner::operator::join::JoinType;
 use crate::planner::operator::mark_apply::MarkApplyQuantifier;
 use crate::planner::operator::sort::SortField;
 use crate::planner::{LogicalPlan, PlanArena};
 use crate::storage::{Storage, Transaction};
+use crate::types::index::IndexId;
+#[cfg(feature = "time")]
+use crate::types::timezone::TimeZone;
 use crate::types::tuple::{SchemaView, Tuple};
 use crate::types::value::DataValue;
 use crate::types::CharLengthUnits;
@@ -48,6 +54,7 @@ pub struct OrmField {
     pub placeholder: &'static str,
     pub primary_key: bool,
     pub unique: bool,
+    pub auto_increment: bool,
 }
 
 /// One row returned by [`Database::describe`] or [`DBTransaction::describe`].
@@ -103,11 +110,13 @@ pub struct FieldSort<M, T> {
     nulls_first: bool,
 }
 
-/// Partitioning and ordering for an ORM window expression.
+/// Partitioning, ordering and framing for an ORM window expression.
 #[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub struct WindowSpec {
     partition_by: Vec<ScalarExpression>,
     order_by: Vec<SortField>,
+    frame: Option<WindowFrame>,
+    ignore_nulls: bool,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -348,6 +357,27 @@ impl WindowSpec {
         self.order_by.push(field);
         self
     }
+
+    /// Sets an explicit frame, e.g. `ROWS BETWEEN 6 PRECEDING AND CURRENT ROW`.
+    pub fn frame(mut self, frame: WindowFrame) -> Self {
+        self.frame = Some(frame);
+        self
+    }
+
+    /// `ROWS BETWEEN start AND end`, offsets are row counts.
+    pub fn rows_between(self, start: WindowFrameBound, end: WindowFrameBound) -> Self {
+        self.frame(WindowFrame {
+            units: WindowFrameUnits::Rows,
+            start,
+            end,
+        })
+    }
+
+    /// Skips NULL values when `lag`/`lead` count their offset.
+    pub fn ignore_nulls(mut self) -> Self {
+        self.ignore_nulls = true;
+        self
+    }
 }
 
 impl<E> IntoOrmScalarExpression for E
@@ -525,7 +555,7 @@ where
         args: Vec<ScalarExpression>,
     ) -> Result<CtxExpression<'bind, 'parent, 'arena, T, A>, DatabaseError> {
         self.binder()
-            .bind_aggregate_function(kind, args, false, self.arena())
+            .bind_aggregate_function(kind, args, false, None, Vec::new(), self.arena())
             .map(|expr| self.wrap(expr))
     }
 
@@ -535,8 +565,16 @@ where
         args: Vec<ScalarExpression>,
         spec: WindowSpec,
     ) -> Result<CtxExpression<'bind, 'parent, 'arena, T, A>, DatabaseError> {
+        let kind = kind.with_ignore_nulls(spec.ignore_nulls)?;
         self.binder()
-            .bind_window_function(kind, args, spec.partition_by, spec.order_by, self.arena())
+            .bind_window_function(
+                kind,
+                args,
+                spec.partition_by,
+                spec.order_by,
+                spec.frame,
+                self.arena(),
+            )
             .map(|expr| self.wrap(expr))
     }
 
@@ -993,6 +1031,36 @@ where
     })
 }
 
+fn explain_analyze_orm_context<E, F>(executor: E, build: F) -> Result<String, DatabaseError>
+where
+    E: BindSource,
+    F: for<'ctx, 'bind, 'parent, 'arena> FnOnce(
+        &'ctx mut OrmContext<
+            'ctx,
+            'bind,
+            'parent,
+            'arena,
+            E::Transaction,
+            &'static [(&'static str, DataValue)],
+        >,
+    ) -> Result<LogicalPlan, DatabaseError>,
+{
+    static EMPTY_BIND_PARAMS: &[(&str, DataValue)] = &[];
+    let mut plans = executor
+        .execute(EMPTY_BIND_PARAMS, |binder, arena| {
+            let plan = build(&mut OrmContext {
+                binder: &mut *binder,
+                arena: &mut *arena,
+            })?;
+            binder.bind_explain(plan, true, ExplainFormat::Text)
+        })?
+        .project_value::<String>();
+    let plan = plans.next().transpose()?.unwrap_or_default();
+    plans.done()?;
+
+    Ok(plan)
+}
+
 /// Binder-backed ORM query context.
 ///
 /// This context is created by [`Database::bind`] or [`DBTransaction::bind`]. Query construction inside
@@ -1028,6 +1096,48 @@ where
     value_exprs: Vec<(ColumnRef, ScalarExpression)>,
 }
 
+/// Model insert builder created by [`OrmContext::insert`].
+pub struct BindInsert<'ctx, 'bind, 'parent, 'arena, T, A, M>
+where
+    T: Transaction,
+    A: AsRef<[(&'static str, DataValue)]>,
+    M: Model,
+{
+    binder: &'ctx mut Binder<'bind, 'parent, T, A>,
+    arena: &'ctx mut PlanArena<'arena>,
+    rows: Vec<Vec<(&'static str, DataValue)>>,
+    returning: Vec<ScalarExpression>,
+    _marker: PhantomData<M>,
+}
+
+/// `ON CONFLICT` clause of a [`BindInsert`].
+///
+/// Expressions are bound against the existing row, with the proposed row
+/// available through the `excluded` relation.
+pub struct BindInsertConflict<'ctx, 'bind, 'parent, 'arena, T, A, M>
+where
+    T: Transaction,
+    A: AsRef<[(&'static str, DataValue)]>,
+    M: Model,
+{
+    insert: BindInsert<'ctx, 'bind, 'parent, 'arena, T, A, M>,
+    conflict_binder: Binder<'bind, 'bind, T, A>,
+    target: Option<IndexId>,
+    predicate: Option<ScalarExpression>,
+}
+
+/// Mutation created by [`BindPlanFrom::returning`] whose result rows are the
+/// projection evaluated against every updated or deleted record.
+pub struct BindReturning<'ctx, 'bind, 'parent, 'arena, T, A, M>
+where
+    T: Transaction,
+    A: AsRef<[(&'static str, DataValue)]>,
+    M: Model,
+{
+    from: BindPlanFrom<'ctx, 'bind, 'parent, 'arena, T, A, M>,
+    returning: Vec<ScalarExpression>,
+}
+
 impl<'ctx, 'bind, 'parent, 'arena, T, A> OrmContext<'ctx, 'bind, 'parent, 'arena, T, A>
 where
     T: Transaction,
@@ -1247,6 +1357,166 @@ where
     pub fn truncate<M: Model>(&mut self) -> Result<LogicalPlan, DatabaseError> {
         self.binder.bind_truncate(M::table_name().into())
     }
+
+    pub fn insert<'scope, M: Model>(
+        &'scope mut self,
+    ) -> BindInsert<'scope, 'bind, 'parent, 'arena, T, A, M> {
+        BindInsert {
+            binder: self.binder,
+            arena: self.arena,
+            rows: Vec::new(),
+            returning: Vec::new(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<'ctx, 'bind, 'parent, 'arena, T, A, M> BindInsert<'ctx, 'bind, 'parent, 'arena, T, A, M>
+where
+    T: Transaction,
+    A: AsRef<[(&'static str, DataValue)]>,
+    M: Model,
+{
+    pub fn values(mut self, model: &M) -> Self {
+        self.rows.push(model.params());
+        self
+    }
+
+    pub fn values_many<I, B>(mut self, models: I) -> Self
+    where
+        I: IntoIterator<Item = B>,
+        B: std::borrow::Borrow<M>,
+    {
+        self.rows
+            .extend(models.into_iter().map(|model| model.borrow().params()));
+        self
+    }
+
+    /// Makes the insert return `P` for every written row, including rows
+    /// rewritten by `ON CONFLICT DO UPDATE`.
+    pub fn returning<P: Projection>(mut self) -> Result<Self, DatabaseError> {
+        self.returning = bind_orm_returning::<_, _, P>(
+            self.binder,
+            M::table_name().into(),
+            None,
+            M::table_name(),
+            self.arena,
+        )?;
+        Ok(self)
+    }
+
+    /// Starts an `ON CONFLICT` clause targeting the primary key or unique index on
+    /// exactly `columns`. An empty target matches a conflict on any of them.
+    pub fn on_conflict<C>(
+        self,
+        columns: C,
+    ) -> Result<BindInsertConflict<'ctx, 'bind, 'parent, 'arena, T, A, M>, DatabaseError>
+    where
+        C: IntoIterator,
+        C::Item: Into<String>,
+    {
+        let table_name: TableName = M::table_name().into();
+        let columns = columns.into_iter().map(Into::into).collect::<Vec<_>>();
+        let target = self
+            .binder
+            .bind_conflict_target(&table_name, &columns, self.arena)?;
+        let conflict_binder = self.binder.conflict_binder(table_name, self.arena)?;
+
+        Ok(BindInsertConflict {
+            insert: self,
+            conflict_binder,
+            target,
+            predicate: None,
+        })
+    }
+
+    pub fn finish(self) -> Result<LogicalPlan, DatabaseError> {
+        bind_orm_insert_models::<_, _, M>(self.binder, self.rows, None, self.returning, self.arena)
+    }
+}
+
+impl<'ctx, 'bind, 'parent, 'arena, T, A, M>
+    BindInsertConflict<'ctx, 'bind, 'parent, 'arena, T, A, M>
+where
+    T: Transaction,
+    A: AsRef<[(&'static str, DataValue)]>,
+    M: Model,
+{
+    /// Restricts `do_update` to conflicting rows matching the predicate.
+    pub fn filter<E>(
+        mut self,
+        build: impl for<'scope> FnOnce(
+            &'scope mut ExprBindScope<'scope, 'bind, 'bind, 'arena, T, A>,
+        ) -> Result<E, DatabaseError>,
+    ) -> Result<Self, DatabaseError>
+    where
+        E: IntoOrmScalarExpression,
+    {
+        let predicate = with_query_bind_step!(self.conflict_binder, QueryBindStep::Where, {
+            let mut scope = ExprBindScope {
+                binder: &mut self.conflict_binder,
+                arena: self.insert.arena,
+            };
+            build(&mut scope)?.into_orm_scalar()
+        })?;
+        self.predicate = Some(match self.predicate.take() {
+            Some(left) => ScalarExpression::Binary {
+                op: expression::BinaryOperator::And,
+                left_expr: Box::new(left),
+                right_expr: Box::new(predicate),
+                evaluator: None,
+                ty: LogicalType::Boolean,
+            },
+            None => predicate,
+        });
+        Ok(self)
+    }
+
+    pub fn do_nothing(self) -> Result<LogicalPlan, DatabaseError> {
+        self.finish(ConflictAction::DoNothing)
+    }
+
+    /// Updates the conflicting row. Use [`UpdateBindScope::set_excluded`] to copy
+    /// values from the proposed row.
+    pub fn do_update(
+        mut self,
+        build: impl FnOnce(
+            &mut UpdateBindScope<'_, 'bind, 'bind, 'arena, T, A>,
+        ) -> Result<(), DatabaseError>,
+    ) -> Result<LogicalPlan, DatabaseError> {
+        let value_exprs = {
+            let mut scope = UpdateBindScope {
+                binder: &mut self.conflict_binder,
+                arena: self.insert.arena,
+                source_name: M::table_name().to_string(),
+                value_exprs: Vec::new(),
+            };
+            build(&mut scope)?;
+            scope.value_exprs
+        };
+        if value_exprs.is_empty() {
+            return Err(DatabaseError::ColumnsEmpty);
+        }
+        let predicate = self.predicate.take();
+        self.finish(ConflictAction::DoUpdate {
+            value_exprs,
+            predicate,
+        })
+    }
+
+    fn finish(self, action: ConflictAction) -> Result<LogicalPlan, DatabaseError> {
+        let on_conflict = OnConflict {
+            target: self.target,
+            action,
+        };
+        bind_orm_insert_models::<_, _, M>(
+            self.insert.binder,
+            self.insert.rows,
+            Some(on_conflict),
+            self.insert.returning,
+            self.insert.arena,
+        )
+    }
 }
 
 impl<'ctx, 'bind, 'parent, 'arena, T, A> ExprBindScope<'ctx, 'bind, 'parent, 'arena, T, A>
@@ -1572,6 +1842,130 @@ where
             .window(WindowFunctionKind::DenseRank, Vec::new(), spec)
     }
 
+    pub fn percent_rank(
+        &self,
+        spec: WindowSpec,
+    ) -> Result<CtxExpression<'bind, 'parent, 'arena, T, A>, DatabaseError> {
+        self.handle()
+            .window(WindowFunctionKind::PercentRank, Vec::new(), spec)
+    }
+
+    pub fn cume_dist(
+        &self,
+        spec: WindowSpec,
+    ) -> Result<CtxExpression<'bind, 'parent, 'arena, T, A>, DatabaseError> {
+        self.handle()
+            .window(WindowFunctionKind::CumeDist, Vec::new(), spec)
+    }
+
+    pub fn ntile(
+        &self,
+        buckets: i64,
+        spec: WindowSpec,
+    ) -> Result<CtxExpression<'bind, 'parent, 'arena, T, A>, DatabaseError> {
+        self.handle()
+            .window(WindowFunctionKind::Ntile, vec![buckets.into()], spec)
+    }
+
+    fn offset_window<E>(
+        &self,
+        kind: WindowFunctionKind,
+        expr: impl IntoOrmScalarExpression,
+        offset: i64,
+        default: Option<E>,
+        spec: WindowSpec,
+    ) -> Result<CtxExpression<'bind, 'parent, 'arena, T, A>, DatabaseError>
+    where
+        E: IntoOrmScalarExpression,
+    {
+        let mut args = vec![expr.into_orm_scalar(), offset.into()];
+        args.extend(default.map(IntoOrmScalarExpression::into_orm_scalar));
+        self.handle().window(kind, args, spec)
+    }
+
+    /// The value `offset` rows before the current row, or `default` (NULL when `None`) past
+    /// the start of the partition.
+    pub fn lag<E>(
+        &self,
+        expr: impl IntoOrmScalarExpression,
+        offset: i64,
+        default: Option<E>,
+        spec: WindowSpec,
+    ) -> Result<CtxExpression<'bind, 'parent, 'arena, T, A>, DatabaseError>
+    where
+        E: IntoOrmScalarExpression,
+    {
+        self.offset_window(
+            WindowFunctionKind::Lag {
+                ignore_nulls: false,
+            },
+            expr,
+            offset,
+            default,
+            spec,
+        )
+    }
+
+    /// The value `offset` rows after the current row, or `default` (NULL when `None`) past the
+    /// end of the partition.
+    pub fn lead<E>(
+        &self,
+        expr: impl IntoOrmScalarExpression,
+        offset: i64,
+        default: Option<E>,
+        spec: WindowSpec,
+    ) -> Result<CtxExpression<'bind, 'parent, 'arena, T, A>, DatabaseError>
+    where
+        E: IntoOrmScalarExpression,
+    {
+        self.offset_window(
+            WindowFunctionKind::Lead {
+                ignore_nulls: false,
+            },
+            expr,
+            offset,
+            default,
+            spec,
+        )
+    }
+
+    pub fn first_value(
+        &self,
+        expr: impl IntoOrmScalarExpression,
+        spec: WindowSpec,
+    ) -> Result<CtxExpression<'bind, 'parent, 'arena, T, A>, DatabaseError> {
+        self.handle().window(
+            WindowFunctionKind::FirstValue,
+            vec![expr.into_orm_scalar()],
+            spec,
+        )
+    }
+
+    pub fn last_value(
+        &self,
+        expr: impl IntoOrmScalarExpression,
+        spec: WindowSpec,
+    ) -> Result<CtxExpression<'bind, 'parent, 'arena, T, A>, DatabaseError> {
+        self.handle().window(
+            WindowFunctionKind::LastValue,
+            vec![expr.into_orm_scalar()],
+            spec,
+        )
+    }
+
+    pub fn nth_value(
+        &self,
+        expr: impl IntoOrmScalarExpression,
+        nth: i64,
+        spec: WindowSpec,
+    ) -> Result<CtxExpression<'bind, 'parent, 'arena, T, A>, DatabaseError> {
+        self.handle().window(
+            WindowFunctionKind::NthValue,
+            vec![expr.into_orm_scalar(), nth.into()],
+            spec,
+        )
+    }
+
     pub fn count_over(
         &self,
         expr: impl IntoOrmScalarExpression,
@@ -1743,6 +2137,17 @@ where
         self.set_value(field, value)
     }
 
+    /// Assigns the field from the proposed row of an `ON CONFLICT DO UPDATE`.
+    pub fn set_excluded<M, V>(&mut self, field: Field<M, V>) -> Result<(), DatabaseError> {
+        let expr = self.binder.bind_column_ref_by_name(
+            Some(EXCLUDED_TABLE),
+            field.column,
+            None,
+            self.arena,
+        )?;
+        self.push_assignment(field.column, expr)
+    }
+
     pub fn set_bound_expr<M, V>(
         &mut self,
         field: Field<M, V>,
@@ -1806,12 +2211,14 @@ where
         self,
         table_name: TableName,
         plan: LogicalPlan,
+        returning: Vec<ScalarExpression>,
     ) -> Result<LogicalPlan, DatabaseError> {
         self.binder.context.allow_default = false;
         if self.value_exprs.is_empty() {
             return Err(DatabaseError::ColumnsEmpty);
         }
-        self.binder.bind_update(table_name, self.value_exprs, plan)
+        self.binder
+            .bind_update(table_name, self.value_exprs, plan, returning, self.arena)
     }
 }
 
@@ -1862,6 +2269,13 @@ where
         self
     }
 
+    /// Forces subsequent equi joins in this query to use sort-merge execution, sorting inputs
+    /// that are not already ordered on the join keys.
+    pub fn force_merge_join(self) -> Self {
+        self.binder.force_merge_join = true;
+        self
+    }
+
     pub fn filter<E>(
         mut self,
         build: impl for<'scope> FnOnce(
@@ -2226,7 +2640,33 @@ where
         self.binder.bind_limit_values(self.plan, None, Some(1))
     }
 
+    /// Makes the following `update` or `delete` return `P` for every affected row.
+    pub fn returning<P: Projection>(
+        self,
+    ) -> Result<BindReturning<'scope_ctx, 'bind, 'parent, 'arena, T, A, M>, DatabaseError> {
+        let relation = self.model_relation_name()?;
+        let alias = (relation != M::table_name()).then(|| TableName::from(relation.as_str()));
+        let returning = bind_orm_returning::<_, _, P>(
+            self.binder,
+            self.model_table_name()?,
+            alias.as_ref(),
+            &relation,
+            self.arena,
+        )?;
+        Ok(BindReturning {
+            from: self,
+            returning,
+        })
+    }
+
     pub fn delete(self) -> Result<LogicalPlan, DatabaseError> {
+        self.delete_returning(Vec::new())
+    }
+
+    fn delete_returning(
+        self,
+        returning: Vec<ScalarExpression>,
+    ) -> Result<LogicalPlan, DatabaseError> {
         let table_name = self.model_table_name()?;
         let primary_keys = self
             .binder
@@ -2238,7 +2678,8 @@ where
             .map(|(_, column)| *column)
             .collect();
         self.binder.with_pk(table_name.clone());
-        self.binder.bind_delete(table_name, primary_keys, self.plan)
+        self.binder
+            .bind_delete(table_name, primary_keys, self.plan, returning)
     }
 
     pub fn update(
@@ -2246,6 +2687,16 @@ where
         build: impl FnOnce(
             &mut UpdateBindScope<'scope_ctx, 'bind, 'parent, 'arena, T, A>,
         ) -> Result<(), DatabaseError>,
+    ) -> Result<LogicalPlan, DatabaseError> {
+        self.update_returning(build, Vec::new())
+    }
+
+    fn update_returning(
+        self,
+        build: impl FnOnce(
+            &mut UpdateBindScope<'scope_ctx, 'bind, 'parent, 'arena, T, A>,
+        ) -> Result<(), DatabaseError>,
+        returning: Vec<ScalarExpression>,
     ) -> Result<LogicalPlan, DatabaseError> {
         let table_name = self.model_table_name()?;
         let source_name = self.model_relation_name()?;
@@ -2258,7 +2709,7 @@ where
             value_exprs: Vec::new(),
         };
         build(&mut scope)?;
-        scope.finish(table_name, self.plan)
+        scope.finish(table_name, self.plan, returning)
     }
 
     pub fn finish(self) -> Result<LogicalPlan, DatabaseError> {
@@ -2266,6 +2717,27 @@ where
     }
 }
 
+impl<'scope_ctx, 'bind, 'parent, 'arena, T, A, M>
+    BindReturning<'scope_ctx, 'bind, 'parent, 'arena, T, A, M>
+where
+    T: Transaction,
+    A: AsRef<[(&'static str, DataValue)]>,
+    M: Model,
+{
+    pub fn delete(self) -> Result<LogicalPlan, DatabaseError> {
+        self.from.delete_returning(self.returning)
+    }
+
+    pub fn update(
+        self,
+        build: impl FnOnce(
+            &mut UpdateBindScope<'scope_ctx, 'bind, 'parent, 'arena, T, A>,
+        ) -> Result<(), DatabaseError>,
+    ) -> Result<LogicalPlan, DatabaseError> {
+        self.from.update_returning(build, self.returning)
+    }
+}
+
 impl<'scope_ctx, 'bind, 'parent, 'arena, T, A, M>
     BindPlanSelectList<'scope_ctx, 'bind, 'parent, 'arena, T, A, M>
 where
@@ -2541,12 +3013,14 @@ where
     };
     input_plan = binder.bind_project(input_plan, projection, arena)?;
 
-    binder.bind_insert_query(table_name, input_plan, overwrite)
+    binder.bind_insert_query(table_name, input_plan, overwrite, None, Vec::new(), arena)
 }
 
-fn bind_orm_insert_model<'bind, 'parent, 'arena, T, A, M>(
+fn bind_orm_insert_models<'bind, 'parent, 'arena, T, A, M>(
     binder: &mut Binder<'bind, 'parent, T, A>,
-    params: Vec<(&'static str, DataValue)>,
+    rows: Vec<Vec<(&'static str, DataValue)>>,
+    on_conflict: Option<OnConflict>,
+    returning: Vec<ScalarExpression>,
     arena: &mut PlanArena<'arena>,
 ) -> Result<LogicalPlan, DatabaseError>
 where
@@ -2559,31 +3033,88 @@ where
         .context
         .source_and_bind(table_name.clone(), None, None, false)?
         .ok_or(DatabaseError::TableNotFound)?;
-    let params = params.into_iter().collect::<BTreeMap<_, _>>();
     let mut schema_ref = Vec::with_capacity(M::fields().len());
-    let mut row = Vec::with_capacity(M::fields().len());
-
     for field in M::fields() {
-        let column = source
-            .column(field.column, arena)
-            .ok_or_else(|| DatabaseError::column_not_found(field.column.to_string()))?;
-        let column_catalog = arena.column(column);
-        let value = params
-            .get(field.placeholder)
-            .ok_or_else(|| DatabaseError::parameter_not_found(field.placeholder))?
-            .clone()
-            .cast(column_catalog.datatype())?;
-        value.check_len(column_catalog.datatype())?;
-        if matches!(value, DataValue::Null) && !column_catalog.nullable() {
-            return Err(DatabaseError::not_null_column(
-                column_catalog.name().to_string(),
-            ));
-        }
-        schema_ref.push(column);
-        row.push(value);
+        schema_ref.push(
+            source
+                .column(field.column, arena)
+                .ok_or_else(|| DatabaseError::column_not_found(field.column.to_string()))?,
+        );
     }
+    let mut values = Vec::with_capacity(rows.len());
+
+    for params in rows {
+        let params = params.into_iter().collect::<BTreeMap<_, _>>();
+        let mut row = Vec::with_capacity(M::fields().len());
+
+        for (field, column) in M::fields().iter().zip(schema_ref.iter()) {
+            let column_catalog = arena.column(*column);
+            let value = params
+                .get(field.placeholder)
+                .ok_or_else(|| DatabaseError::parameter_not_found(field.placeholder))?
+                .clone()
+                .cast(column_catalog.datatype())?;
+            // an unset auto increment value is left for the column's sequence to fill in
+            if field.auto_increment
+                && matches!(
+                    value,
+                    DataValue::Null
+                        | DataValue::Int8(0)
+                        | DataValue::Int16(0)
+                        | DataValue::Int32(0)
+                        | DataValue::Int64(0)
+                        | DataValue::UInt8(0)
+                        | DataValue::UInt16(0)
+                        | DataValue::UInt32(0)
+                        | DataValue::UInt64(0)
+                )
+            {
+                row.push(DataValue::Null);
+                continue;
+            }
+            value.check_len(column_catalog.datatype())?;
+            if matches!(value, DataValue::Null) && !column_catalog.nullable() {
+                return Err(DatabaseError::not_null_column(
+                    column_catalog.name().to_string(),
+                ));
+            }
+            row.push(value);
+        }
+        values.push(row);
+    }
+
+    binder.bind_insert_values(
+        table_name,
+        schema_ref,
+        values,
+        false,
+        true,
+        on_conflict,
+        returning,
+        arena,
+    )
+}
 
-    binder.bind_insert_values(table_name, schema_ref, vec![row], false, true)
+fn bind_orm_returning<'bind, 'parent, 'arena, T, A, P>(
+    binder: &Binder<'bind, 'parent, T, A>,
+    table_name: TableName,
+    alias: Option<&TableName>,
+    relation: &str,
+    arena: &mut PlanArena<'arena>,
+) -> Result<Vec<ScalarExpression>, DatabaseError>
+where
+    T: Transaction,
+    A: AsRef<[(&'static str, DataValue)]>,
+    P: Projection,
+{
+    let mut binder = binder.returning_binder(table_name, alias)?;
+    with_query_bind_step!(binder, QueryBindStep::Project, {
+        let mut scope = ExprBindScope {
+            binder: &mut binder,
+            arena,
+        };
+        P::bind_projection(&mut scope, relation)?
+    })
 }
 
 fn describe_text_value(value: Option<DataValue>) -> String {
@@ -2606,7 +3137,7 @@ pub trait Model: Sized + FromQueryRow {
     /// [`Database::get`](crate::orm::Database::get)
     /// infer the key type directly from the model, so callers only need to
     /// write `database.get::<User>(&id)`.
-    type PrimaryKey: ToDataValue;
+    type PrimaryKey: ToDataValue + FromDataValue;
 
     /// Returns the backing table name for the model.
     fn table_name() -> &'static str;
@@ -2627,6 +3158,23 @@ pub trait Model: Sized + FromQueryRow {
         &[]
     }
 
+    /// Returns `CHECK` constraints declared by the model, paired with the column
+    /// they were declared on.
+    ///
+    /// They are created together with the table; [`Database::migrate`](crate::orm::Database::migrate)
+    /// does not reconcile them on existing tables.
+    fn checks() -> &'static [(Option<&'static str>, &'static str)] {
+        &[]
+    }
+
+    /// Returns `FOREIGN KEY` constraints declared by the model as the referencing
+    /// column, the referenced table and the referenced column.
+    ///
+    /// Like [`Model::checks`], they are only created together with the table.
+    fn foreign_keys() -> &'static [(&'static str, &'static str, &'static str)] {
+        &[]
+    }
+
     /// Converts the model into named query parameters.
     fn params(&self) -> Vec<(&'static str, DataValue)>;
 
@@ -2662,6 +3210,14 @@ pub trait FromDataValue: Sized {
 
     /// Converts a raw [`DataValue`] into `Self`.
     fn from_data_value(value: DataValue) -> Result<Self, DatabaseError>;
+
+    #[cfg(feature = "time")]
+    /// Like [`FromDataValue::from_data_value`], but reads timestamps with time zone in
+    /// `time_zone` instead of the session time zone of the statement running on the current
+    /// thread, e.g. with [`ResultIter::time_zone`] for a value kept past `next_tuple`.
+    fn from_data_value_in(value: DataValue, _time_zone: TimeZone) -> Result<Self, DatabaseError> {
+        Self::from_data_value(value)
+    }
 }
 
 /// Conversion trait from a projected result tuple into a Rust value.
@@ -2962,9 +3518,11 @@ impl DecimalType for Decimal {}
 #[cfg(feature = "time")]
 mod chrono_orm {
     use super::{FromDataValue, ModelColumnType, ToDataValue};
+    use crate::errors::DatabaseError;
+    use crate::types::timezone::{session_time_zone, TimeZone};
     use crate::types::value::DataValue;
     use crate::types::LogicalType;
-    use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+    use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, Utc};
 
     impl_from_data_value_by_method!(NaiveDate, date);
     impl_from_data_value_by_method!(NaiveDateTime, datetime);
@@ -2991,6 +3549,95 @@ mod chrono_orm {
             DataValue::from(self)
         }
     }
+
+    impl_from_data_value_by_method!(DateTime<Utc>, timestamp_tz);
+    impl_model_column_type!(
+        LogicalType::TimeStamp(Some(6), true);
+        DateTime<Utc>,
+        DateTime<FixedOffset>,
+        DateTime<chrono_tz::Tz>
+    );
+
+    /// Read in the offset of the session time zone.
+    impl FromDataValue for DateTime<FixedOffset> {
+        fn logical_type() -> Option<LogicalType> {
+            LogicalType::type_trans::<Self>()
+        }
+
+        fn from_data_value(value: DataValue) -> Result<Self, DatabaseError> {
+            Self::from_data_value_in(value, session_time_zone())
+        }
+
+        fn from_data_value_in(
+            value: DataValue,
+            time_zone: TimeZone,
+        ) -> Result<Self, DatabaseError> {
+            let utc = value
+                .timestamp_tz()
+                .ok_or_else(|| crate::orm::invalid_from_data_value::<Self>(&value))?;
+            Ok(utc.with_timezone(&time_zone.offset(&utc.naive_utc())))
+        }
+    }
+
+    /// Read in the session time zone, or in UTC when it is a fixed offset.
+    impl FromDataValue for DateTime<chrono_tz::Tz> {
+        fn logical_type() -> Option<LogicalType> {
+            LogicalType::type_trans::<Self>()
+        }
+
+        fn from_data_value(value: DataValue) -> Result<Self, DatabaseError> {
+            Self::from_data_value_in(value, session_time_zone())
+        }
+
+        fn from_data_value_in(
+            value: DataValue,
+            time_zone: TimeZone,
+        ) -> Result<Self, DatabaseError> {
+            let utc = value
+                .timestamp_tz()
+                .ok_or_else(|| crate::orm::invalid_from_data_value::<Self>(&value))?;
+            let tz = match time_zone {
+                TimeZone::Named(tz) => tz,
+                TimeZone::Fixed(_) => chrono_tz::Tz::UTC,
+            };
+            Ok(utc.with_timezone(&tz))
+        }
+    }
+
+    impl<Tz: chrono::TimeZone> ToDataValue for DateTime<Tz> {
+        fn to_data_value(&self) -> DataValue {
+            DataValue::from(self)
+        }
+    }
+}
+
+#[cfg(feature = "json")]
+mod serde_json_orm {
+    use super::{FromDataValue, ModelColumnType, ToDataValue};
+    use crate::types::json::Json;
+    use crate::types::value::DataValue;
+    use crate::types::LogicalType;
+
+    impl_model_column_type!(LogicalType::Json; serde_json::Value);
+
+    impl FromDataValue for serde_json::Value {
+        fn logical_type() -> Option<LogicalType> {
+            LogicalType::type_trans::<Self>()
+        }
+
+        fn from_data_value(value: DataValue) -> Result<Self, crate::errors::DatabaseError> {
+            value
+                .json()
+                .map(serde_json::Value::from)
+                .ok_or_else(|| crate::orm::invalid_from_data_value::<Self>(&value))
+        }
+    }
+
+    impl ToDataValue for serde_json::Value {
+        fn to_data_value(&self) -> DataValue {
+            DataValue::Json(Json::from(self.clone()))
+        }
+    }
 }
 
 impl FromDataValue for String {
@@ -3051,6 +3698,15 @@ impl<T: FromDataValue> FromDataValue for Option<T> {
             T::from_data_value(value).map(Some)
         }
     }
+
+    #[cfg(feature = "time")]
+    fn from_data_value_in(value: DataValue, time_zone: TimeZone) -> Result<Self, DatabaseError> {
+        if matches!(value, DataValue::Null) {
+            Ok(None)
+        } else {
+            T::from_data_value_in(value, time_zone).map(Some)
+        }
+    }
 }
 
 impl<T: ToDataValue> ToDataValue for Option<T> {
@@ -3075,6 +3731,53 @@ impl<T: ModelColumnType> ModelColumnType for Option<T> {
 impl<T: StringType> StringType for Option<T> {}
 impl<T: DecimalType> DecimalType for Option<T> {}
 
+/// `Vec<T>` maps to an array of `T`, such as `Vec<String>` to `VARCHAR[]`.
+impl<T: FromDataValue> FromDataValue for Vec<T> {
+    fn logical_type() -> Option<LogicalType> {
+        T::logical_type().map(|ty| LogicalType::Array(Box::new(ty)))
+    }
+
+    fn from_data_value(value: DataValue) -> Result<Self, DatabaseError> {
+        vec_from_data_value(value, T::from_data_value)
+    }
+
+    #[cfg(feature = "time")]
+    fn from_data_value_in(value: DataValue, time_zone: TimeZone) -> Result<Self, DatabaseError> {
+        vec_from_data_value(value, |value| T::from_data_value_in(value, time_zone))
+    }
+}
+
+fn vec_from_data_value<T: FromDataValue>(
+    value: DataValue,
+    convert: impl Fn(DataValue) -> Result<T, DatabaseError>,
+) -> Result<Vec<T>, DatabaseError> {
+    match value {
+        DataValue::Array(values) => values
+            .into_iter()
+            .map(|value| {
+                let value = match T::logical_type() {
+                    Some(ty) => value.cast(&ty)?,
+                    None => value,
+                };
+                convert(value)
+            })
+            .collect(),
+        value => Err(invalid_from_data_value::<Vec<T>>(&value)),
+    }
+}
+
+impl<T: ToDataValue> ToDataValue for Vec<T> {
+    fn to_data_value(&self) -> DataValue {
+        DataValue::Array(self.iter().map(ToDataValue::to_data_value).collect())
+    }
+}
+
+impl<T: ModelColumnType> ModelColumnType for Vec<T> {
+    fn logical_type() -> LogicalType {
+        LogicalType::Array(Box::new(T::logical_type()))
+    }
+}
+
 macro_rules! impl_from_query_tuple {
     ($(($($name:ident),+)),+ $(,)?) => {
         $(
@@ -3121,10 +3824,17 @@ impl_from_query_tuple!(
 );
 
 fn model_column_default(model: &ColumnCatalog) -> Result<Option<DataValue>, DatabaseError> {
+    if model.desc().identity().is_some() {
+        return Ok(None);
+    }
     model.default_value()
 }
 
 fn catalog_column_default(column: &ColumnCatalog) -> Result<Option<DataValue>, DatabaseError> {
+    // identity defaults draw from the column's sequence and are compared by identity instead
+    if column.desc().identity().is_some() {
+        return Ok(None);
+    }
     column.default_value()
 }
 
@@ -3139,6 +3849,7 @@ fn model_column_matches_catalog(
     Ok(model.desc().is_primary() == column.desc().is_primary()
         && model.desc().is_unique() == column.desc().is_unique()
         && model.nullable() == column.nullable()
+        && model.desc().identity() == column.desc().identity()
         && model_column_type_matches_catalog(model, column)
         && model_column_default(model)? == catalog_column_default(column)?)
 }
@@ -3150,6 +3861,7 @@ fn model_column_rename_compatible(
     Ok(model.desc().is_primary() == column.desc().is_primary()
         && model.desc().is_unique() == column.desc().is_unique()
         && model.nullable() == column.nullable()
+        && model.desc().identity() == column.desc().identity()
         && model_column_type_matches_catalog(model, column)
         && model_column_default(model)? == catalog_column_default(column)?)
 }
@@ -3235,13 +3947,26 @@ fn orm_analyze<E: BindSource, M: Model>(executor: E) -> Result<(), DatabaseError
         .done()
 }
 
-fn orm_insert<E: BindSource, M: Model>(executor: E, model: &M) -> Result<(), DatabaseError> {
+fn orm_insert<E: BindSource, M: Model>(
+    executor: E,
+    model: &M,
+) -> Result<M::PrimaryKey, DatabaseError> {
     let params = model.params();
-    executor
-        .execute(&[], |binder, arena| {
-            bind_orm_insert_model::<_, _, M>(binder, params, arena)
-        })?
-        .done()
+    let primary_key = M::primary_key_field();
+    let mut iter = executor.execute(&[], |binder, arena| {
+        let returning = binder
+            .returning_binder(M::table_name().into(), None)?
+            .bind_column_ref_by_name(None, primary_key.column, Some(M::table_name()), arena)?;
+        bind_orm_insert_models::<_, _, M>(binder, vec![params], None, vec![returning], arena)
+    })?;
+    let key = iter
+        .next_tuple(|_, tuple| take_value_at(tuple, Some(0), primary_key.column))?
+        .ok_or(DatabaseError::MisMatch(
+            "the inserted row",
+            "the returned primary key",
+        ))??;
+    iter.done()?;
+    Ok(key)
 }
 
 fn orm_get<E: BindSource, M: Model>(
@@ -3297,6 +4022,7 @@ mod tests {
             placeholder: "id",
             primary_key: true,
             unique: false,
+            auto_increment: false,
         },
         OrmField {
             column: "name",
@@ -3304,6 +4030,7 @@ mod tests {
             placeholder: "name",
             primary_key: false,
             unique: false,
+            auto_increment: false,
         },
         OrmField {
             column: "age",
@@ -3311,6 +4038,7 @@ mod tests {
             placeholder: "age",
             primary_key: false,
             unique: false,
+            auto_increment: false,
         },
     ];
 
@@ -3379,6 +4107,7 @@ mod tests {
             placeholder: "id",
             primary_key: true,
             unique: false,
+            auto_increment: false,
         },
         OrmField {
             column: "user_id",
@@ -3386,6 +4115,7 @@ mod tests {
             placeholder: "user_id",
             primary_key: false,
             unique: false,
+            auto_increment: false,
         },
         OrmField {
             column: "amount",
@@ -3393,6 +4123,7 @@ mod tests {
             placeholder: "amount",
             primary_key: false,
             unique: false,
+            auto_increment: false,
         },
     ];
 
@@ -3595,6 +4326,24 @@ mod tests {
         );
         assert!(<Option<String> as ModelColumnType>::nullable());
 
+        let tags = vec![Some("db".to_string()), None];
+        assert_eq!(
+            tags.to_data_value(),
+            DataValue::Array(vec![DataValue::from("db".to_string()), DataValue::Null])
+        );
+        assert_eq!(
+            Vec::<Option<String>>::from_data_value(tags.to_data_value())?,
+            tags
+        );
+        assert_eq!(
+            Vec::<i64>::from_data_value(DataValue::Array(vec![DataValue::Int32(1)]))?,
+            vec![1]
+        );
+        assert_eq!(
+            <Vec<i32> as ModelColumnType>::logical_type(),
+            LogicalType::Array(Box::new(LogicalType::Integer))
+        );
+
         let err = i32::from_data_value(DataValue::from("not-int".to_string())).unwrap_err();
         assert!(err
             .to_string()
@@ -3603,6 +4352,54 @@ mod tests {
         Ok(())
     }
 
+    #[cfg(feature = "time")]
+    #[test]
+    fn chrono_date_times_convert_through_the_session_time_zone() -> Result<(), DatabaseError> {
+        use crate::types::timezone::TimeZone;
+        use chrono::{DateTime, FixedOffset, Utc};
+
+        let tokyo = DateTime::parse_from_rfc3339("2024-01-01T09:00:00+09:00").unwrap();
+        let value = tokyo.to_data_value();
+        assert_eq!(value, DataValue::Time64(1_704_067_200_000_000, 6, true));
+        assert_eq!(
+            DateTime::<Utc>::from_data_value(value.clone())?,
+            tokyo.with_timezone(&Utc)
+        );
+        assert_eq!(
+            <DateTime<chrono_tz::Tz> as ModelColumnType>::logical_type(),
+            LogicalType::TimeStamp(Some(6), true)
+        );
+
+        let _scope = TimeZone::parse("America/New_York")?.enter();
+        let offset = DateTime::<FixedOffset>::from_data_value(value.clone())?;
+        assert_eq!(offset.to_rfc3339(), "2023-12-31T19:00:00-05:00");
+        let named = DateTime::<chrono_tz::Tz>::from_data_value(value.clone())?;
+        assert_eq!(named.timezone(), chrono_tz::America::New_York);
+        assert_eq!(named, tokyo);
+        assert_eq!(named.to_data_value(), value);
+
+        Ok(())
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn serde_json_values_convert_to_json() -> Result<(), DatabaseError> {
+        let document = serde_json::json!({"name": "kite", "tags": ["db", 1, 2.5, null]});
+        let value = document.to_data_value();
+        assert_eq!(
+            value.to_string(),
+            r#"{"name": "kite", "tags": ["db", 1, 2.5, null]}"#
+        );
+        assert_eq!(serde_json::Value::from_data_value(value)?, document);
+        assert_eq!(
+            <serde_json::Value as ModelColumnType>::logical_type(),
+            LogicalType::Json
+        );
+        assert!(serde_json::Value::from_data_value(DataValue::Int32(1)).is_err());
+
+        Ok(())
+    }
+
     #[test]
     fn tuple_projection_helpers_cast_extract_and_report_width_mismatch() -> Result<(), DatabaseError>
     {
@@ -3763,9 +4560,9 @@ mod tests {
         assert_eq!(
             plan,
             concat!(
-                "Projection [#2] [Project => (Sort Option: Follow)] ",
-                "Filter (#1 >= 4), Is Having: false [Filter => (Sort Option: Follow)] ",
-                "TableScan orm_unit_users -> [#1, #2] [SeqScan => (Sort Option: None)]"
+                "Projection [#6] [Project => (Sort Option: Follow)] ",
+                "Filter (#5 >= 4), Is Having: false [Filter => (Sort Option: Follow)] ",
+                "TableScan orm_unit_users -> [#5, #6] [SeqScan => (Sort Option: None)]"
             ),
             "{plan}"
         );
@@ -3801,9 +4598,9 @@ mod tests {
             expression_plan,
             concat!(
                 "Projection [upper_name] [Project => (Sort Option: Follow)] ",
-                "Sort By #3 Desc Nulls Last [Sort => (Sort Option: OrderBy: (#3 Desc Nulls Last) ignore_prefix_len: 0)] ",
-                "Filter ((#3 is not null && ((#3 >= 18) && (#3 <= 25))) && (!(#2 != Bob) && (#2 = Missing))), Is Having: false ",
-                "[Filter => (Sort Option: Follow)] TableScan orm_unit_users -> [#2, #3] [SeqScan => (Sort Option: None)]"
+                "Sort By #7 Desc Nulls Last [Sort => (Sort Option: OrderBy: (#7 Desc Nulls Last) ignore_prefix_len: 0)] ",
+                "Filter ((#7 is not null && ((#7 >= 18) && (#7 <= 25))) && (!(#6 != Bob) && (#6 = Missing))), Is Having: false ",
+                "[Filter => (Sort Option: Follow)] TableScan orm_unit_users -> [#6, #7] [SeqScan => (Sort Option: None)]"
             ),
             "{expression_plan}"
         );
@@ -3823,10 +4620,10 @@ mod tests {
         assert_eq!(
             list_plan,
             concat!(
-                "Projection [#1] [Project => (Sort Option: Follow)] ",
+                "Projection [#5] [Project => (Sort Option: Follow)] ",
                 "Sort By 1 Asc Nulls Last [Sort => (Sort Option: OrderBy: (1 Asc Nulls Last) ignore_prefix_len: 0)] ",
-                "Filter (((#1 = 3) || ((#1 = 2) || (#1 = 1))) && (#1 != 3)), Is Having: false ",
-                "[Filter => (Sort Option: Follow)] TableScan orm_unit_users -> [#1] [SeqScan => (Sort Option: None)]"
+                "Filter (((#5 = 3) || ((#5 = 2) || (#5 = 1))) && (#5 != 3)), Is Having: false ",
+                "[Filter => (Sort Option: Follow)] TableScan orm_unit_users -> [#5] [SeqScan => (Sort Option: None)]"
             ),
             "{list_plan}"
         );
@@ -3845,9 +4642,9 @@ mod tests {
         assert_eq!(
             nullable_plan,
             concat!(
-                "Projection [#1] [Project => (Sort Option: Follow)] ",
-                "Filter (((#3 < 10) || (#3 > 30)) || #3 is null), Is Having: false ",
-                "[Filter => (Sort Option: Follow)] TableScan orm_unit_users -> [#1, #3] [SeqScan => (Sort Option: None)]"
+                "Projection [#5] [Project => (Sort Option: Follow)] ",
+                "Filter (((#7 < 10) || (#7 > 30)) || #7 is null), Is Having: false ",
+                "[Filter => (Sort Option: Follow)] TableScan orm_unit_users -> [#5, #7] [SeqScan => (Sort Option: None)]"
             ),
             "{nullable_plan}"
         );
@@ -3878,11 +4675,11 @@ mod tests {
         assert_eq!(
             grouped_plan,
             concat!(
-                "Projection [#5, #7] [Project => (Sort Option: Follow)] ",
-                "Sort By #5 Asc Nulls Last [Sort => (Sort Option: OrderBy: (#5 Asc Nulls Last) ignore_prefix_len: 0)] ",
-                "Filter (Sum(#6) >= 200), Is Having: true [Filter => (Sort Option: Follow)] ",
-                "Aggregate [Sum(#6)] -> Group By [#5] [HashAggregate => (Sort Option: None)] ",
-                "TableScan orm_unit_orders -> [#5, #6] [SeqScan => (Sort Option: None)]"
+                "Projection [#9, #11] [Project => (Sort Option: Follow)] ",
+                "Sort By #9 Asc Nulls Last [Sort => (Sort Option: OrderBy: (#9 Asc Nulls Last) ignore_prefix_len: 0)] ",
+                "Filter (Sum(#10) >= 200), Is Having: true [Filter => (Sort Option: Follow)] ",
+                "Aggregate [Sum(#10)] -> Group By [#9] [HashAggregate => (Sort Option: None)] ",
+                "TableScan orm_unit_orders -> [#9, #10] [SeqScan => (Sort Option: None)]"
             ),
             "{grouped_plan}"
         );
@@ -3899,10 +4696,10 @@ mod tests {
         assert_eq!(
             right_join_plan,
             concat!(
-                "Projection [#1] [Project => (Sort Option: Follow)] ",
-                "RightOuter Join On #1 = #5 [HashJoin => (Sort Option: None)] ",
-                "TableScan orm_unit_users -> [#1] [SeqScan => (Sort Option: None)] ",
-                "TableScan orm_unit_orders -> [#5] [SeqScan => (Sort Option: None)]"
+                "Projection [#5] [Project => (Sort Option: Follow)] ",
+                "RightOuter Join On #5 = #9 [HashJoin => (Sort Option: None)] ",
+                "TableScan orm_unit_users -> [#5] [SeqScan => (Sort Option: None)] ",
+                "TableScan orm_unit_orders -> [#9] [SeqScan => (Sort Option: None)]"
             ),
             "{right_join_plan}"
         );
@@ -3919,10 +4716,10 @@ mod tests {
         assert_eq!(
             full_join_plan,
             concat!(
-                "Projection [#1] [Project => (Sort Option: Follow)] ",
-                "Full Join On #1 = #5 [HashJoin => (Sort Option: None)] ",
-                "TableScan orm_unit_users -> [#1] [SeqScan => (Sort Option: None)] ",
-                "TableScan orm_unit_orders -> [#5] [SeqScan => (Sort Option: None)]"
+                "Projection [#5] [Project => (Sort Option: Follow)] ",
+                "Full Join On #5 = #9 [HashJoin => (Sort Option: None)] ",
+                "TableScan orm_unit_users -> [#5] [SeqScan => (Sort Option: None)] ",
+                "TableScan orm_unit_orders -> [#9] [SeqScan => (Sort Option: None)]"
             ),
             "{full_join_plan}"
         );
@@ -3936,10 +4733,10 @@ mod tests {
         assert_eq!(
             cross_join_plan,
             concat!(
-                "Projection [#1, #4] [Project => (Sort Option: Follow)] ",
+                "Projection [#5, #8] [Project => (Sort Option: Follow)] ",
                 "Cross Join Nothing [NestLoopJoin => (Sort Option: None)] ",
-                "TableScan orm_unit_users -> [#1] [SeqScan => (Sort Option: None)] ",
-                "TableScan orm_unit_orders -> [#4] [SeqScan => (Sort Option: None)]"
+                "TableScan orm_unit_users -> [#5] [SeqScan => (Sort Option: None)] ",
+                "TableScan orm_unit_orders -> [#8] [SeqScan => (Sort Option: None)]"
             ),
             "{cross_join_plan}"
         );
@@ -3953,10 +4750,10 @@ mod tests {
         assert_eq!(
             inner_using_plan,
             concat!(
-                "Projection [#1] [Project => (Sort Option: Follow)] ",
-                "Inner Join On #1 = #4 [HashJoin => (Sort Option: None)] ",
-                "TableScan orm_unit_users -> [#1] [SeqScan => (Sort Option: None)] ",
-                "TableScan orm_unit_orders -> [#4] [SeqScan => (Sort Option: None)]"
+                "Projection [#5] [Project => (Sort Option: Follow)] ",
+                "Inner Join On #5 = #8 [HashJoin => (Sort Option: None)] ",
+                "TableScan orm_unit_users -> [#5] [SeqScan => (Sort Option: None)] ",
+                "TableScan orm_unit_orders -> [#8] [SeqScan => (Sort Option: None)]"
             ),
             "{inner_using_plan}"
         );
@@ -3970,10 +4767,10 @@ mod tests {
         assert_eq!(
             left_using_plan,
             concat!(
-                "Projection [#1] [Project => (Sort Option: Follow)] ",
-                "LeftOuter Join On #1 = #4 [HashJoin => (Sort Option: None)] ",
-                "TableScan orm_unit_users -> [#1] [SeqScan => (Sort Option: None)] ",
-                "TableScan orm_unit_orders -> [#4] [SeqScan => (Sort Option: None)]"
+                "Projection [#5] [Project => (Sort Option: Follow)] ",
+                "LeftOuter Join On #5 = #8 [HashJoin => (Sort Option: None)] ",
+                "TableScan orm_unit_users -> [#5] [SeqScan => (Sort Option: None)] ",
+                "TableScan orm_unit_orders -> [#8] [SeqScan => (Sort Option: None)]"
             ),
             "{left_using_plan}"
         );
@@ -3987,10 +4784,10 @@ mod tests {
         assert_eq!(
             right_using_plan,
             concat!(
-                "Projection [#1] [Project => (Sort Option: Follow)] ",
-                "RightOuter Join On #1 = #4 [HashJoin => (Sort Option: None)] ",
-                "TableScan orm_unit_users -> [#1] [SeqScan => (Sort Option: None)] ",
-                "TableScan orm_unit_orders -> [#4] [SeqScan => (Sort Option: None)]"
+                "Projection [#5] [Project => (Sort Option: Follow)] ",
+                "RightOuter Join On #5 = #8 [HashJoin => (Sort Option: None)] ",
+                "TableScan orm_unit_users -> [#5] [SeqScan => (Sort Option: None)] ",
+                "TableScan orm_unit_orders -> [#8] [SeqScan => (Sort Option: None)]"
             ),
             "{right_using_plan}"
         );
@@ -4004,10 +4801,10 @@ mod tests {
         assert_eq!(
             full_using_plan,
             concat!(
-                "Projection [#1] [Project => (Sort Option: Follow)] ",
-                "Full Join On #1 = #4 [HashJoin => (Sort Option: None)] ",
-                "TableScan orm_unit_users -> [#1] [SeqScan => (Sort Option: None)] ",
-                "TableScan orm_unit_orders -> [#4] [SeqScan => (Sort Option: None)]"
+                "Projection [#5] [Project => (Sort Option: Follow)] ",
+                "Full Join On #5 = #8 [HashJoin => (Sort Option: None)] ",
+                "TableScan orm_unit_users -> [#5] [SeqScan => (Sort Option: None)] ",
+                "TableScan orm_unit_orders -> [#8] [SeqScan => (Sort Option: None)]"
             ),
             "{full_using_plan}"
         );
@@ -4032,10 +4829,10 @@ mod tests {
         assert_eq!(
             plan,
             concat!(
-                "Projection [#1] [Project => (Sort Option: Follow)] ",
-                "Inner Join On #1 = #5 [NestLoopJoin => (Sort Option: None)] ",
-                "TableScan orm_unit_users -> [#1] [SeqScan => (Sort Option: None)] ",
-                "TableScan orm_unit_orders -> [#5] [SeqScan => (Sort Option: None)]"
+                "Projection [#5] [Project => (Sort Option: Follow)] ",
+                "Inner Join On #5 = #9 [NestLoopJoin => (Sort Option: None)] ",
+                "TableScan orm_unit_users -> [#5] [SeqScan => (Sort Option: None)] ",
+                "TableScan orm_unit_orders -> [#9] [SeqScan => (Sort Option: None)]"
             ),
             "{plan}"
         );
@@ -4043,6 +4840,68 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn query_builder_force_merge_join() -> Result<(), DatabaseError> {
+        let database = build_orm_unit_database()?;
+
+        let plan = database.explain(|ctx| {
+            ctx.from::<OrmUnitUser>()?
+                .force_merge_join()
+                .inner_join::<OrmUnitOrder, _>(|e| {
+                    e.column(OrmUnitUser::id())?
+                        .eq(e.column(OrmUnitOrder::user_id())?)
+                })?
+                .project_scalar(OrmUnitUser::id())?
+                .finish()
+        })?;
+        assert_eq!(
+            plan,
+            concat!(
+                "Projection [#5] [Project => (Sort Option: Follow)] ",
+                "Inner Join On #5 = #9 [SortMergeJoin => (Sort Option: OrderBy: (#5 Asc Nulls Last) ignore_prefix_len: 0)] ",
+                "Sort By #5 Asc Nulls Last [Sort => (Sort Option: OrderBy: (#5 Asc Nulls Last) ignore_prefix_len: 0)] ",
+                "TableScan orm_unit_users -> [#5] [SeqScan => (Sort Option: None)] ",
+                "Sort By #9 Asc Nulls Last [Sort => (Sort Option: OrderBy: (#9 Asc Nulls Last) ignore_prefix_len: 0)] ",
+                "TableScan orm_unit_orders -> [#9] [SeqScan => (Sort Option: None)]"
+            ),
+            "{plan}"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn query_builder_explain_analyze() -> Result<(), DatabaseError> {
+        let database = build_orm_unit_database()?;
+
+        let plan = database.explain_analyze(|ctx| {
+            ctx.from::<OrmUnitUser>()?
+                .force_nested_loop()
+                .inner_join::<OrmUnitOrder, _>(|e| {
+                    e.column(OrmUnitUser::id())?
+                        .eq(e.column(OrmUnitOrder::user_id())?)
+                })?
+                .project_scalar(OrmUnitUser::id())?
+                .finish()
+        })?;
+        assert!(
+            plan.starts_with(
+                "Projection [#5] [Project => (Sort Option: Follow)] (actual rows: 3, loops: 1, time: "
+            ),
+            "{plan}"
+        );
+        // the inner side of a nested-loop join is rebuilt for every outer row
+        assert!(
+            plan.contains(
+                "TableScan orm_unit_orders -> [#9] [SeqScan => (Sort Option: None)] (actual rows: 9, loops: 3, time: "
+            ),
+            "{plan}"
+        );
+        assert!(plan.ends_with("keys read: 9)"), "{plan}");
+
+        Ok(())
+    }
+
     #[cfg(feature = "spill")]
     #[test]
     fn query_builder_force_spill_aggregate_and_distinct() -> Result<(), DatabaseError> {
@@ -4064,10 +4923,10 @@ mod tests {
         assert_eq!(
             plan,
             concat!(
-                "Projection [#5, #7] [Project => (Sort Option: Follow)] ",
-                "Aggregate [Sum(#6)] -> Group By [#5] [StreamAggregate => (Sort Option: Follow)] ",
-                "Sort By #5 Asc Nulls Last [Sort => (Sort Option: OrderBy: (#5 Asc Nulls Last) ignore_prefix_len: 0)] ",
-                "TableScan orm_unit_orders -> [#5, #6] [SeqScan => (Sort Option: None)]"
+                "Projection [#9, #11] [Project => (Sort Option: Follow)] ",
+                "Aggregate [Sum(#10)] -> Group By [#9] [StreamAggregate => (Sort Option: Follow)] ",
+                "Sort By #9 Asc Nulls Last [Sort => (Sort Option: OrderBy: (#9 Asc Nulls Last) ignore_prefix_len: 0)] ",
+                "TableScan orm_unit_orders -> [#9, #10] [SeqScan => (Sort Option: None)]"
             ),
             "{plan}"
         );
@@ -4082,10 +4941,10 @@ mod tests {
         assert_eq!(
             distinct_plan,
             concat!(
-                "Projection [#5] [Project => (Sort Option: Follow)] ",
-                "Aggregate [] -> Group By [#5] [StreamDistinct => (Sort Option: Follow)] ",
-                "Sort By #5 Asc Nulls Last [Sort => (Sort Option: OrderBy: (#5 Asc Nulls Last) ignore_prefix_len: 0)] ",
-                "TableScan orm_unit_orders -> [#5] [SeqScan => (Sort Option: None)]"
+                "Projection [#9] [Project => (Sort Option: Follow)] ",
+                "Aggregate [] -> Group By [#9] [StreamDistinct => (Sort Option: Follow)] ",
+                "Sort By #9 Asc Nulls Last [Sort => (Sort Option: OrderBy: (#9 Asc Nulls Last) ignore_prefix_len: 0)] ",
+                "TableScan orm_unit_orders -> [#9] [SeqScan => (Sort Option: None)]"
             ),
             "{distinct_plan}"
         );
@@ -13,9 +13,129 @@
 // limitations under the License.
 
 use sqlparser::parser::ParserError;
-use sqlparser::{ast::Statement, dialect::GenericDialect, parser::Parser};
+use sqlparser::tokenizer::Token;
+use sqlparser::{
+    ast::{Expr, Statement},
+    dialect::{Dialect, GenericDialect, Precedence},
+    parser::Parser,
+};
+use std::any::TypeId;
 
-const DIALECT: GenericDialect = GenericDialect {};
+const DIALECT: KiteDialect = KiteDialect(GenericDialect {});
+
+/// [`GenericDialect`] with the JSON operators `->`, `->>`, `#>`, `#>>`, `@>` and `<@`
+/// binding tighter than comparisons, as in PostgreSQL, so `doc -> 'a' = '1'` compares the
+/// extracted value instead of looking up the key `'a' = '1'`. They share `||`'s precedence
+/// and associate left with it, so `doc ->> 'a' || 'x'` appends to the extracted text. Lambdas are left off so
+/// `doc -> 'a'` is always read as the JSON operator.
+#[derive(Debug)]
+struct KiteDialect(GenericDialect);
+
+/// Forwards the `supports_*` flags [`GenericDialect`] overrides, so the wrapper parses
+/// everything it does apart from the operators above. `dialect_matches_generic` checks
+/// a representative statement set against it.
+macro_rules! delegate_flags {
+    ($($name:ident,)*) => {
+        $(
+            fn $name(&self) -> bool {
+                self.0.$name()
+            }
+        )*
+    };
+}
+
+impl Dialect for KiteDialect {
+    fn dialect(&self) -> TypeId {
+        self.0.dialect()
+    }
+
+    fn is_delimited_identifier_start(&self, ch: char) -> bool {
+        self.0.is_delimited_identifier_start(ch)
+    }
+
+    fn is_identifier_start(&self, ch: char) -> bool {
+        self.0.is_identifier_start(ch)
+    }
+
+    fn is_identifier_part(&self, ch: char) -> bool {
+        self.0.is_identifier_part(ch)
+    }
+
+    fn get_next_precedence(&self, parser: &Parser) -> Option<Result<u8, ParserError>> {
+        match parser.peek_token().token {
+            Token::Arrow
+            | Token::LongArrow
+            | Token::HashArrow
+            | Token::HashLongArrow
+            | Token::AtArrow
+            | Token::ArrowAt => Some(Ok(self.prec_value(Precedence::MulDivModOp))),
+            _ => None,
+        }
+    }
+
+    delegate_flags! {
+        supports_unicode_string_literal,
+        supports_group_by_expr,
+        supports_group_by_with_modifier,
+        supports_left_associative_joins_without_parens,
+        supports_connect_by,
+        supports_match_recognize,
+        supports_pipe_operator,
+        supports_start_transaction_modifier,
+        supports_window_function_null_treatment_arg,
+        supports_dictionary_syntax,
+        supports_window_clause_named_window_reference,
+        supports_parenthesized_set_variables,
+        supports_select_wildcard_except,
+        support_map_literal_syntax,
+        allow_extract_custom,
+        allow_extract_single_quotes,
+        supports_extract_comma_syntax,
+        supports_create_view_comment_syntax,
+        supports_parens_around_table_factor,
+        supports_values_as_table_factor,
+        supports_create_index_with_clause,
+        supports_explain_with_utility_options,
+        supports_limit_comma,
+        supports_from_first_select,
+        supports_projection_trailing_commas,
+        supports_asc_desc_in_column_definition,
+        supports_try_convert,
+        supports_bitwise_shift_operators,
+        supports_comment_on,
+        supports_load_extension,
+        supports_named_fn_args_with_assignment_operator,
+        supports_struct_literal,
+        supports_empty_projections,
+        supports_nested_comments,
+        supports_multiline_comment_hints,
+        supports_user_host_grantee,
+        supports_string_escape_constant,
+        supports_array_typedef_with_brackets,
+        supports_match_against,
+        supports_set_names,
+        supports_comma_separated_set_assignments,
+        supports_filter_during_aggregation,
+        supports_select_wildcard_exclude,
+        supports_data_type_signed_suffix,
+        supports_interval_options,
+        supports_quote_delimited_string,
+        supports_select_wildcard_replace,
+        supports_select_wildcard_ilike,
+        supports_select_wildcard_rename,
+        supports_optimize_table,
+        supports_install,
+        supports_detach,
+        supports_prewhere,
+        supports_with_fill,
+        supports_limit_by,
+        supports_interpolate,
+        supports_settings,
+        supports_select_format,
+        supports_comment_optimizer_hint,
+        supports_constraint_keyword_without_name,
+    }
+}
 
 /// Parse a string to a collection of statements.
 ///
@@ -33,10 +153,19 @@ pub fn parse_sql<S: AsRef<str>>(sql: S) -> Result<Vec<Statement>, ParserError> {
     Parser::parse_sql(&DIALECT, sql.as_ref())
 }
 
+/// Parse a string to a single expression.
+pub fn parse_expr<S: AsRef<str>>(sql: S) -> Result<Expr, ParserError> {
+    Parser::new(&DIALECT)
+        .try_with_sql(sql.as_ref())?
+        .parse_expr()
+}
+
 #[cfg(test)]
 mod tests {
-    use super::parse_sql;
-    use sqlparser::ast::{SetExpr, Statement};
+    use super::{parse_expr, parse_sql};
+    use sqlparser::ast::{BinaryOperator, Expr, SetExpr, Statement};
+    use sqlparser::dialect::GenericDialect;
+    use sqlparser::parser::Parser;
 
     #[test]
     fn parses_optimizer_hint() {
@@ -53,4 +182,86 @@ mod tests {
             Some("FORCE_AGG_SPILL")
         );
     }
+
+    #[test]
+    fn json_operators_bind_tighter_than_comparisons() {
+        let Expr::BinaryOp { left, op, .. } = parse_expr("doc -> 'a' = doc ->> 'b'").unwrap()
+        else {
+            panic!("expected binary expression");
+        };
+        assert_eq!(op, BinaryOperator::Eq);
+        assert!(matches!(
+            *left,
+            Expr::BinaryOp {
+                op: BinaryOperator::Arrow,
+                ..
+            }
+        ));
+
+        let Expr::BinaryOp { op, .. } = parse_expr("doc @> '{}' and doc -> 0 + 1 > 2").unwrap()
+        else {
+            panic!("expected binary expression");
+        };
+        assert_eq!(op, BinaryOperator::And);
+    }
+
+    #[test]
+    fn json_operators_share_string_concat_precedence() {
+        let Expr::BinaryOp { left, op, .. } = parse_expr("doc ->> 'a' || 'x'").unwrap() else {
+            panic!("expected binary expression");
+        };
+        assert_eq!(op, BinaryOperator::StringConcat);
+        assert!(matches!(
+            *left,
+            Expr::BinaryOp {
+                op: BinaryOperator::LongArrow,
+                ..
+            }
+        ));
+
+        let Expr::BinaryOp { left, op, .. } = parse_expr("'x' || doc ->> 'a'").unwrap() else {
+            panic!("expected binary expression");
+        };
+        assert_eq!(op, BinaryOperator::LongArrow);
+        assert!(matches!(
+            *left,
+            Expr::BinaryOp {
+                op: BinaryOperator::StringConcat,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn dialect_matches_generic() {
+        let statements = [
+            "SELECT a, b, FROM t",
+            "SELECT * EXCEPT (a) FROM t",
+            "SELECT sum(a) FILTER (WHERE a > 1) FROM t GROUP BY ROLLUP (b, c)",
+            "SELECT * FROM t LIMIT 1, 2",
+            "SELECT row_number() OVER w FROM t WINDOW w AS (PARTITION BY a)",
+            "SELECT a FROM t QUALIFY row_number() OVER (ORDER BY a) = 1",
+            "SELECT U&'\\0041', E'\\n', 1 << 2, extract(year FROM d), {'a': 1}",
+            "SELECT CAST(a AS INT[]), b::VARCHAR FROM t",
+            "SELECT f(a => 1), t.* FROM t NATURAL JOIN s",
+            "SELECT /*+ FORCE_AGG_SPILL */ a /* nested /* comment */ */ FROM t",
+            "SELECT * FROM (VALUES (1), (2)) AS v(a) CROSS JOIN LATERAL unnest(array[a]) u",
+            "WITH RECURSIVE r(n) AS (SELECT 1 UNION ALL SELECT n + 1 FROM r) SELECT * FROM r",
+            "CREATE TABLE t (a INT PRIMARY KEY, b VARCHAR DEFAULT 'x', c INT[] NOT NULL)",
+            "CREATE VIEW v AS SELECT a FROM t",
+            "CREATE INDEX i ON t (a, b)",
+            "INSERT INTO t VALUES (1, 'a') ON CONFLICT (a) DO UPDATE SET b = excluded.b",
+            "UPDATE t SET a = 1, b = 2 WHERE c = 3 RETURNING *",
+            "EXPLAIN (ANALYZE, FORMAT JSON) SELECT 1",
+            "START TRANSACTION; COMMIT",
+            "SET x = 1, y = 2",
+        ];
+        for sql in statements {
+            assert_eq!(
+                parse_sql(sql).unwrap(),
+                Parser::parse_sql(&GenericDialect {}, sql).unwrap(),
+                "{sql}"
+            );
+        }
+    }
 }
@@ -99,17 +99,22 @@ const DUMMY_COLUMN_NAMES: [&str; DUMMY_COLUMN_COUNT] = [
     "ADD COLUMN SUCCESS",
     "CHANGE COLUMN SUCCESS",
     "DROP COLUMN SUCCESS",
+    "ADD CONSTRAINT SUCCESS",
+    "DROP CONSTRAINT SUCCESS",
     "CREATE TABLE SUCCESS",
     "CREATE INDEX SUCCESS",
     "CREATE VIEW SUCCESS",
+    "CREATE SEQUENCE SUCCESS",
     "DROP TABLE SUCCESS",
     "DROP VIEW SUCCESS",
     "DROP INDEX SUCCESS",
+    "DROP SEQUENCE SUCCESS",
     "TRUNCATE TABLE SUCCESS",
     "COPY FROM SOURCE",
     "COPY TO TARGET",
+    "TIMEZONE",
 ];
-const DUMMY_COLUMN_COUNT: usize = 26;
+const DUMMY_COLUMN_COUNT: usize = 31;
 const DUMMY_COLUMN_BASE: usize = usize::MAX - DUMMY_COLUMN_COUNT + 1;
 
 impl TableArenaCell {
@@ -556,6 +561,7 @@ mod tests {
             value_ty: LogicalType::Integer,
             name: name.to_string(),
             ty: IndexType::Normal,
+            json_path: vec![],
         }
     }
 
@@ -0,0 +1,222 @@
+// Copyright 2024 KipData/KiteSQL
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A structured form of a physical plan, produced by `EXPLAIN (FORMAT JSON)` and
+//! [`Database::explain_plan`](crate::db::Database::explain_plan).
+
+use crate::errors::DatabaseError;
+use crate::optimizer::core::cardinality::CardinalityEstimator;
+use crate::planner::operator::join::JoinCondition;
+use crate::planner::operator::Operator;
+use crate::planner::{Childrens, LogicalPlan, PlanArena};
+use std::fmt::Write;
+use std::time::Duration;
+
+/// One operator of a plan together with its children.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExplainNode {
+    /// The kind of the operator, e.g. `Join` or `TableScan`.
+    pub operator: &'static str,
+    /// The operator as printed by the text form of `EXPLAIN`.
+    pub detail: String,
+    /// The algorithm chosen to execute the operator, e.g. `HashJoin` or `IndexScan By ...`.
+    pub physical_option: Option<String>,
+    /// The order the operator produces its rows in.
+    pub sort_option: Option<String>,
+    pub estimated_rows: Option<f64>,
+    /// Cost of the physical option, when it was chosen by cost.
+    pub estimated_cost: Option<usize>,
+    /// Fully qualified names of the output columns.
+    pub columns: Vec<String>,
+    /// Filter predicates, join conditions and subquery correlations evaluated by the operator.
+    pub predicates: Vec<String>,
+    /// Runtime statistics, only reported by `EXPLAIN ANALYZE`.
+    pub actual: Option<ExplainActual>,
+    pub children: Vec<ExplainNode>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExplainActual {
+    /// Rows produced over all loops.
+    pub rows: usize,
+    /// How many times the operator was executed, `0` when it never was.
+    pub loops: usize,
+    pub elapsed: Duration,
+    /// Keys read from storage, only reported for table scans.
+    pub keys_read: Option<usize>,
+}
+
+impl ExplainNode {
+    /// Serializes the tree as a JSON object, children nested under `"children"`.
+    pub fn to_json(&self) -> String {
+        let mut json = String::new();
+        self.write_json(&mut json);
+        json
+    }
+
+    fn write_json(&self, json: &mut String) {
+        json.push_str("{\"operator\":");
+        write_json_str(json, self.operator);
+        json.push_str(",\"detail\":");
+        write_json_str(json, &self.detail);
+        json.push_str(",\"physical_option\":");
+        write_json_opt_str(json, self.physical_option.as_deref());
+        json.push_str(",\"sort_option\":");
+        write_json_opt_str(json, self.sort_option.as_deref());
+        json.push_str(",\"estimated_rows\":");
+        match self.estimated_rows {
+            Some(rows) if rows.is_finite() => {
+                let _ = write!(json, "{rows}");
+            }
+            _ => json.push_str("null"),
+        }
+        json.push_str(",\"estimated_cost\":");
+        match self.estimated_cost {
+            Some(cost) => {
+                let _ = write!(json, "{cost}");
+            }
+            None => json.push_str("null"),
+        }
+        json.push_str(",\"columns\":");
+        write_json_strs(json, &self.columns);
+        json.push_str(",\"predicates\":");
+        write_json_strs(json, &self.predicates);
+        if let Some(actual) = &self.actual {
+            let _ = write!(
+                json,
+                ",\"actual\":{{\"rows\":{},\"loops\":{},\"time_ms\":{:.3}",
+                actual.rows,
+                actual.loops,
+                actual.elapsed.as_secs_f64() * 1000.0
+            );
+            if let Some(keys_read) = actual.keys_read {
+                let _ = write!(json, ",\"keys_read\":{keys_read}");
+            }
+            json.push('}');
+        }
+        json.push_str(",\"children\":[");
+        for (i, child) in self.children.iter().enumerate() {
+            if i > 0 {
+                json.push(',');
+            }
+            child.write_json(json);
+        }
+        json.push_str("]}");
+    }
+}
+
+fn write_json_str(json: &mut String, value: &str) {
+    json.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            c if c < ' ' => {
+                let _ = write!(json, "\\u{:04x}", c as u32);
+            }
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+}
+
+fn write_json_opt_str(json: &mut String, value: Option<&str>) {
+    match value {
+        Some(value) => write_json_str(json, value),
+        None => json.push_str("null"),
+    }
+}
+
+fn write_json_strs(json: &mut String, values: &[String]) {
+    json.push('[');
+    for (i, value) in values.iter().enumerate() {
+        if i > 0 {
+            json.push(',');
+        }
+        write_json_str(json, value);
+    }
+    json.push(']');
+}
+
+impl LogicalPlan {
+    /// Describes the plan as an [`ExplainNode`] tree, with row estimates from `estimator`.
+    pub(crate) fn explain_node(
+        &mut self,
+        arena: &mut PlanArena,
+        estimator: &CardinalityEstimator,
+    ) -> Result<ExplainNode, DatabaseError> {
+        let estimated_rows = estimator.plan_rows(self, arena)?;
+        let columns = self
+            .output_schema(arena)
+            .clone()
+            .into_iter()
+            .map(|column| arena.column(column).full_name())
+            .collect();
+        let mut predicates = Vec::new();
+        match &self.operator {
+            Operator::Filter(op) => predicates.push(op.predicate.output_name(arena)),
+            Operator::Join(op) => {
+                if let JoinCondition::On { on, filter } = &op.on {
+                    for (left_expr, right_expr) in on {
+                        predicates.push(format!(
+                            "{} = {}",
+                            left_expr.output_name(arena),
+                            right_expr.output_name(arena)
+                        ));
+                    }
+                    if let Some(filter) = filter {
+                        predicates.push(filter.output_name(arena));
+                    }
+                }
+            }
+            Operator::MarkApply(op) => {
+                predicates.extend(op.predicates.iter().map(|expr| expr.output_name(arena)));
+            }
+            Operator::LateralApply(op) => {
+                predicates.extend(op.filter.iter().map(|expr| expr.output_name(arena)));
+            }
+            _ => (),
+        }
+        let children = match self.childrens.as_mut() {
+            Childrens::Only(child) => vec![child.explain_node(arena, estimator)?],
+            Childrens::Twins { left, right } => vec![
+                left.explain_node(arena, estimator)?,
+                right.explain_node(arena, estimator)?,
+            ],
+            Childrens::None => vec![],
+        };
+
+        Ok(ExplainNode {
+            operator: self.operator.kind(),
+            detail: self.operator.to_string(),
+            physical_option: self
+                .physical_option
+                .as_ref()
+                .map(|option| option.plan.to_string()),
+            sort_option: self
+                .physical_option
+                .as_ref()
+                .map(|option| option.sort_option().to_string()),
+            estimated_rows,
+            estimated_cost: self.estimated_cost,
+            columns,
+            predicates,
+            actual: None,
+            children,
+        })
+    }
+}
@@ -13,12 +13,18 @@
 // limitations under the License.
 
 mod arena;
+pub mod explain;
 pub mod operator;
 
 use crate::catalog::TableName;
 use crate::errors::DatabaseError;
+use crate::planner::operator::cte_scan::CteScanOperator;
+use crate::planner::operator::delete::DeleteOperator;
+use crate::planner::operator::insert::InsertOperator;
+use crate::planner::operator::recursive_cte::RecursiveCteOperator;
 use crate::planner::operator::set_membership::SetMembershipOperator;
 use crate::planner::operator::union::UnionOperator;
+use crate::planner::operator::update::UpdateOperator;
 use crate::planner::operator::values::ValuesOperator;
 use crate::planner::operator::{Operator, PhysicalOption};
 use kite_sql_serde_macros::ReferenceSerialization;
@@ -97,6 +103,11 @@ pub struct LogicalPlan {
     pub(crate) operator: Operator,
     pub(crate) childrens: Box<Childrens>,
     pub(crate) physical_option: Option<PhysicalOption>,
+    /// Pre-order position of this operator in a plan run by `EXPLAIN ANALYZE`, used to
+    /// attribute runtime statistics back to it.
+    pub(crate) explain_id: Option<usize>,
+    /// Cost the optimizer estimated for `physical_option` when it was chosen by cost.
+    pub(crate) estimated_cost: Option<usize>,
     output_schema: Option<crate::types::tuple::Schema>,
 }
 
@@ -106,6 +117,8 @@ impl LogicalPlan {
             operator,
             childrens: Box::new(childrens),
             physical_option: None,
+            explain_id: None,
+            estimated_cost: None,
             output_schema: None,
         }
     }
@@ -129,6 +142,35 @@ impl LogicalPlan {
         tables
     }
 
+    pub(crate) fn references_cte(&self, table_name: &TableName) -> bool {
+        matches!(&self.operator, Operator::CteScan(op) if &op.table_name == table_name)
+            || self
+                .childrens
+                .iter()
+                .any(|child| child.references_cte(table_name))
+    }
+
+    pub(crate) fn cte_reference_count(&self, table_name: &TableName) -> usize {
+        let count = matches!(&self.operator, Operator::CteScan(op) if &op.table_name == table_name);
+
+        self.childrens
+            .iter()
+            .map(|child| child.cte_reference_count(table_name))
+            .sum::<usize>()
+            + count as usize
+    }
+
+    /// Whether an aggregate function is computed over rows read from the CTE `table_name`.
+    pub(crate) fn aggregates_cte(&self, table_name: &TableName) -> bool {
+        match &self.operator {
+            Operator::Aggregate(op) if !op.agg_calls.is_empty() => self.references_cte(table_name),
+            _ => self
+                .childrens
+                .iter()
+                .any(|child| child.aggregates_cte(table_name)),
+        }
+    }
+
     pub(crate) fn visit_column_refs<A, F>(
         &self,
         arena: &mut A,
@@ -196,14 +238,16 @@ impl LogicalPlan {
                 }
                 _ => unreachable!(),
             },
-            Operator::ScalarApply(_) | Operator::Join(_) => match childrens {
-                Childrens::Twins { left, right } => {
-                    let mut schema = left.output_schema(arena).clone();
-                    schema.extend_from_slice(right.output_schema(arena));
-                    schema
+            Operator::ScalarApply(_) | Operator::LateralApply(_) | Operator::Join(_) => {
+                match childrens {
+                    Childrens::Twins { left, right } => {
+                        let mut schema = left.output_schema(arena).clone();
+                        schema.extend_from_slice(right.output_schema(arena));
+                        schema
+                    }
+                    _ => unreachable!(),
                 }
-                _ => unreachable!(),
-            },
+            }
             Operator::MarkApply(op) => {
                 let mut schema = match childrens {
                     Childrens::Only(left) => left.output_schema(arena).clone(),
@@ -238,11 +282,16 @@ impl LogicalPlan {
             | Operator::SetMembership(SetMembershipOperator {
                 left_schema_ref: schema_ref,
                 ..
-            }) => schema_ref.clone(),
+            })
+            | Operator::RecursiveCte(RecursiveCteOperator {
+                left_schema_ref: schema_ref,
+                ..
+            })
+            | Operator::CteScan(CteScanOperator { schema_ref, .. }) => schema_ref.clone(),
             Operator::Dummy => Vec::new(),
             Operator::ShowTable => Self::dummy_schema(arena, ["TABLE"]),
             Operator::ShowView => Self::dummy_schema(arena, ["VIEW"]),
-            Operator::Explain => Self::dummy_schema(arena, ["PLAN"]),
+            Operator::Explain(_) => Self::dummy_schema(arena, ["PLAN"]),
             Operator::Describe(_) => Self::dummy_schema(
                 arena,
                 [
@@ -255,6 +304,16 @@ impl LogicalPlan {
                     "COLUMN_REF",
                 ],
             ),
+            Operator::Insert(InsertOperator { returning, .. })
+            | Operator::Update(UpdateOperator { returning, .. })
+            | Operator::Delete(DeleteOperator { returning, .. })
+                if !returning.is_empty() =>
+            {
+                returning
+                    .iter()
+                    .map(|expr| expr.output_column_ref(arena))
+                    .collect()
+            }
             Operator::Insert(_) => Self::dummy_schema(arena, ["INSERTED"]),
             Operator::Update(_) => Self::dummy_schema(arena, ["UPDATED"]),
             Operator::Delete(_) => Self::dummy_schema(arena, ["DELETED"]),
@@ -262,12 +321,16 @@ impl LogicalPlan {
             Operator::AddColumn(_) => Self::dummy_schema(arena, ["ADD COLUMN SUCCESS"]),
             Operator::ChangeColumn(_) => Self::dummy_schema(arena, ["CHANGE COLUMN SUCCESS"]),
             Operator::DropColumn(_) => Self::dummy_schema(arena, ["DROP COLUMN SUCCESS"]),
+            Operator::AddConstraint(_) => Self::dummy_schema(arena, ["ADD CONSTRAINT SUCCESS"]),
+            Operator::DropConstraint(_) => Self::dummy_schema(arena, ["DROP CONSTRAINT SUCCESS"]),
             Operator::CreateTable(_) => Self::dummy_schema(arena, ["CREATE TABLE SUCCESS"]),
             Operator::CreateIndex(_) => Self::dummy_schema(arena, ["CREATE INDEX SUCCESS"]),
             Operator::CreateView(_) => Self::dummy_schema(arena, ["CREATE VIEW SUCCESS"]),
+            Operator::CreateSequence(_) => Self::dummy_schema(arena, ["CREATE SEQUENCE SUCCESS"]),
             Operator::DropTable(_) => Self::dummy_schema(arena, ["DROP TABLE SUCCESS"]),
             Operator::DropView(_) => Self::dummy_schema(arena, ["DROP VIEW SUCCESS"]),
             Operator::DropIndex(_) => Self::dummy_schema(arena, ["DROP INDEX SUCCESS"]),
+            Operator::DropSequence(_) => Self::dummy_schema(arena, ["DROP SEQUENCE SUCCESS"]),
             Operator::Truncate(_) => Self::dummy_schema(arena, ["TRUNCATE TABLE SUCCESS"]),
             #[cfg(feature = "copy")]
             Operator::CopyFromFile(_) => Self::dummy_schema(arena, ["COPY FROM SOURCE"]),
@@ -302,22 +365,55 @@ impl LogicalPlan {
         }
     }
 
-    #[allow(clippy::only_used_in_recursion)]
     pub fn explain(&self, arena: &mut PlanArena, indentation: usize) -> String {
+        self.explain_with(arena, indentation, &|_| None)
+    }
+
+    /// Same as [`LogicalPlan::explain`], appending whatever `annotate` returns for an operator
+    /// right after it.
+    #[allow(clippy::only_used_in_recursion)]
+    pub(crate) fn explain_with(
+        &self,
+        arena: &mut PlanArena,
+        indentation: usize,
+        annotate: &dyn Fn(&LogicalPlan) -> Option<String>,
+    ) -> String {
         let mut result = format!("{:indent$}{}", "", self.operator, indent = indentation);
 
         if let Some(physical_option) = &self.physical_option {
             result.push_str(&format!(" [{physical_option}]"));
         }
+        if let Some(annotation) = annotate(self) {
+            result.push_str(&format!(" ({annotation})"));
+        }
 
         for child in self.childrens.iter() {
-            let child = child.explain(arena, indentation + 2);
+            let child = child.explain_with(arena, indentation + 2, annotate);
             result.push(' ');
             result.push_str(child.trim_start());
         }
 
         result
     }
+
+    /// Numbers every operator of the plan in pre-order and returns how many there are.
+    pub(crate) fn assign_explain_ids(&mut self) -> usize {
+        fn assign(plan: &mut LogicalPlan, next_id: &mut usize) {
+            plan.explain_id = Some(*next_id);
+            *next_id += 1;
+            match plan.childrens.as_mut() {
+                Childrens::Only(child) => assign(child, next_id),
+                Childrens::Twins { left, right } => {
+                    assign(left, next_id);
+                    assign(right, next_id);
+                }
+                Childrens::None => (),
+            }
+        }
+        let mut next_id = 0;
+        assign(self, &mut next_id);
+        next_id
+    }
 }
 
 impl Clone for LogicalPlan {
@@ -326,6 +422,8 @@ impl Clone for LogicalPlan {
             operator: self.operator.clone(),
             childrens: self.childrens.clone(),
             physical_option: self.physical_option.clone(),
+            explain_id: self.explain_id,
+            estimated_cost: self.estimated_cost,
             output_schema: None,
         }
     }
@@ -410,6 +508,8 @@ impl crate::serdes::ReferenceSerialization for LogicalPlan {
             operator,
             childrens,
             physical_option,
+            explain_id: None,
+            estimated_cost: None,
             output_schema: None,
         })
     }
@@ -421,6 +521,7 @@ mod tests {
     use super::*;
     use crate::catalog::{ColumnCatalog, ColumnDesc, ColumnRef};
     use crate::planner::operator::describe::DescribeOperator;
+    use crate::planner::operator::explain::ExplainOperator;
     use crate::planner::operator::limit::LimitOperator;
     use crate::planner::operator::table_scan::TableScanOperator;
     use crate::planner::operator::{PlanImpl, SortOption};
@@ -550,7 +651,7 @@ mod tests {
         let cases = [
             (Operator::ShowTable, vec!["TABLE"]),
             (Operator::ShowView, vec!["VIEW"]),
-            (Operator::Explain, vec!["PLAN"]),
+            (Operator::Explain(ExplainOperator::default()), vec!["PLAN"]),
             (
                 Operator::Describe(DescribeOperator {
                     table_name: "users".into(),
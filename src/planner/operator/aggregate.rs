@@ -22,6 +22,9 @@ use std::fmt::Formatter;
 #[derive(Debug, PartialEq, Eq, Clone, Hash, ReferenceSerialization)]
 pub struct AggregateOperator {
     pub groupby_exprs: Vec<ScalarExpression>,
+    /// Positions in `groupby_exprs` of each `GROUPING SETS`, `ROLLUP` or `CUBE` set, empty for
+    /// a plain `GROUP BY`. The group keys outside a set are output as NULL.
+    pub grouping_sets: Vec<Vec<usize>>,
     pub agg_calls: Vec<ScalarExpression>,
     pub is_distinct: bool,
     pub force_spill: bool,
@@ -32,12 +35,14 @@ impl AggregateOperator {
         children: LogicalPlan,
         agg_calls: Vec<ScalarExpression>,
         groupby_exprs: Vec<ScalarExpression>,
+        grouping_sets: Vec<Vec<usize>>,
         is_distinct: bool,
         force_spill: bool,
     ) -> LogicalPlan {
         LogicalPlan::new(
             Operator::Aggregate(Self {
                 groupby_exprs,
+                grouping_sets,
                 agg_calls,
                 is_distinct,
                 force_spill,
@@ -64,6 +69,20 @@ impl fmt::Display for AggregateOperator {
                 .join(", ");
             write!(f, " -> Group By [{groupbys}]")?;
         }
+        if !self.grouping_sets.is_empty() {
+            let sets = self
+                .grouping_sets
+                .iter()
+                .map(|set| {
+                    let exprs = set
+                        .iter()
+                        .map(|index| format!("{}", self.groupby_exprs[*index]))
+                        .join(", ");
+                    format!("({exprs})")
+                })
+                .join(", ");
+            write!(f, " Grouping Sets [{sets}]")?;
+        }
 
         Ok(())
     }
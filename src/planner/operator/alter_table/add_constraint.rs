@@ -0,0 +1,40 @@
+// Copyright 2024 KipData/KiteSQL
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::catalog::{CheckConstraint, TableName};
+use crate::expression::ScalarExpression;
+use kite_sql_serde_macros::ReferenceSerialization;
+use std::fmt;
+use std::fmt::Formatter;
+
+#[derive(Debug, PartialEq, Eq, Clone, Hash, ReferenceSerialization)]
+pub struct AddConstraintOperator {
+    pub table_name: TableName,
+    pub check: CheckConstraint,
+    /// `check` bound against the table columns, verified for every existing row
+    /// before the constraint is stored.
+    pub predicate: ScalarExpression,
+}
+
+impl fmt::Display for AddConstraintOperator {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Add Constraint {} -> {}: {}",
+            self.check.name, self.table_name, self.predicate
+        )?;
+
+        Ok(())
+    }
+}
@@ -13,5 +13,7 @@
 // limitations under the License.
 
 pub mod add_column;
+pub mod add_constraint;
 pub mod change_column;
 pub mod drop_column;
+pub mod drop_constraint;
@@ -14,6 +14,7 @@
 
 use crate::binder::copy::ExtSource;
 use crate::catalog::TableName;
+use crate::expression::ScalarExpression;
 use crate::iter_ext::Itertools;
 use crate::types::tuple::Schema;
 use kite_sql_serde_macros::ReferenceSerialization;
@@ -25,6 +26,8 @@ pub struct CopyFromFileOperator {
     pub table: TableName,
     pub source: ExtSource,
     pub schema_ref: Schema,
+    /// Named `CHECK` constraints of the table, evaluated against each copied row.
+    pub checks: Vec<(String, ScalarExpression)>,
 }
 
 impl fmt::Display for CopyFromFileOperator {
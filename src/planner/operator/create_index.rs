@@ -14,7 +14,7 @@
 
 use crate::catalog::{ColumnRef, TableName};
 use crate::iter_ext::Itertools;
-use crate::types::index::IndexType;
+use crate::types::index::{IndexPathStep, IndexType};
 use kite_sql_serde_macros::ReferenceSerialization;
 use std::fmt;
 use std::fmt::Formatter;
@@ -27,6 +27,8 @@ pub struct CreateIndexOperator {
     pub index_name: String,
     pub if_not_exists: bool,
     pub ty: IndexType,
+    /// The JSON path indexed instead of the value of the only column.
+    pub json_path: Vec<IndexPathStep>,
 }
 
 impl fmt::Display for CreateIndexOperator {
@@ -12,7 +12,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::catalog::{ColumnCatalog, TableName};
+use crate::catalog::sequence::Sequence;
+use crate::catalog::{CheckConstraint, ColumnCatalog, ForeignKey, TableName};
 use crate::iter_ext::Itertools;
 use kite_sql_serde_macros::ReferenceSerialization;
 use std::fmt;
@@ -24,6 +25,12 @@ pub struct CreateTableOperator {
     pub table_name: TableName,
     /// List of columns of the table
     pub columns: Vec<ColumnCatalog>,
+    /// `CHECK` constraints stored with the table
+    pub checks: Vec<CheckConstraint>,
+    /// `FOREIGN KEY` constraints stored with the table
+    pub foreign_keys: Vec<ForeignKey>,
+    /// Sequences of the identity columns, created along with the table
+    pub sequences: Vec<Sequence>,
     pub if_not_exists: bool,
 }
 
@@ -0,0 +1,49 @@
+// Copyright 2024 KipData/KiteSQL
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use crate::catalog::TableName;
+use crate::iter_ext::Itertools;
+use crate::planner::operator::Operator;
+use crate::planner::{Childrens, LogicalPlan};
+use crate::types::tuple::Schema;
+use kite_sql_serde_macros::ReferenceSerialization;
+use std::fmt;
+use std::fmt::Formatter;
+
+#[derive(Debug, PartialEq, Eq, Clone, Hash, ReferenceSerialization)]
+pub struct CteScanOperator {
+    pub table_name: TableName,
+    pub schema_ref: Schema,
+}
+
+impl CteScanOperator {
+    pub fn build(table_name: TableName, schema_ref: Schema) -> LogicalPlan {
+        LogicalPlan::new(
+            Operator::CteScan(CteScanOperator {
+                table_name,
+                schema_ref,
+            }),
+            Childrens::None,
+        )
+    }
+}
+
+impl fmt::Display for CteScanOperator {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let schema = self.schema_ref.iter().join(", ");
+
+        write!(f, "CteScan {}: [{schema}]", self.table_name)?;
+
+        Ok(())
+    }
+}
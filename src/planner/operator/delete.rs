@@ -13,6 +13,8 @@
 // limitations under the License.
 
 use crate::catalog::{ColumnRef, TableName};
+use crate::expression::ScalarExpression;
+use crate::iter_ext::Itertools;
 use kite_sql_serde_macros::ReferenceSerialization;
 use std::fmt;
 use std::fmt::Formatter;
@@ -23,11 +25,16 @@ pub struct DeleteOperator {
     // FIXME
     // for column pruning
     pub primary_keys: Vec<ColumnRef>,
+    /// Evaluated against each deleted row in table column order; empty reports the row count.
+    pub returning: Vec<ScalarExpression>,
 }
 
 impl fmt::Display for DeleteOperator {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
         write!(f, "Delete {}", self.table_name)?;
+        if !self.returning.is_empty() {
+            write!(f, ", Returning: [{}]", self.returning.iter().join(", "))?;
+        }
 
         Ok(())
     }
@@ -0,0 +1,47 @@
+// Copyright 2024 KipData/KiteSQL
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use kite_sql_serde_macros::ReferenceSerialization;
+use std::fmt;
+use std::fmt::Formatter;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, Default, ReferenceSerialization)]
+pub enum ExplainFormat {
+    /// The indented tree of [`LogicalPlan::explain`](crate::planner::LogicalPlan::explain).
+    #[default]
+    Text,
+    /// The [`ExplainNode`](crate::planner::explain::ExplainNode) tree serialized as JSON.
+    Json,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Default, ReferenceSerialization)]
+pub struct ExplainOperator {
+    /// Runs the statement and annotates every operator with its runtime statistics.
+    pub analyze: bool,
+    pub format: ExplainFormat,
+}
+
+impl fmt::Display for ExplainOperator {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "Explain")?;
+        if self.analyze {
+            write!(f, " Analyze")?;
+        }
+        if self.format == ExplainFormat::Json {
+            write!(f, " Format Json")?;
+        }
+
+        Ok(())
+    }
+}
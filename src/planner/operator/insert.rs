@@ -12,7 +12,10 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::catalog::TableName;
+use crate::catalog::{ColumnRef, TableName};
+use crate::expression::ScalarExpression;
+use crate::iter_ext::Itertools;
+use crate::types::index::IndexId;
 use kite_sql_serde_macros::ReferenceSerialization;
 use std::fmt;
 use std::fmt::Formatter;
@@ -22,6 +25,30 @@ pub struct InsertOperator {
     pub table_name: TableName,
     pub is_overwrite: bool,
     pub is_mapping_by_name: bool,
+    pub on_conflict: Option<OnConflict>,
+    /// Evaluated against each written row in table column order; empty reports the row count.
+    pub returning: Vec<ScalarExpression>,
+    /// Named `CHECK` constraints of the table, evaluated against each written row in
+    /// table column order.
+    pub checks: Vec<(String, ScalarExpression)>,
+}
+
+/// Expressions in `DoUpdate` are evaluated against the existing row followed by
+/// the proposed (`EXCLUDED`) row, both laid out in table column order.
+#[derive(Debug, PartialEq, Eq, Clone, Hash, ReferenceSerialization)]
+pub enum ConflictAction {
+    DoNothing,
+    DoUpdate {
+        value_exprs: Vec<(ColumnRef, ScalarExpression)>,
+        predicate: Option<ScalarExpression>,
+    },
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Hash, ReferenceSerialization)]
+pub struct OnConflict {
+    /// `None` matches a conflict on the primary key or any unique index.
+    pub target: Option<IndexId>,
+    pub action: ConflictAction,
 }
 
 impl fmt::Display for InsertOperator {
@@ -31,7 +58,38 @@ impl fmt::Display for InsertOperator {
             "Insert {}, Is Overwrite: {}, Is Mapping By Name: {}",
             self.table_name, self.is_overwrite, self.is_mapping_by_name
         )?;
+        if let Some(on_conflict) = &self.on_conflict {
+            write!(f, ", On Conflict: {on_conflict}")?;
+        }
+        if !self.returning.is_empty() {
+            write!(f, ", Returning: [{}]", self.returning.iter().join(", "))?;
+        }
 
         Ok(())
     }
 }
+
+impl fmt::Display for OnConflict {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        if let Some(target) = self.target {
+            write!(f, "Index #{target} ")?;
+        }
+        match &self.action {
+            ConflictAction::DoNothing => write!(f, "Do Nothing"),
+            ConflictAction::DoUpdate {
+                value_exprs,
+                predicate,
+            } => {
+                let values = value_exprs
+                    .iter()
+                    .map(|(column, expr)| format!("{column} -> {expr}"))
+                    .join(", ");
+                write!(f, "Do Update set {values}")?;
+                if let Some(predicate) = predicate {
+                    write!(f, " where {predicate}")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
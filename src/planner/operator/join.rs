@@ -13,10 +13,14 @@
 // limitations under the License.
 
 use super::{Operator, PlanImpl};
-use crate::expression::ScalarExpression;
+use crate::catalog::ColumnRef;
+use crate::errors::DatabaseError;
+use crate::expression::visitor::{walk_expr, ExprVisitor};
+use crate::expression::{BinaryOperator, ScalarExpression};
 use crate::iter_ext::Itertools;
-use crate::planner::{Childrens, LogicalPlan};
+use crate::planner::{Childrens, LogicalPlan, PlanArena};
 use kite_sql_serde_macros::ReferenceSerialization;
+use std::collections::Bound;
 use std::fmt;
 use std::fmt::Formatter;
 
@@ -51,6 +55,7 @@ pub struct JoinOperator {
     pub on: JoinCondition,
     pub join_type: JoinType,
     pub force_nested_loop: bool,
+    pub force_merge_join: bool,
 }
 
 impl JoinOperator {
@@ -60,12 +65,14 @@ impl JoinOperator {
         on: JoinCondition,
         join_type: JoinType,
         force_nested_loop: bool,
+        force_merge_join: bool,
     ) -> LogicalPlan {
         LogicalPlan::new(
             Operator::Join(JoinOperator {
                 on,
                 join_type,
                 force_nested_loop,
+                force_merge_join,
             }),
             Childrens::Twins {
                 left: Box::new(left),
@@ -77,10 +84,157 @@ impl JoinOperator {
     pub(crate) fn plan_impl(&self) -> PlanImpl {
         match (&self.on, self.force_nested_loop) {
             (_, true) => PlanImpl::NestLoopJoin,
-            (JoinCondition::On { on, .. }, false) if !on.is_empty() => PlanImpl::HashJoin,
+            (JoinCondition::On { on, .. }, false) if !on.is_empty() => {
+                if self.force_merge_join && self.join_type != JoinType::Cross {
+                    PlanImpl::SortMergeJoin
+                } else {
+                    PlanImpl::HashJoin
+                }
+            }
             _ => PlanImpl::NestLoopJoin,
         }
     }
+
+    /// Derives how an index nested-loop join can probe `column` of the right input: with an
+    /// equi key if there is one, otherwise with the comparisons of the join filter.
+    ///
+    /// Probe expressions only read the first `left_len` columns, so they can be evaluated
+    /// against a left tuple alone.
+    pub(crate) fn index_probe(
+        &self,
+        column: ColumnRef,
+        left_len: usize,
+        arena: &PlanArena,
+    ) -> Option<JoinProbe> {
+        if !matches!(self.join_type, JoinType::Inner | JoinType::LeftOuter) {
+            return None;
+        }
+        let JoinCondition::On { on, filter } = &self.on else {
+            return None;
+        };
+        let column_ty = arena.column(column).datatype();
+        for (left_expr, right_expr) in on {
+            if matches!(right_expr.unpack_alias_ref(), ScalarExpression::ColumnRef { column: c, .. } if *c == column)
+                && left_expr.return_type(arena).as_ref() == column_ty
+            {
+                return Some(JoinProbe::Eq(left_expr.clone()));
+            }
+        }
+
+        let mut conjuncts = Vec::new();
+        if let Some(filter) = filter {
+            collect_conjuncts(filter, &mut conjuncts);
+        }
+        let (mut min, mut max) = (Bound::Unbounded, Bound::Unbounded);
+        for conjunct in conjuncts {
+            let ScalarExpression::Binary {
+                op,
+                left_expr,
+                right_expr,
+                ..
+            } = conjunct.unpack_alias_ref()
+            else {
+                continue;
+            };
+            let is_probed = |expr: &ScalarExpression| {
+                matches!(
+                    expr.unpack_alias_ref(),
+                    ScalarExpression::ColumnRef { column: c, position } if *c == column && *position >= left_len
+                )
+            };
+            let (op, bound_expr) = if is_probed(left_expr) {
+                (*op, right_expr.as_ref())
+            } else if is_probed(right_expr) {
+                let Some(op) = flip_comparison(*op) else {
+                    continue;
+                };
+                (op, left_expr.as_ref())
+            } else {
+                continue;
+            };
+            if bound_expr.return_type(arena).as_ref() != column_ty
+                || !reads_left_only(bound_expr, left_len)
+            {
+                continue;
+            }
+            let bound = match op {
+                BinaryOperator::Gt | BinaryOperator::Lt => Bound::Excluded(bound_expr.clone()),
+                BinaryOperator::GtEq | BinaryOperator::LtEq => Bound::Included(bound_expr.clone()),
+                _ => continue,
+            };
+            match op {
+                BinaryOperator::Gt | BinaryOperator::GtEq if matches!(min, Bound::Unbounded) => {
+                    min = bound
+                }
+                BinaryOperator::Lt | BinaryOperator::LtEq if matches!(max, Bound::Unbounded) => {
+                    max = bound
+                }
+                _ => (),
+            }
+        }
+        if matches!((&min, &max), (Bound::Unbounded, Bound::Unbounded)) {
+            return None;
+        }
+        Some(JoinProbe::Scope { min, max })
+    }
+}
+
+/// Lookup an index nested-loop join performs on the right input for every left tuple.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum JoinProbe {
+    Eq(ScalarExpression),
+    Scope {
+        min: Bound<ScalarExpression>,
+        max: Bound<ScalarExpression>,
+    },
+}
+
+fn collect_conjuncts<'a>(expr: &'a ScalarExpression, conjuncts: &mut Vec<&'a ScalarExpression>) {
+    match expr.unpack_alias_ref() {
+        ScalarExpression::Binary {
+            op: BinaryOperator::And,
+            left_expr,
+            right_expr,
+            ..
+        } => {
+            collect_conjuncts(left_expr, conjuncts);
+            collect_conjuncts(right_expr, conjuncts);
+        }
+        expr => conjuncts.push(expr),
+    }
+}
+
+fn flip_comparison(op: BinaryOperator) -> Option<BinaryOperator> {
+    Some(match op {
+        BinaryOperator::Gt => BinaryOperator::Lt,
+        BinaryOperator::GtEq => BinaryOperator::LtEq,
+        BinaryOperator::Lt => BinaryOperator::Gt,
+        BinaryOperator::LtEq => BinaryOperator::GtEq,
+        _ => return None,
+    })
+}
+
+struct LeftOnly {
+    left_len: usize,
+    left_only: bool,
+}
+
+impl ExprVisitor<'_> for LeftOnly {
+    fn visit(&mut self, expr: &ScalarExpression) -> Result<(), DatabaseError> {
+        if let ScalarExpression::ColumnRef { position, .. } = expr {
+            self.left_only &= *position < self.left_len;
+            return Ok(());
+        }
+        walk_expr(self, expr)
+    }
+}
+
+fn reads_left_only(expr: &ScalarExpression, left_len: usize) -> bool {
+    let mut visitor = LeftOnly {
+        left_len,
+        left_only: true,
+    };
+    visitor.visit(expr).is_ok() && visitor.left_only
 }
 
 impl fmt::Display for JoinType {
@@ -143,10 +297,66 @@ mod tests {
             },
             join_type: JoinType::Inner,
             force_nested_loop: false,
+            force_merge_join: false,
         };
         assert_eq!(operator.plan_impl(), PlanImpl::HashJoin);
 
         operator.force_nested_loop = true;
         assert_eq!(operator.plan_impl(), PlanImpl::NestLoopJoin);
     }
+
+    #[test]
+    fn index_probe_prefers_equi_keys_over_filter_ranges() {
+        use crate::catalog::{ColumnCatalog, ColumnDesc};
+        use crate::planner::TableArenaCell;
+        use crate::types::LogicalType;
+
+        let table_arena = TableArenaCell::default();
+        let mut arena = PlanArena::new(&table_arena);
+        let desc = ColumnDesc::new(LogicalType::Integer, None, false, None).unwrap();
+        let left = arena.alloc_column(ColumnCatalog::new("l".to_string(), true, desc.clone()));
+        let right = arena.alloc_column(ColumnCatalog::new("r".to_string(), true, desc));
+        let compare = |op, left_expr, right_expr| ScalarExpression::Binary {
+            op,
+            left_expr: Box::new(left_expr),
+            right_expr: Box::new(right_expr),
+            evaluator: None,
+            ty: LogicalType::Boolean,
+        };
+        let left_expr = ScalarExpression::column_expr(left, 0);
+        let right_expr = ScalarExpression::column_expr(right, 1);
+        let mut operator = JoinOperator {
+            on: JoinCondition::On {
+                on: vec![],
+                filter: Some(compare(
+                    BinaryOperator::And,
+                    compare(BinaryOperator::Lt, left_expr.clone(), right_expr.clone()),
+                    compare(BinaryOperator::LtEq, right_expr.clone(), right_expr.clone()),
+                )),
+            },
+            join_type: JoinType::LeftOuter,
+            force_nested_loop: false,
+            force_merge_join: false,
+        };
+        assert_eq!(
+            operator.index_probe(right, 1, &arena),
+            Some(JoinProbe::Scope {
+                min: Bound::Excluded(left_expr.clone()),
+                max: Bound::Unbounded,
+            })
+        );
+        assert_eq!(operator.index_probe(left, 1, &arena), None);
+
+        let JoinCondition::On { on, .. } = &mut operator.on else {
+            unreachable!()
+        };
+        on.push((left_expr.clone(), ScalarExpression::column_expr(right, 0)));
+        assert_eq!(
+            operator.index_probe(right, 1, &arena),
+            Some(JoinProbe::Eq(left_expr))
+        );
+
+        operator.join_type = JoinType::RightOuter;
+        assert_eq!(operator.index_probe(right, 1, &arena), None);
+    }
 }
@@ -0,0 +1,74 @@
+// Copyright 2024 KipData/KiteSQL
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::Operator;
+use crate::expression::ScalarExpression;
+use crate::iter_ext::Itertools;
+use crate::planner::operator::join::JoinType;
+use crate::planner::{Childrens, LogicalPlan};
+use kite_sql_serde_macros::ReferenceSerialization;
+use std::fmt;
+use std::fmt::Formatter;
+
+/// Joins every left row with the rows of a `LATERAL` right input, which is
+/// re-executed per left row with `parameters` bound to the values of that row.
+#[derive(Debug, PartialEq, Eq, Clone, Hash, ReferenceSerialization)]
+pub struct LateralApplyOperator {
+    /// `Inner`, `Cross` or `LeftOuter`; a left row without matching right rows
+    /// is padded with nulls for `LeftOuter`.
+    pub join_type: JoinType,
+    /// Join condition evaluated over the concatenated left and right row.
+    pub filter: Option<ScalarExpression>,
+    pub parameters: Vec<ScalarExpression>,
+}
+
+impl LateralApplyOperator {
+    pub fn build(
+        left: LogicalPlan,
+        right: LogicalPlan,
+        join_type: JoinType,
+        filter: Option<ScalarExpression>,
+        parameters: Vec<ScalarExpression>,
+    ) -> LogicalPlan {
+        LogicalPlan::new(
+            Operator::LateralApply(LateralApplyOperator {
+                join_type,
+                filter,
+                parameters,
+            }),
+            Childrens::Twins {
+                left: Box::new(left),
+                right: Box::new(right),
+            },
+        )
+    }
+}
+
+impl fmt::Display for LateralApplyOperator {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{} LateralApply", self.join_type)?;
+        if let Some(filter) = &self.filter {
+            write!(f, " Where {filter}")?;
+        }
+        if !self.parameters.is_empty() {
+            let parameters = self
+                .parameters
+                .iter()
+                .map(|expr| format!("{expr}"))
+                .join(", ");
+            write!(f, " Parameters [{parameters}]")?;
+        }
+        Ok(())
+    }
+}
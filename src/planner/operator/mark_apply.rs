@@ -15,6 +15,7 @@
 use super::Operator;
 use crate::catalog::ColumnRef;
 use crate::expression::ScalarExpression;
+use crate::iter_ext::Itertools;
 use crate::planner::{Childrens, LogicalPlan};
 use kite_sql_serde_macros::ReferenceSerialization;
 use std::fmt;
@@ -38,6 +39,9 @@ pub struct MarkApplyOperator {
     pub predicates: Vec<ScalarExpression>,
     output_column: ColumnRef,
     pub parameterized_probe: Option<ScalarExpression>,
+    /// Left columns referenced by a correlated right input whose correlation
+    /// could not be pulled up into `predicates`, see `ScalarApplyOperator`.
+    pub parameters: Vec<ScalarExpression>,
 }
 
 impl MarkApplyOperator {
@@ -47,6 +51,7 @@ impl MarkApplyOperator {
             predicates,
             output_column,
             parameterized_probe: None,
+            parameters: Vec::new(),
         }
     }
 
@@ -79,6 +84,7 @@ impl MarkApplyOperator {
             predicates,
             output_column,
             parameterized_probe: None,
+            parameters: Vec::new(),
         }
     }
 
@@ -136,14 +142,27 @@ impl MarkApplyOperator {
     pub fn set_parameterized_probe(&mut self, probe: Option<ScalarExpression>) {
         self.parameterized_probe = probe;
     }
+
+    pub fn set_parameters(&mut self, parameters: Vec<ScalarExpression>) {
+        self.parameters = parameters;
+    }
 }
 
 impl fmt::Display for MarkApplyOperator {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
         match self.kind {
-            MarkApplyKind::Exists => write!(f, "MarkExistsApply"),
-            MarkApplyKind::Quantified(MarkApplyQuantifier::Any) => write!(f, "MarkAnyApply"),
-            MarkApplyKind::Quantified(MarkApplyQuantifier::All) => write!(f, "MarkAllApply"),
+            MarkApplyKind::Exists => write!(f, "MarkExistsApply")?,
+            MarkApplyKind::Quantified(MarkApplyQuantifier::Any) => write!(f, "MarkAnyApply")?,
+            MarkApplyKind::Quantified(MarkApplyQuantifier::All) => write!(f, "MarkAllApply")?,
+        }
+        if !self.parameters.is_empty() {
+            let parameters = self
+                .parameters
+                .iter()
+                .map(|expr| format!("{expr}"))
+                .join(", ");
+            write!(f, " Parameters [{parameters}]")?;
         }
+        Ok(())
     }
 }
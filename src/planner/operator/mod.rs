@@ -20,20 +20,26 @@ pub mod copy_from_file;
 #[cfg(feature = "copy")]
 pub mod copy_to_file;
 pub mod create_index;
+pub mod create_sequence;
 pub mod create_table;
 pub mod create_view;
+pub mod cte_scan;
 pub mod delete;
 pub mod describe;
 pub mod drop_index;
+pub mod drop_sequence;
 pub mod drop_table;
 pub mod drop_view;
+pub mod explain;
 pub mod filter;
 pub mod function_scan;
 pub mod insert;
 pub mod join;
+pub mod lateral_apply;
 pub mod limit;
 pub mod mark_apply;
 pub mod project;
+pub mod recursive_cte;
 pub mod scalar_apply;
 pub mod scalar_subquery;
 pub mod set_membership;
@@ -51,32 +57,39 @@ pub mod window;
 use self::{
     aggregate::AggregateOperator, alter_table::add_column::AddColumnOperator,
     alter_table::change_column::ChangeColumnOperator, filter::FilterOperator, join::JoinOperator,
-    limit::LimitOperator, mark_apply::MarkApplyOperator, project::ProjectOperator,
-    scalar_apply::ScalarApplyOperator, scalar_subquery::ScalarSubqueryOperator, sort::SortOperator,
-    table_scan::TableScanOperator,
+    lateral_apply::LateralApplyOperator, limit::LimitOperator, mark_apply::MarkApplyOperator,
+    project::ProjectOperator, scalar_apply::ScalarApplyOperator,
+    scalar_subquery::ScalarSubqueryOperator, sort::SortOperator, table_scan::TableScanOperator,
 };
 use crate::catalog::ColumnRef;
 use crate::errors::DatabaseError;
 use crate::expression::visitor::{walk_expr, ExprVisitor};
 use crate::expression::ScalarExpression;
+use crate::planner::operator::alter_table::add_constraint::AddConstraintOperator;
 use crate::planner::operator::alter_table::change_column::DefaultChange as ColumnDefaultChange;
 use crate::planner::operator::alter_table::drop_column::DropColumnOperator;
+use crate::planner::operator::alter_table::drop_constraint::DropConstraintOperator;
 use crate::planner::operator::analyze::AnalyzeOperator;
 #[cfg(feature = "copy")]
 use crate::planner::operator::copy_from_file::CopyFromFileOperator;
 #[cfg(feature = "copy")]
 use crate::planner::operator::copy_to_file::CopyToFileOperator;
 use crate::planner::operator::create_index::CreateIndexOperator;
+use crate::planner::operator::create_sequence::CreateSequenceOperator;
 use crate::planner::operator::create_table::CreateTableOperator;
 use crate::planner::operator::create_view::CreateViewOperator;
+use crate::planner::operator::cte_scan::CteScanOperator;
 use crate::planner::operator::delete::DeleteOperator;
 use crate::planner::operator::describe::DescribeOperator;
 use crate::planner::operator::drop_index::DropIndexOperator;
+use crate::planner::operator::drop_sequence::DropSequenceOperator;
 use crate::planner::operator::drop_table::DropTableOperator;
 use crate::planner::operator::drop_view::DropViewOperator;
+use crate::planner::operator::explain::ExplainOperator;
 use crate::planner::operator::function_scan::FunctionScanOperator;
 use crate::planner::operator::insert::InsertOperator;
 use crate::planner::operator::join::JoinCondition;
+use crate::planner::operator::recursive_cte::RecursiveCteOperator;
 use crate::planner::operator::set_membership::SetMembershipOperator;
 use crate::planner::operator::sort::SortField;
 use crate::planner::operator::top_k::TopKOperator;
@@ -98,6 +111,7 @@ pub enum Operator {
     Aggregate(AggregateOperator),
     ScalarApply(ScalarApplyOperator),
     MarkApply(MarkApplyOperator),
+    LateralApply(LateralApplyOperator),
     Filter(FilterOperator),
     Join(JoinOperator),
     Project(ProjectOperator),
@@ -110,10 +124,12 @@ pub enum Operator {
     Values(ValuesOperator),
     ShowTable,
     ShowView,
-    Explain,
+    Explain(ExplainOperator),
     Describe(DescribeOperator),
     SetMembership(SetMembershipOperator),
     Union(UnionOperator),
+    RecursiveCte(RecursiveCteOperator),
+    CteScan(CteScanOperator),
     // DML
     Insert(InsertOperator),
     Update(UpdateOperator),
@@ -123,12 +139,16 @@ pub enum Operator {
     AddColumn(AddColumnOperator),
     ChangeColumn(ChangeColumnOperator),
     DropColumn(DropColumnOperator),
+    AddConstraint(AddConstraintOperator),
+    DropConstraint(DropConstraintOperator),
     CreateTable(CreateTableOperator),
     CreateIndex(CreateIndexOperator),
     CreateView(CreateViewOperator),
+    CreateSequence(CreateSequenceOperator),
     DropTable(DropTableOperator),
     DropView(DropViewOperator),
     DropIndex(DropIndexOperator),
+    DropSequence(DropSequenceOperator),
     Truncate(TruncateOperator),
     // Copy
     #[cfg(feature = "copy")]
@@ -174,11 +194,15 @@ pub enum PlanImpl {
     HashAggregate,
     StreamAggregate,
     StreamDistinct,
+    SortGroupingSets,
     ScalarApply,
     MarkApply,
+    LateralApply,
     Filter,
     HashJoin,
     NestLoopJoin,
+    IndexNestLoopJoin,
+    SortMergeJoin,
     Project,
     ScalarSubquery,
     SeqScan,
@@ -251,11 +275,21 @@ impl Operator {
                 Ok(())
             }
 
+            fn visit_scalar_apply(
+                &mut self,
+                op: &'operator ScalarApplyOperator,
+            ) -> Result<(), DatabaseError> {
+                for expr in &op.parameters {
+                    ExprVisitor::visit(self, expr)?;
+                }
+                Ok(())
+            }
+
             fn visit_mark_apply(
                 &mut self,
                 op: &'operator MarkApplyOperator,
             ) -> Result<(), DatabaseError> {
-                for expr in &op.predicates {
+                for expr in op.predicates.iter().chain(&op.parameters) {
                     ExprVisitor::visit(self, expr)?;
                 }
                 if let Some(expr) = &op.parameterized_probe {
@@ -264,6 +298,16 @@ impl Operator {
                 Ok(())
             }
 
+            fn visit_lateral_apply(
+                &mut self,
+                op: &'operator LateralApplyOperator,
+            ) -> Result<(), DatabaseError> {
+                for expr in op.filter.iter().chain(&op.parameters) {
+                    ExprVisitor::visit(self, expr)?;
+                }
+                Ok(())
+            }
+
             fn visit_filter(&mut self, op: &'operator FilterOperator) -> Result<(), DatabaseError> {
                 ExprVisitor::visit(self, &op.predicate)
             }
@@ -354,6 +398,26 @@ impl Operator {
                 Ok(())
             }
 
+            fn visit_recursive_cte(
+                &mut self,
+                op: &'operator RecursiveCteOperator,
+            ) -> Result<(), DatabaseError> {
+                for column in op.left_schema_ref.iter().chain(&op._right_schema_ref) {
+                    self.visit_column_ref(column)?;
+                }
+                Ok(())
+            }
+
+            fn visit_cte_scan(
+                &mut self,
+                op: &'operator CteScanOperator,
+            ) -> Result<(), DatabaseError> {
+                for column in &op.schema_ref {
+                    self.visit_column_ref(column)?;
+                }
+                Ok(())
+            }
+
             fn visit_set_membership(
                 &mut self,
                 op: &'operator SetMembershipOperator,
@@ -447,6 +511,60 @@ impl Operator {
     }
 }
 
+impl Operator {
+    /// The name of the operator variant, e.g. `Join` or `TableScan`.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Operator::Dummy => "Dummy",
+            Operator::Aggregate(_) => "Aggregate",
+            Operator::ScalarApply(_) => "ScalarApply",
+            Operator::MarkApply(_) => "MarkApply",
+            Operator::LateralApply(_) => "LateralApply",
+            Operator::Filter(_) => "Filter",
+            Operator::Join(_) => "Join",
+            Operator::Project(_) => "Project",
+            Operator::ScalarSubquery(_) => "ScalarSubquery",
+            Operator::TableScan(_) => "TableScan",
+            Operator::FunctionScan(_) => "FunctionScan",
+            Operator::Sort(_) => "Sort",
+            Operator::Limit(_) => "Limit",
+            Operator::TopK(_) => "TopK",
+            Operator::Values(_) => "Values",
+            Operator::ShowTable => "ShowTable",
+            Operator::ShowView => "ShowView",
+            Operator::Explain(_) => "Explain",
+            Operator::Describe(_) => "Describe",
+            Operator::Insert(_) => "Insert",
+            Operator::Update(_) => "Update",
+            Operator::Delete(_) => "Delete",
+            Operator::Analyze(_) => "Analyze",
+            Operator::AddColumn(_) => "AddColumn",
+            Operator::ChangeColumn(_) => "ChangeColumn",
+            Operator::DropColumn(_) => "DropColumn",
+            Operator::AddConstraint(_) => "AddConstraint",
+            Operator::DropConstraint(_) => "DropConstraint",
+            Operator::CreateTable(_) => "CreateTable",
+            Operator::CreateIndex(_) => "CreateIndex",
+            Operator::CreateView(_) => "CreateView",
+            Operator::DropTable(_) => "DropTable",
+            Operator::DropView(_) => "DropView",
+            Operator::DropIndex(_) => "DropIndex",
+            Operator::CreateSequence(_) => "CreateSequence",
+            Operator::DropSequence(_) => "DropSequence",
+            Operator::Truncate(_) => "Truncate",
+            #[cfg(feature = "copy")]
+            Operator::CopyFromFile(_) => "CopyFromFile",
+            #[cfg(feature = "copy")]
+            Operator::CopyToFile(_) => "CopyToFile",
+            Operator::Union(_) => "Union",
+            Operator::RecursiveCte(_) => "RecursiveCte",
+            Operator::CteScan(_) => "CteScan",
+            Operator::SetMembership(_) => "SetMembership",
+            Operator::Window(_) => "Window",
+        }
+    }
+}
+
 impl fmt::Display for Operator {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
         match self {
@@ -454,6 +572,7 @@ impl fmt::Display for Operator {
             Operator::Aggregate(op) => write!(f, "{op}"),
             Operator::ScalarApply(op) => write!(f, "{op}"),
             Operator::MarkApply(op) => write!(f, "{op}"),
+            Operator::LateralApply(op) => write!(f, "{op}"),
             Operator::Filter(op) => write!(f, "{op}"),
             Operator::Join(op) => write!(f, "{op}"),
             Operator::Project(op) => write!(f, "{op}"),
@@ -466,7 +585,7 @@ impl fmt::Display for Operator {
             Operator::Values(op) => write!(f, "{op}"),
             Operator::ShowTable => write!(f, "Show Tables"),
             Operator::ShowView => write!(f, "Show Views"),
-            Operator::Explain => unreachable!(),
+            Operator::Explain(op) => write!(f, "{op}"),
             Operator::Describe(op) => write!(f, "{op}"),
             Operator::Insert(op) => write!(f, "{op}"),
             Operator::Update(op) => write!(f, "{op}"),
@@ -475,18 +594,24 @@ impl fmt::Display for Operator {
             Operator::AddColumn(op) => write!(f, "{op}"),
             Operator::ChangeColumn(op) => write!(f, "{op}"),
             Operator::DropColumn(op) => write!(f, "{op}"),
+            Operator::AddConstraint(op) => write!(f, "{op}"),
+            Operator::DropConstraint(op) => write!(f, "{op}"),
             Operator::CreateTable(op) => write!(f, "{op}"),
             Operator::CreateIndex(op) => write!(f, "{op}"),
             Operator::CreateView(op) => write!(f, "{op}"),
             Operator::DropTable(op) => write!(f, "{op}"),
             Operator::DropView(op) => write!(f, "{op}"),
             Operator::DropIndex(op) => write!(f, "{op}"),
+            Operator::CreateSequence(op) => write!(f, "{op}"),
+            Operator::DropSequence(op) => write!(f, "{op}"),
             Operator::Truncate(op) => write!(f, "{op}"),
             #[cfg(feature = "copy")]
             Operator::CopyFromFile(op) => write!(f, "{op}"),
             #[cfg(feature = "copy")]
             Operator::CopyToFile(op) => write!(f, "{op}"),
             Operator::Union(op) => write!(f, "{op}"),
+            Operator::RecursiveCte(op) => write!(f, "{op}"),
+            Operator::CteScan(op) => write!(f, "{op}"),
             Operator::SetMembership(op) => write!(f, "{op}"),
             Operator::Window(op) => write!(f, "{op}"),
         }
@@ -530,11 +655,15 @@ impl fmt::Display for PlanImpl {
             PlanImpl::HashAggregate => write!(f, "HashAggregate"),
             PlanImpl::StreamAggregate => write!(f, "StreamAggregate"),
             PlanImpl::StreamDistinct => write!(f, "StreamDistinct"),
+            PlanImpl::SortGroupingSets => write!(f, "SortGroupingSets"),
             PlanImpl::ScalarApply => write!(f, "ScalarApply"),
             PlanImpl::MarkApply => write!(f, "MarkApply"),
+            PlanImpl::LateralApply => write!(f, "LateralApply"),
             PlanImpl::Filter => write!(f, "Filter"),
             PlanImpl::HashJoin => write!(f, "HashJoin"),
             PlanImpl::NestLoopJoin => write!(f, "NestLoopJoin"),
+            PlanImpl::IndexNestLoopJoin => write!(f, "IndexNestLoopJoin"),
+            PlanImpl::SortMergeJoin => write!(f, "SortMergeJoin"),
             PlanImpl::Project => write!(f, "Project"),
             PlanImpl::ScalarSubquery => write!(f, "ScalarSubquery"),
             PlanImpl::SeqScan => write!(f, "SeqScan"),
@@ -569,7 +698,7 @@ impl fmt::Display for PlanImpl {
 mod tests {
     use super::*;
     use crate::catalog::view::View;
-    use crate::catalog::{ColumnCatalog, ColumnDesc, ColumnRef};
+    use crate::catalog::{CheckConstraint, ColumnCatalog, ColumnDesc, ColumnRef};
     use crate::expression::function::table::{
         ArcTableFunctionImpl, TableFunction, TableFunctionCatalog, TableFunctionImpl,
     };
@@ -577,6 +706,7 @@ mod tests {
     use crate::function::numbers::Numbers;
     use crate::planner::operator::alter_table::change_column::{DefaultChange, NotNullChange};
     use crate::planner::operator::delete::DeleteOperator;
+    use crate::planner::operator::insert::{ConflictAction, OnConflict};
     use crate::planner::operator::mark_apply::MarkApplyQuantifier;
     use crate::planner::operator::set_membership::SetMembershipKind;
     use crate::planner::operator::sort::SortField;
@@ -657,11 +787,15 @@ mod tests {
             (PlanImpl::HashAggregate, "HashAggregate"),
             (PlanImpl::StreamAggregate, "StreamAggregate"),
             (PlanImpl::StreamDistinct, "StreamDistinct"),
+            (PlanImpl::SortGroupingSets, "SortGroupingSets"),
             (PlanImpl::ScalarApply, "ScalarApply"),
             (PlanImpl::MarkApply, "MarkApply"),
+            (PlanImpl::LateralApply, "LateralApply"),
             (PlanImpl::Filter, "Filter"),
             (PlanImpl::HashJoin, "HashJoin"),
             (PlanImpl::NestLoopJoin, "NestLoopJoin"),
+            (PlanImpl::IndexNestLoopJoin, "IndexNestLoopJoin"),
+            (PlanImpl::SortMergeJoin, "SortMergeJoin"),
             (PlanImpl::Project, "Project"),
             (PlanImpl::ScalarSubquery, "ScalarSubquery"),
             (PlanImpl::SeqScan, "SeqScan"),
@@ -719,6 +853,7 @@ mod tests {
         let delete = Operator::Delete(DeleteOperator {
             table_name: "users".into(),
             primary_keys: vec![left],
+            returning: vec![],
         });
         assert!(delete.any_referenced_column(&mut arena, |column| *column == left)?);
         assert!(Operator::Dummy.all_referenced_columns(&mut arena, |_| false)?);
@@ -738,6 +873,7 @@ mod tests {
         let aggregate = Operator::Aggregate(AggregateOperator {
             agg_calls: vec![column_expr(a, 0)],
             groupby_exprs: vec![column_expr(b, 1)],
+            grouping_sets: Vec::new(),
             is_distinct: false,
             force_spill: false,
         });
@@ -750,6 +886,13 @@ mod tests {
             vec![c, d]
         );
 
+        let lateral_apply = Operator::LateralApply(LateralApplyOperator {
+            join_type: join::JoinType::LeftOuter,
+            filter: Some(column_expr(a, 0)),
+            parameters: vec![column_expr(b, 1)],
+        });
+        assert_eq!(referenced_columns(&lateral_apply, &mut arena)?, vec![a, b]);
+
         let filter = Operator::Filter(FilterOperator {
             predicate: column_expr(a, 0),
             is_optimized: false,
@@ -760,6 +903,7 @@ mod tests {
         let join = Operator::Join(JoinOperator {
             join_type: join::JoinType::Inner,
             force_nested_loop: false,
+            force_merge_join: false,
             on: JoinCondition::On {
                 on: vec![(column_expr(a, 0), column_expr(b, 1))],
                 filter: Some(column_expr(c, 2)),
@@ -776,6 +920,8 @@ mod tests {
         let update = Operator::Update(UpdateOperator {
             table_name: "users".into(),
             value_exprs: vec![(b, column_expr(a, 0))],
+            returning: vec![],
+            checks: vec![],
         });
         assert_eq!(referenced_columns(&update, &mut arena)?, vec![a]);
 
@@ -817,6 +963,9 @@ mod tests {
                     Some(ScalarExpression::from(2_i32)),
                 )?,
             )],
+            checks: vec![],
+            foreign_keys: vec![],
+            sequences: vec![],
             if_not_exists: false,
         });
         assert!(referenced_columns(&create_table, &mut arena)?.is_empty());
@@ -869,11 +1018,12 @@ mod tests {
         let delete = Operator::Delete(DeleteOperator {
             table_name: "users".into(),
             primary_keys: vec![a],
+            returning: vec![],
         });
         assert_eq!(referenced_columns(&delete, &mut arena)?, vec![a]);
 
         let no_reference_operators = [
-            Operator::ScalarApply(ScalarApplyOperator),
+            Operator::ScalarApply(ScalarApplyOperator::default()),
             Operator::ScalarSubquery(ScalarSubqueryOperator),
             Operator::Analyze(AnalyzeOperator {
                 table_name: "users".into(),
@@ -951,6 +1101,9 @@ mod tests {
                 Operator::CreateTable(CreateTableOperator {
                     table_name: "users".into(),
                     columns: vec![column_catalog("id"), column_catalog("name")],
+                    checks: vec![],
+                    foreign_keys: vec![],
+                    sequences: vec![],
                     if_not_exists: true,
                 }),
                 "Create users -> [id, name], If Not Exists: true",
@@ -962,6 +1115,7 @@ mod tests {
                     index_name: "idx_users_name".to_string(),
                     if_not_exists: false,
                     ty: IndexType::Normal,
+                    json_path: vec![],
                 }),
                 "Create Index On users -> [#0, #1], If Not Exists: false",
             ),
@@ -994,6 +1148,25 @@ mod tests {
                 }),
                 "Drop age -> users, If Exists: true",
             ),
+            (
+                Operator::AddConstraint(AddConstraintOperator {
+                    table_name: "users".into(),
+                    check: CheckConstraint {
+                        name: "users_check".to_string(),
+                        expr: "1".to_string(),
+                    },
+                    predicate: ScalarExpression::from(1_i32),
+                }),
+                "Add Constraint users_check -> users: 1",
+            ),
+            (
+                Operator::DropConstraint(DropConstraintOperator {
+                    table_name: "users".into(),
+                    constraint_name: "users_check".to_string(),
+                    if_exists: true,
+                }),
+                "Drop Constraint users_check -> users, If Exists: true",
+            ),
             (
                 Operator::AddColumn(AddColumnOperator {
                     table_name: "users".into(),
@@ -1038,13 +1211,35 @@ mod tests {
                     table_name: "users".into(),
                     is_overwrite: true,
                     is_mapping_by_name: false,
+                    on_conflict: None,
+                    returning: vec![],
+                    checks: vec![],
                 }),
                 "Insert users, Is Overwrite: true, Is Mapping By Name: false",
             ),
+            (
+                Operator::Insert(InsertOperator {
+                    table_name: "users".into(),
+                    is_overwrite: false,
+                    is_mapping_by_name: true,
+                    on_conflict: Some(OnConflict {
+                        target: Some(1),
+                        action: ConflictAction::DoUpdate {
+                            value_exprs: vec![(id, ScalarExpression::from(7_i32))],
+                            predicate: None,
+                        },
+                    }),
+                    returning: vec![],
+                    checks: vec![],
+                }),
+                "Insert users, Is Overwrite: false, Is Mapping By Name: true, On Conflict: Index #1 Do Update set #0 -> 7",
+            ),
             (
                 Operator::Update(UpdateOperator {
                     table_name: "users".into(),
                     value_exprs: vec![(id, ScalarExpression::from(7_i32))],
+                    returning: vec![],
+                    checks: vec![],
                 }),
                 "Update users set #0 -> 7",
             ),
@@ -1052,8 +1247,9 @@ mod tests {
                 Operator::Delete(DeleteOperator {
                     table_name: "users".into(),
                     primary_keys: vec![id],
+                    returning: vec![column_expr(id, 0)],
                 }),
-                "Delete users",
+                "Delete users, Returning: [#0]",
             ),
             (
                 Operator::Describe(DescribeOperator {
@@ -1225,6 +1421,7 @@ mod tests {
                 },
             },
             schema_ref: vec![id, name],
+            checks: vec![],
         });
 
         assert_eq!(
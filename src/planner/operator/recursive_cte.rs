@@ -0,0 +1,69 @@
+// Copyright 2024 KipData/KiteSQL
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use crate::catalog::TableName;
+use crate::iter_ext::Itertools;
+use crate::planner::operator::Operator;
+use crate::planner::{Childrens, LogicalPlan};
+use crate::types::tuple::Schema;
+use kite_sql_serde_macros::ReferenceSerialization;
+use std::fmt;
+use std::fmt::Formatter;
+
+/// Upper bound on how many times the recursive term of a `WITH RECURSIVE` may be re-evaluated.
+pub const MAX_RECURSIVE_CTE_ITERATIONS: usize = 1000;
+
+#[derive(Debug, PartialEq, Eq, Clone, Hash, ReferenceSerialization)]
+pub struct RecursiveCteOperator {
+    // working table read by `CteScan` inside the recursive term
+    pub table_name: TableName,
+    pub is_all: bool,
+    pub left_schema_ref: Schema,
+    // mainly use `left_schema` as output and `right_schema` for `column pruning`
+    pub _right_schema_ref: Schema,
+}
+
+impl RecursiveCteOperator {
+    pub fn build(
+        table_name: TableName,
+        is_all: bool,
+        left_schema_ref: Schema,
+        right_schema_ref: Schema,
+        anchor_plan: LogicalPlan,
+        recursive_plan: LogicalPlan,
+    ) -> LogicalPlan {
+        LogicalPlan::new(
+            Operator::RecursiveCte(RecursiveCteOperator {
+                table_name,
+                is_all,
+                left_schema_ref,
+                _right_schema_ref: right_schema_ref,
+            }),
+            Childrens::Twins {
+                left: Box::new(anchor_plan),
+                right: Box::new(recursive_plan),
+            },
+        )
+    }
+}
+
+impl fmt::Display for RecursiveCteOperator {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let schema = self.left_schema_ref.iter().join(", ");
+        let union = if self.is_all { "Union All" } else { "Union" };
+
+        write!(f, "RecursiveCte {} {union}: [{schema}]", self.table_name)?;
+
+        Ok(())
+    }
+}
@@ -13,18 +13,32 @@
 // limitations under the License.
 
 use super::Operator;
+use crate::expression::ScalarExpression;
+use crate::iter_ext::Itertools;
 use crate::planner::{Childrens, LogicalPlan};
 use kite_sql_serde_macros::ReferenceSerialization;
 use std::fmt;
 use std::fmt::Formatter;
 
-#[derive(Debug, PartialEq, Eq, Clone, Hash, ReferenceSerialization)]
-pub struct ScalarApplyOperator;
+#[derive(Debug, PartialEq, Eq, Clone, Default, Hash, ReferenceSerialization)]
+pub struct ScalarApplyOperator {
+    /// Left columns referenced by a correlated right input, which is then
+    /// re-executed for every left row with these columns bound to its values.
+    pub parameters: Vec<ScalarExpression>,
+}
 
 impl ScalarApplyOperator {
     pub fn build(left: LogicalPlan, right: LogicalPlan) -> LogicalPlan {
+        Self::build_parameterized(left, right, Vec::new())
+    }
+
+    pub fn build_parameterized(
+        left: LogicalPlan,
+        right: LogicalPlan,
+        parameters: Vec<ScalarExpression>,
+    ) -> LogicalPlan {
         LogicalPlan::new(
-            Operator::ScalarApply(ScalarApplyOperator),
+            Operator::ScalarApply(ScalarApplyOperator { parameters }),
             Childrens::Twins {
                 left: Box::new(left),
                 right: Box::new(right),
@@ -35,6 +49,15 @@ impl ScalarApplyOperator {
 
 impl fmt::Display for ScalarApplyOperator {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
-        write!(f, "ScalarApply")
+        write!(f, "ScalarApply")?;
+        if !self.parameters.is_empty() {
+            let parameters = self
+                .parameters
+                .iter()
+                .map(|expr| format!("{expr}"))
+                .join(", ");
+            write!(f, " Parameters [{parameters}]")?;
+        }
+        Ok(())
     }
 }
@@ -16,6 +16,7 @@ use super::{Operator, SortOption};
 use crate::catalog::{ColumnRef, TableCatalog, TableName};
 use crate::errors::DatabaseError;
 use crate::expression::ScalarExpression;
+use crate::function::json_path::json_path_expr;
 use crate::iter_ext::Itertools;
 use crate::planner::operator::sort::SortField;
 use crate::planner::{Childrens, LogicalPlan, PlanArena};
@@ -57,7 +58,10 @@ impl TableScanOperator {
                     DatabaseError::column_not_found(format!("index column id: {col_id} not found"))
                 })?;
                 sort_fields.push(SortField {
-                    expr: ScalarExpression::column_expr(column_ref, sort_fields.len()),
+                    expr: json_path_expr(
+                        ScalarExpression::column_expr(column_ref, sort_fields.len()),
+                        &index_meta.json_path,
+                    )?,
                     asc: true,
                     nulls_first: false,
                 })
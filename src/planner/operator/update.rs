@@ -23,6 +23,11 @@ use std::fmt::Formatter;
 pub struct UpdateOperator {
     pub table_name: TableName,
     pub value_exprs: Vec<(ColumnRef, ScalarExpression)>,
+    /// Evaluated against each updated row in table column order; empty reports the row count.
+    pub returning: Vec<ScalarExpression>,
+    /// Named `CHECK` constraints of the table, evaluated against each updated row in
+    /// table column order.
+    pub checks: Vec<(String, ScalarExpression)>,
 }
 
 impl fmt::Display for UpdateOperator {
@@ -33,6 +38,9 @@ impl fmt::Display for UpdateOperator {
             .map(|(column, expr)| format!("{column} -> {expr}"))
             .join(", ");
         write!(f, "Update {} set {}", self.table_name, values)?;
+        if !self.returning.is_empty() {
+            write!(f, ", Returning: [{}]", self.returning.iter().join(", "))?;
+        }
 
         Ok(())
     }
@@ -16,6 +16,7 @@ use super::alter_table::change_column::DefaultChange;
 use super::*;
 use crate::errors::DatabaseError;
 use crate::expression::visitor::ExprVisitor;
+use crate::planner::operator::insert::{ConflictAction, OnConflict};
 
 pub trait OperatorVisitor<'a>: Sized {
     fn visit_operator(&mut self, operator: &'a Operator) -> Result<(), DatabaseError> {
@@ -38,6 +39,10 @@ pub trait OperatorVisitor<'a>: Sized {
         Ok(())
     }
 
+    fn visit_lateral_apply(&mut self, _op: &'a LateralApplyOperator) -> Result<(), DatabaseError> {
+        Ok(())
+    }
+
     fn visit_filter(&mut self, _op: &'a FilterOperator) -> Result<(), DatabaseError> {
         Ok(())
     }
@@ -93,7 +98,7 @@ pub trait OperatorVisitor<'a>: Sized {
         Ok(())
     }
 
-    fn visit_explain(&mut self) -> Result<(), DatabaseError> {
+    fn visit_explain(&mut self, _op: &'a ExplainOperator) -> Result<(), DatabaseError> {
         Ok(())
     }
 
@@ -112,6 +117,14 @@ pub trait OperatorVisitor<'a>: Sized {
         Ok(())
     }
 
+    fn visit_recursive_cte(&mut self, _op: &'a RecursiveCteOperator) -> Result<(), DatabaseError> {
+        Ok(())
+    }
+
+    fn visit_cte_scan(&mut self, _op: &'a CteScanOperator) -> Result<(), DatabaseError> {
+        Ok(())
+    }
+
     fn visit_insert(&mut self, _op: &'a InsertOperator) -> Result<(), DatabaseError> {
         Ok(())
     }
@@ -140,6 +153,20 @@ pub trait OperatorVisitor<'a>: Sized {
         Ok(())
     }
 
+    fn visit_add_constraint(
+        &mut self,
+        _op: &'a AddConstraintOperator,
+    ) -> Result<(), DatabaseError> {
+        Ok(())
+    }
+
+    fn visit_drop_constraint(
+        &mut self,
+        _op: &'a DropConstraintOperator,
+    ) -> Result<(), DatabaseError> {
+        Ok(())
+    }
+
     fn visit_create_table(&mut self, _op: &'a CreateTableOperator) -> Result<(), DatabaseError> {
         Ok(())
     }
@@ -152,6 +179,13 @@ pub trait OperatorVisitor<'a>: Sized {
         Ok(())
     }
 
+    fn visit_create_sequence(
+        &mut self,
+        _op: &'a CreateSequenceOperator,
+    ) -> Result<(), DatabaseError> {
+        Ok(())
+    }
+
     fn visit_drop_table(&mut self, _op: &'a DropTableOperator) -> Result<(), DatabaseError> {
         Ok(())
     }
@@ -164,6 +198,10 @@ pub trait OperatorVisitor<'a>: Sized {
         Ok(())
     }
 
+    fn visit_drop_sequence(&mut self, _op: &'a DropSequenceOperator) -> Result<(), DatabaseError> {
+        Ok(())
+    }
+
     fn visit_truncate(&mut self, _op: &'a TruncateOperator) -> Result<(), DatabaseError> {
         Ok(())
     }
@@ -197,8 +235,15 @@ impl<'a, V: ExprVisitor<'a>> OperatorVisitor<'a> for OperatorExprVisitor<'_, V>
         Ok(())
     }
 
+    fn visit_scalar_apply(&mut self, op: &'a ScalarApplyOperator) -> Result<(), DatabaseError> {
+        for expr in &op.parameters {
+            ExprVisitor::visit(self.visitor, expr)?;
+        }
+        Ok(())
+    }
+
     fn visit_mark_apply(&mut self, op: &'a MarkApplyOperator) -> Result<(), DatabaseError> {
-        for expr in &op.predicates {
+        for expr in op.predicates.iter().chain(&op.parameters) {
             ExprVisitor::visit(self.visitor, expr)?;
         }
         if let Some(expr) = &op.parameterized_probe {
@@ -207,6 +252,13 @@ impl<'a, V: ExprVisitor<'a>> OperatorVisitor<'a> for OperatorExprVisitor<'_, V>
         Ok(())
     }
 
+    fn visit_lateral_apply(&mut self, op: &'a LateralApplyOperator) -> Result<(), DatabaseError> {
+        for expr in op.filter.iter().chain(&op.parameters) {
+            ExprVisitor::visit(self.visitor, expr)?;
+        }
+        Ok(())
+    }
+
     fn visit_filter(&mut self, op: &'a FilterOperator) -> Result<(), DatabaseError> {
         ExprVisitor::visit(self.visitor, &op.predicate)
     }
@@ -278,8 +330,45 @@ impl<'a, V: ExprVisitor<'a>> OperatorVisitor<'a> for OperatorExprVisitor<'_, V>
         Ok(())
     }
 
+    fn visit_insert(&mut self, op: &'a InsertOperator) -> Result<(), DatabaseError> {
+        if let Some(OnConflict {
+            action:
+                ConflictAction::DoUpdate {
+                    value_exprs,
+                    predicate,
+                },
+            ..
+        }) = &op.on_conflict
+        {
+            for expr in value_exprs.iter().map(|(_, expr)| expr).chain(predicate) {
+                ExprVisitor::visit(self.visitor, expr)?;
+            }
+        }
+        for expr in op
+            .returning
+            .iter()
+            .chain(op.checks.iter().map(|(_, expr)| expr))
+        {
+            ExprVisitor::visit(self.visitor, expr)?;
+        }
+        Ok(())
+    }
+
     fn visit_update(&mut self, op: &'a UpdateOperator) -> Result<(), DatabaseError> {
-        for (_, expr) in &op.value_exprs {
+        for expr in op
+            .value_exprs
+            .iter()
+            .map(|(_, expr)| expr)
+            .chain(op.checks.iter().map(|(_, expr)| expr))
+            .chain(&op.returning)
+        {
+            ExprVisitor::visit(self.visitor, expr)?;
+        }
+        Ok(())
+    }
+
+    fn visit_delete(&mut self, op: &'a DeleteOperator) -> Result<(), DatabaseError> {
+        for expr in &op.returning {
             ExprVisitor::visit(self.visitor, expr)?;
         }
         Ok(())
@@ -299,6 +388,10 @@ impl<'a, V: ExprVisitor<'a>> OperatorVisitor<'a> for OperatorExprVisitor<'_, V>
         Ok(())
     }
 
+    fn visit_add_constraint(&mut self, op: &'a AddConstraintOperator) -> Result<(), DatabaseError> {
+        ExprVisitor::visit(self.visitor, &op.predicate)
+    }
+
     fn visit_create_table(&mut self, op: &'a CreateTableOperator) -> Result<(), DatabaseError> {
         for column in &op.columns {
             if let Some(expr) = &column.desc().default {
@@ -307,6 +400,14 @@ impl<'a, V: ExprVisitor<'a>> OperatorVisitor<'a> for OperatorExprVisitor<'_, V>
         }
         Ok(())
     }
+
+    #[cfg(feature = "copy")]
+    fn visit_copy_from_file(&mut self, op: &'a CopyFromFileOperator) -> Result<(), DatabaseError> {
+        for (_, expr) in &op.checks {
+            ExprVisitor::visit(self.visitor, expr)?;
+        }
+        Ok(())
+    }
 }
 
 pub fn walk_operator<'a, V: OperatorVisitor<'a>>(
@@ -318,6 +419,7 @@ pub fn walk_operator<'a, V: OperatorVisitor<'a>>(
         Operator::Aggregate(op) => visitor.visit_aggregate(op),
         Operator::ScalarApply(op) => visitor.visit_scalar_apply(op),
         Operator::MarkApply(op) => visitor.visit_mark_apply(op),
+        Operator::LateralApply(op) => visitor.visit_lateral_apply(op),
         Operator::Filter(op) => visitor.visit_filter(op),
         Operator::Join(op) => visitor.visit_join(op),
         Operator::Project(op) => visitor.visit_project(op),
@@ -330,10 +432,12 @@ pub fn walk_operator<'a, V: OperatorVisitor<'a>>(
         Operator::Values(op) => visitor.visit_values(op),
         Operator::ShowTable => visitor.visit_show_table(),
         Operator::ShowView => visitor.visit_show_view(),
-        Operator::Explain => visitor.visit_explain(),
+        Operator::Explain(op) => visitor.visit_explain(op),
         Operator::Describe(op) => visitor.visit_describe(op),
         Operator::SetMembership(op) => visitor.visit_set_membership(op),
         Operator::Union(op) => visitor.visit_union(op),
+        Operator::RecursiveCte(op) => visitor.visit_recursive_cte(op),
+        Operator::CteScan(op) => visitor.visit_cte_scan(op),
         Operator::Insert(op) => visitor.visit_insert(op),
         Operator::Update(op) => visitor.visit_update(op),
         Operator::Delete(op) => visitor.visit_delete(op),
@@ -341,12 +445,16 @@ pub fn walk_operator<'a, V: OperatorVisitor<'a>>(
         Operator::AddColumn(op) => visitor.visit_add_column(op),
         Operator::ChangeColumn(op) => visitor.visit_change_column(op),
         Operator::DropColumn(op) => visitor.visit_drop_column(op),
+        Operator::AddConstraint(op) => visitor.visit_add_constraint(op),
+        Operator::DropConstraint(op) => visitor.visit_drop_constraint(op),
         Operator::CreateTable(op) => visitor.visit_create_table(op),
         Operator::CreateIndex(op) => visitor.visit_create_index(op),
         Operator::CreateView(op) => visitor.visit_create_view(op),
+        Operator::CreateSequence(op) => visitor.visit_create_sequence(op),
         Operator::DropTable(op) => visitor.visit_drop_table(op),
         Operator::DropView(op) => visitor.visit_drop_view(op),
         Operator::DropIndex(op) => visitor.visit_drop_index(op),
+        Operator::DropSequence(op) => visitor.visit_drop_sequence(op),
         Operator::Truncate(op) => visitor.visit_truncate(op),
         #[cfg(feature = "copy")]
         Operator::CopyFromFile(op) => visitor.visit_copy_from_file(op),
@@ -361,8 +469,9 @@ pub(crate) mod tests {
     use super::*;
     #[cfg(feature = "copy")]
     use crate::binder::copy::{ExtSource, FileFormat};
+    use crate::catalog::sequence::Sequence;
     use crate::catalog::view::View;
-    use crate::catalog::{ColumnCatalog, ColumnDesc, ColumnRef};
+    use crate::catalog::{CheckConstraint, ColumnCatalog, ColumnDesc, ColumnRef};
     use crate::expression::function::table::{
         ArcTableFunctionImpl, TableFunction, TableFunctionCatalog,
     };
@@ -415,12 +524,18 @@ pub(crate) mod tests {
             Operator::Dummy,
             Operator::Aggregate(AggregateOperator {
                 groupby_exprs: vec![1_i32.into()],
+                grouping_sets: Vec::new(),
                 agg_calls: vec![2_i32.into()],
                 is_distinct: false,
                 force_spill: false,
             }),
-            Operator::ScalarApply(ScalarApplyOperator),
+            Operator::ScalarApply(ScalarApplyOperator::default()),
             Operator::MarkApply(mark_apply),
+            Operator::LateralApply(LateralApplyOperator {
+                join_type: JoinType::LeftOuter,
+                filter: Some(20_i32.into()),
+                parameters: vec![21_i32.into()],
+            }),
             Operator::Filter(FilterOperator {
                 predicate: 5_i32.into(),
                 is_optimized: false,
@@ -433,6 +548,7 @@ pub(crate) mod tests {
                 },
                 join_type: JoinType::Inner,
                 force_nested_loop: false,
+                force_merge_join: false,
             }),
             Operator::Project(ProjectOperator {
                 exprs: vec![9_i32.into()],
@@ -473,11 +589,12 @@ pub(crate) mod tests {
                     args: Vec::new(),
                     ty: LogicalType::Bigint,
                 }],
+                frames: vec![None],
                 output_columns: vec![column_ref],
             }),
             Operator::ShowTable,
             Operator::ShowView,
-            Operator::Explain,
+            Operator::Explain(ExplainOperator::default()),
             Operator::Describe(DescribeOperator {
                 table_name: "t1".into(),
             }),
@@ -494,14 +611,20 @@ pub(crate) mod tests {
                 table_name: "t1".into(),
                 is_overwrite: false,
                 is_mapping_by_name: false,
+                on_conflict: None,
+                returning: vec![],
+                checks: vec![],
             }),
             Operator::Update(UpdateOperator {
                 table_name: "t1".into(),
                 value_exprs: vec![(column_ref, 15_i32.into())],
+                returning: vec![],
+                checks: vec![],
             }),
             Operator::Delete(DeleteOperator {
                 table_name: "t1".into(),
                 primary_keys: vec![column_ref],
+                returning: vec![],
             }),
             Operator::Analyze(AnalyzeOperator {
                 table_name: "t1".into(),
@@ -526,9 +649,25 @@ pub(crate) mod tests {
                 column_name: "value".to_string(),
                 if_exists: false,
             }),
+            Operator::AddConstraint(AddConstraintOperator {
+                table_name: "t1".into(),
+                check: CheckConstraint {
+                    name: "t1_check".to_string(),
+                    expr: "value > 17".to_string(),
+                },
+                predicate: 17_i32.into(),
+            }),
+            Operator::DropConstraint(DropConstraintOperator {
+                table_name: "t1".into(),
+                constraint_name: "t1_check".to_string(),
+                if_exists: false,
+            }),
             Operator::CreateTable(CreateTableOperator {
                 table_name: "t1".into(),
                 columns: vec![column],
+                checks: vec![],
+                foreign_keys: vec![],
+                sequences: vec![],
                 if_not_exists: false,
             }),
             Operator::CreateIndex(CreateIndexOperator {
@@ -537,6 +676,7 @@ pub(crate) mod tests {
                 index_name: "idx".to_string(),
                 if_not_exists: false,
                 ty: IndexType::Normal,
+                json_path: vec![],
             }),
             Operator::CreateView(CreateViewOperator {
                 view: View {
@@ -546,6 +686,18 @@ pub(crate) mod tests {
                 },
                 or_replace: false,
             }),
+            Operator::CreateSequence(CreateSequenceOperator {
+                sequence: Sequence::new(
+                    "s1".to_string(),
+                    &LogicalType::Bigint,
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                )?,
+                if_not_exists: false,
+            }),
             Operator::DropTable(DropTableOperator {
                 table_name: "t1".into(),
                 if_exists: false,
@@ -559,6 +711,10 @@ pub(crate) mod tests {
                 index_name: "idx".to_string(),
                 if_exists: false,
             }),
+            Operator::DropSequence(DropSequenceOperator {
+                sequence_name: "s1".to_string(),
+                if_exists: false,
+            }),
             Operator::Truncate(TruncateOperator {
                 table_name: "t1".into(),
             }),
@@ -579,6 +735,7 @@ pub(crate) mod tests {
                 table: "t1".into(),
                 source: source.clone(),
                 schema_ref: vec![column_ref],
+                checks: vec![],
             }));
             with_copy_operators.push(Operator::CopyToFile(CopyToFileOperator { target: source }));
             with_copy_operators
@@ -611,7 +768,7 @@ pub(crate) mod tests {
         for operator in &operators {
             visitor.visit_operator(operator)?;
         }
-        assert_eq!(counter.0, 20);
+        assert_eq!(counter.0, 23);
 
         Ok(())
     }
@@ -16,6 +16,7 @@ use super::alter_table::change_column::DefaultChange;
 use super::*;
 use crate::errors::DatabaseError;
 use crate::expression::visitor_mut::ExprVisitorMut;
+use crate::planner::operator::insert::{ConflictAction, OnConflict};
 
 pub trait OperatorVisitorMut<'a>: Sized {
     fn visit_operator(&mut self, operator: &'a mut Operator) -> Result<(), DatabaseError> {
@@ -41,6 +42,13 @@ pub trait OperatorVisitorMut<'a>: Sized {
         Ok(())
     }
 
+    fn visit_lateral_apply(
+        &mut self,
+        _op: &'a mut LateralApplyOperator,
+    ) -> Result<(), DatabaseError> {
+        Ok(())
+    }
+
     fn visit_filter(&mut self, _op: &'a mut FilterOperator) -> Result<(), DatabaseError> {
         Ok(())
     }
@@ -99,7 +107,7 @@ pub trait OperatorVisitorMut<'a>: Sized {
         Ok(())
     }
 
-    fn visit_explain(&mut self) -> Result<(), DatabaseError> {
+    fn visit_explain(&mut self, _op: &'a mut ExplainOperator) -> Result<(), DatabaseError> {
         Ok(())
     }
 
@@ -118,6 +126,17 @@ pub trait OperatorVisitorMut<'a>: Sized {
         Ok(())
     }
 
+    fn visit_recursive_cte(
+        &mut self,
+        _op: &'a mut RecursiveCteOperator,
+    ) -> Result<(), DatabaseError> {
+        Ok(())
+    }
+
+    fn visit_cte_scan(&mut self, _op: &'a mut CteScanOperator) -> Result<(), DatabaseError> {
+        Ok(())
+    }
+
     fn visit_insert(&mut self, _op: &'a mut InsertOperator) -> Result<(), DatabaseError> {
         Ok(())
     }
@@ -149,6 +168,20 @@ pub trait OperatorVisitorMut<'a>: Sized {
         Ok(())
     }
 
+    fn visit_add_constraint(
+        &mut self,
+        _op: &'a mut AddConstraintOperator,
+    ) -> Result<(), DatabaseError> {
+        Ok(())
+    }
+
+    fn visit_drop_constraint(
+        &mut self,
+        _op: &'a mut DropConstraintOperator,
+    ) -> Result<(), DatabaseError> {
+        Ok(())
+    }
+
     fn visit_create_table(
         &mut self,
         _op: &'a mut CreateTableOperator,
@@ -167,6 +200,13 @@ pub trait OperatorVisitorMut<'a>: Sized {
         Ok(())
     }
 
+    fn visit_create_sequence(
+        &mut self,
+        _op: &'a mut CreateSequenceOperator,
+    ) -> Result<(), DatabaseError> {
+        Ok(())
+    }
+
     fn visit_drop_table(&mut self, _op: &'a mut DropTableOperator) -> Result<(), DatabaseError> {
         Ok(())
     }
@@ -179,6 +219,13 @@ pub trait OperatorVisitorMut<'a>: Sized {
         Ok(())
     }
 
+    fn visit_drop_sequence(
+        &mut self,
+        _op: &'a mut DropSequenceOperator,
+    ) -> Result<(), DatabaseError> {
+        Ok(())
+    }
+
     fn visit_truncate(&mut self, _op: &'a mut TruncateOperator) -> Result<(), DatabaseError> {
         Ok(())
     }
@@ -215,8 +262,15 @@ impl<'a, V: ExprVisitorMut<'a>> OperatorVisitorMut<'a> for OperatorExprVisitorMu
         Ok(())
     }
 
+    fn visit_scalar_apply(&mut self, op: &'a mut ScalarApplyOperator) -> Result<(), DatabaseError> {
+        for expr in &mut op.parameters {
+            ExprVisitorMut::visit(self.visitor, expr)?;
+        }
+        Ok(())
+    }
+
     fn visit_mark_apply(&mut self, op: &'a mut MarkApplyOperator) -> Result<(), DatabaseError> {
-        for expr in &mut op.predicates {
+        for expr in op.predicates.iter_mut().chain(&mut op.parameters) {
             ExprVisitorMut::visit(self.visitor, expr)?;
         }
         if let Some(expr) = &mut op.parameterized_probe {
@@ -225,6 +279,16 @@ impl<'a, V: ExprVisitorMut<'a>> OperatorVisitorMut<'a> for OperatorExprVisitorMu
         Ok(())
     }
 
+    fn visit_lateral_apply(
+        &mut self,
+        op: &'a mut LateralApplyOperator,
+    ) -> Result<(), DatabaseError> {
+        for expr in op.filter.iter_mut().chain(&mut op.parameters) {
+            ExprVisitorMut::visit(self.visitor, expr)?;
+        }
+        Ok(())
+    }
+
     fn visit_filter(&mut self, op: &'a mut FilterOperator) -> Result<(), DatabaseError> {
         ExprVisitorMut::visit(self.visitor, &mut op.predicate)
     }
@@ -303,8 +367,49 @@ impl<'a, V: ExprVisitorMut<'a>> OperatorVisitorMut<'a> for OperatorExprVisitorMu
         Ok(())
     }
 
+    fn visit_insert(&mut self, op: &'a mut InsertOperator) -> Result<(), DatabaseError> {
+        if let Some(OnConflict {
+            action:
+                ConflictAction::DoUpdate {
+                    value_exprs,
+                    predicate,
+                },
+            ..
+        }) = &mut op.on_conflict
+        {
+            for expr in value_exprs
+                .iter_mut()
+                .map(|(_, expr)| expr)
+                .chain(predicate.iter_mut())
+            {
+                ExprVisitorMut::visit(self.visitor, expr)?;
+            }
+        }
+        for expr in op
+            .returning
+            .iter_mut()
+            .chain(op.checks.iter_mut().map(|(_, expr)| expr))
+        {
+            ExprVisitorMut::visit(self.visitor, expr)?;
+        }
+        Ok(())
+    }
+
     fn visit_update(&mut self, op: &'a mut UpdateOperator) -> Result<(), DatabaseError> {
-        for (_, expr) in &mut op.value_exprs {
+        for expr in op
+            .value_exprs
+            .iter_mut()
+            .map(|(_, expr)| expr)
+            .chain(op.checks.iter_mut().map(|(_, expr)| expr))
+            .chain(op.returning.iter_mut())
+        {
+            ExprVisitorMut::visit(self.visitor, expr)?;
+        }
+        Ok(())
+    }
+
+    fn visit_delete(&mut self, op: &'a mut DeleteOperator) -> Result<(), DatabaseError> {
+        for expr in &mut op.returning {
             ExprVisitorMut::visit(self.visitor, expr)?;
         }
         Ok(())
@@ -327,6 +432,13 @@ impl<'a, V: ExprVisitorMut<'a>> OperatorVisitorMut<'a> for OperatorExprVisitorMu
         Ok(())
     }
 
+    fn visit_add_constraint(
+        &mut self,
+        op: &'a mut AddConstraintOperator,
+    ) -> Result<(), DatabaseError> {
+        ExprVisitorMut::visit(self.visitor, &mut op.predicate)
+    }
+
     fn visit_create_table(&mut self, op: &'a mut CreateTableOperator) -> Result<(), DatabaseError> {
         for column in &mut op.columns {
             if let Some(expr) = &mut column.desc_mut().default {
@@ -335,6 +447,17 @@ impl<'a, V: ExprVisitorMut<'a>> OperatorVisitorMut<'a> for OperatorExprVisitorMu
         }
         Ok(())
     }
+
+    #[cfg(feature = "copy")]
+    fn visit_copy_from_file(
+        &mut self,
+        op: &'a mut CopyFromFileOperator,
+    ) -> Result<(), DatabaseError> {
+        for (_, expr) in &mut op.checks {
+            ExprVisitorMut::visit(self.visitor, expr)?;
+        }
+        Ok(())
+    }
 }
 
 pub fn walk_mut_operator<'a, V: OperatorVisitorMut<'a>>(
@@ -346,6 +469,7 @@ pub fn walk_mut_operator<'a, V: OperatorVisitorMut<'a>>(
         Operator::Aggregate(op) => visitor.visit_aggregate(op),
         Operator::ScalarApply(op) => visitor.visit_scalar_apply(op),
         Operator::MarkApply(op) => visitor.visit_mark_apply(op),
+        Operator::LateralApply(op) => visitor.visit_lateral_apply(op),
         Operator::Filter(op) => visitor.visit_filter(op),
         Operator::Join(op) => visitor.visit_join(op),
         Operator::Project(op) => visitor.visit_project(op),
@@ -358,10 +482,12 @@ pub fn walk_mut_operator<'a, V: OperatorVisitorMut<'a>>(
         Operator::Values(op) => visitor.visit_values(op),
         Operator::ShowTable => visitor.visit_show_table(),
         Operator::ShowView => visitor.visit_show_view(),
-        Operator::Explain => visitor.visit_explain(),
+        Operator::Explain(op) => visitor.visit_explain(op),
         Operator::Describe(op) => visitor.visit_describe(op),
         Operator::SetMembership(op) => visitor.visit_set_membership(op),
         Operator::Union(op) => visitor.visit_union(op),
+        Operator::RecursiveCte(op) => visitor.visit_recursive_cte(op),
+        Operator::CteScan(op) => visitor.visit_cte_scan(op),
         Operator::Insert(op) => visitor.visit_insert(op),
         Operator::Update(op) => visitor.visit_update(op),
         Operator::Delete(op) => visitor.visit_delete(op),
@@ -369,12 +495,16 @@ pub fn walk_mut_operator<'a, V: OperatorVisitorMut<'a>>(
         Operator::AddColumn(op) => visitor.visit_add_column(op),
         Operator::ChangeColumn(op) => visitor.visit_change_column(op),
         Operator::DropColumn(op) => visitor.visit_drop_column(op),
+        Operator::AddConstraint(op) => visitor.visit_add_constraint(op),
+        Operator::DropConstraint(op) => visitor.visit_drop_constraint(op),
         Operator::CreateTable(op) => visitor.visit_create_table(op),
         Operator::CreateIndex(op) => visitor.visit_create_index(op),
         Operator::CreateView(op) => visitor.visit_create_view(op),
+        Operator::CreateSequence(op) => visitor.visit_create_sequence(op),
         Operator::DropTable(op) => visitor.visit_drop_table(op),
         Operator::DropView(op) => visitor.visit_drop_view(op),
         Operator::DropIndex(op) => visitor.visit_drop_index(op),
+        Operator::DropSequence(op) => visitor.visit_drop_sequence(op),
         Operator::Truncate(op) => visitor.visit_truncate(op),
         #[cfg(feature = "copy")]
         Operator::CopyFromFile(op) => visitor.visit_copy_from_file(op),
@@ -419,7 +549,7 @@ mod tests {
                 visitor.visit_operator(operator)?;
             }
         }
-        assert_eq!(counter.0, 20);
+        assert_eq!(counter.0, 23);
 
         Ok(())
     }
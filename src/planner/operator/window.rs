@@ -13,7 +13,7 @@
 // limitations under the License.
 
 use crate::catalog::ColumnRef;
-use crate::expression::window::WindowFunction;
+use crate::expression::window::{WindowFrame, WindowFunction};
 use crate::iter_ext::Itertools;
 use crate::planner::operator::sort::SortField;
 use crate::planner::operator::SortOption;
@@ -25,6 +25,8 @@ pub struct WindowOperator {
     pub sort_fields: Vec<SortField>,
     pub partition_by_len: usize,
     pub functions: Vec<WindowFunction>,
+    /// The explicit frame of each function, `None` for the default frame.
+    pub frames: Vec<Option<WindowFrame>>,
     pub output_columns: Vec<ColumnRef>,
 }
 
@@ -72,6 +74,19 @@ impl fmt::Display for WindowOperator {
                 order_by.iter().map(ToString::to_string).join(", ")
             )?;
         }
+        if self.frames.iter().any(Option::is_some) {
+            write!(
+                f,
+                " Frames [{}]",
+                self.frames
+                    .iter()
+                    .map(|frame| match frame {
+                        Some(frame) => frame.to_string(),
+                        None => "DEFAULT".to_string(),
+                    })
+                    .join(", ")
+            )?;
+        }
         Ok(())
     }
 }
@@ -80,11 +95,12 @@ impl fmt::Display for WindowOperator {
 #[cfg(all(test, not(target_arch = "wasm32")))]
 mod tests {
     use super::*;
-    use crate::expression::window::WindowFunctionKind;
+    use crate::expression::window::{WindowFrameBound, WindowFrameUnits, WindowFunctionKind};
     use crate::expression::ScalarExpression;
     use crate::planner::TableArena;
     use crate::serdes::{ReferenceSerialization, ReferenceTables};
     use crate::storage::rocksdb::RocksTransaction;
+    use crate::types::value::DataValue;
     use crate::types::LogicalType;
     use std::io::{Cursor, Seek, SeekFrom};
 
@@ -102,10 +118,21 @@ mod tests {
                 args: Vec::new(),
                 ty: LogicalType::Bigint,
             }],
+            frames: vec![None],
             output_columns: Vec::new(),
         }
     }
 
+    fn framed_operator() -> WindowOperator {
+        let mut operator = operator(Vec::new(), vec![ScalarExpression::from(2).desc()]);
+        operator.frames = vec![Some(WindowFrame {
+            units: WindowFrameUnits::Rows,
+            start: WindowFrameBound::Preceding(DataValue::Int64(6)),
+            end: WindowFrameBound::CurrentRow,
+        })];
+        operator
+    }
+
     #[test]
     fn display_window_spec() {
         let function = "Window [WindowFunction { kind: RowNumber, args: [], ty: Bigint }]";
@@ -122,6 +149,12 @@ mod tests {
             operator(vec![1.into()], vec![ScalarExpression::from(2).desc()]).to_string(),
             format!("{function} -> Partition By [1] Order By [2 Desc Nulls Last]")
         );
+        assert_eq!(
+            framed_operator().to_string(),
+            format!(
+                "{function} -> Order By [2 Desc Nulls Last] Frames [ROWS BETWEEN 6 PRECEDING AND CURRENT ROW]"
+            )
+        );
         assert_eq!(
             operator(Vec::new(), Vec::new()).sort_option(),
             SortOption::Follow
@@ -140,22 +173,26 @@ mod tests {
 
     #[test]
     fn serialization_roundtrip() -> Result<(), crate::errors::DatabaseError> {
-        let source = operator(vec![1.into()], vec![ScalarExpression::from(2).desc()]);
-        let mut cursor = Cursor::new(Vec::new());
-        let mut reference_tables = ReferenceTables::new();
-        let arena = TableArena::default();
-        source.encode(&mut cursor, false, &mut reference_tables, &arena)?;
-        cursor.seek(SeekFrom::Start(0))?;
+        for source in [
+            operator(vec![1.into()], vec![ScalarExpression::from(2).desc()]),
+            framed_operator(),
+        ] {
+            let mut cursor = Cursor::new(Vec::new());
+            let mut reference_tables = ReferenceTables::new();
+            let arena = TableArena::default();
+            source.encode(&mut cursor, false, &mut reference_tables, &arena)?;
+            cursor.seek(SeekFrom::Start(0))?;
 
-        assert_eq!(
-            WindowOperator::decode::<RocksTransaction, _, _>(
-                &mut cursor,
-                None,
-                &reference_tables,
-                &mut TableArena::default(),
-            )?,
-            source
-        );
+            assert_eq!(
+                WindowOperator::decode::<RocksTransaction, _, _>(
+                    &mut cursor,
+                    None,
+                    &reference_tables,
+                    &mut TableArena::default(),
+                )?,
+                source
+            );
+        }
         Ok(())
     }
 }
@@ -21,10 +21,14 @@ use crate::storage::lmdb::LmdbStorage;
 use crate::storage::memory::MemoryStorage;
 #[cfg(feature = "rocksdb")]
 use crate::storage::rocksdb::RocksStorage;
+#[cfg(feature = "time")]
+use crate::types::timezone::session_time_zone;
 use crate::types::tuple::{SchemaView, Tuple};
 use crate::types::value::DataValue;
 use pyo3::exceptions::{PyRuntimeError, PyValueError};
 use pyo3::prelude::*;
+#[cfg(feature = "time")]
+use pyo3::types::{PyDateTime, PyDelta, PyTzInfo};
 use pyo3::types::{PyDict, PyList, PyModule};
 
 fn to_py_err(err: impl ToString) -> PyErr {
@@ -47,12 +51,16 @@ fn data_value_to_py(py: Python<'_>, value: &DataValue) -> PyResult<PyObject> {
         DataValue::UInt32(value) => value.into_py(py),
         DataValue::UInt64(value) => value.into_py(py),
         DataValue::Utf8 { value, .. } => value.clone().into_py(py),
+        #[cfg(feature = "time")]
+        DataValue::Time64(_, _, true) => timestamp_tz_to_py(py, value)?,
         DataValue::Date32(_)
         | DataValue::Date64(_)
         | DataValue::Time32(_, _)
         | DataValue::Time64(_, _, _)
+        | DataValue::Interval(_)
+        | DataValue::Json(_)
         | DataValue::Decimal(_) => value.to_string().into_py(py),
-        DataValue::Tuple(values, _is_upper) => {
+        DataValue::Tuple(values, _) | DataValue::Array(values) => {
             let py_values = values
                 .iter()
                 .map(|value| data_value_to_py(py, value))
@@ -64,6 +72,36 @@ fn data_value_to_py(py: Python<'_>, value: &DataValue) -> PyResult<PyObject> {
     Ok(object)
 }
 
+/// A timezone-aware `datetime` in the session time zone, with a fixed `timezone` offset
+/// as `tzinfo`.
+#[cfg(feature = "time")]
+fn timestamp_tz_to_py(py: Python<'_>, value: &DataValue) -> PyResult<PyObject> {
+    use chrono::{Datelike, Timelike};
+
+    let utc = value
+        .timestamp_tz()
+        .ok_or_else(|| PyValueError::new_err(format!("invalid timestamp: {value}")))?
+        .naive_utc();
+    let offset = session_time_zone().offset(&utc);
+    let local = utc + offset;
+    let tzinfo = PyModule::import(py, "datetime")?
+        .getattr("timezone")?
+        .call1((PyDelta::new(py, 0, offset.local_minus_utc(), 0, true)?,))?;
+    let datetime = PyDateTime::new(
+        py,
+        local.year(),
+        local.month() as u8,
+        local.day() as u8,
+        local.hour() as u8,
+        local.minute() as u8,
+        local.second() as u8,
+        local.nanosecond() / 1_000,
+        Some(tzinfo.downcast::<PyTzInfo>()?),
+    )?;
+
+    Ok(datetime.into_any().unbind())
+}
+
 fn tuple_to_python_row(py: Python<'_>, tuple: &Tuple) -> PyResult<PyObject> {
     let row = PyDict::new(py);
 
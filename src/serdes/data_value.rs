@@ -15,6 +15,8 @@
 use crate::errors::DatabaseError;
 use crate::serdes::{ReferenceSerialization, ReferenceTables};
 use crate::storage::Transaction;
+use crate::types::interval::Interval;
+use crate::types::serialize::{read_json, write_json};
 use crate::types::value::DataValue;
 use crate::types::value::Utf8Type;
 use crate::types::CharLengthUnits;
@@ -42,6 +44,9 @@ const TAG_TIME32: u8 = 15;
 const TAG_TIME64: u8 = 16;
 const TAG_DECIMAL: u8 = 17;
 const TAG_TUPLE: u8 = 18;
+const TAG_INTERVAL: u8 = 19;
+const TAG_JSON: u8 = 20;
+const TAG_ARRAY: u8 = 21;
 
 impl ReferenceSerialization for Utf8Type {
     fn encode<W: Write, A: crate::planner::MetaArena>(
@@ -179,6 +184,24 @@ impl DataValue {
                 }
                 write_bool(writer, *is_upper)
             }
+            DataValue::Interval(interval) => {
+                write_u8(writer, TAG_INTERVAL)?;
+                write_i32(writer, interval.months)?;
+                write_i32(writer, interval.days)?;
+                write_i64(writer, interval.nanos)
+            }
+            DataValue::Json(json) => {
+                write_u8(writer, TAG_JSON)?;
+                Ok(write_json(writer, json)?)
+            }
+            DataValue::Array(values) => {
+                write_u8(writer, TAG_ARRAY)?;
+                write_len(writer, values.len())?;
+                for value in values {
+                    value.encode_reference_value(writer)?;
+                }
+                Ok(())
+            }
         }
     }
 
@@ -231,6 +254,20 @@ impl DataValue {
                 }
                 Ok(DataValue::Tuple(values, read_bool(reader)?))
             }
+            TAG_INTERVAL => Ok(DataValue::Interval(Interval::new(
+                read_i32(reader)?,
+                read_i32(reader)?,
+                read_i64(reader)?,
+            ))),
+            TAG_JSON => Ok(DataValue::Json(read_json(reader)?)),
+            TAG_ARRAY => {
+                let len = read_len(reader)?;
+                let mut values = Vec::with_capacity(len);
+                for _ in 0..len {
+                    values.push(DataValue::decode_reference_value(reader)?);
+                }
+                Ok(DataValue::Array(values))
+            }
             tag => Err(DatabaseError::InvalidValue(format!(
                 "invalid data value tag: {tag}"
             ))),
@@ -449,6 +486,13 @@ pub(crate) mod test {
             #[cfg(feature = "decimal")]
             DataValue::Decimal(Decimal::new(12345, 2)),
             DataValue::Tuple(vec![DataValue::Null, DataValue::Int32(42)], false),
+            DataValue::Json(crate::types::json::Json::parse(
+                r#"{"a": [1, -2.5, "x", null, true], "b": {}}"#,
+            )?),
+            DataValue::Array(vec![
+                DataValue::Array(vec![DataValue::Int32(1)]),
+                DataValue::Null,
+            ]),
         ];
 
         let mut reference_tables = ReferenceTables::new();
@@ -91,6 +91,10 @@ impl ReferenceSerialization for CastEvaluatorParams {
                 6u8.encode(writer, is_direct, reference_tables, arena)?;
                 evaluators.encode(writer, is_direct, reference_tables, arena)
             }
+            CastEvaluatorParams::Array { element } => {
+                7u8.encode(writer, is_direct, reference_tables, arena)?;
+                element.encode(writer, is_direct, reference_tables, arena)
+            }
         }
     }
 
@@ -141,6 +145,14 @@ impl ReferenceSerialization for CastEvaluatorParams {
                         arena,
                     )?,
                 },
+                7 => CastEvaluatorParams::Array {
+                    element: Box::<CastEvaluatorRef>::decode(
+                        reader,
+                        context,
+                        reference_tables,
+                        arena,
+                    )?,
+                },
                 _ => unreachable!(),
             },
         )
@@ -345,6 +357,18 @@ mod tests {
             },
             6,
         )?;
+        roundtrip_cast_params(
+            CastEvaluatorParams::Array {
+                element: Box::new(CastEvaluatorRef::new(
+                    2,
+                    CastEvaluatorParams::String {
+                        len: None,
+                        unit: CharLengthUnits::Octets,
+                    },
+                )),
+            },
+            7,
+        )?;
 
         Ok(())
     }
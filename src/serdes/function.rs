@@ -13,6 +13,7 @@
 // limitations under the License.
 
 use crate::errors::DatabaseError;
+use crate::expression::function::aggregate::ArcAggregateFunctionImpl;
 use crate::expression::function::scala::ArcScalarFunctionImpl;
 use crate::expression::function::table::ArcTableFunctionImpl;
 use crate::expression::function::FunctionSummary;
@@ -92,10 +93,46 @@ impl ReferenceSerialization for ArcTableFunctionImpl {
     }
 }
 
+impl ReferenceSerialization for ArcAggregateFunctionImpl {
+    fn encode<W: Write, A: crate::planner::MetaArena>(
+        &self,
+        writer: &mut W,
+        is_direct: bool,
+        reference_tables: &mut ReferenceTables,
+        arena: &A,
+    ) -> Result<(), DatabaseError> {
+        self.summary()
+            .encode(writer, is_direct, reference_tables, arena)
+    }
+
+    fn decode<T: Transaction, R: Read, A: crate::planner::MetaArena>(
+        reader: &mut R,
+        context: Option<&ReferenceDecodeContext<'_, T>>,
+        reference_tables: &ReferenceTables,
+        arena: &mut A,
+    ) -> Result<Self, DatabaseError> {
+        let summary = FunctionSummary::decode(reader, context, reference_tables, arena)?;
+        let Some(functions) = context.and_then(ReferenceDecodeContext::aggregate_functions) else {
+            return Err(DatabaseError::InvalidValue(format!(
+                "aggregate function decode context missing for {}",
+                summary.name
+            )));
+        };
+        let Some(function) = functions.get(&summary) else {
+            return Err(DatabaseError::InvalidValue(format!(
+                "aggregate function not found when decoding: {}",
+                summary.name
+            )));
+        };
+
+        Ok(Self(function.clone()))
+    }
+}
+
 #[cfg(all(test, not(target_arch = "wasm32")))]
 mod tests {
     use super::*;
-    use crate::db::{ScalaFunctions, TableFunctions};
+    use crate::db::{AggregateFunctions, ScalaFunctions, TableFunctions};
     use crate::planner::TableArena;
     use crate::serdes::ReferenceDecodeContext;
     use crate::storage::rocksdb::RocksTransaction;
@@ -138,7 +175,13 @@ mod tests {
 
         let scalars = ScalaFunctions::default();
         let table_functions = TableFunctions::default();
-        let context = ReferenceDecodeContext::with_functions(None, &scalars, &table_functions);
+        let aggregate_functions = AggregateFunctions::default();
+        let context = ReferenceDecodeContext::with_functions(
+            None,
+            &scalars,
+            &table_functions,
+            &aggregate_functions,
+        );
         let err = ArcScalarFunctionImpl::decode::<RocksTransaction, _, _>(
             &mut Cursor::new(&bytes),
             Some(&context),
@@ -168,7 +211,13 @@ mod tests {
 
         let scalars = ScalaFunctions::default();
         let table_functions = TableFunctions::default();
-        let context = ReferenceDecodeContext::with_functions(None, &scalars, &table_functions);
+        let aggregate_functions = AggregateFunctions::default();
+        let context = ReferenceDecodeContext::with_functions(
+            None,
+            &scalars,
+            &table_functions,
+            &aggregate_functions,
+        );
         let err = ArcTableFunctionImpl::decode::<RocksTransaction, _, _>(
             &mut Cursor::new(&bytes),
             Some(&context),
@@ -178,4 +227,40 @@ mod tests {
         .unwrap_err();
         assert!(err.to_string().contains("table function not found"));
     }
+
+    #[test]
+    fn aggregate_function_decode_requires_context_and_registration() {
+        let bytes = encoded_summary("missing_aggregate");
+        let tables = ReferenceTables::new();
+        let mut arena = TableArena::default();
+
+        let err = ArcAggregateFunctionImpl::decode::<RocksTransaction, _, _>(
+            &mut Cursor::new(&bytes),
+            None,
+            &tables,
+            &mut arena,
+        )
+        .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("aggregate function decode context missing"));
+
+        let scalars = ScalaFunctions::default();
+        let table_functions = TableFunctions::default();
+        let aggregate_functions = AggregateFunctions::default();
+        let context = ReferenceDecodeContext::with_functions(
+            None,
+            &scalars,
+            &table_functions,
+            &aggregate_functions,
+        );
+        let err = ArcAggregateFunctionImpl::decode::<RocksTransaction, _, _>(
+            &mut Cursor::new(&bytes),
+            Some(&context),
+            &tables,
+            &mut arena,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("aggregate function not found"));
+    }
 }
@@ -36,7 +36,7 @@ mod trim;
 mod vec;
 
 use crate::catalog::TableName;
-use crate::db::{ScalaFunctions, TableFunctions};
+use crate::db::{AggregateFunctions, ScalaFunctions, TableFunctions};
 use crate::errors::DatabaseError;
 use crate::planner::MetaArena;
 use crate::storage::{TableCache, Transaction};
@@ -66,6 +66,7 @@ pub struct ReferenceDecodeContext<'a, T: Transaction> {
     drive: Option<(&'a T, &'a TableCache)>,
     scala_functions: Option<&'a ScalaFunctions>,
     table_functions: Option<&'a TableFunctions>,
+    aggregate_functions: Option<&'a AggregateFunctions>,
 }
 
 impl<'a, T: Transaction> ReferenceDecodeContext<'a, T> {
@@ -74,6 +75,7 @@ impl<'a, T: Transaction> ReferenceDecodeContext<'a, T> {
             drive,
             scala_functions: None,
             table_functions: None,
+            aggregate_functions: None,
         }
     }
 
@@ -81,11 +83,13 @@ impl<'a, T: Transaction> ReferenceDecodeContext<'a, T> {
         drive: Option<(&'a T, &'a TableCache)>,
         scala_functions: &'a ScalaFunctions,
         table_functions: &'a TableFunctions,
+        aggregate_functions: &'a AggregateFunctions,
     ) -> Self {
         Self {
             drive,
             scala_functions: Some(scala_functions),
             table_functions: Some(table_functions),
+            aggregate_functions: Some(aggregate_functions),
         }
     }
 
@@ -100,6 +104,10 @@ impl<'a, T: Transaction> ReferenceDecodeContext<'a, T> {
     pub(crate) fn table_functions(&self) -> Option<&'a TableFunctions> {
         self.table_functions
     }
+
+    pub(crate) fn aggregate_functions(&self) -> Option<&'a AggregateFunctions> {
+        self.aggregate_functions
+    }
 }
 
 #[derive(Debug, Default)]
@@ -17,13 +17,77 @@ use crate::storage::table_codec::Bytes;
 use crate::storage::{
     EmptyStorageMetrics, InnerIter, Storage, Transaction, TransactionIsolationLevel,
 };
-use std::cell::{Ref, RefCell};
 use std::collections::{BTreeMap, Bound, VecDeque};
-use std::rc::Rc;
+use std::mem;
+use std::sync::{Arc, PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+type Value = Arc<[u8]>;
+
+/// Committed versions of every key, ordered by ascending commit version.
+/// A `None` value marks a deletion.
+type Versions = Vec<(u64, Option<Value>)>;
+
+#[derive(Default)]
+struct MemoryInner {
+    data: BTreeMap<Bytes, Versions>,
+    /// The version of the latest committed transaction.
+    version: u64,
+    /// Snapshot versions held by open transactions, with their reference counts.
+    /// Older versions than the smallest one are invisible and can be discarded.
+    snapshots: BTreeMap<u64, usize>,
+}
+
+impl MemoryInner {
+    fn acquire_snapshot(&mut self) -> u64 {
+        *self.snapshots.entry(self.version).or_default() += 1;
+        self.version
+    }
+
+    fn release_snapshot(&mut self, version: u64) {
+        if let Some(count) = self.snapshots.get_mut(&version) {
+            *count -= 1;
+            if *count == 0 {
+                self.snapshots.remove(&version);
+            }
+        }
+    }
+
+    fn visible(versions: &Versions, version: u64) -> Option<&Value> {
+        versions
+            .iter()
+            .rev()
+            .find(|(commit_version, _)| *commit_version <= version)
+            .and_then(|(_, value)| value.as_ref())
+    }
+
+    /// Drops the versions of `key` that no open snapshot can see anymore.
+    fn vacuum(&mut self, key: &[u8]) {
+        let oldest = self
+            .snapshots
+            .first_key_value()
+            .map(|(version, _)| *version)
+            .unwrap_or(self.version);
+        let Some(versions) = self.data.get_mut(key) else {
+            return;
+        };
+        if let Some(pos) = versions.iter().rposition(|(version, _)| *version <= oldest) {
+            versions.drain(..pos);
+        }
+        if matches!(versions.as_slice(), [(version, None)] if *version <= oldest) {
+            self.data.remove(key);
+        }
+    }
+}
 
+/// A multi-version in-memory storage.
+///
+/// Transactions buffer their writes and only publish them on `commit`, so a dropped or
+/// failed transaction leaves no trace. Reads see a committed snapshot chosen by the
+/// transaction isolation level, and a transaction fails to commit if another one has
+/// committed a key it wrote after the snapshot its write was based on.
 #[derive(Clone, Default)]
 pub struct MemoryStorage {
-    inner: Rc<RefCell<BTreeMap<Vec<u8>, Vec<u8>>>>,
+    inner: Arc<RwLock<MemoryInner>>,
 }
 
 impl MemoryStorage {
@@ -45,32 +109,86 @@ impl Storage for MemoryStorage {
         isolation: TransactionIsolationLevel,
     ) -> Result<Self::TransactionType<'_>, DatabaseError> {
         self.validate_transaction_isolation(isolation)?;
+        let snapshot = matches!(isolation, TransactionIsolationLevel::RepeatableRead)
+            .then(|| write_lock(&self.inner).acquire_snapshot());
+
         Ok(MemoryTransaction {
             inner: self.inner.clone(),
+            isolation,
+            snapshot,
+            writes: BTreeMap::new(),
         })
     }
 
     fn default_transaction_isolation(&self) -> TransactionIsolationLevel {
-        TransactionIsolationLevel::ReadCommitted
+        TransactionIsolationLevel::RepeatableRead
     }
+
+    fn validate_transaction_isolation(
+        &self,
+        isolation: TransactionIsolationLevel,
+    ) -> Result<(), DatabaseError> {
+        match isolation {
+            TransactionIsolationLevel::ReadCommitted
+            | TransactionIsolationLevel::RepeatableRead => Ok(()),
+        }
+    }
+}
+
+fn read_lock(inner: &RwLock<MemoryInner>) -> RwLockReadGuard<'_, MemoryInner> {
+    inner.read().unwrap_or_else(PoisonError::into_inner)
+}
+
+fn write_lock(inner: &RwLock<MemoryInner>) -> RwLockWriteGuard<'_, MemoryInner> {
+    inner.write().unwrap_or_else(PoisonError::into_inner)
 }
 
 pub struct MemoryTransaction {
-    inner: Rc<RefCell<BTreeMap<Vec<u8>, Vec<u8>>>>,
+    inner: Arc<RwLock<MemoryInner>>,
+    isolation: TransactionIsolationLevel,
+    /// The committed version reads are served from, the latest one when `None`.
+    snapshot: Option<u64>,
+    /// Uncommitted writes with the committed version each of them is based on.
+    writes: BTreeMap<Bytes, (u64, Option<Value>)>,
+}
+
+impl MemoryTransaction {
+    fn write(&mut self, key: &[u8], value: Option<Value>) {
+        if let Some((_, current)) = self.writes.get_mut(key) {
+            *current = value;
+            return;
+        }
+        let base = self
+            .snapshot
+            .unwrap_or_else(|| read_lock(&self.inner).version);
+        self.writes.insert(key.to_vec(), (base, value));
+    }
+
+    fn release_snapshot(&mut self) {
+        if let Some(version) = self.snapshot.take() {
+            write_lock(&self.inner).release_snapshot(version);
+        }
+    }
+}
+
+impl Drop for MemoryTransaction {
+    fn drop(&mut self) {
+        self.release_snapshot();
+    }
 }
 
 pub struct MemoryIter {
-    entries: VecDeque<(Bytes, Bytes)>,
-    current: Option<(Bytes, Bytes)>,
+    entries: VecDeque<(Bytes, Value)>,
+    current: Option<(Bytes, Value)>,
 }
 
-pub struct MemoryValue<'a> {
-    value: Ref<'a, Vec<u8>>,
+pub struct MemoryValue {
+    value: Value,
 }
 
-impl AsRef<[u8]> for MemoryValue<'_> {
+impl AsRef<[u8]> for MemoryValue {
     fn as_ref(&self) -> &[u8] {
-        self.value.as_slice()
+        &self.value
     }
 }
 
@@ -81,13 +199,13 @@ impl InnerIter for MemoryIter {
         Ok(self
             .current
             .as_ref()
-            .map(|(key, value)| (key.as_slice(), value.as_slice())))
+            .map(|(key, value)| (key.as_slice(), value.as_ref())))
     }
 }
 
 impl Transaction for MemoryTransaction {
     type BorrowedBytes<'a>
-        = MemoryValue<'a>
+        = MemoryValue
     where
         Self: 'a;
 
@@ -95,25 +213,48 @@ impl Transaction for MemoryTransaction {
         = MemoryIter
     where
         Self: 'a;
+
+    fn begin_statement_scope(&mut self) -> Result<(), DatabaseError> {
+        if self.isolation == TransactionIsolationLevel::ReadCommitted {
+            self.release_snapshot();
+            self.snapshot = Some(write_lock(&self.inner).acquire_snapshot());
+        }
+        Ok(())
+    }
+
+    fn end_statement_scope(&mut self) -> Result<(), DatabaseError> {
+        if self.isolation == TransactionIsolationLevel::ReadCommitted {
+            self.release_snapshot();
+        }
+        Ok(())
+    }
+
     fn get_borrowed<'a>(
         &'a self,
         key: &[u8],
     ) -> Result<Option<Self::BorrowedBytes<'a>>, DatabaseError> {
-        let map = self.inner.borrow();
-        let Ok(value) = Ref::filter_map(map, |map| map.get(key)) else {
-            return Ok(None);
-        };
-
-        Ok(Some(MemoryValue { value }))
+        if let Some((_, value)) = self.writes.get(key) {
+            return Ok(value.clone().map(|value| MemoryValue { value }));
+        }
+        let inner = read_lock(&self.inner);
+        let version = self.snapshot.unwrap_or(inner.version);
+
+        Ok(inner
+            .data
+            .get(key)
+            .and_then(|versions| MemoryInner::visible(versions, version))
+            .map(|value| MemoryValue {
+                value: value.clone(),
+            }))
     }
 
     fn set(&mut self, key: &[u8], value: &[u8]) -> Result<(), DatabaseError> {
-        self.inner.borrow_mut().insert(key.to_vec(), value.to_vec());
+        self.write(key, Some(value.into()));
         Ok(())
     }
 
     fn remove(&mut self, key: &[u8]) -> Result<(), DatabaseError> {
-        self.inner.borrow_mut().remove(key);
+        self.write(key, None);
         Ok(())
     }
 
@@ -122,30 +263,59 @@ impl Transaction for MemoryTransaction {
         min: Bound<&'key [u8]>,
         max: Bound<&'key [u8]>,
     ) -> Result<Self::IterType<'txn>, DatabaseError> {
-        let map = self.inner.borrow();
-        let start = match &min {
-            Bound::Included(b) => Bound::Included(*b),
-            Bound::Excluded(b) => Bound::Excluded(*b),
-            Bound::Unbounded => Bound::Unbounded,
-        };
-        let end = match &max {
-            Bound::Included(b) => Bound::Included(*b),
-            Bound::Excluded(b) => Bound::Excluded(*b),
-            Bound::Unbounded => Bound::Unbounded,
-        };
-
-        let entries = map
-            .range::<[u8], _>((start, end))
-            .map(|(k, v)| (k.clone(), v.clone()))
-            .collect();
+        let inner = read_lock(&self.inner);
+        let version = self.snapshot.unwrap_or(inner.version);
+
+        let mut entries = inner
+            .data
+            .range::<[u8], _>((min, max))
+            .filter_map(|(key, versions)| {
+                MemoryInner::visible(versions, version).map(|value| (key.clone(), value.clone()))
+            })
+            .collect::<BTreeMap<_, _>>();
+        drop(inner);
+        for (key, (_, value)) in self.writes.range::<[u8], _>((min, max)) {
+            match value {
+                Some(value) => entries.insert(key.clone(), value.clone()),
+                None => entries.remove(key),
+            };
+        }
 
         Ok(MemoryIter {
-            entries,
+            entries: entries.into_iter().collect(),
             current: None,
         })
     }
 
-    fn commit(self) -> Result<(), DatabaseError> {
+    fn commit(mut self) -> Result<(), DatabaseError> {
+        self.release_snapshot();
+        let writes = mem::take(&mut self.writes);
+        if writes.is_empty() {
+            return Ok(());
+        }
+        let mut inner = write_lock(&self.inner);
+
+        for (key, (base, _)) in writes.iter() {
+            let latest = inner
+                .data
+                .get(key)
+                .and_then(|versions| versions.last())
+                .map(|(version, _)| *version);
+            if latest.is_some_and(|latest| latest > *base) {
+                return Err(DatabaseError::TransactionConflict);
+            }
+        }
+        inner.version += 1;
+        let version = inner.version;
+        for (key, (_, value)) in writes {
+            inner
+                .data
+                .entry(key.clone())
+                .or_default()
+                .push((version, value));
+            inner.vacuum(&key);
+        }
+
         Ok(())
     }
 }
@@ -300,7 +470,7 @@ mod wasm_tests {
 mod native_tests {
     use super::*;
     use crate::catalog::{ColumnCatalog, ColumnDesc, TableName};
-    use crate::db::{CatalogKind, DataBaseBuilder};
+    use crate::db::{CatalogKind, DataBaseBuilder, ResultIter};
     use crate::expression::range_detacher::Range;
     use crate::planner::{PlanArena, TableArenaCell};
     use crate::storage::table_codec::TableCodec;
@@ -439,4 +609,151 @@ mod native_tests {
 
         Ok(())
     }
+
+    fn get(transaction: &MemoryTransaction, key: &[u8]) -> Option<Vec<u8>> {
+        transaction
+            .get_borrowed(key)
+            .unwrap()
+            .map(|value| value.as_ref().to_vec())
+    }
+
+    fn keys(transaction: &MemoryTransaction) -> Result<Vec<Vec<u8>>, DatabaseError> {
+        let mut iter = transaction.range(Bound::Unbounded, Bound::Unbounded)?;
+        let mut keys = Vec::new();
+        while let Some((key, _)) = iter.try_next()? {
+            keys.push(key.to_vec());
+        }
+        Ok(keys)
+    }
+
+    #[test]
+    fn memory_storage_is_send_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+
+        assert_send_sync::<MemoryStorage>();
+    }
+
+    #[test]
+    fn memory_storage_buffers_writes_until_commit() -> Result<(), DatabaseError> {
+        let storage = MemoryStorage::new();
+        let mut transaction = storage.transaction()?;
+        transaction.set(b"k1", b"v1")?;
+        transaction.set(b"k2", b"v2")?;
+        transaction.remove(b"k2")?;
+
+        let other = storage.transaction()?;
+        assert_eq!(get(&transaction, b"k1"), Some(b"v1".to_vec()));
+        assert_eq!(get(&transaction, b"k2"), None);
+        assert_eq!(keys(&transaction)?, vec![b"k1".to_vec()]);
+        assert_eq!(get(&other, b"k1"), None);
+        assert!(keys(&other)?.is_empty());
+        transaction.commit()?;
+
+        let transaction = storage.transaction()?;
+        assert_eq!(get(&transaction, b"k1"), Some(b"v1".to_vec()));
+        assert_eq!(keys(&transaction)?, vec![b"k1".to_vec()]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn memory_storage_rolls_back_dropped_transaction() -> Result<(), DatabaseError> {
+        let mut kite_sql = DataBaseBuilder::path("./memory").build_in_memory()?;
+        kite_sql.ddl("create table t1 (a int primary key, b int)")?;
+
+        let mut transaction = kite_sql.new_transaction()?;
+        transaction
+            .run("insert into t1 values (0, 0), (1, 1)")?
+            .done()?;
+        drop(transaction);
+
+        let mut iter = kite_sql.run("select * from t1")?;
+        assert!(iter.next_tuple(|_, _| ())?.is_none());
+        iter.done()?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn memory_storage_snapshots_follow_isolation_level() -> Result<(), DatabaseError> {
+        let storage = MemoryStorage::new();
+        let mut transaction = storage.transaction()?;
+        transaction.set(b"k1", b"v1")?;
+        transaction.commit()?;
+
+        let repeatable_read =
+            storage.transaction_with_isolation(TransactionIsolationLevel::RepeatableRead)?;
+        let mut read_committed =
+            storage.transaction_with_isolation(TransactionIsolationLevel::ReadCommitted)?;
+        read_committed.begin_statement_scope()?;
+        assert_eq!(get(&read_committed, b"k1"), Some(b"v1".to_vec()));
+
+        let mut writer = storage.transaction()?;
+        writer.set(b"k1", b"v2")?;
+        writer.set(b"k2", b"v2")?;
+        writer.commit()?;
+
+        assert_eq!(get(&read_committed, b"k1"), Some(b"v1".to_vec()));
+        read_committed.end_statement_scope()?;
+        read_committed.begin_statement_scope()?;
+        assert_eq!(get(&read_committed, b"k1"), Some(b"v2".to_vec()));
+
+        assert_eq!(get(&repeatable_read, b"k1"), Some(b"v1".to_vec()));
+        assert_eq!(keys(&repeatable_read)?, vec![b"k1".to_vec()]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn memory_storage_detects_write_conflicts() -> Result<(), DatabaseError> {
+        let storage = MemoryStorage::new();
+        let mut transaction_1 = storage.transaction()?;
+        let mut transaction_2 = storage.transaction()?;
+        let mut transaction_3 = storage.transaction()?;
+        transaction_1.set(b"k1", b"v1")?;
+        transaction_2.set(b"k1", b"v2")?;
+        transaction_3.set(b"k3", b"v3")?;
+
+        transaction_1.commit()?;
+        assert!(matches!(
+            transaction_2.commit(),
+            Err(DatabaseError::TransactionConflict)
+        ));
+        transaction_3.commit()?;
+
+        let transaction = storage.transaction()?;
+        assert_eq!(get(&transaction, b"k1"), Some(b"v1".to_vec()));
+        assert_eq!(get(&transaction, b"k3"), Some(b"v3".to_vec()));
+
+        Ok(())
+    }
+
+    #[test]
+    fn memory_storage_discards_invisible_versions() -> Result<(), DatabaseError> {
+        let storage = MemoryStorage::new();
+        let versions = |key: &[u8]| {
+            read_lock(&storage.inner)
+                .data
+                .get(key)
+                .map_or(0, |versions| versions.len())
+        };
+        let mut transaction = storage.transaction()?;
+        transaction.set(b"k1", b"v1")?;
+        transaction.commit()?;
+
+        let reader = storage.transaction()?;
+        let mut transaction = storage.transaction()?;
+        transaction.set(b"k1", b"v2")?;
+        transaction.commit()?;
+        assert_eq!(versions(b"k1"), 2);
+        assert_eq!(get(&reader, b"k1"), Some(b"v1".to_vec()));
+        drop(reader);
+
+        let mut transaction = storage.transaction()?;
+        transaction.remove(b"k1")?;
+        transaction.commit()?;
+        assert_eq!(versions(b"k1"), 0);
+
+        Ok(())
+    }
 }
@@ -19,9 +19,12 @@ pub mod memory;
 pub mod rocksdb;
 pub(crate) mod table_codec;
 
+use crate::catalog::sequence::{Sequence, SequenceCache};
 use crate::catalog::view::View;
-use crate::catalog::{ColumnCatalog, ColumnRef, TableCatalog, TableMeta, TableName};
-use crate::db::{ScalaFunctions, TableFunctions};
+use crate::catalog::{
+    CheckConstraint, ColumnCatalog, ColumnRef, ForeignKey, TableCatalog, TableMeta, TableName,
+};
+use crate::db::{AggregateFunctions, ScalaFunctions, TableFunctions};
 use crate::errors::DatabaseError;
 use crate::expression::range_detacher::Range;
 use crate::expression::ScalarExpression;
@@ -34,7 +37,7 @@ use crate::planner::operator::alter_table::change_column::{DefaultChange, NotNul
 use crate::planner::{MetaArena, PlanArena, TableArenaCell};
 use crate::serdes::ReferenceTables;
 use crate::storage::table_codec::{Bytes, StatisticsCodecType, TableCodec, BOUND_MAX_TAG};
-use crate::types::index::{Index, IndexId, IndexMeta, IndexMetaRef, IndexType};
+use crate::types::index::{Index, IndexId, IndexMeta, IndexMetaRef, IndexPathStep, IndexType};
 use crate::types::serialize::TupleValueSerializableImpl;
 use crate::types::tuple::{Tuple, TupleId};
 use crate::types::value::{DataValue, TupleMappingRef};
@@ -200,6 +203,7 @@ pub trait Transaction: Sized {
                 deserializers,
                 total_len: table.columns_len(),
                 iter,
+                keys_read: 0,
             })
         })
     }
@@ -271,6 +275,7 @@ pub trait Transaction: Sized {
             state: IndexIterState::Init,
             encode_min_buffer: Bytes::new(),
             encode_max_buffer: Bytes::new(),
+            keys_read: 0,
         })
     }
 
@@ -316,6 +321,7 @@ pub trait Transaction: Sized {
         deserializers
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn add_index_meta(
         &mut self,
         table_codec: &mut TableCodec,
@@ -324,11 +330,12 @@ pub trait Transaction: Sized {
         index_name: String,
         column_ids: Vec<ColumnId>,
         ty: IndexType,
+        json_path: Vec<IndexPathStep>,
     ) -> Result<(TableCatalog, IndexId), DatabaseError> {
         let mut table = self
             .load_table(table_codec, plan_arena, table_name.clone())?
             .ok_or(DatabaseError::TableNotFound)?;
-        let index_meta = table.add_index_meta(index_name, column_ids, ty, plan_arena)?;
+        let index_meta = table.add_index_meta(index_name, column_ids, ty, json_path, plan_arena)?;
         let index_meta = plan_arena.index(index_meta);
         let index_id = index_meta.id;
         table_codec.with_index_meta(
@@ -419,6 +426,58 @@ pub trait Transaction: Sized {
         table_codec.with_tuple(table_name, tuple_id, None, |key, _| self.remove(key))
     }
 
+    fn get_tuple<I, S>(
+        &self,
+        table_codec: &mut TableCodec,
+        table_name: &str,
+        tuple_id: &TupleId,
+        deserializers: I,
+        total_len: usize,
+    ) -> Result<Option<Tuple>, DatabaseError>
+    where
+        I: IntoIterator<Item = S>,
+        S: Borrow<TupleValueSerializableImpl>,
+    {
+        table_codec.with_tuple(table_name, tuple_id, None, |key, _| {
+            let Some(bytes) = self.get_borrowed(key)? else {
+                return Ok(None);
+            };
+            let mut tuple = Tuple::default();
+            TableCodec::decode_tuple_into(
+                &mut tuple,
+                deserializers,
+                Some(tuple_id.clone()),
+                bytes.as_ref(),
+                total_len,
+            )?;
+            Ok(Some(tuple))
+        })
+    }
+
+    fn contains_tuple(
+        &self,
+        table_codec: &mut TableCodec,
+        table_name: &str,
+        tuple_id: &TupleId,
+    ) -> Result<bool, DatabaseError> {
+        table_codec.with_tuple(table_name, tuple_id, None, |key, _| self.exists(key))
+    }
+
+    /// Returns the tuple id currently holding `index` in a unique index.
+    fn get_unique_index(
+        &self,
+        table_codec: &mut TableCodec,
+        table_name: &str,
+        index: &Index,
+    ) -> Result<Option<TupleId>, DatabaseError> {
+        debug_assert!(matches!(index.ty, IndexType::Unique));
+        table_codec.with_index(table_name, index, None, |key, _| {
+            self.get_borrowed(key)?
+                .map(|bytes| TableCodec::decode_index(bytes.as_ref()))
+                .transpose()
+        })
+    }
+
     fn rewrite_table_metadata(
         &mut self,
         table_codec: &mut TableCodec,
@@ -527,18 +586,24 @@ pub trait Transaction: Sized {
                     column_ids: index_meta.column_ids.clone(),
                     table_name: table_name.clone(),
                     pk_ty: temp_table.primary_keys_type().clone(),
-                    value_ty: index_value_type(&temp_table, plan_arena, &index_meta.column_ids)?,
+                    value_ty: if index_meta.json_path.is_empty() {
+                        index_value_type(&temp_table, plan_arena, &index_meta.column_ids)?
+                    } else {
+                        index_meta.value_ty.clone()
+                    },
                     name: index_meta.name.clone(),
                     ty: index_meta.ty,
+                    json_path: index_meta.json_path.clone(),
                 })
             })
             .collect::<Result<Vec<_>, DatabaseError>>()?;
-        let updated_table = TableCatalog::reload(
+        let mut updated_table = TableCatalog::reload(
             table_name.clone(),
             column_catalogs.into_iter(),
             index_metas.into_iter(),
             plan_arena,
         )?;
+        updated_table.set_meta(table.meta());
         self.rewrite_table_metadata(table_codec, plan_arena, &updated_table)?;
         table_codec.with_statistics_bound(table_name.as_ref(), |min, max| {
             self.remove_range(Bound::Included(min), Bound::Included(max))
@@ -578,6 +643,7 @@ pub trait Transaction: Sized {
                 format!("uk_{}", column.name()),
                 vec![col_id],
                 IndexType::Unique,
+                vec![],
                 plan_arena,
             )?;
             let meta = plan_arena.index(meta_ref);
@@ -739,6 +805,97 @@ pub trait Transaction: Sized {
         Ok(true)
     }
 
+    fn create_sequence(
+        &mut self,
+        table_codec: &mut TableCodec,
+        arena: &impl MetaArena,
+        sequence: &Sequence,
+        if_not_exists: bool,
+    ) -> Result<bool, DatabaseError> {
+        if table_codec.with_sequence(sequence.name(), None, arena, |key, _| self.exists(key))? {
+            if if_not_exists {
+                return Ok(false);
+            }
+            return Err(DatabaseError::SequenceExists(sequence.name().to_string()));
+        }
+        self.save_sequence(table_codec, arena, sequence)?;
+
+        Ok(true)
+    }
+
+    fn save_sequence(
+        &mut self,
+        table_codec: &mut TableCodec,
+        arena: &impl MetaArena,
+        sequence: &Sequence,
+    ) -> Result<(), DatabaseError> {
+        table_codec.with_sequence(sequence.name(), Some(sequence), arena, |key, value| {
+            self.set(key, value)
+        })
+    }
+
+    fn drop_sequence(
+        &mut self,
+        table_codec: &mut TableCodec,
+        arena: &impl MetaArena,
+        sequence_name: &str,
+        if_exists: bool,
+    ) -> Result<bool, DatabaseError> {
+        if !table_codec.with_sequence(sequence_name, None, arena, |key, _| self.exists(key))? {
+            if if_exists {
+                return Ok(false);
+            }
+            return Err(DatabaseError::SequenceNotFound(sequence_name.to_string()));
+        }
+        table_codec.with_sequence(sequence_name, None, arena, |key, _| self.remove(key))?;
+
+        Ok(true)
+    }
+
+    fn sequence(
+        &self,
+        table_codec: &mut TableCodec,
+        arena: &mut impl MetaArena,
+        sequence_name: &str,
+    ) -> Result<Option<Sequence>, DatabaseError> {
+        let bytes = table_codec.with_sequence(sequence_name, None, &*arena, |key, _| {
+            Ok(self.get_borrowed(key)?.map(|bytes| bytes.as_ref().to_vec()))
+        })?;
+
+        bytes
+            .map(|bytes| TableCodec::decode_sequence::<Self>(&bytes, arena))
+            .transpose()
+    }
+
+    /// Hands out the next value of a sequence. Values come from the block cached for the
+    /// sequence while the stored record still names it, so concurrent writers only read
+    /// the record; once the block runs out a new one is reserved by advancing the stored
+    /// record within this transaction.
+    fn next_sequence_value(
+        &mut self,
+        table_codec: &mut TableCodec,
+        arena: &mut impl MetaArena,
+        cache: &SequenceCache,
+        sequence_name: &str,
+    ) -> Result<i64, DatabaseError> {
+        let mut sequence = self
+            .sequence(table_codec, arena, sequence_name)?
+            .ok_or_else(|| DatabaseError::SequenceNotFound(sequence_name.to_string()))?;
+        let value = match cache.take(&sequence) {
+            Some(value) => value,
+            None => {
+                let mut block = sequence.reserve(cache.next_block_id())?;
+                self.save_sequence(table_codec, &*arena, &sequence)?;
+                let value = block.take().expect("a reserved block holds values");
+                cache.publish(&sequence, block);
+                value
+            }
+        };
+        cache.set_last_value(sequence_name, value);
+
+        Ok(value)
+    }
+
     fn drop_index(
         &mut self,
         table_codec: &mut TableCodec,
@@ -849,12 +1006,19 @@ pub trait Transaction: Sized {
         view_cache: &'a ViewCache,
         scala_functions: &'a ScalaFunctions,
         table_functions: &'a TableFunctions,
+        aggregate_functions: &'a AggregateFunctions,
         view_name: TableName,
     ) -> Result<Option<&'a View>, DatabaseError> {
-        let _ = (table_cache, scala_functions, table_functions);
+        let _ = (
+            table_cache,
+            scala_functions,
+            table_functions,
+            aggregate_functions,
+        );
         Ok(view_cache.get(&view_name))
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn load_view(
         &self,
         table_codec: &mut TableCodec,
@@ -862,6 +1026,7 @@ pub trait Transaction: Sized {
         table_arena: &TableArenaCell,
         scala_functions: &ScalaFunctions,
         table_functions: &TableFunctions,
+        aggregate_functions: &AggregateFunctions,
         view_name: TableName,
     ) -> Result<Option<View>, DatabaseError> {
         table_codec.with_view(&view_name, |key, _| {
@@ -873,6 +1038,7 @@ pub trait Transaction: Sized {
                 (self, table_cache),
                 scala_functions,
                 table_functions,
+                aggregate_functions,
                 table_arena.borrow_mut(),
             )
             .map(Some)
@@ -886,6 +1052,7 @@ pub trait Transaction: Sized {
         table_arena: &'a TableArenaCell,
         scala_functions: &'a ScalaFunctions,
         table_functions: &'a TableFunctions,
+        aggregate_functions: &'a AggregateFunctions,
     ) -> Result<ViewIter<'a, Self>, DatabaseError> {
         table_codec.with_view_bound(|min, max| {
             Ok(ViewIter {
@@ -895,6 +1062,7 @@ pub trait Transaction: Sized {
                 table_arena,
                 scala_functions,
                 table_functions,
+                aggregate_functions,
             })
         })
     }
@@ -913,13 +1081,138 @@ pub trait Transaction: Sized {
         arena: &mut impl MetaArena,
         table_name: TableName,
     ) -> Result<Option<TableCatalog>, DatabaseError> {
-        self.table_collect(table_codec, &table_name, arena)?
-            .map(|(columns, indexes)| {
-                TableCatalog::reload(table_name, columns.into_iter(), indexes.into_iter(), arena)
-            })
+        let Some((columns, indexes)) = self.table_collect(table_codec, &table_name, arena)? else {
+            return Ok(None);
+        };
+        let meta = self.table_meta(table_codec, arena, &table_name)?;
+        let mut table =
+            TableCatalog::reload(table_name, columns.into_iter(), indexes.into_iter(), arena)?;
+        if let Some(meta) = meta {
+            table.set_meta(meta);
+        }
+
+        Ok(Some(table))
+    }
+
+    fn table_meta(
+        &self,
+        table_codec: &mut TableCodec,
+        arena: &mut impl MetaArena,
+        table_name: &TableName,
+    ) -> Result<Option<TableMeta>, DatabaseError> {
+        let bytes = table_codec.with_root_table(table_name.as_ref(), None, &*arena, |key, _| {
+            Ok(self.get_borrowed(key)?.map(|bytes| bytes.as_ref().to_vec()))
+        })?;
+
+        bytes
+            .map(|bytes| TableCodec::decode_root_table::<Self>(&bytes, arena))
             .transpose()
     }
 
+    /// Stores the constraints declared by `CREATE TABLE`, indexing the columns of each
+    /// foreign key no index covers yet and registering the table with the tables it
+    /// references. Returns the updated catalog and the updated metas of the other
+    /// referenced tables.
+    fn add_table_constraints(
+        &mut self,
+        table_codec: &mut TableCodec,
+        plan_arena: &mut PlanArena,
+        table_name: &TableName,
+        checks: Vec<CheckConstraint>,
+        foreign_keys: Vec<ForeignKey>,
+    ) -> Result<(TableCatalog, Vec<TableMeta>), DatabaseError> {
+        let table = self
+            .load_table(table_codec, plan_arena, table_name.clone())?
+            .ok_or(DatabaseError::TableNotFound)?;
+        let mut indexed = table
+            .indexes()
+            .map(|index| plan_arena.index(*index))
+            .filter(|index| index.json_path.is_empty())
+            .map(|index| index.column_ids.clone())
+            .collect_vec();
+        let mut referenced_tables = Vec::new();
+        for foreign_key in foreign_keys.iter() {
+            if !referenced_tables.contains(&foreign_key.referenced_table) {
+                referenced_tables.push(foreign_key.referenced_table.clone());
+            }
+            let column_ids = foreign_key
+                .columns
+                .iter()
+                .map(|name| {
+                    table
+                        .get_column_by_name(name)
+                        .and_then(|column| plan_arena.column(column).id())
+                        .ok_or_else(|| DatabaseError::column_not_found(name.clone()))
+                })
+                .collect::<Result<Vec<_>, DatabaseError>>()?;
+            if indexed.contains(&column_ids) {
+                continue;
+            }
+            let ty = if column_ids.len() == 1 {
+                IndexType::Normal
+            } else {
+                IndexType::Composite
+            };
+            self.add_index_meta(
+                table_codec,
+                plan_arena,
+                table_name,
+                format!("fk_{}_index", foreign_key.name),
+                column_ids.clone(),
+                ty,
+                vec![],
+            )?;
+            indexed.push(column_ids);
+        }
+
+        let self_referenced = referenced_tables.contains(table_name);
+        self.update_table_meta(table_codec, plan_arena, table_name, |meta| {
+            meta.checks = checks;
+            meta.foreign_keys = foreign_keys;
+            if self_referenced {
+                meta.referenced_by.push(table_name.clone());
+            }
+        })?;
+        let metas = referenced_tables
+            .into_iter()
+            .filter(|referenced_table| referenced_table != table_name)
+            .map(|referenced_table| {
+                self.update_table_meta(table_codec, plan_arena, &referenced_table, |meta| {
+                    if !meta.referenced_by.contains(table_name) {
+                        meta.referenced_by.push(table_name.clone());
+                    }
+                })
+            })
+            .collect::<Result<Vec<_>, DatabaseError>>()?;
+        let table = self
+            .load_table(table_codec, plan_arena, table_name.clone())?
+            .ok_or(DatabaseError::TableNotFound)?;
+
+        Ok((table, metas))
+    }
+
+    /// Rewrites the constraints kept in the meta of a table, returning the updated meta.
+    fn update_table_meta(
+        &mut self,
+        table_codec: &mut TableCodec,
+        plan_arena: &mut PlanArena,
+        table_name: &TableName,
+        f: impl FnOnce(&mut TableMeta),
+    ) -> Result<TableMeta, DatabaseError> {
+        let mut table_meta = self
+            .table_meta(table_codec, plan_arena, table_name)?
+            .ok_or(DatabaseError::TableNotFound)?;
+        f(&mut table_meta);
+        table_codec.with_root_table(
+            table_name.as_ref(),
+            Some(&table_meta),
+            plan_arena,
+            |key, value| self.set(key, value),
+        )?;
+
+        Ok(table_meta)
+    }
+
     fn tables<'a>(
         &'a self,
         table_codec: &mut TableCodec,
@@ -1115,7 +1408,8 @@ pub trait Transaction: Sized {
                 continue;
             };
             let index_name = format!("uk_{}_index", col.name());
-            let meta_ref = table.add_index_meta(index_name, vec![col_id], index_ty, arena)?;
+            let meta_ref =
+                table.add_index_meta(index_name, vec![col_id], index_ty, vec![], arena)?;
             let meta = arena.index(meta_ref);
             table_codec.with_index_meta(
                 &table_name,
@@ -1133,8 +1427,13 @@ pub trait Transaction: Sized {
         let pk_index_ty = IndexType::PrimaryKey {
             is_multiple: primary_keys.len() != 1,
         };
-        let meta_ref =
-            table.add_index_meta("pk_index".to_string(), primary_keys, pk_index_ty, arena)?;
+        let meta_ref = table.add_index_meta(
+            "pk_index".to_string(),
+            primary_keys,
+            pk_index_ty,
+            vec![],
+            arena,
+        )?;
         let meta = arena.index(meta_ref);
         table_codec.with_index_meta(&table_name, meta.id, Some(meta), arena, |key, value| {
             self.set(key, value)
@@ -1808,6 +2107,7 @@ pub struct TupleIter<'a, T: Transaction + 'a> {
     deserializers: Vec<TupleValueSerializableImpl>,
     total_len: usize,
     iter: T::IterType<'a>,
+    keys_read: usize,
 }
 
 impl<'a, T: Transaction + 'a> Iter for TupleIter<'a, T> {
@@ -1820,10 +2120,12 @@ impl<'a, T: Transaction + 'a> Iter for TupleIter<'a, T> {
             if self.iter.try_next()?.is_none() {
                 return Ok(false);
             }
+            self.keys_read += 1;
         }
 
         #[allow(clippy::never_loop)]
         while let Some((key, value)) = self.iter.try_next()? {
+            self.keys_read += 1;
             if self.bounds.limit_reached() {
                 return Ok(false);
             }
@@ -1846,6 +2148,10 @@ impl<'a, T: Transaction + 'a> Iter for TupleIter<'a, T> {
 
         Ok(false)
     }
+
+    fn take_keys_read(&mut self) -> usize {
+        mem::take(&mut self.keys_read)
+    }
 }
 
 enum IndexRangesInner {
@@ -1930,6 +2236,7 @@ pub struct IndexIter<'a, T: Transaction> {
     state: IndexIterState<'a, T>,
     encode_min_buffer: Bytes,
     encode_max_buffer: Bytes,
+    keys_read: usize,
 }
 
 pub enum IndexIterState<'a, T: Transaction + 'a> {
@@ -2006,6 +2313,7 @@ impl<T: Transaction> Iter for IndexIter<'_, T> {
                                 &mut self.encode_max_buffer,
                             )? {
                                 IndexResult::Hit => {
+                                    self.keys_read += 1;
                                     if self.bounds.consume_offset() {
                                         continue;
                                     }
@@ -2023,6 +2331,7 @@ impl<T: Transaction> Iter for IndexIter<'_, T> {
                 }
                 IndexIterState::Range(iter) => {
                     while let Some((key, value)) = iter.try_next()? {
+                        self.keys_read += 1;
                         if self.bounds.consume_offset() {
                             continue;
                         }
@@ -2043,6 +2352,10 @@ impl<T: Transaction> Iter for IndexIter<'_, T> {
             }
         }
     }
+
+    fn take_keys_read(&mut self) -> usize {
+        mem::take(&mut self.keys_read)
+    }
 }
 
 pub trait InnerIter {
@@ -2055,6 +2368,9 @@ pub trait Iter {
         table_codec: &mut TableCodec,
         tuple: &mut Tuple,
     ) -> Result<bool, DatabaseError>;
+
+    /// Returns how many keys were read from storage since the last call.
+    fn take_keys_read(&mut self) -> usize;
 }
 
 pub struct TableIter<'a, T: Transaction + 'a> {
@@ -2081,6 +2397,7 @@ pub struct ViewIter<'a, T: Transaction + 'a> {
     table_arena: &'a TableArenaCell,
     scala_functions: &'a ScalaFunctions,
     table_functions: &'a TableFunctions,
+    aggregate_functions: &'a AggregateFunctions,
 }
 
 impl<T: Transaction> ViewIter<'_, T> {
@@ -2094,6 +2411,7 @@ impl<T: Transaction> ViewIter<'_, T> {
             (self.transaction, self.table_cache),
             self.scala_functions,
             self.table_functions,
+            self.aggregate_functions,
             self.table_arena.borrow_mut(),
         )?))
     }
@@ -2404,6 +2722,7 @@ mod test {
             "i1".to_string(),
             vec![c3_column_id],
             IndexType::Normal,
+            vec![],
         )?;
         let table = table.transplant_to_table_arena(&plan_arena)?;
         table_cache.insert(table.name().clone(), table);
@@ -2415,6 +2734,7 @@ mod test {
             "i2".to_string(),
             vec![c3_column_id, c2_column_id],
             IndexType::Composite,
+            vec![],
         )?;
         let table = table.transplant_to_table_arena(&plan_arena)?;
         table_cache.insert(table.name().clone(), table);
@@ -2569,6 +2889,7 @@ mod test {
             "i1".to_string(),
             vec![c3_column_id],
             IndexType::Normal,
+            vec![],
         )?;
         let table = table.transplant_to_table_arena(&plan_arena)?;
         table_cache.insert(table.name().clone(), table);
@@ -2705,6 +3026,7 @@ mod test {
                 "i1".to_string(),
                 vec![c3_column_id],
                 IndexType::Normal,
+                vec![],
             )?;
             let table = table.transplant_to_table_arena(&plan_arena)?;
             table_cache.insert(table.name().clone(), table);
@@ -2874,6 +3196,7 @@ mod test {
         let table_state = build_t1_table()?;
         let scala_functions = Default::default();
         let table_functions = Default::default();
+        let aggregate_functions = Default::default();
 
         let view_name: TableName = "v1".to_string().into();
         let mut plan_arena = PlanArena::new(&table_state.table_arena);
@@ -2901,6 +3224,7 @@ mod test {
                     &view_cache,
                     &scala_functions,
                     &table_functions,
+                    &aggregate_functions,
                     view_name.clone(),
                 )?
                 .unwrap()
@@ -2913,6 +3237,7 @@ mod test {
                     &view_cache,
                     &scala_functions,
                     &table_functions,
+                    &aggregate_functions,
                     view_name.clone(),
                 )?
                 .unwrap()
@@ -2930,6 +3255,7 @@ mod test {
                 &view_cache,
                 &scala_functions,
                 &table_functions,
+                &aggregate_functions,
                 view_name,
             )?
             .is_none());
@@ -991,6 +991,7 @@ mod test {
             inner: IndexImplEnum::PrimaryKey(PrimaryKeyIndexImpl),
             encode_min_buffer: Vec::new(),
             encode_max_buffer: Vec::new(),
+            keys_read: 0,
         };
         let mut result = Vec::new();
 
@@ -12,9 +12,10 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::catalog::sequence::Sequence;
 use crate::catalog::view::View;
 use crate::catalog::{ColumnCatalog, ColumnRelation, TableMeta};
-use crate::db::{ScalaFunctions, TableFunctions};
+use crate::db::{AggregateFunctions, ScalaFunctions, TableFunctions};
 use crate::errors::DatabaseError;
 use crate::optimizer::core::cm_sketch::{CountMinSketchMeta, CountMinSketchPage};
 use crate::optimizer::core::histogram::Bucket;
@@ -48,6 +49,7 @@ const STATISTICS_BUCKET_ORD_LEN: usize = 4;
 static ROOT_BYTES: LazyLock<Vec<u8>> = LazyLock::new(|| b"Root".to_vec());
 static VIEW_BYTES: LazyLock<Vec<u8>> = LazyLock::new(|| b"View".to_vec());
 static HASH_BYTES: LazyLock<Vec<u8>> = LazyLock::new(|| b"Hash".to_vec());
+static SEQUENCE_BYTES: LazyLock<Vec<u8>> = LazyLock::new(|| b"Sequence".to_vec());
 static EMPTY_REFERENCE_TABLES: LazyLock<ReferenceTables> = LazyLock::new(ReferenceTables::new);
 
 pub type Bytes = Vec<u8>;
@@ -86,6 +88,7 @@ enum CodecType {
     Tuple,
     Root,
     Hash,
+    Sequence,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -225,6 +228,11 @@ impl TableCodec {
                 out.push(BOUND_MIN_TAG);
                 out.extend_from_slice(&table_hash);
             }
+            CodecType::Sequence => {
+                out.extend_from_slice(SEQUENCE_BYTES.as_slice());
+                out.push(BOUND_MIN_TAG);
+                out.extend_from_slice(&table_hash);
+            }
         }
     }
 
@@ -742,6 +750,36 @@ impl TableCodec {
             f(lower.as_slice(), value.as_slice())
         })
     }
+
+    /// Key: `Sequence{BOUND_MIN_TAG}{SequenceNameHash}`.
+    pub fn with_sequence<R>(
+        &mut self,
+        sequence_name: &str,
+        sequence: Option<&Sequence>,
+        arena: &impl MetaArena,
+        f: impl FnOnce(&[u8], &[u8]) -> Result<R, DatabaseError>,
+    ) -> Result<R, DatabaseError> {
+        self.clear_buffers();
+        self.with_table_hash_buffers(sequence_name, |lower, table_hash, value, refs| {
+            Self::write_key_prefix(lower, CodecType::Sequence, table_hash);
+
+            if let Some(sequence) = sequence {
+                sequence.encode(value, true, refs, arena)?;
+            }
+
+            f(lower.as_slice(), value.as_slice())
+        })
+    }
+
+    pub fn decode_sequence<T: Transaction>(
+        bytes: &[u8],
+        arena: &mut impl MetaArena,
+    ) -> Result<Sequence, DatabaseError> {
+        let mut bytes = Cursor::new(bytes);
+
+        Sequence::decode::<T, _, _>(&mut bytes, None, &EMPTY_REFERENCE_TABLES, arena)
+    }
+
     pub fn decode_tuple_key(bytes: &[u8], pk_ty: &LogicalType) -> Result<TupleId, DatabaseError> {
         DataValue::memcomparable_decode(&mut Cursor::new(&bytes[TUPLE_KEY_PREFIX_LEN..]), pk_ty)
     }
@@ -969,6 +1007,7 @@ impl TableCodec {
         drive: (&T, &TableCache),
         scala_functions: &ScalaFunctions,
         table_functions: &TableFunctions,
+        aggregate_functions: &AggregateFunctions,
         arena: &mut impl MetaArena,
     ) -> Result<View, DatabaseError> {
         let mut cursor = Cursor::new(bytes);
@@ -981,8 +1020,12 @@ impl TableCodec {
         let reference_tables = ReferenceTables::from_raw(&mut cursor)?;
         cursor.seek(SeekFrom::Start(4))?;
 
-        let context =
-            ReferenceDecodeContext::with_functions(Some(drive), scala_functions, table_functions);
+        let context = ReferenceDecodeContext::with_functions(
+            Some(drive),
+            scala_functions,
+            table_functions,
+            aggregate_functions,
+        );
         View::decode(&mut cursor, Some(&context), &reference_tables, arena)
     }
 
@@ -1008,8 +1051,12 @@ impl TableCodec {
 #[cfg(all(test, not(target_arch = "wasm32")))]
 mod tests {
     use crate::binder::test::build_t1_table;
+    use crate::catalog::sequence::Sequence;
     use crate::catalog::view::View;
-    use crate::catalog::{ColumnCatalog, ColumnDesc, ColumnRelation, TableCatalog, TableMeta};
+    use crate::catalog::{
+        CheckConstraint, ColumnCatalog, ColumnDesc, ColumnRelation, ForeignKey, ReferentialAction,
+        TableCatalog, TableMeta,
+    };
     use crate::errors::DatabaseError;
     use crate::iter_ext::Itertools;
     use crate::optimizer::core::histogram::{HistogramBuilder, ANALYZE_STATISTICS_RELATIVE_ERROR};
@@ -1089,6 +1136,19 @@ mod tests {
         let table_catalog = build_table_codec(&table_arena);
         let meta = TableMeta {
             table_name: table_catalog.name.clone(),
+            checks: vec![CheckConstraint {
+                name: "t1_c1_check".to_string(),
+                expr: "c1 > 0".to_string(),
+            }],
+            foreign_keys: vec![ForeignKey {
+                name: "t1_c2_fkey".to_string(),
+                columns: vec!["c2".to_string()],
+                referenced_table: "t2".into(),
+                referenced_columns: vec!["id".to_string()],
+                on_delete: ReferentialAction::Cascade,
+                on_update: ReferentialAction::Restrict,
+            }],
+            referenced_by: vec!["t3".into()],
         };
         let bytes = table_codec
             .with_root_table(
@@ -1104,6 +1164,39 @@ mod tests {
                 .unwrap();
 
         assert_eq!(table_meta.table_name.as_ref(), table_catalog.name.as_ref());
+        assert_eq!(table_meta, meta);
+    }
+
+    #[test]
+    fn test_sequence_codec() -> Result<(), DatabaseError> {
+        let mut table_codec = TableCodec::default();
+        let table_arena = TableArenaCell::default();
+        let mut sequence = Sequence::new(
+            "t1_id_seq".to_string(),
+            &LogicalType::Integer,
+            Some(5),
+            None,
+            None,
+            Some(10),
+            Some(4),
+        )?;
+        sequence.owned_by = Some("t1".into());
+        let _ = sequence.reserve(3)?;
+
+        let (key, bytes) = table_codec.with_sequence(
+            sequence.name(),
+            Some(&sequence),
+            table_arena.borrow(),
+            |key, value| Ok::<_, DatabaseError>((key.to_vec(), value.to_vec())),
+        )?;
+        assert!(key.starts_with(b"Sequence"));
+        let decoded =
+            TableCodec::decode_sequence::<RocksTransaction>(&bytes, table_arena.borrow_mut())?;
+        assert_eq!(decoded, sequence);
+        assert_eq!(decoded.next, Some(30));
+        assert_eq!(decoded.block, 3);
+
+        Ok(())
     }
 
     #[test]
@@ -1119,6 +1212,7 @@ mod tests {
             value_ty: LogicalType::Integer,
             name: "pk_c1".to_string(),
             ty: IndexType::PrimaryKey { is_multiple: false },
+            json_path: vec![],
         };
         let mut builder = HistogramBuilder::new(&index_meta, ANALYZE_STATISTICS_RELATIVE_ERROR)?;
 
@@ -1245,6 +1339,7 @@ mod tests {
             value_ty: LogicalType::Integer,
             name: "index_1".to_string(),
             ty: IndexType::PrimaryKey { is_multiple: false },
+            json_path: vec![],
         };
         let bytes =
             table_codec.with_index_meta("t1", 0, Some(&index_meta), &plan_arena, |_, value| {
@@ -1338,6 +1433,7 @@ mod tests {
         let table_state = build_t1_table()?;
         let scala_functions = Default::default();
         let table_functions = Default::default();
+        let aggregate_functions = Default::default();
         let build_view = |name: &str, sql: &str| -> Result<(View, PlanArena<'_>), DatabaseError> {
             let mut plan_arena = PlanArena::new(&table_state.table_arena);
             let mut plan = table_state.plan_with_arena(sql, &mut plan_arena)?;
@@ -1371,6 +1467,7 @@ mod tests {
                         (&transaction, &table_state.table_cache),
                         &scala_functions,
                         &table_functions,
+                        &aggregate_functions,
                         &mut decode_arena,
                     )
                 },
@@ -1399,6 +1496,7 @@ mod tests {
                         (&transaction, &table_state.table_cache),
                         &scala_functions,
                         &table_functions,
+                        &aggregate_functions,
                         &mut decode_arena,
                     )
                 },
@@ -1427,6 +1525,7 @@ mod tests {
                         (&transaction, &table_state.table_cache),
                         &scala_functions,
                         &table_functions,
+                        &aggregate_functions,
                         &mut decode_arena,
                     )
                 },
@@ -1517,6 +1616,7 @@ mod tests {
                 value_ty: LogicalType::Integer,
                 name: format!("{index_id}_index"),
                 ty: IndexType::PrimaryKey { is_multiple: false },
+                json_path: vec![],
             };
 
             let table_arena = TableArenaCell::default();
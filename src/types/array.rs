@@ -0,0 +1,209 @@
+// Copyright 2024 KipData/KiteSQL
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::errors::DatabaseError;
+use crate::types::value::DataValue;
+use std::fmt;
+use std::fmt::Write;
+
+/// Writes `values` in PostgreSQL's `{1,2,NULL}` text form, quoting the elements that would
+/// not read back as themselves. Each element is shown by `to_text`.
+pub(crate) fn write_array<W: Write>(
+    f: &mut W,
+    values: &[DataValue],
+    to_text: &impl Fn(&DataValue) -> String,
+) -> fmt::Result {
+    f.write_char('{')?;
+    for (i, value) in values.iter().enumerate() {
+        if i > 0 {
+            f.write_char(',')?;
+        }
+        match value {
+            DataValue::Null => f.write_str("NULL")?,
+            DataValue::Array(values) => write_array(f, values, to_text)?,
+            value => {
+                let text = to_text(value);
+
+                if needs_quotes(&text) {
+                    f.write_char('"')?;
+                    for c in text.chars() {
+                        if matches!(c, '"' | '\\') {
+                            f.write_char('\\')?;
+                        }
+                        f.write_char(c)?;
+                    }
+                    f.write_char('"')?;
+                } else {
+                    f.write_str(&text)?;
+                }
+            }
+        }
+    }
+    f.write_char('}')
+}
+
+fn needs_quotes(text: &str) -> bool {
+    text.is_empty()
+        || text.eq_ignore_ascii_case("null")
+        || text
+            .chars()
+            .any(|c| matches!(c, '{' | '}' | ',' | '"' | '\\') || c.is_whitespace())
+}
+
+/// Splits an array literal such as `{1,"a b",NULL}` into its elements: `None` for an unquoted
+/// `NULL`, the unescaped text otherwise. Nested arrays are returned with their braces so that
+/// the element cast can parse them in turn.
+pub(crate) fn parse_array(text: &str) -> Result<Vec<Option<String>>, DatabaseError> {
+    let invalid = || DatabaseError::InvalidValue(format!("malformed array literal: {text}"));
+    let inner = text
+        .trim()
+        .strip_prefix('{')
+        .and_then(|inner| inner.strip_suffix('}'))
+        .ok_or_else(invalid)?;
+    let mut elements = Vec::new();
+    if inner.trim().is_empty() {
+        return Ok(elements);
+    }
+    let mut chars = inner.chars().peekable();
+
+    loop {
+        while chars.next_if(|c| c.is_whitespace()).is_some() {}
+        let element = match chars.peek() {
+            Some('"') => {
+                chars.next();
+                let mut element = String::new();
+                loop {
+                    match chars.next().ok_or_else(invalid)? {
+                        '\\' => element.push(chars.next().ok_or_else(invalid)?),
+                        '"' => break,
+                        c => element.push(c),
+                    }
+                }
+                Some(element)
+            }
+            Some('{') => {
+                let mut element = String::new();
+                let mut depth = 0;
+                let mut quoted = false;
+                loop {
+                    let c = chars.next().ok_or_else(invalid)?;
+                    element.push(c);
+                    match c {
+                        '\\' if quoted => element.push(chars.next().ok_or_else(invalid)?),
+                        '"' => quoted = !quoted,
+                        '{' if !quoted => depth += 1,
+                        '}' if !quoted => {
+                            depth -= 1;
+                            if depth == 0 {
+                                break;
+                            }
+                        }
+                        _ => (),
+                    }
+                }
+                Some(element)
+            }
+            _ => {
+                let mut element = String::new();
+                while let Some(c) = chars.next_if(|c| *c != ',') {
+                    match c {
+                        '{' | '}' | '"' => return Err(invalid()),
+                        '\\' => element.push(chars.next().ok_or_else(invalid)?),
+                        c => element.push(c),
+                    }
+                }
+                let element = element.trim_end();
+                if element.is_empty() {
+                    return Err(invalid());
+                }
+                (!element.eq_ignore_ascii_case("null")).then(|| element.to_string())
+            }
+        };
+        elements.push(element);
+
+        while chars.next_if(|c| c.is_whitespace()).is_some() {}
+        match chars.next() {
+            None => break,
+            Some(',') => (),
+            Some(_) => return Err(invalid()),
+        }
+    }
+    Ok(elements)
+}
+
+// GRCOV_EXCL_START
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_array() -> Result<(), DatabaseError> {
+        assert_eq!(parse_array("{}")?, Vec::<Option<String>>::new());
+        assert_eq!(
+            parse_array(" { 1, 2 ,NULL} ")?,
+            vec![Some("1".to_string()), Some("2".to_string()), None]
+        );
+        assert_eq!(
+            parse_array(r#"{"a b","NULL","",x\,y,"q\"t"}"#)?,
+            vec![
+                Some("a b".to_string()),
+                Some("NULL".to_string()),
+                Some("".to_string()),
+                Some("x,y".to_string()),
+                Some("q\"t".to_string()),
+            ]
+        );
+        assert_eq!(
+            parse_array(r#"{{1,2},{"}",4}}"#)?,
+            vec![Some("{1,2}".to_string()), Some(r#"{"}",4}"#.to_string())]
+        );
+
+        for text in ["", "1,2", "{1,,2}", "{1", "{\"a}", "{a\"b}", "{1} 2}"] {
+            assert!(parse_array(text).is_err(), "{text}");
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_array() {
+        let mut text = String::new();
+        write_array(
+            &mut text,
+            &[
+                DataValue::Int32(1),
+                DataValue::Null,
+                DataValue::Array(vec![DataValue::Int32(2)]),
+                DataValue::from("a b".to_string()),
+                DataValue::from("null".to_string()),
+                DataValue::from("q\"t".to_string()),
+                DataValue::from(String::new()),
+            ],
+            &DataValue::to_string,
+        )
+        .unwrap();
+
+        assert_eq!(text, r#"{1,NULL,{2},"a b","null","q\"t",""}"#);
+        assert_eq!(
+            parse_array(&text).unwrap()[3..],
+            [
+                Some("a b".to_string()),
+                Some("null".to_string()),
+                Some("q\"t".to_string()),
+                Some(String::new()),
+            ]
+        );
+    }
+}
+// GRCOV_EXCL_STOP
@@ -0,0 +1,196 @@
+// Copyright 2024 KipData/KiteSQL
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::errors::DatabaseError;
+use crate::types::array::parse_array;
+use crate::types::evaluator::cast::{to_char, to_varchar};
+use crate::types::evaluator::{CastEvaluatorRef, DataValue};
+use crate::types::CharLengthUnits;
+use std::hint;
+
+macro_rules! array_binary {
+    ($name:ident, $op:tt) => {
+        pub fn $name(left: &DataValue, right: &DataValue) -> Result<DataValue, DatabaseError> {
+            Ok(match (left, right) {
+                (DataValue::Array(_), DataValue::Array(_)) => left
+                    .partial_cmp(right)
+                    .map(|order| DataValue::Boolean(order $op std::cmp::Ordering::Equal))
+                    .unwrap_or(DataValue::Null),
+                (DataValue::Array(_), DataValue::Null)
+                | (DataValue::Null, DataValue::Array(_))
+                | (DataValue::Null, DataValue::Null) => DataValue::Null,
+                _ => unsafe { hint::unreachable_unchecked() },
+            })
+        }
+    };
+}
+
+array_binary!(array_gt_binary_eval, >);
+array_binary!(array_gt_eq_binary_eval, >=);
+array_binary!(array_lt_binary_eval, <);
+array_binary!(array_lt_eq_binary_eval, <=);
+array_binary!(array_eq_binary_eval, ==);
+array_binary!(array_not_eq_binary_eval, !=);
+
+crate::define_cast_evaluator!(
+    array_to_char_cast_eval {
+        len: u32,
+        unit: CharLengthUnits
+    },
+    array @ DataValue::Array(_) => |this| to_char(array.to_string(), this.len, this.unit)
+);
+crate::define_cast_evaluator!(
+    array_to_varchar_cast_eval {
+        len: Option<u32>,
+        unit: CharLengthUnits
+    },
+    array @ DataValue::Array(_) => |this| to_varchar(array.to_string(), this.len, this.unit)
+);
+
+/// Casts every element of an array with `element`, the cast between the element types.
+pub(crate) fn eval_array_cast(
+    element: &CastEvaluatorRef,
+    value: &DataValue,
+) -> Result<DataValue, DatabaseError> {
+    match value {
+        DataValue::Null => Ok(DataValue::Null),
+        DataValue::Array(values) => Ok(DataValue::Array(
+            values
+                .iter()
+                .map(|value| element.eval(value))
+                .collect::<Result<_, _>>()?,
+        )),
+        _ => unsafe { hint::unreachable_unchecked() },
+    }
+}
+
+/// Parses a `{1,2,NULL}` literal, casting the text of every element with `element`.
+pub(crate) fn eval_utf8_to_array_cast(
+    element: &CastEvaluatorRef,
+    value: &DataValue,
+) -> Result<DataValue, DatabaseError> {
+    match value {
+        DataValue::Null => Ok(DataValue::Null),
+        DataValue::Utf8 { value, .. } => Ok(DataValue::Array(
+            parse_array(value)?
+                .into_iter()
+                .map(|text| match text {
+                    Some(text) => element.eval(&DataValue::from(text)),
+                    None => Ok(DataValue::Null),
+                })
+                .collect::<Result<_, _>>()?,
+        )),
+        _ => unsafe { hint::unreachable_unchecked() },
+    }
+}
+
+// GRCOV_EXCL_START
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod test {
+    use super::*;
+    use crate::types::evaluator::cast_create;
+    use crate::types::LogicalType;
+    use std::borrow::Cow;
+
+    fn int_array(values: &[Option<i32>]) -> DataValue {
+        DataValue::Array(
+            values
+                .iter()
+                .map(|value| value.map(DataValue::Int32).unwrap_or(DataValue::Null))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn test_array_binary_evaluators() -> Result<(), DatabaseError> {
+        let short = int_array(&[Some(1)]);
+        let long = int_array(&[Some(1), Some(2)]);
+        let with_null = int_array(&[Some(1), None]);
+
+        assert_eq!(
+            array_lt_binary_eval(&short, &long)?,
+            DataValue::Boolean(true)
+        );
+        assert_eq!(
+            array_gt_binary_eval(&with_null, &long)?,
+            DataValue::Boolean(true)
+        );
+        assert_eq!(
+            array_eq_binary_eval(&long, &long.clone())?,
+            DataValue::Boolean(true)
+        );
+        assert_eq!(
+            array_not_eq_binary_eval(&short, &long)?,
+            DataValue::Boolean(true)
+        );
+        assert_eq!(
+            array_lt_eq_binary_eval(&short, &DataValue::Null)?,
+            DataValue::Null
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_array_casts() -> Result<(), DatabaseError> {
+        let int_array_type = LogicalType::Array(Box::new(LogicalType::Integer));
+        let varchar = LogicalType::Varchar(None, CharLengthUnits::Characters);
+
+        let parsed = cast_create(Cow::Borrowed(&varchar), Cow::Borrowed(&int_array_type))?
+            .eval(&DataValue::from("{1, NULL,3}".to_string()))?;
+        assert_eq!(parsed, int_array(&[Some(1), None, Some(3)]));
+
+        let widened = cast_create(
+            Cow::Borrowed(&int_array_type),
+            Cow::Owned(LogicalType::Array(Box::new(LogicalType::Bigint))),
+        )?
+        .eval(&parsed)?;
+        assert_eq!(
+            widened,
+            DataValue::Array(vec![
+                DataValue::Int64(1),
+                DataValue::Null,
+                DataValue::Int64(3)
+            ])
+        );
+
+        let text =
+            cast_create(Cow::Borrowed(&int_array_type), Cow::Borrowed(&varchar))?.eval(&parsed)?;
+        assert_eq!(text, DataValue::from("{1,NULL,3}".to_string()));
+
+        let nested = cast_create(
+            Cow::Borrowed(&varchar),
+            Cow::Owned(LogicalType::Array(Box::new(int_array_type.clone()))),
+        )?
+        .eval(&DataValue::from("{{1},{2,3}}".to_string()))?;
+        assert_eq!(
+            nested,
+            DataValue::Array(vec![int_array(&[Some(1)]), int_array(&[Some(2), Some(3)])])
+        );
+
+        assert!(
+            cast_create(Cow::Borrowed(&varchar), Cow::Borrowed(&int_array_type))?
+                .eval(&DataValue::from("{a}".to_string()))
+                .is_err()
+        );
+        assert!(cast_create(
+            Cow::Borrowed(&int_array_type),
+            Cow::Owned(LogicalType::Integer)
+        )
+        .is_err());
+
+        Ok(())
+    }
+}
+// GRCOV_EXCL_STOP
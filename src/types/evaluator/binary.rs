@@ -14,6 +14,7 @@
 
 use crate::errors::DatabaseError;
 use crate::expression::BinaryOperator;
+use crate::types::evaluator::array::*;
 use crate::types::evaluator::boolean::*;
 #[cfg(feature = "time")]
 use crate::types::evaluator::date::*;
@@ -27,6 +28,8 @@ use crate::types::evaluator::int16::*;
 use crate::types::evaluator::int32::*;
 use crate::types::evaluator::int64::*;
 use crate::types::evaluator::int8::*;
+use crate::types::evaluator::interval::*;
+use crate::types::evaluator::json::*;
 use crate::types::evaluator::null::*;
 #[cfg(feature = "time")]
 use crate::types::evaluator::time32::*;
@@ -113,6 +116,44 @@ const TUPLE_GT_OFFSET: u16 = 2;
 const TUPLE_GT_EQ_OFFSET: u16 = 3;
 const TUPLE_LT_OFFSET: u16 = 4;
 const TUPLE_LT_EQ_OFFSET: u16 = 5;
+const TUPLE_OPS_LEN: u16 = TUPLE_LT_EQ_OFFSET + 1;
+
+const INTERVAL_PLUS_OFFSET: u16 = 0;
+const INTERVAL_MINUS_OFFSET: u16 = 1;
+const INTERVAL_GT_OFFSET: u16 = 2;
+const INTERVAL_GT_EQ_OFFSET: u16 = 3;
+const INTERVAL_LT_OFFSET: u16 = 4;
+const INTERVAL_LT_EQ_OFFSET: u16 = 5;
+const INTERVAL_EQ_OFFSET: u16 = 6;
+const INTERVAL_NOT_EQ_OFFSET: u16 = 7;
+const INTERVAL_OPS_LEN: u16 = INTERVAL_NOT_EQ_OFFSET + 1;
+
+#[cfg_attr(not(feature = "time"), allow(dead_code))]
+const TEMPORAL_PLUS_INTERVAL_OFFSET: u16 = 0;
+#[cfg_attr(not(feature = "time"), allow(dead_code))]
+const TEMPORAL_MINUS_INTERVAL_OFFSET: u16 = 1;
+#[cfg_attr(not(feature = "time"), allow(dead_code))]
+const TEMPORAL_MINUS_TEMPORAL_OFFSET: u16 = 2;
+const TEMPORAL_OPS_LEN: u16 = TEMPORAL_MINUS_TEMPORAL_OFFSET + 1;
+
+const JSON_GT_OFFSET: u16 = 0;
+const JSON_GT_EQ_OFFSET: u16 = 1;
+const JSON_LT_OFFSET: u16 = 2;
+const JSON_LT_EQ_OFFSET: u16 = 3;
+const JSON_EQ_OFFSET: u16 = 4;
+const JSON_NOT_EQ_OFFSET: u16 = 5;
+const JSON_OPS_LEN: u16 = JSON_NOT_EQ_OFFSET + 1;
+
+const ARRAY_GT_OFFSET: u16 = 0;
+const ARRAY_GT_EQ_OFFSET: u16 = 1;
+const ARRAY_LT_OFFSET: u16 = 2;
+const ARRAY_LT_EQ_OFFSET: u16 = 3;
+const ARRAY_EQ_OFFSET: u16 = 4;
+const ARRAY_NOT_EQ_OFFSET: u16 = 5;
+const ARRAY_OPS_LEN: u16 = ARRAY_NOT_EQ_OFFSET + 1;
+
+const INTERVAL_SCALE_MULTIPLY_OFFSET: u16 = 0;
+const INTERVAL_SCALE_DIVIDE_OFFSET: u16 = 1;
 
 const BINARY_INT8_BASE: u16 = 0;
 const BINARY_INT16_BASE: u16 = BINARY_INT8_BASE + NUMERIC_OPS_LEN;
@@ -133,6 +174,13 @@ const BINARY_BOOLEAN_BASE: u16 = BINARY_TIME64_BASE + TIMESTAMP_OPS_LEN;
 const BINARY_UTF8_BASE: u16 = BINARY_BOOLEAN_BASE + BOOLEAN_OPS_LEN;
 const BINARY_SQL_NULL: u16 = BINARY_UTF8_BASE + UTF8_OPS_LEN;
 const BINARY_TUPLE_BASE: u16 = BINARY_SQL_NULL + SQL_NULL_OPS_LEN;
+const BINARY_INTERVAL_BASE: u16 = BINARY_TUPLE_BASE + TUPLE_OPS_LEN;
+const BINARY_TEMPORAL_BASE: u16 = BINARY_INTERVAL_BASE + INTERVAL_OPS_LEN;
+#[cfg_attr(feature = "time", allow(dead_code))]
+const BINARY_END: u16 = BINARY_TEMPORAL_BASE + TEMPORAL_OPS_LEN;
+const BINARY_JSON_BASE: u16 = BINARY_TEMPORAL_BASE + TEMPORAL_OPS_LEN;
+const BINARY_ARRAY_BASE: u16 = BINARY_JSON_BASE + JSON_OPS_LEN;
+const BINARY_INTERVAL_SCALE_BASE: u16 = BINARY_ARRAY_BASE + ARRAY_OPS_LEN;
 
 // Evaluator positions are serialized ABI. Do not reorder or reuse existing
 // positions; only append new positions at the end of the current layout.
@@ -311,9 +359,158 @@ pub fn binary_create(
             }
             _ => Err(DatabaseError::UnsupportedBinaryOperator(ty.clone(), op)),
         },
+        LogicalType::Interval => match op {
+            BinaryOperator::Plus => {
+                unit_binary_ref(binary_pos(BINARY_INTERVAL_BASE, INTERVAL_PLUS_OFFSET))
+            }
+            BinaryOperator::Minus => {
+                unit_binary_ref(binary_pos(BINARY_INTERVAL_BASE, INTERVAL_MINUS_OFFSET))
+            }
+            BinaryOperator::Gt => {
+                unit_binary_ref(binary_pos(BINARY_INTERVAL_BASE, INTERVAL_GT_OFFSET))
+            }
+            BinaryOperator::GtEq => {
+                unit_binary_ref(binary_pos(BINARY_INTERVAL_BASE, INTERVAL_GT_EQ_OFFSET))
+            }
+            BinaryOperator::Lt => {
+                unit_binary_ref(binary_pos(BINARY_INTERVAL_BASE, INTERVAL_LT_OFFSET))
+            }
+            BinaryOperator::LtEq => {
+                unit_binary_ref(binary_pos(BINARY_INTERVAL_BASE, INTERVAL_LT_EQ_OFFSET))
+            }
+            BinaryOperator::Eq => {
+                unit_binary_ref(binary_pos(BINARY_INTERVAL_BASE, INTERVAL_EQ_OFFSET))
+            }
+            BinaryOperator::NotEq => {
+                unit_binary_ref(binary_pos(BINARY_INTERVAL_BASE, INTERVAL_NOT_EQ_OFFSET))
+            }
+            _ => Err(DatabaseError::UnsupportedBinaryOperator(ty.clone(), op)),
+        },
+        LogicalType::Json => match op {
+            BinaryOperator::Gt => unit_binary_ref(binary_pos(BINARY_JSON_BASE, JSON_GT_OFFSET)),
+            BinaryOperator::GtEq => {
+                unit_binary_ref(binary_pos(BINARY_JSON_BASE, JSON_GT_EQ_OFFSET))
+            }
+            BinaryOperator::Lt => unit_binary_ref(binary_pos(BINARY_JSON_BASE, JSON_LT_OFFSET)),
+            BinaryOperator::LtEq => {
+                unit_binary_ref(binary_pos(BINARY_JSON_BASE, JSON_LT_EQ_OFFSET))
+            }
+            BinaryOperator::Eq => unit_binary_ref(binary_pos(BINARY_JSON_BASE, JSON_EQ_OFFSET)),
+            BinaryOperator::NotEq => {
+                unit_binary_ref(binary_pos(BINARY_JSON_BASE, JSON_NOT_EQ_OFFSET))
+            }
+            _ => Err(DatabaseError::UnsupportedBinaryOperator(ty.clone(), op)),
+        },
+        LogicalType::Array(_) => match op {
+            BinaryOperator::Gt => unit_binary_ref(binary_pos(BINARY_ARRAY_BASE, ARRAY_GT_OFFSET)),
+            BinaryOperator::GtEq => {
+                unit_binary_ref(binary_pos(BINARY_ARRAY_BASE, ARRAY_GT_EQ_OFFSET))
+            }
+            BinaryOperator::Lt => unit_binary_ref(binary_pos(BINARY_ARRAY_BASE, ARRAY_LT_OFFSET)),
+            BinaryOperator::LtEq => {
+                unit_binary_ref(binary_pos(BINARY_ARRAY_BASE, ARRAY_LT_EQ_OFFSET))
+            }
+            BinaryOperator::Eq => unit_binary_ref(binary_pos(BINARY_ARRAY_BASE, ARRAY_EQ_OFFSET)),
+            BinaryOperator::NotEq => {
+                unit_binary_ref(binary_pos(BINARY_ARRAY_BASE, ARRAY_NOT_EQ_OFFSET))
+            }
+            _ => Err(DatabaseError::UnsupportedBinaryOperator(ty.clone(), op)),
+        },
     }
 }
 
+/// `interval * number`, `number * interval` and `interval / number`, whose operands keep
+/// their own types like the date/time arithmetic of `temporal_binary_create`.
+fn interval_scale_create(
+    left: &LogicalType,
+    right: &LogicalType,
+    op: &BinaryOperator,
+) -> Option<(BinaryEvaluatorRef, LogicalType)> {
+    let is_number = |ty: &LogicalType| ty.is_numeric() || ty == &LogicalType::SqlNull;
+    let offset = match (left, right, op) {
+        (LogicalType::Interval, number, BinaryOperator::Multiply)
+        | (number, LogicalType::Interval, BinaryOperator::Multiply)
+            if is_number(number) =>
+        {
+            INTERVAL_SCALE_MULTIPLY_OFFSET
+        }
+        (LogicalType::Interval, number, BinaryOperator::Divide) if is_number(number) => {
+            INTERVAL_SCALE_DIVIDE_OFFSET
+        }
+        _ => return None,
+    };
+    Some((
+        BinaryEvaluatorRef::new(
+            binary_pos(BINARY_INTERVAL_SCALE_BASE, offset),
+            BinaryEvaluatorParams::Unit,
+        ),
+        LogicalType::Interval,
+    ))
+}
+
+/// Date/time arithmetic whose operands keep their own types instead of being cast to a
+/// common one: `temporal ± interval`, `interval + temporal`, `timestamp - timestamp` and
+/// scaling an interval by a number.
+///
+/// Returns the evaluator together with the result type, or `None` when `binary_create`
+/// should handle the operands as usual.
+#[cfg(feature = "time")]
+pub fn temporal_binary_create(
+    left: &LogicalType,
+    right: &LogicalType,
+    op: &BinaryOperator,
+) -> Option<(BinaryEvaluatorRef, LogicalType)> {
+    if let Some(scale) = interval_scale_create(left, right, op) {
+        return Some(scale);
+    }
+    fn is_temporal(ty: &LogicalType) -> bool {
+        matches!(
+            ty,
+            LogicalType::Date | LogicalType::DateTime | LogicalType::TimeStamp(_, _)
+        )
+    }
+    fn is_timestamp(ty: &LogicalType) -> bool {
+        matches!(ty, LogicalType::DateTime | LogicalType::TimeStamp(_, _))
+    }
+    let temporal_ref = |offset| {
+        BinaryEvaluatorRef::new(
+            binary_pos(BINARY_TEMPORAL_BASE, offset),
+            BinaryEvaluatorParams::Unit,
+        )
+    };
+
+    match (left, right, op) {
+        (temporal, LogicalType::Interval, BinaryOperator::Plus) if is_temporal(temporal) => Some((
+            temporal_ref(TEMPORAL_PLUS_INTERVAL_OFFSET),
+            temporal.clone(),
+        )),
+        (LogicalType::Interval, temporal, BinaryOperator::Plus) if is_temporal(temporal) => Some((
+            temporal_ref(TEMPORAL_PLUS_INTERVAL_OFFSET),
+            temporal.clone(),
+        )),
+        (temporal, LogicalType::Interval, BinaryOperator::Minus) if is_temporal(temporal) => {
+            Some((
+                temporal_ref(TEMPORAL_MINUS_INTERVAL_OFFSET),
+                temporal.clone(),
+            ))
+        }
+        (end, start, BinaryOperator::Minus) if is_timestamp(end) && is_timestamp(start) => Some((
+            temporal_ref(TEMPORAL_MINUS_TEMPORAL_OFFSET),
+            LogicalType::Interval,
+        )),
+        _ => None,
+    }
+}
+
+#[cfg(not(feature = "time"))]
+pub fn temporal_binary_create(
+    left: &LogicalType,
+    right: &LogicalType,
+    op: &BinaryOperator,
+) -> Option<(BinaryEvaluatorRef, LogicalType)> {
+    interval_scale_create(left, right, op)
+}
+
 macro_rules! eval_numeric_binary {
     ($pos:expr, $base:expr, $value_type:ident, $left:expr, $right:expr) => {
         paste! {
@@ -512,6 +709,82 @@ pub(crate) fn eval_binary(
         x if x == binary_pos(BINARY_TUPLE_BASE, TUPLE_LT_EQ_OFFSET) => {
             tuple_lt_eq_binary_eval(left, right)
         }
+        x if x == binary_pos(BINARY_INTERVAL_BASE, INTERVAL_PLUS_OFFSET) => {
+            interval_plus_binary_eval(left, right)
+        }
+        x if x == binary_pos(BINARY_INTERVAL_BASE, INTERVAL_MINUS_OFFSET) => {
+            interval_minus_binary_eval(left, right)
+        }
+        x if x == binary_pos(BINARY_INTERVAL_BASE, INTERVAL_GT_OFFSET) => {
+            interval_gt_binary_eval(left, right)
+        }
+        x if x == binary_pos(BINARY_INTERVAL_BASE, INTERVAL_GT_EQ_OFFSET) => {
+            interval_gt_eq_binary_eval(left, right)
+        }
+        x if x == binary_pos(BINARY_INTERVAL_BASE, INTERVAL_LT_OFFSET) => {
+            interval_lt_binary_eval(left, right)
+        }
+        x if x == binary_pos(BINARY_INTERVAL_BASE, INTERVAL_LT_EQ_OFFSET) => {
+            interval_lt_eq_binary_eval(left, right)
+        }
+        x if x == binary_pos(BINARY_INTERVAL_BASE, INTERVAL_EQ_OFFSET) => {
+            interval_eq_binary_eval(left, right)
+        }
+        x if x == binary_pos(BINARY_INTERVAL_BASE, INTERVAL_NOT_EQ_OFFSET) => {
+            interval_not_eq_binary_eval(left, right)
+        }
+        #[cfg(feature = "time")]
+        x if x == binary_pos(BINARY_TEMPORAL_BASE, TEMPORAL_PLUS_INTERVAL_OFFSET) => {
+            temporal_plus_interval_binary_eval(left, right)
+        }
+        #[cfg(feature = "time")]
+        x if x == binary_pos(BINARY_TEMPORAL_BASE, TEMPORAL_MINUS_INTERVAL_OFFSET) => {
+            temporal_minus_interval_binary_eval(left, right)
+        }
+        #[cfg(feature = "time")]
+        x if x == binary_pos(BINARY_TEMPORAL_BASE, TEMPORAL_MINUS_TEMPORAL_OFFSET) => {
+            temporal_minus_temporal_binary_eval(left, right)
+        }
+        #[cfg(not(feature = "time"))]
+        BINARY_TEMPORAL_BASE..BINARY_END => Err(DatabaseError::UnsupportedStmt(
+            "time types require the `time` feature".to_string(),
+        )),
+        x if x == binary_pos(BINARY_JSON_BASE, JSON_GT_OFFSET) => json_gt_binary_eval(left, right),
+        x if x == binary_pos(BINARY_JSON_BASE, JSON_GT_EQ_OFFSET) => {
+            json_gt_eq_binary_eval(left, right)
+        }
+        x if x == binary_pos(BINARY_JSON_BASE, JSON_LT_OFFSET) => json_lt_binary_eval(left, right),
+        x if x == binary_pos(BINARY_JSON_BASE, JSON_LT_EQ_OFFSET) => {
+            json_lt_eq_binary_eval(left, right)
+        }
+        x if x == binary_pos(BINARY_JSON_BASE, JSON_EQ_OFFSET) => json_eq_binary_eval(left, right),
+        x if x == binary_pos(BINARY_JSON_BASE, JSON_NOT_EQ_OFFSET) => {
+            json_not_eq_binary_eval(left, right)
+        }
+        x if x == binary_pos(BINARY_ARRAY_BASE, ARRAY_GT_OFFSET) => {
+            array_gt_binary_eval(left, right)
+        }
+        x if x == binary_pos(BINARY_ARRAY_BASE, ARRAY_GT_EQ_OFFSET) => {
+            array_gt_eq_binary_eval(left, right)
+        }
+        x if x == binary_pos(BINARY_ARRAY_BASE, ARRAY_LT_OFFSET) => {
+            array_lt_binary_eval(left, right)
+        }
+        x if x == binary_pos(BINARY_ARRAY_BASE, ARRAY_LT_EQ_OFFSET) => {
+            array_lt_eq_binary_eval(left, right)
+        }
+        x if x == binary_pos(BINARY_ARRAY_BASE, ARRAY_EQ_OFFSET) => {
+            array_eq_binary_eval(left, right)
+        }
+        x if x == binary_pos(BINARY_ARRAY_BASE, ARRAY_NOT_EQ_OFFSET) => {
+            array_not_eq_binary_eval(left, right)
+        }
+        x if x == binary_pos(BINARY_INTERVAL_SCALE_BASE, INTERVAL_SCALE_MULTIPLY_OFFSET) => {
+            interval_multiply_binary_eval(left, right)
+        }
+        x if x == binary_pos(BINARY_INTERVAL_SCALE_BASE, INTERVAL_SCALE_DIVIDE_OFFSET) => {
+            interval_divide_binary_eval(left, right)
+        }
         _ => unreachable!("unknown binary evaluator position {pos}"),
     }
 }
@@ -715,6 +988,60 @@ mod test {
             .pos,
             binary_pos(BINARY_UTF8_BASE, UTF8_STRING_CONCAT_OFFSET)
         );
+        assert_eq!(BINARY_INTERVAL_BASE, BINARY_TUPLE_BASE + 6);
+        assert_eq!(
+            create(LogicalType::Interval, BinaryOperator::Minus)?.pos,
+            binary_pos(BINARY_INTERVAL_BASE, INTERVAL_MINUS_OFFSET)
+        );
+        assert_eq!(BINARY_INTERVAL_SCALE_BASE, BINARY_ARRAY_BASE + 6);
+
+        Ok(())
+    }
+
+    #[cfg(feature = "time")]
+    #[test]
+    fn test_temporal_binary_create() -> Result<(), DatabaseError> {
+        use crate::types::interval::Interval;
+
+        let timestamp = LogicalType::TimeStamp(Some(3), false);
+        let (evaluator, ty) = temporal_binary_create(
+            &LogicalType::Interval,
+            &LogicalType::Date,
+            &BinaryOperator::Plus,
+        )
+        .unwrap();
+        assert_eq!(ty, LogicalType::Date);
+        assert_eq!(
+            evaluator.binary_eval(
+                &DataValue::Interval(Interval::new(0, 1, 0)),
+                &DataValue::Date32(1)
+            )?,
+            DataValue::Date32(2)
+        );
+        assert_eq!(
+            temporal_binary_create(&timestamp, &LogicalType::Interval, &BinaryOperator::Minus)
+                .unwrap()
+                .1,
+            timestamp
+        );
+        let (evaluator, ty) =
+            temporal_binary_create(&timestamp, &LogicalType::DateTime, &BinaryOperator::Minus)
+                .unwrap();
+        assert_eq!(ty, LogicalType::Interval);
+        assert_eq!(
+            evaluator.pos,
+            binary_pos(BINARY_TEMPORAL_BASE, TEMPORAL_MINUS_TEMPORAL_OFFSET)
+        );
+        assert!(temporal_binary_create(
+            &LogicalType::Date,
+            &LogicalType::Date,
+            &BinaryOperator::Minus
+        )
+        .is_none());
+        assert!(
+            temporal_binary_create(&LogicalType::Interval, &timestamp, &BinaryOperator::Minus)
+                .is_none()
+        );
 
         Ok(())
     }
@@ -13,6 +13,9 @@
 // limitations under the License.
 
 use crate::errors::DatabaseError;
+use crate::types::evaluator::array::{
+    array_to_char_cast_eval, array_to_varchar_cast_eval, eval_array_cast, eval_utf8_to_array_cast,
+};
 use crate::types::evaluator::boolean::*;
 #[cfg(feature = "time")]
 use crate::types::evaluator::date::*;
@@ -26,6 +29,10 @@ use crate::types::evaluator::int16::*;
 use crate::types::evaluator::int32::*;
 use crate::types::evaluator::int64::*;
 use crate::types::evaluator::int8::*;
+use crate::types::evaluator::interval::{
+    interval_to_char_cast_eval, interval_to_varchar_cast_eval,
+};
+use crate::types::evaluator::json::{json_to_char_cast_eval, json_to_varchar_cast_eval};
 use crate::types::evaluator::null::{null_cast_eval, to_sql_null_cast_eval};
 #[cfg(feature = "time")]
 use crate::types::evaluator::time32::*;
@@ -74,6 +81,9 @@ const CAST_TIMESTAMP: u16 = 17;
 #[cfg(feature = "decimal")]
 const CAST_DECIMAL: u16 = 18;
 const CAST_TUPLE: u16 = 19;
+const CAST_INTERVAL: u16 = 20;
+const CAST_JSON: u16 = 21;
+const CAST_ARRAY: u16 = 22;
 
 // Cast positions are serialized ABI. Type codes above must never be reordered
 // or reused; new cast families should append a new code and keep old positions.
@@ -102,6 +112,9 @@ fn cast_type_code(ty: &LogicalType) -> u16 {
         #[cfg(not(feature = "decimal"))]
         LogicalType::Decimal(_, _) => unreachable!("DECIMAL requires the `decimal` feature"),
         LogicalType::Tuple(_) => CAST_TUPLE,
+        LogicalType::Interval => CAST_INTERVAL,
+        LogicalType::Json => CAST_JSON,
+        LogicalType::Array(_) => CAST_ARRAY,
     }
 }
 
@@ -711,6 +724,24 @@ pub fn cast_create(
         (LogicalType::Char(_, _) | LogicalType::Varchar(_, _), LogicalType::Date) => {
             cast_ref!(from, to)
         }
+        (LogicalType::Char(_, _) | LogicalType::Varchar(_, _), LogicalType::Interval) => {
+            cast_ref!(from, to)
+        }
+        (LogicalType::Interval, LogicalType::Char(len, unit)) => {
+            cast_string_ref!(from, to, Some(*len), *unit)
+        }
+        (LogicalType::Interval, LogicalType::Varchar(len, unit)) => {
+            cast_string_ref!(from, to, *len, *unit)
+        }
+        (LogicalType::Char(_, _) | LogicalType::Varchar(_, _), LogicalType::Json) => {
+            cast_ref!(from, to)
+        }
+        (LogicalType::Json, LogicalType::Char(len, unit)) => {
+            cast_string_ref!(from, to, Some(*len), *unit)
+        }
+        (LogicalType::Json, LogicalType::Varchar(len, unit)) => {
+            cast_string_ref!(from, to, *len, *unit)
+        }
         (LogicalType::Char(_, _) | LogicalType::Varchar(_, _), LogicalType::DateTime) => {
             cast_ref!(from, to)
         }
@@ -799,6 +830,33 @@ pub fn cast_create(
             LogicalType::Varchar(len, unit) => cast_string_ref!(from, to, *len, *unit),
             _ => Err(cast_fail(from.clone(), to.clone())),
         },
+        (LogicalType::Char(_, _) | LogicalType::Varchar(_, _), LogicalType::Array(to_element)) => {
+            let element = cast_create(
+                Cow::Owned(LogicalType::Varchar(None, CharLengthUnits::Characters)),
+                Cow::Borrowed(to_element),
+            )?;
+            Ok(CastEvaluatorRef::new(
+                cast_pos(from, to),
+                CastEvaluatorParams::Array {
+                    element: Box::new(element),
+                },
+            ))
+        }
+        (LogicalType::Array(_), LogicalType::Char(len, unit)) => {
+            cast_string_ref!(from, to, Some(*len), *unit)
+        }
+        (LogicalType::Array(_), LogicalType::Varchar(len, unit)) => {
+            cast_string_ref!(from, to, *len, *unit)
+        }
+        (LogicalType::Array(from_element), LogicalType::Array(to_element)) => {
+            let element = cast_create(Cow::Borrowed(from_element), Cow::Borrowed(to_element))?;
+            Ok(CastEvaluatorRef::new(
+                cast_pos(from, to),
+                CastEvaluatorParams::Array {
+                    element: Box::new(element),
+                },
+            ))
+        }
         (LogicalType::Tuple(from_types), LogicalType::Tuple(to_types)) => {
             let evaluators = from_types
                 .iter()
@@ -835,6 +893,13 @@ fn string_param(params: &CastEvaluatorParams) -> (Option<u32>, CharLengthUnits)
     (*len, *unit)
 }
 
+fn array_element_param(params: &CastEvaluatorParams) -> &CastEvaluatorRef {
+    let CastEvaluatorParams::Array { element } = params else {
+        unreachable!("array cast must have an element evaluator")
+    };
+    element
+}
+
 #[cfg(feature = "decimal")]
 fn decimal_param(params: &CastEvaluatorParams) -> (Option<u8>, Option<u8>) {
     let CastEvaluatorParams::Decimal { precision, scale } = params else {
@@ -1022,6 +1087,8 @@ impl CastEvaluatorRef {
                 let (len, unit) = string_param(params);
                 run!(utf8_to_varchar_cast_eval { len, unit })
             }
+            (CAST_CHAR | CAST_VARCHAR, CAST_INTERVAL) => run!(utf8_to_interval_cast_eval),
+            (CAST_CHAR | CAST_VARCHAR, CAST_JSON) => run!(utf8_to_json_cast_eval),
             #[cfg(feature = "time")]
             (CAST_CHAR | CAST_VARCHAR, CAST_DATE) => run!(utf8_to_date_cast_eval),
             #[cfg(feature = "time")]
@@ -1135,12 +1202,43 @@ impl CastEvaluatorRef {
                 CAST_UBIGINT => run!(decimal_to_ubigint_cast_eval),
                 _ => unreachable!("invalid decimal cast evaluator position"),
             },
+            (CAST_INTERVAL, CAST_CHAR) => {
+                let (len, unit) = string_param(params);
+                let len = len.expect("char cast must have fixed length");
+                run!(interval_to_char_cast_eval { len, unit })
+            }
+            (CAST_INTERVAL, CAST_VARCHAR) => {
+                let (len, unit) = string_param(params);
+                run!(interval_to_varchar_cast_eval { len, unit })
+            }
+            (CAST_JSON, CAST_CHAR) => {
+                let (len, unit) = string_param(params);
+                let len = len.expect("char cast must have fixed length");
+                run!(json_to_char_cast_eval { len, unit })
+            }
+            (CAST_JSON, CAST_VARCHAR) => {
+                let (len, unit) = string_param(params);
+                run!(json_to_varchar_cast_eval { len, unit })
+            }
             (CAST_TUPLE, CAST_TUPLE) => {
                 let CastEvaluatorParams::Tuple { evaluators } = params else {
                     unreachable!("tuple cast must have tuple parameters")
                 };
                 eval_tuple_cast(evaluators, value)
             }
+            (CAST_CHAR | CAST_VARCHAR, CAST_ARRAY) => {
+                eval_utf8_to_array_cast(array_element_param(params), value)
+            }
+            (CAST_ARRAY, CAST_CHAR) => {
+                let (len, unit) = string_param(params);
+                let len = len.expect("char cast must have fixed length");
+                run!(array_to_char_cast_eval { len, unit })
+            }
+            (CAST_ARRAY, CAST_VARCHAR) => {
+                let (len, unit) = string_param(params);
+                run!(array_to_varchar_cast_eval { len, unit })
+            }
+            (CAST_ARRAY, CAST_ARRAY) => eval_array_cast(array_element_param(params), value),
             _ => unreachable!("invalid cast evaluator position"),
         }
     }
@@ -1278,6 +1376,11 @@ mod test {
                 CastEvaluatorParams::Unit,
                 DataValue::Tuple(vec![DataValue::Int32(1)], false),
             ),
+            (
+                CAST_ARRAY * CAST_TYPE_STRIDE + CAST_ARRAY,
+                CastEvaluatorParams::Unit,
+                DataValue::Array(vec![DataValue::Int32(1)]),
+            ),
             (u16::MAX, CastEvaluatorParams::Unit, DataValue::Int32(1)),
         ];
 
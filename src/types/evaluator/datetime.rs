@@ -15,6 +15,7 @@
 use crate::numeric_binary_evaluator_definition;
 use crate::types::evaluator::cast::{cast_fail, to_char, to_varchar};
 use crate::types::evaluator::DataValue;
+use crate::types::timezone::session_time_zone;
 use crate::types::CharLengthUnits;
 use crate::types::LogicalType;
 use chrono::{DateTime, Datelike, Timelike};
@@ -81,7 +82,23 @@ crate::define_cast_evaluator!(
         zone: bool
     },
     DataValue::Date64(value) => |this| {
-        Ok(DataValue::Time64(*value, this.precision.unwrap_or(0), this.zone))
+        let precision = this.precision.unwrap_or(0);
+        let mut value = DateTime::from_timestamp(*value, 0).ok_or_else(|| {
+            cast_fail(
+                LogicalType::DateTime,
+                LogicalType::TimeStamp(this.precision, this.zone),
+            )
+        })?;
+        if this.zone {
+            // wall clock time of the session time zone
+            value = session_time_zone().to_utc(&value.naive_utc()).and_utc();
+        }
+
+        Ok(DataValue::Time64(
+            DataValue::timestamp_precision(value, precision),
+            precision,
+            this.zone,
+        ))
     }
 );
 
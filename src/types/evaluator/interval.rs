@@ -0,0 +1,368 @@
+// Copyright 2024 KipData/KiteSQL
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::errors::DatabaseError;
+use crate::types::evaluator::cast::{to_char, to_varchar};
+use crate::types::evaluator::DataValue;
+use crate::types::{CharLengthUnits, LogicalType};
+use std::hint;
+
+macro_rules! interval_binary {
+    ($name:ident, $op:tt) => {
+        pub fn $name(left: &DataValue, right: &DataValue) -> Result<DataValue, DatabaseError> {
+            Ok(match (left, right) {
+                (DataValue::Interval(v1), DataValue::Interval(v2)) => DataValue::Boolean(v1 $op v2),
+                (DataValue::Interval(_), DataValue::Null)
+                | (DataValue::Null, DataValue::Interval(_))
+                | (DataValue::Null, DataValue::Null) => DataValue::Null,
+                _ => unsafe { hint::unreachable_unchecked() },
+            })
+        }
+    };
+}
+
+interval_binary!(interval_gt_binary_eval, >);
+interval_binary!(interval_gt_eq_binary_eval, >=);
+interval_binary!(interval_lt_binary_eval, <);
+interval_binary!(interval_lt_eq_binary_eval, <=);
+interval_binary!(interval_eq_binary_eval, ==);
+interval_binary!(interval_not_eq_binary_eval, !=);
+
+pub fn interval_plus_binary_eval(
+    left: &DataValue,
+    right: &DataValue,
+) -> Result<DataValue, DatabaseError> {
+    Ok(match (left, right) {
+        (DataValue::Interval(v1), DataValue::Interval(v2)) => {
+            DataValue::Interval(v1.checked_add(v2).ok_or(DatabaseError::OverFlow)?)
+        }
+        (DataValue::Interval(_), DataValue::Null)
+        | (DataValue::Null, DataValue::Interval(_))
+        | (DataValue::Null, DataValue::Null) => DataValue::Null,
+        _ => unsafe { hint::unreachable_unchecked() },
+    })
+}
+
+pub fn interval_minus_binary_eval(
+    left: &DataValue,
+    right: &DataValue,
+) -> Result<DataValue, DatabaseError> {
+    Ok(match (left, right) {
+        (DataValue::Interval(v1), DataValue::Interval(v2)) => {
+            DataValue::Interval(v1.checked_sub(v2).ok_or(DatabaseError::OverFlow)?)
+        }
+        (DataValue::Interval(_), DataValue::Null)
+        | (DataValue::Null, DataValue::Interval(_))
+        | (DataValue::Null, DataValue::Null) => DataValue::Null,
+        _ => unsafe { hint::unreachable_unchecked() },
+    })
+}
+
+fn scale_factor(value: &DataValue) -> Result<f64, DatabaseError> {
+    Ok(value
+        .clone()
+        .cast(&LogicalType::Double)?
+        .double()
+        .unwrap_or_default())
+}
+
+/// `interval * number` and `number * interval`.
+pub fn interval_multiply_binary_eval(
+    left: &DataValue,
+    right: &DataValue,
+) -> Result<DataValue, DatabaseError> {
+    Ok(match (left, right) {
+        (DataValue::Null, _) | (_, DataValue::Null) => DataValue::Null,
+        (DataValue::Interval(v), factor) | (factor, DataValue::Interval(v)) => DataValue::Interval(
+            v.checked_mul_f64(scale_factor(factor)?)
+                .ok_or(DatabaseError::OverFlow)?,
+        ),
+        _ => unsafe { hint::unreachable_unchecked() },
+    })
+}
+
+/// `interval / number`.
+pub fn interval_divide_binary_eval(
+    left: &DataValue,
+    right: &DataValue,
+) -> Result<DataValue, DatabaseError> {
+    Ok(match (left, right) {
+        (DataValue::Null, _) | (_, DataValue::Null) => DataValue::Null,
+        (DataValue::Interval(v), divisor) => {
+            let divisor = scale_factor(divisor)?;
+            if divisor == 0.0 {
+                return Err(DatabaseError::DivisionByZero);
+            }
+            DataValue::Interval(v.checked_div_f64(divisor).ok_or(DatabaseError::OverFlow)?)
+        }
+        _ => unsafe { hint::unreachable_unchecked() },
+    })
+}
+
+crate::define_cast_evaluator!(
+    interval_to_char_cast_eval {
+        len: u32,
+        unit: CharLengthUnits
+    },
+    DataValue::Interval(value) => |this| to_char(value.to_string(), this.len, this.unit)
+);
+crate::define_cast_evaluator!(
+    interval_to_varchar_cast_eval {
+        len: Option<u32>,
+        unit: CharLengthUnits
+    },
+    DataValue::Interval(value) => |this| to_varchar(value.to_string(), this.len, this.unit)
+);
+
+#[cfg(feature = "time")]
+mod temporal {
+    use crate::errors::DatabaseError;
+    use crate::types::interval::Interval;
+    use crate::types::timezone::session_time_zone;
+    use crate::types::value::DataValue;
+    use chrono::{DateTime, Datelike, NaiveDate, NaiveDateTime, NaiveTime, TimeDelta};
+
+    /// The date or timestamp held by `value` as a `NaiveDateTime`, `None` for other values.
+    /// A timestamp with time zone gives its UTC instant.
+    pub(crate) fn to_instant(value: &DataValue) -> Option<NaiveDateTime> {
+        match value {
+            DataValue::Date32(days) => NaiveDate::from_num_days_from_ce_opt(*days)
+                .map(|date| date.and_time(NaiveTime::MIN)),
+            DataValue::Date64(secs) => DateTime::from_timestamp(*secs, 0).map(|v| v.naive_utc()),
+            DataValue::Time64(v, precision, _) => {
+                DataValue::from_timestamp_precision(*v, *precision).map(|v| v.naive_utc())
+            }
+            _ => None,
+        }
+    }
+
+    /// Like [`to_instant`], but a timestamp with time zone gives its wall clock time in the
+    /// session time zone, which is what fields, truncation and formatting work on.
+    pub(crate) fn to_naive_datetime(value: &DataValue) -> Option<NaiveDateTime> {
+        let datetime = to_instant(value)?;
+        Some(match value {
+            DataValue::Time64(_, _, true) => session_time_zone().to_local(&datetime),
+            _ => datetime,
+        })
+    }
+
+    /// Stores the wall clock time `datetime` in the same variant, and for timestamps the same
+    /// precision, as `like`, which is how date arithmetic keeps the type of its temporal
+    /// operand.
+    pub(crate) fn from_naive_datetime(
+        like: &DataValue,
+        datetime: NaiveDateTime,
+    ) -> Result<DataValue, DatabaseError> {
+        match like {
+            DataValue::Time64(_, _, true) => {
+                from_instant(like, session_time_zone().to_utc(&datetime))
+            }
+            _ => from_instant(like, datetime),
+        }
+    }
+
+    fn from_instant(like: &DataValue, datetime: NaiveDateTime) -> Result<DataValue, DatabaseError> {
+        Ok(match like {
+            DataValue::Date32(_) => {
+                if datetime.time() != NaiveTime::MIN {
+                    return Err(DatabaseError::InvalidValue(
+                        "a DATE can only be moved by whole days, cast it to a TIMESTAMP first"
+                            .to_string(),
+                    ));
+                }
+                DataValue::Date32(datetime.num_days_from_ce())
+            }
+            DataValue::Date64(_) => DataValue::Date64(datetime.and_utc().timestamp()),
+            DataValue::Time64(_, precision, zone) => {
+                let datetime = datetime.and_utc();
+                if *precision == 9 && datetime.timestamp_nanos_opt().is_none() {
+                    return Err(DatabaseError::OverFlow);
+                }
+                DataValue::Time64(
+                    DataValue::timestamp_precision(datetime, *precision),
+                    *precision,
+                    *zone,
+                )
+            }
+            _ => unreachable!("only dates and timestamps are moved by intervals"),
+        })
+    }
+
+    fn shift(
+        temporal: &DataValue,
+        interval: &Interval,
+        is_plus: bool,
+    ) -> Result<DataValue, DatabaseError> {
+        let interval = if is_plus {
+            *interval
+        } else {
+            interval.checked_neg().ok_or(DatabaseError::OverFlow)?
+        };
+        if let DataValue::Time64(_, _, true) = temporal {
+            // like PostgreSQL, months and days keep the wall clock time of the session time
+            // zone across daylight saving time changes, the clock part is absolute time
+            let calendar = Interval::new(interval.months, interval.days, 0);
+            let datetime = to_naive_datetime(temporal).ok_or(DatabaseError::OverFlow)?;
+            let datetime = calendar
+                .add_to(datetime)
+                .map(|datetime| session_time_zone().to_utc(&datetime))
+                .and_then(|datetime| {
+                    datetime.checked_add_signed(TimeDelta::nanoseconds(interval.nanos))
+                })
+                .ok_or(DatabaseError::OverFlow)?;
+            return from_instant(temporal, datetime);
+        }
+        let datetime = to_naive_datetime(temporal).ok_or(DatabaseError::OverFlow)?;
+        from_naive_datetime(
+            temporal,
+            interval.add_to(datetime).ok_or(DatabaseError::OverFlow)?,
+        )
+    }
+
+    pub fn temporal_plus_interval_binary_eval(
+        left: &DataValue,
+        right: &DataValue,
+    ) -> Result<DataValue, DatabaseError> {
+        match (left, right) {
+            (DataValue::Null, _) | (_, DataValue::Null) => Ok(DataValue::Null),
+            (DataValue::Interval(interval), temporal)
+            | (temporal, DataValue::Interval(interval)) => shift(temporal, interval, true),
+            _ => unsafe { std::hint::unreachable_unchecked() },
+        }
+    }
+
+    pub fn temporal_minus_interval_binary_eval(
+        left: &DataValue,
+        right: &DataValue,
+    ) -> Result<DataValue, DatabaseError> {
+        match (left, right) {
+            (DataValue::Null, _) | (_, DataValue::Null) => Ok(DataValue::Null),
+            (temporal, DataValue::Interval(interval)) => shift(temporal, interval, false),
+            _ => unsafe { std::hint::unreachable_unchecked() },
+        }
+    }
+
+    pub fn temporal_minus_temporal_binary_eval(
+        left: &DataValue,
+        right: &DataValue,
+    ) -> Result<DataValue, DatabaseError> {
+        if left.is_null() || right.is_null() {
+            return Ok(DataValue::Null);
+        }
+        let (Some(end), Some(start)) = (to_instant(left), to_instant(right)) else {
+            return Err(DatabaseError::OverFlow);
+        };
+        Interval::between(end, start)
+            .map(DataValue::Interval)
+            .ok_or(DatabaseError::OverFlow)
+    }
+}
+
+#[cfg(feature = "time")]
+pub use temporal::*;
+
+// GRCOV_EXCL_START
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod test {
+    use super::*;
+    use crate::types::interval::Interval;
+
+    #[test]
+    fn test_interval_binary_evaluators() -> Result<(), DatabaseError> {
+        let day = DataValue::Interval(Interval::new(0, 1, 0));
+        let month = DataValue::Interval(Interval::new(1, 0, 0));
+
+        assert_eq!(
+            interval_plus_binary_eval(&day, &month)?,
+            DataValue::Interval(Interval::new(1, 1, 0))
+        );
+        assert_eq!(
+            interval_minus_binary_eval(&day, &month)?,
+            DataValue::Interval(Interval::new(-1, 1, 0))
+        );
+        assert_eq!(
+            interval_lt_binary_eval(&day, &month)?,
+            DataValue::Boolean(true)
+        );
+        assert_eq!(
+            interval_eq_binary_eval(&day, &DataValue::Null)?,
+            DataValue::Null
+        );
+        assert!(matches!(
+            interval_plus_binary_eval(&DataValue::Interval(Interval::new(i32::MAX, 0, 0)), &month),
+            Err(DatabaseError::OverFlow)
+        ));
+
+        Ok(())
+    }
+
+    #[cfg(feature = "time")]
+    #[test]
+    fn test_temporal_binary_evaluators() -> Result<(), DatabaseError> {
+        use chrono::{Datelike, NaiveDate};
+
+        let date = |y, m, d| {
+            DataValue::Date32(NaiveDate::from_ymd_opt(y, m, d).unwrap().num_days_from_ce())
+        };
+        let timestamp = |y, m, d, h| {
+            let datetime = NaiveDate::from_ymd_opt(y, m, d)
+                .unwrap()
+                .and_hms_opt(h, 0, 0)
+                .unwrap()
+                .and_utc();
+            DataValue::Time64(DataValue::timestamp_precision(datetime, 3), 3, false)
+        };
+
+        assert_eq!(
+            temporal_plus_interval_binary_eval(
+                &date(2024, 1, 31),
+                &DataValue::Interval(Interval::new(1, 0, 0))
+            )?,
+            date(2024, 2, 29)
+        );
+        assert_eq!(
+            temporal_plus_interval_binary_eval(
+                &DataValue::Interval(Interval::new(0, 2, 0)),
+                &date(2024, 2, 28)
+            )?,
+            date(2024, 3, 1)
+        );
+        assert!(temporal_minus_interval_binary_eval(
+            &date(2024, 1, 1),
+            &DataValue::Interval(Interval::parse("1 hour")?)
+        )
+        .is_err());
+        assert_eq!(
+            temporal_minus_interval_binary_eval(
+                &timestamp(2024, 3, 1, 0),
+                &DataValue::Interval(Interval::parse("1 day 1 hour")?)
+            )?,
+            timestamp(2024, 2, 28, 23)
+        );
+        assert_eq!(
+            temporal_minus_temporal_binary_eval(
+                &timestamp(2024, 3, 1, 6),
+                &timestamp(2024, 2, 28, 0)
+            )?,
+            DataValue::Interval(Interval::parse("2 days 6 hours")?)
+        );
+        assert_eq!(
+            temporal_minus_interval_binary_eval(&DataValue::Null, &DataValue::Null)?,
+            DataValue::Null
+        );
+
+        Ok(())
+    }
+}
+// GRCOV_EXCL_STOP
@@ -0,0 +1,55 @@
+// Copyright 2024 KipData/KiteSQL
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::errors::DatabaseError;
+use crate::types::evaluator::cast::{to_char, to_varchar};
+use crate::types::evaluator::DataValue;
+use crate::types::CharLengthUnits;
+use std::hint;
+
+macro_rules! json_binary {
+    ($name:ident, $op:tt) => {
+        pub fn $name(left: &DataValue, right: &DataValue) -> Result<DataValue, DatabaseError> {
+            Ok(match (left, right) {
+                (DataValue::Json(v1), DataValue::Json(v2)) => DataValue::Boolean(v1 $op v2),
+                (DataValue::Json(_), DataValue::Null)
+                | (DataValue::Null, DataValue::Json(_))
+                | (DataValue::Null, DataValue::Null) => DataValue::Null,
+                _ => unsafe { hint::unreachable_unchecked() },
+            })
+        }
+    };
+}
+
+json_binary!(json_gt_binary_eval, >);
+json_binary!(json_gt_eq_binary_eval, >=);
+json_binary!(json_lt_binary_eval, <);
+json_binary!(json_lt_eq_binary_eval, <=);
+json_binary!(json_eq_binary_eval, ==);
+json_binary!(json_not_eq_binary_eval, !=);
+
+crate::define_cast_evaluator!(
+    json_to_char_cast_eval {
+        len: u32,
+        unit: CharLengthUnits
+    },
+    DataValue::Json(value) => |this| to_char(value.to_string(), this.len, this.unit)
+);
+crate::define_cast_evaluator!(
+    json_to_varchar_cast_eval {
+        len: Option<u32>,
+        unit: CharLengthUnits
+    },
+    DataValue::Json(value) => |this| to_varchar(value.to_string(), this.len, this.unit)
+);
@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+pub mod array;
 pub mod binary;
 pub mod boolean;
 pub mod cast;
@@ -27,6 +28,8 @@ pub mod int16;
 pub mod int32;
 pub mod int64;
 pub mod int8;
+pub mod interval;
+pub mod json;
 pub mod null;
 #[cfg(feature = "time")]
 pub mod time32;
@@ -40,7 +43,7 @@ pub mod uint8;
 pub mod unary;
 pub mod utf8;
 
-pub use self::binary::binary_create;
+pub use self::binary::{binary_create, temporal_binary_create};
 pub use self::cast::cast_create;
 pub use self::unary::unary_create;
 
@@ -118,6 +121,9 @@ pub enum CastEvaluatorParams {
     Tuple {
         evaluators: Vec<CastEvaluatorRef>,
     },
+    Array {
+        element: Box<CastEvaluatorRef>,
+    },
 }
 
 impl CastEvaluatorRef {
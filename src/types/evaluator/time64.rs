@@ -15,11 +15,22 @@
 use crate::errors::DatabaseError;
 use crate::types::evaluator::cast::{cast_fail, to_char, to_varchar};
 use crate::types::evaluator::DataValue;
+use crate::types::timezone::session_time_zone;
 use crate::types::CharLengthUnits;
 use crate::types::LogicalType;
-use chrono::{Datelike, Timelike};
+use chrono::{Datelike, NaiveDateTime, Timelike};
 use std::hint;
 
+/// The wall clock time of a timestamp, of the session time zone when it has a time zone.
+fn wall_clock(value: i64, precision: u64, zone: bool) -> Option<NaiveDateTime> {
+    let utc = DataValue::from_timestamp_precision(value, precision)?.naive_utc();
+    Some(if zone {
+        session_time_zone().to_local(&utc)
+    } else {
+        utc
+    })
+}
+
 macro_rules! time64_binary {
     ($name:ident, $op:tt) => {
         pub fn $name(left: &DataValue, right: &DataValue) -> Result<DataValue, DatabaseError> {
@@ -56,8 +67,9 @@ crate::define_cast_evaluator!(
         unit: CharLengthUnits
     },
     DataValue::Time64(value, precision, zone) => |this| {
+        let text = DataValue::format_timestamp(*value, *precision, zone.then(session_time_zone));
         to_char(
-            DataValue::format_timestamp(*value, *precision).ok_or_else(|| {
+            text.ok_or_else(|| {
                 cast_fail(
                     LogicalType::TimeStamp(Some(*precision), *zone),
                     LogicalType::Char(this.len, this.unit),
@@ -74,8 +86,9 @@ crate::define_cast_evaluator!(
         unit: CharLengthUnits
     },
     DataValue::Time64(value, precision, zone) => |this| {
+        let text = DataValue::format_timestamp(*value, *precision, zone.then(session_time_zone));
         to_varchar(
-            DataValue::format_timestamp(*value, *precision).ok_or_else(|| {
+            text.ok_or_else(|| {
                 cast_fail(
                     LogicalType::TimeStamp(Some(*precision), *zone),
                     LogicalType::Varchar(this.len, this.unit),
@@ -89,14 +102,13 @@ crate::define_cast_evaluator!(
 crate::define_cast_evaluator!(
     time64_to_date_cast_eval,
     DataValue::Time64(value, precision, zone) => {
-        let value = DataValue::from_timestamp_precision(*value, *precision)
+        let value = wall_clock(*value, *precision, *zone)
             .ok_or_else(|| {
                 cast_fail(
                     LogicalType::TimeStamp(Some(*precision), *zone),
                     LogicalType::Date,
                 )
             })?
-            .naive_utc()
             .date()
             .num_days_from_ce();
 
@@ -106,13 +118,14 @@ crate::define_cast_evaluator!(
 crate::define_cast_evaluator!(
     time64_to_datetime_cast_eval,
     DataValue::Time64(value, precision, zone) => {
-        let value = DataValue::from_timestamp_precision(*value, *precision)
+        let value = wall_clock(*value, *precision, *zone)
             .ok_or_else(|| {
                 cast_fail(
                     LogicalType::TimeStamp(Some(*precision), *zone),
                     LogicalType::DateTime,
                 )
             })?
+            .and_utc()
             .timestamp();
 
         Ok(DataValue::Date64(value))
@@ -124,7 +137,7 @@ crate::define_cast_evaluator!(
     },
     DataValue::Time64(value, precision, zone) => |this| {
         let target_precision = this.precision.unwrap_or(0);
-        let (value, nano) = DataValue::from_timestamp_precision(*value, *precision)
+        let (value, nano) = wall_clock(*value, *precision, *zone)
             .map(|date_time| {
                 (
                     date_time.time().num_seconds_from_midnight(),
@@ -149,8 +162,28 @@ crate::define_cast_evaluator!(
         precision: Option<u64>,
         zone: bool
     },
-    DataValue::Time64(value, _precision, _) => |this| {
-        Ok(DataValue::Time64(*value, this.precision.unwrap_or(0), this.zone))
+    DataValue::Time64(value, precision, zone) => |this| {
+        let target_precision = this.precision.unwrap_or(0);
+        let utc = DataValue::from_timestamp_precision(*value, *precision)
+            .ok_or_else(|| {
+                cast_fail(
+                    LogicalType::TimeStamp(Some(*precision), *zone),
+                    LogicalType::TimeStamp(this.precision, this.zone),
+                )
+            })?
+            .naive_utc();
+        // a timestamp without time zone is wall clock time of the session time zone
+        let value = match (*zone, this.zone) {
+            (false, true) => session_time_zone().to_utc(&utc),
+            (true, false) => session_time_zone().to_local(&utc),
+            _ => utc,
+        };
+
+        Ok(DataValue::Time64(
+            DataValue::timestamp_precision(value.and_utc(), target_precision),
+            target_precision,
+            this.zone,
+        ))
     }
 );
 time64_binary!(time64_lt_binary_eval, <);
@@ -162,6 +195,7 @@ time64_binary!(time64_not_eq_binary_eval, !=);
 #[cfg(all(test, not(target_arch = "wasm32")))]
 mod test {
     use super::*;
+    use crate::types::timezone::TimeZone;
     use crate::types::value::Utf8Type;
     use crate::types::CharLengthUnits;
 
@@ -271,6 +305,11 @@ mod test {
             DataValue::Time64(timestamp, 3, true)
         );
 
+        assert_eq!(
+            time64_to_timestamp_cast_eval(Some(6), false, &value).unwrap(),
+            DataValue::Time64(timestamp * 1_000, 6, false)
+        );
+
         let invalid = DataValue::Time64(i64::MAX, 0, false);
         assert!(time64_to_char_cast_eval(23, CharLengthUnits::Characters, &invalid).is_err());
         assert!(time64_to_varchar_cast_eval(None, CharLengthUnits::Characters, &invalid).is_err());
@@ -278,5 +317,39 @@ mod test {
         assert!(time64_to_datetime_cast_eval(&invalid).is_err());
         assert!(time64_to_time_cast_eval(Some(0), &invalid).is_err());
     }
+
+    #[test]
+    fn test_time64_session_time_zone_casts() -> Result<(), DatabaseError> {
+        let _scope = TimeZone::parse("Asia/Kolkata")?.enter();
+        let utc = chrono::NaiveDate::from_ymd_opt(2024, 1, 2)
+            .unwrap()
+            .and_hms_opt(20, 0, 0)
+            .unwrap()
+            .and_utc()
+            .timestamp();
+        let value = DataValue::Time64(utc, 0, true);
+
+        assert_eq!(
+            time64_to_varchar_cast_eval(None, CharLengthUnits::Characters, &value)?,
+            DataValue::Utf8 {
+                value: "2024-01-03 01:30:00+05:30".to_string(),
+                ty: Utf8Type::Variable(None),
+                unit: CharLengthUnits::Characters,
+            }
+        );
+        assert_eq!(
+            time64_to_date_cast_eval(&value)?,
+            DataValue::Date32(
+                chrono::NaiveDate::from_ymd_opt(2024, 1, 3)
+                    .unwrap()
+                    .num_days_from_ce()
+            )
+        );
+        let local = time64_to_timestamp_cast_eval(None, false, &value)?;
+        assert_eq!(local, DataValue::Time64(utc + 5 * 3600 + 30 * 60, 0, false));
+        assert_eq!(time64_to_timestamp_cast_eval(None, true, &local)?, value);
+
+        Ok(())
+    }
 }
 // GRCOV_EXCL_STOP
@@ -15,6 +15,8 @@
 use crate::errors::DatabaseError;
 use crate::types::evaluator::cast::{to_char, to_varchar};
 use crate::types::evaluator::DataValue;
+use crate::types::interval::Interval;
+use crate::types::json::Json;
 use crate::types::value::Utf8Type;
 use crate::types::CharLengthUnits;
 use ordered_float::OrderedFloat;
@@ -97,12 +99,19 @@ crate::define_cast_evaluator!(
     },
     DataValue::Utf8 { value, .. } => |this| to_varchar(value.clone(), this.len, this.unit)
 );
+crate::define_cast_evaluator!(utf8_to_interval_cast_eval, DataValue::Utf8 { value, .. } => {
+    Ok(DataValue::Interval(Interval::parse(value)?))
+});
+crate::define_cast_evaluator!(utf8_to_json_cast_eval, DataValue::Utf8 { value, .. } => {
+    Ok(DataValue::Json(Json::parse(value)?))
+});
 #[cfg(feature = "time")]
 mod chrono_cast {
     use super::DataValue;
     use crate::types::evaluator::cast::cast_fail;
+    use crate::types::timezone::{session_time_zone, split_zone};
     use crate::types::LogicalType;
-    use chrono::{DateTime, Datelike, NaiveDate, NaiveDateTime, NaiveTime, Timelike};
+    use chrono::{Datelike, NaiveDate, NaiveDateTime, NaiveTime, Timelike};
 
     crate::define_cast_evaluator!(utf8_to_date_cast_eval, DataValue::Utf8 { value, .. } => {
         Ok(DataValue::Date32(
@@ -155,45 +164,25 @@ mod chrono_cast {
         DataValue::Utf8 { value, .. } => |this| {
             let precision = this.precision.unwrap_or(0);
             let target_type = || LogicalType::TimeStamp(this.precision, this.zone);
-            let fmt = match (precision, this.zone) {
-                (0, false) => crate::types::value::DATE_TIME_FMT,
-                (0, true) => crate::types::value::TIME_STAMP_FMT_WITHOUT_PRECISION,
-                (3 | 6 | 9, false) => crate::types::value::TIME_STAMP_FMT_WITHOUT_ZONE,
-                _ => crate::types::value::TIME_STAMP_FMT_WITH_ZONE,
+            let fmt = match precision {
+                0 => crate::types::value::DATE_TIME_FMT,
+                _ => crate::types::value::TIME_STAMP_FMT_WITHOUT_ZONE,
             };
-            let complete_value = if this.zone {
-                if value.contains("+") {
-                    value.clone()
-                } else {
-                    format!("{value}+00:00")
-                }
+            let value = if this.zone {
+                // text without an offset is wall clock time of the session time zone
+                let (local, zone) = split_zone(value)?;
+                let local = NaiveDateTime::parse_from_str(&local.replacen('T', " ", 1), fmt)?;
+                zone.unwrap_or_else(session_time_zone).to_utc(&local).and_utc()
             } else {
-                value.clone()
+                NaiveDateTime::parse_from_str(value, fmt)?.and_utc()
             };
-
-            if !this.zone {
-                let value = NaiveDateTime::parse_from_str(&complete_value, fmt)?.and_utc();
-                let value = match precision {
-                    3 => value.timestamp_millis(),
-                    6 => value.timestamp_micros(),
-                    9 => value
-                        .timestamp_nanos_opt()
-                        .ok_or_else(|| cast_fail(target_type(), target_type()))?,
-                    0 => value.timestamp(),
-                    _ => unreachable!(),
-                };
-
-                return Ok(DataValue::Time64(value, precision, false));
-            }
-
-            let value = DateTime::parse_from_str(&complete_value, fmt);
             let value = match precision {
-                3 => value.map(|date_time| date_time.timestamp_millis())?,
-                6 => value.map(|date_time| date_time.timestamp_micros())?,
+                3 => value.timestamp_millis(),
+                6 => value.timestamp_micros(),
                 9 => value
-                    .map(|date_time| date_time.timestamp_nanos_opt())?
+                    .timestamp_nanos_opt()
                     .ok_or_else(|| cast_fail(target_type(), target_type()))?,
-                0 => value.map(|date_time| date_time.timestamp())?,
+                0 => value.timestamp(),
                 _ => unreachable!(),
             };
 
@@ -16,6 +16,7 @@ use crate::catalog::{TableCatalog, TableName};
 use crate::errors::DatabaseError;
 use crate::expression::range_detacher::Range;
 use crate::expression::ScalarExpression;
+use crate::function::json_path::json_path_expr;
 use crate::planner::operator::SortOption;
 use crate::planner::PlanArena;
 use crate::types::serialize::TupleValueSerializableImpl;
@@ -99,6 +100,14 @@ impl IndexOrderHint {
     }
 }
 
+/// One function of the chain an index on a JSON path applies to its column, e.g.
+/// `json_object_field_text` with `'name'` for `CREATE INDEX ON t ((doc ->> 'name'))`.
+#[derive(Debug, Clone, Eq, PartialEq, Hash, ReferenceSerialization)]
+pub struct IndexPathStep {
+    pub function: String,
+    pub args: Vec<DataValue>,
+}
+
 #[derive(Debug, Clone, Eq, PartialEq, Hash, ReferenceSerialization)]
 pub struct IndexMeta {
     pub id: IndexId,
@@ -108,6 +117,9 @@ pub struct IndexMeta {
     pub value_ty: LogicalType,
     pub name: String,
     pub ty: IndexType,
+    /// The JSON path the index stores instead of the value of its only column, empty for an
+    /// index on plain columns.
+    pub json_path: Vec<IndexPathStep>,
 }
 
 impl IndexMeta {
@@ -125,7 +137,10 @@ impl IndexMeta {
                 .enumerate()
                 .find(|(_, column)| arena.column(*column).id() == Some(*column_id))
             {
-                exprs.push(ScalarExpression::column_expr(column_ref, position));
+                exprs.push(json_path_expr(
+                    ScalarExpression::column_expr(column_ref, position),
+                    &self.json_path,
+                )?);
             } else {
                 return Err(DatabaseError::column_not_found(column_id.to_string()));
             }
@@ -213,6 +228,7 @@ mod tests {
             value_ty: LogicalType::Integer,
             name: "idx_t".to_string(),
             ty: IndexType::Normal,
+            json_path: vec![],
         }
     }
 
@@ -353,6 +369,18 @@ mod tests {
             roundtrip_with_arena(index_meta(), &encode_arena, &mut decode_arena)?,
             index_meta()
         );
+        let json_path_meta = IndexMeta {
+            value_ty: LogicalType::Varchar(None, crate::types::CharLengthUnits::Characters),
+            json_path: vec![IndexPathStep {
+                function: "json_object_field_text".to_string(),
+                args: vec![DataValue::from("name".to_string())],
+            }],
+            ..index_meta()
+        };
+        assert_eq!(
+            roundtrip_with_arena(json_path_meta.clone(), &encode_arena, &mut decode_arena)?,
+            json_path_meta
+        );
 
         let meta = encode_arena.alloc_index(index_meta());
         let info = IndexInfo {
@@ -403,10 +431,25 @@ mod tests {
             value_ty: LogicalType::Integer,
             name: "idx_id".to_string(),
             ty: IndexType::Normal,
+            json_path: vec![],
         };
 
         assert_eq!(meta.column_exprs(&table, &arena)?.len(), 1);
 
+        let json_path = vec![IndexPathStep {
+            function: "json_object_field_text".to_string(),
+            args: vec![DataValue::from("name".to_string())],
+        }];
+        let json_path_meta = IndexMeta {
+            json_path: json_path.clone(),
+            ..meta.clone()
+        };
+        let exprs = json_path_meta.column_exprs(&table, &arena)?;
+        assert_eq!(
+            crate::function::json_path::split_json_path(&exprs[0]).map(|(_, path)| path),
+            Some(json_path)
+        );
+
         let missing = IndexMeta {
             column_ids: vec![u64::MAX],
             ..meta
@@ -0,0 +1,617 @@
+// Copyright 2024 KipData/KiteSQL
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::errors::DatabaseError;
+#[cfg(feature = "time")]
+use chrono::{Months, NaiveDateTime, TimeDelta};
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+
+pub const NANOS_PER_SECOND: i64 = 1_000_000_000;
+pub const NANOS_PER_MINUTE: i64 = 60 * NANOS_PER_SECOND;
+pub const NANOS_PER_HOUR: i64 = 60 * NANOS_PER_MINUTE;
+pub const NANOS_PER_DAY: i64 = 24 * NANOS_PER_HOUR;
+/// Used to compare intervals, as PostgreSQL does: a month counts as 30 days.
+const DAYS_PER_MONTH: i64 = 30;
+
+/// A span of time. Months and days are kept apart from the clock part because their
+/// length depends on the date they are applied to, e.g. `'2024-01-31' + interval '1 month'`
+/// is `2024-02-29`.
+///
+/// Intervals compare by their length, so `'1 month'` equals `'30 days'` even though the two
+/// keep their parts and shift dates differently.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Interval {
+    pub months: i32,
+    pub days: i32,
+    pub nanos: i64,
+}
+
+impl Interval {
+    pub const fn new(months: i32, days: i32, nanos: i64) -> Self {
+        Interval {
+            months,
+            days,
+            nanos,
+        }
+    }
+
+    /// The length of the interval in nanoseconds, with a month counted as 30 days.
+    pub fn total_nanos(&self) -> i128 {
+        (self.months as i128 * DAYS_PER_MONTH as i128 + self.days as i128) * NANOS_PER_DAY as i128
+            + self.nanos as i128
+    }
+
+    pub fn checked_neg(&self) -> Option<Interval> {
+        Some(Interval {
+            months: self.months.checked_neg()?,
+            days: self.days.checked_neg()?,
+            nanos: self.nanos.checked_neg()?,
+        })
+    }
+
+    pub fn checked_add(&self, other: &Interval) -> Option<Interval> {
+        Some(Interval {
+            months: self.months.checked_add(other.months)?,
+            days: self.days.checked_add(other.days)?,
+            nanos: self.nanos.checked_add(other.nanos)?,
+        })
+    }
+
+    pub fn checked_sub(&self, other: &Interval) -> Option<Interval> {
+        self.checked_add(&other.checked_neg()?)
+    }
+
+    /// Multiplies every part by `factor`. Like PostgreSQL, the fraction of a month spills
+    /// into days (a month counting as 30 days) and the fraction of a day into the clock part,
+    /// so `'1 month' * 1.5` is `1 mon 15 days`.
+    pub fn checked_mul_f64(&self, factor: f64) -> Option<Interval> {
+        self.checked_scale(|part| part * factor)
+    }
+
+    /// Divides every part by `divisor`, spilling fractions like [`Interval::checked_mul_f64`],
+    /// so `'1 day' / 2` is `12:00:00`.
+    pub fn checked_div_f64(&self, divisor: f64) -> Option<Interval> {
+        self.checked_scale(|part| part / divisor)
+    }
+
+    fn checked_scale(&self, scale: impl Fn(f64) -> f64) -> Option<Interval> {
+        let months = scale(self.months as f64);
+        let whole_months = months.trunc();
+        let days = scale(self.days as f64) + (months - whole_months) * DAYS_PER_MONTH as f64;
+        let whole_days = days.trunc();
+        let nanos = (scale(self.nanos as f64) + (days - whole_days) * NANOS_PER_DAY as f64).round();
+        // `as` saturates, so check the range first, which also rejects NaN and infinities
+        let in_range = |value: f64, min: f64, max: f64| value >= min && value <= max;
+        if !in_range(whole_months, i32::MIN as f64, i32::MAX as f64)
+            || !in_range(whole_days, i32::MIN as f64, i32::MAX as f64)
+            || !in_range(nanos, i64::MIN as f64, i64::MAX as f64)
+        {
+            return None;
+        }
+
+        Some(Interval {
+            months: whole_months as i32,
+            days: whole_days as i32,
+            nanos: nanos as i64,
+        })
+    }
+
+    /// Parses the PostgreSQL style input `'1 year 2 months 3 days 04:05:06.5'`.
+    ///
+    /// Each quantity may be signed and fractional, units may be abbreviated or plural, a
+    /// clock `[-]HH:MM[:SS[.fraction]]` may appear once and a trailing `ago` negates the
+    /// whole interval.
+    pub fn parse(input: &str) -> Result<Interval, DatabaseError> {
+        let invalid = || {
+            DatabaseError::InvalidValue(format!("invalid input syntax for type interval: {input}"))
+        };
+        let mut builder = IntervalBuilder::default();
+        let mut tokens = input.split_whitespace().peekable();
+        let mut has_part = false;
+        let mut has_clock = false;
+        let mut is_ago = false;
+
+        while let Some(token) = tokens.next() {
+            if is_ago {
+                return Err(invalid());
+            }
+            if token.eq_ignore_ascii_case("ago") && has_part {
+                is_ago = true;
+                continue;
+            }
+            if token.contains(':') {
+                if has_clock {
+                    return Err(invalid());
+                }
+                builder.add_clock(token).ok_or_else(invalid)?;
+                has_clock = true;
+                has_part = true;
+                continue;
+            }
+            // the unit may be glued to the quantity, as in `10s`
+            let split = token
+                .find(|c: char| c.is_ascii_alphabetic())
+                .unwrap_or(token.len());
+            let (quantity, unit) = if split < token.len() {
+                token.split_at(split)
+            } else {
+                (token, tokens.next().ok_or_else(invalid)?)
+            };
+            let unit = IntervalUnit::parse(unit).ok_or_else(invalid)?;
+            builder
+                .add(&parse_quantity(quantity).ok_or_else(invalid)?, unit)
+                .ok_or(DatabaseError::OverFlow)?;
+            has_part = true;
+        }
+        if !has_part {
+            return Err(invalid());
+        }
+        let interval = builder.build().ok_or(DatabaseError::OverFlow)?;
+
+        if is_ago {
+            interval.checked_neg().ok_or(DatabaseError::OverFlow)
+        } else {
+            Ok(interval)
+        }
+    }
+
+    /// Parses the quantity of a single unit interval such as MySQL's `INTERVAL 3 DAY`.
+    pub fn parse_with_unit(quantity: &str, unit: &str) -> Result<Interval, DatabaseError> {
+        let invalid = || {
+            DatabaseError::InvalidValue(format!(
+                "invalid input syntax for type interval: {quantity} {unit}"
+            ))
+        };
+        let unit = IntervalUnit::parse(unit).ok_or_else(invalid)?;
+        let mut builder = IntervalBuilder::default();
+        builder
+            .add(&parse_quantity(quantity.trim()).ok_or_else(invalid)?, unit)
+            .ok_or(DatabaseError::OverFlow)?;
+
+        builder.build().ok_or(DatabaseError::OverFlow)
+    }
+}
+
+#[cfg(feature = "time")]
+impl Interval {
+    /// Applies the months first, then the days and finally the clock part, so that adding
+    /// `'1 month 1 day'` to January 31st gives March 1st.
+    pub fn add_to(&self, datetime: NaiveDateTime) -> Option<NaiveDateTime> {
+        let datetime = if self.months >= 0 {
+            datetime.checked_add_months(Months::new(self.months as u32))?
+        } else {
+            datetime.checked_sub_months(Months::new(self.months.unsigned_abs()))?
+        };
+        datetime
+            .checked_add_signed(TimeDelta::try_days(self.days as i64)?)?
+            .checked_add_signed(TimeDelta::nanoseconds(self.nanos))
+    }
+
+    pub fn sub_from(&self, datetime: NaiveDateTime) -> Option<NaiveDateTime> {
+        self.checked_neg()?.add_to(datetime)
+    }
+
+    /// The interval from `start` to `end` in days and a clock part, never in months.
+    pub fn between(end: NaiveDateTime, start: NaiveDateTime) -> Option<Interval> {
+        let delta = end.signed_duration_since(start);
+        let days = delta.num_days();
+        let nanos = (delta - TimeDelta::try_days(days)?).num_nanoseconds()?;
+
+        Some(Interval::new(0, i32::try_from(days).ok()?, nanos))
+    }
+}
+
+impl PartialEq for Interval {
+    fn eq(&self, other: &Self) -> bool {
+        self.total_nanos() == other.total_nanos()
+    }
+}
+
+impl Eq for Interval {}
+
+impl Hash for Interval {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.total_nanos().hash(state)
+    }
+}
+
+impl PartialOrd for Interval {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Interval {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.total_nanos().cmp(&other.total_nanos())
+    }
+}
+
+impl fmt::Display for Interval {
+    /// Formats like PostgreSQL: `1 year 2 mons 3 days 04:05:06.5`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fn write_part(
+            f: &mut fmt::Formatter<'_>,
+            is_first: &mut bool,
+            value: i64,
+            unit: &str,
+        ) -> fmt::Result {
+            if value == 0 {
+                return Ok(());
+            }
+            if !*is_first {
+                f.write_str(" ")?;
+            }
+            *is_first = false;
+            write!(f, "{value} {unit}")?;
+            if value != 1 {
+                f.write_str("s")?;
+            }
+            Ok(())
+        }
+        let mut is_first = true;
+        write_part(f, &mut is_first, (self.months / 12) as i64, "year")?;
+        write_part(f, &mut is_first, (self.months % 12) as i64, "mon")?;
+        write_part(f, &mut is_first, self.days as i64, "day")?;
+
+        if self.nanos != 0 || is_first {
+            if !is_first {
+                f.write_str(" ")?;
+            }
+            let nanos = self.nanos.unsigned_abs();
+            let seconds = nanos / NANOS_PER_SECOND as u64;
+            if self.nanos < 0 {
+                f.write_str("-")?;
+            }
+            write!(
+                f,
+                "{:02}:{:02}:{:02}",
+                seconds / 3600,
+                seconds / 60 % 60,
+                seconds % 60
+            )?;
+            let fraction = nanos % NANOS_PER_SECOND as u64;
+            if fraction != 0 {
+                let fraction = format!("{fraction:09}");
+                write!(f, ".{}", fraction.trim_end_matches('0'))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum IntervalUnit {
+    Millennium,
+    Century,
+    Decade,
+    Year,
+    Quarter,
+    Month,
+    Week,
+    Day,
+    Hour,
+    Minute,
+    Second,
+    Millisecond,
+    Microsecond,
+}
+
+impl IntervalUnit {
+    fn parse(unit: &str) -> Option<IntervalUnit> {
+        Some(match unit.to_ascii_lowercase().as_str() {
+            "millennium" | "millennia" | "millenniums" | "mil" | "mils" => IntervalUnit::Millennium,
+            "century" | "centuries" | "c" | "cent" => IntervalUnit::Century,
+            "decade" | "decades" | "dec" | "decs" => IntervalUnit::Decade,
+            "year" | "years" | "y" | "yr" | "yrs" => IntervalUnit::Year,
+            "quarter" | "quarters" | "qtr" => IntervalUnit::Quarter,
+            "month" | "months" | "mon" | "mons" => IntervalUnit::Month,
+            "week" | "weeks" | "w" => IntervalUnit::Week,
+            "day" | "days" | "d" => IntervalUnit::Day,
+            "hour" | "hours" | "h" | "hr" | "hrs" => IntervalUnit::Hour,
+            "minute" | "minutes" | "m" | "min" | "mins" => IntervalUnit::Minute,
+            "second" | "seconds" | "s" | "sec" | "secs" => IntervalUnit::Second,
+            "millisecond" | "milliseconds" | "ms" | "msec" | "msecs" => IntervalUnit::Millisecond,
+            "microsecond" | "microseconds" | "us" | "usec" | "usecs" => IntervalUnit::Microsecond,
+            _ => return None,
+        })
+    }
+}
+
+/// A signed decimal quantity split into its whole part and its fraction in nanoseconds.
+struct Quantity {
+    whole: i64,
+    fraction_nanos: i64,
+}
+
+fn parse_quantity(quantity: &str) -> Option<Quantity> {
+    let (is_negative, digits) = match quantity.as_bytes().first()? {
+        b'-' => (true, &quantity[1..]),
+        b'+' => (false, &quantity[1..]),
+        _ => (false, quantity),
+    };
+    let (whole, fraction) = digits.split_once('.').unwrap_or((digits, ""));
+    if (whole.is_empty() && fraction.is_empty())
+        || !whole.bytes().all(|b| b.is_ascii_digit())
+        || !fraction.bytes().all(|b| b.is_ascii_digit())
+    {
+        return None;
+    }
+    let whole = if whole.is_empty() {
+        0
+    } else {
+        whole.parse::<i64>().ok()?
+    };
+    // digits beyond nanoseconds are truncated
+    let fraction = &fraction[..fraction.len().min(9)];
+    let fraction_nanos = if fraction.is_empty() {
+        0
+    } else {
+        fraction.parse::<i64>().ok()? * 10_i64.pow(9 - fraction.len() as u32)
+    };
+    let sign = if is_negative { -1 } else { 1 };
+
+    Some(Quantity {
+        whole: whole * sign,
+        fraction_nanos: fraction_nanos * sign,
+    })
+}
+
+/// Accumulates the parts of an interval in `i128`, so overflow is only checked once.
+#[derive(Default)]
+struct IntervalBuilder {
+    months: i128,
+    days: i128,
+    nanos: i128,
+}
+
+impl IntervalBuilder {
+    fn add(&mut self, quantity: &Quantity, unit: IntervalUnit) -> Option<()> {
+        let whole = quantity.whole as i128;
+        let fraction = quantity.fraction_nanos as i128;
+        let months_per_unit = match unit {
+            IntervalUnit::Millennium => Some(12_000),
+            IntervalUnit::Century => Some(1_200),
+            IntervalUnit::Decade => Some(120),
+            IntervalUnit::Year => Some(12),
+            IntervalUnit::Quarter => Some(3),
+            IntervalUnit::Month => Some(1),
+            _ => None,
+        };
+        if let Some(months_per_unit) = months_per_unit {
+            // a fraction of a month spills into days and then into the clock part
+            let fraction_months = fraction * months_per_unit;
+            self.months += whole * months_per_unit + fraction_months / NANOS_PER_SECOND as i128;
+            let fraction_days = fraction_months % NANOS_PER_SECOND as i128 * DAYS_PER_MONTH as i128;
+            self.days += fraction_days / NANOS_PER_SECOND as i128;
+            self.nanos += fraction_days % NANOS_PER_SECOND as i128 * NANOS_PER_DAY as i128
+                / NANOS_PER_SECOND as i128;
+            return Some(());
+        }
+        let (days_per_unit, nanos_per_unit) = match unit {
+            IntervalUnit::Week => (7, 0),
+            IntervalUnit::Day => (1, 0),
+            IntervalUnit::Hour => (0, NANOS_PER_HOUR),
+            IntervalUnit::Minute => (0, NANOS_PER_MINUTE),
+            IntervalUnit::Second => (0, NANOS_PER_SECOND),
+            IntervalUnit::Millisecond => (0, 1_000_000),
+            IntervalUnit::Microsecond => (0, 1_000),
+            _ => unreachable!(),
+        };
+        if days_per_unit != 0 {
+            self.days += whole * days_per_unit;
+            self.nanos +=
+                fraction * days_per_unit * NANOS_PER_DAY as i128 / NANOS_PER_SECOND as i128;
+        } else {
+            let nanos_per_unit = nanos_per_unit as i128;
+            self.nanos +=
+                whole * nanos_per_unit + fraction * nanos_per_unit / NANOS_PER_SECOND as i128;
+        }
+        (self.nanos.abs() < i64::MAX as i128).then_some(())
+    }
+
+    fn add_clock(&mut self, clock: &str) -> Option<()> {
+        let (is_negative, clock) = match clock.strip_prefix('-') {
+            Some(clock) => (true, clock),
+            None => (false, clock.strip_prefix('+').unwrap_or(clock)),
+        };
+        let mut parts = clock.split(':');
+        let hours = parts.next()?;
+        let minutes = parts.next()?;
+        let seconds = parts.next().unwrap_or("0");
+        if parts.next().is_some()
+            || [hours, minutes]
+                .iter()
+                .any(|part| part.is_empty() || !part.bytes().all(|b| b.is_ascii_digit()))
+            || seconds.starts_with(['-', '+'])
+        {
+            return None;
+        }
+        let seconds = parse_quantity(seconds)?;
+        let minutes = minutes.parse::<i128>().ok()?;
+        if minutes >= 60 || seconds.whole >= 60 {
+            return None;
+        }
+        let nanos = hours.parse::<i128>().ok()? * NANOS_PER_HOUR as i128
+            + minutes * NANOS_PER_MINUTE as i128
+            + seconds.whole as i128 * NANOS_PER_SECOND as i128
+            + seconds.fraction_nanos as i128;
+        self.nanos += if is_negative { -nanos } else { nanos };
+
+        (self.nanos.abs() < i64::MAX as i128).then_some(())
+    }
+
+    fn build(self) -> Option<Interval> {
+        Some(Interval {
+            months: i32::try_from(self.months).ok()?,
+            days: i32::try_from(self.days).ok()?,
+            nanos: i64::try_from(self.nanos).ok()?,
+        })
+    }
+}
+
+// GRCOV_EXCL_START
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_interval_parse() -> Result<(), DatabaseError> {
+        assert_eq!(Interval::parse("1 day")?, Interval::new(0, 1, 0));
+        assert_eq!(
+            Interval::parse("1 year 2 months 3 days 04:05:06.5")?,
+            Interval::new(
+                14,
+                3,
+                4 * NANOS_PER_HOUR + 5 * NANOS_PER_MINUTE + 6_500_000_000
+            )
+        );
+        assert_eq!(
+            Interval::parse("2 hours 30 mins")?,
+            Interval::new(0, 0, 2 * NANOS_PER_HOUR + 30 * NANOS_PER_MINUTE)
+        );
+        assert_eq!(
+            Interval::parse("10s")?,
+            Interval::new(0, 0, 10 * NANOS_PER_SECOND)
+        );
+        assert_eq!(
+            Interval::parse("1.5 days")?,
+            Interval::new(0, 1, 12 * NANOS_PER_HOUR)
+        );
+        assert_eq!(Interval::parse("1.5 months")?, Interval::new(1, 15, 0));
+        assert_eq!(Interval::parse("1 week ago")?, Interval::new(0, -7, 0));
+        assert_eq!(
+            Interval::parse("-1 day +02:00")?,
+            Interval::new(0, -1, 2 * NANOS_PER_HOUR)
+        );
+        assert_eq!(
+            Interval::parse_with_unit("3", "DAY")?,
+            Interval::new(0, 3, 0)
+        );
+
+        for input in [
+            "",
+            "day",
+            "1",
+            "1 fortnight",
+            "1:2:3:4",
+            "ago",
+            "1 day ago ago",
+        ] {
+            assert!(Interval::parse(input).is_err(), "{input}");
+        }
+        assert!(matches!(
+            Interval::parse("3000000000 days"),
+            Err(DatabaseError::OverFlow)
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_interval_display() -> Result<(), DatabaseError> {
+        let cases = [
+            ("1 day", "1 day"),
+            (
+                "1 year 2 months 3 days 04:05:06.5",
+                "1 year 2 mons 3 days 04:05:06.5",
+            ),
+            ("-2 days", "-2 days"),
+            ("-90 minutes", "-01:30:00"),
+            ("0 seconds", "00:00:00"),
+            ("36 hours", "36:00:00"),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(Interval::parse(input)?.to_string(), expected);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_interval_ordering() -> Result<(), DatabaseError> {
+        assert!(Interval::parse("1 day")? < Interval::parse("25 hours")?);
+        assert!(Interval::parse("1 month")? > Interval::parse("29 days")?);
+        assert!(Interval::parse("-1 second")? < Interval::default());
+        assert_eq!(Interval::parse("1 month")?, Interval::parse("30 days")?);
+        assert_eq!(Interval::parse("1 day")?, Interval::parse("24 hours")?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_interval_scale() -> Result<(), DatabaseError> {
+        let scale = |input, factor| Interval::parse(input).map(|v| v.checked_mul_f64(factor));
+        let divide = |input, divisor| Interval::parse(input).map(|v| v.checked_div_f64(divisor));
+        let parts = |v: Option<Interval>| v.map(|v| (v.months, v.days, v.nanos));
+
+        assert_eq!(parts(scale("1 month", 1.5)?), Some((1, 15, 0)));
+        assert_eq!(
+            parts(scale("1 day", 0.5)?),
+            Some((0, 0, 12 * NANOS_PER_HOUR))
+        );
+        assert_eq!(parts(divide("3 mons", 3.0)?), Some((1, 0, 0)));
+        assert_eq!(
+            parts(divide("1 mon", 4.0)?),
+            Some((0, 7, 12 * NANOS_PER_HOUR))
+        );
+        assert_eq!(
+            parts(scale("1 mon 2 days 03:00:00", -2.0)?),
+            Some((-2, -4, -6 * NANOS_PER_HOUR))
+        );
+        assert_eq!(scale("1 year", 1e10)?, None);
+        assert_eq!(scale("1 day", f64::NAN)?, None);
+        assert_eq!(divide("1 day", 0.0)?, None);
+
+        Ok(())
+    }
+
+    #[cfg(feature = "time")]
+    #[test]
+    fn test_interval_date_arithmetic() -> Result<(), DatabaseError> {
+        use chrono::NaiveDate;
+
+        let datetime = |y, m, d, h| {
+            NaiveDate::from_ymd_opt(y, m, d)
+                .unwrap()
+                .and_hms_opt(h, 0, 0)
+                .unwrap()
+        };
+        assert_eq!(
+            Interval::parse("1 month")?.add_to(datetime(2024, 1, 31, 0)),
+            Some(datetime(2024, 2, 29, 0))
+        );
+        assert_eq!(
+            Interval::parse("1 month 1 day 1 hour")?.add_to(datetime(2024, 1, 31, 0)),
+            Some(datetime(2024, 3, 1, 1))
+        );
+        assert_eq!(
+            Interval::parse("1 year")?.sub_from(datetime(2024, 2, 29, 0)),
+            Some(datetime(2023, 2, 28, 0))
+        );
+        assert_eq!(
+            Interval::between(datetime(2024, 3, 2, 6), datetime(2024, 3, 1, 0)),
+            Some(Interval::new(0, 1, 6 * NANOS_PER_HOUR))
+        );
+        assert_eq!(
+            Interval::between(datetime(2024, 3, 1, 0), datetime(2024, 3, 2, 6)),
+            Some(Interval::new(0, -1, -6 * NANOS_PER_HOUR))
+        );
+
+        Ok(())
+    }
+}
+// GRCOV_EXCL_STOP
@@ -0,0 +1,784 @@
+// Copyright 2024 KipData/KiteSQL
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::errors::DatabaseError;
+use crate::types::value::DataValue;
+use ordered_float::OrderedFloat;
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fmt::Write;
+
+/// How deep arrays and objects may nest, so parsing and printing cannot overflow the stack.
+const MAX_DEPTH: usize = 256;
+
+/// A parsed JSON document. Like PostgreSQL's `jsonb` the object keys are kept sorted and
+/// unique, the last duplicate wins, and whole numbers are stored as integers so that `1` and
+/// `1.0` are the same value.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Json {
+    Null,
+    Bool(bool),
+    Int(i64),
+    Float(OrderedFloat<f64>),
+    String(String),
+    Array(Vec<Json>),
+    Object(BTreeMap<String, Json>),
+}
+
+/// A step of a `$.key[0]` path.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum JsonPathStep {
+    Key(String),
+    /// An array element, counted from the end when negative.
+    Index(i64),
+}
+
+impl Json {
+    /// A number, stored as an integer when it is whole and exact in both representations.
+    pub fn number(value: f64) -> Json {
+        const EXACT: f64 = (1_u64 << 53) as f64;
+
+        if value.fract() == 0.0 && value.abs() <= EXACT {
+            Json::Int(value as i64)
+        } else {
+            Json::Float(OrderedFloat(value))
+        }
+    }
+
+    pub fn parse(text: &str) -> Result<Json, DatabaseError> {
+        let mut parser = Parser {
+            bytes: text.as_bytes(),
+            pos: 0,
+        };
+        let result = parser.value(0).and_then(|json| {
+            parser.skip_whitespace();
+            if parser.pos < parser.bytes.len() {
+                return Err("unexpected trailing characters");
+            }
+            Ok(json)
+        });
+        result.map_err(|reason| {
+            DatabaseError::InvalidValue(format!(
+                "invalid input syntax for type json: {reason} at position {}",
+                parser.pos
+            ))
+        })
+    }
+
+    /// The type name `json_typeof` reports.
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            Json::Null => "null",
+            Json::Bool(_) => "boolean",
+            Json::Int(_) | Json::Float(_) => "number",
+            Json::String(_) => "string",
+            Json::Array(_) => "array",
+            Json::Object(_) => "object",
+        }
+    }
+
+    pub fn get_key(&self, key: &str) -> Option<&Json> {
+        match self {
+            Json::Object(members) => members.get(key),
+            _ => None,
+        }
+    }
+
+    pub fn get_index(&self, index: i64) -> Option<&Json> {
+        match self {
+            Json::Array(elements) => {
+                let index = if index < 0 {
+                    elements.len().checked_sub(index.unsigned_abs() as usize)?
+                } else {
+                    index as usize
+                };
+                elements.get(index)
+            }
+            _ => None,
+        }
+    }
+
+    /// Follows a PostgreSQL `#>` path, whose elements are keys of objects and indexes of
+    /// arrays.
+    pub fn get_text_path<S: AsRef<str>>(&self, path: &[S]) -> Option<&Json> {
+        path.iter().try_fold(self, |json, element| {
+            let element = element.as_ref();
+            match json {
+                Json::Object(_) => json.get_key(element),
+                Json::Array(_) => json.get_index(element.trim().parse().ok()?),
+                _ => None,
+            }
+        })
+    }
+
+    pub fn get_path(&self, path: &[JsonPathStep]) -> Option<&Json> {
+        path.iter().try_fold(self, |json, step| match step {
+            JsonPathStep::Key(key) => json.get_key(key),
+            JsonPathStep::Index(index) => json.get_index(*index),
+        })
+    }
+
+    /// Replaces the value at `path`, adding the last key to its object or appending to its
+    /// array when it is past the end. Paths through missing members change nothing.
+    pub fn set_path(&mut self, path: &[JsonPathStep], value: Json) {
+        let Some((last, parents)) = path.split_last() else {
+            *self = value;
+            return;
+        };
+        let mut json = self;
+        for step in parents {
+            let next = match (json, step) {
+                (Json::Object(members), JsonPathStep::Key(key)) => members.get_mut(key),
+                (Json::Array(elements), JsonPathStep::Index(index)) => {
+                    Self::array_index(elements.len(), *index).and_then(|i| elements.get_mut(i))
+                }
+                _ => None,
+            };
+            let Some(next) = next else {
+                return;
+            };
+            json = next;
+        }
+        match (json, last) {
+            (Json::Object(members), JsonPathStep::Key(key)) => {
+                members.insert(key.clone(), value);
+            }
+            (Json::Array(elements), JsonPathStep::Index(index)) => {
+                match Self::array_index(elements.len(), *index) {
+                    Some(i) if i < elements.len() => elements[i] = value,
+                    Some(_) => elements.push(value),
+                    None => (),
+                }
+            }
+            _ => (),
+        }
+    }
+
+    fn array_index(len: usize, index: i64) -> Option<usize> {
+        if index < 0 {
+            len.checked_sub(index.unsigned_abs() as usize)
+        } else {
+            Some(index as usize)
+        }
+    }
+
+    /// The text `->>` returns: strings without their quotes, SQL `NULL` for a JSON `null`.
+    pub fn to_text(&self) -> Option<String> {
+        match self {
+            Json::Null => None,
+            Json::String(value) => Some(value.clone()),
+            json => Some(json.to_string()),
+        }
+    }
+
+    /// Converts a SQL value into JSON, as `json_agg` and `json_set` do with their arguments:
+    /// numbers, booleans and strings map to their JSON counterparts, other values to their
+    /// text.
+    pub fn from_data_value(value: &DataValue) -> Json {
+        match value {
+            DataValue::Null => Json::Null,
+            DataValue::Boolean(value) => Json::Bool(*value),
+            DataValue::Int8(value) => Json::Int(*value as i64),
+            DataValue::Int16(value) => Json::Int(*value as i64),
+            DataValue::Int32(value) => Json::Int(*value as i64),
+            DataValue::Int64(value) => Json::Int(*value),
+            DataValue::UInt8(value) => Json::Int(*value as i64),
+            DataValue::UInt16(value) => Json::Int(*value as i64),
+            DataValue::UInt32(value) => Json::Int(*value as i64),
+            DataValue::UInt64(value) => i64::try_from(*value)
+                .map(Json::Int)
+                .unwrap_or_else(|_| Json::number(*value as f64)),
+            DataValue::Float32(value) => Self::float(value.0 as f64),
+            DataValue::Float64(value) => Self::float(value.0),
+            DataValue::Utf8 { value, .. } => Json::String(value.clone()),
+            DataValue::Json(json) => json.clone(),
+            DataValue::Tuple(values, _) | DataValue::Array(values) => {
+                Json::Array(values.iter().map(Json::from_data_value).collect())
+            }
+            #[cfg(feature = "decimal")]
+            DataValue::Decimal(value) => {
+                Json::parse(&value.normalize().to_string()).unwrap_or(Json::Null)
+            }
+            value => Json::String(value.to_string()),
+        }
+    }
+
+    fn float(value: f64) -> Json {
+        if value.is_finite() {
+            Json::number(value)
+        } else {
+            Json::String(value.to_string())
+        }
+    }
+
+    fn rank(&self) -> u8 {
+        match self {
+            Json::Null => 0,
+            Json::String(_) => 1,
+            Json::Int(_) | Json::Float(_) => 2,
+            Json::Bool(_) => 3,
+            Json::Array(_) => 4,
+            Json::Object(_) => 5,
+        }
+    }
+}
+
+/// Orders values as PostgreSQL's `jsonb` does: by type first (null, string, number, boolean,
+/// array, object), numbers by value, arrays and objects by their length before their
+/// contents.
+impl Ord for Json {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (Json::Bool(v1), Json::Bool(v2)) => v1.cmp(v2),
+            (Json::Int(v1), Json::Int(v2)) => v1.cmp(v2),
+            (Json::Float(v1), Json::Float(v2)) => v1.cmp(v2),
+            (Json::Int(v1), Json::Float(v2)) => {
+                (*v1 as f64).total_cmp(&v2.0).then(Ordering::Greater)
+            }
+            (Json::Float(v1), Json::Int(v2)) => v1.0.total_cmp(&(*v2 as f64)).then(Ordering::Less),
+            (Json::String(v1), Json::String(v2)) => v1.cmp(v2),
+            (Json::Array(v1), Json::Array(v2)) => v1.len().cmp(&v2.len()).then_with(|| v1.cmp(v2)),
+            (Json::Object(v1), Json::Object(v2)) => {
+                v1.len().cmp(&v2.len()).then_with(|| v1.cmp(v2))
+            }
+            (v1, v2) => v1.rank().cmp(&v2.rank()),
+        }
+    }
+}
+
+impl PartialOrd for Json {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+#[cfg(feature = "json")]
+impl From<serde_json::Value> for Json {
+    fn from(value: serde_json::Value) -> Self {
+        match value {
+            serde_json::Value::Null => Json::Null,
+            serde_json::Value::Bool(value) => Json::Bool(value),
+            serde_json::Value::Number(number) => match number.as_i64() {
+                Some(value) => Json::Int(value),
+                None => Json::number(number.as_f64().unwrap_or_default()),
+            },
+            serde_json::Value::String(value) => Json::String(value),
+            serde_json::Value::Array(elements) => {
+                Json::Array(elements.into_iter().map(Json::from).collect())
+            }
+            serde_json::Value::Object(members) => Json::Object(
+                members
+                    .into_iter()
+                    .map(|(key, value)| (key, Json::from(value)))
+                    .collect(),
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "json")]
+impl From<&Json> for serde_json::Value {
+    fn from(value: &Json) -> Self {
+        match value {
+            Json::Null => serde_json::Value::Null,
+            Json::Bool(value) => serde_json::Value::Bool(*value),
+            Json::Int(value) => serde_json::Value::from(*value),
+            Json::Float(value) => serde_json::Value::from(value.0),
+            Json::String(value) => serde_json::Value::String(value.clone()),
+            Json::Array(elements) => {
+                serde_json::Value::Array(elements.iter().map(serde_json::Value::from).collect())
+            }
+            Json::Object(members) => serde_json::Value::Object(
+                members
+                    .iter()
+                    .map(|(key, value)| (key.clone(), serde_json::Value::from(value)))
+                    .collect(),
+            ),
+        }
+    }
+}
+
+impl fmt::Display for Json {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Json::Null => f.write_str("null"),
+            Json::Bool(value) => write!(f, "{value}"),
+            Json::Int(value) => write!(f, "{value}"),
+            Json::Float(value) => write!(f, "{:?}", value.0),
+            Json::String(value) => write_string(f, value),
+            Json::Array(elements) => {
+                f.write_char('[')?;
+                for (i, element) in elements.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(", ")?;
+                    }
+                    write!(f, "{element}")?;
+                }
+                f.write_char(']')
+            }
+            Json::Object(members) => {
+                f.write_char('{')?;
+                for (i, (key, value)) in members.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(", ")?;
+                    }
+                    write_string(f, key)?;
+                    write!(f, ": {value}")?;
+                }
+                f.write_char('}')
+            }
+        }
+    }
+}
+
+fn write_string(f: &mut fmt::Formatter<'_>, value: &str) -> fmt::Result {
+    f.write_char('"')?;
+    for c in value.chars() {
+        match c {
+            '"' => f.write_str("\\\"")?,
+            '\\' => f.write_str("\\\\")?,
+            '\n' => f.write_str("\\n")?,
+            '\r' => f.write_str("\\r")?,
+            '\t' => f.write_str("\\t")?,
+            '\u{08}' => f.write_str("\\b")?,
+            '\u{0c}' => f.write_str("\\f")?,
+            c if (c as u32) < 0x20 => write!(f, "\\u{:04x}", c as u32)?,
+            c => f.write_char(c)?,
+        }
+    }
+    f.write_char('"')
+}
+
+struct Parser<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn skip_whitespace(&mut self) {
+        while matches!(self.peek(), Some(b' ' | b'\t' | b'\n' | b'\r')) {
+            self.pos += 1;
+        }
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.bytes.get(self.pos).copied()
+    }
+
+    fn expect_literal(&mut self, literal: &str, json: Json) -> Result<Json, &'static str> {
+        if self.bytes[self.pos..].starts_with(literal.as_bytes()) {
+            self.pos += literal.len();
+            Ok(json)
+        } else {
+            Err("invalid token")
+        }
+    }
+
+    fn value(&mut self, depth: usize) -> Result<Json, &'static str> {
+        if depth > MAX_DEPTH {
+            return Err("nesting is too deep");
+        }
+        self.skip_whitespace();
+        match self.peek() {
+            None => Err("unexpected end of input"),
+            Some(b'n') => self.expect_literal("null", Json::Null),
+            Some(b't') => self.expect_literal("true", Json::Bool(true)),
+            Some(b'f') => self.expect_literal("false", Json::Bool(false)),
+            Some(b'"') => self.string().map(Json::String),
+            Some(b'[') => {
+                self.pos += 1;
+                let mut elements = Vec::new();
+                self.skip_whitespace();
+                if self.peek() == Some(b']') {
+                    self.pos += 1;
+                    return Ok(Json::Array(elements));
+                }
+                loop {
+                    elements.push(self.value(depth + 1)?);
+                    self.skip_whitespace();
+                    match self.peek() {
+                        Some(b',') => self.pos += 1,
+                        Some(b']') => {
+                            self.pos += 1;
+                            return Ok(Json::Array(elements));
+                        }
+                        _ => return Err("expected `,` or `]`"),
+                    }
+                }
+            }
+            Some(b'{') => {
+                self.pos += 1;
+                let mut members = BTreeMap::new();
+                self.skip_whitespace();
+                if self.peek() == Some(b'}') {
+                    self.pos += 1;
+                    return Ok(Json::Object(members));
+                }
+                loop {
+                    self.skip_whitespace();
+                    if self.peek() != Some(b'"') {
+                        return Err("expected a string key");
+                    }
+                    let key = self.string()?;
+                    self.skip_whitespace();
+                    if self.peek() != Some(b':') {
+                        return Err("expected `:`");
+                    }
+                    self.pos += 1;
+                    members.insert(key, self.value(depth + 1)?);
+                    self.skip_whitespace();
+                    match self.peek() {
+                        Some(b',') => self.pos += 1,
+                        Some(b'}') => {
+                            self.pos += 1;
+                            return Ok(Json::Object(members));
+                        }
+                        _ => return Err("expected `,` or `}`"),
+                    }
+                }
+            }
+            Some(b'-' | b'0'..=b'9') => self.number(),
+            Some(_) => Err("invalid token"),
+        }
+    }
+
+    fn digits(&mut self) -> usize {
+        let start = self.pos;
+        while matches!(self.peek(), Some(b'0'..=b'9')) {
+            self.pos += 1;
+        }
+        self.pos - start
+    }
+
+    fn number(&mut self) -> Result<Json, &'static str> {
+        let start = self.pos;
+        if self.peek() == Some(b'-') {
+            self.pos += 1;
+        }
+        let int_start = self.pos;
+        let int_len = self.digits();
+        if int_len == 0 || (int_len > 1 && self.bytes[int_start] == b'0') {
+            return Err("invalid number");
+        }
+        let mut is_integer = true;
+        if self.peek() == Some(b'.') {
+            self.pos += 1;
+            if self.digits() == 0 {
+                return Err("invalid number");
+            }
+            is_integer = false;
+        }
+        if matches!(self.peek(), Some(b'e' | b'E')) {
+            self.pos += 1;
+            if matches!(self.peek(), Some(b'+' | b'-')) {
+                self.pos += 1;
+            }
+            if self.digits() == 0 {
+                return Err("invalid number");
+            }
+            is_integer = false;
+        }
+        // the number only holds ASCII characters
+        let text = std::str::from_utf8(&self.bytes[start..self.pos]).unwrap();
+        if is_integer {
+            if let Ok(value) = text.parse::<i64>() {
+                return Ok(Json::Int(value));
+            }
+        }
+        match text.parse::<f64>() {
+            Ok(value) if value.is_finite() => Ok(Json::number(value)),
+            _ => Err("number out of range"),
+        }
+    }
+
+    fn hex4(&mut self) -> Result<u32, &'static str> {
+        let hex = self
+            .bytes
+            .get(self.pos..self.pos + 4)
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+            .ok_or("invalid unicode escape")?;
+        self.pos += 4;
+        Ok(hex)
+    }
+
+    fn string(&mut self) -> Result<String, &'static str> {
+        // skips the opening quote
+        self.pos += 1;
+        let mut value = Vec::new();
+        loop {
+            let Some(byte) = self.peek() else {
+                return Err("unterminated string");
+            };
+            self.pos += 1;
+            match byte {
+                b'"' => break,
+                b'\\' => {
+                    let Some(escape) = self.peek() else {
+                        return Err("unterminated string");
+                    };
+                    self.pos += 1;
+                    let c = match escape {
+                        b'"' => '"',
+                        b'\\' => '\\',
+                        b'/' => '/',
+                        b'b' => '\u{08}',
+                        b'f' => '\u{0c}',
+                        b'n' => '\n',
+                        b'r' => '\r',
+                        b't' => '\t',
+                        b'u' => {
+                            let high = self.hex4()?;
+                            let code = if (0xD800..0xDC00).contains(&high) {
+                                if !self.bytes[self.pos..].starts_with(b"\\u") {
+                                    return Err("invalid unicode surrogate pair");
+                                }
+                                self.pos += 2;
+                                let low = self.hex4()?;
+                                if !(0xDC00..0xE000).contains(&low) {
+                                    return Err("invalid unicode surrogate pair");
+                                }
+                                0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00)
+                            } else {
+                                high
+                            };
+                            char::from_u32(code).ok_or("invalid unicode escape")?
+                        }
+                        _ => return Err("invalid escape"),
+                    };
+                    let mut buf = [0; 4];
+                    value.extend_from_slice(c.encode_utf8(&mut buf).as_bytes());
+                }
+                0x00..0x20 => return Err("unescaped control character in string"),
+                byte => value.push(byte),
+            }
+        }
+        // the input is a `str` and escapes are re-encoded, so the bytes stay valid UTF-8
+        String::from_utf8(value).map_err(|_| "invalid UTF-8")
+    }
+}
+
+/// Parses a `$.key[0]` path, `."quoted key"` naming keys with dots or brackets.
+pub fn parse_json_path(path: &str) -> Result<Vec<JsonPathStep>, DatabaseError> {
+    let invalid = || DatabaseError::InvalidValue(format!("invalid JSON path: {path}"));
+    let Some(mut rest) = path.trim().strip_prefix('$') else {
+        return Err(invalid());
+    };
+    let mut steps = Vec::new();
+    while !rest.is_empty() {
+        if let Some(tail) = rest.strip_prefix('.') {
+            if let Some(tail) = tail.strip_prefix('"') {
+                let end = tail.find('"').ok_or_else(invalid)?;
+                steps.push(JsonPathStep::Key(tail[..end].to_string()));
+                rest = &tail[end + 1..];
+            } else {
+                let end = tail.find(['.', '[']).unwrap_or(tail.len());
+                if end == 0 {
+                    return Err(invalid());
+                }
+                steps.push(JsonPathStep::Key(tail[..end].to_string()));
+                rest = &tail[end..];
+            }
+        } else if let Some(tail) = rest.strip_prefix('[') {
+            let end = tail.find(']').ok_or_else(invalid)?;
+            let index = tail[..end].trim().parse().map_err(|_| invalid())?;
+            steps.push(JsonPathStep::Index(index));
+            rest = &tail[end + 1..];
+        } else {
+            return Err(invalid());
+        }
+    }
+    Ok(steps)
+}
+
+/// Appends `step` to the `$.key[0]` path `path`, quoting keys that are not plain names.
+pub fn push_json_path(path: &mut String, step: &JsonPathStep) {
+    match step {
+        JsonPathStep::Key(key) if !key.is_empty() && !key.contains(['.', '[', ']', '"']) => {
+            let _ = write!(path, ".{key}");
+        }
+        JsonPathStep::Key(key) => {
+            let _ = write!(path, ".\"{key}\"");
+        }
+        JsonPathStep::Index(index) => {
+            let _ = write!(path, "[{index}]");
+        }
+    }
+}
+
+/// Parses the `'{a,b,0}'` array literal a `#>` path is written as.
+pub fn parse_text_path(path: &str) -> Result<Vec<String>, DatabaseError> {
+    let invalid = || DatabaseError::InvalidValue(format!("malformed path literal: {path}"));
+    let inner = path
+        .trim()
+        .strip_prefix('{')
+        .and_then(|path| path.strip_suffix('}'))
+        .ok_or_else(invalid)?;
+    if inner.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+    let mut elements = Vec::new();
+    let mut chars = inner.chars().peekable();
+    loop {
+        while chars.next_if(|c| c.is_whitespace()).is_some() {}
+        let mut element = String::new();
+        if chars.next_if_eq(&'"').is_some() {
+            loop {
+                match chars.next() {
+                    Some('"') => break,
+                    Some('\\') => element.push(chars.next().ok_or_else(invalid)?),
+                    Some(c) => element.push(c),
+                    None => return Err(invalid()),
+                }
+            }
+            while chars.next_if(|c| c.is_whitespace()).is_some() {}
+        } else {
+            while let Some(c) = chars.next_if(|c| *c != ',') {
+                element.push(c);
+            }
+            element.truncate(element.trim_end().len());
+            if element.is_empty() {
+                return Err(invalid());
+            }
+        }
+        elements.push(element);
+        match chars.next() {
+            Some(',') => (),
+            None => return Ok(elements),
+            Some(_) => return Err(invalid()),
+        }
+    }
+}
+
+// GRCOV_EXCL_START
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_and_prints_documents() -> Result<(), DatabaseError> {
+        let json = Json::parse(
+            r#" {"b": [1, 2.5, -3e2, true, null], "a": "x\"\u00e9\ud83d\ude00", "b": {}} "#,
+        )?;
+        assert_eq!(json.to_string(), r#"{"a": "x\"é😀", "b": {}}"#);
+        assert_eq!(
+            Json::parse("[1, 2.5, -3e2, 1.0, 1e300]")?.to_string(),
+            "[1, 2.5, -300, 1, 1e300]"
+        );
+        assert_eq!(Json::parse("\"\\n\\u0001\"")?.to_string(), "\"\\n\\u0001\"");
+        assert_eq!(
+            Json::parse("18446744073709551616")?,
+            Json::number(1.8446744073709552e19)
+        );
+
+        for invalid in [
+            "",
+            "{",
+            "[1,]",
+            "{\"a\" 1}",
+            "01",
+            "1.",
+            "-",
+            "tru",
+            "\"a",
+            "[1] 2",
+            "1e999",
+            "\"\t\"",
+            "{1: 2}",
+            "\"\\ud83d\"",
+        ] {
+            assert!(Json::parse(invalid).is_err(), "{invalid}");
+        }
+        assert!(Json::parse(&"[".repeat(MAX_DEPTH + 2)).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn orders_like_jsonb() -> Result<(), DatabaseError> {
+        let values = [
+            "null",
+            "\"a\"",
+            "\"b\"",
+            "-1.5",
+            "1",
+            "1.5",
+            "2",
+            "false",
+            "true",
+            "[]",
+            "[3]",
+            "[1, 2]",
+            "{}",
+            "{\"a\": 2}",
+            "{\"b\": 1}",
+            "{\"a\": 1, \"b\": 1}",
+        ]
+        .iter()
+        .map(|text| Json::parse(text))
+        .collect::<Result<Vec<_>, _>>()?;
+        for window in values.windows(2) {
+            assert!(window[0] < window[1], "{} < {}", window[0], window[1]);
+        }
+        assert_eq!(Json::parse("1.0")?, Json::parse("1")?);
+        Ok(())
+    }
+
+    #[test]
+    fn follows_and_sets_paths() -> Result<(), DatabaseError> {
+        let mut json = Json::parse(r#"{"a": {"b": [10, 20, 30]}, "c.d": 1}"#)?;
+        let path = parse_json_path("$.a.b[-1]")?;
+        assert_eq!(json.get_path(&path), Some(&Json::Int(30)));
+        assert_eq!(
+            json.get_path(&parse_json_path("$.\"c.d\"")?),
+            Some(&Json::Int(1))
+        );
+        assert_eq!(json.get_text_path(&["a", "b", "1"]), Some(&Json::Int(20)));
+        assert_eq!(json.get_text_path(&["a", "x"]), None);
+
+        json.set_path(&path, Json::Bool(true));
+        json.set_path(&parse_json_path("$.a.b[9]")?, Json::Null);
+        json.set_path(&parse_json_path("$.a.e")?, Json::Int(5));
+        json.set_path(&parse_json_path("$.x.y")?, Json::Int(6));
+        assert_eq!(
+            json.to_string(),
+            r#"{"a": {"b": [10, 20, true, null], "e": 5}, "c.d": 1}"#
+        );
+        assert!(parse_json_path("a.b").is_err());
+        assert!(parse_json_path("$.a[x]").is_err());
+        assert!(parse_json_path("$..a").is_err());
+
+        let mut path = "$".to_string();
+        for step in [
+            JsonPathStep::Key("a".to_string()),
+            JsonPathStep::Index(0),
+            JsonPathStep::Key("b.c".to_string()),
+        ] {
+            push_json_path(&mut path, &step);
+        }
+        assert_eq!(path, "$.a[0].\"b.c\"");
+        Ok(())
+    }
+
+    #[test]
+    fn parses_text_paths() -> Result<(), DatabaseError> {
+        assert_eq!(parse_text_path("{a, b ,0}")?, vec!["a", "b", "0"]);
+        assert_eq!(parse_text_path("{\"a,b\", \"c\\\"\"}")?, vec!["a,b", "c\""]);
+        assert!(parse_text_path("{}")?.is_empty());
+        assert!(parse_text_path("a,b").is_err());
+        assert!(parse_text_path("{a,,b}").is_err());
+        Ok(())
+    }
+}
+// GRCOV_EXCL_STOP
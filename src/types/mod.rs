@@ -12,15 +12,20 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+pub mod array;
 pub mod evaluator;
 pub mod index;
+pub mod interval;
+pub mod json;
 pub mod serialize;
+#[cfg(feature = "time")]
+pub mod timezone;
 pub mod tuple;
 pub mod tuple_builder;
 pub mod value;
 
 #[cfg(feature = "time")]
-use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, Utc};
 #[cfg(feature = "decimal")]
 use rust_decimal::Decimal;
 use std::any::TypeId;
@@ -70,6 +75,9 @@ pub enum LogicalType {
     // decimal (precision, scale)
     Decimal(Option<u8>, Option<u8>),
     Tuple(Vec<LogicalType>),
+    Interval,
+    Json,
+    Array(Box<LogicalType>),
 }
 
 impl LogicalType {
@@ -106,6 +114,10 @@ impl LogicalType {
         } else if type_id == TypeId::of::<String>() {
             Some(LogicalType::Varchar(None, CharLengthUnits::Characters))
         } else {
+            #[cfg(feature = "json")]
+            if type_id == TypeId::of::<serde_json::Value>() {
+                return Some(LogicalType::Json);
+            }
             #[cfg(feature = "time")]
             {
                 if type_id == TypeId::of::<NaiveDate>() {
@@ -117,6 +129,12 @@ impl LogicalType {
                 if type_id == TypeId::of::<NaiveTime>() {
                     return Some(LogicalType::Time(Some(0)));
                 }
+                if type_id == TypeId::of::<DateTime<Utc>>()
+                    || type_id == TypeId::of::<DateTime<FixedOffset>>()
+                    || type_id == TypeId::of::<DateTime<chrono_tz::Tz>>()
+                {
+                    return Some(LogicalType::TimeStamp(Some(6), true));
+                }
             }
             None
         }
@@ -148,6 +166,9 @@ impl LogicalType {
             LogicalType::Time(_) => Some(4),
             LogicalType::TimeStamp(_, _) => Some(8),
             LogicalType::Tuple(_) => unreachable!(),
+            LogicalType::Interval => Some(16),
+            LogicalType::Json => None,
+            LogicalType::Array(_) => None,
         }
     }
 
@@ -225,6 +246,17 @@ impl LogicalType {
                     return Ok(Cow::Borrowed(right));
                 }
             }
+            (LogicalType::Array(element_0), LogicalType::Array(element_1)) => {
+                return Ok(Cow::Owned(LogicalType::Array(Box::new(
+                    LogicalType::max_logical_type(element_0, element_1)?.into_owned(),
+                ))));
+            }
+            (LogicalType::Array(_), LogicalType::Varchar(..) | LogicalType::Char(..)) => {
+                return Ok(Cow::Borrowed(left))
+            }
+            (LogicalType::Varchar(..) | LogicalType::Char(..), LogicalType::Array(_)) => {
+                return Ok(Cow::Borrowed(right))
+            }
             _ => {}
         }
         if left.is_numeric() && right.is_numeric() {
@@ -254,6 +286,17 @@ impl LogicalType {
                 return Ok(Cow::Owned(LogicalType::DateTime));
             }
         }
+        if let (LogicalType::Interval, LogicalType::Varchar(..) | LogicalType::Char(..))
+        | (LogicalType::Varchar(..) | LogicalType::Char(..), LogicalType::Interval) =
+            (left, right)
+        {
+            return Ok(Cow::Owned(LogicalType::Interval));
+        }
+        if let (LogicalType::Json, LogicalType::Varchar(..) | LogicalType::Char(..))
+        | (LogicalType::Varchar(..) | LogicalType::Char(..), LogicalType::Json) = (left, right)
+        {
+            return Ok(Cow::Owned(LogicalType::Json));
+        }
         if let (LogicalType::Char(..), LogicalType::Varchar(..))
         | (LogicalType::Varchar(..), LogicalType::Char(..))
         | (LogicalType::Char(..), LogicalType::Char(..))
@@ -406,9 +449,16 @@ impl LogicalType {
                     | LogicalType::Varchar(..)
                     | LogicalType::Char(..)
             ),
-            LogicalType::Time(..) => {
+            LogicalType::Time(..) | LogicalType::Interval | LogicalType::Json => {
                 matches!(to, LogicalType::Varchar(..) | LogicalType::Char(..))
             }
+            LogicalType::Array(from_element) => match to {
+                LogicalType::Array(to_element) => {
+                    LogicalType::can_implicit_cast(from_element, to_element)
+                }
+                LogicalType::Varchar(..) | LogicalType::Char(..) => true,
+                _ => false,
+            },
             LogicalType::Decimal(_, _) | LogicalType::Tuple(_) => false,
         }
     }
@@ -452,6 +502,9 @@ impl std::fmt::Display for LogicalType {
                 }
                 write!(f, ")")?
             }
+            LogicalType::Interval => write!(f, "Interval")?,
+            LogicalType::Json => write!(f, "Json")?,
+            LogicalType::Array(element) => write!(f, "Array({element})")?,
         }
 
         Ok(())
@@ -553,6 +606,7 @@ pub(crate) mod test {
             (LogicalType::DateTime, Some(8)),
             (LogicalType::Time(None), Some(4)),
             (LogicalType::TimeStamp(None, false), Some(8)),
+            (LogicalType::Interval, Some(16)),
         ];
         for (ty, len) in fixed {
             assert_eq!(ty.raw_len(), len);
@@ -637,6 +691,38 @@ pub(crate) mod test {
             .as_ref(),
             &LogicalType::Tuple(vec![LogicalType::Integer, LogicalType::Bigint])
         );
+        assert_eq!(
+            LogicalType::max_logical_type(
+                &LogicalType::Interval,
+                &LogicalType::Varchar(None, CharLengthUnits::Characters)
+            )?
+            .as_ref(),
+            &LogicalType::Interval
+        );
+        assert_eq!(
+            LogicalType::max_logical_type(
+                &LogicalType::Varchar(None, CharLengthUnits::Characters),
+                &LogicalType::Json
+            )?
+            .as_ref(),
+            &LogicalType::Json
+        );
+        assert_eq!(
+            LogicalType::max_logical_type(
+                &LogicalType::Array(Box::new(LogicalType::SqlNull)),
+                &LogicalType::Array(Box::new(LogicalType::Integer))
+            )?
+            .as_ref(),
+            &LogicalType::Array(Box::new(LogicalType::Integer))
+        );
+        assert_eq!(
+            LogicalType::max_logical_type(
+                &LogicalType::Varchar(None, CharLengthUnits::Characters),
+                &LogicalType::Array(Box::new(LogicalType::Integer))
+            )?
+            .as_ref(),
+            &LogicalType::Array(Box::new(LogicalType::Integer))
+        );
 
         let numeric_cases = vec![
             (
@@ -1004,6 +1090,13 @@ pub(crate) mod test {
             &mut reference_tables,
             LogicalType::Tuple(vec![LogicalType::Integer]),
         )?;
+        fn_assert(&mut cursor, &mut reference_tables, LogicalType::Interval)?;
+        fn_assert(&mut cursor, &mut reference_tables, LogicalType::Json)?;
+        fn_assert(
+            &mut cursor,
+            &mut reference_tables,
+            LogicalType::Array(Box::new(LogicalType::Array(Box::new(LogicalType::Integer)))),
+        )?;
 
         Ok(())
     }
@@ -13,6 +13,8 @@
 // limitations under the License.
 
 use crate::errors::DatabaseError;
+use crate::types::interval::Interval;
+use crate::types::json::Json;
 use crate::types::value::{DataValue, Utf8Type};
 use crate::types::CharLengthUnits;
 use crate::types::LogicalType;
@@ -92,6 +94,19 @@ pub enum TupleValueSerializableImpl {
     Decimal,
     SkipFixed(usize),
     SkipVariable,
+    Interval,
+    Json,
+    Array(Box<TupleValueSerializableImpl>),
+}
+
+impl TupleValueSerializableImpl {
+    /// Whether this entry only advances past an unprojected column.
+    pub(crate) fn is_skip(&self) -> bool {
+        matches!(
+            self,
+            TupleValueSerializableImpl::SkipFixed(_) | TupleValueSerializableImpl::SkipVariable
+        )
+    }
 }
 
 impl TupleValueSerializable for TupleValueSerializableImpl {
@@ -132,6 +147,11 @@ impl TupleValueSerializable for TupleValueSerializableImpl {
             TupleValueSerializableImpl::Decimal => DecimalSerializable.to_raw(value, writer),
             TupleValueSerializableImpl::SkipFixed(len) => SkipFixed(*len).to_raw(value, writer),
             TupleValueSerializableImpl::SkipVariable => SkipVariable.to_raw(value, writer),
+            TupleValueSerializableImpl::Interval => IntervalSerializable.to_raw(value, writer),
+            TupleValueSerializableImpl::Json => JsonSerializable.to_raw(value, writer),
+            TupleValueSerializableImpl::Array(element) => {
+                ArraySerializable(element).to_raw(value, writer)
+            }
         }
     }
 
@@ -172,6 +192,11 @@ impl TupleValueSerializable for TupleValueSerializableImpl {
             TupleValueSerializableImpl::Decimal => DecimalSerializable.from_raw(reader),
             TupleValueSerializableImpl::SkipFixed(len) => SkipFixed(*len).from_raw(reader),
             TupleValueSerializableImpl::SkipVariable => SkipVariable.from_raw(reader),
+            TupleValueSerializableImpl::Interval => IntervalSerializable.from_raw(reader),
+            TupleValueSerializableImpl::Json => JsonSerializable.from_raw(reader),
+            TupleValueSerializableImpl::Array(element) => {
+                ArraySerializable(element).from_raw(reader)
+            }
         }
     }
 
@@ -228,6 +253,13 @@ impl TupleValueSerializable for TupleValueSerializableImpl {
                 SkipFixed(*len).filling_value(reader, values)
             }
             TupleValueSerializableImpl::SkipVariable => SkipVariable.filling_value(reader, values),
+            TupleValueSerializableImpl::Interval => {
+                IntervalSerializable.filling_value(reader, values)
+            }
+            TupleValueSerializableImpl::Json => JsonSerializable.filling_value(reader, values),
+            TupleValueSerializableImpl::Array(element) => {
+                ArraySerializable(element).filling_value(reader, values)
+            }
         }
     }
 }
@@ -287,6 +319,15 @@ struct TimeStampSerializable {
 #[derive(Debug)]
 struct DecimalSerializable;
 
+#[derive(Debug)]
+struct IntervalSerializable;
+
+#[derive(Debug)]
+struct JsonSerializable;
+
+#[derive(Debug)]
+struct ArraySerializable<'a>(&'a TupleValueSerializableImpl);
+
 #[derive(Debug)]
 struct SkipFixed(usize);
 #[derive(Debug)]
@@ -535,6 +576,198 @@ impl TupleValueSerializable for TimeStampSerializable {
     }
 }
 
+impl_tuple_value_serializable!(
+    IntervalSerializable,
+    Interval,
+    |writer: &mut dyn Write, value: &Interval| {
+        write_i32_le(writer, value.months)?;
+        write_i32_le(writer, value.days)?;
+        write_i64_le(writer, value.nanos)
+    },
+    |reader: &mut Cursor<&[u8]>| {
+        Result::<_, DatabaseError>::Ok(Interval::new(
+            read_i32_le(reader)?,
+            read_i32_le(reader)?,
+            read_i64_le(reader)?,
+        ))
+    }
+);
+
+// Tags of the binary JSON encoding. Integers are zigzag varints, and strings, arrays and
+// objects are prefixed with their varint length, so small documents stay compact.
+const JSON_NULL: u8 = 0;
+const JSON_FALSE: u8 = 1;
+const JSON_TRUE: u8 = 2;
+const JSON_INT: u8 = 3;
+const JSON_FLOAT: u8 = 4;
+const JSON_STRING: u8 = 5;
+const JSON_ARRAY: u8 = 6;
+const JSON_OBJECT: u8 = 7;
+
+fn write_varint<W: Write + ?Sized>(writer: &mut W, mut value: u64) -> std::io::Result<()> {
+    while value >= 0x80 {
+        write_u8(writer, value as u8 | 0x80)?;
+        value >>= 7;
+    }
+    write_u8(writer, value as u8)
+}
+
+fn read_varint<R: Read + ?Sized>(reader: &mut R) -> Result<u64, DatabaseError> {
+    let mut value = 0_u64;
+    for shift in (0..64).step_by(7) {
+        let byte = read_u8(reader)?;
+        value |= ((byte & 0x7f) as u64) << shift;
+        if byte & 0x80 == 0 {
+            return Ok(value);
+        }
+    }
+    Err(DatabaseError::InvalidValue(
+        "invalid binary json varint".to_string(),
+    ))
+}
+
+fn write_json_str<W: Write + ?Sized>(writer: &mut W, value: &str) -> std::io::Result<()> {
+    write_varint(writer, value.len() as u64)?;
+    writer.write_all(value.as_bytes())
+}
+
+fn read_json_str<R: Read + ?Sized>(reader: &mut R) -> Result<String, DatabaseError> {
+    let len = read_varint(reader)? as usize;
+    let mut bytes = Vec::with_capacity(len);
+    reader.take(len as u64).read_to_end(&mut bytes)?;
+    if bytes.len() != len {
+        return Err(std::io::Error::from(std::io::ErrorKind::UnexpectedEof).into());
+    }
+    Ok(String::from_utf8(bytes)?)
+}
+
+pub(crate) fn write_json<W: Write + ?Sized>(writer: &mut W, json: &Json) -> std::io::Result<()> {
+    match json {
+        Json::Null => write_u8(writer, JSON_NULL),
+        Json::Bool(false) => write_u8(writer, JSON_FALSE),
+        Json::Bool(true) => write_u8(writer, JSON_TRUE),
+        Json::Int(value) => {
+            write_u8(writer, JSON_INT)?;
+            write_varint(writer, ((value << 1) ^ (value >> 63)) as u64)
+        }
+        Json::Float(value) => {
+            write_u8(writer, JSON_FLOAT)?;
+            write_f64_le(writer, value.0)
+        }
+        Json::String(value) => {
+            write_u8(writer, JSON_STRING)?;
+            write_json_str(writer, value)
+        }
+        Json::Array(elements) => {
+            write_u8(writer, JSON_ARRAY)?;
+            write_varint(writer, elements.len() as u64)?;
+            elements
+                .iter()
+                .try_for_each(|element| write_json(writer, element))
+        }
+        Json::Object(members) => {
+            write_u8(writer, JSON_OBJECT)?;
+            write_varint(writer, members.len() as u64)?;
+            members.iter().try_for_each(|(key, value)| {
+                write_json_str(writer, key)?;
+                write_json(writer, value)
+            })
+        }
+    }
+}
+
+pub(crate) fn read_json<R: Read + ?Sized>(reader: &mut R) -> Result<Json, DatabaseError> {
+    Ok(match read_u8(reader)? {
+        JSON_NULL => Json::Null,
+        JSON_FALSE => Json::Bool(false),
+        JSON_TRUE => Json::Bool(true),
+        JSON_INT => {
+            let value = read_varint(reader)?;
+            Json::Int((value >> 1) as i64 ^ -((value & 1) as i64))
+        }
+        JSON_FLOAT => Json::Float(OrderedFloat(read_f64_le(reader)?)),
+        JSON_STRING => Json::String(read_json_str(reader)?),
+        JSON_ARRAY => {
+            let len = read_varint(reader)?;
+            Json::Array(
+                (0..len)
+                    .map(|_| read_json(reader))
+                    .collect::<Result<_, _>>()?,
+            )
+        }
+        JSON_OBJECT => {
+            let len = read_varint(reader)?;
+            let mut members = std::collections::BTreeMap::new();
+            for _ in 0..len {
+                let key = read_json_str(reader)?;
+                members.insert(key, read_json(reader)?);
+            }
+            Json::Object(members)
+        }
+        tag => {
+            return Err(DatabaseError::InvalidValue(format!(
+                "invalid binary json tag: {tag}"
+            )))
+        }
+    })
+}
+
+impl TupleValueSerializable for JsonSerializable {
+    fn to_raw<W: Write>(&self, value: &DataValue, writer: &mut W) -> Result<(), DatabaseError> {
+        let DataValue::Json(json) = value else {
+            unsafe { std::hint::unreachable_unchecked() }
+        };
+        // the length prefix lets unprojected columns be skipped like any variable value
+        let mut bytes = Vec::new();
+        write_json(&mut bytes, json)?;
+        write_u32_le(writer, bytes.len() as u32)?;
+        writer.write_all(&bytes)?;
+        Ok(())
+    }
+
+    fn from_raw(&self, reader: &mut Cursor<&[u8]>) -> Result<DataValue, DatabaseError> {
+        let _ = read_u32_le(reader)?;
+        Ok(DataValue::Json(read_json(reader)?))
+    }
+}
+
+impl TupleValueSerializable for ArraySerializable<'_> {
+    fn to_raw<W: Write>(&self, value: &DataValue, writer: &mut W) -> Result<(), DatabaseError> {
+        let DataValue::Array(values) = value else {
+            unsafe { std::hint::unreachable_unchecked() }
+        };
+        // the element count and a null flag per element follow the byte length, which lets
+        // unprojected columns be skipped like any variable value
+        let mut bytes = Vec::new();
+        write_u32_le(&mut bytes, values.len() as u32)?;
+        for value in values {
+            if value.is_null() {
+                write_u8(&mut bytes, 0)?;
+            } else {
+                write_u8(&mut bytes, 1)?;
+                self.0.to_raw(value, &mut bytes)?;
+            }
+        }
+        write_u32_le(writer, bytes.len() as u32)?;
+        writer.write_all(&bytes)?;
+        Ok(())
+    }
+
+    fn from_raw(&self, reader: &mut Cursor<&[u8]>) -> Result<DataValue, DatabaseError> {
+        let _ = read_u32_le(reader)?;
+        let len = read_u32_le(reader)? as usize;
+        let mut values = Vec::with_capacity(len);
+        for _ in 0..len {
+            values.push(if read_u8(reader)? == 0 {
+                DataValue::Null
+            } else {
+                self.0.from_raw(reader)?
+            });
+        }
+        Ok(DataValue::Array(values))
+    }
+}
+
 #[cfg(feature = "decimal")]
 impl_tuple_value_serializable!(
     DecimalSerializable,
@@ -642,6 +875,11 @@ impl LogicalType {
                 zone: *zone,
             },
             LogicalType::Decimal(_, _) => TupleValueSerializableImpl::Decimal,
+            LogicalType::Interval => TupleValueSerializableImpl::Interval,
+            LogicalType::Json => TupleValueSerializableImpl::Json,
+            LogicalType::Array(element) => {
+                TupleValueSerializableImpl::Array(Box::new(element.serializable()))
+            }
             LogicalType::SqlNull | LogicalType::Tuple(_) => unreachable!(),
         }
     }
@@ -740,6 +978,27 @@ mod tests {
                 TupleValueSerializableImpl::Decimal,
                 DataValue::Decimal(Decimal::new(1234, 2)),
             ),
+            (
+                TupleValueSerializableImpl::Interval,
+                DataValue::Interval(Interval::new(1, -2, 3)),
+            ),
+            (
+                TupleValueSerializableImpl::Json,
+                DataValue::Json(Json::parse(
+                    r#"{"a": [null, true, false, -1, 300, 2.5, "x"], "b": {}, "": -9223372036854775808}"#,
+                )?),
+            ),
+            (
+                LogicalType::Array(Box::new(LogicalType::Array(Box::new(
+                    LogicalType::Varchar(None, CharLengthUnits::Characters),
+                ))))
+                .serializable(),
+                DataValue::Array(vec![
+                    DataValue::Array(vec![DataValue::from("a".to_string()), DataValue::Null]),
+                    DataValue::Null,
+                    DataValue::Array(vec![]),
+                ]),
+            ),
         ];
 
         for (serializer, value) in cases {
@@ -781,6 +1040,27 @@ mod tests {
         skip_variable.filling_value(&mut Cursor::new(bytes.as_slice()), &mut values)?;
         assert_eq!(values, vec![DataValue::Int32(2)]);
 
+        let mut bytes = Vec::new();
+        TupleValueSerializableImpl::Json.to_raw(&DataValue::Json(Json::Int(1)), &mut bytes)?;
+        assert_eq!(bytes, [2, 0, 0, 0, JSON_INT, 2]);
+        bytes.push(9);
+        let mut cursor = Cursor::new(bytes.as_slice());
+        assert_eq!(skip_variable.from_raw(&mut cursor)?, DataValue::Null);
+        assert_eq!(read_u8(&mut cursor)?, 9);
+
+        let mut bytes = Vec::new();
+        LogicalType::Array(Box::new(LogicalType::Integer))
+            .serializable()
+            .to_raw(
+                &DataValue::Array(vec![DataValue::Int32(1), DataValue::Null]),
+                &mut bytes,
+            )?;
+        assert_eq!(bytes, [10, 0, 0, 0, 2, 0, 0, 0, 1, 1, 0, 0, 0, 0]);
+        bytes.push(9);
+        let mut cursor = Cursor::new(bytes.as_slice());
+        assert_eq!(skip_variable.from_raw(&mut cursor)?, DataValue::Null);
+        assert_eq!(read_u8(&mut cursor)?, 9);
+
         Ok(())
     }
 
@@ -0,0 +1,355 @@
+// Copyright 2024 KipData/KiteSQL
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Time zones of `TIMESTAMP WITH TIME ZONE` values and the session time zone.
+//!
+//! A `TIMESTAMPTZ` is stored as a UTC instant. It is shown, and text without an explicit
+//! offset is read, in the time zone of the session running the statement, which is set with
+//! `SET TIME ZONE`. Evaluators and [`DataValue`](crate::types::value::DataValue)'s `Display` run
+//! without any context, so the zone of the running statement is kept in a thread local entered
+//! with [`TimeZoneScope`] around planning and around every `next_tuple` of a result iterator.
+//! Outside of those the zone is passed explicitly: a result iterator keeps it in
+//! `ResultIter::time_zone`, for `DataValue::display_in` and the ORM's `from_data_value_in`.
+
+use crate::errors::DatabaseError;
+use chrono::{FixedOffset, LocalResult, NaiveDateTime, Offset, TimeDelta, TimeZone as _};
+use chrono_tz::{Tz, TZ_VARIANTS};
+use std::cell::Cell;
+use std::fmt;
+
+thread_local! {
+    static SESSION_TIME_ZONE: Cell<TimeZone> = const { Cell::new(TimeZone::UTC) };
+}
+
+/// A fixed UTC offset such as `+05:30` or an IANA zone such as `America/New_York`, whose
+/// offset follows daylight saving time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TimeZone {
+    Fixed(FixedOffset),
+    Named(Tz),
+}
+
+impl Default for TimeZone {
+    fn default() -> Self {
+        TimeZone::UTC
+    }
+}
+
+impl TimeZone {
+    pub const UTC: TimeZone = TimeZone::Named(Tz::UTC);
+
+    /// Parses `UTC`, an ISO 8601 offset (`+05`, `+05:30`, `-0800`) or an IANA zone name,
+    /// case-insensitively. Offsets count east of Greenwich, unlike POSIX zone strings.
+    pub fn parse(name: &str) -> Result<Self, DatabaseError> {
+        let name = name.trim();
+        let invalid =
+            || DatabaseError::InvalidValue(format!("time zone \"{name}\" not recognized"));
+
+        if name.starts_with(['+', '-']) {
+            return parse_offset(name).map(TimeZone::Fixed).ok_or_else(invalid);
+        }
+        if ["Z", "UTC", "GMT"]
+            .iter()
+            .any(|utc| name.eq_ignore_ascii_case(utc))
+        {
+            return Ok(TimeZone::UTC);
+        }
+        name.parse::<Tz>()
+            .ok()
+            .or_else(|| {
+                TZ_VARIANTS
+                    .iter()
+                    .find(|tz| tz.name().eq_ignore_ascii_case(name))
+                    .copied()
+            })
+            .map(TimeZone::Named)
+            .ok_or_else(invalid)
+    }
+
+    /// The offset from UTC in effect at the UTC instant `utc`.
+    pub fn offset(&self, utc: &NaiveDateTime) -> FixedOffset {
+        match self {
+            TimeZone::Fixed(offset) => *offset,
+            TimeZone::Named(tz) => tz.offset_from_utc_datetime(utc).fix(),
+        }
+    }
+
+    /// The wall clock time in this zone at the UTC instant `utc`.
+    pub fn to_local(&self, utc: &NaiveDateTime) -> NaiveDateTime {
+        *utc + self.offset(utc)
+    }
+
+    /// The UTC instant of the wall clock time `local` in this zone. Like PostgreSQL, a time
+    /// repeated when the clocks go back is read as the earlier one, and a time skipped when
+    /// they go forward is read with the offset in effect before the change.
+    pub fn to_utc(&self, local: &NaiveDateTime) -> NaiveDateTime {
+        let offset = match self {
+            TimeZone::Fixed(offset) => *offset,
+            TimeZone::Named(tz) => match tz.offset_from_local_datetime(local) {
+                LocalResult::Single(offset) | LocalResult::Ambiguous(offset, _) => offset.fix(),
+                LocalResult::None => tz
+                    .offset_from_utc_datetime(&(*local - TimeDelta::days(1)))
+                    .fix(),
+            },
+        };
+        *local - offset
+    }
+
+    /// Makes this the zone of the statements run on the current thread until the returned
+    /// scope is dropped.
+    pub(crate) fn enter(self) -> TimeZoneScope {
+        TimeZoneScope {
+            previous: SESSION_TIME_ZONE.with(|zone| zone.replace(self)),
+        }
+    }
+}
+
+impl fmt::Display for TimeZone {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TimeZone::Fixed(offset) => write!(f, "{offset}"),
+            TimeZone::Named(tz) => write!(f, "{}", tz.name()),
+        }
+    }
+}
+
+/// Restores the previous session time zone of the thread when dropped.
+#[must_use]
+pub(crate) struct TimeZoneScope {
+    previous: TimeZone,
+}
+
+impl Drop for TimeZoneScope {
+    fn drop(&mut self) {
+        SESSION_TIME_ZONE.with(|zone| zone.set(self.previous));
+    }
+}
+
+/// The time zone of the statement running on the current thread, UTC outside of one.
+pub fn session_time_zone() -> TimeZone {
+    SESSION_TIME_ZONE.with(Cell::get)
+}
+
+/// Parses `+HH`, `+HH:MM`, `+HHMM` or `+HH:MM:SS`, with either sign.
+pub(crate) fn parse_offset(offset: &str) -> Option<FixedOffset> {
+    let (sign, digits) = match offset.split_at_checked(1)? {
+        ("+", digits) => (1, digits),
+        ("-", digits) => (-1, digits),
+        _ => return None,
+    };
+    let parts = if digits.contains(':') {
+        digits.split(':').collect::<Vec<_>>()
+    } else if digits.len() == 4 {
+        vec![&digits[..2], &digits[2..]]
+    } else {
+        vec![digits]
+    };
+    if parts.len() > 3
+        || parts.iter().any(|part| {
+            part.is_empty() || part.len() > 2 || !part.bytes().all(|b| b.is_ascii_digit())
+        })
+    {
+        return None;
+    }
+    let mut seconds = 0;
+    for (part, unit) in parts.iter().zip([3600, 60, 1]) {
+        let value = part.parse::<i32>().ok()?;
+        if unit != 3600 && value >= 60 {
+            return None;
+        }
+        seconds += value * unit;
+    }
+    FixedOffset::east_opt(sign * seconds)
+}
+
+/// Formats an offset like PostgreSQL does after a timestamp: `+00`, `+05:30` or `-08`.
+pub(crate) fn format_offset(offset: FixedOffset) -> String {
+    let seconds = offset.local_minus_utc();
+    let sign = if seconds < 0 { '-' } else { '+' };
+    let seconds = seconds.unsigned_abs();
+    let mut formatted = format!("{sign}{:02}", seconds / 3600);
+    if !seconds.is_multiple_of(3600) {
+        formatted.push_str(&format!(":{:02}", seconds / 60 % 60));
+    }
+    if !seconds.is_multiple_of(60) {
+        formatted.push_str(&format!(":{:02}", seconds % 60));
+    }
+    formatted
+}
+
+/// Splits the zone off the end of a timestamp text, such as `Z`, `+05:30` or
+/// ` America/New_York`. The date keeps its dashes since the zone is only looked for after the
+/// time of day.
+pub(crate) fn split_zone(text: &str) -> Result<(&str, Option<TimeZone>), DatabaseError> {
+    let text = text.trim();
+    let Some(time_start) = text.find([' ', 'T']) else {
+        return Ok((text, None));
+    };
+    let time = &text[time_start + 1..];
+    let time = time.trim_start();
+    let time_start = text.len() - time.len();
+
+    if let Some(position) = time.find(|c: char| c.is_ascii_alphabetic()) {
+        let zone = TimeZone::parse(&time[position..])?;
+        return Ok((text[..time_start + position].trim_end(), Some(zone)));
+    }
+    if let Some(position) = time.rfind(['+', '-']) {
+        let offset = &time[position..];
+        let offset = parse_offset(offset.trim()).ok_or_else(|| {
+            DatabaseError::InvalidValue(format!("time zone \"{offset}\" not recognized"))
+        })?;
+        return Ok((
+            text[..time_start + position].trim_end(),
+            Some(TimeZone::Fixed(offset)),
+        ));
+    }
+    Ok((text, None))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::errors::DatabaseError;
+    use crate::types::timezone::{format_offset, session_time_zone, split_zone, TimeZone};
+    use chrono::{FixedOffset, NaiveDate, NaiveDateTime};
+
+    fn datetime(text: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(text, "%Y-%m-%d %H:%M:%S").unwrap()
+    }
+
+    #[test]
+    fn test_parse() -> Result<(), DatabaseError> {
+        assert_eq!(TimeZone::parse("utc")?, TimeZone::UTC);
+        assert_eq!(TimeZone::parse("Z")?, TimeZone::UTC);
+        assert_eq!(
+            TimeZone::parse("+05:30")?,
+            TimeZone::Fixed(FixedOffset::east_opt(5 * 3600 + 30 * 60).unwrap())
+        );
+        assert_eq!(
+            TimeZone::parse("-08")?,
+            TimeZone::Fixed(FixedOffset::west_opt(8 * 3600).unwrap())
+        );
+        assert_eq!(
+            TimeZone::parse("-0330")?,
+            TimeZone::Fixed(FixedOffset::west_opt(3 * 3600 + 30 * 60).unwrap())
+        );
+        assert_eq!(
+            TimeZone::parse("america/new_york")?.to_string(),
+            "America/New_York"
+        );
+        assert_eq!(TimeZone::parse("Asia/Kolkata")?.to_string(), "Asia/Kolkata");
+        assert!(TimeZone::parse("Mars/Olympus_Mons").is_err());
+        assert!(TimeZone::parse("+25").is_err());
+        assert!(TimeZone::parse("+05:60").is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_daylight_saving_time() -> Result<(), DatabaseError> {
+        let new_york = TimeZone::parse("America/New_York")?;
+
+        assert_eq!(
+            new_york.to_local(&datetime("2024-01-15 12:00:00")),
+            datetime("2024-01-15 07:00:00")
+        );
+        assert_eq!(
+            new_york.to_local(&datetime("2024-07-15 12:00:00")),
+            datetime("2024-07-15 08:00:00")
+        );
+        // skipped by the spring forward, read with the standard time offset
+        assert_eq!(
+            new_york.to_utc(&datetime("2024-03-10 02:30:00")),
+            datetime("2024-03-10 07:30:00")
+        );
+        // repeated by the fall back, read as the daylight saving time one
+        assert_eq!(
+            new_york.to_utc(&datetime("2024-11-03 01:30:00")),
+            datetime("2024-11-03 05:30:00")
+        );
+        assert_eq!(
+            format_offset(new_york.offset(&datetime("2024-07-15 12:00:00"))),
+            "-04"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_format_offset() {
+        assert_eq!(format_offset(FixedOffset::east_opt(0).unwrap()), "+00");
+        assert_eq!(
+            format_offset(FixedOffset::east_opt(5 * 3600 + 30 * 60).unwrap()),
+            "+05:30"
+        );
+        assert_eq!(
+            format_offset(FixedOffset::west_opt(3600 + 45).unwrap()),
+            "-01:00:45"
+        );
+    }
+
+    #[test]
+    fn test_split_zone() -> Result<(), DatabaseError> {
+        assert_eq!(
+            split_zone("2024-01-01 10:00:00")?,
+            ("2024-01-01 10:00:00", None)
+        );
+        assert_eq!(
+            split_zone("2024-01-01 10:00:00.5-08:00")?,
+            (
+                "2024-01-01 10:00:00.5",
+                Some(TimeZone::Fixed(FixedOffset::west_opt(8 * 3600).unwrap()))
+            )
+        );
+        assert_eq!(
+            split_zone("2024-01-01T10:00:00Z")?,
+            ("2024-01-01T10:00:00", Some(TimeZone::UTC))
+        );
+        assert_eq!(
+            split_zone("2024-01-01 10:00:00 Europe/Paris")?.1,
+            Some(TimeZone::parse("Europe/Paris")?)
+        );
+        assert_eq!(split_zone("2024-01-01")?, ("2024-01-01", None));
+        assert!(split_zone("2024-01-01 10:00:00 Nowhere").is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_scope() -> Result<(), DatabaseError> {
+        let tokyo = TimeZone::parse("Asia/Tokyo")?;
+
+        assert_eq!(session_time_zone(), TimeZone::UTC);
+        {
+            let _scope = tokyo.enter();
+            assert_eq!(session_time_zone(), tokyo);
+            {
+                let _scope = TimeZone::UTC.enter();
+                assert_eq!(session_time_zone(), TimeZone::UTC);
+            }
+            assert_eq!(session_time_zone(), tokyo);
+        }
+        assert_eq!(session_time_zone(), TimeZone::UTC);
+        assert_eq!(
+            tokyo.to_local(
+                &NaiveDate::from_ymd_opt(2024, 1, 1)
+                    .unwrap()
+                    .and_hms_opt(0, 0, 0)
+                    .unwrap()
+            ),
+            datetime("2024-01-01 09:00:00")
+        );
+
+        Ok(())
+    }
+}
@@ -215,13 +215,14 @@ impl Tuple {
         let mut cursor = Cursor::new(&bytes[bits_len..]);
 
         for (i, deserializer) in deserializers.into_iter().enumerate() {
+            let deserializer = deserializer.borrow();
             if is_null(bytes[i / BITS_MAX_INDEX], i % BITS_MAX_INDEX) {
-                self.values.push(DataValue::Null);
+                if !deserializer.is_skip() {
+                    self.values.push(DataValue::Null);
+                }
                 continue;
             }
-            deserializer
-                .borrow()
-                .filling_value(&mut cursor, &mut self.values)?;
+            deserializer.filling_value(&mut cursor, &mut self.values)?;
         }
         Ok(())
     }
@@ -539,6 +540,23 @@ mod tests {
                     values: vec![DataValue::Int32(0), DataValue::Int16(1)],
                 }
             );
+
+            let mut tuple_null = Tuple {
+                pk: tuples[1].pk.clone(),
+                values: Vec::with_capacity(2),
+            };
+            tuples[1].serialize_to(&serializers, &mut bytes).unwrap();
+            tuple_null
+                .deserialize_from_into(&projection_serializers, &bytes, columns.len())
+                .unwrap();
+
+            assert_eq!(
+                tuple_null,
+                Tuple {
+                    pk: Some(DataValue::Int32(1)),
+                    values: vec![DataValue::Int32(1), DataValue::Null],
+                }
+            );
         }
         // multiple pk
         {
@@ -16,7 +16,12 @@ use super::LogicalType;
 use crate::errors::DatabaseError;
 use crate::iter_ext::Itertools;
 use crate::storage::table_codec::{BumpBytes, BOUND_MAX_TAG, NOTNULL_TAG, NULL_TAG};
+use crate::types::array::write_array;
 use crate::types::evaluator::cast::{cast_create, to_char, to_varchar};
+use crate::types::interval::Interval;
+use crate::types::json::Json;
+#[cfg(feature = "time")]
+use crate::types::timezone::{format_offset, session_time_zone, TimeZone};
 use crate::types::CharLengthUnits;
 #[cfg(feature = "time")]
 use chrono::{
@@ -160,6 +165,9 @@ pub enum DataValue {
     Decimal(Decimal),
     /// (values, is_upper)
     Tuple(Vec<DataValue>, bool),
+    Interval(Interval),
+    Json(Json),
+    Array(Vec<DataValue>),
 }
 
 #[derive(Clone, Copy)]
@@ -322,6 +330,12 @@ impl PartialEq for DataValue {
                 values_1.eq(values_2) && is_upper_1.eq(is_upper_2)
             }
             (Tuple(..), _) => false,
+            (Interval(v1), Interval(v2)) => v1.eq(v2),
+            (Interval(_), _) => false,
+            (Json(v1), Json(v2)) => v1.eq(v2),
+            (Json(_), _) => false,
+            (Array(v1), Array(v2)) => v1.eq(v2),
+            (Array(_), _) => false,
         }
     }
 }
@@ -418,6 +432,12 @@ impl PartialOrd for DataValue {
                 tuple_partial_cmp((v1, *is_upper1), (v2, *is_upper2))
             }
             (Tuple(..), _) => None,
+            (Interval(v1), Interval(v2)) => v1.partial_cmp(v2),
+            (Interval(_), _) => None,
+            (Json(v1), Json(v2)) => v1.partial_cmp(v2),
+            (Json(_), _) => None,
+            (Array(v1), Array(v2)) => tuple_partial_cmp((v1, false), (v2, false)),
+            (Array(_), _) => None,
         }
     }
 }
@@ -517,6 +537,18 @@ impl Hash for DataValue {
                 values.hash(state);
                 is_upper.hash(state);
             }
+            Interval(v) => {
+                19u8.hash(state);
+                v.hash(state);
+            }
+            Json(v) => {
+                20u8.hash(state);
+                v.hash(state);
+            }
+            Array(values) => {
+                21u8.hash(state);
+                values.hash(state);
+            }
         }
     }
 }
@@ -547,6 +579,16 @@ impl DataValue {
             #[cfg(feature = "decimal")]
             DataValue::Decimal(_) => 16,
             DataValue::Tuple(values, _) => values.iter().map(DataValue::serialized_len_hint).sum(),
+            DataValue::Interval(_) => 16,
+            // the binary document is at least as long as its length prefix
+            DataValue::Json(_) => std::mem::size_of::<u32>(),
+            DataValue::Array(values) => {
+                2 * std::mem::size_of::<u32>()
+                    + values
+                        .iter()
+                        .map(|value| 1 + value.serialized_len_hint())
+                        .sum::<usize>()
+            }
         }
     }
 
@@ -566,6 +608,30 @@ impl DataValue {
         }
     }
 
+    pub fn interval(&self) -> Option<Interval> {
+        if let DataValue::Interval(val) = self {
+            Some(*val)
+        } else {
+            None
+        }
+    }
+
+    pub fn json(&self) -> Option<&Json> {
+        if let DataValue::Json(val) = self {
+            Some(val)
+        } else {
+            None
+        }
+    }
+
+    pub fn array(&self) -> Option<&[DataValue]> {
+        if let DataValue::Array(values) = self {
+            Some(values)
+        } else {
+            None
+        }
+    }
+
     pub fn utf8(&self) -> Option<&str> {
         if let DataValue::Utf8 { value, .. } = self {
             Some(value)
@@ -777,6 +843,9 @@ impl DataValue {
 
                 DataValue::Tuple(values, false)
             }
+            LogicalType::Interval => DataValue::Interval(Interval::default()),
+            LogicalType::Json => DataValue::Json(Json::Null),
+            LogicalType::Array(_) => DataValue::Array(Vec::new()),
         }
     }
 
@@ -808,13 +877,22 @@ impl DataValue {
             DataValue::Date32(_) => LogicalType::Date,
             DataValue::Date64(_) => LogicalType::DateTime,
             DataValue::Time32(..) => LogicalType::Time(None),
-            DataValue::Time64(..) => LogicalType::TimeStamp(None, false),
+            DataValue::Time64(_, _, zone) => LogicalType::TimeStamp(None, *zone),
             #[cfg(feature = "decimal")]
             DataValue::Decimal(_) => LogicalType::Decimal(None, None),
             DataValue::Tuple(values, ..) => {
                 let types = values.iter().map(|v| v.logical_type()).collect_vec();
                 LogicalType::Tuple(types)
             }
+            DataValue::Interval(_) => LogicalType::Interval,
+            DataValue::Json(_) => LogicalType::Json,
+            DataValue::Array(values) => LogicalType::Array(Box::new(
+                values
+                    .iter()
+                    .find(|value| !value.is_null())
+                    .map(DataValue::logical_type)
+                    .unwrap_or(LogicalType::SqlNull),
+            )),
         }
     }
 
@@ -972,11 +1050,124 @@ impl DataValue {
                     }
                 }
             }
+            // the total length orders the values, the parts follow so they can be decoded, which
+            // leaves equal intervals such as `1 mon` and `30 days` next to each other
+            DataValue::Interval(v) => {
+                encode_u!(b, v.total_nanos() as u128 ^ (1_u128 << 127));
+                encode_u!(b, v.months as u32 ^ 0x80000000_u32);
+                encode_u!(b, v.days as u32 ^ 0x80000000_u32);
+                encode_u!(b, v.nanos as u64 ^ 0x8000000000000000_u64);
+            }
+            DataValue::Json(v) => Self::encode_json(b, v),
+            // every element is preceded by a marker and the list ends with a terminator,
+            // so a prefix sorts before any longer array sharing it
+            DataValue::Array(values) => {
+                for value in values {
+                    b.push_byte(1);
+                    value.memcomparable_encode(b)?;
+                }
+                b.push_byte(0);
+            }
         }
 
         Ok(())
     }
 
+    // follows the order of `Json`: the type rank first, then numbers by value with integers
+    // after equal floats, arrays and objects by their length before their contents
+    fn encode_json<B: MemComparableBuffer>(b: &mut B, json: &Json) {
+        match json {
+            Json::Null => b.push_byte(0),
+            Json::String(v) => {
+                b.push_byte(1);
+                Self::encode_string(b, v.as_bytes());
+            }
+            Json::Int(_) | Json::Float(_) => {
+                b.push_byte(2);
+                let f = match json {
+                    Json::Int(v) => *v as f64,
+                    Json::Float(v) => v.0,
+                    _ => unreachable!(),
+                };
+                let mut u = f.to_bits();
+                if f >= 0_f64 {
+                    u |= 0x8000000000000000_u64;
+                } else {
+                    u = !u;
+                }
+                encode_u!(b, u);
+                if let Json::Int(v) = json {
+                    b.push_byte(1);
+                    encode_u!(b, *v as u64 ^ 0x8000000000000000_u64);
+                } else {
+                    b.push_byte(0);
+                }
+            }
+            Json::Bool(v) => {
+                b.push_byte(3);
+                b.push_byte(*v as u8);
+            }
+            Json::Array(elements) => {
+                b.push_byte(4);
+                encode_u!(b, elements.len() as u32);
+                for element in elements {
+                    Self::encode_json(b, element);
+                }
+            }
+            Json::Object(members) => {
+                b.push_byte(5);
+                encode_u!(b, members.len() as u32);
+                for (key, value) in members {
+                    Self::encode_string(b, key.as_bytes());
+                    Self::encode_json(b, value);
+                }
+            }
+        }
+    }
+
+    fn decode_json<R: Read>(reader: &mut R) -> Result<Json, DatabaseError> {
+        Ok(match reader.read_u8()? {
+            0 => Json::Null,
+            1 => Json::String(String::from_utf8(Self::decode_string(reader)?)?),
+            2 => {
+                let mut u = decode_u!(reader, u64);
+                if reader.read_u8()? == 1 {
+                    Json::Int((decode_u!(reader, u64) ^ 0x8000_0000_0000_0000) as i64)
+                } else {
+                    if (u & 0x8000_0000_0000_0000) != 0 {
+                        u &= !0x8000_0000_0000_0000;
+                    } else {
+                        u = !u;
+                    }
+                    Json::Float(f64::from_bits(u).into())
+                }
+            }
+            3 => Json::Bool(reader.read_u8()? == 1),
+            4 => {
+                let len = decode_u!(reader, u32);
+                Json::Array(
+                    (0..len)
+                        .map(|_| Self::decode_json(reader))
+                        .collect::<Result<_, _>>()?,
+                )
+            }
+            5 => {
+                let len = decode_u!(reader, u32);
+                let mut members = std::collections::BTreeMap::new();
+                for _ in 0..len {
+                    let key = String::from_utf8(Self::decode_string(reader)?)?;
+                    members.insert(key, Self::decode_json(reader)?);
+                }
+                Json::Object(members)
+            }
+            tag => {
+                return Err(DatabaseError::InvalidValue(format!(
+                    "invalid memcomparable json tag: {tag}"
+                )))
+            }
+        })
+    }
+
     #[inline]
     pub fn memcomparable_encode<B: MemComparableBuffer>(
         &self,
@@ -1097,6 +1288,22 @@ impl DataValue {
                 }
                 Ok(DataValue::Tuple(collector.finish(), false))
             }
+            LogicalType::Interval => {
+                let _total_nanos = decode_u!(reader, u128);
+                let months = (decode_u!(reader, u32) ^ 0x8000_0000) as i32;
+                let days = (decode_u!(reader, u32) ^ 0x8000_0000) as i32;
+                let nanos = (decode_u!(reader, u64) ^ 0x8000_0000_0000_0000) as i64;
+                Ok(DataValue::Interval(Interval::new(months, days, nanos)))
+            }
+            LogicalType::Json => Ok(DataValue::Json(Self::decode_json(reader)?)),
+            LogicalType::Array(element) => {
+                let mut values = Vec::new();
+
+                while reader.read_u8()? == 1 {
+                    values.push(Self::memcomparable_decode_mapping(reader, element, None)?);
+                }
+                Ok(DataValue::Array(values))
+            }
         }
     }
 
@@ -1395,6 +1602,15 @@ impl DataValue {
         }
     }
 
+    /// The instant of a `TIMESTAMP WITH TIME ZONE` value.
+    pub fn timestamp_tz(&self) -> Option<DateTime<Utc>> {
+        if let DataValue::Time64(val, precision, true) = self {
+            Self::from_timestamp_precision(*val, *precision)
+        } else {
+            None
+        }
+    }
+
     pub(crate) fn format_date(value: i32) -> Option<String> {
         Self::date_format(value).map(|fmt| format!("{fmt}"))
     }
@@ -1407,8 +1623,22 @@ impl DataValue {
         Self::time_format(value, precision).map(|fmt| format!("{fmt}"))
     }
 
-    pub(crate) fn format_timestamp(value: i64, precision: u64) -> Option<String> {
-        Self::time_stamp_format(value, precision, false).map(|fmt| format!("{fmt}"))
+    /// Formats a timestamp. One with a time zone is given the zone it is shown in and is
+    /// followed by its offset there, e.g. `2024-01-01 09:00:00+09`.
+    pub(crate) fn format_timestamp(
+        value: i64,
+        precision: u64,
+        time_zone: Option<TimeZone>,
+    ) -> Option<String> {
+        let Some(time_zone) = time_zone else {
+            return Self::time_stamp_format(value, precision).map(|fmt| format!("{fmt}"));
+        };
+        let utc = Self::from_timestamp_precision(value, precision)?.naive_utc();
+        Some(format!(
+            "{}{}",
+            time_zone.to_local(&utc).format(TIME_STAMP_FMT_WITHOUT_ZONE),
+            format_offset(time_zone.offset(&utc))
+        ))
     }
 
     fn date_format<'a>(v: i32) -> Option<DelayedFormat<StrftimeItems<'a>>> {
@@ -1425,11 +1655,7 @@ impl DataValue {
             .map(|time| time.format(TIME_FMT_WITHOUT_ZONE))
     }
 
-    fn time_stamp_format<'a>(
-        v: i64,
-        precision: u64,
-        _zone: bool,
-    ) -> Option<DelayedFormat<StrftimeItems<'a>>> {
+    fn time_stamp_format<'a>(v: i64, precision: u64) -> Option<DelayedFormat<StrftimeItems<'a>>> {
         Self::from_timestamp_precision(v, precision)
             .map(|date_time| date_time.format(TIME_STAMP_FMT_WITHOUT_ZONE))
     }
@@ -1583,6 +1809,24 @@ impl From<Option<&NaiveDateTime>> for DataValue {
     }
 }
 
+#[cfg(feature = "time")]
+impl<Tz: chrono::TimeZone> From<&DateTime<Tz>> for DataValue {
+    fn from(value: &DateTime<Tz>) -> Self {
+        DataValue::Time64(value.timestamp_micros(), 6, true)
+    }
+}
+
+#[cfg(feature = "time")]
+impl<Tz: chrono::TimeZone> From<Option<&DateTime<Tz>>> for DataValue {
+    fn from(value: Option<&DateTime<Tz>>) -> Self {
+        if let Some(value) = value {
+            DataValue::from(value)
+        } else {
+            DataValue::Null
+        }
+    }
+}
+
 #[cfg(feature = "time")]
 impl From<&NaiveTime> for DataValue {
     fn from(value: &NaiveTime) -> Self {
@@ -1620,9 +1864,51 @@ macro_rules! format_float_option {
     }};
 }
 
+/// Shows a [`DataValue`], see [`DataValue::display_in`].
+pub struct DataValueDisplay<'a> {
+    value: &'a DataValue,
+    #[cfg(feature = "time")]
+    time_zone: TimeZone,
+}
+
+#[cfg(feature = "time")]
+impl DataValue {
+    /// Shows the value with its timestamps with time zone in `time_zone`. `Display` shows
+    /// them in the session time zone of the statement running on the current thread, which
+    /// is UTC once a value is taken out of its result iterator, so pass
+    /// [`ResultIter::time_zone`](crate::db::ResultIter::time_zone) here instead.
+    pub fn display_in(&self, time_zone: TimeZone) -> DataValueDisplay<'_> {
+        DataValueDisplay {
+            value: self,
+            time_zone,
+        }
+    }
+}
+
 impl fmt::Display for DataValue {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
-        match self {
+        DataValueDisplay {
+            value: self,
+            #[cfg(feature = "time")]
+            time_zone: session_time_zone(),
+        }
+        .fmt(f)
+    }
+}
+
+impl DataValueDisplay<'_> {
+    fn nested<'b>(&self, value: &'b DataValue) -> DataValueDisplay<'b> {
+        DataValueDisplay {
+            value,
+            #[cfg(feature = "time")]
+            time_zone: self.time_zone,
+        }
+    }
+}
+
+impl fmt::Display for DataValueDisplay<'_> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self.value {
             DataValue::Boolean(e) => write!(f, "{e}")?,
             DataValue::Float32(e) => format_float_option!(f, e)?,
             DataValue::Float64(e) => format_float_option!(f, e)?,
@@ -1662,7 +1948,8 @@ impl fmt::Display for DataValue {
                 write!(
                     f,
                     "{}",
-                    DataValue::time_stamp_format(*e, *precision, *zone).unwrap()
+                    DataValue::format_timestamp(*e, *precision, zone.then_some(self.time_zone))
+                        .unwrap()
                 )?;
                 #[cfg(not(feature = "time"))]
                 {
@@ -1677,13 +1964,18 @@ impl fmt::Display for DataValue {
                 let len = values.len();
 
                 for (i, value) in values.iter().enumerate() {
-                    value.fmt(f)?;
+                    self.nested(value).fmt(f)?;
                     if len != i + 1 {
                         write!(f, ", ")?;
                     }
                 }
                 write!(f, ")")?;
             }
+            DataValue::Interval(e) => write!(f, "{e}")?,
+            DataValue::Json(e) => write!(f, "{e}")?,
+            DataValue::Array(values) => {
+                write_array(f, values, &|value| self.nested(value).to_string())?
+            }
         };
         Ok(())
     }
@@ -1718,6 +2010,9 @@ impl fmt::Debug for DataValue {
                 }
                 write!(f, ")")
             }
+            DataValue::Interval(_) => write!(f, "Interval({self})"),
+            DataValue::Json(_) => write!(f, "Json({self})"),
+            DataValue::Array(_) => write!(f, "Array({self})"),
         }
     }
 }
@@ -1727,6 +2022,7 @@ impl fmt::Debug for DataValue {
 mod test {
     use crate::errors::DatabaseError;
     use crate::storage::table_codec::{BumpBytes, NOTNULL_TAG, NULL_TAG};
+    use crate::types::interval::Interval;
     use crate::types::value::{DataValue, TupleMappingRef, Utf8Type};
     use crate::types::CharLengthUnits;
     use crate::types::LogicalType;
@@ -1932,7 +2228,7 @@ mod test {
         );
         assert_eq!(
             DataValue::init(&LogicalType::TimeStamp(None, true)).logical_type(),
-            LogicalType::TimeStamp(None, false)
+            LogicalType::TimeStamp(None, true)
         );
         assert_eq!(
             DataValue::init(&LogicalType::TimeStamp(Some(3), false)).logical_type(),
@@ -1976,7 +2272,7 @@ mod test {
             (DataValue::Time32(1, 2), LogicalType::Time(None)),
             (
                 DataValue::Time64(1, 2, true),
-                LogicalType::TimeStamp(None, false),
+                LogicalType::TimeStamp(None, true),
             ),
             #[cfg(feature = "decimal")]
             (
@@ -2018,6 +2314,10 @@ mod test {
                 DataValue::Decimal(Decimal::new(123, 2)),
                 LogicalType::Decimal(None, None),
             ),
+            (
+                DataValue::Interval(Interval::new(-1, 2, -3)),
+                LogicalType::Interval,
+            ),
         ];
         for (value, ty) in cases {
             assert_eq!(roundtrip_memcomparable(&value, &ty), value);
@@ -2774,6 +3074,39 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn test_mem_comparable_interval() -> Result<(), DatabaseError> {
+        let values = [
+            DataValue::Interval(Interval::new(-1, 0, 0)),
+            DataValue::Interval(Interval::new(0, -1, 0)),
+            DataValue::Interval(Interval::new(0, 0, -1)),
+            DataValue::Interval(Interval::default()),
+            DataValue::Interval(Interval::new(0, 30, 0)),
+            DataValue::Interval(Interval::new(1, 0, 0)),
+            DataValue::Interval(Interval::new(1, 0, 1)),
+            DataValue::Null,
+        ];
+        let keys = values
+            .iter()
+            .map(|value| {
+                let mut key = Vec::new();
+                value.memcomparable_encode(&mut key).map(|_| key)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        for window in keys.windows(2) {
+            assert!(window[0] < window[1]);
+        }
+        for (value, key) in values.iter().zip(keys) {
+            assert_eq!(
+                value,
+                &DataValue::memcomparable_decode(&mut Cursor::new(key), &LogicalType::Interval)?
+            );
+        }
+
+        Ok(())
+    }
+
     #[test]
     fn test_mem_comparable_float() -> Result<(), DatabaseError> {
         let arena = Bump::new();
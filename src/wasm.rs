@@ -16,6 +16,8 @@
 
 use crate::db::{DataBaseBuilder, Database, DatabaseIter};
 use crate::storage::memory::MemoryStorage;
+#[cfg(feature = "time")]
+use crate::types::timezone::session_time_zone;
 use crate::types::tuple::Tuple;
 use crate::types::value::{DataValue, Utf8Type};
 use crate::types::CharLengthUnits;
@@ -65,8 +67,33 @@ fn data_value_to_js(value: &DataValue) -> Result<JsValue, JsValue> {
             set_prop(&object, "value", JsValue::from_f64(*value as f64))?;
             set_prop(&object, "precision", JsValue::from_f64(*precision as f64))?;
             set_prop(&object, "withTimezone", JsValue::from_bool(*with_tz))?;
+            // `value` is UTC, the offset is the session time zone's at that instant
+            #[cfg(feature = "time")]
+            if let Some(utc) = DataValue::Time64(*value, *precision, *with_tz).timestamp_tz() {
+                let time_zone = session_time_zone();
+                let offset = time_zone.offset(&utc.naive_utc());
+                set_prop(
+                    &object,
+                    "timeZone",
+                    JsValue::from_str(&time_zone.to_string()),
+                )?;
+                set_prop(
+                    &object,
+                    "offsetSeconds",
+                    JsValue::from_f64(offset.local_minus_utc() as f64),
+                )?;
+            }
+            Ok(object.into())
+        }
+        DataValue::Interval(interval) => {
+            let object = Object::new();
+            set_prop(&object, "months", JsValue::from_f64(interval.months as f64))?;
+            set_prop(&object, "days", JsValue::from_f64(interval.days as f64))?;
+            set_prop(&object, "nanos", JsValue::from_f64(interval.nanos as f64))?;
             Ok(object.into())
         }
+        DataValue::Json(json) => js_sys::JSON::parse(&json.to_string()),
+        DataValue::Array(values) => data_values_to_js(values),
         #[cfg(feature = "decimal")]
         DataValue::Decimal(value) => Ok(JsValue::from_str(&value.to_string())),
         DataValue::Tuple(values, is_upper) => {
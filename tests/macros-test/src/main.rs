@@ -19,19 +19,22 @@ mod test {
     use kite_sql::catalog::column::{ColumnCatalog, ColumnDesc};
     use kite_sql::db::{DataBaseBuilder, Database, ResultIter};
     use kite_sql::errors::DatabaseError;
+    use kite_sql::expression::function::aggregate::AggregateFunctionImpl;
     use kite_sql::expression::function::scala::ScalarFunctionImpl;
     use kite_sql::expression::function::table::TableFunctionImpl;
     use kite_sql::expression::function::FunctionSummary;
     use kite_sql::expression::BinaryOperator;
     use kite_sql::expression::ScalarExpression;
-    use kite_sql::orm::{AggKind, OrmQueryResultExt, WindowSpec};
+    use kite_sql::orm::{AggKind, OrmQueryResultExt, WindowFrameBound, WindowSpec};
     use kite_sql::planner::{MetaArena, PlanArena, TableArena, TableArenaCell};
     use kite_sql::storage::rocksdb::RocksStorage;
     use kite_sql::types::evaluator::binary_create;
     use kite_sql::types::tuple::{Schema, SchemaView, Tuple};
     use kite_sql::types::value::{DataValue, Utf8Type};
     use kite_sql::types::{CharLengthUnits, LogicalType};
-    use kite_sql::{from_tuple, scala_function, table_function, Model, Projection};
+    use kite_sql::{
+        aggregate_function, from_tuple, scala_function, table_function, Model, Projection,
+    };
     use rust_decimal::Decimal;
     use tempfile::TempDir;
 
@@ -206,6 +209,35 @@ mod test {
         code: String,
     }
 
+    #[derive(Default, Debug, PartialEq, Model)]
+    #[model(table = "price_ranges")]
+    #[model(check = "low <= high")]
+    struct PriceRange {
+        #[model(primary_key)]
+        id: i32,
+        #[model(check = "low >= 0")]
+        low: i32,
+        high: i32,
+    }
+
+    #[derive(Default, Debug, PartialEq, Model)]
+    #[model(table = "user_notes")]
+    struct UserNote {
+        #[model(primary_key)]
+        id: i32,
+        #[model(references = "users(id)")]
+        user_id: i32,
+    }
+
+    #[derive(Default, Debug, PartialEq, Model)]
+    #[model(table = "articles")]
+    struct Article {
+        #[model(primary_key)]
+        id: i32,
+        tags: Vec<String>,
+        scores: Option<Vec<Option<i32>>>,
+    }
+
     #[derive(Default, Debug, PartialEq, Model)]
     #[model(table = "user_name_snapshots")]
     struct UserNameSnapshot {
@@ -224,6 +256,14 @@ mod test {
         score: i32,
     }
 
+    #[derive(Default, Debug, PartialEq, Model)]
+    #[model(table = "tickets")]
+    struct Ticket {
+        #[model(primary_key, auto_increment)]
+        id: i64,
+        title: String,
+    }
+
     #[derive(Default, Debug, PartialEq, Model)]
     #[model(table = "orders")]
     struct Order {
@@ -523,6 +563,164 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn test_model_check_ddl() -> Result<(), DatabaseError> {
+        let (_temp_dir, mut database) = build_test_database()?;
+
+        create_model_table::<PriceRange>(&mut database)?;
+        database.insert(&PriceRange {
+            id: 1,
+            low: 1,
+            high: 2,
+        })?;
+
+        assert!(matches!(
+            database.insert(&PriceRange {
+                id: 2,
+                low: -1,
+                high: 2,
+            }),
+            Err(DatabaseError::CheckViolation { constraint }) if constraint == "price_ranges_low_check"
+        ));
+        assert!(matches!(
+            database.insert(&PriceRange {
+                id: 3,
+                low: 3,
+                high: 2,
+            }),
+            Err(DatabaseError::CheckViolation { constraint }) if constraint == "price_ranges_check"
+        ));
+        assert_eq!(
+            database.get::<PriceRange>(&1)?,
+            Some(PriceRange {
+                id: 1,
+                low: 1,
+                high: 2,
+            })
+        );
+
+        database.drop_table::<PriceRange>()?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_model_foreign_key_ddl() -> Result<(), DatabaseError> {
+        let (_temp_dir, mut database) = build_test_database()?;
+
+        create_model_table::<User>(&mut database)?;
+        create_model_table::<UserNote>(&mut database)?;
+        database.insert(&User {
+            id: 1,
+            name: "Alice".to_string(),
+            age: Some(18),
+            cache: "".to_string(),
+        })?;
+        database.insert(&UserNote { id: 1, user_id: 1 })?;
+
+        assert!(matches!(
+            database.insert(&UserNote { id: 2, user_id: 2 }),
+            Err(DatabaseError::ForeignKeyViolation { constraint }) if constraint == "user_notes_user_id_fkey"
+        ));
+        assert!(matches!(
+            database.drop_table::<User>(),
+            Err(DatabaseError::TableReferenced { .. })
+        ));
+        assert_eq!(
+            database.get::<UserNote>(&1)?,
+            Some(UserNote { id: 1, user_id: 1 })
+        );
+
+        database.drop_table::<UserNote>()?;
+        database.drop_table::<User>()?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_model_array_fields() -> Result<(), DatabaseError> {
+        let (_temp_dir, mut database) = build_test_database()?;
+
+        create_model_table::<Article>(&mut database)?;
+        let article = Article {
+            id: 1,
+            tags: vec!["db".to_string(), "sql".to_string()],
+            scores: Some(vec![Some(3), None]),
+        };
+        database.insert(&article)?;
+        database.insert(&Article {
+            id: 2,
+            tags: Vec::new(),
+            scores: None,
+        })?;
+        assert_eq!(database.get::<Article>(&1)?, Some(article));
+
+        let rows = collect_result_tuples(
+            database.run("select id from articles where 'sql' = any(tags)")?,
+        )?;
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].values[0], DataValue::Int32(1));
+
+        database.drop_table::<Article>()?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_model_auto_increment() -> Result<(), DatabaseError> {
+        let (_temp_dir, mut database) = build_test_database()?;
+
+        create_model_table::<Ticket>(&mut database)?;
+        let first = database.insert(&Ticket {
+            id: 0,
+            title: "first".to_string(),
+        })?;
+        let second = database.insert(&Ticket {
+            id: 0,
+            title: "second".to_string(),
+        })?;
+        assert_eq!((first, second), (1, 2));
+        assert_eq!(
+            database.insert(&Ticket {
+                id: 10,
+                title: "explicit".to_string(),
+            })?,
+            10
+        );
+        assert_eq!(
+            database.get::<Ticket>(&2)?,
+            Some(Ticket {
+                id: 2,
+                title: "second".to_string(),
+            })
+        );
+
+        let mut transaction = database.new_transaction()?;
+        let third = transaction.insert(&Ticket {
+            id: 0,
+            title: "third".to_string(),
+        })?;
+        transaction.commit()?;
+        assert_eq!(third, 3);
+
+        // the model matches the catalog, so migrating is a no-op
+        migrate_model::<Ticket>(&mut database)?;
+        assert_eq!(database.fetch::<Ticket>()?.count(), 4);
+
+        database.drop_table::<Ticket>()?;
+        create_model_table::<Ticket>(&mut database)?;
+        assert_eq!(
+            database.insert(&Ticket {
+                id: 0,
+                title: "restarted".to_string(),
+            })?,
+            1
+        );
+        database.drop_table::<Ticket>()?;
+
+        Ok(())
+    }
+
     #[test]
     fn test_model_migrate() -> Result<(), DatabaseError> {
         let (_temp_dir, mut database) = build_test_database()?;
@@ -1211,20 +1409,26 @@ mod test {
             .collect::<Result<Vec<_>, _>>()?;
         assert_eq!(projected_scalar_subquery, vec![3, 3, 3]);
 
-        assert!(database
+        let correlated_scalar_subquery = database
             .bind(|ctx| {
                 ctx.from::<User>()?
+                    .order_by(User::id())?
                     .project_value(|e| {
                         e.scalar_subquery(|ctx| {
                             ctx.from::<Order>()?
                                 .filter(|e| e.column(Order::user_id())?.eq(e.column(User::id())?))?
-                                .project_scalar(Order::amount())?
+                                .project_value(|e| {
+                                    let amount = e.column(Order::amount())?;
+                                    e.aggregate(AggKind::Max, vec![amount])
+                                })?
                                 .finish()
                         })
                     })?
                     .finish()
-            })
-            .is_err());
+            })?
+            .project_value::<Option<i32>>()
+            .collect::<Result<Vec<_>, _>>()?;
+        assert_eq!(correlated_scalar_subquery, vec![Some(200), Some(300), None]);
 
         assert_eq!(
             database
@@ -1823,33 +2027,40 @@ mod test {
             })?
             .done()?;
 
-        assert!(database
-            .bind(|ctx| {
-                ctx.from::<User>()?
-                    .filter(|e| {
-                        e.exists_subquery(false, |ctx| {
-                            ctx.union(
-                                false,
-                                |ctx| {
-                                    ctx.from::<Order>()?
-                                        .filter(|e| {
-                                            e.column(Order::user_id())?.eq(e.column(User::id())?)
-                                        })?
-                                        .project_scalar(Order::id())?
-                                        .finish()
-                                },
-                                |ctx| {
-                                    ctx.from::<Order>()?
-                                        .filter(|e| e.column(Order::amount())?.eq(300))?
-                                        .project_scalar(Order::id())?
-                                        .finish()
-                                },
-                            )
-                        })
-                    })?
-                    .count()
-            })
-            .is_err());
+        assert_eq!(
+            database
+                .bind(|ctx| {
+                    ctx.from::<User>()?
+                        .filter(|e| {
+                            e.exists_subquery(false, |ctx| {
+                                ctx.union(
+                                    false,
+                                    |ctx| {
+                                        ctx.from::<Order>()?
+                                            .filter(|e| {
+                                                e.column(Order::user_id())?
+                                                    .eq(e.column(User::id())?)
+                                            })?
+                                            .project_scalar(Order::id())?
+                                            .finish()
+                                    },
+                                    |ctx| {
+                                        ctx.from::<Order>()?
+                                            .filter(|e| e.column(Order::amount())?.eq(300))?
+                                            .project_scalar(Order::id())?
+                                            .finish()
+                                    },
+                                )
+                            })
+                        })?
+                        .count()
+                })?
+                .project_value::<i32>()
+                .next()
+                .transpose()?
+                .unwrap() as usize,
+            3
+        );
 
         let max_id_user = database
             .bind(|ctx| {
@@ -2152,6 +2363,44 @@ mod test {
         windowed_min_max.sort_by_key(|row| row.0);
         assert_eq!(windowed_min_max, vec![(1, 10, 20), (2, 10, 20), (3, 5, 5)]);
 
+        let mut windowed_offsets = database
+            .bind(|ctx| {
+                ctx.from::<EventLog>()?
+                    .project_tuple(|e| {
+                        let id = e.column(EventLog::id())?;
+                        let category = e.column(EventLog::category())?;
+                        let score = e.column(EventLog::score())?;
+                        let ordered = WindowSpec::new()
+                            .partition_by(category)
+                            .order_by(score.clone().asc());
+                        let trailing = ordered.clone().rows_between(
+                            WindowFrameBound::Preceding(1.into()),
+                            WindowFrameBound::CurrentRow,
+                        );
+                        Ok(vec![
+                            id,
+                            e.lag(score.clone(), 1, Some(0), ordered.clone())?,
+                            e.lead(score.clone(), 1, None::<i32>, ordered.clone())?,
+                            e.first_value(score.clone(), ordered.clone())?,
+                            e.ntile(2, ordered.clone())?,
+                            e.cume_dist(ordered)?,
+                            e.sum_over(score, trailing)?,
+                        ])
+                    })?
+                    .finish()
+            })?
+            .project_tuple::<(i32, i32, Option<i32>, i32, i64, f64, i32)>()
+            .collect::<Result<Vec<_>, _>>()?;
+        windowed_offsets.sort_by_key(|row| row.0);
+        assert_eq!(
+            windowed_offsets,
+            vec![
+                (1, 0, Some(20), 10, 1, 0.5, 10),
+                (2, 10, None, 10, 2, 1.0, 30),
+                (3, 0, None, 5, 1, 1.0, 5),
+            ]
+        );
+
         let mut grouped_categories = database
             .bind(|ctx| {
                 ctx.from::<EventLog>()?
@@ -2771,6 +3020,170 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn test_orm_insert_on_conflict_builder() -> Result<(), DatabaseError> {
+        let (_temp_dir, mut database) = build_test_database()?;
+        create_model_table::<User>(&mut database)?;
+
+        let alice = User {
+            id: 1,
+            name: "Alice".to_string(),
+            age: Some(18),
+            cache: String::new(),
+        };
+        database
+            .bind(|ctx| ctx.insert::<User>().values(&alice).finish())?
+            .done()?;
+
+        let renamed = User {
+            id: 1,
+            name: "Alicia".to_string(),
+            age: Some(30),
+            cache: String::new(),
+        };
+        database
+            .bind(|ctx| {
+                ctx.insert::<User>()
+                    .values(&renamed)
+                    .on_conflict([User::id().column_name()])?
+                    .do_nothing()
+            })?
+            .done()?;
+        assert_eq!(database.get::<User>(&1)?.unwrap().name, "Alice");
+
+        let bob = User {
+            id: 2,
+            name: "Bob".to_string(),
+            age: Some(20),
+            cache: String::new(),
+        };
+        database
+            .bind(|ctx| {
+                ctx.insert::<User>()
+                    .values_many([&renamed, &bob])
+                    .on_conflict([User::id().column_name()])?
+                    .filter(|e| e.column(User::age())?.lt(25))?
+                    .do_update(|u| {
+                        u.set_excluded(User::name())?;
+                        u.set_expr(User::age(), |e| {
+                            let age = e.qualified_column("excluded", User::age())?;
+                            e.binary(age, BinaryOperator::Plus, e.value(1))
+                        })
+                    })
+            })?
+            .done()?;
+        let updated = database.get::<User>(&1)?.unwrap();
+        assert_eq!(updated.name, "Alicia");
+        assert_eq!(updated.age, Some(31));
+        assert_eq!(database.get::<User>(&2)?.unwrap().name, "Bob");
+
+        let missing_target = database.bind(|ctx| {
+            ctx.insert::<User>()
+                .values(&bob)
+                .on_conflict([User::age().column_name()])?
+                .do_nothing()
+        });
+        assert!(matches!(
+            missing_target,
+            Err(DatabaseError::ConflictTargetNotFound(_))
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_orm_mutation_returning() -> Result<(), DatabaseError> {
+        let (_temp_dir, mut database) = build_test_database()?;
+        create_model_table::<User>(&mut database)?;
+
+        let alice = User {
+            id: 1,
+            name: "Alice".to_string(),
+            age: Some(18),
+            cache: String::new(),
+        };
+        let bob = User {
+            id: 2,
+            name: "Bob".to_string(),
+            age: None,
+            cache: String::new(),
+        };
+        let mut inserted_iter = database
+            .bind(|ctx| {
+                ctx.insert::<User>()
+                    .values_many([&alice, &bob])
+                    .returning::<UserSummary>()?
+                    .finish()
+            })?
+            .orm::<UserSummary>();
+        let inserted = inserted_iter.by_ref().collect::<Result<Vec<_>, _>>()?;
+        inserted_iter.done()?;
+        assert_eq!(
+            inserted,
+            vec![
+                UserSummary {
+                    id: 1,
+                    display_name: "Alice".to_string(),
+                    age: Some(18),
+                },
+                UserSummary {
+                    id: 2,
+                    display_name: "Bob".to_string(),
+                    age: None,
+                },
+            ]
+        );
+
+        let updated = database.bind(|ctx| {
+            ctx.mutate::<User>()?
+                .filter(|e| e.column(User::id())?.eq(1))?
+                .returning::<UserSummary>()?
+                .update(|u| u.set_value(User::age(), 19))
+        })?;
+        assert_eq!(
+            updated.schema(|schema| {
+                schema
+                    .iter()
+                    .map(|column| column.name().to_string())
+                    .collect::<Vec<_>>()
+            }),
+            vec!["id", "display_name", "age"]
+        );
+        let mut updated = updated.orm::<UserSummary>();
+        assert_eq!(
+            updated.by_ref().collect::<Result<Vec<_>, _>>()?,
+            vec![UserSummary {
+                id: 1,
+                display_name: "Alice".to_string(),
+                age: Some(19),
+            }]
+        );
+        updated.done()?;
+        assert_eq!(database.get::<User>(&1)?.unwrap().age, Some(19));
+
+        let mut deleted_iter = database
+            .bind(|ctx| {
+                ctx.mutate_as::<User>("u")?
+                    .filter(|e| e.qualified_column("u", User::id())?.eq(2))?
+                    .returning::<UserSummary>()?
+                    .delete()
+            })?
+            .orm::<UserSummary>();
+        let deleted = deleted_iter.by_ref().collect::<Result<Vec<_>, _>>()?;
+        deleted_iter.done()?;
+        assert_eq!(
+            deleted,
+            vec![UserSummary {
+                id: 2,
+                display_name: "Bob".to_string(),
+                age: None,
+            }]
+        );
+        assert!(database.get::<User>(&2)?.is_none());
+
+        Ok(())
+    }
+
     #[test]
     fn test_orm_extended_write_and_ddl_helpers() -> Result<(), DatabaseError> {
         let (_temp_dir, mut database) = build_test_database()?;
@@ -3044,6 +3457,57 @@ mod test {
                 ])))) as Box<dyn Iterator<Item = Result<Tuple, DatabaseError>>>)
     }));
 
+    table_function!(MyTagsFunction::SPLIT_TAGS(LogicalType::Varchar(None, CharLengthUnits::Characters)) -> [tag: LogicalType::Varchar(None, CharLengthUnits::Characters)] => (|v1: DataValue| {
+        let tags = v1
+            .utf8()
+            .map(|tags| {
+                tags.split(',')
+                    .map(|tag| Ok(Tuple::new(None, vec![DataValue::Utf8 {
+                        value: tag.trim().to_string(),
+                        ty: Utf8Type::Variable(None),
+                        unit: CharLengthUnits::Characters,
+                    }])))
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+
+        Ok(Box::new(tags.into_iter()) as Box<dyn Iterator<Item = Result<Tuple, DatabaseError>>>)
+    }));
+
+    aggregate_function!(MyAggregateFunction::WEIGHTED_AVG(LogicalType::Double, LogicalType::Double) -> LogicalType::Double => {
+        init: || Ok(DataValue::Tuple(vec![DataValue::Float64(0.0.into()), DataValue::Float64(0.0.into())], false)),
+        update: |state: &mut DataValue, value: DataValue, weight: DataValue| {
+            let DataValue::Tuple(sums, _) = state else {
+                unreachable!()
+            };
+            if let (Some(value), Some(weight)) = (value.double(), weight.double()) {
+                sums[0] = DataValue::Float64((sums[0].double().unwrap() + value * weight).into());
+                sums[1] = DataValue::Float64((sums[1].double().unwrap() + weight).into());
+            }
+            Ok(())
+        },
+        merge: |state: &mut DataValue, other: DataValue| {
+            let (DataValue::Tuple(sums, _), DataValue::Tuple(others, _)) = (state, other) else {
+                unreachable!()
+            };
+            for (sum, other) in sums.iter_mut().zip(others) {
+                *sum = DataValue::Float64((sum.double().unwrap() + other.double().unwrap()).into());
+            }
+            Ok(())
+        },
+        finalize: |state: &DataValue| {
+            let DataValue::Tuple(sums, _) = state else {
+                unreachable!()
+            };
+            let weight = sums[1].double().unwrap();
+            Ok(if weight == 0.0 {
+                DataValue::Null
+            } else {
+                DataValue::Float64((sums[0].double().unwrap() / weight).into())
+            })
+        },
+    });
+
     #[test]
     fn test_scala_function() -> Result<(), DatabaseError> {
         let function = MyScalaFunction::new();
@@ -3113,4 +3577,141 @@ mod test {
 
         Ok(())
     }
+
+    #[test]
+    fn test_lateral_table_function() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("create temp dir for lateral table function test");
+        let mut database = DataBaseBuilder::path(temp_dir.path()).build_rocksdb()?;
+        database.load(kite_sql::db::CatalogKind::TableFunction(
+            MyTagsFunction::new(),
+        ))?;
+        database.ddl("create table posts (id int primary key, tags varchar)")?;
+        drain_result_iter(
+            database.run("insert into posts values (1, 'rust, sql'), (2, 'db'), (3, null)")?,
+        )?;
+        let tag = |value: &str| DataValue::Utf8 {
+            value: value.to_string(),
+            ty: Utf8Type::Variable(None),
+            unit: CharLengthUnits::Characters,
+        };
+
+        let rows = collect_result_tuples(database.run(
+            "select id, tag from posts cross join lateral split_tags(posts.tags) order by id, tag",
+        )?)?;
+        assert_eq!(
+            rows.into_iter().map(|row| row.values).collect::<Vec<_>>(),
+            vec![
+                vec![DataValue::Int32(1), tag("rust")],
+                vec![DataValue::Int32(1), tag("sql")],
+                vec![DataValue::Int32(2), tag("db")],
+            ]
+        );
+
+        let rows = collect_result_tuples(database.run(
+            "select id, t.tag from posts left join lateral split_tags(tags) t on true where id > 1 order by id",
+        )?)?;
+        assert_eq!(
+            rows.into_iter().map(|row| row.values).collect::<Vec<_>>(),
+            vec![
+                vec![DataValue::Int32(2), tag("db")],
+                vec![DataValue::Int32(3), DataValue::Null],
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_aggregate_function() -> Result<(), DatabaseError> {
+        let function = MyAggregateFunction::new();
+        assert_eq!(
+            function.summary,
+            FunctionSummary {
+                name: "weighted_avg".to_string().into(),
+                arg_types: vec![LogicalType::Double, LogicalType::Double],
+            }
+        );
+
+        let mut left = function.init()?;
+        function.update(&mut left, &[DataValue::Int32(1), DataValue::Int32(1)])?;
+        let mut right = function.init()?;
+        function.update(
+            &mut right,
+            &[
+                DataValue::Float64(4.0.into()),
+                DataValue::Float64(2.0.into()),
+            ],
+        )?;
+        function.update(
+            &mut right,
+            &[DataValue::Null, DataValue::Float64(5.0.into())],
+        )?;
+        function.merge(&mut left, right)?;
+        assert_eq!(function.finalize(&left)?, DataValue::Float64(3.0.into()));
+        assert_eq!(function.finalize(&function.init()?)?, DataValue::Null);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_aggregate_function_in_queries() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("create temp dir for aggregate function test");
+        let mut database = DataBaseBuilder::path(temp_dir.path())
+            .register_aggregate_function(MyAggregateFunction::new())
+            .build_rocksdb()?;
+        database
+            .ddl("create table prices (id int primary key, item int, price double, qty double)")?;
+        drain_result_iter(database.run(
+            "insert into prices values (1, 1, 1.0, 1.0), (2, 1, 4.0, 2.0), (3, 2, 2.0, 3.0), (4, 2, null, 1.0)",
+        )?)?;
+
+        let rows = collect_result_tuples(database.run(
+            "select item, weighted_avg(price, qty) from prices group by item order by item",
+        )?)?;
+        assert_eq!(
+            rows.iter()
+                .map(|row| row.values.clone())
+                .collect::<Vec<_>>(),
+            vec![
+                vec![DataValue::Int32(1), DataValue::Float64(3.0.into())],
+                vec![DataValue::Int32(2), DataValue::Float64(2.0.into())],
+            ]
+        );
+
+        let rows = collect_result_tuples(
+            database.run("select weighted_avg(price, qty), count(*) from prices")?,
+        )?;
+        assert_eq!(
+            rows[0].values,
+            vec![DataValue::Float64(2.5.into()), DataValue::Int32(4)]
+        );
+
+        let rows = collect_result_tuples(database.run(
+            "select id, weighted_avg(price, qty) over (partition by item order by id) from prices order by id",
+        )?)?;
+        assert_eq!(
+            rows.iter()
+                .map(|row| row.values[1].clone())
+                .collect::<Vec<_>>(),
+            vec![
+                DataValue::Float64(1.0.into()),
+                DataValue::Float64(3.0.into()),
+                DataValue::Float64(2.0.into()),
+                DataValue::Float64(2.0.into()),
+            ]
+        );
+
+        // user-defined aggregates are resolved by their argument types like scalar functions
+        assert!(matches!(
+            database.run("select weighted_avg(id, qty) from prices"),
+            Err(DatabaseError::FunctionNotFound { .. })
+        ));
+        // DISTINCT keeps one of each distinct (price, qty) argument row
+        let rows = collect_result_tuples(
+            database.run("select weighted_avg(distinct price, qty) from prices")?,
+        )?;
+        assert_eq!(rows[0].values, vec![DataValue::Float64(2.5.into())]);
+
+        Ok(())
+    }
 }
@@ -233,6 +233,9 @@ fn convert_value(value: &DataValue) -> Result<Value, TpccError> {
         ),
         DataValue::Time32(_, _) => Value::Null,
         DataValue::Time64(value, precision, _) => Value::String(format_time64(*value, *precision)?),
+        DataValue::Interval(v) => Value::String(v.to_string()),
+        DataValue::Json(v) => Value::String(v.to_string()),
+        DataValue::Array(_) => Value::String(value.to_string()),
         DataValue::Decimal(v) => Value::String(v.to_string()),
         DataValue::Tuple(_, _) => Value::Null,
     })